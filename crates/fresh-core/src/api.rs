@@ -244,6 +244,16 @@ pub enum PluginResponse {
         request_id: u64,
         spans: Vec<TsHighlightSpan>,
     },
+    /// Response to GetDocumentSymbols
+    DocumentSymbols {
+        request_id: u64,
+        symbols: Vec<DocumentSymbolInfo>,
+    },
+    /// Response to PrepareHierarchy and GetHierarchyChildren
+    HierarchyItems {
+        request_id: u64,
+        items: Vec<HierarchyItemInfo>,
+    },
     /// Response to GetBufferText with the text content
     BufferText {
         request_id: u64,
@@ -295,6 +305,8 @@ impl PluginResponse {
             | Self::TerminalCreated { request_id, .. }
             | Self::LspRequest { request_id, .. }
             | Self::HighlightsComputed { request_id, .. }
+            | Self::DocumentSymbols { request_id, .. }
+            | Self::HierarchyItems { request_id, .. }
             | Self::BufferText { request_id, .. }
             | Self::LineStartPosition { request_id, .. }
             | Self::LineEndPosition { request_id, .. }
@@ -2459,6 +2471,32 @@ pub enum PluginCommand {
         request_id: u64,
     },
 
+    /// Request the symbol outline of a buffer
+    GetDocumentSymbols {
+        buffer_id: BufferId,
+        request_id: u64,
+    },
+
+    /// Request the call or type hierarchy items at a byte offset.
+    /// `kind` is "call" or "type".
+    PrepareHierarchy {
+        buffer_id: BufferId,
+        position: usize,
+        kind: String,
+        request_id: u64,
+    },
+
+    /// Request the neighbours of a hierarchy item from the server that
+    /// produced it. `direction` is "incoming", "outgoing", "supertypes"
+    /// or "subtypes".
+    GetHierarchyChildren {
+        server: String,
+        direction: String,
+        #[ts(type = "any")]
+        item: JsonValue,
+        request_id: u64,
+    },
+
     /// Close a split (if not the last one)
     CloseSplit { split_id: SplitId },
 
@@ -2509,6 +2547,15 @@ pub enum PluginCommand {
         request_id: u64,
     },
 
+    /// Send a request to the running debug adapter and return the raw JSON
+    /// response body
+    SendDebugRequest {
+        command: String,
+        #[ts(type = "any")]
+        arguments: Option<JsonValue>,
+        request_id: u64,
+    },
+
     /// Set the internal clipboard content
     SetClipboard { text: String },
 
@@ -2950,6 +2997,24 @@ pub enum PluginCommand {
     /// `DEVCONTAINER_SPEC_GAP_PLAN.md`).
     KillHostProcess { process_id: u64 },
 
+    /// Ask the user whether a plugin package may use a capability it
+    /// declared in its manifest. Sent once, the first time the plugin
+    /// tries to use it; `detail` describes that first use (e.g.
+    /// "run `git`"). The answer goes back through the shared
+    /// `PermissionRegistry` and is persisted in the plugin's config.
+    RequestPluginPermission {
+        plugin_name: String,
+        capability: crate::permissions::Capability,
+        detail: String,
+    },
+
+    /// Reject a pending callback, e.g. when an async operation was
+    /// refused by the permission check before being started.
+    RejectCallback {
+        callback_id: JsCallbackId,
+        error: String,
+    },
+
     /// Mount a declarative widget panel inside an existing virtual
     /// buffer. The host renders the `WidgetSpec` and writes the
     /// resulting text-property entries into the buffer. The
@@ -3164,6 +3229,70 @@ pub struct TsHighlightSpan {
     pub italic: bool,
 }
 
+/// Entry of a buffer's symbol outline, in document order
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct DocumentSymbolInfo {
+    /// Symbol name
+    pub name: String,
+    /// Extra information from the language server (e.g., a signature)
+    #[ts(optional)]
+    pub detail: Option<String>,
+    /// Symbol kind (e.g., "function", "class", "method")
+    pub kind: String,
+    /// Byte offset where the definition starts
+    pub start: u32,
+    /// Byte offset where the definition ends
+    pub end: u32,
+    /// Byte offset of the symbol's name (where navigation should land)
+    pub name_offset: u32,
+    /// Zero-indexed line of the symbol's name
+    pub line: u32,
+    /// Nesting depth (0 for top-level symbols)
+    pub depth: u32,
+}
+
+/// Node of a call or type hierarchy
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct HierarchyItemInfo {
+    /// Symbol name
+    pub name: String,
+    /// Extra information from the language server (e.g., a signature)
+    #[ts(optional)]
+    pub detail: Option<String>,
+    /// Symbol kind (e.g., "function", "method", "class")
+    pub kind: String,
+    /// Path of the file defining the symbol
+    pub file: String,
+    /// One-indexed line of the symbol's name
+    pub line: u32,
+    /// One-indexed column of the symbol's name
+    pub column: u32,
+    /// Where the calls happen, for incoming and outgoing calls
+    pub call_sites: Vec<HierarchyCallSite>,
+    /// Language server that produced the item
+    pub server: String,
+    /// The item as sent by the language server, passed back when
+    /// expanding this node
+    #[ts(type = "any")]
+    pub item: JsonValue,
+}
+
+/// Location of a call in a call hierarchy
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HierarchyCallSite {
+    /// Path of the file containing the call
+    pub file: String,
+    /// One-indexed line of the call
+    pub line: u32,
+    /// One-indexed column of the call
+    pub column: u32,
+}
+
 /// Result from spawning a process with spawnProcess
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
@@ -4185,6 +4314,46 @@ impl PluginApi {
         })
     }
 
+    /// Request the document outline of a buffer
+    pub fn get_document_symbols(&self, buffer_id: BufferId, request_id: u64) -> Result<(), String> {
+        self.send_command(PluginCommand::GetDocumentSymbols {
+            buffer_id,
+            request_id,
+        })
+    }
+
+    /// Request the call or type hierarchy items at a byte offset
+    pub fn prepare_hierarchy(
+        &self,
+        buffer_id: BufferId,
+        position: usize,
+        kind: String,
+        request_id: u64,
+    ) -> Result<(), String> {
+        self.send_command(PluginCommand::PrepareHierarchy {
+            buffer_id,
+            position,
+            kind,
+            request_id,
+        })
+    }
+
+    /// Request the neighbours of a hierarchy item
+    pub fn get_hierarchy_children(
+        &self,
+        server: String,
+        direction: String,
+        item: JsonValue,
+        request_id: u64,
+    ) -> Result<(), String> {
+        self.send_command(PluginCommand::GetHierarchyChildren {
+            server,
+            direction,
+            item,
+            request_id,
+        })
+    }
+
     // === Query Methods ===
 
     /// Get the currently active buffer ID
@@ -5243,6 +5412,35 @@ mod tests {
             PluginCommand::RequestHighlights { buffer_id, range, request_id }
                 if buffer_id == BufferId(1) && range == (0..10) && request_id == 7
         );
+
+        // get_document_symbols
+        assert_dispatches!(
+            |a: &PluginApi| a.get_document_symbols(BufferId(3), 9),
+            PluginCommand::GetDocumentSymbols { buffer_id, request_id }
+                if buffer_id == BufferId(3) && request_id == 9
+        );
+
+        // prepare_hierarchy
+        assert_dispatches!(
+            |a: &PluginApi| a.prepare_hierarchy(BufferId(3), 42, "call".into(), 10),
+            PluginCommand::PrepareHierarchy { buffer_id, position, kind, request_id }
+                if buffer_id == BufferId(3) && position == 42 && kind == "call" && request_id == 10
+        );
+
+        // get_hierarchy_children
+        assert_dispatches!(
+            |a: &PluginApi| a.get_hierarchy_children(
+                "rust-analyzer".into(),
+                "incoming".into(),
+                serde_json::json!({ "name": "parse" }),
+                11,
+            ),
+            PluginCommand::GetHierarchyChildren { server, direction, item, request_id }
+                if server == "rust-analyzer"
+                    && direction == "incoming"
+                    && item["name"] == "parse"
+                    && request_id == 11
+        );
     }
 
     /// `get_active_split_id` reads the snapshot verbatim; a non-{0,1}
@@ -1,7 +1,9 @@
 //! Configuration types shared across crates
 
+use crate::permissions::Capability;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 fn default_true() -> bool {
@@ -22,6 +24,12 @@ pub struct PluginConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[schemars(extend("readOnly" = true))]
     pub path: Option<PathBuf>,
+
+    /// Capabilities the user allowed (true) or denied (false) for this
+    /// plugin package. Recorded when the plugin first asks; remove an
+    /// entry to be asked again. Only applies to installed packages.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub grants: BTreeMap<Capability, bool>,
 }
 
 impl Default for PluginConfig {
@@ -29,6 +37,7 @@ impl Default for PluginConfig {
         Self {
             enabled: true,
             path: None,
+            grants: BTreeMap::new(),
         }
     }
 }
@@ -38,6 +47,7 @@ impl PluginConfig {
         Self {
             enabled: true,
             path: Some(path),
+            grants: BTreeMap::new(),
         }
     }
 }
@@ -187,10 +187,14 @@ pub enum HookArgs {
     /// Request keyboard shortcuts data (key, action) for the help buffer
     KeyboardShortcuts { bindings: Vec<(String, String)> },
 
-    /// LSP find references response received
+    /// LSP find references (or a multi-result go-to implementation,
+    /// type definition or declaration) response received
     LspReferences {
         /// The symbol name being queried
         symbol: String,
+        /// What was asked for: "references", "implementation",
+        /// "type_definition" or "declaration"
+        kind: String,
         /// The locations where the symbol is referenced
         locations: Vec<LspLocation>,
     },
@@ -258,6 +262,22 @@ pub enum HookArgs {
         params: Option<String>,
     },
 
+    /// A code lens command the editor cannot run itself, e.g.
+    /// rust-analyzer's `rust-analyzer.runSingle`. By LSP convention these
+    /// client-side commands are run by the client, so plugins handle them.
+    LspClientCommand {
+        /// Language of the buffer the lens belongs to
+        language: String,
+        /// Name of the server that produced the lens
+        server_name: String,
+        /// Command identifier
+        command: String,
+        /// Title shown for the lens
+        title: String,
+        /// The command arguments as a JSON string
+        arguments: Option<String>,
+    },
+
     /// Viewport changed (scrolled or resized)
     ViewportChanged {
         split_id: SplitId,
@@ -298,6 +318,49 @@ pub enum HookArgs {
         user_dismissed: bool,
     },
 
+    /// A debug session was started
+    DebugStarted {
+        session_id: u64,
+        /// Name of the launch/attach configuration
+        configuration: String,
+        /// Name of the debug adapter
+        adapter: String,
+    },
+
+    /// The debuggee paused (breakpoint, step, pause, exception)
+    DebugStopped {
+        session_id: u64,
+        /// DAP stop reason: "breakpoint", "step", "pause", "exception", ...
+        reason: String,
+        thread_id: Option<i64>,
+        /// Top stack frame, if it has a source file
+        frame_id: Option<i64>,
+        file: Option<PathBuf>,
+        /// 1-based line of the top stack frame
+        line: Option<usize>,
+    },
+
+    /// The debuggee resumed
+    DebugContinued {
+        session_id: u64,
+        thread_id: Option<i64>,
+    },
+
+    /// Output of the debuggee or the adapter (DAP `output` event)
+    DebugOutput {
+        session_id: u64,
+        /// "console", "stdout", "stderr", ...
+        category: String,
+        output: String,
+    },
+
+    /// The debug session ended
+    DebugEnded {
+        session_id: u64,
+        /// Set when the adapter failed rather than exiting normally
+        error: Option<String>,
+    },
+
     /// User selected an action from an action popup
     ActionPopupResult {
         /// The popup ID
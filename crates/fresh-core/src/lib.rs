@@ -98,6 +98,7 @@ pub mod file_explorer;
 pub mod file_uri;
 pub mod menu;
 pub mod overlay;
+pub mod permissions;
 pub mod services;
 pub mod text_property;
 
@@ -0,0 +1,681 @@
+//! Capability-based permissions for plugin packages
+//!
+//! Plugins installed through the package manager run third-party code, so
+//! they only get the capabilities their `package.json` declares under
+//! `fresh.permissions`, and only after the user allows each capability the
+//! first time the plugin uses it. Answers are persisted per plugin in the
+//! `plugins.<name>.grants` config and can be changed in the settings UI.
+//!
+//! Plugins the user placed in their plugins directory themselves, bundled
+//! plugins and `init.ts` are trusted and never checked.
+//!
+//! The plugin runtime checks each file, process, network, environment,
+//! clipboard and authority operation against the shared
+//! [`PermissionRegistry`] before performing it or sending the
+//! corresponding `PluginCommand`. Operations no capability bounds, such as
+//! running editor actions, are refused to plugin packages.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use ts_rs::TS;
+
+/// A capability a plugin package must declare and the user must allow
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema, TS,
+)]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum Capability {
+    /// Read and write files within the declared scopes
+    Filesystem,
+    /// Spawn host processes and terminals
+    Process,
+    /// Replace the editor's authority (where files and processes live)
+    Authority,
+    /// Reach the network, through network clients, shells and terminals
+    Network,
+    /// Write to the clipboard
+    Clipboard,
+    /// Read environment variables
+    Env,
+}
+
+impl Capability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Filesystem => "filesystem",
+            Self::Process => "process",
+            Self::Authority => "authority",
+            Self::Network => "network",
+            Self::Clipboard => "clipboard",
+            Self::Env => "env",
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Paths a plugin package may access.
+///
+/// Entries may start with `${package}` (the package directory),
+/// `${workspace}` (the editor's working directory) or `~`; relative entries
+/// are relative to the package directory, and `*` matches every path. The
+/// package directory itself is always readable.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct FilesystemScope {
+    /// Directories (or files) the plugin may read
+    #[serde(default)]
+    pub read: Vec<String>,
+    /// Directories (or files) the plugin may create, write and delete in;
+    /// write access implies read access
+    #[serde(default)]
+    pub write: Vec<String>,
+}
+
+/// The `fresh.permissions` block of a plugin package manifest
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PluginPermissions {
+    /// File access scopes
+    #[serde(default)]
+    pub filesystem: FilesystemScope,
+    /// Commands the plugin may spawn, by name (e.g. `"git"`); `"*"` allows
+    /// any command, including interactive terminals
+    #[serde(default)]
+    pub process: Vec<String>,
+    /// Whether the plugin may replace the editor's authority
+    #[serde(default)]
+    pub authority: bool,
+    /// Whether the plugin may reach the network. Spawning network clients
+    /// (`curl`, `ssh`, ...), git's remote subcommands, shells and
+    /// terminals needs this in addition to `process`.
+    #[serde(default)]
+    pub network: bool,
+    /// Whether the plugin may write to the clipboard
+    #[serde(default)]
+    pub clipboard: bool,
+    /// Whether the plugin may read environment variables
+    #[serde(default)]
+    pub env: bool,
+}
+
+impl PluginPermissions {
+    /// What a package whose manifest has no `permissions` block is assumed
+    /// to declare: every capability, unscoped. The user is still asked
+    /// before each capability is first used.
+    pub fn unscoped() -> Self {
+        Self {
+            filesystem: FilesystemScope {
+                read: vec!["*".to_string()],
+                write: vec!["*".to_string()],
+            },
+            process: vec!["*".to_string()],
+            authority: true,
+            network: true,
+            clipboard: true,
+            env: true,
+        }
+    }
+}
+
+/// A plugin installed as a package, with what its manifest declares
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    /// The package's root directory
+    pub package_dir: PathBuf,
+    pub permissions: PluginPermissions,
+}
+
+impl Sandbox {
+    /// The sandbox for a plugin file, or `None` if the plugin is trusted.
+    ///
+    /// A plugin is sandboxed when it lives inside an installed package
+    /// (`…/plugins/packages/<name>/`). A manifest without a `permissions`
+    /// block declares everything (see [`PluginPermissions::unscoped`]); a
+    /// missing or malformed manifest declares nothing.
+    pub fn for_plugin(plugin_path: &Path) -> Option<Self> {
+        let package_dir = package_dir(plugin_path)?;
+        let manifest = std::fs::read_to_string(package_dir.join("package.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+        let permissions = match manifest {
+            Some(manifest) => match manifest.get("fresh").and_then(|f| f.get("permissions")) {
+                Some(block) => serde_json::from_value(block.clone()).unwrap_or_default(),
+                None => PluginPermissions::unscoped(),
+            },
+            None => PluginPermissions::default(),
+        };
+        Some(Self {
+            package_dir: package_dir.to_path_buf(),
+            permissions,
+        })
+    }
+
+    /// Whether the manifest declares `request`. `workspace` expands
+    /// `${workspace}` in filesystem scopes.
+    fn declares(&self, request: &Request<'_>, workspace: &Path) -> bool {
+        let fs = &self.permissions.filesystem;
+        match *request {
+            Request::Read(path) => {
+                let path = resolve(path);
+                path.starts_with(resolve(&self.package_dir))
+                    || fs
+                        .read
+                        .iter()
+                        .chain(&fs.write)
+                        .any(|scope| self.in_scope(scope, &path, workspace))
+            }
+            Request::Write(path) => {
+                let path = resolve(path);
+                fs.write
+                    .iter()
+                    .any(|scope| self.in_scope(scope, &path, workspace))
+            }
+            // A bare name is looked up in PATH; a command given with a
+            // path must be declared with exactly that path, or any
+            // writable scope could hold a `git` of the plugin's own.
+            Request::Spawn(command) => self
+                .permissions
+                .process
+                .iter()
+                .any(|allowed| allowed == "*" || allowed == command),
+            Request::Terminal => self.permissions.process.iter().any(|p| p == "*"),
+            Request::Authority => self.permissions.authority,
+            Request::Network => self.permissions.network,
+            Request::Clipboard => self.permissions.clipboard,
+            Request::Env(_) => self.permissions.env,
+        }
+    }
+
+    fn in_scope(&self, scope: &str, path: &Path, workspace: &Path) -> bool {
+        if scope == "*" {
+            return true;
+        }
+        let root = if let Some(rest) = scope.strip_prefix("${package}") {
+            self.package_dir.join(rest.trim_start_matches(['/', '\\']))
+        } else if let Some(rest) = scope.strip_prefix("${workspace}") {
+            workspace.join(rest.trim_start_matches(['/', '\\']))
+        } else if let Some(rest) = scope.strip_prefix('~') {
+            let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))
+            else {
+                return false;
+            };
+            PathBuf::from(home).join(rest.trim_start_matches(['/', '\\']))
+        } else {
+            self.package_dir.join(scope)
+        };
+        path.starts_with(resolve(&root))
+    }
+}
+
+/// The installed package (`…/packages/<name>/`) a plugin file lives in.
+fn package_dir(plugin_path: &Path) -> Option<&Path> {
+    plugin_path.ancestors().skip(1).find(|dir| {
+        dir.parent()
+            .and_then(|parent| parent.file_name())
+            .is_some_and(|name| name == "packages")
+    })
+}
+
+/// The name a plugin file is loaded, sandboxed and granted under: its
+/// file stem, or `<package>:<file stem>` inside an installed package, so
+/// that two packages with the same entry file name don't share grants.
+pub fn plugin_name(plugin_path: &Path) -> Option<String> {
+    let stem = plugin_path.file_stem()?.to_str()?;
+    let Some(package_dir) = package_dir(plugin_path) else {
+        return Some(stem.to_string());
+    };
+    let package = package_dir.file_name()?.to_str()?;
+    let relative = plugin_path.parent()?.strip_prefix(package_dir).ok()?;
+    let mut name = package.to_string();
+    for component in relative.components() {
+        name.push(':');
+        name.push_str(component.as_os_str().to_str()?);
+    }
+    name.push(':');
+    name.push_str(stem);
+    Some(name)
+}
+
+/// Resolve a path the way the file operations it guards will: symlinks
+/// in its longest existing prefix are followed, then `.` and `..` in the
+/// rest are resolved textually.
+fn resolve(path: &Path) -> PathBuf {
+    let absolute = if path.is_relative() {
+        std::env::current_dir().unwrap_or_default().join(path)
+    } else {
+        path.to_path_buf()
+    };
+    for existing in absolute.ancestors() {
+        if let Ok(canonical) = existing.canonicalize() {
+            let rest = absolute.strip_prefix(existing).unwrap_or(Path::new(""));
+            return normalize(&canonical.join(rest));
+        }
+    }
+    normalize(&absolute)
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+/// Relative paths are taken relative to the process working directory,
+/// like the file operations they guard.
+fn normalize(path: &Path) -> PathBuf {
+    let absolute = if path.is_relative() {
+        std::env::current_dir().unwrap_or_default().join(path)
+    } else {
+        path.to_path_buf()
+    };
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// One operation a plugin wants to perform
+#[derive(Debug, Clone, Copy)]
+pub enum Request<'a> {
+    Read(&'a Path),
+    Write(&'a Path),
+    Spawn(&'a str),
+    Terminal,
+    Authority,
+    Network,
+    Clipboard,
+    Env(&'a str),
+}
+
+impl Request<'_> {
+    pub fn capability(&self) -> Capability {
+        match self {
+            Self::Read(_) | Self::Write(_) => Capability::Filesystem,
+            Self::Spawn(_) | Self::Terminal => Capability::Process,
+            Self::Authority => Capability::Authority,
+            Self::Network => Capability::Network,
+            Self::Clipboard => Capability::Clipboard,
+            Self::Env(_) => Capability::Env,
+        }
+    }
+}
+
+impl fmt::Display for Request<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(path) => write!(f, "read {}", path.display()),
+            Self::Write(path) => write!(f, "write {}", path.display()),
+            Self::Spawn(command) => write!(f, "run `{}`", command),
+            Self::Terminal => f.write_str("open a terminal"),
+            Self::Authority => f.write_str("replace the authority"),
+            Self::Network => f.write_str("use the network"),
+            Self::Clipboard => f.write_str("write to the clipboard"),
+            Self::Env(name) => write!(f, "read the environment variable {}", name),
+        }
+    }
+}
+
+/// Settings a sandboxed plugin may not change with `setSetting`: plugin
+/// grants and package sources, and settings holding commands the editor
+/// runs. `*` matches any one key.
+const PROTECTED_SETTINGS: &[&str] = &[
+    "plugins",
+    "packages",
+    "lsp",
+    "universal_lsp",
+    "languages.*.formatter",
+    "languages.*.on_save",
+    "tasks",
+    "terminal.shell",
+    "debug.adapters",
+];
+
+/// Whether writing the dot-separated setting `path` could change a
+/// protected setting, i.e. `path` is within one or contains one.
+pub fn is_protected_setting(path: &str) -> bool {
+    PROTECTED_SETTINGS.iter().any(|protected| {
+        path.split('.')
+            .zip(protected.split('.'))
+            .all(|(key, pattern)| pattern == "*" || key == pattern)
+    })
+}
+
+/// Commands that reach the network by design.
+const NETWORK_COMMANDS: &[&str] = &[
+    "curl", "wget", "ssh", "scp", "sftp", "rsync", "nc", "ncat", "netcat", "telnet", "ftp",
+];
+
+/// Shells, which can run any command, network clients included.
+const SHELLS: &[&str] = &[
+    "sh",
+    "bash",
+    "zsh",
+    "fish",
+    "dash",
+    "ksh",
+    "csh",
+    "tcsh",
+    "nu",
+    "cmd",
+    "powershell",
+    "pwsh",
+];
+
+/// Git subcommands that talk to a remote.
+const GIT_REMOTE_SUBCOMMANDS: &[&str] = &[
+    "clone",
+    "fetch",
+    "pull",
+    "push",
+    "ls-remote",
+    "remote",
+    "submodule",
+    "archive",
+];
+
+/// Whether running `command` with `args` can reach the network, so the
+/// plugin needs the network capability as well as the process one.
+pub fn spawn_uses_network(command: &str, args: &[String]) -> bool {
+    let name = Path::new(command)
+        .file_stem()
+        .and_then(|n| n.to_str())
+        .unwrap_or(command);
+    if NETWORK_COMMANDS.contains(&name) || SHELLS.contains(&name) {
+        return true;
+    }
+    if name != "git" {
+        return false;
+    }
+    // The subcommand is the first argument that isn't an option; `-C`
+    // and `-c` take the next argument as their value.
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-C" | "-c" => {
+                args.next();
+            }
+            option if option.starts_with('-') => {}
+            subcommand => return GIT_REMOTE_SUBCOMMANDS.contains(&subcommand),
+        }
+    }
+    false
+}
+
+/// Outcome of checking a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Access {
+    Allowed,
+    /// Refused, with the reason
+    Denied(String),
+    /// Declared, but the user has not answered yet
+    Ask,
+}
+
+/// Permission state shared by the plugin thread and the editor
+#[derive(Debug, Default)]
+pub struct PermissionState {
+    /// Package plugins by name. Plugins not listed here are trusted.
+    sandboxes: HashMap<String, Sandbox>,
+    /// The user's answers per plugin: `true` allowed, `false` denied
+    grants: HashMap<String, BTreeMap<Capability, bool>>,
+    /// Questions sent to the editor and not answered yet
+    asked: HashSet<(String, Capability)>,
+}
+
+/// Shared handle to the permission state
+pub type PermissionRegistry = Arc<RwLock<PermissionState>>;
+
+impl PermissionState {
+    /// Register (or, with `None`, trust) a plugin when it loads.
+    pub fn set_sandbox(&mut self, plugin_name: &str, sandbox: Option<Sandbox>) {
+        match sandbox {
+            Some(sandbox) => {
+                self.sandboxes.insert(plugin_name.to_string(), sandbox);
+            }
+            None => {
+                self.sandboxes.remove(plugin_name);
+            }
+        }
+    }
+
+    /// Whether a plugin is sandboxed
+    pub fn is_sandboxed(&self, plugin_name: &str) -> bool {
+        self.sandboxes.contains_key(plugin_name)
+    }
+
+    /// Replace all answers, e.g. after the `plugins.*.grants` config changed.
+    pub fn set_grants(&mut self, grants: HashMap<String, BTreeMap<Capability, bool>>) {
+        self.grants = grants;
+    }
+
+    /// Record the user's answer for one capability.
+    pub fn answer(&mut self, plugin_name: &str, capability: Capability, allowed: bool) {
+        self.asked.remove(&(plugin_name.to_string(), capability));
+        self.grants
+            .entry(plugin_name.to_string())
+            .or_default()
+            .insert(capability, allowed);
+    }
+
+    /// Note that the user is being asked about a capability. Returns
+    /// `false` if the question is already pending.
+    pub fn start_asking(&mut self, plugin_name: &str, capability: Capability) -> bool {
+        self.asked.insert((plugin_name.to_string(), capability))
+    }
+
+    /// Check a request from a plugin.
+    pub fn check(&self, plugin_name: &str, request: &Request<'_>, workspace: &Path) -> Access {
+        let Some(sandbox) = self.sandboxes.get(plugin_name) else {
+            return Access::Allowed;
+        };
+        let capability = request.capability();
+        if !sandbox.declares(request, workspace) {
+            return Access::Denied(format!(
+                "not declared in the package manifest's {} permissions",
+                capability
+            ));
+        }
+        match self
+            .grants
+            .get(plugin_name)
+            .and_then(|grants| grants.get(&capability))
+        {
+            Some(true) => Access::Allowed,
+            Some(false) => Access::Denied(format!("{} access was denied", capability)),
+            None => Access::Ask,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with(permissions: PluginPermissions) -> PermissionState {
+        let mut state = PermissionState::default();
+        state.set_sandbox(
+            "pkg",
+            Some(Sandbox {
+                package_dir: PathBuf::from("/cfg/plugins/packages/pkg"),
+                permissions,
+            }),
+        );
+        state
+    }
+
+    #[test]
+    fn test_trusted_plugins_are_not_checked() {
+        let state = PermissionState::default();
+        let request = Request::Write(Path::new("/etc/passwd"));
+        assert_eq!(
+            state.check("mine", &request, Path::new("/w")),
+            Access::Allowed
+        );
+    }
+
+    #[test]
+    fn test_filesystem_scopes() {
+        let mut state = state_with(PluginPermissions {
+            filesystem: FilesystemScope {
+                read: vec!["${workspace}".to_string()],
+                write: vec!["${workspace}/.cache".to_string()],
+            },
+            ..Default::default()
+        });
+        state.answer("pkg", Capability::Filesystem, true);
+        let workspace = Path::new("/w");
+        let check = |request| state.check("pkg", &request, workspace);
+
+        assert_eq!(
+            check(Request::Read(Path::new("/w/src/a.rs"))),
+            Access::Allowed
+        );
+        assert_eq!(
+            check(Request::Write(Path::new("/w/.cache/x"))),
+            Access::Allowed
+        );
+        // The package directory is always readable
+        let own = Path::new("/cfg/plugins/packages/pkg/data.json");
+        assert_eq!(check(Request::Read(own)), Access::Allowed);
+        assert!(matches!(
+            check(Request::Write(Path::new("/w/src/a.rs"))),
+            Access::Denied(_)
+        ));
+        // `..` cannot escape a scope
+        assert!(matches!(
+            check(Request::Read(Path::new("/w/../etc/passwd"))),
+            Access::Denied(_)
+        ));
+    }
+
+    #[test]
+    fn test_declared_capability_asks_until_answered() {
+        let mut state = state_with(PluginPermissions {
+            process: vec!["git".to_string()],
+            ..Default::default()
+        });
+        let workspace = Path::new("/w");
+        let git = Request::Spawn("git");
+
+        assert_eq!(state.check("pkg", &git, workspace), Access::Ask);
+        assert!(state.start_asking("pkg", Capability::Process));
+        assert!(!state.start_asking("pkg", Capability::Process));
+        assert!(matches!(
+            state.check("pkg", &Request::Spawn("curl"), workspace),
+            Access::Denied(_)
+        ));
+
+        state.answer("pkg", Capability::Process, false);
+        assert!(matches!(
+            state.check("pkg", &git, workspace),
+            Access::Denied(_)
+        ));
+        state.answer("pkg", Capability::Process, true);
+        assert_eq!(state.check("pkg", &git, workspace), Access::Allowed);
+        // Only the bare name was declared, not a `git` at some path
+        assert!(matches!(
+            state.check("pkg", &Request::Spawn("/w/.cache/git"), workspace),
+            Access::Denied(_)
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks_cannot_escape_a_scope() {
+        let root = std::env::temp_dir().join(format!("fresh-perm-test-{}", std::process::id()));
+        let workspace = root.join("workspace");
+        let outside = root.join("outside");
+        std::fs::create_dir_all(&workspace).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("secret"), "x").unwrap();
+        std::os::unix::fs::symlink(&outside, workspace.join("link")).unwrap();
+
+        let mut state = state_with(PluginPermissions {
+            filesystem: FilesystemScope {
+                read: vec!["${workspace}".to_string()],
+                write: vec!["${workspace}".to_string()],
+            },
+            ..Default::default()
+        });
+        state.answer("pkg", Capability::Filesystem, true);
+        let check = |request| state.check("pkg", &request, &workspace);
+        let escaped = workspace.join("link/secret");
+        let escaped_new = workspace.join("link/new");
+        let inside_new = workspace.join("new/file");
+
+        assert!(matches!(check(Request::Read(&escaped)), Access::Denied(_)));
+        // Nor through a file that doesn't exist yet
+        assert!(matches!(
+            check(Request::Write(&escaped_new)),
+            Access::Denied(_)
+        ));
+        assert_eq!(check(Request::Write(&inside_new)), Access::Allowed);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_package_plugins_are_named_after_their_package() {
+        let packages = Path::new("/cfg/plugins/packages");
+        assert_eq!(
+            plugin_name(&packages.join("lint/main.ts")).unwrap(),
+            "lint:main"
+        );
+        assert_eq!(
+            plugin_name(&packages.join("fmt/main.ts")).unwrap(),
+            "fmt:main"
+        );
+        assert_eq!(
+            plugin_name(&packages.join("fmt/src/extra.ts")).unwrap(),
+            "fmt:src:extra"
+        );
+        assert_eq!(
+            plugin_name(Path::new("/cfg/plugins/mine.ts")).unwrap(),
+            "mine"
+        );
+    }
+
+    #[test]
+    fn test_network_use_of_spawned_commands() {
+        let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert!(spawn_uses_network("curl", &args(&["-s", "https://x"])));
+        assert!(spawn_uses_network("/bin/sh", &args(&["-c", "true"])));
+        assert!(spawn_uses_network("git", &args(&["-C", "/w", "fetch"])));
+        assert!(!spawn_uses_network(
+            "git",
+            &args(&["status", "--porcelain"])
+        ));
+        assert!(!spawn_uses_network("rg", &args(&["fetch"])));
+    }
+
+    #[test]
+    fn test_unscoped_manifest_still_asks() {
+        let state = state_with(PluginPermissions::unscoped());
+        let request = Request::Env("HOME");
+        assert_eq!(state.check("pkg", &request, Path::new("/w")), Access::Ask);
+    }
+
+    #[test]
+    fn test_protected_settings() {
+        assert!(is_protected_setting("plugins.pkg.grants"));
+        assert!(is_protected_setting("plugins"));
+        assert!(is_protected_setting("lsp.rust"));
+        assert!(is_protected_setting("languages"));
+        assert!(is_protected_setting("languages.rust"));
+        assert!(is_protected_setting("languages.rust.formatter.command"));
+        assert!(is_protected_setting("terminal.shell"));
+        assert!(!is_protected_setting("languages.rust.tab_size"));
+        assert!(!is_protected_setting("terminal.mouse"));
+        assert!(!is_protected_setting("editor.tab_size"));
+    }
+}
@@ -0,0 +1,400 @@
+/// <reference path="./lib/fresh.d.ts" />
+import {
+  col,
+  hintBar,
+  key as widgetKey,
+  styledRow,
+  tree,
+  treeNode,
+  type TreeNode,
+  WidgetPanel,
+} from "./lib/widgets.ts";
+
+const editor = getEditor();
+
+/**
+ * Call Hierarchy Plugin
+ *
+ * Explores the callers, callees, supertypes or subtypes of the symbol at
+ * the cursor as a tree in a buffer group tab. Items come from
+ * `editor.prepareHierarchy` (`textDocument/prepareCallHierarchy` /
+ * `prepareTypeHierarchy`); a node's children are only requested from the
+ * language server when it is expanded, so deep or recursive hierarchies
+ * cost nothing until they are walked.
+ *
+ * Key features:
+ * - Right/Left expand and collapse, Enter jumps to the call site
+ * - `t` reverses the direction (incoming <-> outgoing, super <-> sub)
+ * - q/Escape closes the tab
+ */
+
+// =============================================================================
+// State
+// =============================================================================
+
+type Direction = "incoming" | "outgoing" | "supertypes" | "subtypes";
+
+const REVERSED: Record<Direction, Direction> = {
+  incoming: "outgoing",
+  outgoing: "incoming",
+  supertypes: "subtypes",
+  subtypes: "supertypes",
+};
+
+interface HierarchyNode {
+  /** Stable tree key: the root index followed by the child path */
+  key: string;
+  info: HierarchyItemInfo;
+  /** `null` until the node has been expanded once */
+  children: HierarchyNode[] | null;
+}
+
+interface HierarchyState {
+  groupId: number | null;
+  treeBufferId: number | null;
+  treePanel: WidgetPanel | null;
+  /** Split the group tab lives in; jumps open files there */
+  sourceSplitId: number;
+  direction: Direction;
+  roots: HierarchyNode[];
+  expandedKeys: Set<string>;
+  /** Nodes in the order they were last rendered, to resolve tree indices */
+  flat: HierarchyNode[];
+  selectedIndex: number;
+  /** Bumped whenever the tree is rebuilt, to drop stale expansions */
+  generation: number;
+}
+
+let state: HierarchyState | null = null;
+
+const GROUP_LAYOUT = JSON.stringify({
+  type: "split",
+  direction: "v",
+  ratio: 0.05, // ignored when one side is `fixed`
+  first: { type: "fixed", id: "header", height: 1 },
+  second: { type: "scrollable", id: "tree" },
+});
+
+const THEME = {
+  header: "syntax.keyword" as OverlayColorSpec,
+  name: "syntax.function" as OverlayColorSpec,
+  detail: "editor.line_number_fg" as OverlayColorSpec,
+  location: "syntax.string" as OverlayColorSpec,
+  count: "syntax.number" as OverlayColorSpec,
+};
+
+// =============================================================================
+// Mode
+// =============================================================================
+
+editor.defineMode(
+  "call-hierarchy",
+  [
+    ["Up", "call_hierarchy_up"],
+    ["Down", "call_hierarchy_down"],
+    ["PageUp", "call_hierarchy_page_up"],
+    ["PageDown", "call_hierarchy_page_down"],
+    ["Left", "call_hierarchy_collapse"],
+    ["Right", "call_hierarchy_expand"],
+    ["Return", "call_hierarchy_enter"],
+    ["t", "call_hierarchy_reverse"],
+    ["q", "call_hierarchy_close"],
+    ["Escape", "call_hierarchy_close"],
+  ],
+  true, // read-only
+  false, // allow_text_input
+  true, // inherit Normal-context bindings for unbound keys
+);
+
+// Navigation and expansion are owned by the Tree widget; the plugin only
+// reacts to the `widget_event`s it fires back.
+registerHandler("call_hierarchy_up", () => state?.treePanel?.command(widgetKey("Up")));
+registerHandler("call_hierarchy_down", () => state?.treePanel?.command(widgetKey("Down")));
+registerHandler("call_hierarchy_page_up", () => state?.treePanel?.command(widgetKey("PageUp")));
+registerHandler("call_hierarchy_page_down", () => state?.treePanel?.command(widgetKey("PageDown")));
+registerHandler("call_hierarchy_collapse", () => state?.treePanel?.command(widgetKey("Left")));
+registerHandler("call_hierarchy_expand", () => state?.treePanel?.command(widgetKey("Right")));
+registerHandler("call_hierarchy_enter", () => state?.treePanel?.command(widgetKey("Enter")));
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+function getRelativePath(filePath: string): string {
+  const cwd = editor.getCwd();
+  if (filePath.startsWith(cwd)) {
+    return filePath.slice(cwd.length + 1);
+  }
+  return filePath;
+}
+
+function headerText(): string {
+  if (!state) return "";
+  const names = state.roots.map((root) => root.info.name).join(", ");
+  return editor.t(`header.${state.direction}`, { name: names });
+}
+
+function buildRow(node: HierarchyNode): TextPropertyEntry {
+  const { info } = node;
+  const segments: StyledSegment[] = [
+    { text: info.name, style: { fg: THEME.name, bold: true } },
+  ];
+  if (info.detail) {
+    segments.push({ text: `  ${info.detail}`, style: { fg: THEME.detail } });
+  }
+  // Call nodes point at their first call site; roots and type nodes at
+  // the declaration.
+  const site = info.callSites[0] ?? info;
+  segments.push({
+    text: `  ${getRelativePath(site.file)}:${site.line}`,
+    style: { fg: THEME.location },
+  });
+  if (info.callSites.length > 1) {
+    segments.push({
+      text: `  ×${info.callSites.length}`,
+      style: { fg: THEME.count },
+    });
+  }
+  return styledRow(segments);
+}
+
+/** Depth-first flattening; the host hides children of collapsed nodes. */
+function flatten(
+  nodes: HierarchyNode[],
+  depth: number,
+  out: { node: HierarchyNode; tree: TreeNode }[],
+): void {
+  for (const node of nodes) {
+    // Unexpanded nodes may have children; an expansion that comes back
+    // empty drops the disclosure glyph.
+    const hasChildren = node.children === null || node.children.length > 0;
+    out.push({ node, tree: treeNode(buildRow(node), { depth, hasChildren }) });
+    if (node.children) flatten(node.children, depth + 1, out);
+  }
+}
+
+function render(): void {
+  if (!state || state.groupId === null || !state.treePanel) return;
+  const rows: { node: HierarchyNode; tree: TreeNode }[] = [];
+  flatten(state.roots, 0, rows);
+  state.flat = rows.map((r) => r.node);
+
+  editor.setPanelContent(state.groupId, "header", [
+    styledRow([
+      { text: headerText(), style: { fg: THEME.header, bold: true } },
+    ]),
+  ]);
+  state.treePanel.set(
+    col(
+      tree({
+        nodes: rows.map((r) => r.tree),
+        itemKeys: rows.map((r) => r.node.key),
+        selectedIndex: state.selectedIndex,
+        visibleRows: Math.max(1, rows.length),
+        expandedKeys: [...state.expandedKeys],
+        key: "hierarchyTree",
+      }),
+      hintBar([
+        { keys: "←/→", label: editor.t("hint.expand") },
+        { keys: "RET", label: editor.t("hint.jump") },
+        { keys: "t", label: editor.t("hint.reverse") },
+        { keys: "q", label: editor.t("hint.close") },
+      ]),
+    ),
+  );
+  // Expansion is host-owned once mounted; push ours so rebuilt trees
+  // (direction reversal) start from the plugin's view.
+  state.treePanel.setExpandedKeys("hierarchyTree", [...state.expandedKeys]);
+}
+
+// =============================================================================
+// Hierarchy walking
+// =============================================================================
+
+function toNodes(items: HierarchyItemInfo[], parentKey: string | null): HierarchyNode[] {
+  return items.map((info, i) => ({
+    key: parentKey === null ? String(i) : `${parentKey}/${i}`,
+    info,
+    children: null,
+  }));
+}
+
+async function loadChildren(node: HierarchyNode): Promise<void> {
+  if (!state || node.children !== null) return;
+  const generation = state.generation;
+  const items = await editor.getHierarchyChildren(node.info, state.direction);
+  if (!state || state.generation !== generation) return;
+  node.children = toNodes(items, node.key);
+}
+
+/** Rebuild the tree from the current roots with their children loaded. */
+async function rebuild(): Promise<void> {
+  if (!state) return;
+  state.generation++;
+  for (const root of state.roots) root.children = null;
+  state.expandedKeys = new Set(state.roots.map((root) => root.key));
+  state.selectedIndex = 0;
+  await Promise.all(state.roots.map(loadChildren));
+}
+
+async function onExpand(key: string, expanded: boolean): Promise<void> {
+  if (!state) return;
+  if (!expanded) {
+    state.expandedKeys.delete(key);
+    return;
+  }
+  state.expandedKeys.add(key);
+  const node = state.flat.find((n) => n.key === key);
+  if (!node || node.children !== null) return;
+  await loadChildren(node);
+  render();
+}
+
+function jumpTo(node: HierarchyNode): void {
+  if (!state) return;
+  const target = node.info.callSites[0] ?? node.info;
+  editor.openFileInSplit(state.sourceSplitId, target.file, target.line, target.column);
+}
+
+// =============================================================================
+// Open / close
+// =============================================================================
+
+async function showHierarchy(direction: Direction): Promise<void> {
+  const bufferId = editor.getActiveBufferId();
+  if (!editor.getBufferPath(bufferId)) {
+    editor.setStatus(editor.t("status.no_file"));
+    return;
+  }
+  const kind = direction === "incoming" || direction === "outgoing" ? "call" : "type";
+  editor.setStatus(editor.t("status.loading"));
+  const items = await editor.prepareHierarchy(bufferId, editor.getCursorPosition(), kind);
+  if (items.length === 0) {
+    editor.setStatus(editor.t(`status.no_${kind}_item`));
+    return;
+  }
+
+  call_hierarchy_close();
+  const sourceSplitId = editor.getActiveSplitId();
+  state = {
+    groupId: null,
+    treeBufferId: null,
+    treePanel: null,
+    sourceSplitId,
+    direction,
+    roots: toNodes(items, null),
+    expandedKeys: new Set(),
+    flat: [],
+    selectedIndex: 0,
+    generation: 0,
+  };
+  await rebuild();
+  if (!state) return;
+
+  // `createBufferGroup` is a runtime-only binding (see git_log.ts).
+  const group = await (editor as any).createBufferGroup(
+    "*Call Hierarchy*",
+    "call-hierarchy",
+    GROUP_LAYOUT,
+  );
+  state.groupId = group.groupId as number;
+  state.treeBufferId = (group.panels["tree"] as number | undefined) ?? null;
+  if (state.treeBufferId !== null) {
+    state.treePanel = new WidgetPanel(state.treeBufferId);
+  }
+  render();
+  editor.focusBufferGroupPanel(state.groupId, "tree");
+  editor.setStatus("");
+}
+
+async function call_hierarchy_reverse(): Promise<void> {
+  if (!state) return;
+  state.direction = REVERSED[state.direction];
+  await rebuild();
+  render();
+}
+registerHandler("call_hierarchy_reverse", call_hierarchy_reverse);
+
+function call_hierarchy_close(): void {
+  if (!state) return;
+  const groupId = state.groupId;
+  state = null;
+  if (groupId !== null) {
+    editor.closeBufferGroup(groupId);
+  }
+}
+registerHandler("call_hierarchy_close", call_hierarchy_close);
+
+registerHandler("show_incoming_calls", () => showHierarchy("incoming"));
+registerHandler("show_outgoing_calls", () => showHierarchy("outgoing"));
+registerHandler("show_supertypes", () => showHierarchy("supertypes"));
+registerHandler("show_subtypes", () => showHierarchy("subtypes"));
+
+// =============================================================================
+// Event handlers
+// =============================================================================
+
+editor.on("widget_event", (args) => {
+  if (!state || args.panel_id !== state.treePanel?.id()) return;
+
+  if (args.event_type === "select") {
+    const idx = (args.payload as { index?: number } | undefined)?.index;
+    if (typeof idx === "number") state.selectedIndex = idx;
+    return;
+  }
+
+  // Right/Left or a click on the disclosure glyph. Children are fetched
+  // the first time a node opens.
+  if (args.event_type === "expand") {
+    const payload = args.payload as { key?: string; expanded?: boolean } | undefined;
+    if (typeof payload?.key === "string" && typeof payload.expanded === "boolean") {
+      void onExpand(payload.key, payload.expanded);
+    }
+    return;
+  }
+
+  if (args.event_type === "activate") {
+    const idx = (args.payload as { index?: number } | undefined)?.index;
+    const node = typeof idx === "number" ? state.flat[idx] : undefined;
+    if (node) jumpTo(node);
+  }
+});
+
+// Closing the tab from the tab bar tears the group down without going
+// through `call_hierarchy_close`.
+editor.on("buffer_closed", (args) => {
+  if (state && args.buffer_id === state.treeBufferId) {
+    state = null;
+  }
+});
+
+// =============================================================================
+// Command registration
+// =============================================================================
+
+editor.registerCommand(
+  "%cmd.show_incoming_calls",
+  "%cmd.show_incoming_calls_desc",
+  "show_incoming_calls",
+  null
+);
+editor.registerCommand(
+  "%cmd.show_outgoing_calls",
+  "%cmd.show_outgoing_calls_desc",
+  "show_outgoing_calls",
+  null
+);
+editor.registerCommand(
+  "%cmd.show_supertypes",
+  "%cmd.show_supertypes_desc",
+  "show_supertypes",
+  null
+);
+editor.registerCommand(
+  "%cmd.show_subtypes",
+  "%cmd.show_subtypes_desc",
+  "show_subtypes",
+  null
+);
+
+editor.debug("Call Hierarchy plugin initialized");
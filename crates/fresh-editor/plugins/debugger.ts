@@ -0,0 +1,549 @@
+/// <reference path="./lib/fresh.d.ts" />
+import {
+  col,
+  hintBar,
+  key as widgetKey,
+  styledRow,
+  tree,
+  treeNode,
+  type TreeNode,
+  WidgetPanel,
+} from "./lib/widgets.ts";
+
+const editor = getEditor();
+
+/**
+ * Debugger Panels
+ *
+ * Shows the call stack, variables and watch expressions of a paused debug
+ * session in the Utility Dock. The session itself (adapter process,
+ * breakpoints, stepping, current-line marker) is run by the editor; this
+ * plugin only reads from it with `editor.sendDebugRequest` whenever a
+ * `debug_stopped` hook fires.
+ *
+ * Variables are fetched lazily: a scope or structured value only asks the
+ * adapter for its children (`variables`) the first time it is expanded.
+ *
+ * Key features:
+ * - Enter on a frame selects it and jumps to its source
+ * - Right/Left expand and collapse scopes and structured values
+ * - `w` adds a watch expression, `d` removes the selected one
+ * - q/Escape closes the panel
+ */
+
+// =============================================================================
+// State
+// =============================================================================
+
+interface StackFrame {
+  id: number;
+  name: string;
+  line: number;
+  column: number;
+  source?: { path?: string; name?: string };
+}
+
+interface PanelNode {
+  /** Stable tree key */
+  key: string;
+  label: string;
+  value: string | null;
+  /** DAP `variablesReference`; 0 when the node has no children */
+  reference: number;
+  /** `null` until the node has been expanded once */
+  children: PanelNode[] | null;
+  /** Set on call stack rows */
+  frame?: StackFrame;
+  /** Set on watch rows: index into `watches` */
+  watchIndex?: number;
+}
+
+interface DebuggerState {
+  bufferId: number | null;
+  panel: WidgetPanel | null;
+  sessionId: number | null;
+  threadId: number | null;
+  frames: StackFrame[];
+  selectedFrameId: number | null;
+  scopes: PanelNode[];
+  watchValues: PanelNode[];
+  expandedKeys: Set<string>;
+  /** Nodes in the order they were last rendered, to resolve tree indices */
+  flat: PanelNode[];
+  selectedIndex: number;
+  /** Bumped on every stop, to drop answers about an older pause */
+  generation: number;
+}
+
+/** Watch expressions outlive sessions */
+const watches: string[] = [];
+
+const state: DebuggerState = {
+  bufferId: null,
+  panel: null,
+  sessionId: null,
+  threadId: null,
+  frames: [],
+  selectedFrameId: null,
+  scopes: [],
+  watchValues: [],
+  expandedKeys: new Set(["stack", "variables", "watch"]),
+  flat: [],
+  selectedIndex: 0,
+  generation: 0,
+};
+
+const STACK_LEVELS = 50;
+
+const THEME = {
+  section: "syntax.keyword" as OverlayColorSpec,
+  name: "syntax.variable" as OverlayColorSpec,
+  frame: "syntax.function" as OverlayColorSpec,
+  value: "syntax.string" as OverlayColorSpec,
+  detail: "editor.line_number_fg" as OverlayColorSpec,
+  current: "diagnostic.warning_fg" as OverlayColorSpec,
+};
+
+// =============================================================================
+// Mode
+// =============================================================================
+
+editor.defineMode(
+  "debugger-panel",
+  [
+    ["Up", "debugger_up"],
+    ["Down", "debugger_down"],
+    ["PageUp", "debugger_page_up"],
+    ["PageDown", "debugger_page_down"],
+    ["Left", "debugger_collapse"],
+    ["Right", "debugger_expand"],
+    ["Return", "debugger_enter"],
+    ["w", "debug_add_watch"],
+    ["d", "debugger_remove_watch"],
+    ["q", "debugger_close"],
+    ["Escape", "debugger_close"],
+  ],
+  true, // read-only
+  false, // allow_text_input
+  true, // inherit Normal-context bindings for unbound keys
+);
+
+registerHandler("debugger_up", () => state.panel?.command(widgetKey("Up")));
+registerHandler("debugger_down", () => state.panel?.command(widgetKey("Down")));
+registerHandler("debugger_page_up", () => state.panel?.command(widgetKey("PageUp")));
+registerHandler("debugger_page_down", () => state.panel?.command(widgetKey("PageDown")));
+registerHandler("debugger_collapse", () => state.panel?.command(widgetKey("Left")));
+registerHandler("debugger_expand", () => state.panel?.command(widgetKey("Right")));
+registerHandler("debugger_enter", () => state.panel?.command(widgetKey("Enter")));
+
+// =============================================================================
+// Adapter requests
+// =============================================================================
+
+async function request<T>(command: string, args: Record<string, unknown>): Promise<T | null> {
+  try {
+    return (await editor.sendDebugRequest(command, args)) as T;
+  } catch (e) {
+    editor.debug(`debugger: ${command} failed: ${e}`);
+    return null;
+  }
+}
+
+interface Variable {
+  name: string;
+  value: string;
+  type?: string;
+  variablesReference: number;
+}
+
+async function loadVariables(reference: number, parentKey: string): Promise<PanelNode[]> {
+  const body = await request<{ variables?: Variable[] }>("variables", {
+    variablesReference: reference,
+  });
+  return (body?.variables ?? []).map((v, i) => ({
+    key: `${parentKey}/${i}`,
+    label: v.name,
+    value: v.value,
+    reference: v.variablesReference,
+    children: null,
+  }));
+}
+
+async function loadScopes(frameId: number): Promise<PanelNode[]> {
+  const body = await request<{
+    scopes?: { name: string; variablesReference: number; expensive?: boolean }[];
+  }>("scopes", { frameId });
+  const scopes = (body?.scopes ?? []).map((s, i) => ({
+    key: `variables/${i}`,
+    label: s.name,
+    value: null,
+    reference: s.variablesReference,
+    children: null as PanelNode[] | null,
+  }));
+  // Open the innermost cheap scope, as that is what one usually wants
+  const first = (body?.scopes ?? []).findIndex((s) => !s.expensive);
+  if (first >= 0) {
+    scopes[first].children = await loadVariables(scopes[first].reference, scopes[first].key);
+    state.expandedKeys.add(scopes[first].key);
+  }
+  return scopes;
+}
+
+async function evaluateWatches(frameId: number | null): Promise<PanelNode[]> {
+  return await Promise.all(
+    watches.map(async (expression, i) => {
+      const args: Record<string, unknown> = { expression, context: "watch" };
+      if (frameId !== null) args.frameId = frameId;
+      const body = await request<{ result: string; variablesReference: number }>(
+        "evaluate",
+        args,
+      );
+      return {
+        key: `watch/${i}`,
+        label: expression,
+        value: body ? body.result : editor.t("panel.unavailable"),
+        reference: body?.variablesReference ?? 0,
+        children: null,
+        watchIndex: i,
+      };
+    }),
+  );
+}
+
+/** Reload scopes and watches for the selected frame. */
+async function refreshFrame(): Promise<void> {
+  const generation = state.generation;
+  const frameId = state.selectedFrameId;
+  const [scopes, watchValues] = await Promise.all([
+    frameId !== null ? loadScopes(frameId) : Promise.resolve([]),
+    evaluateWatches(frameId),
+  ]);
+  if (generation !== state.generation) return;
+  state.scopes = scopes;
+  state.watchValues = watchValues;
+  render();
+}
+
+async function onStopped(sessionId: number, threadId: number | null): Promise<void> {
+  state.generation++;
+  const generation = state.generation;
+  state.sessionId = sessionId;
+  state.threadId = threadId;
+  state.frames = [];
+  state.scopes = [];
+  if (threadId !== null) {
+    const body = await request<{ stackFrames?: StackFrame[] }>("stackTrace", {
+      threadId,
+      startFrame: 0,
+      levels: STACK_LEVELS,
+    });
+    if (generation !== state.generation) return;
+    state.frames = body?.stackFrames ?? [];
+  }
+  state.selectedFrameId = state.frames[0]?.id ?? null;
+  await openPanel();
+  await refreshFrame();
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+function getRelativePath(filePath: string): string {
+  const cwd = editor.getCwd();
+  if (filePath.startsWith(cwd)) {
+    return filePath.slice(cwd.length + 1);
+  }
+  return filePath;
+}
+
+function section(key: string, label: string, children: PanelNode[]): PanelNode {
+  return { key, label, value: null, reference: 0, children };
+}
+
+function frameNodes(): PanelNode[] {
+  return state.frames.map((frame, i) => ({
+    key: `stack/${i}`,
+    label: frame.name,
+    value: null,
+    reference: 0,
+    children: [],
+    frame,
+  }));
+}
+
+function buildRow(node: PanelNode, depth: number): TextPropertyEntry {
+  if (depth === 0) {
+    return styledRow([{ text: node.label, style: { fg: THEME.section, bold: true } }]);
+  }
+  if (node.frame) {
+    const frame = node.frame;
+    const current = frame.id === state.selectedFrameId;
+    const segments: StyledSegment[] = [
+      { text: current ? "▶ " : "  ", style: { fg: THEME.current } },
+      { text: frame.name, style: { fg: THEME.frame } },
+    ];
+    const path = frame.source?.path ?? frame.source?.name;
+    if (path) {
+      segments.push({
+        text: `  ${getRelativePath(path)}:${frame.line}`,
+        style: { fg: THEME.detail },
+      });
+    }
+    return styledRow(segments);
+  }
+  const segments: StyledSegment[] = [{ text: node.label, style: { fg: THEME.name } }];
+  if (node.value !== null) {
+    segments.push({ text: " = ", style: { fg: THEME.detail } });
+    segments.push({ text: node.value, style: { fg: THEME.value } });
+  }
+  return styledRow(segments);
+}
+
+/** Depth-first flattening; the host hides children of collapsed nodes. */
+function flatten(
+  nodes: PanelNode[],
+  depth: number,
+  out: { node: PanelNode; tree: TreeNode }[],
+): void {
+  for (const node of nodes) {
+    const hasChildren = depth === 0 ||
+      (node.children === null ? node.reference > 0 : node.children.length > 0);
+    out.push({ node, tree: treeNode(buildRow(node, depth), { depth, hasChildren }) });
+    if (node.children) flatten(node.children, depth + 1, out);
+  }
+}
+
+function render(): void {
+  if (!state.panel) return;
+  const roots = [
+    section("stack", editor.t("panel.call_stack"), frameNodes()),
+    section("variables", editor.t("panel.variables"), state.scopes),
+    section("watch", editor.t("panel.watch"), state.watchValues),
+  ];
+  const rows: { node: PanelNode; tree: TreeNode }[] = [];
+  flatten(roots, 0, rows);
+  state.flat = rows.map((r) => r.node);
+
+  state.panel.set(
+    col(
+      tree({
+        nodes: rows.map((r) => r.tree),
+        itemKeys: rows.map((r) => r.node.key),
+        selectedIndex: state.selectedIndex,
+        visibleRows: Math.max(1, rows.length),
+        expandedKeys: [...state.expandedKeys],
+        key: "debuggerTree",
+      }),
+      hintBar([
+        { keys: "←/→", label: editor.t("hint.expand") },
+        { keys: "RET", label: editor.t("hint.select_frame") },
+        { keys: "w", label: editor.t("hint.add_watch") },
+        { keys: "d", label: editor.t("hint.remove_watch") },
+        { keys: "q", label: editor.t("hint.close") },
+      ]),
+    ),
+  );
+  state.panel.setExpandedKeys("debuggerTree", [...state.expandedKeys]);
+}
+
+// =============================================================================
+// Interaction
+// =============================================================================
+
+function findNode(nodes: PanelNode[], key: string): PanelNode | null {
+  for (const node of nodes) {
+    if (node.key === key) return node;
+    if (node.children) {
+      const found = findNode(node.children, key);
+      if (found) return found;
+    }
+  }
+  return null;
+}
+
+async function onExpand(key: string, expanded: boolean): Promise<void> {
+  if (!expanded) {
+    state.expandedKeys.delete(key);
+    return;
+  }
+  state.expandedKeys.add(key);
+  const node = findNode(state.scopes, key) ?? findNode(state.watchValues, key);
+  if (!node || node.children !== null || node.reference === 0) return;
+  const generation = state.generation;
+  const children = await loadVariables(node.reference, node.key);
+  if (generation !== state.generation) return;
+  node.children = children;
+  render();
+}
+
+function onActivate(node: PanelNode): void {
+  const frame = node.frame;
+  if (!frame) return;
+  state.selectedFrameId = frame.id;
+  const path = frame.source?.path;
+  if (path) {
+    editor.openFile(path, frame.line, Math.max(1, frame.column));
+  }
+  void refreshFrame();
+}
+
+// =============================================================================
+// Open / close
+// =============================================================================
+
+async function openPanel(): Promise<void> {
+  if (state.bufferId !== null) return;
+  try {
+    const result = await editor.createVirtualBufferInSplit({
+      name: "*Debug*",
+      mode: "debugger-panel",
+      readOnly: true,
+      entries: [],
+      ratio: 0.7,
+      panelId: "debugger-panel",
+      role: "utility_dock",
+      showLineNumbers: false,
+      showCursors: false,
+      editingDisabled: true,
+    });
+    state.bufferId = result.bufferId;
+    state.panel = new WidgetPanel(result.bufferId);
+  } catch (error) {
+    const errorMessage = error instanceof Error ? error.message : String(error);
+    editor.debug(`debugger: failed to open panel: ${errorMessage}`);
+  }
+}
+
+function debugger_close(): void {
+  const bufferId = state.bufferId;
+  state.panel?.unmount();
+  state.panel = null;
+  state.bufferId = null;
+  if (bufferId !== null) {
+    editor.closeBuffer(bufferId);
+  }
+}
+registerHandler("debugger_close", debugger_close);
+
+async function debug_show_panel(): Promise<void> {
+  await openPanel();
+  render();
+}
+registerHandler("debug_show_panel", debug_show_panel);
+
+function debug_add_watch(): void {
+  editor.startPrompt(editor.t("prompt.watch"), "debugger-add-watch");
+}
+registerHandler("debug_add_watch", debug_add_watch);
+
+async function debugger_remove_watch(): Promise<void> {
+  const node = state.flat[state.selectedIndex];
+  if (node?.watchIndex === undefined) return;
+  watches.splice(node.watchIndex, 1);
+  state.watchValues = await evaluateWatches(state.selectedFrameId);
+  render();
+}
+registerHandler("debugger_remove_watch", debugger_remove_watch);
+
+// =============================================================================
+// Event handlers
+// =============================================================================
+
+editor.on("prompt_confirmed", (args) => {
+  if (args.prompt_type !== "debugger-add-watch") return true;
+  const expression = args.input.trim();
+  if (expression) {
+    watches.push(expression);
+    void (async () => {
+      state.watchValues = state.sessionId !== null
+        ? await evaluateWatches(state.selectedFrameId)
+        : watches.map((label, i) => ({
+          key: `watch/${i}`,
+          label,
+          value: null,
+          reference: 0,
+          children: null,
+          watchIndex: i,
+        }));
+      await openPanel();
+      render();
+    })();
+  }
+  return true;
+});
+
+editor.on("debug_stopped", (args) => {
+  void onStopped(args.session_id, args.thread_id);
+});
+
+editor.on("debug_continued", () => {
+  // Values are stale while the program runs; keep the layout
+  state.generation++;
+  state.frames = [];
+  state.scopes = [];
+  state.watchValues = state.watchValues.map((w) => ({ ...w, value: null, children: null }));
+  render();
+});
+
+editor.on("debug_ended", () => {
+  state.generation++;
+  state.sessionId = null;
+  state.threadId = null;
+  state.frames = [];
+  state.selectedFrameId = null;
+  state.scopes = [];
+  state.watchValues = state.watchValues.map((w) => ({ ...w, value: null, children: null }));
+  render();
+});
+
+editor.on("widget_event", (args) => {
+  if (!state.panel || args.panel_id !== state.panel.id()) return;
+
+  if (args.event_type === "select") {
+    const idx = (args.payload as { index?: number } | undefined)?.index;
+    if (typeof idx === "number") state.selectedIndex = idx;
+    return;
+  }
+
+  if (args.event_type === "expand") {
+    const payload = args.payload as { key?: string; expanded?: boolean } | undefined;
+    if (typeof payload?.key === "string" && typeof payload.expanded === "boolean") {
+      void onExpand(payload.key, payload.expanded);
+    }
+    return;
+  }
+
+  if (args.event_type === "activate") {
+    const idx = (args.payload as { index?: number } | undefined)?.index;
+    const node = typeof idx === "number" ? state.flat[idx] : undefined;
+    if (node) onActivate(node);
+  }
+});
+
+// Closing the tab from the tab bar bypasses `debugger_close`.
+editor.on("buffer_closed", (args) => {
+  if (args.buffer_id === state.bufferId) {
+    state.panel = null;
+    state.bufferId = null;
+  }
+});
+
+// =============================================================================
+// Command registration
+// =============================================================================
+
+editor.registerCommand(
+  "%cmd.debug_show_panel",
+  "%cmd.debug_show_panel_desc",
+  "debug_show_panel",
+  null
+);
+editor.registerCommand(
+  "%cmd.debug_add_watch",
+  "%cmd.debug_add_watch_desc",
+  "debug_add_watch",
+  null
+);
+
+editor.debug("Debugger plugin initialized");
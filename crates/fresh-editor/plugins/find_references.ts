@@ -83,13 +83,24 @@ async function loadLineContent(
   return result;
 }
 
-// Handle lsp_references hook
-
+// Panel title for each kind of location list
+function panelTitle(kind: string, symbol: string, count: number): string {
+  switch (kind) {
+    case "implementation":
+      return `Implementations of '${symbol}' (${count})`;
+    case "type_definition":
+      return `Type definitions of '${symbol}' (${count})`;
+    case "declaration":
+      return `Declarations of '${symbol}' (${count})`;
+    default:
+      return `References to '${symbol}' (${count})`;
+  }
+}
 
 // Register the hook handler
 editor.on("lsp_references", async (data) => {
   editor.debug(
-    `Received ${data.locations.length} references for '${data.symbol}'`
+    `Received ${data.locations.length} ${data.kind} locations for '${data.symbol}'`
   );
 
   if (data.locations.length === 0) {
@@ -102,7 +113,7 @@ editor.on("lsp_references", async (data) => {
 
   // Use prompt mode with filter source - same UX as grep plugins
   finder.prompt({
-    title: `References to '${data.symbol}' (${data.locations.length})`,
+    title: panelTitle(data.kind, data.symbol, data.locations.length),
     source: {
       mode: "filter",
       load: async () => pendingRefs,
@@ -616,6 +616,16 @@ export class Finder<T> {
     }
   }
 
+  /**
+   * Move the panel cursor to the item at `index` (for live panels that
+   * follow the editor with their own notion of the "current" item)
+   */
+  reveal(index: number): void {
+    if (this.isPanelMode) {
+      this.revealItem(index);
+    }
+  }
+
   // ==========================================================================
   // Prompt Mode Implementation
   // ==========================================================================
@@ -1152,6 +1152,93 @@ type CreateVirtualBufferOptions = {
 	*/
 	entries?: Array<TextPropertyEntry>;
 };
+type DocumentSymbolInfo = {
+	/**
+	* Symbol name
+	*/
+	name: string;
+	/**
+	* Extra information from the language server (e.g., a signature)
+	*/
+	detail?: string;
+	/**
+	* Symbol kind (e.g., "function", "class", "method")
+	*/
+	kind: string;
+	/**
+	* Byte offset where the definition starts
+	*/
+	start: number;
+	/**
+	* Byte offset where the definition ends
+	*/
+	end: number;
+	/**
+	* Byte offset of the symbol's name (where navigation should land)
+	*/
+	nameOffset: number;
+	/**
+	* Zero-indexed line of the symbol's name
+	*/
+	line: number;
+	/**
+	* Nesting depth (0 for top-level symbols)
+	*/
+	depth: number;
+};
+type HierarchyItemInfo = {
+	/**
+	* Symbol name
+	*/
+	name: string;
+	/**
+	* Extra information from the language server (e.g., a signature)
+	*/
+	detail?: string;
+	/**
+	* Symbol kind (e.g., "function", "method", "class")
+	*/
+	kind: string;
+	/**
+	* Path of the file defining the symbol
+	*/
+	file: string;
+	/**
+	* One-indexed line of the symbol's name
+	*/
+	line: number;
+	/**
+	* One-indexed column of the symbol's name
+	*/
+	column: number;
+	/**
+	* Where the calls happen, for incoming and outgoing calls
+	*/
+	callSites: Array<HierarchyCallSite>;
+	/**
+	* Language server that produced the item
+	*/
+	server: string;
+	/**
+	* The item as sent by the language server, passed back when
+	* expanding this node
+	*/
+	item: any;
+};
+type HierarchyCallSite = {
+	/**
+	* Path of the file containing the call
+	*/
+	file: string;
+	/**
+	* One-indexed line of the call
+	*/
+	line: number;
+	/**
+	* One-indexed column of the call
+	*/
+	column: number;
+};
 type GrepMatch = {
 	/**
 	* Absolute file path
@@ -1381,7 +1468,8 @@ interface EditorAPI {
 	*/
 	setContext(name: string, active: boolean): boolean;
 	/**
-	* Execute a built-in action
+	* Execute a built-in action. Not available to plugin packages: actions
+	* save, run tasks and copy without going through their permissions.
 	*/
 	executeAction(actionName: string): boolean;
 	/**
@@ -1694,7 +1782,8 @@ interface EditorAPI {
 	* 
 	* Returns `true` if the write was queued. The actual update is
 	* asynchronous; a subsequent `getConfig()` will reflect it after the
-	* editor processes the command.
+	* editor processes the command. Plugin packages may not change plugin
+	* settings or settings holding commands, and get `false`.
 	*/
 	setSetting(path: string, value: unknown): boolean;
 	/**
@@ -1840,6 +1929,29 @@ interface EditorAPI {
 	*/
 	getHighlights(bufferId: number, start: number, end: number): Promise<TsHighlightSpan[]>;
 	/**
+	* Get the symbol outline of a buffer (async)
+	* 
+	* Symbols come from the language server when one supports
+	* `textDocument/documentSymbol`, otherwise from the grammar's
+	* tree-sitter tags query. Resolves to an empty array when neither is
+	* available.
+	*/
+	getDocumentSymbols(bufferId: number): Promise<DocumentSymbolInfo[]>;
+	/**
+	* Get the call or type hierarchy items at a byte offset (async)
+	* 
+	* `kind` is "call" or "type". Resolves to an empty array when no
+	* language server for the buffer supports the hierarchy.
+	*/
+	prepareHierarchy(bufferId: number, position: number, kind: string): Promise<HierarchyItemInfo[]>;
+	/**
+	* Get the neighbours of a hierarchy item (async)
+	* 
+	* `direction` is "incoming" or "outgoing" for call hierarchy items
+	* and "supertypes" or "subtypes" for type hierarchy items.
+	*/
+	getHierarchyChildren(node: HierarchyItemInfo, direction: string): Promise<HierarchyItemInfo[]>;
+	/**
 	* Add an overlay with styling options
 	* 
 	* Colors can be specified as RGB arrays `[r, g, b]` or theme key strings.
@@ -2267,7 +2379,8 @@ interface EditorAPI {
 	/**
 	* Execute multiple actions in sequence
 	* 
-	* Takes typed ActionSpec array - serde validates field names at runtime
+	* Takes typed ActionSpec array - serde validates field names at runtime.
+	* Not available to plugin packages, like `execute_action`.
 	*/
 	executeActions(actions: ActionSpec[]): boolean;
 	/**
@@ -2467,6 +2580,10 @@ interface EditorAPI {
 	*/
 	sendLspRequest(language: string, method: string, params: Record<string, unknown> | null): Promise<unknown>;
 	/**
+	* Send a request to the running debug adapter (async, returns request_id)
+	*/
+	sendDebugRequest(command: string, arguments: Record<string, unknown> | null): Promise<unknown>;
+	/**
 	* Spawn a background process (async, returns request_id which is also process_id)
 	*/
 	spawnBackgroundProcess(command: string, args: string[], cwd?: string): ProcessHandle<BackgroundProcessResult>;
@@ -2712,6 +2829,7 @@ interface HookEventMap {
 	};
 	lsp_references: {
 		symbol: string;
+		kind: "references" | "implementation" | "type_definition" | "declaration";
 		locations: {
 			file: string;
 			line: number;
@@ -2724,6 +2842,13 @@ interface HookEventMap {
 		server_command: string;
 		params: string | null;
 	};
+	lsp_client_command: {
+		language: string;
+		server_name: string;
+		command: string;
+		title: string;
+		arguments: string | null;
+	};
 	lsp_server_error: {
 		language: string;
 		server_command: string;
@@ -2736,6 +2861,33 @@ interface HookEventMap {
 		missing_servers: string[];
 		user_dismissed: boolean;
 	};
+	// ── Debugger ─────────────────────────────────────────────────────────────
+	debug_started: {
+		session_id: number;
+		configuration: string;
+		adapter: string;
+	};
+	debug_stopped: {
+		session_id: number;
+		reason: string;
+		thread_id: number | null;
+		frame_id: number | null;
+		file: string | null;
+		line: number | null;
+	};
+	debug_continued: {
+		session_id: number;
+		thread_id: number | null;
+	};
+	debug_output: {
+		session_id: number;
+		category: string;
+		output: string;
+	};
+	debug_ended: {
+		session_id: number;
+		error: string | null;
+	};
 	// ── UI events ────────────────────────────────────────────────────────────
 	action_popup_result: {
 		popup_id: string;
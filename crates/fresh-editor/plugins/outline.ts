@@ -0,0 +1,211 @@
+/// <reference path="./lib/fresh.d.ts" />
+
+/**
+ * Outline Panel Plugin
+ *
+ * Shows the symbols of the active buffer as an indented tree in the
+ * Utility Dock. Symbols come from `editor.getDocumentSymbols`, which asks
+ * the language server (`textDocument/documentSymbol`) and falls back to
+ * tree-sitter tags queries when no server is attached.
+ *
+ * Key features:
+ * - livePanel mode, refreshed on buffer switch and (debounced) on edits
+ * - Follows the cursor: the innermost symbol around it is highlighted
+ * - Enter jumps to the symbol in the source split
+ */
+
+import { Finder, createLiveProvider, type FinderProvider } from "./lib/finder.ts";
+
+const editor = getEditor();
+
+const REFRESH_DEBOUNCE_MS = 300;
+
+// State
+let sourceBufferId: number | null = null;
+let sourceSplitId: number | null = null;
+let symbols: DocumentSymbolInfo[] = [];
+let isOpen = false;
+let refreshToken = 0;
+
+const provider = createLiveProvider(() => symbols);
+
+const finder = new Finder<DocumentSymbolInfo>(editor, {
+  id: "outline",
+  format: (s) => ({
+    label: `${"  ".repeat(s.depth)}${s.name}`,
+    description: s.detail ? `${s.kind}  ${s.detail}` : s.kind,
+  }),
+  groupBy: "none",
+  useUtilityDock: true,
+  onSelect: (s) => {
+    if (sourceBufferId === null) return;
+    if (sourceSplitId !== null) {
+      editor.focusSplit(sourceSplitId);
+    }
+    editor.setBufferCursor(sourceBufferId, s.nameOffset);
+  },
+  onClose: () => {
+    isOpen = false;
+    sourceBufferId = null;
+    sourceSplitId = null;
+    symbols = [];
+  },
+});
+
+function getTitle(): string {
+  const path = sourceBufferId !== null ? editor.getBufferPath(sourceBufferId) : "";
+  const file = path ? path.split(/[\\/]/).pop() ?? path : "";
+  return editor.t("panel.header", { file });
+}
+
+// Index of the innermost symbol whose range contains `offset`
+function symbolAt(offset: number): number {
+  let best = -1;
+  for (let i = 0; i < symbols.length; i++) {
+    const s = symbols[i];
+    if (s.start <= offset && offset <= s.end) {
+      if (best < 0 || s.depth > symbols[best].depth) {
+        best = i;
+      }
+    }
+  }
+  return best;
+}
+
+function revealCursorSymbol(offset: number): void {
+  const index = symbolAt(offset);
+  if (index >= 0) {
+    finder.reveal(index);
+  }
+}
+
+async function refresh(): Promise<void> {
+  if (!isOpen || sourceBufferId === null) return;
+  const bufferId = sourceBufferId;
+  const token = ++refreshToken;
+
+  const result = await editor.getDocumentSymbols(bufferId);
+  // Drop answers for a buffer we have since switched away from
+  if (!isOpen || token !== refreshToken) return;
+
+  symbols = result;
+  finder.updateTitle(getTitle());
+  provider.notify();
+  if (editor.getActiveBufferId() === bufferId) {
+    revealCursorSymbol(editor.getCursorPosition());
+  }
+}
+
+async function scheduleRefresh(): Promise<void> {
+  const token = ++refreshToken;
+  await editor.delay(REFRESH_DEBOUNCE_MS);
+  if (token !== refreshToken) return;
+  await refresh();
+}
+
+// Commands
+async function show_outline_panel(): Promise<void> {
+  const bufferId = editor.getActiveBufferId();
+  if (!editor.getBufferPath(bufferId)) {
+    editor.setStatus(editor.t("status.no_file"));
+    return;
+  }
+
+  sourceBufferId = bufferId;
+  sourceSplitId = editor.getActiveSplitId();
+
+  if (isOpen) {
+    await refresh();
+    return;
+  }
+
+  await finder.livePanel({
+    title: getTitle(),
+    provider: provider as FinderProvider<DocumentSymbolInfo>,
+    ratio: 0.3,
+  });
+  isOpen = true;
+  await refresh();
+}
+registerHandler("show_outline_panel", show_outline_panel);
+
+function outline_close(): void {
+  finder.close();
+  isOpen = false;
+  sourceBufferId = null;
+  sourceSplitId = null;
+  symbols = [];
+  editor.setStatus(editor.t("status.closed"));
+}
+registerHandler("outline_close", outline_close);
+
+function toggle_outline_panel(): void {
+  if (isOpen) {
+    outline_close();
+  } else {
+    show_outline_panel();
+  }
+}
+registerHandler("toggle_outline_panel", toggle_outline_panel);
+
+// Event Handlers
+editor.on("buffer_activated", (data) => {
+  if (!isOpen) return;
+
+  // Skip virtual buffers (e.g. the outline panel itself)
+  if (!editor.getBufferPath(data.buffer_id)) return;
+  if (data.buffer_id === sourceBufferId) return;
+
+  sourceBufferId = data.buffer_id;
+  sourceSplitId = editor.getActiveSplitId();
+  refresh();
+});
+
+editor.on("after_insert", (data) => {
+  if (isOpen && data.buffer_id === sourceBufferId) {
+    scheduleRefresh();
+  }
+});
+
+editor.on("after_delete", (data) => {
+  if (isOpen && data.buffer_id === sourceBufferId) {
+    scheduleRefresh();
+  }
+});
+
+editor.on("after_file_save", (data) => {
+  if (isOpen && data.buffer_id === sourceBufferId) {
+    refresh();
+  }
+});
+
+editor.on("cursor_moved", (data) => {
+  if (isOpen && data.buffer_id === sourceBufferId) {
+    revealCursorSymbol(data.new_position);
+  }
+});
+
+editor.on("buffer_closed", (data) => {
+  if (isOpen && data.buffer_id === sourceBufferId) {
+    sourceBufferId = null;
+    symbols = [];
+    provider.notify();
+  }
+});
+
+// Command Registration
+editor.registerCommand(
+  "%cmd.show_outline_panel",
+  "%cmd.show_outline_panel_desc",
+  "show_outline_panel",
+  null
+);
+
+editor.registerCommand(
+  "%cmd.toggle_outline_panel",
+  "%cmd.toggle_outline_panel_desc",
+  "toggle_outline_panel",
+  null
+);
+
+editor.debug("Outline plugin initialized (using Finder abstraction)");
@@ -171,6 +171,16 @@ interface PackageManifest {
     languages?: BundleLanguage[];
     /** Plugins included in this bundle */
     plugins?: BundlePlugin[];
+
+    /** Capabilities the package's plugins need; asked on first use */
+    permissions?: {
+      filesystem?: { read?: string[]; write?: string[] };
+      process?: string[];
+      authority?: boolean;
+      network?: boolean;
+      clipboard?: boolean;
+      env?: boolean;
+    };
   };
   keywords?: string[];
 }
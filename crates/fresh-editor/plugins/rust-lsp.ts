@@ -14,6 +14,7 @@ const editor = getEditor();
  * - Shows popup with install commands (rustup, brew)
  * - Allows copying install commands to clipboard
  * - Provides option to disable Rust LSP
+ * - Runs rust-analyzer's "Run Test" code lenses in a terminal
  */
 
 interface LspServerErrorData {
@@ -249,4 +250,69 @@ editor.on("action_popup_result", (data) => {
   }
 });
 
+/**
+ * rust-analyzer's "Run Test" / "Run" code lenses carry a client-side
+ * command (`rust-analyzer.runSingle`) that the editor hands to plugins.
+ * The first argument is a runnable: `{ label, kind: "cargo", args: {
+ * cwd, workspaceRoot, cargoArgs, executableArgs } }`.
+ */
+interface CargoRunnable {
+  label: string;
+  kind: string;
+  args: {
+    cwd?: string;
+    workspaceRoot?: string;
+    cargoArgs?: string[];
+    executableArgs?: string[];
+    environment?: Record<string, string>;
+  };
+}
+
+function shellQuote(arg: string): string {
+  return /^[A-Za-z0-9_\-.,:\/=+@]+$/.test(arg) ? arg : `'${arg.replace(/'/g, `'\\''`)}'`;
+}
+
+function cargoCommandLine(runnable: CargoRunnable): string {
+  const args = runnable.args;
+  const parts = ["cargo", ...(args.cargoArgs ?? [])].map(shellQuote);
+  if (args.executableArgs && args.executableArgs.length > 0) {
+    parts.push("--", ...args.executableArgs.map(shellQuote));
+  }
+  const env = Object.entries(args.environment ?? {}).map(
+    ([key, value]) => `${key}=${shellQuote(value)}`
+  );
+  return [...env, ...parts].join(" ");
+}
+
+editor.on("lsp_client_command", async (data) => {
+  if (data.language !== "rust") {
+    return;
+  }
+
+  switch (data.command) {
+    case "rust-analyzer.runSingle": {
+      let runnable: CargoRunnable | undefined;
+      try {
+        runnable = JSON.parse(data.arguments ?? "[]")[0];
+      } catch (e) {
+        editor.debug(`rust-lsp: Bad runnable arguments: ${e}`);
+      }
+      if (!runnable || runnable.kind !== "cargo") {
+        editor.setStatus(`Rust LSP: Cannot run "${data.title}"`);
+        return;
+      }
+      const term = await editor.createTerminal({
+        cwd: runnable.args.cwd ?? runnable.args.workspaceRoot,
+        direction: "horizontal",
+      });
+      editor.sendTerminalInput(term.terminalId, cargoCommandLine(runnable) + "\n");
+      break;
+    }
+
+    case "rust-analyzer.debugSingle":
+      editor.setStatus("Rust LSP: Debugging from a code lens is not supported yet");
+      break;
+  }
+});
+
 editor.debug("rust-lsp: Plugin loaded");
@@ -0,0 +1,31 @@
+# Shell integration for the Fresh integrated terminal (bash 4.4+).
+#
+# Marks prompts, commands and their exit status (OSC 133) and reports the
+# working directory (OSC 7). Add to ~/.bashrc:
+#
+#   [ -n "$FRESH_SHELL_INTEGRATION_DIR" ] && . "$FRESH_SHELL_INTEGRATION_DIR/fresh.bash"
+
+if [ -n "$__fresh_integration" ] || [[ $- != *i* ]]; then
+    return
+fi
+__fresh_integration=1
+__fresh_prompted=
+
+__fresh_prompt_command() {
+    local status=$?
+    if [ -n "$__fresh_prompted" ]; then
+        printf '\033]133;D;%s\007' "$status"
+    fi
+    __fresh_prompted=1
+    local dir=${PWD//%/%25}
+    printf '\033]7;file://%s%s\007' "$HOSTNAME" "${dir// /%20}"
+    printf '\033]133;A\007'
+    # Prompt themes may rebuild PS1 on every prompt
+    case "$PS1" in
+        *'133;B'*) ;;
+        *) PS1="$PS1"'\[\033]133;B\007\]' ;;
+    esac
+}
+
+PROMPT_COMMAND="__fresh_prompt_command${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+PS0="${PS0}"$'\033]133;C\007'
@@ -0,0 +1,31 @@
+# Shell integration for the Fresh integrated terminal (fish 3+).
+#
+# Marks prompts, commands and their exit status (OSC 133) and reports the
+# working directory (OSC 7). Add to ~/.config/fish/config.fish:
+#
+#   set -q FRESH_SHELL_INTEGRATION_DIR; and source "$FRESH_SHELL_INTEGRATION_DIR/fresh.fish"
+
+if not status is-interactive; or set -q __fresh_integration
+    return
+end
+set -g __fresh_integration 1
+
+function __fresh_prompt_start --on-event fish_prompt
+    set -l dir (string replace -a ' ' '%20' (string replace -a '%' '%25' $PWD))
+    printf '\e]7;file://%s%s\a' $hostname $dir
+    printf '\e]133;A\a'
+end
+
+function __fresh_preexec --on-event fish_preexec
+    printf '\e]133;C\a'
+end
+
+function __fresh_postexec --on-event fish_postexec
+    printf '\e]133;D;%s\a' $status
+end
+
+functions -c fish_prompt __fresh_original_fish_prompt
+function fish_prompt
+    __fresh_original_fish_prompt
+    printf '\e]133;B\a'
+end
@@ -84,6 +84,7 @@ impl Editor {
                     self.resend_did_open_for_language(&language);
                     self.request_semantic_tokens_for_language(&language);
                     self.request_folding_ranges_for_language(&language);
+                    self.request_code_lens_for_language(&language);
                     // Now that capabilities are known, kick off inlay hints
                     // and pull-diagnostics for buffers that opened before the
                     // `initialize` handshake completed. Both paths route
@@ -91,6 +92,9 @@ impl Editor {
                     // didn't advertise the capability are skipped.
                     self.request_inlay_hints_for_language(&language);
                     self.pull_diagnostics_for_language(&language);
+                    // Outlines computed from tree-sitter while the server
+                    // was starting can now be replaced by its symbols.
+                    self.invalidate_document_symbols_for_language(&language);
                 }
                 AsyncMessage::LspError {
                     language,
@@ -168,6 +172,36 @@ impl Editor {
                         tracing::error!("Error handling goto definition response: {}", e);
                     }
                 }
+                AsyncMessage::LspGotoLocations {
+                    request_id,
+                    kind,
+                    locations,
+                } => {
+                    self.handle_goto_locations_response(request_id, kind, locations);
+                }
+                AsyncMessage::LspHierarchy {
+                    request_id,
+                    server_name,
+                    entries,
+                } => {
+                    self.handle_hierarchy_response(request_id, &server_name, entries);
+                }
+                AsyncMessage::LspSelectionRanges { request_id, ranges } => {
+                    self.handle_selection_range_response(request_id, ranges);
+                }
+                AsyncMessage::LspDocumentSymbols {
+                    request_id,
+                    symbols,
+                } => {
+                    self.handle_document_symbols_response(request_id, symbols);
+                }
+                AsyncMessage::LspWorkspaceSymbols {
+                    request_id,
+                    server_name,
+                    symbols,
+                } => {
+                    self.handle_workspace_symbols_response(request_id, &server_name, symbols);
+                }
                 AsyncMessage::LspRename { request_id, result } => {
                     if let Err(e) = self.handle_rename_response(request_id, result) {
                         tracing::error!("Error handling rename response: {}", e);
@@ -277,6 +311,20 @@ impl Editor {
                 } => {
                     self.handle_lsp_folding_ranges(request_id, uri, ranges);
                 }
+                AsyncMessage::LspCodeLenses {
+                    request_id,
+                    uri,
+                    server_name,
+                    lenses,
+                } => {
+                    self.handle_lsp_code_lenses(request_id, uri, server_name, lenses);
+                }
+                AsyncMessage::LspCodeLensResolved { request_id, lens } => {
+                    self.handle_lsp_code_lens_resolved(request_id, lens);
+                }
+                AsyncMessage::LspCodeLensRefresh { language } => {
+                    self.handle_lsp_code_lens_refresh(language);
+                }
                 AsyncMessage::LspSemanticTokens {
                     request_id,
                     uri,
@@ -570,6 +618,42 @@ impl Editor {
                 } => {
                     self.handle_plugin_lsp_response(request_id, result);
                 }
+                AsyncMessage::DapResponse {
+                    session_id,
+                    request_seq,
+                    command,
+                    success,
+                    message,
+                    body,
+                } => {
+                    self.handle_dap_response(
+                        session_id,
+                        request_seq,
+                        command,
+                        success,
+                        message,
+                        body,
+                    );
+                }
+                AsyncMessage::DapEvent {
+                    session_id,
+                    event,
+                    body,
+                } => {
+                    self.handle_dap_event(session_id, event, body);
+                }
+                AsyncMessage::DapExited { session_id, error } => {
+                    self.handle_dap_exited(session_id, error);
+                }
+                AsyncMessage::TaskFinished {
+                    run_id,
+                    exit_code,
+                    problems,
+                    output,
+                    error,
+                } => {
+                    self.handle_task_finished(run_id, exit_code, problems, output, error);
+                }
                 AsyncMessage::PluginProcessOutput {
                     process_id,
                     stdout,
@@ -887,8 +887,9 @@ impl Editor {
             }
         }
 
-        // Folding ranges may improve after project is fully loaded
+        // Folding ranges and code lenses may improve after project is fully loaded
         self.request_folding_ranges_for_language(&language);
+        self.request_code_lens_for_language(&language);
     }
 
     /// Handle workspace/diagnostic/refresh request from the LSP server.
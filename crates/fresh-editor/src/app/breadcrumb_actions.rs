@@ -0,0 +1,287 @@
+//! Breadcrumb bar orchestrators.
+//!
+//! Every frame the bar of each split is rebuilt from its buffer's file path
+//! and the cached outline (`document_symbols.rs`) around the split's
+//! cursor. A segment's dropdown is a list popup anchored under it: the
+//! entries of the parent directory for a path segment, the symbols sharing
+//! the container for a symbol segment. Left and Right in a dropdown move to
+//! the neighbouring segment's.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use rust_i18n::t;
+
+use crate::model::event::{BufferId, Event, LeafId};
+use crate::primitives::document_symbols::symbol_kind_name;
+use crate::view::popup::{Popup, PopupKind, PopupListItem, PopupPosition, PopupResolver};
+use crate::view::ui::breadcrumbs::{
+    enclosing_symbols, path_segments, sibling_symbols, symbol_label, BreadcrumbBar,
+    BreadcrumbSegment, BreadcrumbTarget,
+};
+
+use super::Editor;
+
+const DROPDOWN_WIDTH: u16 = 40;
+const DROPDOWN_MAX_HEIGHT: u16 = 15;
+
+impl Editor {
+    /// Breadcrumb bars of the active window's splits. The active split's
+    /// marks the segment whose dropdown is open.
+    pub(crate) fn breadcrumb_bars(&self) -> HashMap<LeafId, BreadcrumbBar> {
+        let focused = match self.active_state().popups.top().map(|p| &p.resolver) {
+            Some(PopupResolver::Breadcrumb { segment }) => Some(*segment),
+            _ => None,
+        };
+        let active_split = self.split_manager().active_split();
+        self.split_view_states()
+            .iter()
+            .filter_map(|(&split_id, view_state)| {
+                let buffer_id = self.split_manager().get_buffer_id(split_id.into())?;
+                let cursor = view_state.cursors.primary().position;
+                let mut bar = self.breadcrumb_bar(buffer_id, cursor);
+                if split_id == active_split {
+                    bar.focused = focused;
+                }
+                Some((split_id, bar))
+            })
+            .collect()
+    }
+
+    /// Segments of the bar of a split showing `buffer_id` with its cursor
+    /// at `cursor`.
+    fn breadcrumb_bar(&self, buffer_id: BufferId, cursor: usize) -> BreadcrumbBar {
+        let window = self.active_window();
+        let mut segments = match window.buffer_metadata.get(&buffer_id) {
+            Some(metadata) => match metadata.file_path() {
+                Some(path) => path_segments(path, &self.working_dir),
+                None => vec![BreadcrumbSegment {
+                    label: metadata.display_name.clone(),
+                    target: BreadcrumbTarget::Label,
+                }],
+            },
+            None => Vec::new(),
+        };
+        if let (Some(state), Some(outline)) = (
+            window.buffers.get(&buffer_id),
+            window.document_symbols.cached(buffer_id),
+        ) {
+            // An outdated outline may reach past the end of the buffer
+            let len = state.buffer.len();
+            segments.extend(
+                enclosing_symbols(&outline.symbols, cursor)
+                    .into_iter()
+                    .filter(|&index| outline.symbols[index].range.end <= len)
+                    .map(|index| BreadcrumbSegment {
+                        label: symbol_label(&state.buffer, &outline.symbols[index]),
+                        target: BreadcrumbTarget::Symbol(index),
+                    }),
+            );
+        }
+        BreadcrumbBar {
+            segments,
+            focused: None,
+        }
+    }
+
+    /// Bar of the active split, whether or not it is shown.
+    fn active_breadcrumb_bar(&self) -> BreadcrumbBar {
+        let cursor = self.active_cursors().primary().position;
+        self.breadcrumb_bar(self.active_buffer(), cursor)
+    }
+
+    /// Open the dropdown of the innermost segment of the active split's
+    /// bar.
+    pub(crate) fn focus_breadcrumbs(&mut self) {
+        let buffer_id = self.active_buffer();
+        self.refresh_document_symbols(buffer_id);
+        let bar = self.active_breadcrumb_bar();
+        match bar
+            .segments
+            .iter()
+            .rposition(|segment| segment.target != BreadcrumbTarget::Label)
+        {
+            Some(index) => self.open_breadcrumb_dropdown(index),
+            None => self.set_status_message(t!("breadcrumbs.none").to_string()),
+        }
+    }
+
+    /// Open the dropdown of segment `index` of the active split's bar.
+    pub(crate) fn open_breadcrumb_dropdown(&mut self, index: usize) {
+        let bar = self.active_breadcrumb_bar();
+        match bar.segments.get(index).map(|segment| &segment.target) {
+            Some(BreadcrumbTarget::Path(path)) => {
+                if let Some(parent) = path.parent() {
+                    self.show_directory_dropdown(index, parent, Some(path));
+                }
+            }
+            Some(BreadcrumbTarget::Symbol(symbol)) => self.show_symbol_dropdown(index, *symbol),
+            Some(BreadcrumbTarget::Label) | None => {}
+        }
+    }
+
+    /// Move from the open dropdown to the one of the segment `delta` places
+    /// away, skipping segments without a dropdown.
+    pub(crate) fn step_breadcrumb(&mut self, delta: isize) {
+        let Some(PopupResolver::Breadcrumb { segment }) =
+            self.active_state().popups.top().map(|p| p.resolver.clone())
+        else {
+            return;
+        };
+        let bar = self.active_breadcrumb_bar();
+        let mut index = segment as isize + delta;
+        while let Some(target) = usize::try_from(index)
+            .ok()
+            .and_then(|i| bar.segments.get(i))
+            .map(|segment| &segment.target)
+        {
+            if *target != BreadcrumbTarget::Label {
+                self.open_breadcrumb_dropdown(index as usize);
+                return;
+            }
+            index += delta;
+        }
+    }
+
+    /// Act on the entry chosen in the dropdown of segment `segment`:
+    /// list a directory, open a file or jump to a symbol.
+    pub(crate) fn confirm_breadcrumb(&mut self, segment: usize, data: &str) {
+        let bar = self.active_breadcrumb_bar();
+        match bar.segments.get(segment).map(|segment| &segment.target) {
+            Some(BreadcrumbTarget::Path(_)) => {
+                let path = PathBuf::from(data);
+                if self.authority.filesystem.is_dir(&path).unwrap_or(false) {
+                    self.show_directory_dropdown(segment, &path, None);
+                } else {
+                    self.open_file_with_jump(path, None, None);
+                }
+            }
+            Some(BreadcrumbTarget::Symbol(_)) => {
+                if let Ok(offset) = data.parse::<usize>() {
+                    self.goto_byte_offset(offset);
+                    self.apply_event_to_active_buffer(&Event::Recenter);
+                }
+            }
+            Some(BreadcrumbTarget::Label) | None => {}
+        }
+    }
+
+    /// List the entries of `dir`, directories first, with `current`
+    /// selected.
+    fn show_directory_dropdown(&mut self, segment: usize, dir: &Path, current: Option<&Path>) {
+        let mut entries = match self.authority.filesystem.read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                self.set_status_message(
+                    t!(
+                        "breadcrumbs.read_dir_failed",
+                        path = dir.display().to_string(),
+                        error = e.to_string()
+                    )
+                    .to_string(),
+                );
+                return;
+            }
+        };
+        // Hidden entries are left out, unless one is what the segment shows
+        entries
+            .retain(|entry| !entry.name.starts_with('.') || Some(entry.path.as_path()) == current);
+        if entries.is_empty() {
+            self.set_status_message(
+                t!(
+                    "breadcrumbs.empty_directory",
+                    path = dir.display().to_string()
+                )
+                .to_string(),
+            );
+            return;
+        }
+        entries.sort_by(|a, b| {
+            b.is_dir()
+                .cmp(&a.is_dir())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        let selected = current
+            .and_then(|current| entries.iter().position(|entry| entry.path == current))
+            .unwrap_or(0);
+        let items = entries
+            .into_iter()
+            .map(|entry| PopupListItem {
+                text: if entry.is_dir() {
+                    format!("{}/", entry.name)
+                } else {
+                    entry.name
+                },
+                detail: None,
+                icon: None,
+                data: Some(entry.path.to_string_lossy().into_owned()),
+                disabled: false,
+            })
+            .collect();
+        self.show_breadcrumb_popup(segment, items, selected);
+    }
+
+    /// List the symbols sharing the container of outline symbol `symbol`.
+    fn show_symbol_dropdown(&mut self, segment: usize, symbol: usize) {
+        let buffer_id = self.active_buffer();
+        let Some(symbols) = self
+            .active_window()
+            .document_symbols
+            .cached(buffer_id)
+            .map(|outline| outline.symbols.clone())
+        else {
+            return;
+        };
+        let siblings = sibling_symbols(&symbols, symbol);
+        let selected = siblings.iter().position(|&i| i == symbol).unwrap_or(0);
+        let items = siblings
+            .into_iter()
+            .map(|i| PopupListItem {
+                text: symbols[i].name.clone(),
+                detail: Some(symbol_kind_name(symbols[i].kind).to_string()),
+                icon: None,
+                data: Some(symbols[i].selection_range.start.to_string()),
+                disabled: false,
+            })
+            .collect();
+        self.show_breadcrumb_popup(segment, items, selected);
+    }
+
+    /// Show a dropdown under segment `segment` of the active split's bar,
+    /// or under the cursor when the bar is hidden.
+    fn show_breadcrumb_popup(
+        &mut self,
+        segment: usize,
+        items: Vec<PopupListItem>,
+        selected: usize,
+    ) {
+        let active_split = self.split_manager().active_split();
+        let anchor = self
+            .active_layout()
+            .breadcrumb_layouts
+            .get(&active_split)
+            .map(|layout| layout.segment_area(segment).unwrap_or(layout.bar_area));
+
+        let theme = self.theme.read().unwrap();
+        let mut popup = Popup::list(items, &theme);
+        popup.kind = PopupKind::Action;
+        popup.position = match anchor {
+            Some(area) => PopupPosition::Fixed {
+                x: area.x,
+                y: area.y + 1,
+            },
+            None => PopupPosition::BelowCursor,
+        };
+        popup.width = DROPDOWN_WIDTH;
+        popup.max_height = DROPDOWN_MAX_HEIGHT;
+        popup.border_style = ratatui::style::Style::default().fg(theme.popup_border_fg);
+        popup.background_style = ratatui::style::Style::default().bg(theme.popup_bg);
+        // Selected row's `data` is a path or a byte offset, by segment kind
+        popup.resolver = PopupResolver::Breadcrumb { segment };
+        popup.focused = true;
+        popup.select_index(selected);
+        drop(theme);
+
+        self.active_state_mut().popups.show_or_replace(popup);
+    }
+}
@@ -57,6 +57,7 @@ impl Editor {
 
         // Save file state before closing (for per-file session persistence)
         self.active_window().save_file_state_on_close(id);
+        self.save_undo_history(id);
 
         // Delete recovery data for explicitly closed buffers (including unnamed)
         if let Err(e) = self.delete_buffer_recovery(id) {
@@ -305,6 +306,7 @@ impl Editor {
                 .set_pane_buffer(split_id, replacement_buffer);
         }
 
+        self.remember_breakpoints(id);
         self.windows
             .get_mut(&self.active_window)
             .map(|w| &mut w.buffers)
@@ -341,6 +343,9 @@ impl Editor {
         self.active_window_mut()
             .semantic_tokens_full_debounce
             .remove(&id);
+        self.forget_document_symbols(id);
+        self.active_window_mut().code_lens.remove_buffer(id);
+        self.active_window_mut().spell_check.remove_buffer(id);
 
         // Remove buffer from the active window's panel_ids mapping
         // if it was a panel buffer. Prevents stale entries when the
@@ -137,6 +137,39 @@ pub(crate) fn screen_to_buffer_position(
     Some(position)
 }
 
+/// Locate a click on a virtual row drawn directly above a source row, such
+/// as a code lens.
+///
+/// Returns the first source byte of the row below (the virtual line's
+/// anchor line) and the char index clicked within the virtual row, or
+/// `None` for gutter clicks and clicks anywhere else.
+pub(crate) fn virtual_row_above_source_hit(
+    col: u16,
+    row: u16,
+    content_rect: Rect,
+    gutter_width: u16,
+    cached_mappings: &Option<Vec<ViewLineMapping>>,
+    compose_width: Option<u16>,
+) -> Option<(usize, usize)> {
+    let content_rect = adjust_content_rect_for_compose(content_rect, compose_width);
+    let content_col = col.checked_sub(content_rect.x)?;
+    let text_col = content_col.checked_sub(gutter_width)? as usize;
+    let visual_row = row.checked_sub(content_rect.y)? as usize;
+
+    let mappings = cached_mappings.as_ref()?;
+    let clicked = mappings.get(visual_row)?;
+    if clicked.char_source_bytes.iter().any(Option::is_some) {
+        return None;
+    }
+    let anchor_byte = mappings
+        .get(visual_row + 1)?
+        .char_source_bytes
+        .iter()
+        .find_map(|byte| *byte)?;
+    let char_index = *clicked.visual_to_char.get(text_col)?;
+    Some((anchor_byte, char_index))
+}
+
 /// Check whether a gutter click at `target_position` should toggle a fold.
 ///
 /// Returns `Some(target_position)` (the byte to fold at) or `None` when the
@@ -250,6 +283,57 @@ mod tests {
         assert_eq!(pos, Some(42));
     }
 
+    fn virtual_row(len: usize) -> ViewLineMapping {
+        ViewLineMapping {
+            char_source_bytes: vec![None; len],
+            visual_to_char: (0..len).collect(),
+            line_end_byte: 0,
+        }
+    }
+
+    fn source_row(start: usize, len: usize) -> ViewLineMapping {
+        ViewLineMapping {
+            char_source_bytes: (start..start + len).map(Some).collect(),
+            visual_to_char: (0..len).collect(),
+            line_end_byte: start + len - 1,
+        }
+    }
+
+    #[test]
+    fn virtual_row_hit_reports_anchor_and_char_index() {
+        let r = Rect::new(0, 0, 100, 20);
+        let mappings = Some(vec![source_row(0, 5), virtual_row(10), source_row(5, 8)]);
+        // Gutter is 3 wide, so column 7 is char 4 of the virtual row.
+        assert_eq!(
+            virtual_row_above_source_hit(7, 1, r, 3, &mappings, None),
+            Some((5, 4))
+        );
+    }
+
+    #[test]
+    fn virtual_row_hit_ignores_source_rows_gutter_and_past_end() {
+        let r = Rect::new(0, 0, 100, 20);
+        let mappings = Some(vec![source_row(0, 5), virtual_row(10), source_row(5, 8)]);
+        assert_eq!(
+            virtual_row_above_source_hit(4, 0, r, 3, &mappings, None),
+            None
+        );
+        assert_eq!(
+            virtual_row_above_source_hit(1, 1, r, 3, &mappings, None),
+            None
+        );
+        assert_eq!(
+            virtual_row_above_source_hit(20, 1, r, 3, &mappings, None),
+            None
+        );
+        // Last row has nothing below it
+        let trailing = Some(vec![source_row(0, 5), virtual_row(10)]);
+        assert_eq!(
+            virtual_row_above_source_hit(4, 1, r, 3, &trailing, None),
+            None
+        );
+    }
+
     #[test]
     fn screen_to_buffer_position_rejects_gutter_click_when_not_allowed() {
         let r = Rect::new(0, 0, 100, 20);
@@ -351,7 +351,7 @@ impl Editor {
             .and_then(|vs| vs.compose_width);
 
         // Calculate clicked position in buffer
-        let (toggle_fold_byte, onclick_action, target_position, cursor_snapshot) =
+        let (gutter_hit, code_lens_hit, onclick_action, target_position, cursor_snapshot) =
             if let Some(state) = self
                 .windows
                 .get(&self.active_window)
@@ -400,6 +400,24 @@ impl Editor {
                     gutter_width,
                 );
 
+                // A click in the indicator column of a file with a debug
+                // adapter toggles a breakpoint
+                let breakpoint_line = (content_col == 0
+                    && state.buffer.file_path().is_some()
+                    && self.config.debug.has_adapter_for_language(&state.language))
+                .then(|| state.buffer.get_line_number(target_position));
+
+                // A click on a code lens line runs the lens instead of
+                // moving the cursor
+                let code_lens_hit = super::click_geometry::virtual_row_above_source_hit(
+                    col,
+                    row,
+                    content_rect,
+                    gutter_width,
+                    &cached_mappings,
+                    compose_width,
+                );
+
                 let cursor_snapshot = self
                     .windows
                     .get(&self.active_window)
@@ -432,7 +450,8 @@ impl Editor {
                     });
 
                 (
-                    toggle_fold_byte,
+                    (toggle_fold_byte, breakpoint_line),
+                    code_lens_hit,
                     onclick_action,
                     target_position,
                     cursor_snapshot,
@@ -441,12 +460,28 @@ impl Editor {
                 return Ok(());
             };
 
+        // Folding keeps the click on foldable lines unless there is a
+        // breakpoint to remove
+        let (toggle_fold_byte, breakpoint_line) = gutter_hit;
+        if let Some(line) = breakpoint_line {
+            if toggle_fold_byte.is_none() || self.has_breakpoint(buffer_id, line) {
+                self.debug_toggle_breakpoint(buffer_id, line);
+                return Ok(());
+            }
+        }
+
         if toggle_fold_byte.is_some() {
             self.active_window_mut()
                 .toggle_fold_at_byte(buffer_id, target_position);
             return Ok(());
         }
 
+        if let Some((anchor_byte, column)) = code_lens_hit {
+            if self.run_code_lens_at_click(buffer_id, anchor_byte, column) {
+                return Ok(());
+            }
+        }
+
         let (primary_cursor_id, old_position, old_anchor, old_sticky_column, deselect_on_move) =
             cursor_snapshot;
 
@@ -464,6 +499,13 @@ impl Editor {
             return Ok(());
         }
 
+        // Ctrl+click on a link in terminal scrollback opens it
+        if modifiers.contains(KeyModifiers::CONTROL)
+            && self.try_open_terminal_link_at_position(buffer_id, target_position)
+        {
+            return Ok(());
+        }
+
         // Move cursor to clicked position (respect shift for selection)
         // Both modifiers supported since some terminals intercept shift+click.
         let extend_selection =
@@ -503,6 +545,23 @@ impl Editor {
         self.active_window_mut().mouse_state.drag_selection_anchor =
             Some(new_anchor.unwrap_or(target_position));
 
+        // Alt+drag selects a block from the clicked cell; any other click
+        // leaves block mode
+        let block_anchor = if modifiers.contains(KeyModifiers::ALT) && !extend_selection {
+            self.block_position_at(split_id, buffer_id, content_rect, col, row)
+        } else {
+            None
+        };
+        self.active_window_mut().mouse_state.drag_block_anchor = block_anchor;
+        if let Some(view_state) = self
+            .active_window_mut()
+            .splits
+            .as_mut()
+            .and_then(|(_, vs)| vs.get_mut(&split_id))
+        {
+            view_state.cursors.primary_mut().clear_block_selection();
+        }
+
         Ok(())
     }
 
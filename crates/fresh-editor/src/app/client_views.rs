@@ -0,0 +1,588 @@
+//! Per-client views for shared session daemons.
+//!
+//! When several clients attach to one session (`fresh -a`) they share
+//! buffers, undo history and LSP state, but each keeps its own active
+//! split, cursors, scroll positions and terminal size. The live split
+//! state always belongs to one client: the server calls
+//! [`Editor::switch_client_view`] before handling a client's input or
+//! rendering its frame, which parks the previous owner's view and
+//! installs the next one. Parked cursors follow every edit, and are
+//! drawn for the live client as labelled carets.
+//!
+//! Only split state is per client. Prompts, popups, menus and the
+//! status line belong to the window: a prompt one client opens is shown
+//! to, and answered by, whichever client is live next.
+
+use std::collections::HashMap;
+
+use fresh_core::{BufferId, WindowId};
+use ratatui::style::{Modifier, Style};
+
+use super::Editor;
+use crate::model::cursor::Cursors;
+use crate::model::event::{Event, LeafId};
+use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
+use crate::view::split::TabTarget;
+use crate::view::virtual_text::VirtualTextPosition;
+
+/// Identifier the server uses for an attached client.
+pub type ClientViewId = u64;
+
+/// Prefix of the virtual-text ids used for caret labels.
+const CARET_LABEL_PREFIX: &str = "client-caret:";
+
+/// Namespace for the overlays drawn under other clients' carets.
+fn client_caret_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("client-carets".to_string())
+}
+
+/// Cursors and scroll position of one buffer in one split.
+#[derive(Debug, Clone)]
+struct ParkedBufferView {
+    cursors: Cursors,
+    top_byte: usize,
+    top_view_line_offset: usize,
+    left_column: usize,
+}
+
+/// The view state a client keeps while another client is live.
+#[derive(Debug, Clone)]
+pub(crate) struct ClientView {
+    /// Label shown next to this client's carets.
+    label: String,
+    /// Terminal size of the client.
+    width: u16,
+    height: u16,
+    /// Window the split state below was captured from.
+    window: Option<WindowId>,
+    active_split: Option<LeafId>,
+    /// Buffer shown in each split (showing a buffer tab) when the view
+    /// was captured.
+    active_buffers: HashMap<LeafId, BufferId>,
+    buffers: HashMap<(LeafId, BufferId), ParkedBufferView>,
+}
+
+/// All attached clients' views.
+#[derive(Debug, Default)]
+pub(crate) struct ClientViews {
+    /// Client whose view is installed in the live split state.
+    live: Option<ClientViewId>,
+    views: HashMap<ClientViewId, ClientView>,
+    /// Carets drawn for the live client: (buffer, position, label).
+    drawn_carets: Vec<(BufferId, usize, String)>,
+}
+
+impl ClientView {
+    /// Where the client's primary view is: its active split, the buffer
+    /// shown there and that buffer's cursors.
+    fn focus(&self) -> Option<(BufferId, &Cursors)> {
+        let split = self.active_split?;
+        let buffer = *self.active_buffers.get(&split)?;
+        let view = self.buffers.get(&(split, buffer))?;
+        Some((buffer, &view.cursors))
+    }
+
+    /// Shift parked cursors and scroll positions of `buffer` for an edit.
+    fn adjust_for_event(&mut self, buffer: BufferId, event: &Event, buffer_len: usize) {
+        let adjustments: Vec<(usize, usize, usize)> = match event {
+            Event::Insert { position, text, .. } => vec![(*position, 0, text.len())],
+            Event::Delete { range, .. } => vec![(range.start, range.len(), 0)],
+            Event::Batch { events, .. } => events
+                .iter()
+                .filter_map(|e| match e {
+                    Event::Insert { position, text, .. } => Some((*position, 0, text.len())),
+                    Event::Delete { range, .. } => Some((range.start, range.len(), 0)),
+                    _ => None,
+                })
+                .collect(),
+            // Listed from the end of the buffer backwards, so an edit never
+            // shifts the positions the edits after it refer to.
+            Event::BulkEdit { edits, .. } => edits.clone(),
+            _ => Vec::new(),
+        };
+
+        for ((_, parked_buffer), view) in self.buffers.iter_mut() {
+            if *parked_buffer != buffer {
+                continue;
+            }
+            for &(edit_pos, old_len, new_len) in &adjustments {
+                view.cursors.adjust_for_edit(edit_pos, old_len, new_len);
+                if edit_pos < view.top_byte {
+                    let deleted = old_len.min(view.top_byte - edit_pos);
+                    view.top_byte = view.top_byte - deleted + new_len;
+                }
+            }
+            // A bulk edit restored without its edit list (from persisted
+            // history) can't be mapped; keep everything in range.
+            view.cursors.map(|cursor| {
+                cursor.position = cursor.position.min(buffer_len);
+                cursor.anchor = cursor.anchor.map(|a| a.min(buffer_len));
+            });
+            view.top_byte = view.top_byte.min(buffer_len);
+        }
+    }
+}
+
+impl Editor {
+    /// Register a client attached to this session. The first client owns
+    /// the live view; later clients start from a copy of it.
+    pub fn attach_client_view(&mut self, id: ClientViewId, label: String, width: u16, height: u16) {
+        let mut view = self.capture_client_view(label);
+        view.width = width;
+        view.height = height;
+        self.client_views.views.insert(id, view);
+        if self.client_views.live.is_none() {
+            self.client_views.live = Some(id);
+            if (self.terminal_width, self.terminal_height) != (width, height) {
+                self.resize(width, height);
+            }
+        }
+    }
+
+    /// Forget a client that left the session.
+    pub fn detach_client_view(&mut self, id: ClientViewId) {
+        self.client_views.views.remove(&id);
+        if self.client_views.live == Some(id) {
+            self.client_views.live = None;
+        }
+        self.refresh_client_carets();
+    }
+
+    /// Record a client's new terminal size. The live client's resize is
+    /// applied right away, including to terminal PTYs and plugins.
+    pub fn set_client_view_size(&mut self, id: ClientViewId, width: u16, height: u16) {
+        let Some(view) = self.client_views.views.get_mut(&id) else {
+            return;
+        };
+        view.width = width;
+        view.height = height;
+        if self.client_views.live == Some(id) {
+            self.resize(width, height);
+        }
+    }
+
+    /// Install `id`'s view in the live split state, parking the current
+    /// owner's. Returns true if the live view changed.
+    pub fn switch_client_view(&mut self, id: ClientViewId) -> bool {
+        if self.client_views.live == Some(id) || !self.client_views.views.contains_key(&id) {
+            return false;
+        }
+        if let Some(previous) = self.client_views.live {
+            if let Some(label) = self
+                .client_views
+                .views
+                .get(&previous)
+                .map(|v| v.label.clone())
+            {
+                let mut parked = self.capture_client_view(label);
+                let old = &self.client_views.views[&previous];
+                parked.width = old.width;
+                parked.height = old.height;
+                self.client_views.views.insert(previous, parked);
+            }
+        }
+
+        let view = self.client_views.views[&id].clone();
+        self.install_client_view(&view);
+        self.client_views.live = Some(id);
+        self.resize_client_view(view.width, view.height);
+        self.refresh_client_carets();
+        true
+    }
+
+    /// Shift the parked views of `buffer` after an edit to it.
+    pub(crate) fn adjust_client_views_for_event(&mut self, buffer: BufferId, event: &Event) {
+        if self.client_views.views.len() < 2 {
+            return;
+        }
+        let Some(buffer_len) = self.buffers().get(&buffer).map(|s| s.buffer.len()) else {
+            return;
+        };
+        let window = self.active_window;
+        let live = self.client_views.live;
+        for (id, view) in self.client_views.views.iter_mut() {
+            if Some(*id) != live && view.window == Some(window) {
+                view.adjust_for_event(buffer, event, buffer_len);
+            }
+        }
+    }
+
+    fn capture_client_view(&self, label: String) -> ClientView {
+        let window = self.active_window();
+        let mut view = ClientView {
+            label,
+            width: self.terminal_width,
+            height: self.terminal_height,
+            window: Some(window.id),
+            active_split: None,
+            active_buffers: HashMap::new(),
+            buffers: HashMap::new(),
+        };
+        let Some((mgr, view_states)) = window.splits.as_ref() else {
+            return view;
+        };
+        let active_split = mgr.active_split();
+        view.active_split = Some(active_split);
+        for (leaf, state) in view_states {
+            if state.active_group_tab.is_none() {
+                view.active_buffers.insert(*leaf, state.active_buffer);
+            }
+            for (buffer, buffer_view) in &state.keyed_states {
+                view.buffers.insert(
+                    (*leaf, *buffer),
+                    ParkedBufferView {
+                        cursors: buffer_view.cursors.clone(),
+                        top_byte: buffer_view.viewport.top_byte,
+                        top_view_line_offset: buffer_view.viewport.top_view_line_offset,
+                        left_column: buffer_view.viewport.left_column,
+                    },
+                );
+            }
+        }
+        view
+    }
+
+    fn install_client_view(&mut self, view: &ClientView) {
+        if view.window != Some(self.active_window) {
+            return;
+        }
+        let window = self.active_window_mut();
+        // Show the buffers the client had in front of it, where those are
+        // still open in the same split.
+        for (leaf, buffer) in &view.active_buffers {
+            let still_open = window
+                .splits
+                .as_ref()
+                .and_then(|(_, view_states)| view_states.get(leaf))
+                .is_some_and(|state| {
+                    state.active_group_tab.is_none()
+                        && state.active_buffer != *buffer
+                        && state.open_buffers.contains(&TabTarget::Buffer(*buffer))
+                });
+            if still_open && window.buffers.contains_key(buffer) {
+                window.set_pane_buffer(*leaf, *buffer);
+            }
+        }
+        let buffer_lens: HashMap<BufferId, usize> = window
+            .buffers
+            .iter()
+            .map(|(id, state)| (*id, state.buffer.len()))
+            .collect();
+        let Some((mgr, view_states)) = window.splits.as_mut() else {
+            return;
+        };
+        for ((leaf, buffer), parked) in &view.buffers {
+            let Some(state) = view_states.get_mut(leaf) else {
+                continue;
+            };
+            let Some(buffer_view) = state.keyed_states.get_mut(buffer) else {
+                continue;
+            };
+            let len = buffer_lens.get(buffer).copied().unwrap_or(0);
+            let mut cursors = parked.cursors.clone();
+            cursors.map(|cursor| {
+                cursor.position = cursor.position.min(len);
+                cursor.anchor = cursor.anchor.map(|a| a.min(len));
+            });
+            buffer_view.cursors = cursors;
+            buffer_view.viewport.top_byte = parked.top_byte.min(len);
+            buffer_view.viewport.top_view_line_offset = parked.top_view_line_offset;
+            buffer_view.viewport.left_column = parked.left_column;
+            state.invalidate_layout();
+        }
+        if let Some(split) = view.active_split {
+            if view_states.contains_key(&split) {
+                mgr.set_active_split(split);
+            }
+        }
+    }
+
+    /// Lay the active window out for a client's terminal size without
+    /// resizing terminal PTYs or notifying plugins; those follow the
+    /// size the live client last reported.
+    fn resize_client_view(&mut self, width: u16, height: u16) {
+        if self.terminal_width == width && self.terminal_height == height {
+            return;
+        }
+        self.terminal_width = width;
+        self.terminal_height = height;
+        let window = self.active_window_mut();
+        window.terminal_width = width;
+        window.terminal_height = height;
+        if let Some(view_states) = window.split_view_states_mut() {
+            for view_state in view_states.values_mut() {
+                view_state.viewport.resize(width, height);
+            }
+        }
+    }
+
+    /// Draw the other clients' primary carets in the live client's view.
+    fn refresh_client_carets(&mut self) {
+        let window = self.active_window;
+        let live = self.client_views.live;
+        let mut carets: Vec<(BufferId, usize, String)> = Vec::new();
+        for (id, view) in &self.client_views.views {
+            if Some(*id) == live || view.window != Some(window) {
+                continue;
+            }
+            if let Some((buffer, cursors)) = view.focus() {
+                carets.push((buffer, cursors.primary().position, view.label.clone()));
+            }
+        }
+        carets.sort_by(|a, b| (a.0 .0, a.1, &a.2).cmp(&(b.0 .0, b.1, &b.2)));
+        if carets == self.client_views.drawn_carets {
+            return;
+        }
+
+        let ns = client_caret_namespace();
+        let window = self.active_window_mut();
+        for state in window.buffers.values_mut() {
+            state.overlays.clear_namespace(&ns, &mut state.marker_list);
+            state
+                .virtual_texts
+                .remove_by_prefix(&mut state.marker_list, CARET_LABEL_PREFIX);
+        }
+        for (buffer, position, label) in &carets {
+            let Some(state) = window.buffers.get_mut(buffer) else {
+                continue;
+            };
+            let len = state.buffer.len();
+            let position = (*position).min(len);
+            let face = OverlayFace::ThemedStyle {
+                fallback_style: Style::default().add_modifier(Modifier::REVERSED),
+                fg_theme: None,
+                bg_theme: Some("editor.inactive_cursor".to_string()),
+            };
+            // At the end of the buffer there is no cell to highlight;
+            // the label alone marks the caret.
+            if position < len {
+                let overlay = Overlay::with_namespace(
+                    &mut state.marker_list,
+                    position..position + 1,
+                    face,
+                    ns.clone(),
+                )
+                .with_priority_value(20)
+                .with_theme_key("editor.inactive_cursor");
+                state.overlays.add(overlay);
+            }
+            state.virtual_texts.add_with_id_and_theme_keys(
+                &mut state.marker_list,
+                position,
+                label.clone(),
+                Style::default().add_modifier(Modifier::REVERSED),
+                Some("editor.bg".to_string()),
+                Some("editor.inactive_cursor".to_string()),
+                VirtualTextPosition::BeforeChar,
+                0,
+                format!("{}{}", CARET_LABEL_PREFIX, label),
+            );
+        }
+        self.client_views.drawn_carets = carets;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::cursor::Cursor;
+    use crate::model::event::{CursorId, SplitId};
+
+    fn parked(position: usize, top_byte: usize) -> ParkedBufferView {
+        let mut cursors = Cursors::new();
+        *cursors.primary_mut() = Cursor::new(position);
+        ParkedBufferView {
+            cursors,
+            top_byte,
+            top_view_line_offset: 0,
+            left_column: 0,
+        }
+    }
+
+    fn view_with(buffer: BufferId, entry: ParkedBufferView) -> ClientView {
+        let leaf = LeafId(SplitId(0));
+        ClientView {
+            label: "a".to_string(),
+            width: 80,
+            height: 24,
+            window: None,
+            active_split: Some(leaf),
+            active_buffers: HashMap::from([(leaf, buffer)]),
+            buffers: HashMap::from([((leaf, buffer), entry)]),
+        }
+    }
+
+    #[test]
+    fn parked_cursor_follows_edits_before_it() {
+        let buffer = BufferId(1);
+        let mut view = view_with(buffer, parked(10, 4));
+        let insert = Event::Insert {
+            position: 2,
+            text: "abc".to_string(),
+            cursor_id: CursorId(0),
+        };
+        view.adjust_for_event(buffer, &insert, 100);
+        let (_, cursors) = view.focus().unwrap();
+        assert_eq!(cursors.primary().position, 13);
+        assert_eq!(view.buffers.values().next().unwrap().top_byte, 7);
+
+        // Edits after the caret leave it alone, and other buffers are
+        // not touched.
+        let later = Event::Insert {
+            position: 50,
+            text: "x".to_string(),
+            cursor_id: CursorId(0),
+        };
+        view.adjust_for_event(buffer, &later, 100);
+        view.adjust_for_event(BufferId(2), &insert, 100);
+        let (_, cursors) = view.focus().unwrap();
+        assert_eq!(cursors.primary().position, 13);
+    }
+
+    #[test]
+    fn parked_scroll_clamps_to_a_deletion_covering_it() {
+        let buffer = BufferId(1);
+        let mut view = view_with(buffer, parked(20, 10));
+        let delete = Event::Delete {
+            range: 5..15,
+            deleted_text: "x".repeat(10),
+            cursor_id: CursorId(0),
+        };
+        view.adjust_for_event(buffer, &delete, 100);
+        let entry = view.buffers.values().next().unwrap();
+        assert_eq!(entry.top_byte, 5);
+        assert_eq!(entry.cursors.primary().position, 10);
+    }
+
+    #[test]
+    fn parked_cursors_follow_a_bulk_edit() {
+        let buffer = BufferId(1);
+        let mut entry = parked(30, 0);
+        let secondary = entry.cursors.add(Cursor::with_selection(10, 14));
+        let mut view = view_with(buffer, entry);
+        let bulk = Event::BulkEdit {
+            old_snapshot: None,
+            new_snapshot: None,
+            old_cursors: Vec::new(),
+            new_cursors: Vec::new(),
+            description: "Replace all".to_string(),
+            edits: vec![(20, 2, 5), (12, 0, 1), (0, 3, 0)],
+            displaced_markers: Vec::new(),
+        };
+        view.adjust_for_event(buffer, &bulk, 100);
+        let (_, cursors) = view.focus().unwrap();
+        assert_eq!(cursors.count(), 2);
+        assert_eq!(cursors.primary().position, 31);
+        let secondary = cursors.get(secondary).unwrap();
+        assert_eq!(secondary.anchor, Some(7));
+        assert_eq!(secondary.position, 12);
+    }
+
+    #[test]
+    fn clients_keep_their_own_cursor_and_size() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut editor = Editor::new(
+            crate::config::Config::default(),
+            80,
+            24,
+            crate::config_io::DirectoryContext::for_testing(temp.path()),
+            crate::view::color_support::ColorCapability::TrueColor,
+            std::sync::Arc::new(crate::model::filesystem::StdFileSystem),
+        )
+        .unwrap();
+        editor.attach_client_view(1, "one#1".to_string(), 80, 24);
+        editor.attach_client_view(2, "two#2".to_string(), 120, 40);
+
+        for c in "hello".chars() {
+            editor
+                .handle_key(KeyCode::Char(c), KeyModifiers::NONE)
+                .unwrap();
+        }
+        assert_eq!(editor.active_cursors().primary().position, 5);
+
+        // Client 2 shares the text; its caret was pushed along by the
+        // insertion and it moves on its own.
+        assert!(editor.switch_client_view(2));
+        assert_eq!(editor.terminal_width, 120);
+        assert_eq!(editor.active_cursors().primary().position, 5);
+        editor
+            .handle_key(KeyCode::Home, KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(editor.active_cursors().primary().position, 0);
+
+        assert!(editor.switch_client_view(1));
+        assert_eq!(editor.terminal_width, 80);
+        assert_eq!(editor.active_cursors().primary().position, 5);
+        assert!(!editor.switch_client_view(1));
+
+        // Client 2's caret is drawn, labelled, for client 1.
+        assert_eq!(
+            editor.client_views.drawn_carets,
+            vec![(editor.active_buffer(), 0, "two#2".to_string())]
+        );
+    }
+
+    #[test]
+    fn parked_client_keeps_its_cursors_through_a_bulk_edit() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut editor = Editor::new(
+            crate::config::Config::default(),
+            80,
+            24,
+            crate::config_io::DirectoryContext::for_testing(temp.path()),
+            crate::view::color_support::ColorCapability::TrueColor,
+            std::sync::Arc::new(crate::model::filesystem::StdFileSystem),
+        )
+        .unwrap();
+        editor.attach_client_view(1, "one#1".to_string(), 80, 24);
+        editor.attach_client_view(2, "two#2".to_string(), 80, 24);
+        for c in "hello world".chars() {
+            editor
+                .handle_key(KeyCode::Char(c), KeyModifiers::NONE)
+                .unwrap();
+        }
+
+        // Client 2 selects "world" and keeps a second caret after "hello".
+        assert!(editor.switch_client_view(2));
+        let second = {
+            let cursors = editor.active_cursors_mut();
+            *cursors.primary_mut() = Cursor::with_selection(6, 11);
+            cursors.add(Cursor::new(5))
+        };
+
+        // Client 1 edits both ends of the line in one bulk edit.
+        assert!(editor.switch_client_view(1));
+        let primary = editor.active_cursors().primary_id();
+        editor.active_cursors_mut().primary_mut().position = 0;
+        editor
+            .apply_events_as_bulk_edit(
+                vec![
+                    Event::Insert {
+                        position: 11,
+                        text: "!".to_string(),
+                        cursor_id: primary,
+                    },
+                    Event::Insert {
+                        position: 0,
+                        text: ">> ".to_string(),
+                        cursor_id: primary,
+                    },
+                ],
+                "Edit both ends".to_string(),
+            )
+            .unwrap();
+        assert_eq!(editor.active_cursors().count(), 1);
+        assert_eq!(editor.active_cursors().primary().position, 3);
+
+        assert!(editor.switch_client_view(2));
+        let cursors = editor.active_cursors();
+        assert_eq!(cursors.count(), 2);
+        assert_eq!(cursors.primary().anchor, Some(9));
+        assert_eq!(cursors.primary().position, 15);
+        assert_eq!(cursors.get(second).unwrap().position, 8);
+    }
+}
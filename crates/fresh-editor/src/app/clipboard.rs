@@ -7,10 +7,14 @@
 
 use rust_i18n::t;
 
+use std::collections::HashMap;
+
+use crate::input::actions::{convert_block_selection_to_cursors, pad_virtual_space};
 use crate::input::multi_cursor::{
     add_cursor_above, add_cursor_at_next_match, add_cursor_below, AddCursorResult,
 };
-use crate::model::buffer_position::byte_to_2d;
+use crate::model::block_selection::{block_cursor_position, line_span, BlockRect};
+use crate::model::cursor::Position2D;
 use crate::model::event::{CursorId, Event};
 use crate::primitives::word_navigation::{
     find_vi_word_end, find_word_start_left, find_word_start_right,
@@ -45,7 +49,7 @@ impl Editor {
             // Block selection: copy rectangular region
             let text = self.copy_block_selection_text();
             if !text.is_empty() {
-                self.clipboard.copy(text);
+                self.clipboard.copy_block(text);
                 self.active_window_mut().status_message = Some(t!("clipboard.copied").to_string());
             }
             return;
@@ -112,95 +116,78 @@ impl Editor {
 
     /// Extract text from block (rectangular) selection
     ///
-    /// For block selection, we need to extract a rectangular region defined by:
-    /// - The block anchor (stored as Position2D with line and column)
-    /// - The current cursor position (byte offset, converted to 2D)
-    ///
-    /// This works for both small and large files by using line_iterator
-    /// for iteration and only using 2D positions for column extraction.
+    /// Each line of the block contributes the characters that start inside
+    /// its display columns; lines that end before the block contribute an
+    /// empty line.
     fn copy_block_selection_text(&mut self) -> String {
-        let estimated_line_length = 120;
-
-        // Collect block selection info from all cursors
-        let block_infos: Vec<_> = self
-            .active_cursors()
-            .iter()
-            .filter_map(|(_, cursor)| {
-                if !cursor.has_block_selection() {
-                    return None;
-                }
-                let block_anchor = cursor.block_anchor?;
-                let anchor_byte = cursor.anchor?; // byte offset of anchor
-                let cursor_byte = cursor.position;
-                Some((block_anchor, anchor_byte, cursor_byte))
-            })
-            .collect();
-
-        let mut result = String::new();
-
-        for (block_anchor, anchor_byte, cursor_byte) in block_infos {
-            // Get current cursor position as 2D
-            let cursor_2d = {
-                let state = self.active_state();
-                byte_to_2d(&state.buffer, cursor_byte)
-            };
+        let tab_size = self.active_state().buffer_settings.tab_size;
 
-            // Calculate column bounds (min and max columns for the rectangle)
-            let min_col = block_anchor.column.min(cursor_2d.column);
-            let max_col = block_anchor.column.max(cursor_2d.column);
-
-            // Calculate line bounds using byte positions
-            let start_byte = anchor_byte.min(cursor_byte);
-            let end_byte = anchor_byte.max(cursor_byte);
-
-            // Use line_iterator to iterate through lines
-            let state = self.active_state_mut();
-            let mut iter = state
-                .buffer
-                .line_iterator(start_byte, estimated_line_length);
-
-            // Collect lines within the block selection range
-            let mut lines_text = Vec::new();
-            loop {
-                let line_start = iter.current_position();
-
-                // Stop if we've passed the end of the selection
-                if line_start > end_byte {
-                    break;
-                }
-
-                if let Some((_offset, line_content)) = iter.next_line() {
-                    // Extract the column range from this line
-                    // Remove trailing newline for column calculation
-                    let content_without_newline = line_content.trim_end_matches(&['\n', '\r'][..]);
-                    let chars: Vec<char> = content_without_newline.chars().collect();
+        // Collect each line's part of every block selection
+        let blocks: Vec<Vec<std::ops::Range<usize>>> = {
+            let buffer = &self.active_state().buffer;
+            self.active_cursors()
+                .iter()
+                .filter_map(|(_, cursor)| BlockRect::from_cursor(buffer, cursor, tab_size))
+                .map(|rect| {
+                    rect.lines()
+                        .map(|line| rect.span(buffer, line, tab_size).range)
+                        .collect()
+                })
+                .collect()
+        };
 
-                    // Extract characters from min_col to max_col (exclusive)
-                    let extracted: String = chars
-                        .iter()
-                        .skip(min_col)
-                        .take(max_col.saturating_sub(min_col))
-                        .collect();
+        let state = self.active_state_mut();
+        blocks
+            .into_iter()
+            .map(|ranges| {
+                ranges
+                    .into_iter()
+                    .map(|range| state.get_text_range(range.start, range.end))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 
-                    lines_text.push(extracted);
+    /// Replace a block selection with one cursor per line, each selecting
+    /// that line's part of the block. Returns the padding of cursors in
+    /// virtual space.
+    fn split_block_selection(&mut self) -> HashMap<CursorId, usize> {
+        let tab_size = self.active_state().buffer_settings.tab_size;
+        let mut cursors = self.active_cursors().clone();
+        let state = self.active_state_mut();
+        let (events, padding) = convert_block_selection_to_cursors(state, &mut cursors, tab_size);
+        for event in &events {
+            state.apply(&mut cursors, event);
+        }
+        *self.active_cursors_mut() = cursors;
+        padding
+    }
 
-                    // If this line extends past end_byte, we're done
-                    if line_start + line_content.len() > end_byte {
-                        break;
-                    }
-                } else {
-                    break;
-                }
-            }
+    /// Cursor states for a bulk edit's undo, taken before a block selection
+    /// is split so that undo returns to the block's corners.
+    fn cursor_snapshot(&self) -> Vec<(CursorId, usize, Option<usize>)> {
+        self.active_cursors()
+            .iter()
+            .map(|(id, c)| (id, c.position, c.anchor))
+            .collect()
+    }
 
-            // Join the extracted text from each line
-            if !result.is_empty() && !lines_text.is_empty() {
-                result.push('\n');
+    /// Apply a cut or paste as one bulk edit whose undo restores
+    /// `cursors_before`, and log it.
+    fn apply_and_log_bulk_edit(
+        &mut self,
+        events: Vec<Event>,
+        description: String,
+        cursors_before: Vec<(CursorId, usize, Option<usize>)>,
+    ) {
+        if let Some(mut bulk_edit) = self.apply_events_as_bulk_edit(events, description) {
+            if let Event::BulkEdit { old_cursors, .. } = &mut bulk_edit {
+                *old_cursors = cursors_before;
             }
-            result.push_str(&lines_text.join("\n"));
+            self.active_event_log_mut().append(bulk_edit);
         }
-
-        result
     }
 
     /// Copy selection with a specific theme's formatting
@@ -393,15 +380,26 @@ impl Editor {
     /// If no selection exists, cuts the entire current line (like VSCode/Rider/Zed).
     pub fn cut_selection(&mut self) {
         // Check if any cursor has a selection
-        let has_selection = self
+        let has_block_selection = self
             .active_cursors()
             .iter()
-            .any(|(_, cursor)| cursor.selection_range().is_some());
+            .any(|(_, cursor)| cursor.has_block_selection());
+        let has_selection = has_block_selection
+            || self
+                .active_cursors()
+                .iter()
+                .any(|(_, cursor)| cursor.selection_range().is_some());
 
-        // Copy first (this handles both selection and whole-line cases)
+        // Copy first (this handles block, selection and whole-line cases)
         self.copy_selection();
 
         if has_selection {
+            // A block is cut line by line, leaving a cursor on each line
+            let cursors_before = self.cursor_snapshot();
+            if has_block_selection {
+                self.split_block_selection();
+            }
+
             // Delete selected text from all cursors
             // IMPORTANT: Sort deletions by position to ensure we process from end to start
             let mut deletions: Vec<_> = self
@@ -428,11 +426,9 @@ impl Editor {
                 .collect();
 
             // Apply events with atomic undo using bulk edit for O(n) performance
-            if events.len() > 1 {
-                // Use optimized bulk edit for multi-cursor cut
-                if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, "Cut".to_string()) {
-                    self.active_event_log_mut().append(bulk_edit);
-                }
+            if events.len() > 1 || has_block_selection {
+                // Use optimized bulk edit for multi-cursor and block cut
+                self.apply_and_log_bulk_edit(events, "Cut".to_string(), cursors_before);
             } else if let Some(event) = events.into_iter().next() {
                 self.log_and_apply_event(&event);
             }
@@ -516,8 +512,139 @@ impl Editor {
             None => return,
         };
 
-        // Use paste_text which handles line ending normalization
-        self.paste_text(text);
+        self.paste_clipboard_text(text);
+    }
+
+    /// Paste clipboard text: a block copy goes in column-wise (see
+    /// [`Self::paste_block`]) when there is a single cursor or a block
+    /// selection; anything else through [`Self::paste_text`].
+    fn paste_clipboard_text(&mut self, text: String) {
+        let paste_as_block = self.clipboard.is_block(&text)
+            && self.active_window().prompt.is_none()
+            && !self.active_window().terminal_mode
+            && {
+                let cursors = self.active_cursors();
+                cursors.iter().any(|(_, c)| c.has_block_selection())
+                    || (cursors.count() == 1 && cursors.primary().selection_range().is_none())
+            };
+        if paste_as_block {
+            self.paste_block(&text);
+        } else {
+            // paste_text handles line ending normalization
+            self.paste_text(text);
+        }
+    }
+
+    /// Paste a block copy column-wise: line `i` of the block goes into the
+    /// `i`-th line from the cursor, at the cursor's display column. Short
+    /// lines are padded with spaces, lines are added at the end of the
+    /// buffer as needed, and an active block selection is replaced.
+    fn paste_block(&mut self, text: &str) {
+        let tab_size = self.active_state().buffer_settings.tab_size;
+        let normalized = text.replace("\r\n", "\n");
+        let lines: Vec<&str> = normalized.split('\n').collect();
+
+        let (origin, rect) = {
+            let buffer = &self.active_state().buffer;
+            let cursors = self.active_cursors();
+            let rect = cursors
+                .iter()
+                .find_map(|(_, cursor)| BlockRect::from_cursor(buffer, cursor, tab_size));
+            let origin = match rect {
+                Some(rect) => Position2D {
+                    line: rect.start_line,
+                    column: rect.start_col,
+                },
+                None => block_cursor_position(buffer, cursors.primary(), tab_size),
+            };
+            (origin, rect)
+        };
+
+        let primary_id = self.active_cursors().primary_id();
+        let state = self.active_state_mut();
+        let last_line = state.buffer.get_line_number(state.buffer.len());
+        let newline = state.buffer.line_ending().as_str();
+        let rows = rect
+            .map_or(0, |rect| rect.end_line - rect.start_line + 1)
+            .max(lines.len());
+
+        // (replaced range, inserted text) for each existing line, top to
+        // bottom, plus the text appended past the end of the buffer
+        let mut edits: Vec<(std::ops::Range<usize>, String)> = Vec::new();
+        let mut appended = String::new();
+        for row in 0..rows {
+            let line = origin.line + row;
+            let line_text = lines.get(row).copied().unwrap_or("");
+            if line > last_line {
+                appended.push_str(newline);
+                if !line_text.is_empty() {
+                    appended.push_str(&" ".repeat(origin.column));
+                    appended.push_str(line_text);
+                }
+                continue;
+            }
+            let end_col = match rect {
+                Some(rect) if line <= rect.end_line => rect.end_col,
+                _ => origin.column,
+            };
+            let span = line_span(&state.buffer, line, origin.column, end_col, tab_size);
+            let inserted = if line_text.is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", " ".repeat(span.padding), line_text)
+            };
+            edits.push((span.range, inserted));
+        }
+        let new_position = edits
+            .first()
+            .map(|(range, inserted)| range.start + inserted.len());
+
+        // Build events bottom-up so earlier offsets stay valid
+        let mut events = Vec::new();
+        if !appended.is_empty() {
+            events.push(Event::Insert {
+                position: state.buffer.len(),
+                text: appended,
+                cursor_id: primary_id,
+            });
+        }
+        for (range, inserted) in edits.into_iter().rev() {
+            if !range.is_empty() {
+                let deleted_text = state.get_text_range(range.start, range.end);
+                events.push(Event::Delete {
+                    range: range.clone(),
+                    deleted_text,
+                    cursor_id: primary_id,
+                });
+            }
+            if !inserted.is_empty() {
+                events.push(Event::Insert {
+                    position: range.start,
+                    text: inserted,
+                    cursor_id: primary_id,
+                });
+            }
+        }
+
+        // Apply events with atomic undo using bulk edit for O(n) performance
+        if events.len() > 1 {
+            if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, "Paste".to_string()) {
+                self.active_event_log_mut().append(bulk_edit);
+            }
+        } else if let Some(event) = events.into_iter().next() {
+            self.log_and_apply_event(&event);
+        }
+
+        // Leave a single cursor after the first pasted line
+        let cursors = self.active_cursors_mut();
+        cursors.remove_secondary();
+        let cursor = cursors.primary_mut();
+        cursor.clear_selection();
+        if let Some(position) = new_position {
+            cursor.position = position;
+        }
+
+        self.active_window_mut().status_message = Some(t!("clipboard.pasted").to_string());
     }
 
     /// Paste text directly into the editor
@@ -557,6 +684,11 @@ impl Editor {
             return;
         }
 
+        // A block selection is replaced line by line, like a multi-cursor
+        // selection; text landing in virtual space is padded out to the block
+        let cursors_before = self.cursor_snapshot();
+        let virtual_padding = self.split_block_selection();
+
         // Collect cursor info sorted in reverse order by position
         let mut cursor_data: Vec<_> = self
             .active_cursors()
@@ -636,13 +768,12 @@ impl Editor {
                 cursor_id,
             });
         }
+        pad_virtual_space(&mut events, &virtual_padding);
 
         // Apply events with atomic undo using bulk edit for O(n) performance
         if events.len() > 1 {
             // Use optimized bulk edit for multi-cursor paste
-            if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, "Paste".to_string()) {
-                self.active_event_log_mut().append(bulk_edit);
-            }
+            self.apply_and_log_bulk_edit(events, "Paste".to_string(), cursors_before);
         } else if let Some(event) = events.into_iter().next() {
             self.log_and_apply_event(&event);
         }
@@ -670,7 +801,7 @@ impl Editor {
         };
 
         // Use the same paste logic as the regular paste method
-        self.paste_text(paste_text);
+        self.paste_clipboard_text(paste_text);
     }
 
     /// Get clipboard content for testing purposes
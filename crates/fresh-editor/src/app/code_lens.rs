@@ -0,0 +1,204 @@
+//! Per-window code lens state.
+//!
+//! Lenses are stored per buffer together with the server that produced
+//! them, since their commands only mean something to that server. Every
+//! source line with lenses gets one virtual line above it; the id of that
+//! virtual line is kept next to its layout so a click on it (or the
+//! `lsp_run_code_lens` action) still finds the right lens after edits
+//! moved it around.
+//!
+//! Requests are debounced like folding ranges: edits push the deadline
+//! back and the render loop fires the request once it has passed.
+//! Requesting, drawing and running lenses lives on `Editor` in
+//! `code_lens_actions.rs`.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::model::event::BufferId;
+use crate::primitives::code_lens::CodeLensLine;
+use crate::view::virtual_text::VirtualTextId;
+
+/// Virtual-text namespace of the lens lines
+pub(crate) const CODE_LENS_NAMESPACE: &str = "lsp-code-lens";
+
+/// How long edits must pause before lenses are requested again
+const CODE_LENS_DEBOUNCE_MS: u64 = 500;
+
+/// One drawn lens line.
+#[derive(Debug, Clone)]
+pub(crate) struct CodeLensRow {
+    pub virtual_text: VirtualTextId,
+    pub line: CodeLensLine,
+}
+
+/// Lenses of one buffer.
+#[derive(Debug, Clone)]
+pub(crate) struct BufferCodeLenses {
+    /// Buffer version the lenses were computed for
+    pub version: u64,
+    pub server_name: String,
+    pub lenses: Vec<lsp_types::CodeLens>,
+    pub rows: Vec<CodeLensRow>,
+}
+
+/// A `textDocument/codeLens` or `codeLens/resolve` request waiting for
+/// its answer.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PendingCodeLens {
+    pub buffer_id: BufferId,
+    /// Buffer version at request time
+    pub version: u64,
+    /// Index of the lens being resolved; unused for `textDocument/codeLens`
+    pub index: usize,
+}
+
+/// Owner of the code lenses of one window.
+#[derive(Default)]
+pub(crate) struct CodeLensState {
+    buffers: HashMap<BufferId, BufferCodeLenses>,
+    /// In-flight `textDocument/codeLens` requests by request id
+    pending: HashMap<u64, PendingCodeLens>,
+    /// In-flight `codeLens/resolve` requests by request id
+    resolving: HashMap<u64, PendingCodeLens>,
+    debounce: HashMap<BufferId, Instant>,
+}
+
+impl CodeLensState {
+    /// Request lenses for `buffer_id` once edits have paused.
+    pub(crate) fn schedule(&mut self, buffer_id: BufferId) {
+        self.debounce.insert(
+            buffer_id,
+            Instant::now() + Duration::from_millis(CODE_LENS_DEBOUNCE_MS),
+        );
+    }
+
+    /// Whether the debounce of `buffer_id` has elapsed; clears it if so.
+    pub(crate) fn take_due(&mut self, buffer_id: BufferId, now: Instant) -> bool {
+        match self.debounce.get(&buffer_id) {
+            Some(&ready_at) if now >= ready_at => {
+                self.debounce.remove(&buffer_id);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn get(&self, buffer_id: BufferId) -> Option<&BufferCodeLenses> {
+        self.buffers.get(&buffer_id)
+    }
+
+    pub(crate) fn get_mut(&mut self, buffer_id: BufferId) -> Option<&mut BufferCodeLenses> {
+        self.buffers.get_mut(&buffer_id)
+    }
+
+    pub(crate) fn store(&mut self, buffer_id: BufferId, lenses: BufferCodeLenses) {
+        self.buffers.insert(buffer_id, lenses);
+    }
+
+    /// Whether a request for `buffer_id` at `version` is already in flight.
+    pub(crate) fn is_pending(&self, buffer_id: BufferId, version: u64) -> bool {
+        self.pending
+            .values()
+            .any(|p| p.buffer_id == buffer_id && p.version == version)
+    }
+
+    pub(crate) fn add_pending(&mut self, request_id: u64, buffer_id: BufferId, version: u64) {
+        self.pending.insert(
+            request_id,
+            PendingCodeLens {
+                buffer_id,
+                version,
+                index: 0,
+            },
+        );
+    }
+
+    pub(crate) fn take_pending(&mut self, request_id: u64) -> Option<PendingCodeLens> {
+        self.pending.remove(&request_id)
+    }
+
+    pub(crate) fn add_resolving(&mut self, request_id: u64, pending: PendingCodeLens) {
+        self.resolving.insert(request_id, pending);
+    }
+
+    pub(crate) fn take_resolving(&mut self, request_id: u64) -> Option<PendingCodeLens> {
+        self.resolving.remove(&request_id)
+    }
+
+    /// Forget everything about `buffer_id`, e.g. because it was closed or
+    /// its language server was disabled.
+    ///
+    /// Returns the lenses that were stored so their lines can be removed.
+    pub(crate) fn remove_buffer(&mut self, buffer_id: BufferId) -> Option<BufferCodeLenses> {
+        self.debounce.remove(&buffer_id);
+        self.pending.retain(|_, p| p.buffer_id != buffer_id);
+        self.resolving.retain(|_, p| p.buffer_id != buffer_id);
+        self.buffers.remove(&buffer_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(version: u64) -> BufferCodeLenses {
+        BufferCodeLenses {
+            version,
+            server_name: "rust-analyzer".to_string(),
+            lenses: Vec::new(),
+            rows: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn debounce_fires_once_after_deadline() {
+        let mut state = CodeLensState::default();
+        let buf = BufferId(1);
+        state.schedule(buf);
+
+        assert!(!state.take_due(buf, Instant::now()));
+        let later = Instant::now() + Duration::from_millis(CODE_LENS_DEBOUNCE_MS + 1);
+        assert!(state.take_due(buf, later));
+        assert!(!state.take_due(buf, later));
+    }
+
+    #[test]
+    fn pending_requests_are_tracked_per_version() {
+        let mut state = CodeLensState::default();
+        let buf = BufferId(1);
+        state.add_pending(7, buf, 3);
+
+        assert!(state.is_pending(buf, 3));
+        assert!(!state.is_pending(buf, 4));
+        assert_eq!(state.take_pending(7).map(|p| p.version), Some(3));
+        assert!(!state.is_pending(buf, 3));
+    }
+
+    #[test]
+    fn remove_buffer_forgets_requests_and_lenses() {
+        let mut state = CodeLensState::default();
+        let buf = BufferId(1);
+        let other = BufferId(2);
+        state.store(buf, stored(1));
+        state.schedule(buf);
+        state.add_pending(1, buf, 1);
+        state.add_pending(2, other, 1);
+        state.add_resolving(
+            3,
+            PendingCodeLens {
+                buffer_id: buf,
+                version: 1,
+                index: 0,
+            },
+        );
+
+        assert!(state.remove_buffer(buf).is_some());
+        assert!(state.get(buf).is_none());
+        assert!(state.take_pending(1).is_none());
+        assert!(state.take_pending(2).is_some());
+        assert!(state.take_resolving(3).is_none());
+        let later = Instant::now() + Duration::from_millis(CODE_LENS_DEBOUNCE_MS + 1);
+        assert!(!state.take_due(buf, later));
+    }
+}
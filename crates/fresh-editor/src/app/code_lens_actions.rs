@@ -0,0 +1,460 @@
+//! Code lens orchestrators.
+//!
+//! Lenses are requested after edits pause and whenever the server asks
+//! for a refresh (`workspace/codeLens/refresh`), resolved if the server
+//! left their commands out, and drawn as virtual lines above the line they
+//! belong to. Running a lens — by clicking it or with `lsp_run_code_lens`
+//! on its line — sends its command back to the server that produced it
+//! when that server advertised the command. Commands the client is
+//! expected to run itself are handled here (`*.showReferences`) or passed
+//! to plugins through the `lsp_client_command` hook (rust-analyzer's
+//! "Run" and "Debug").
+
+use rust_i18n::t;
+
+use crate::app::code_lens::{BufferCodeLenses, CodeLensRow, PendingCodeLens, CODE_LENS_NAMESPACE};
+use crate::model::event::BufferId;
+use crate::primitives::code_lens::{group_by_line, lens_title, CodeLensLine};
+use crate::services::plugins::hooks::HookArgs;
+use crate::types::LspFeature;
+use crate::view::virtual_text::{VirtualTextNamespace, VirtualTextPosition};
+
+use super::Editor;
+
+/// Lens lines render closest to their anchor among other virtual lines
+/// above it, which click handling relies on.
+const CODE_LENS_PRIORITY: i32 = 1000;
+
+impl Editor {
+    /// Issue a debounced code lens request if the timer has elapsed.
+    pub(crate) fn maybe_request_code_lens_debounced(&mut self, buffer_id: BufferId) {
+        if !self
+            .active_window_mut()
+            .code_lens
+            .take_due(buffer_id, std::time::Instant::now())
+        {
+            return;
+        }
+        self.request_code_lens_for_buffer(buffer_id);
+    }
+
+    /// Request code lenses for a buffer if enabled and supported.
+    pub(crate) fn request_code_lens_for_buffer(&mut self, buffer_id: BufferId) {
+        if !self.config.editor.enable_code_lens {
+            return;
+        }
+        let Some(version) = self
+            .buffers()
+            .get(&buffer_id)
+            .map(|state| state.buffer.version())
+        else {
+            return;
+        };
+        if self
+            .active_window()
+            .code_lens
+            .is_pending(buffer_id, version)
+        {
+            return;
+        }
+
+        let request_id = self.active_window_mut().next_lsp_request_id;
+        let sent = self
+            .with_lsp_for_buffer(buffer_id, LspFeature::CodeLens, |handle, uri, _language| {
+                let result = handle.code_lens(request_id, uri.as_uri().clone());
+                if result.is_ok() {
+                    tracing::info!(
+                        "Requested code lenses for {} (request_id={})",
+                        uri.as_str(),
+                        request_id
+                    );
+                }
+                result.is_ok()
+            })
+            .unwrap_or(false);
+
+        if sent {
+            let window = self.active_window_mut();
+            window.next_lsp_request_id += 1;
+            window.code_lens.add_pending(request_id, buffer_id, version);
+        }
+    }
+
+    /// Request code lenses for all open buffers matching a language.
+    pub(super) fn request_code_lens_for_language(&mut self, language: &str) {
+        for (buffer_id, _) in self.buffers_for_language(language) {
+            self.active_window_mut().code_lens.schedule(buffer_id);
+        }
+    }
+
+    /// Handle workspace/codeLens/refresh request from the LSP server.
+    pub(super) fn handle_lsp_code_lens_refresh(&mut self, language: String) {
+        tracing::info!("LSP ({}) code lens refresh requested", language);
+        self.request_code_lens_for_language(&language);
+    }
+
+    /// Handle LSP code lens response
+    pub(super) fn handle_lsp_code_lenses(
+        &mut self,
+        request_id: u64,
+        uri: String,
+        server_name: String,
+        lenses: Vec<lsp_types::CodeLens>,
+    ) {
+        let Some(request) = self.active_window_mut().code_lens.take_pending(request_id) else {
+            tracing::debug!(
+                "Ignoring code lenses without pending request (request_id={})",
+                request_id
+            );
+            return;
+        };
+        let buffer_id = request.buffer_id;
+        let Some(current) = self
+            .buffers()
+            .get(&buffer_id)
+            .map(|state| state.buffer.version())
+        else {
+            return;
+        };
+        if current != request.version {
+            // Lines moved since the request; keep showing the old lenses
+            // (their markers follow edits) until a fresh answer arrives.
+            tracing::debug!(
+                "Ignoring stale code lenses for {} (version={}, current={})",
+                uri,
+                request.version,
+                current
+            );
+            self.active_window_mut().code_lens.schedule(buffer_id);
+            return;
+        }
+
+        tracing::debug!("Received {} code lenses for {}", lenses.len(), uri);
+        let can_resolve = self
+            .lsp()
+            .and_then(|lsp| lsp.handle_named(&server_name))
+            .is_some_and(|sh| sh.capabilities.code_lens_resolve);
+        let unresolved: Vec<(usize, lsp_types::CodeLens)> = if can_resolve {
+            lenses
+                .iter()
+                .enumerate()
+                .filter(|(_, lens)| lens.command.is_none())
+                .map(|(index, lens)| (index, lens.clone()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        self.active_window_mut().code_lens.store(
+            buffer_id,
+            BufferCodeLenses {
+                version: current,
+                server_name: server_name.clone(),
+                lenses,
+                rows: Vec::new(),
+            },
+        );
+        self.draw_code_lenses(buffer_id);
+
+        for (index, lens) in unresolved {
+            self.resolve_code_lens(buffer_id, current, &server_name, index, lens);
+        }
+    }
+
+    /// Ask `server_name` for the command of an unresolved lens.
+    fn resolve_code_lens(
+        &mut self,
+        buffer_id: BufferId,
+        version: u64,
+        server_name: &str,
+        index: usize,
+        lens: lsp_types::CodeLens,
+    ) {
+        let request_id = self.active_window_mut().next_lsp_request_id;
+        let Some(sh) = self.lsp().and_then(|lsp| lsp.handle_named(server_name)) else {
+            return;
+        };
+        if let Err(e) = sh.handle.code_lens_resolve(request_id, lens) {
+            tracing::debug!("Failed to resolve code lens: {}", e);
+            return;
+        }
+        let window = self.active_window_mut();
+        window.next_lsp_request_id += 1;
+        window.code_lens.add_resolving(
+            request_id,
+            PendingCodeLens {
+                buffer_id,
+                version,
+                index,
+            },
+        );
+    }
+
+    /// Handle LSP codeLens/resolve response
+    pub(super) fn handle_lsp_code_lens_resolved(
+        &mut self,
+        request_id: u64,
+        lens: Option<lsp_types::CodeLens>,
+    ) {
+        let Some(request) = self
+            .active_window_mut()
+            .code_lens
+            .take_resolving(request_id)
+        else {
+            return;
+        };
+        let Some(lens) = lens else {
+            return;
+        };
+        let Some(stored) = self
+            .active_window_mut()
+            .code_lens
+            .get_mut(request.buffer_id)
+        else {
+            return;
+        };
+        // A newer codeLens answer replaced the list this lens came from
+        if stored.version != request.version {
+            return;
+        }
+        if let Some(slot) = stored.lenses.get_mut(request.index) {
+            *slot = lens;
+        }
+        self.draw_code_lenses(request.buffer_id);
+    }
+
+    /// Replace the lens lines of `buffer_id` with its stored lenses.
+    fn draw_code_lenses(&mut self, buffer_id: BufferId) {
+        use ratatui::style::{Color, Style};
+
+        let window = self.active_window_mut();
+        let (Some(state), Some(stored)) = (
+            window.buffers.get_mut(&buffer_id),
+            window.code_lens.get_mut(buffer_id),
+        ) else {
+            return;
+        };
+        let namespace = VirtualTextNamespace::from_string(CODE_LENS_NAMESPACE.to_string());
+        state
+            .virtual_texts
+            .clear_namespace(&mut state.marker_list, &namespace);
+        stored.rows.clear();
+
+        // Dimmed like inlay hints; the theme key wins when it resolves
+        let style = Style::default().fg(Color::Rgb(128, 128, 128));
+        let tab_size = state.buffer_settings.tab_size.max(1);
+        for (line, indices) in group_by_line(&stored.lenses) {
+            let line = line as usize;
+            if line >= state.buffer.line_count().unwrap_or(usize::MAX) {
+                continue;
+            }
+            let indent_width = state
+                .buffer
+                .get_line(line)
+                .map(|bytes| {
+                    bytes
+                        .iter()
+                        .take_while(|b| **b == b' ' || **b == b'\t')
+                        .fold(0, |width, b| match b {
+                            b'\t' => width + tab_size - width % tab_size,
+                            _ => width + 1,
+                        })
+                })
+                .unwrap_or(0);
+            let Some(layout) = CodeLensLine::layout(indent_width, &stored.lenses, &indices) else {
+                continue;
+            };
+            let anchor = state.buffer.lsp_position_to_byte(line, 0);
+            let virtual_text = state.virtual_texts.add_line_with_theme_keys(
+                &mut state.marker_list,
+                anchor,
+                layout.text.clone(),
+                style,
+                Some("editor.line_number_fg".to_string()),
+                None,
+                VirtualTextPosition::LineAbove,
+                namespace.clone(),
+                CODE_LENS_PRIORITY,
+            );
+            stored.rows.push(CodeLensRow {
+                virtual_text,
+                line: layout,
+            });
+        }
+    }
+
+    /// The lens line drawn above the source line containing `byte`.
+    fn code_lens_row_at(&self, buffer_id: BufferId, byte: usize) -> Option<&CodeLensRow> {
+        let state = self.buffers().get(&buffer_id)?;
+        let stored = self.active_window().code_lens.get(buffer_id)?;
+        let line = state.buffer.get_line_number(byte);
+        stored.rows.iter().find(|row| {
+            state
+                .virtual_texts
+                .position(&state.marker_list, row.virtual_text)
+                .is_some_and(|anchor| state.buffer.get_line_number(anchor) == line)
+        })
+    }
+
+    /// Run the lens drawn at `column` of the lens line above the source
+    /// line starting at `anchor_byte`.
+    ///
+    /// Returns whether a lens was hit; clicks between lenses are swallowed
+    /// too, so they don't move the cursor.
+    pub(super) fn run_code_lens_at_click(
+        &mut self,
+        buffer_id: BufferId,
+        anchor_byte: usize,
+        column: usize,
+    ) -> bool {
+        let Some(row) = self.code_lens_row_at(buffer_id, anchor_byte) else {
+            return false;
+        };
+        if let Some(index) = row.line.lens_at(column) {
+            self.run_code_lens(buffer_id, index);
+        }
+        true
+    }
+
+    /// Run the lens of the cursor line, or let the user pick one if the
+    /// line has several.
+    pub(crate) fn run_code_lens_at_cursor(&mut self) {
+        use crate::view::popup::{Popup, PopupKind, PopupListItem, PopupPosition, PopupResolver};
+
+        let buffer_id = self.active_buffer();
+        let cursor = self.active_cursors().primary().position;
+        let Some(row) = self.code_lens_row_at(buffer_id, cursor) else {
+            self.set_status_message(t!("lsp.no_code_lens").to_string());
+            return;
+        };
+        let indices: Vec<usize> = row.line.lenses().collect();
+        if let [index] = indices[..] {
+            self.run_code_lens(buffer_id, index);
+            return;
+        }
+
+        let items = {
+            let Some(stored) = self.active_window().code_lens.get(buffer_id) else {
+                return;
+            };
+            indices
+                .iter()
+                .filter_map(|&index| {
+                    let title = lens_title(stored.lenses.get(index)?)?;
+                    Some(PopupListItem {
+                        text: title.to_string(),
+                        detail: None,
+                        icon: None,
+                        data: Some(index.to_string()),
+                        disabled: false,
+                    })
+                })
+                .collect()
+        };
+        let theme = self.theme.read().unwrap();
+        let mut popup = Popup::list(items, &theme);
+        popup.kind = PopupKind::Action;
+        popup.title = Some(t!("lsp.popup_code_lens").to_string());
+        popup.position = PopupPosition::BelowCursor;
+        popup.width = 50;
+        popup.max_height = 10;
+        popup.border_style = ratatui::style::Style::default().fg(theme.popup_border_fg);
+        popup.background_style = ratatui::style::Style::default().bg(theme.popup_bg);
+        // Selected row's `data` is the lens index in the active buffer
+        popup.resolver = PopupResolver::CodeLens;
+        popup.focused = true;
+        drop(theme);
+
+        if let Some(state) = self.buffers_mut().get_mut(&buffer_id) {
+            state.popups.show_or_replace(popup);
+        }
+    }
+
+    /// Run the command of lens `index` of `buffer_id`.
+    pub(crate) fn run_code_lens(&mut self, buffer_id: BufferId, index: usize) {
+        let Some((server_name, command)) =
+            self.active_window().code_lens.get(buffer_id).and_then(|s| {
+                let command = s.lenses.get(index)?.command.clone()?;
+                Some((s.server_name.clone(), command))
+            })
+        else {
+            return;
+        };
+        let Some(language) = self
+            .buffers()
+            .get(&buffer_id)
+            .map(|state| state.language.clone())
+        else {
+            return;
+        };
+        tracing::info!(
+            "Running code lens '{}' ({}) from {}",
+            command.title,
+            command.command,
+            server_name
+        );
+
+        let server_runs_it = self
+            .lsp()
+            .and_then(|lsp| lsp.handle_named(&server_name))
+            .is_some_and(|sh| sh.capabilities.execute_commands.contains(&command.command));
+        if server_runs_it {
+            if let Some(sh) = self.lsp().and_then(|lsp| lsp.handle_named(&server_name)) {
+                if let Err(e) = sh
+                    .handle
+                    .execute_command(command.command.clone(), command.arguments.clone())
+                {
+                    tracing::warn!("Failed to send executeCommand to '{}': {}", server_name, e);
+                }
+            }
+            return;
+        }
+
+        if command.command.ends_with(".showReferences") {
+            self.show_code_lens_references(&command);
+            return;
+        }
+
+        self.set_status_message(t!("lsp.code_lens_run", title = &command.title).to_string());
+        self.plugin_manager.read().unwrap().run_hook(
+            "lsp_client_command",
+            HookArgs::LspClientCommand {
+                language,
+                server_name,
+                command: command.command,
+                title: command.title,
+                arguments: command
+                    .arguments
+                    .map(|args| serde_json::Value::Array(args).to_string()),
+            },
+        );
+    }
+
+    /// Show the locations of a `*.showReferences` command (arguments:
+    /// document URI, position, locations) in the references panel.
+    fn show_code_lens_references(&mut self, command: &lsp_types::Command) {
+        let args = command.arguments.as_deref().unwrap_or_default();
+        let locations: Vec<lsp_types::Location> = args
+            .get(2)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        if locations.is_empty() {
+            self.set_status_message(t!("lsp.no_references").to_string());
+            return;
+        }
+        // The references are of the symbol at the lens position
+        let symbol = args
+            .get(1)
+            .and_then(|v| serde_json::from_value::<lsp_types::Position>(v.clone()).ok())
+            .map(|pos| {
+                let byte = self
+                    .active_state()
+                    .buffer
+                    .lsp_position_to_byte(pos.line as usize, pos.character as usize);
+                self.word_at_cursor(byte)
+            })
+            .filter(|word| !word.is_empty())
+            .unwrap_or_else(|| command.title.clone());
+        self.show_lsp_locations_panel("references", symbol, &locations);
+    }
+}
@@ -210,10 +210,10 @@ impl Editor {
         self.active_window_mut().dabbrev_state = None;
     }
 
-    /// Run the `CompletionService` (buffer-words + dabbrev providers) and
-    /// return results as `PopupListItemData` items suitable for the
-    /// completion popup. The items use icon `"w"` to visually distinguish
-    /// them from LSP results.
+    /// Run the `CompletionService` (buffer-words, dabbrev and snippet
+    /// providers) and return results as `PopupListItemData` items suitable
+    /// for the completion popup. Word items use icon `"w"` and snippets
+    /// `"s"` to visually distinguish them from LSP results.
     ///
     /// Returns an empty vec if the prefix is empty or no candidates match.
     pub(crate) fn get_buffer_completion_popup_items(
@@ -241,6 +241,7 @@ impl Editor {
         let scan_range = CompletionContext::compute_scan_range(cursor_pos, buffer_len, is_large);
         let buffer_window = self.active_state().buffer.slice_bytes(scan_range.clone());
         let word_chars_extra = self.active_state().buffer_settings.word_characters.clone();
+        let language_id = self.active_state().language.clone();
 
         let active_buf_id = self.active_buffer();
         let other_buffers = self.collect_other_buffer_slices(active_buf_id);
@@ -276,7 +277,7 @@ impl Editor {
             scan_range,
             viewport_top_byte,
             viewport_bottom_byte,
-            language_id: None,
+            language_id: Some(language_id),
             word_chars_extra,
             prefix_has_uppercase: prefix_has_upper,
             other_buffers,
@@ -292,7 +293,7 @@ impl Editor {
             .map(|c| PopupListItemData {
                 text: c.label.clone(),
                 detail: c.detail.clone(),
-                icon: Some("w".to_string()),
+                icon: c.icon.or_else(|| Some("w".to_string())),
                 data: c.insert_text.or(Some(c.label)),
             })
             .collect()
@@ -0,0 +1,234 @@
+//! Per-window debugger state.
+//!
+//! Breakpoints of open buffers live in their gutter as line indicators in
+//! [`BREAKPOINT_NAMESPACE`], so they follow edits like any other marker.
+//! When a buffer is closed its breakpoint lines are parked here by path
+//! and put back when the file is opened again; a running session is told
+//! about both.
+//!
+//! At most one debug session runs per window. Its requests are tracked by
+//! DAP sequence number so each response can be routed to what asked for
+//! it. Starting, stepping and drawing the current line lives on `Editor`
+//! in `debug_actions.rs`.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::services::dap::client::DapHandle;
+use crate::services::dap::protocol::Capabilities;
+
+/// Line-indicator namespace of breakpoints
+pub(crate) const BREAKPOINT_NAMESPACE: &str = "debug-breakpoint";
+
+/// Line-indicator and overlay namespace of the current execution line
+pub(crate) const CURRENT_LINE_NAMESPACE: &str = "debug-current-line";
+
+/// What a DAP request in flight was sent for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PendingDebugRequest {
+    Initialize,
+    /// `launch` or `attach`
+    Start,
+    SetBreakpoints,
+    ConfigurationDone,
+    /// `threads`, sent to find a thread to pause
+    Threads,
+    StackTrace {
+        thread_id: i64,
+        reason: String,
+    },
+    /// `continue`, `next`, `stepIn`, `stepOut` or `pause`
+    Control,
+    Disconnect,
+    /// Request from `editor.sendDebugRequest`, answered to this callback
+    Plugin(u64),
+}
+
+/// A running debug adapter and what the editor knows about it.
+pub(crate) struct DebugSession {
+    pub handle: DapHandle,
+    /// Index into `config.debug.configurations`
+    pub configuration: usize,
+    pub capabilities: Capabilities,
+    /// Whether the adapter sent `initialized` and got the breakpoints
+    pub configured: bool,
+    /// Thread the debuggee is paused in; `None` while it runs
+    pub stopped_thread: Option<i64>,
+    /// Thread of the last `stopped` event, used by `pause`
+    pub last_thread_id: Option<i64>,
+    pending: HashMap<i64, PendingDebugRequest>,
+}
+
+impl DebugSession {
+    pub(crate) fn new(handle: DapHandle, configuration: usize) -> Self {
+        Self {
+            handle,
+            configuration,
+            capabilities: Capabilities::default(),
+            configured: false,
+            stopped_thread: None,
+            last_thread_id: None,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn id(&self) -> u64 {
+        self.handle.session_id()
+    }
+
+    /// Send a request and remember what it was for.
+    pub(crate) fn request(
+        &mut self,
+        command: &str,
+        arguments: Option<&Value>,
+        purpose: PendingDebugRequest,
+    ) -> Result<i64, String> {
+        let seq = self.handle.request(command, arguments)?;
+        self.pending.insert(seq, purpose);
+        Ok(seq)
+    }
+
+    /// [`Self::request`] for requests whose failure needs no handling: the
+    /// adapter is gone and its exit ends the session anyway.
+    pub(crate) fn send(
+        &mut self,
+        command: &str,
+        arguments: Option<&Value>,
+        purpose: PendingDebugRequest,
+    ) {
+        if let Err(e) = self.request(command, arguments, purpose) {
+            tracing::warn!("Failed to send DAP '{}' request: {}", command, e);
+        }
+    }
+
+    pub(crate) fn take_pending(&mut self, seq: i64) -> Option<PendingDebugRequest> {
+        self.pending.remove(&seq)
+    }
+
+    /// Callback ids of plugin requests that will never be answered.
+    pub(crate) fn pending_plugin_callbacks(&self) -> Vec<u64> {
+        self.pending
+            .values()
+            .filter_map(|p| match p {
+                PendingDebugRequest::Plugin(id) => Some(*id),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Owner of the breakpoints of closed files and the debug session of one
+/// window.
+#[derive(Default)]
+pub(crate) struct DebugState {
+    /// 0-based breakpoint lines of files that are not open
+    stored_breakpoints: HashMap<PathBuf, BTreeSet<usize>>,
+    next_session_id: u64,
+    pub session: Option<DebugSession>,
+}
+
+impl DebugState {
+    pub(crate) fn allocate_session_id(&mut self) -> u64 {
+        self.next_session_id += 1;
+        self.next_session_id
+    }
+
+    /// The session with id `session_id`, ignoring messages from sessions
+    /// that were already replaced.
+    pub(crate) fn session_mut(&mut self, session_id: u64) -> Option<&mut DebugSession> {
+        self.session.as_mut().filter(|s| s.id() == session_id)
+    }
+
+    /// Park the breakpoints of a file that is being closed.
+    pub(crate) fn store_breakpoints(&mut self, path: PathBuf, lines: BTreeSet<usize>) {
+        if lines.is_empty() {
+            self.stored_breakpoints.remove(&path);
+        } else {
+            self.stored_breakpoints.insert(path, lines);
+        }
+    }
+
+    /// Hand back the parked breakpoints of a file that is being opened.
+    pub(crate) fn take_stored_breakpoints(&mut self, path: &Path) -> Option<BTreeSet<usize>> {
+        self.stored_breakpoints.remove(path)
+    }
+
+    pub(crate) fn stored_breakpoints(&self) -> impl Iterator<Item = (&PathBuf, &BTreeSet<usize>)> {
+        self.stored_breakpoints.iter()
+    }
+}
+
+/// Substitute `${workspaceFolder}` and `${file}` in every string of a
+/// launch/attach argument value.
+pub(crate) fn substitute_variables(value: &Value, workspace_folder: &str, file: &str) -> Value {
+    match value {
+        Value::String(s) => Value::String(
+            s.replace("${workspaceFolder}", workspace_folder)
+                .replace("${file}", file),
+        ),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|v| substitute_variables(v, workspace_folder, file))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), substitute_variables(v, workspace_folder, file)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn stored_breakpoints_round_trip_by_path() {
+        let mut state = DebugState::default();
+        let path = PathBuf::from("/src/main.rs");
+        state.store_breakpoints(path.clone(), BTreeSet::from([3, 9]));
+
+        assert_eq!(state.stored_breakpoints().count(), 1);
+        assert_eq!(
+            state.take_stored_breakpoints(&path),
+            Some(BTreeSet::from([3, 9]))
+        );
+        assert!(state.take_stored_breakpoints(&path).is_none());
+    }
+
+    #[test]
+    fn storing_no_breakpoints_forgets_the_file() {
+        let mut state = DebugState::default();
+        let path = PathBuf::from("/src/main.rs");
+        state.store_breakpoints(path.clone(), BTreeSet::from([1]));
+        state.store_breakpoints(path.clone(), BTreeSet::new());
+
+        assert!(state.take_stored_breakpoints(&path).is_none());
+    }
+
+    #[test]
+    fn variables_are_substituted_recursively() {
+        let args = json!({
+            "program": "${workspaceFolder}/target/debug/app",
+            "args": ["${file}", 3],
+            "env": { "ROOT": "${workspaceFolder}" },
+            "stopOnEntry": true
+        });
+
+        assert_eq!(
+            substitute_variables(&args, "/work", "/work/src/main.rs"),
+            json!({
+                "program": "/work/target/debug/app",
+                "args": ["/work/src/main.rs", 3],
+                "env": { "ROOT": "/work" },
+                "stopOnEntry": true
+            })
+        );
+    }
+}
@@ -0,0 +1,805 @@
+//! Debugger orchestrators.
+//!
+//! A session follows the DAP startup sequence: `initialize`, then
+//! `launch`/`attach` once the adapter answered, then — when the adapter
+//! sends `initialized` — every known breakpoint, `setExceptionBreakpoints`
+//! and `configurationDone`. A `stopped` event asks for the stack trace and
+//! the top frame is opened and marked with a current-line indicator.
+//! Control requests (continue, step, pause) clear that marker right away,
+//! since adapters need not send `continued` for requests the client made.
+//!
+//! The call stack, variables and watch panels are a plugin
+//! (`plugins/debugger.ts`) built on the `debug_*` hooks and
+//! `editor.sendDebugRequest`.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use rust_i18n::t;
+use serde_json::{json, Value};
+
+use crate::app::debug::{
+    substitute_variables, DebugSession, PendingDebugRequest, BREAKPOINT_NAMESPACE,
+    CURRENT_LINE_NAMESPACE,
+};
+use crate::model::event::BufferId;
+use crate::services::dap::client::DapHandle;
+use crate::services::dap::protocol::{
+    Capabilities, ContinuedEventBody, ExitedEventBody, OutputEventBody, Source, SourceBreakpoint,
+    StackTraceResponseBody, StoppedEventBody,
+};
+use crate::services::plugins::hooks::HookArgs;
+use crate::view::margin::LineIndicator;
+use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
+
+use super::Editor;
+
+/// Breakpoints win over git and diff markers in the gutter
+const BREAKPOINT_PRIORITY: i32 = 20;
+
+/// The current line wins over a breakpoint on the same line
+const CURRENT_LINE_PRIORITY: i32 = 30;
+
+/// Frames requested per `stackTrace`; the panel asks for more itself
+const STACK_TRACE_LEVELS: usize = 20;
+
+impl Editor {
+    /// Start debugging: directly with the only configuration, otherwise
+    /// after picking one.
+    pub(crate) fn debug_start(&mut self) {
+        use crate::view::popup::{Popup, PopupKind, PopupListItem, PopupPosition, PopupResolver};
+
+        if self.active_window().debug.session.is_some() {
+            self.set_status_message(t!("debug.already_running").to_string());
+            return;
+        }
+        let configurations = &self.config.debug.configurations;
+        match configurations.len() {
+            0 => {
+                self.set_status_message(t!("debug.no_configurations").to_string());
+                return;
+            }
+            1 => {
+                self.debug_start_configuration(0);
+                return;
+            }
+            _ => {}
+        }
+
+        let items = configurations
+            .iter()
+            .enumerate()
+            .map(|(index, configuration)| PopupListItem {
+                text: configuration.name.clone(),
+                detail: Some(format!(
+                    "{} ({})",
+                    configuration.adapter,
+                    configuration.request.command()
+                )),
+                icon: None,
+                data: Some(index.to_string()),
+                disabled: false,
+            })
+            .collect();
+        let theme = self.theme.read().unwrap();
+        let mut popup = Popup::list(items, &theme);
+        popup.kind = PopupKind::Action;
+        popup.title = Some(t!("debug.popup_configurations").to_string());
+        popup.position = PopupPosition::BelowCursor;
+        popup.width = 50;
+        popup.max_height = 10;
+        popup.border_style = ratatui::style::Style::default().fg(theme.popup_border_fg);
+        popup.background_style = ratatui::style::Style::default().bg(theme.popup_bg);
+        // Selected row's `data` is the configuration index
+        popup.resolver = PopupResolver::DebugConfiguration;
+        popup.focused = true;
+        drop(theme);
+
+        self.active_state_mut().popups.show_or_replace(popup);
+    }
+
+    /// Spawn the adapter of configuration `index` and send `initialize`.
+    pub(crate) fn debug_start_configuration(&mut self, index: usize) {
+        if self.active_window().debug.session.is_some() {
+            self.set_status_message(t!("debug.already_running").to_string());
+            return;
+        }
+        let Some(configuration) = self.config.debug.configurations.get(index).cloned() else {
+            return;
+        };
+        let Some(adapter) = self
+            .config
+            .debug
+            .adapters
+            .get(&configuration.adapter)
+            .cloned()
+        else {
+            self.set_status_message(
+                t!("debug.unknown_adapter", adapter = configuration.adapter).to_string(),
+            );
+            return;
+        };
+        let Some(runtime) = self.tokio_runtime.as_ref().map(|rt| rt.handle().clone()) else {
+            self.set_status_message(
+                t!("debug.adapter_error", error = "async runtime not available").to_string(),
+            );
+            return;
+        };
+
+        let session_id = self.active_window_mut().debug.allocate_session_id();
+        let handle = DapHandle::spawn(
+            &runtime,
+            session_id,
+            &configuration.adapter,
+            &adapter,
+            &self.active_window().bridge,
+            self.authority.long_running_spawner.clone(),
+        );
+        let mut session = DebugSession::new(handle, index);
+        let arguments = json!({
+            "clientID": "fresh",
+            "clientName": "Fresh",
+            "adapterID": configuration.adapter,
+            "linesStartAt1": true,
+            "columnsStartAt1": true,
+            "pathFormat": "path",
+            "locale": crate::i18n::current_locale(),
+        });
+        if let Err(e) = session.request(
+            "initialize",
+            Some(&arguments),
+            PendingDebugRequest::Initialize,
+        ) {
+            self.set_status_message(t!("debug.adapter_error", error = e).to_string());
+            return;
+        }
+        self.active_window_mut().debug.session = Some(session);
+
+        tracing::info!(
+            "Starting debug session {} '{}' with adapter '{}'",
+            session_id,
+            configuration.name,
+            configuration.adapter
+        );
+        self.set_status_message(t!("debug.starting", name = configuration.name).to_string());
+        self.plugin_manager.read().unwrap().run_hook(
+            "debug_started",
+            HookArgs::DebugStarted {
+                session_id,
+                configuration: configuration.name,
+                adapter: configuration.adapter,
+            },
+        );
+    }
+
+    /// Ask the adapter to end the session and terminate the debuggee.
+    pub(crate) fn debug_stop(&mut self) {
+        let Some(session) = self.active_window_mut().debug.session.as_mut() else {
+            self.set_status_message(t!("debug.no_session").to_string());
+            return;
+        };
+        let arguments = json!({ "terminateDebuggee": true });
+        if session
+            .request(
+                "disconnect",
+                Some(&arguments),
+                PendingDebugRequest::Disconnect,
+            )
+            .is_err()
+        {
+            let session_id = session.id();
+            self.end_debug_session(session_id, None);
+        }
+    }
+
+    /// Continue the paused debuggee, or start debugging if no session runs.
+    pub(crate) fn debug_continue(&mut self) {
+        if self.active_window().debug.session.is_none() {
+            self.debug_start();
+            return;
+        }
+        self.debug_step("continue");
+    }
+
+    /// Send a thread control request (`continue`, `next`, `stepIn`,
+    /// `stepOut`) for the paused thread.
+    pub(crate) fn debug_step(&mut self, command: &str) {
+        let Some(session) = self.active_window_mut().debug.session.as_mut() else {
+            self.set_status_message(t!("debug.no_session").to_string());
+            return;
+        };
+        let Some(thread_id) = session.stopped_thread else {
+            self.set_status_message(t!("debug.not_paused").to_string());
+            return;
+        };
+        let session_id = session.id();
+        let arguments = json!({ "threadId": thread_id });
+        match session.request(command, Some(&arguments), PendingDebugRequest::Control) {
+            Ok(_) => self.debug_resumed(session_id, Some(thread_id)),
+            Err(e) => self.set_status_message(t!("debug.adapter_error", error = e).to_string()),
+        }
+    }
+
+    /// Pause the running debuggee.
+    pub(crate) fn debug_pause(&mut self) {
+        let Some(session) = self.active_window_mut().debug.session.as_mut() else {
+            self.set_status_message(t!("debug.no_session").to_string());
+            return;
+        };
+        if session.stopped_thread.is_some() {
+            return;
+        }
+        let result = match session.last_thread_id {
+            Some(thread_id) => session.request(
+                "pause",
+                Some(&json!({ "threadId": thread_id })),
+                PendingDebugRequest::Control,
+            ),
+            // Nothing stopped yet, so no thread is known: ask for them
+            None => session.request("threads", None, PendingDebugRequest::Threads),
+        };
+        if let Err(e) = result {
+            self.set_status_message(t!("debug.adapter_error", error = e).to_string());
+        }
+    }
+
+    /// Toggle a breakpoint on the cursor line of the active buffer.
+    pub(crate) fn debug_toggle_breakpoint_at_cursor(&mut self) {
+        let buffer_id = self.active_buffer();
+        let cursor = self.active_cursors().primary().position;
+        let line = self.active_state().buffer.get_line_number(cursor);
+        self.debug_toggle_breakpoint(buffer_id, line);
+    }
+
+    /// Toggle a breakpoint on 0-based `line` of `buffer_id` and tell the
+    /// running session, if any.
+    pub(crate) fn debug_toggle_breakpoint(&mut self, buffer_id: BufferId, line: usize) {
+        let color = self.theme.read().unwrap().diagnostic_error_fg;
+        let Some(state) = self.buffers_mut().get_mut(&buffer_id) else {
+            return;
+        };
+        let Some(path) = state.buffer.file_path().map(Path::to_path_buf) else {
+            self.set_status_message(t!("debug.breakpoint_needs_file").to_string());
+            return;
+        };
+
+        let existing = state
+            .margins
+            .line_indicator_positions(BREAKPOINT_NAMESPACE)
+            .into_iter()
+            .find(|&(_, byte)| state.buffer.get_line_number(byte) == line);
+        match existing {
+            Some((marker_id, _)) => {
+                state
+                    .margins
+                    .remove_line_indicator(marker_id, BREAKPOINT_NAMESPACE);
+            }
+            None => {
+                let byte = state.buffer.line_start_offset(line).unwrap_or(0);
+                state.margins.set_line_indicator(
+                    byte,
+                    BREAKPOINT_NAMESPACE.to_string(),
+                    LineIndicator::new("●", color, BREAKPOINT_PRIORITY),
+                );
+            }
+        }
+
+        let lines = self.breakpoint_lines(buffer_id);
+        self.send_breakpoints(&path, &lines);
+    }
+
+    /// Whether 0-based `line` of `buffer_id` has a breakpoint.
+    pub(crate) fn has_breakpoint(&self, buffer_id: BufferId, line: usize) -> bool {
+        self.breakpoint_lines(buffer_id).contains(&line)
+    }
+
+    /// Park the breakpoints of a buffer that is being closed.
+    pub(crate) fn remember_breakpoints(&mut self, buffer_id: BufferId) {
+        let Some(path) = self
+            .buffers()
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path())
+            .map(Path::to_path_buf)
+        else {
+            return;
+        };
+        let lines = self.breakpoint_lines(buffer_id);
+        self.active_window_mut()
+            .debug
+            .store_breakpoints(path, lines);
+    }
+
+    /// Put back the breakpoints of a file that was open before.
+    pub(crate) fn restore_breakpoints(&mut self, buffer_id: BufferId, path: &Path) {
+        let Some(lines) = self.active_window_mut().debug.take_stored_breakpoints(path) else {
+            return;
+        };
+        let color = self.theme.read().unwrap().diagnostic_error_fg;
+        let Some(state) = self.buffers_mut().get_mut(&buffer_id) else {
+            return;
+        };
+        for line in lines {
+            // The file may have shrunk while it was closed
+            let Some(byte) = state.buffer.line_start_offset(line) else {
+                continue;
+            };
+            state.margins.set_line_indicator(
+                byte,
+                BREAKPOINT_NAMESPACE.to_string(),
+                LineIndicator::new("●", color, BREAKPOINT_PRIORITY),
+            );
+        }
+    }
+
+    /// 0-based breakpoint lines of an open buffer.
+    fn breakpoint_lines(&self, buffer_id: BufferId) -> BTreeSet<usize> {
+        let Some(state) = self.buffers().get(&buffer_id) else {
+            return BTreeSet::new();
+        };
+        state
+            .margins
+            .line_indicator_positions(BREAKPOINT_NAMESPACE)
+            .into_iter()
+            .map(|(_, byte)| state.buffer.get_line_number(byte))
+            .collect()
+    }
+
+    /// Breakpoints of every file, open or parked.
+    fn all_breakpoints(&self) -> Vec<(PathBuf, BTreeSet<usize>)> {
+        let mut all: Vec<(PathBuf, BTreeSet<usize>)> = self
+            .buffers()
+            .iter()
+            .filter_map(|(&id, state)| {
+                let path = state.buffer.file_path()?.to_path_buf();
+                let lines = self.breakpoint_lines(id);
+                (!lines.is_empty()).then_some((path, lines))
+            })
+            .collect();
+        all.extend(
+            self.active_window()
+                .debug
+                .stored_breakpoints()
+                .map(|(path, lines)| (path.clone(), lines.clone())),
+        );
+        all
+    }
+
+    /// Replace the breakpoints of one file in the running session.
+    fn send_breakpoints(&mut self, path: &Path, lines: &BTreeSet<usize>) {
+        let source = Source {
+            name: path.file_name().map(|n| n.to_string_lossy().into_owned()),
+            path: Some(self.debug_remote_path(path)),
+        };
+        let breakpoints: Vec<SourceBreakpoint> = lines
+            .iter()
+            .map(|&line| SourceBreakpoint { line: line + 1 })
+            .collect();
+        let arguments = json!({
+            "source": source,
+            "breakpoints": breakpoints,
+            "sourceModified": false,
+        });
+        let Some(session) = self
+            .active_window_mut()
+            .debug
+            .session
+            .as_mut()
+            .filter(|s| s.configured)
+        else {
+            return;
+        };
+        if let Err(e) = session.request(
+            "setBreakpoints",
+            Some(&arguments),
+            PendingDebugRequest::SetBreakpoints,
+        ) {
+            tracing::warn!("Failed to send breakpoints for {:?}: {}", path, e);
+        }
+    }
+
+    /// Path of a host file as the adapter sees it.
+    fn debug_remote_path(&self, path: &Path) -> String {
+        self.authority
+            .path_translation
+            .as_ref()
+            .and_then(|t| t.host_to_remote(path))
+            .unwrap_or_else(|| path.to_path_buf())
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Path of an adapter-side file on the host.
+    fn debug_host_path(&self, path: &str) -> PathBuf {
+        let path = Path::new(path);
+        self.authority
+            .path_translation
+            .as_ref()
+            .and_then(|t| t.remote_to_host(path))
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+
+    /// Handle a response from the debug adapter.
+    pub(crate) fn handle_dap_response(
+        &mut self,
+        session_id: u64,
+        request_seq: i64,
+        command: String,
+        success: bool,
+        message: Option<String>,
+        body: Option<Value>,
+    ) {
+        let Some(session) = self.active_window_mut().debug.session_mut(session_id) else {
+            return;
+        };
+        let Some(purpose) = session.take_pending(request_seq) else {
+            tracing::debug!("Ignoring DAP response to unknown request {}", request_seq);
+            return;
+        };
+        let message = message.unwrap_or_else(|| "unknown error".to_string());
+
+        match purpose {
+            PendingDebugRequest::Plugin(callback_id) => {
+                let callback_id = fresh_core::api::JsCallbackId::from(callback_id);
+                let plugin_manager = self.plugin_manager.read().unwrap();
+                if success {
+                    plugin_manager
+                        .resolve_callback(callback_id, body.unwrap_or(Value::Null).to_string());
+                } else {
+                    plugin_manager.reject_callback(callback_id, message);
+                }
+            }
+            PendingDebugRequest::Initialize | PendingDebugRequest::Start if !success => {
+                self.end_debug_session(
+                    session_id,
+                    Some(format!("{} failed: {}", command, message)),
+                );
+            }
+            PendingDebugRequest::Initialize => {
+                session.capabilities = body
+                    .and_then(|b| serde_json::from_value::<Capabilities>(b).ok())
+                    .unwrap_or_default();
+                let index = session.configuration;
+                let Some(configuration) = self.config.debug.configurations.get(index).cloned()
+                else {
+                    return;
+                };
+                let arguments = self.debug_start_arguments(&configuration.arguments);
+                let Some(session) = self.active_window_mut().debug.session_mut(session_id) else {
+                    return;
+                };
+                if let Err(e) = session.request(
+                    configuration.request.command(),
+                    Some(&arguments),
+                    PendingDebugRequest::Start,
+                ) {
+                    self.end_debug_session(session_id, Some(e));
+                }
+            }
+            PendingDebugRequest::Threads if success => {
+                let thread_id = body
+                    .as_ref()
+                    .and_then(|b| b.get("threads"))
+                    .and_then(|t| t.get(0))
+                    .and_then(|t| t.get("id"))
+                    .and_then(Value::as_i64);
+                if let Some(thread_id) = thread_id {
+                    session.send(
+                        "pause",
+                        Some(&json!({ "threadId": thread_id })),
+                        PendingDebugRequest::Control,
+                    );
+                }
+            }
+            PendingDebugRequest::StackTrace { thread_id, reason } if success => {
+                let top = body
+                    .and_then(|b| serde_json::from_value::<StackTraceResponseBody>(b).ok())
+                    .and_then(|b| b.stack_frames.into_iter().next());
+                self.debug_show_stop(session_id, thread_id, reason, top);
+            }
+            PendingDebugRequest::Disconnect => {
+                self.end_debug_session(session_id, None);
+            }
+            _ if !success => {
+                self.set_status_message(
+                    t!("debug.request_failed", command = command, message = message).to_string(),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle an event from the debug adapter.
+    pub(crate) fn handle_dap_event(&mut self, session_id: u64, event: String, body: Option<Value>) {
+        if self
+            .active_window_mut()
+            .debug
+            .session_mut(session_id)
+            .is_none()
+        {
+            return;
+        }
+        let body = body.unwrap_or(Value::Null);
+
+        match event.as_str() {
+            "initialized" => self.debug_configure(session_id),
+            "stopped" => {
+                let Ok(stopped) = serde_json::from_value::<StoppedEventBody>(body) else {
+                    return;
+                };
+                let Some(session) = self.active_window_mut().debug.session_mut(session_id) else {
+                    return;
+                };
+                let Some(thread_id) = stopped.thread_id.or(session.last_thread_id) else {
+                    return;
+                };
+                session.last_thread_id = Some(thread_id);
+                session.stopped_thread = Some(thread_id);
+                let arguments = json!({
+                    "threadId": thread_id,
+                    "startFrame": 0,
+                    "levels": STACK_TRACE_LEVELS,
+                });
+                session.send(
+                    "stackTrace",
+                    Some(&arguments),
+                    PendingDebugRequest::StackTrace {
+                        thread_id,
+                        reason: stopped.reason,
+                    },
+                );
+            }
+            "continued" => {
+                let thread_id = serde_json::from_value::<ContinuedEventBody>(body)
+                    .ok()
+                    .map(|b| b.thread_id);
+                self.debug_resumed(session_id, thread_id);
+            }
+            "output" => {
+                let Ok(output) = serde_json::from_value::<OutputEventBody>(body) else {
+                    return;
+                };
+                self.plugin_manager.read().unwrap().run_hook(
+                    "debug_output",
+                    HookArgs::DebugOutput {
+                        session_id,
+                        category: output.category.unwrap_or_else(|| "console".to_string()),
+                        output: output.output,
+                    },
+                );
+            }
+            "exited" => {
+                if let Ok(exited) = serde_json::from_value::<ExitedEventBody>(body) {
+                    tracing::info!(
+                        "Debuggee of session {} exited with code {}",
+                        session_id,
+                        exited.exit_code
+                    );
+                }
+            }
+            "terminated" => {
+                // The debuggee is gone; release the adapter too
+                let Some(session) = self.active_window_mut().debug.session_mut(session_id) else {
+                    return;
+                };
+                if session
+                    .request("disconnect", None, PendingDebugRequest::Disconnect)
+                    .is_err()
+                {
+                    self.end_debug_session(session_id, None);
+                }
+            }
+            _ => tracing::trace!("Unhandled DAP event '{}'", event),
+        }
+    }
+
+    /// Handle the adapter process going away.
+    pub(crate) fn handle_dap_exited(&mut self, session_id: u64, error: Option<String>) {
+        self.end_debug_session(session_id, error);
+    }
+
+    /// Send a plugin's DAP request to the running session.
+    pub(crate) fn handle_send_debug_request(
+        &mut self,
+        command: String,
+        arguments: Option<Value>,
+        request_id: u64,
+    ) {
+        let error = match self.active_window_mut().debug.session.as_mut() {
+            Some(session) => session
+                .request(
+                    &command,
+                    arguments.as_ref(),
+                    PendingDebugRequest::Plugin(request_id),
+                )
+                .err(),
+            None => Some(t!("debug.no_session").to_string()),
+        };
+        if let Some(err_msg) = error {
+            self.plugin_manager
+                .read()
+                .unwrap()
+                .reject_callback(fresh_core::api::JsCallbackId::from(request_id), err_msg);
+        }
+    }
+
+    /// Launch/attach arguments with editor variables substituted.
+    fn debug_start_arguments(&self, arguments: &std::collections::HashMap<String, Value>) -> Value {
+        let workspace_folder = self.debug_remote_path(&self.working_dir);
+        let file = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| self.debug_remote_path(p))
+            .unwrap_or_default();
+        let arguments: serde_json::Map<String, Value> = arguments
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        substitute_variables(&Value::Object(arguments), &workspace_folder, &file)
+    }
+
+    /// Answer the adapter's `initialized` event with every breakpoint.
+    fn debug_configure(&mut self, session_id: u64) {
+        let Some(session) = self.active_window_mut().debug.session_mut(session_id) else {
+            return;
+        };
+        session.configured = true;
+
+        for (path, lines) in self.all_breakpoints() {
+            self.send_breakpoints(&path, &lines);
+        }
+
+        let Some(session) = self.active_window_mut().debug.session_mut(session_id) else {
+            return;
+        };
+        session.send(
+            "setExceptionBreakpoints",
+            Some(&json!({ "filters": [] })),
+            PendingDebugRequest::Control,
+        );
+        if session.capabilities.supports_configuration_done_request {
+            session.send(
+                "configurationDone",
+                None,
+                PendingDebugRequest::ConfigurationDone,
+            );
+        }
+    }
+
+    /// Jump to the top stack frame of a stopped thread and mark its line.
+    fn debug_show_stop(
+        &mut self,
+        session_id: u64,
+        thread_id: i64,
+        reason: String,
+        top: Option<crate::services::dap::protocol::StackFrame>,
+    ) {
+        let frame_id = top.as_ref().map(|f| f.id);
+
+        self.clear_debug_location();
+        let location = top.as_ref().and_then(|frame| {
+            let path = frame.source.as_ref()?.path.as_deref()?;
+            Some((self.debug_host_path(path), frame.line, frame.column))
+        });
+        if let Some((path, line, column)) = &location {
+            match self.open_file(path) {
+                Ok(buffer_id) => {
+                    self.goto_line_col(*line, Some((*column).max(1)));
+                    self.mark_debug_location(buffer_id, line.saturating_sub(1));
+                }
+                Err(e) => tracing::warn!("Cannot open stopped frame {:?}: {}", path, e),
+            }
+        }
+
+        self.set_status_message(t!("debug.paused", reason = reason).to_string());
+        self.plugin_manager.read().unwrap().run_hook(
+            "debug_stopped",
+            HookArgs::DebugStopped {
+                session_id,
+                reason,
+                thread_id: Some(thread_id),
+                frame_id,
+                file: location.as_ref().map(|(path, _, _)| path.clone()),
+                line: location.as_ref().map(|&(_, line, _)| line),
+            },
+        );
+    }
+
+    /// The debuggee runs again: drop the current-line marker.
+    fn debug_resumed(&mut self, session_id: u64, thread_id: Option<i64>) {
+        let Some(session) = self.active_window_mut().debug.session_mut(session_id) else {
+            return;
+        };
+        if session.stopped_thread.take().is_none() {
+            return;
+        }
+        self.clear_debug_location();
+        self.set_status_message(t!("debug.running").to_string());
+        self.plugin_manager.read().unwrap().run_hook(
+            "debug_continued",
+            HookArgs::DebugContinued {
+                session_id,
+                thread_id,
+            },
+        );
+    }
+
+    /// Tear down session `session_id` and report why it ended.
+    fn end_debug_session(&mut self, session_id: u64, error: Option<String>) {
+        let window = self.active_window_mut();
+        if window.debug.session_mut(session_id).is_none() {
+            return;
+        }
+        // Dropping the handle shuts the adapter down
+        let Some(session) = window.debug.session.take() else {
+            return;
+        };
+        for callback_id in session.pending_plugin_callbacks() {
+            self.plugin_manager.read().unwrap().reject_callback(
+                fresh_core::api::JsCallbackId::from(callback_id),
+                t!("debug.ended").to_string(),
+            );
+        }
+        drop(session);
+
+        self.clear_debug_location();
+        match &error {
+            Some(e) => {
+                tracing::warn!("Debug session {} failed: {}", session_id, e);
+                self.set_status_message(t!("debug.adapter_error", error = e).to_string());
+            }
+            None => self.set_status_message(t!("debug.ended").to_string()),
+        }
+        self.plugin_manager
+            .read()
+            .unwrap()
+            .run_hook("debug_ended", HookArgs::DebugEnded { session_id, error });
+    }
+
+    /// Draw the current-line marker on 0-based `line` of `buffer_id`.
+    fn mark_debug_location(&mut self, buffer_id: BufferId, line: usize) {
+        let color = self.theme.read().unwrap().diagnostic_warning_fg;
+        let Some(state) = self.buffers_mut().get_mut(&buffer_id) else {
+            return;
+        };
+        let Some(start) = state.buffer.line_start_offset(line) else {
+            return;
+        };
+        let end = state
+            .buffer
+            .line_start_offset(line + 1)
+            .unwrap_or_else(|| state.buffer.len());
+        state.margins.set_line_indicator(
+            start,
+            CURRENT_LINE_NAMESPACE.to_string(),
+            LineIndicator::new("▶", color, CURRENT_LINE_PRIORITY),
+        );
+        let overlay = Overlay::with_namespace(
+            &mut state.marker_list,
+            start..end,
+            OverlayFace::ThemedStyle {
+                fallback_style: ratatui::style::Style::default()
+                    .bg(ratatui::style::Color::Rgb(64, 56, 16)),
+                fg_theme: None,
+                bg_theme: Some("diagnostic.warning_bg".to_string()),
+            },
+            OverlayNamespace::from_string(CURRENT_LINE_NAMESPACE.to_string()),
+        )
+        .with_extend_to_line_end(true)
+        .with_priority_value(CURRENT_LINE_PRIORITY);
+        state.overlays.add(overlay);
+    }
+
+    /// Remove the current-line marker from every buffer.
+    fn clear_debug_location(&mut self) {
+        let namespace = OverlayNamespace::from_string(CURRENT_LINE_NAMESPACE.to_string());
+        for state in self.buffers_mut().values_mut() {
+            state
+                .margins
+                .clear_line_indicators_for_namespace(CURRENT_LINE_NAMESPACE);
+            state
+                .overlays
+                .clear_namespace(&namespace, &mut state.marker_list);
+        }
+    }
+}
@@ -1,20 +1,27 @@
-//! Cursor navigation between LSP diagnostics on `Editor`.
+//! Cursor navigation between diagnostics on `Editor`.
 //!
 //! `jump_to_next_error` and `jump_to_previous_error` walk the active
-//! buffer's diagnostic overlays, find the nearest one in each direction,
-//! and emit a MoveCursor event. Status messages report the diagnostic at
-//! the new cursor position.
+//! buffer's diagnostic overlays (from language servers and task output),
+//! find the nearest one in each direction, and emit a MoveCursor event.
+//! Status messages report the diagnostic at the new cursor position.
+//!
+//! While the last task run left problems behind they take over: the
+//! jumps step through that list instead, across files, like the
+//! quickfix list it is shown in.
 
 use rust_i18n::t;
 
 use crate::model::event::Event;
+use crate::services::lsp::diagnostics::is_diagnostic_namespace;
 
 use super::Editor;
 
 impl Editor {
     /// Jump to next error/diagnostic
     pub(super) fn jump_to_next_error(&mut self) {
-        let diagnostic_ns = self.active_window().lsp_diagnostic_namespace.clone();
+        if self.jump_to_task_problem(true) {
+            return;
+        }
         let cursor_pos = self.active_cursors().primary().position;
         let cursor_id = self.active_cursors().primary_id();
         let cursor = *self.active_cursors().primary();
@@ -26,8 +33,8 @@ impl Editor {
             .all()
             .iter()
             .filter_map(|overlay| {
-                // Only consider LSP diagnostics (those in the diagnostic namespace)
-                if overlay.namespace.as_ref() == Some(&diagnostic_ns) {
+                // Only consider diagnostics (those in a diagnostic namespace)
+                if is_diagnostic_namespace(overlay.namespace.as_ref()) {
                     Some(overlay.range(&state.marker_list).start)
                 } else {
                     None
@@ -72,7 +79,7 @@ impl Editor {
             let state = self.active_state();
             if let Some(msg) = state.overlays.all().iter().find_map(|overlay| {
                 let range = overlay.range(&state.marker_list);
-                if range.start == new_pos && overlay.namespace.as_ref() == Some(&diagnostic_ns) {
+                if range.start == new_pos && is_diagnostic_namespace(overlay.namespace.as_ref()) {
                     overlay.message.clone()
                 } else {
                     None
@@ -85,7 +92,9 @@ impl Editor {
 
     /// Jump to previous error/diagnostic
     pub(super) fn jump_to_previous_error(&mut self) {
-        let diagnostic_ns = self.active_window().lsp_diagnostic_namespace.clone();
+        if self.jump_to_task_problem(false) {
+            return;
+        }
         let cursor_pos = self.active_cursors().primary().position;
         let cursor_id = self.active_cursors().primary_id();
         let cursor = *self.active_cursors().primary();
@@ -97,8 +106,8 @@ impl Editor {
             .all()
             .iter()
             .filter_map(|overlay| {
-                // Only consider LSP diagnostics (those in the diagnostic namespace)
-                if overlay.namespace.as_ref() == Some(&diagnostic_ns) {
+                // Only consider diagnostics (those in a diagnostic namespace)
+                if is_diagnostic_namespace(overlay.namespace.as_ref()) {
                     Some(overlay.range(&state.marker_list).start)
                 } else {
                     None
@@ -144,7 +153,7 @@ impl Editor {
             let state = self.active_state();
             if let Some(msg) = state.overlays.all().iter().find_map(|overlay| {
                 let range = overlay.range(&state.marker_list);
-                if range.start == new_pos && overlay.namespace.as_ref() == Some(&diagnostic_ns) {
+                if range.start == new_pos && is_diagnostic_namespace(overlay.namespace.as_ref()) {
                     overlay.message.clone()
                 } else {
                     None
@@ -0,0 +1,248 @@
+//! Document outline orchestrators.
+//!
+//! An outline is computed from the buffer's tree-sitter tags query right
+//! away and replaced by the language server's `textDocument/documentSymbol`
+//! answer when it arrives. Consumers read the per-window cache in
+//! `Window::document_symbols`: the `@` quick-open provider refreshes its
+//! list when the answer lands, and plugin `getDocumentSymbols` calls are
+//! resolved once the best available outline is known.
+
+use std::sync::Arc;
+
+use crate::app::document_symbols::{CachedOutline, PendingDocumentSymbols};
+use crate::model::event::BufferId;
+use crate::primitives::document_symbols::{lsp_symbols, symbol_kind_name, OutlineSymbol};
+use crate::types::LspFeature;
+use crate::view::prompt::PromptType;
+use fresh_core::api::{DocumentSymbolInfo, PluginResponse};
+
+use super::Editor;
+
+impl Editor {
+    /// Bring the outline of `buffer_id` as up to date as is possible right
+    /// now, asking the language server for a better one if needed.
+    ///
+    /// Returns the id of the LSP request the outline is still waiting on.
+    pub(crate) fn refresh_document_symbols(&mut self, buffer_id: BufferId) -> Option<u64> {
+        let version = self.buffers().get(&buffer_id)?.buffer.version();
+        let symbols = &self.active_window().document_symbols;
+        if symbols.is_complete(buffer_id, version) {
+            return None;
+        }
+        if let Some(request_id) = symbols.pending_request_for(buffer_id, version) {
+            return Some(request_id);
+        }
+        if symbols.cached(buffer_id).map(|c| c.version) != Some(version) {
+            self.compute_syntax_symbols(buffer_id, version);
+        }
+
+        let request_id = self.request_document_symbols(buffer_id, version);
+        if request_id.is_none() {
+            self.active_window_mut()
+                .document_symbols
+                .mark_complete(buffer_id, version);
+        }
+        request_id
+    }
+
+    /// Keep the outline of a visible buffer fresh for the breadcrumb bar:
+    /// right away if there is none yet, else once edits have paused.
+    pub(crate) fn maybe_refresh_document_symbols_debounced(&mut self, buffer_id: BufferId) {
+        let symbols = &mut self.active_window_mut().document_symbols;
+        let due = symbols.take_due(buffer_id, std::time::Instant::now());
+        if due || symbols.cached(buffer_id).is_none() {
+            self.refresh_document_symbols(buffer_id);
+        }
+    }
+
+    /// Cache the tree-sitter outline of `buffer_id` (empty if the grammar
+    /// has no tags query).
+    fn compute_syntax_symbols(&mut self, buffer_id: BufferId, version: u64) {
+        let window = self.active_window_mut();
+        let Some(state) = window.buffers.get(&buffer_id) else {
+            return;
+        };
+        let symbols = state
+            .highlighter
+            .language()
+            .and_then(|language| {
+                window
+                    .document_symbols
+                    .extractor
+                    .symbols(&state.buffer, language)
+            })
+            .unwrap_or_default();
+        window.document_symbols.store(
+            buffer_id,
+            CachedOutline {
+                version,
+                complete: false,
+                symbols: Arc::new(symbols),
+            },
+        );
+    }
+
+    /// Ask the language server for the outline of `buffer_id`.
+    /// Returns `None` when no server for this buffer provides one.
+    fn request_document_symbols(&mut self, buffer_id: BufferId, version: u64) -> Option<u64> {
+        let request_id = self.active_window_mut().next_lsp_request_id;
+
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::DocumentSymbols,
+                |handle, uri, _language| {
+                    let result = handle.document_symbols(request_id, uri.as_uri().clone());
+                    if result.is_ok() {
+                        tracing::info!("Requested document symbols for {}", uri.as_str());
+                    }
+                    result.is_ok()
+                },
+            )
+            .unwrap_or(false);
+        if !sent {
+            return None;
+        }
+
+        let window = self.active_window_mut();
+        window.next_lsp_request_id += 1;
+        window.document_symbols.add_pending(
+            request_id,
+            PendingDocumentSymbols {
+                buffer_id,
+                version,
+                plugin_requests: Vec::new(),
+            },
+        );
+        Some(request_id)
+    }
+
+    /// Handle LSP document symbol response
+    pub(crate) fn handle_document_symbols_response(
+        &mut self,
+        request_id: u64,
+        symbols: Option<lsp_types::DocumentSymbolResponse>,
+    ) {
+        let Some(pending) = self
+            .active_window_mut()
+            .document_symbols
+            .take_pending(request_id)
+        else {
+            tracing::debug!(
+                "Ignoring document symbol response for unknown request {}",
+                request_id
+            );
+            return;
+        };
+
+        let current_version = self
+            .buffers()
+            .get(&pending.buffer_id)
+            .map(|state| state.buffer.version());
+        // A failed request or an edit since it was sent leaves the
+        // tree-sitter outline in place.
+        if current_version == Some(pending.version) {
+            if let Some(response) = symbols {
+                let symbols = lsp_symbols(&self.buffers()[&pending.buffer_id].buffer, response);
+                self.active_window_mut().document_symbols.store(
+                    pending.buffer_id,
+                    CachedOutline {
+                        version: pending.version,
+                        complete: true,
+                        symbols: Arc::new(symbols),
+                    },
+                );
+            } else {
+                self.active_window_mut()
+                    .document_symbols
+                    .mark_complete(pending.buffer_id, pending.version);
+            }
+        }
+
+        for plugin_request_id in pending.plugin_requests {
+            self.send_document_symbols_to_plugin(pending.buffer_id, plugin_request_id);
+        }
+        if pending.buffer_id == self.active_buffer() {
+            self.refresh_quick_open_symbols();
+        }
+    }
+
+    /// Handle GetDocumentSymbols plugin command
+    pub(super) fn handle_get_document_symbols(&mut self, buffer_id: BufferId, request_id: u64) {
+        match self.refresh_document_symbols(buffer_id) {
+            Some(lsp_request_id) => self
+                .active_window_mut()
+                .document_symbols
+                .add_plugin_waiter(lsp_request_id, request_id),
+            None => self.send_document_symbols_to_plugin(buffer_id, request_id),
+        }
+    }
+
+    /// Resolve a plugin `getDocumentSymbols` call with the cached outline.
+    pub(super) fn send_document_symbols_to_plugin(&self, buffer_id: BufferId, request_id: u64) {
+        let symbols = self
+            .active_window()
+            .document_symbols
+            .cached(buffer_id)
+            .map(|cached| cached.symbols.iter().map(to_plugin_symbol).collect())
+            .unwrap_or_default();
+        self.send_plugin_response(PluginResponse::DocumentSymbols {
+            request_id,
+            symbols,
+        });
+    }
+
+    /// Re-ask servers that just started for the outlines of their buffers.
+    pub(super) fn invalidate_document_symbols_for_language(&mut self, language: &str) {
+        for (buffer_id, _) in self.buffers_for_language(language) {
+            self.active_window_mut()
+                .document_symbols
+                .invalidate(buffer_id);
+        }
+    }
+
+    /// Forget the outline of a closed buffer, resolving plugin calls that
+    /// were still waiting on it with an empty outline.
+    pub(super) fn forget_document_symbols(&mut self, buffer_id: BufferId) {
+        let waiting = self
+            .active_window_mut()
+            .document_symbols
+            .remove_buffer(buffer_id);
+        for request_id in waiting {
+            self.send_plugin_response(PluginResponse::DocumentSymbols {
+                request_id,
+                symbols: Vec::new(),
+            });
+        }
+    }
+
+    /// Rebuild the `@` quick-open list if it is showing, e.g. after better
+    /// symbols arrived from the language server.
+    fn refresh_quick_open_symbols(&mut self) {
+        let Some(input) = self
+            .active_window()
+            .prompt
+            .as_ref()
+            .filter(|prompt| prompt.prompt_type == PromptType::QuickOpen)
+            .map(|prompt| prompt.input.clone())
+        else {
+            return;
+        };
+        if self.quick_open_shows_symbols(&input) {
+            self.update_quick_open_suggestions(&input);
+        }
+    }
+}
+
+fn to_plugin_symbol(symbol: &OutlineSymbol) -> DocumentSymbolInfo {
+    DocumentSymbolInfo {
+        name: symbol.name.clone(),
+        detail: symbol.detail.clone(),
+        kind: symbol_kind_name(symbol.kind).to_string(),
+        start: symbol.range.start as u32,
+        end: symbol.range.end as u32,
+        name_offset: symbol.selection_range.start as u32,
+        line: symbol.line as u32,
+        depth: symbol.depth as u32,
+    }
+}
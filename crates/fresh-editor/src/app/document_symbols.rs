@@ -0,0 +1,236 @@
+//! Per-window cache of document outlines.
+//!
+//! Outlines are keyed by buffer and stamped with the buffer version they
+//! were computed for. Tree-sitter symbols are stored first and marked
+//! complete once the language server has answered for that version (or
+//! there is no server to ask). An outdated entry is still served while a
+//! fresh one is being computed, since slightly shifted offsets are better
+//! than an empty outline.
+//!
+//! The breadcrumb bar keeps the outline of visible buffers fresh; edits
+//! push its refresh back until typing pauses.
+//!
+//! Requesting and consuming outlines (quick open `@`, the plugin
+//! `getDocumentSymbols` API) lives on `Editor` in
+//! `document_symbol_actions.rs`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::model::event::BufferId;
+use crate::primitives::document_symbols::{OutlineSymbol, SyntaxSymbolExtractor};
+
+/// Pause after the last edit before the breadcrumb bar refreshes an outline.
+const DOCUMENT_SYMBOLS_DEBOUNCE_MS: u64 = 300;
+
+/// Outline of one buffer.
+#[derive(Debug, Clone)]
+pub(crate) struct CachedOutline {
+    /// Buffer version the symbols were computed for
+    pub version: u64,
+    /// Whether nothing better is expected for this version: the language
+    /// server has answered, or there is none to ask
+    pub complete: bool,
+    pub symbols: Arc<Vec<OutlineSymbol>>,
+}
+
+/// A `textDocument/documentSymbol` request waiting for its answer.
+#[derive(Debug, Clone)]
+pub(crate) struct PendingDocumentSymbols {
+    pub buffer_id: BufferId,
+    /// Buffer version at request time
+    pub version: u64,
+    /// Plugin `getDocumentSymbols` calls to resolve with the answer
+    pub plugin_requests: Vec<u64>,
+}
+
+/// Owner of the outline cache for one window.
+#[derive(Default)]
+pub(crate) struct DocumentSymbolState {
+    pub extractor: SyntaxSymbolExtractor,
+    cache: HashMap<BufferId, CachedOutline>,
+    /// In-flight LSP requests by request id
+    pending: HashMap<u64, PendingDocumentSymbols>,
+    /// Refresh deadline per edited buffer
+    debounce: HashMap<BufferId, Instant>,
+}
+
+impl DocumentSymbolState {
+    /// The cached outline of `buffer_id`, whatever its version.
+    pub(crate) fn cached(&self, buffer_id: BufferId) -> Option<&CachedOutline> {
+        self.cache.get(&buffer_id)
+    }
+
+    /// Whether the cached outline of `buffer_id` is complete for `version`.
+    pub(crate) fn is_complete(&self, buffer_id: BufferId, version: u64) -> bool {
+        self.cache
+            .get(&buffer_id)
+            .is_some_and(|c| c.complete && c.version == version)
+    }
+
+    /// Refresh the outline of `buffer_id` once edits have paused.
+    pub(crate) fn schedule(&mut self, buffer_id: BufferId) {
+        self.debounce.insert(
+            buffer_id,
+            Instant::now() + Duration::from_millis(DOCUMENT_SYMBOLS_DEBOUNCE_MS),
+        );
+    }
+
+    /// Whether the debounce of `buffer_id` has elapsed; clears it if so.
+    pub(crate) fn take_due(&mut self, buffer_id: BufferId, now: Instant) -> bool {
+        match self.debounce.get(&buffer_id) {
+            Some(&ready_at) if now >= ready_at => {
+                self.debounce.remove(&buffer_id);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub(crate) fn store(&mut self, buffer_id: BufferId, outline: CachedOutline) {
+        self.cache.insert(buffer_id, outline);
+    }
+
+    /// Mark the outline of `buffer_id` complete if it is for `version`.
+    pub(crate) fn mark_complete(&mut self, buffer_id: BufferId, version: u64) {
+        if let Some(cached) = self.cache.get_mut(&buffer_id) {
+            if cached.version == version {
+                cached.complete = true;
+            }
+        }
+    }
+
+    /// Let the next refresh ask the language server again, e.g. because a
+    /// server just finished starting.
+    pub(crate) fn invalidate(&mut self, buffer_id: BufferId) {
+        if let Some(cached) = self.cache.get_mut(&buffer_id) {
+            cached.complete = false;
+        }
+    }
+
+    /// Request id of the in-flight request for `buffer_id` at `version`.
+    pub(crate) fn pending_request_for(&self, buffer_id: BufferId, version: u64) -> Option<u64> {
+        self.pending
+            .iter()
+            .find(|(_, p)| p.buffer_id == buffer_id && p.version == version)
+            .map(|(&request_id, _)| request_id)
+    }
+
+    pub(crate) fn add_pending(&mut self, request_id: u64, pending: PendingDocumentSymbols) {
+        self.pending.insert(request_id, pending);
+    }
+
+    /// Register a plugin call to resolve when `request_id` is answered.
+    pub(crate) fn add_plugin_waiter(&mut self, request_id: u64, plugin_request_id: u64) {
+        if let Some(pending) = self.pending.get_mut(&request_id) {
+            pending.plugin_requests.push(plugin_request_id);
+        }
+    }
+
+    pub(crate) fn take_pending(&mut self, request_id: u64) -> Option<PendingDocumentSymbols> {
+        self.pending.remove(&request_id)
+    }
+
+    /// Forget everything about a closed buffer.
+    ///
+    /// Returns the plugin calls that were still waiting on it.
+    pub(crate) fn remove_buffer(&mut self, buffer_id: BufferId) -> Vec<u64> {
+        self.cache.remove(&buffer_id);
+        self.debounce.remove(&buffer_id);
+        let mut waiting = Vec::new();
+        self.pending.retain(|_, p| {
+            if p.buffer_id == buffer_id {
+                waiting.append(&mut p.plugin_requests);
+                false
+            } else {
+                true
+            }
+        });
+        waiting
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outline(version: u64, name: &str) -> CachedOutline {
+        CachedOutline {
+            version,
+            complete: false,
+            symbols: Arc::new(vec![OutlineSymbol {
+                name: name.to_string(),
+                detail: None,
+                kind: lsp_types::SymbolKind::FUNCTION,
+                range: 0..1,
+                selection_range: 0..1,
+                line: 0,
+                depth: 0,
+                container: None,
+            }]),
+        }
+    }
+
+    fn cached_name(state: &DocumentSymbolState, buffer_id: BufferId) -> &str {
+        &state.cached(buffer_id).unwrap().symbols[0].name
+    }
+
+    #[test]
+    fn completeness_is_per_version() {
+        let mut state = DocumentSymbolState::default();
+        let buf = BufferId(1);
+        state.store(buf, outline(3, "syntax"));
+        assert!(!state.is_complete(buf, 3));
+
+        state.mark_complete(buf, 2);
+        assert!(!state.is_complete(buf, 3), "stale version is ignored");
+        state.mark_complete(buf, 3);
+        assert!(state.is_complete(buf, 3));
+        assert!(!state.is_complete(buf, 4));
+
+        state.invalidate(buf);
+        assert!(!state.is_complete(buf, 3));
+        assert_eq!(cached_name(&state, buf), "syntax");
+    }
+
+    #[test]
+    fn pending_requests_collect_plugin_waiters() {
+        let mut state = DocumentSymbolState::default();
+        let buf = BufferId(1);
+        state.add_pending(
+            7,
+            PendingDocumentSymbols {
+                buffer_id: buf,
+                version: 2,
+                plugin_requests: vec![],
+            },
+        );
+        assert_eq!(state.pending_request_for(buf, 2), Some(7));
+        assert_eq!(state.pending_request_for(buf, 3), None);
+
+        state.add_plugin_waiter(7, 100);
+        state.add_plugin_waiter(7, 101);
+        let pending = state.take_pending(7).unwrap();
+        assert_eq!(pending.plugin_requests, vec![100, 101]);
+        assert!(state.take_pending(7).is_none());
+    }
+
+    #[test]
+    fn remove_buffer_returns_waiting_plugin_calls() {
+        let mut state = DocumentSymbolState::default();
+        let buf = BufferId(1);
+        state.store(buf, outline(1, "a"));
+        state.add_pending(
+            7,
+            PendingDocumentSymbols {
+                buffer_id: buf,
+                version: 1,
+                plugin_requests: vec![100],
+            },
+        );
+        assert_eq!(state.remove_buffer(buf), vec![100]);
+        assert!(state.cached(buf).is_none());
+        assert_eq!(state.pending_request_for(buf, 1), None);
+    }
+}
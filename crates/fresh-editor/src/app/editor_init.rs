@@ -116,6 +116,9 @@ pub(super) struct EditorParts {
     pub(super) theme_registry: Arc<crate::view::theme::ThemeRegistry>,
     pub(super) theme_cache: Arc<RwLock<HashMap<String, serde_json::Value>>>,
 
+    // Snippets
+    pub(super) snippet_library: Arc<crate::services::completion::SnippetLibrary>,
+
     // Grammar
     pub(super) grammar_registry: Arc<crate::primitives::grammar::GrammarRegistry>,
     pub(super) pending_grammars: Vec<PendingGrammar>,
@@ -198,6 +201,7 @@ impl Editor {
             theme: parts.theme,
             theme_registry: parts.theme_registry,
             theme_cache: parts.theme_cache,
+            snippet_library: parts.snippet_library,
             keybindings: parts.keybindings,
             terminal_width: parts.terminal_width,
             terminal_height: parts.terminal_height,
@@ -240,6 +244,8 @@ impl Editor {
             mode_registry: ModeRegistry::new(),
             pending_authority: None,
             remote_indicator_override: None,
+            pending_plugin_permissions: std::collections::VecDeque::new(),
+            client_views: Default::default(),
             menus: crate::config::MenuConfig::translated(),
             background_process_handles: HashMap::new(),
             host_process_handles: HashMap::new(),
@@ -487,6 +493,12 @@ impl Editor {
         t.phase("theme_loader.load_all");
         tracing::info!("Themes loaded");
 
+        let snippet_library = Arc::new(crate::services::completion::SnippetLibrary::load(
+            &dir_context.config_dir,
+            &scan_result.snippet_files,
+        ));
+        t.phase("snippets");
+
         // Get active theme from registry, falling back to default if not found
         let theme_inner = theme_registry.get_cloned(&config.theme).unwrap_or_else(|| {
             tracing::warn!(
@@ -706,6 +718,8 @@ impl Editor {
         )));
         quick_open_registry.register(Box::new(BufferProvider::new()));
         quick_open_registry.register(Box::new(GotoLineProvider::new()));
+        quick_open_registry.register(Box::new(DocumentSymbolProvider::new()));
+        quick_open_registry.register(Box::new(WorkspaceSymbolProvider::new()));
 
         // Build shared theme cache for plugin access
         let theme_cache = Arc::new(RwLock::new(theme_registry.to_json_map()));
@@ -717,6 +731,7 @@ impl Editor {
             Arc::clone(&command_registry),
             dir_context.clone(),
             Arc::clone(&theme_cache),
+            &config.editor,
         )));
         t.phase("PluginManager::new");
 
@@ -736,6 +751,12 @@ impl Editor {
             populate_builtin_keybinding_labels(&mut snapshot, &keybindings);
         }
 
+        // Restore the user's plugin permission answers before any plugin runs
+        super::plugin_permissions::sync_plugin_grants(
+            &plugin_manager.read().unwrap(),
+            &config.plugins,
+        );
+
         // Load TypeScript plugins from multiple directories:
         // 1. Next to the executable (for cargo-dist installations)
         // 2. From embedded plugins (for cargo-binstall and `cargo run`,
@@ -1026,6 +1047,7 @@ impl Editor {
             config: Arc::clone(&config_arc),
             grammar_registry: Arc::clone(&grammar_registry),
             theme_registry: Arc::clone(&theme_registry),
+            snippet_library: Arc::clone(&snippet_library),
             theme_cache: Arc::clone(&theme_cache),
             keybindings: Arc::clone(&keybindings),
             command_registry: Arc::clone(&command_registry),
@@ -1110,6 +1132,7 @@ impl Editor {
                     config: Arc::clone(&config_arc),
                     grammar_registry: Arc::clone(&grammar_registry),
                     theme_registry: Arc::clone(&theme_registry),
+                    snippet_library: Arc::clone(&snippet_library),
                     theme_cache: Arc::clone(&theme_cache),
                     keybindings: Arc::clone(&keybindings),
                     command_registry: Arc::clone(&command_registry),
@@ -1193,6 +1216,7 @@ impl Editor {
             theme,
             theme_registry,
             theme_cache,
+            snippet_library,
             grammar_registry,
             pending_grammars,
             needs_full_grammar_build: true,
@@ -128,6 +128,9 @@ impl Editor {
                 win.invalidate_layouts_for_buffer(buf);
                 win.schedule_semantic_tokens_full_refresh(buf);
                 win.schedule_folding_ranges_refresh(buf);
+                win.code_lens.schedule(buf);
+                win.document_symbols.schedule(buf);
+                win.spell_check.schedule(buf);
             }
             Event::Batch { events, .. } => {
                 let has_edits = events
@@ -139,6 +142,9 @@ impl Editor {
                     win.invalidate_layouts_for_buffer(buf);
                     win.schedule_semantic_tokens_full_refresh(buf);
                     win.schedule_folding_ranges_refresh(buf);
+                    win.code_lens.schedule(buf);
+                    win.document_symbols.schedule(buf);
+                    win.spell_check.schedule(buf);
                 }
             }
             _ => {}
@@ -147,6 +153,8 @@ impl Editor {
         // 2. Adjust cursors in other splits that share the same buffer
         self.active_window_mut()
             .adjust_other_split_cursors_for_event(event);
+        let buf = self.active_buffer();
+        self.adjust_client_views_for_event(buf, event);
 
         // 3. Clear search highlights on edit (Insert/Delete events)
         // This preserves highlights while navigating but clears them when modifying text
@@ -494,6 +502,7 @@ impl Editor {
         let win = self.active_window_mut();
         win.invalidate_layouts_for_buffer(buf);
         win.adjust_other_split_cursors_for_event(&bulk_edit);
+        self.adjust_client_views_for_event(buf, &bulk_edit);
         // Note: Do NOT clear search overlays - markers track through edits for F3/Shift+F3
 
         // Notify LSP of the change using full document replacement.
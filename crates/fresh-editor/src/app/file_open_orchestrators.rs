@@ -442,6 +442,9 @@ impl Editor {
         self.active_window_mut()
             .restore_global_file_state(buffer_id, path, target_split);
 
+        // Restore the undo history from a previous session (if enabled)
+        self.restore_undo_history(buffer_id, path);
+
         // Emit control event
         self.emit_event(
             crate::model::control_event::events::FILE_OPENED.name,
@@ -454,6 +457,12 @@ impl Editor {
         // Track file for auto-revert and conflict detection
         self.watch_file(path);
 
+        // Put back breakpoints set before the file was last closed
+        self.restore_breakpoints(buffer_id, path);
+
+        // Draw problems the last task run found in this file
+        self.apply_task_problems(buffer_id, path);
+
         // Fire AfterFileOpen hook for plugins
         self.plugin_manager.read().unwrap().run_hook(
             "after_file_open",
@@ -1150,9 +1150,10 @@ impl Editor {
                     }
                 }
 
-                // Schedule folding range refresh
+                // Schedule folding range and code lens refresh
                 self.active_window_mut()
                     .schedule_folding_ranges_refresh(buffer_id);
+                self.active_window_mut().code_lens.schedule(buffer_id);
             }
             LspSpawnResult::NotAutoStart => {
                 tracing::debug!(
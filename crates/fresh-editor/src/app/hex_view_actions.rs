@@ -0,0 +1,282 @@
+//! Hex view actions.
+//!
+//! The hex view of a split is toggled per buffer view; while it is shown,
+//! navigation and typing keys are routed here before keybinding
+//! resolution. Edits overwrite bytes in place — the buffer never changes
+//! length — and are recorded as snapshot-based `BulkEdit`s, which undo
+//! regardless of whether the bytes are valid UTF-8. Saving goes through
+//! the regular save path, so unchanged regions of large files are copied
+//! rather than rewritten.
+
+use anyhow::Result as AnyhowResult;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use lsp_types::TextDocumentContentChangeEvent;
+use rust_i18n::t;
+
+use crate::app::Editor;
+use crate::model::event::{CursorId, Event};
+use crate::view::hex_view::{
+    find_bytes, parse_byte_pattern, parse_offset, set_nibble, HexColumn, HexViewState,
+    BYTES_PER_ROW,
+};
+use crate::view::prompt::PromptType;
+
+impl Editor {
+    /// Hex view state of the active buffer in the active split, if shown.
+    fn active_hex_view_mut(&mut self) -> Option<&mut HexViewState> {
+        let split_id = self.effective_active_split();
+        let buffer_id = self.active_buffer();
+        self.split_view_states_mut()
+            .get_mut(&split_id)?
+            .keyed_states
+            .get_mut(&buffer_id)?
+            .hex_view
+            .as_mut()
+    }
+
+    /// Whether the active split shows its buffer as a hex dump.
+    pub(crate) fn is_hex_view_active(&self) -> bool {
+        let split_id = self.effective_active_split();
+        let buffer_id = self.active_buffer();
+        self.split_view_states()
+            .get(&split_id)
+            .and_then(|vs| vs.keyed_states.get(&buffer_id))
+            .is_some_and(|bvs| bvs.hex_view.is_some())
+    }
+
+    /// Whether the active buffer can be changed. Binary files are read-only
+    /// as text, but their bytes can be edited in the hex view.
+    pub(crate) fn can_hex_edit(&self) -> bool {
+        !self.active_window().is_editing_disabled()
+            || (self.is_hex_view_active() && self.active_state().buffer.is_binary())
+    }
+
+    /// Switch the active split between the text and the hex view of its
+    /// buffer.
+    pub fn toggle_hex_view(&mut self) {
+        let split_id = self.effective_active_split();
+        let buffer_id = self.active_buffer();
+        let Some(view_state) = self
+            .split_view_states_mut()
+            .get_mut(&split_id)
+            .and_then(|vs| vs.keyed_states.get_mut(&buffer_id))
+        else {
+            return;
+        };
+
+        if view_state.hex_view.take().is_some() {
+            self.set_status_message(t!("hex.disabled").to_string());
+            return;
+        }
+        let mut hex = HexViewState::default();
+        hex.ensure_visible(
+            view_state.cursors.primary().position,
+            view_state.viewport.height as usize,
+        );
+        view_state.hex_view = Some(hex);
+        self.set_status_message(t!("hex.enabled").to_string());
+    }
+
+    /// Open the hex view if needed and prompt for an offset to jump to.
+    pub fn start_hex_goto_offset_prompt(&mut self) {
+        if !self.is_hex_view_active() {
+            self.toggle_hex_view();
+        }
+        self.start_prompt(t!("hex.goto_prompt").to_string(), PromptType::HexGotoOffset);
+    }
+
+    /// Open the hex view if needed and prompt for a byte pattern to find.
+    pub fn start_hex_search_prompt(&mut self) {
+        if !self.is_hex_view_active() {
+            self.toggle_hex_view();
+        }
+        self.start_prompt(t!("hex.search_prompt").to_string(), PromptType::HexSearch);
+    }
+
+    /// Confirm handler of the goto-offset prompt.
+    pub(super) fn hex_goto_offset(&mut self, input: &str) {
+        match parse_offset(input) {
+            Some(offset) => self.hex_move_to(offset),
+            None => {
+                self.set_status_message(t!("hex.invalid_offset", input = input.trim()).to_string())
+            }
+        }
+    }
+
+    /// Confirm handler of the byte search prompt: find the next match after
+    /// the cursor, wrapping around.
+    pub(super) fn hex_search_bytes(&mut self, input: &str) {
+        let Some(pattern) = parse_byte_pattern(input) else {
+            self.set_status_message(t!("hex.invalid_pattern", input = input.trim()).to_string());
+            return;
+        };
+        let from = self.active_cursors().primary().position + 1;
+        match find_bytes(&mut self.active_state_mut().buffer, &pattern, from) {
+            Some(offset) => {
+                self.hex_move_to(offset);
+                self.set_status_message(
+                    t!("hex.found", offset = format!("{:08X}", offset)).to_string(),
+                );
+            }
+            None => self.set_status_message(t!("hex.not_found").to_string()),
+        }
+    }
+
+    /// Handle a key while the active split shows the hex view. Keys that
+    /// are not navigation or byte input fall through to the keybindings.
+    pub(super) fn try_route_hex_key(&mut self, key_event: &KeyEvent) -> Option<AnyhowResult<()>> {
+        let hex = self.active_hex_view_mut()?.clone();
+        let len = self.active_state().buffer.len();
+        let cursor = self.active_cursors().primary().position;
+        let rows = self.active_viewport().height.max(1) as usize;
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key_event.modifiers.contains(KeyModifiers::ALT);
+
+        let target = match key_event.code {
+            KeyCode::Left => cursor.saturating_sub(1),
+            KeyCode::Right => cursor + 1,
+            KeyCode::Up => cursor.saturating_sub(BYTES_PER_ROW),
+            KeyCode::Down => cursor + BYTES_PER_ROW,
+            KeyCode::PageUp => cursor.saturating_sub(rows * BYTES_PER_ROW),
+            KeyCode::PageDown => cursor + rows * BYTES_PER_ROW,
+            KeyCode::Home if ctrl => 0,
+            KeyCode::End if ctrl => len,
+            KeyCode::Home => cursor - cursor % BYTES_PER_ROW,
+            KeyCode::End => cursor - cursor % BYTES_PER_ROW + BYTES_PER_ROW - 1,
+            KeyCode::Backspace => {
+                if hex.low_nibble {
+                    cursor
+                } else {
+                    cursor.saturating_sub(1)
+                }
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                if let Some(hex) = self.active_hex_view_mut() {
+                    hex.column = match hex.column {
+                        HexColumn::Hex => HexColumn::Ascii,
+                        HexColumn::Ascii => HexColumn::Hex,
+                    };
+                    hex.low_nibble = false;
+                }
+                return Some(Ok(()));
+            }
+            // Overwrite-only: nothing may change the buffer length
+            KeyCode::Enter | KeyCode::Delete => return Some(Ok(())),
+            KeyCode::Char(c) if !ctrl && !alt => {
+                self.hex_type_char(&hex, cursor, len, c);
+                return Some(Ok(()));
+            }
+            _ => return None,
+        };
+        self.hex_move_to(target);
+        Some(Ok(()))
+    }
+
+    /// Overwrite the cursor byte with a typed hex digit (hex column) or
+    /// character (ASCII column).
+    fn hex_type_char(&mut self, hex: &HexViewState, cursor: usize, len: usize, c: char) {
+        if cursor >= len {
+            return;
+        }
+        if !self.can_hex_edit() {
+            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            return;
+        }
+
+        match hex.column {
+            HexColumn::Hex => {
+                let Some(digit) = c.to_digit(16) else {
+                    return;
+                };
+                let Some(old) = self.hex_byte_at(cursor) else {
+                    return;
+                };
+                self.hex_write_byte(cursor, set_nibble(old, hex.low_nibble, digit as u8));
+                if hex.low_nibble {
+                    self.hex_move_to(cursor + 1);
+                } else if let Some(hex) = self.active_hex_view_mut() {
+                    hex.low_nibble = true;
+                }
+            }
+            HexColumn::Ascii => {
+                if !c.is_ascii() || c.is_ascii_control() {
+                    return;
+                }
+                self.hex_write_byte(cursor, c as u8);
+                self.hex_move_to(cursor + 1);
+            }
+        }
+    }
+
+    fn hex_byte_at(&mut self, offset: usize) -> Option<u8> {
+        self.active_state_mut()
+            .buffer
+            .get_text_range_mut(offset, 1)
+            .ok()?
+            .first()
+            .copied()
+    }
+
+    /// Move the cursor to `offset` (clamped to the last byte), start at the
+    /// high nibble and scroll the hex view to it.
+    fn hex_move_to(&mut self, offset: usize) {
+        let len = self.active_state().buffer.len();
+        let offset = offset.min(len.saturating_sub(1));
+        self.goto_byte_offset(offset);
+        let rows = self.active_viewport().height as usize;
+        if let Some(hex) = self.active_hex_view_mut() {
+            hex.low_nibble = false;
+            hex.ensure_visible(offset, rows);
+        }
+    }
+
+    /// Replace the byte at `offset` as one undoable edit.
+    fn hex_write_byte(&mut self, offset: usize, byte: u8) {
+        let buffer_id = self.active_buffer();
+        let cursors: Vec<(CursorId, usize, Option<usize>)> = self
+            .active_cursors()
+            .iter()
+            .map(|(id, c)| (id, c.position, c.anchor))
+            .collect();
+
+        let state = self.active_state_mut();
+        let old_snapshot = state.buffer.snapshot_buffer_state();
+        state.buffer.delete_bytes(offset, 1);
+        state.buffer.insert_bytes(offset, vec![byte]);
+        let new_snapshot = state.buffer.snapshot_buffer_state();
+        state.highlighter.invalidate_range(offset..offset + 1);
+
+        let bulk_edit = Event::BulkEdit {
+            old_snapshot: Some(old_snapshot),
+            new_snapshot: Some(new_snapshot),
+            old_cursors: cursors.clone(),
+            new_cursors: cursors,
+            description: t!("hex.edit_description").to_string(),
+            edits: vec![(offset, 1, 1)],
+            displaced_markers: Vec::new(),
+        };
+        self.active_event_log_mut().append(bulk_edit);
+        self.active_window_mut()
+            .invalidate_layouts_for_buffer(buffer_id);
+
+        // A server that hasn't opened the buffer yet gets its whole text
+        // on didOpen. The byte may be part of a multi-byte character, so
+        // there is no UTF-16 range to replace: send the whole document.
+        let lsp_attached = self
+            .active_window()
+            .buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|m| m.lsp_enabled && !m.lsp_opened_with.is_empty());
+        if lsp_attached {
+            if let Some(text) = self.active_state().buffer.to_string() {
+                let change = TextDocumentContentChangeEvent {
+                    range: None,
+                    range_length: None,
+                    text,
+                };
+                self.active_window_mut()
+                    .send_lsp_changes_for_buffer(buffer_id, vec![change]);
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Call and type hierarchy orchestrators.
+//!
+//! Plugins walk a hierarchy one level at a time: `prepareHierarchy`
+//! resolves the items at a position and `getHierarchyChildren` expands one
+//! of them. Hierarchy items only mean something to the server that
+//! produced them, so each node records its server and is expanded there.
+
+use crate::app::types::LspUri;
+use crate::model::event::BufferId;
+use crate::primitives::document_symbols::symbol_kind_name;
+use crate::primitives::hierarchy::{HierarchyDirection, HierarchyEntry, HierarchyKind};
+use crate::types::LspFeature;
+use fresh_core::api::{HierarchyCallSite, HierarchyItemInfo, PluginResponse};
+
+use super::Editor;
+
+impl Editor {
+    /// Handle PrepareHierarchy plugin command
+    pub(super) fn handle_prepare_hierarchy(
+        &mut self,
+        buffer_id: BufferId,
+        position: usize,
+        kind: &str,
+        request_id: u64,
+    ) {
+        let Some(kind) = HierarchyKind::from_name(kind) else {
+            tracing::warn!("Unknown hierarchy kind {:?}", kind);
+            self.send_hierarchy_items(request_id, Vec::new());
+            return;
+        };
+        let Some((line, character)) = self
+            .buffers()
+            .get(&buffer_id)
+            .map(|state| state.buffer.position_to_lsp_position(position))
+        else {
+            self.send_hierarchy_items(request_id, Vec::new());
+            return;
+        };
+        let feature = match kind {
+            HierarchyKind::Call => LspFeature::CallHierarchy,
+            HierarchyKind::Type => LspFeature::TypeHierarchy,
+        };
+
+        let lsp_request_id = self.active_window_mut().next_lsp_request_id;
+        let sent = self
+            .with_lsp_for_buffer(buffer_id, feature, |handle, uri, _language| {
+                let result = handle.prepare_hierarchy(
+                    lsp_request_id,
+                    kind,
+                    uri.as_uri().clone(),
+                    line as u32,
+                    character as u32,
+                );
+                if result.is_ok() {
+                    tracing::info!(
+                        "Requested {} at {}:{}:{}",
+                        kind.prepare_method(),
+                        uri.as_str(),
+                        line,
+                        character
+                    );
+                }
+                result.is_ok()
+            })
+            .unwrap_or(false);
+        self.track_hierarchy_request(sent, lsp_request_id, request_id);
+    }
+
+    /// Handle GetHierarchyChildren plugin command
+    pub(super) fn handle_get_hierarchy_children(
+        &mut self,
+        server: &str,
+        direction: &str,
+        item: serde_json::Value,
+        request_id: u64,
+    ) {
+        let Some(direction) = HierarchyDirection::from_name(direction) else {
+            tracing::warn!("Unknown hierarchy direction {:?}", direction);
+            self.send_hierarchy_items(request_id, Vec::new());
+            return;
+        };
+
+        let lsp_request_id = self.active_window_mut().next_lsp_request_id;
+        let sent = match self.lsp().and_then(|lsp| lsp.handle_named(server)) {
+            Some(sh) => match sh
+                .handle
+                .hierarchy_children(lsp_request_id, direction, item)
+            {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to request {} from {}: {}",
+                        direction.method(),
+                        server,
+                        e
+                    );
+                    false
+                }
+            },
+            None => {
+                tracing::debug!("Hierarchy server {} is no longer running", server);
+                false
+            }
+        };
+        self.track_hierarchy_request(sent, lsp_request_id, request_id);
+    }
+
+    /// Remember which plugin request an LSP request resolves, or resolve
+    /// it right away with no items if nothing was sent.
+    fn track_hierarchy_request(&mut self, sent: bool, lsp_request_id: u64, request_id: u64) {
+        if !sent {
+            self.send_hierarchy_items(request_id, Vec::new());
+            return;
+        }
+        let window = self.active_window_mut();
+        window.next_lsp_request_id += 1;
+        window
+            .pending_hierarchy_requests
+            .insert(lsp_request_id, request_id);
+    }
+
+    /// Handle LSP call/type hierarchy response
+    pub(crate) fn handle_hierarchy_response(
+        &mut self,
+        request_id: u64,
+        server_name: &str,
+        entries: Vec<HierarchyEntry>,
+    ) {
+        let Some(plugin_request_id) = self
+            .active_window_mut()
+            .pending_hierarchy_requests
+            .remove(&request_id)
+        else {
+            tracing::debug!(
+                "Ignoring hierarchy response for unknown request {}",
+                request_id
+            );
+            return;
+        };
+
+        // Hand plugins host paths, like the `lsp_references` hook does.
+        let translation = self.authority.path_translation.clone();
+        let file = |uri: &lsp_types::Uri| {
+            LspUri::from_wire(uri.clone())
+                .to_host_path(translation.as_ref())
+                .map(|path| path.to_string_lossy().into_owned())
+                .unwrap_or_else(|| uri.as_str().to_string())
+        };
+        let items = entries
+            .into_iter()
+            .map(|entry| HierarchyItemInfo {
+                file: file(&entry.uri),
+                line: entry.selection_range.start.line + 1,
+                column: entry.selection_range.start.character + 1,
+                call_sites: entry
+                    .call_sites
+                    .iter()
+                    .map(|site| HierarchyCallSite {
+                        file: file(&site.uri),
+                        line: site.range.start.line + 1,
+                        column: site.range.start.character + 1,
+                    })
+                    .collect(),
+                kind: symbol_kind_name(entry.kind).to_string(),
+                name: entry.name,
+                detail: entry.detail,
+                server: server_name.to_string(),
+                item: entry.item,
+            })
+            .collect();
+        self.send_hierarchy_items(plugin_request_id, items);
+    }
+
+    fn send_hierarchy_items(&self, request_id: u64, items: Vec<HierarchyItemInfo>) {
+        self.send_plugin_response(PluginResponse::HierarchyItems { request_id, items });
+    }
+}
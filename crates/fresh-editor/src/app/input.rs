@@ -1,4 +1,5 @@
 use super::*;
+use crate::services::lsp::async_handler::GotoKind;
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
 
@@ -92,18 +93,46 @@ impl Editor {
         query: String,
         matches: Vec<crate::services::live_grep_state::GrepMatch>,
     ) {
+        // One match per line:
+        //   path:line:col  ⎯  context
+        let header = format!("Quickfix: {} ({} matches)", query, matches.len());
+        let rows = matches
+            .iter()
+            .map(|m| format!("{}:{}:{}  {}", m.file, m.line, m.column, m.content.trim()))
+            .collect();
+        match self.show_quickfix_in_dock(header, rows) {
+            Some(true) => {
+                self.set_status_message(format!("Quickfix updated: {} matches", matches.len()));
+            }
+            Some(false) => {
+                self.set_status_message(format!(
+                    "Quickfix exported: {} matches in dock",
+                    matches.len()
+                ));
+            }
+            None => {}
+        }
+    }
+
+    /// Show `header` and `rows` as the Quickfix list in the Utility
+    /// Dock, replacing the previous list if there is one.
+    ///
+    /// Returns whether an existing list was replaced, or `None` if the
+    /// list could not be shown (the failure is logged).
+    pub(super) fn show_quickfix_in_dock(
+        &mut self,
+        header: String,
+        rows: Vec<String>,
+    ) -> Option<bool> {
         use crate::model::event::SplitDirection;
         use crate::primitives::text_property::TextPropertyEntry;
         use crate::view::split::SplitRole;
 
-        // Build the buffer's text content. One match per line:
-        //   path:line:col  ⎯  context
-        let mut entries = Vec::with_capacity(matches.len() + 2);
-        let header = format!("Quickfix: {} ({} matches)\n", query, matches.len());
-        entries.push(TextPropertyEntry::text(header));
-        for m in &matches {
-            let line = format!("{}:{}:{}  {}\n", m.file, m.line, m.column, m.content.trim());
-            entries.push(TextPropertyEntry::text(line));
+        // Build the buffer's text content, one row per line
+        let mut entries = Vec::with_capacity(rows.len() + 1);
+        entries.push(TextPropertyEntry::text(format!("{}\n", header)));
+        for row in rows {
+            entries.push(TextPropertyEntry::text(format!("{}\n", row)));
         }
 
         // If a Quickfix buffer already exists (panel_id "quickfix"),
@@ -119,7 +148,7 @@ impl Editor {
             {
                 if let Err(e) = self.set_virtual_buffer_content(existing, entries) {
                     tracing::error!("Failed to update quickfix buffer: {}", e);
-                    return;
+                    return None;
                 }
                 // Make sure the dock displays the quickfix buffer.
                 if let Some(dock_leaf) = self
@@ -138,8 +167,7 @@ impl Editor {
                     self.active_window_mut()
                         .set_pane_buffer(dock_leaf, existing);
                 }
-                self.set_status_message(format!("Quickfix updated: {} matches", matches.len()));
-                return;
+                return Some(true);
             }
             // Stale entry — remove and fall through to create.
             self.panel_ids_mut().remove(&panel_key);
@@ -169,7 +197,7 @@ impl Editor {
         self.panel_ids_mut().insert(panel_key, buffer_id);
         if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
             tracing::error!("Failed to set quickfix buffer content: {}", e);
-            return;
+            return None;
         }
 
         // Place the buffer in the dock — reuse the existing dock leaf
@@ -271,15 +299,12 @@ impl Editor {
                 }
                 Err(e) => {
                     tracing::error!("Failed to create dock split for quickfix: {}", e);
-                    return;
+                    return None;
                 }
             }
         }
 
-        self.set_status_message(format!(
-            "Quickfix exported: {} matches in dock",
-            matches.len()
-        ));
+        Some(false)
     }
 
     /// Whether editor-pane popups (LSP completion, hover, signature help,
@@ -716,6 +741,25 @@ impl Editor {
             }
         }
 
+        // --- Hex view input routing ---
+        // Navigation and byte input of a split showing the hex view; other
+        // keys (save, undo, commands) resolve through the keybindings.
+        if matches!(context, crate::input::keybindings::KeyContext::Normal)
+            && self.is_hex_view_active()
+        {
+            if let Some(handled) = self.try_route_hex_key(&key_event) {
+                return handled;
+            }
+        }
+
+        // --- Snippet session routing ---
+        // Tab / Shift+Tab visit the tabstops of an inserted snippet.
+        if matches!(context, crate::input::keybindings::KeyContext::Normal) {
+            if let Some(handled) = self.try_route_snippet_key(&key_event) {
+                return handled;
+            }
+        }
+
         // Check for chord sequence matches first
         let key_event = crossterm::event::KeyEvent::new(code, modifiers);
         let (chord_result, action) = {
@@ -759,6 +803,9 @@ impl Editor {
         match action {
             Action::LspCompletion
             | Action::LspGotoDefinition
+            | Action::LspGotoImplementation
+            | Action::LspGotoTypeDefinition
+            | Action::LspGotoDeclaration
             | Action::LspReferences
             | Action::LspHover
             | Action::None => {
@@ -773,7 +820,9 @@ impl Editor {
         // Note: Modal components (Settings, Menu, Prompt, Popup, File Browser) are now
         // handled by dispatch_modal_input using the InputHandler system.
         // All remaining actions delegate to handle_action.
-        self.handle_action(action)
+        let result = self.handle_action(action);
+        self.check_snippet_session();
+        result
     }
 
     /// Handle an action (for normal mode and command execution).
@@ -1544,6 +1593,9 @@ impl Editor {
                     current,
                 );
             }
+            Action::ToggleHexView => self.toggle_hex_view(),
+            Action::HexGotoOffset => self.start_hex_goto_offset_prompt(),
+            Action::HexSearchBytes => self.start_hex_search_prompt(),
             Action::SetBackground => {
                 let default_path = self
                     .ansi_background_path
@@ -1575,9 +1627,21 @@ impl Editor {
             Action::DabbrevExpand => {
                 self.dabbrev_expand();
             }
+            Action::InsertSnippet => {
+                self.start_insert_snippet_prompt();
+            }
             Action::LspGotoDefinition => {
                 self.request_goto_definition()?;
             }
+            Action::LspGotoImplementation => {
+                self.request_goto_locations(GotoKind::Implementation)?;
+            }
+            Action::LspGotoTypeDefinition => {
+                self.request_goto_locations(GotoKind::TypeDefinition)?;
+            }
+            Action::LspGotoDeclaration => {
+                self.request_goto_locations(GotoKind::Declaration)?;
+            }
             Action::LspRename => {
                 self.start_rename()?;
             }
@@ -1593,6 +1657,33 @@ impl Editor {
             Action::LspCodeActions => {
                 self.request_code_actions()?;
             }
+            Action::LspRunCodeLens => {
+                self.run_code_lens_at_cursor();
+            }
+            Action::DebugStart => {
+                self.debug_start();
+            }
+            Action::DebugStop => {
+                self.debug_stop();
+            }
+            Action::DebugContinue => {
+                self.debug_continue();
+            }
+            Action::DebugPause => {
+                self.debug_pause();
+            }
+            Action::DebugStepOver => {
+                self.debug_step("next");
+            }
+            Action::DebugStepInto => {
+                self.debug_step("stepIn");
+            }
+            Action::DebugStepOut => {
+                self.debug_step("stepOut");
+            }
+            Action::DebugToggleBreakpoint => {
+                self.debug_toggle_breakpoint_at_cursor();
+            }
             Action::LspRestart => {
                 self.handle_lsp_restart();
             }
@@ -1683,6 +1774,12 @@ impl Editor {
             Action::FindSelectionPrevious => {
                 self.find_selection_previous();
             }
+            Action::ExpandSelection => {
+                self.expand_selection()?;
+            }
+            Action::ShrinkSelection => {
+                self.shrink_selection()?;
+            }
             Action::AddCursorNextMatch => self.add_cursor_at_next_match(),
             Action::AddCursorAbove => self.add_cursor_above(),
             Action::AddCursorBelow => self.add_cursor_below(),
@@ -1747,6 +1844,12 @@ impl Editor {
             Action::TogglePromptLine => self.active_window_mut().toggle_prompt_line(),
             Action::ToggleVerticalScrollbar => self.toggle_vertical_scrollbar(),
             Action::ToggleHorizontalScrollbar => self.toggle_horizontal_scrollbar(),
+            Action::ToggleMinimap => self.toggle_minimap(),
+            Action::ToggleStickyScroll => self.toggle_sticky_scroll(),
+            Action::ToggleBreadcrumbs => self.toggle_breadcrumbs(),
+            Action::FocusBreadcrumbs => self.focus_breadcrumbs(),
+            Action::ToggleIndentGuides => self.toggle_indent_guides(),
+            Action::ToggleSpellCheck => self.toggle_spell_check(),
             Action::ToggleLineNumbers => self.toggle_line_numbers(),
             Action::ToggleScrollSync => self.active_window_mut().toggle_scroll_sync(),
             Action::ToggleMouseCapture => self.toggle_mouse_capture(),
@@ -2243,6 +2346,7 @@ impl Editor {
                     );
                 }
             }
+            Action::ShowPluginPerformance => self.show_plugin_performance(),
             Action::InitReload => {
                 // Same code path as auto-load: read init.ts and push it
                 // through the existing plugin pipeline. The runtime's
@@ -2359,6 +2463,21 @@ impl Editor {
                     }
                 }
             }
+            Action::TerminalPreviousPrompt => {
+                self.terminal_jump_to_prompt(false);
+            }
+            Action::TerminalNextPrompt => {
+                self.terminal_jump_to_prompt(true);
+            }
+            Action::TerminalSelectLastOutput => {
+                self.terminal_select_last_output();
+            }
+            Action::TerminalCopyLastOutput => {
+                self.terminal_copy_last_output();
+            }
+            Action::TerminalOpenLink => {
+                self.terminal_open_link_under_cursor();
+            }
             Action::ShellCommand => {
                 // Run shell command on buffer/selection, output to new buffer
                 self.start_shell_command_prompt(false);
@@ -2367,6 +2486,15 @@ impl Editor {
                 // Run shell command on buffer/selection, replace content
                 self.start_shell_command_prompt(true);
             }
+            Action::RunTask => {
+                self.run_task_picker();
+            }
+            Action::CancelTask => {
+                self.cancel_task();
+            }
+            Action::ShowTaskOutput => {
+                self.show_task_output();
+            }
             Action::OpenSettings => {
                 self.open_settings();
             }
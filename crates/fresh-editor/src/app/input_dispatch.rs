@@ -322,6 +322,19 @@ impl Editor {
                 }
             }
             DeferredAction::PromptSelectionChanged { selected_index } => {
+                // Quick Open `@` previews the newly selected symbol
+                let quick_open_input = self
+                    .active_window()
+                    .prompt
+                    .as_ref()
+                    .filter(|p| p.prompt_type == crate::view::prompt::PromptType::QuickOpen)
+                    .map(|p| p.input.clone());
+                if let Some(input) = quick_open_input {
+                    if self.quick_open_shows_symbols(&input) {
+                        self.preview_quick_open_symbol();
+                    }
+                }
+
                 // Fire hook for plugin prompts so they can update live preview
                 let plugin_custom_type =
                     self.active_window()
@@ -366,6 +379,9 @@ impl Editor {
                 self.clipboard.copy(text);
                 self.set_status_message(t!("clipboard.copied").to_string());
             }
+            DeferredAction::BreadcrumbStep(delta) => {
+                self.step_breadcrumb(delta);
+            }
 
             // Generic action execution
             DeferredAction::ExecuteAction(kb_action) => {
@@ -1080,6 +1080,8 @@ impl Editor {
         self.active_window_mut()
             .pending_inlay_hints_requests
             .retain(|_, req| req.buffer_id != buffer_id);
+        // Same for code lenses; their lines go with the overlays below.
+        self.active_window_mut().code_lens.remove_buffer(buffer_id);
 
         // Clear all LSP-related overlays for this buffer (diagnostics + inlay hints)
         let diagnostic_ns = crate::services::lsp::diagnostics::lsp_diagnostic_namespace();
@@ -1226,6 +1228,7 @@ impl Editor {
         let _ = handle;
         self.active_window_mut()
             .schedule_folding_ranges_refresh(buffer_id);
+        self.active_window_mut().code_lens.schedule(buffer_id);
     }
 
     /// Set up a plugin development workspace for LSP support on a buffer.
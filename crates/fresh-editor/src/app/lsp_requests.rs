@@ -23,7 +23,8 @@ use crate::state::EditorState;
 use crate::view::prompt::{Prompt, PromptType};
 use std::collections::HashMap;
 
-use crate::services::lsp::async_handler::LspHandle;
+use crate::app::types::PendingGotoLocations;
+use crate::services::lsp::async_handler::{GotoKind, LspHandle};
 use crate::types::LspFeature;
 
 use super::{Editor, SemanticTokenRangeRequest};
@@ -226,7 +227,39 @@ impl Editor {
 
         // For now, just jump to the first location
         let location = &locations[0];
+        let Some(buffer_id) = self.jump_to_lsp_location(location) else {
+            return Ok(());
+        };
+        let line = location.range.start.line as usize;
 
+        // The buffer's `file_path` is the *destination* path — the host
+        // path on a bind-mounted file, the container path on a fetched
+        // one — so we read it back for the status message rather than
+        // formatting the original wire URI.
+        let display_path = self
+            .buffers()
+            .get(&buffer_id)
+            .and_then(|s| s.buffer.file_path().map(|p| p.display().to_string()))
+            .unwrap_or_default();
+        self.active_window_mut().status_message = Some(
+            t!(
+                "lsp.jumped_to_definition",
+                path = display_path,
+                line = line + 1
+            )
+            .to_string(),
+        );
+
+        Ok(())
+    }
+
+    /// Open the file of an LSP `location` and move the cursor to its start,
+    /// scrolling it into view. Returns the destination buffer, or `None`
+    /// (with a status message) if the file could not be opened.
+    pub(crate) fn jump_to_lsp_location(
+        &mut self,
+        location: &lsp_types::Location,
+    ) -> Option<BufferId> {
         // Resolve the URI to a buffer. `open_lsp_uri_target` handles
         // all three cases: host file under the workspace mount,
         // container-only file fetched via `docker exec cat`, and
@@ -246,15 +279,11 @@ impl Editor {
                         t!("file.error_opening", error = e.to_string()).to_string(),
                     );
                 }
-                return Ok(());
+                return None;
             }
         };
 
-        // Move cursor to the definition position. The buffer's
-        // `file_path` is the *destination* path — the host path on a
-        // bind-mounted file, the container path on a fetched one —
-        // so we read it back for the status message rather than
-        // formatting the original wire URI.
+        // Move cursor to the target position.
         let line = location.range.start.line as usize;
         let character = location.range.start.character as usize;
         let position = self
@@ -299,21 +328,7 @@ impl Editor {
                 .ensure_active_cursor_visible_for_navigation(true);
         }
 
-        let display_path = self
-            .buffers()
-            .get(&buffer_id)
-            .and_then(|s| s.buffer.file_path().map(|p| p.display().to_string()))
-            .unwrap_or_default();
-        self.active_window_mut().status_message = Some(
-            t!(
-                "lsp.jumped_to_definition",
-                path = display_path,
-                line = line + 1
-            )
-            .to_string(),
-        );
-
-        Ok(())
+        Some(buffer_id)
     }
 
     /// Dispatch an exclusive LSP feature request to the first handle that allows the feature.
@@ -773,6 +788,141 @@ impl Editor {
         Ok(())
     }
 
+    /// Request LSP go-to implementation, type definition or declaration at
+    /// the current cursor position
+    pub(crate) fn request_goto_locations(&mut self, kind: GotoKind) -> AnyhowResult<()> {
+        let cursor_pos = self.active_cursors().primary().position;
+        let (line, character) = self
+            .active_state()
+            .buffer
+            .position_to_lsp_position(cursor_pos);
+        let symbol = self.word_at_cursor(cursor_pos);
+        let buffer_id = self.active_buffer();
+        let request_id = self.active_window_mut().next_lsp_request_id;
+
+        let sent = self
+            .with_lsp_for_buffer(buffer_id, kind.feature(), |handle, uri, _language| {
+                let result = handle.goto_locations(
+                    request_id,
+                    kind,
+                    uri.as_uri().clone(),
+                    line as u32,
+                    character as u32,
+                );
+                if result.is_ok() {
+                    tracing::info!(
+                        "Requested {} at {}:{}:{}",
+                        kind.method(),
+                        uri.as_str(),
+                        line,
+                        character
+                    );
+                }
+                result.is_ok()
+            })
+            .unwrap_or(false);
+
+        if sent {
+            let window = self.active_window_mut();
+            window.next_lsp_request_id += 1;
+            window.pending_goto_locations_request = Some(PendingGotoLocations {
+                request_id,
+                kind,
+                symbol,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Handle an LSP go-to implementation / type definition / declaration
+    /// response: jump straight to a single result, list several in the
+    /// Find References panel.
+    pub(crate) fn handle_goto_locations_response(
+        &mut self,
+        request_id: u64,
+        kind: GotoKind,
+        locations: Vec<lsp_types::Location>,
+    ) {
+        let Some(pending) = self
+            .active_window_mut()
+            .pending_goto_locations_request
+            .take_if(|pending| pending.request_id == request_id)
+        else {
+            tracing::debug!(
+                "Ignoring {} response for outdated request {}",
+                kind.method(),
+                request_id
+            );
+            return;
+        };
+
+        match locations.as_slice() {
+            [] => {
+                let message = match kind {
+                    GotoKind::Implementation => t!("lsp.no_implementation"),
+                    GotoKind::TypeDefinition => t!("lsp.no_type_definition"),
+                    GotoKind::Declaration => t!("lsp.no_declaration"),
+                };
+                self.set_status_message(message.to_string());
+            }
+            [location] => {
+                let Some(buffer_id) = self.jump_to_lsp_location(location) else {
+                    return;
+                };
+                let display_path = self
+                    .buffers()
+                    .get(&buffer_id)
+                    .and_then(|s| s.buffer.file_path().map(|p| p.display().to_string()))
+                    .unwrap_or_default();
+                let line = location.range.start.line + 1;
+                let message = match kind {
+                    GotoKind::Implementation => {
+                        t!(
+                            "lsp.jumped_to_implementation",
+                            path = display_path,
+                            line = line
+                        )
+                    }
+                    GotoKind::TypeDefinition => {
+                        t!(
+                            "lsp.jumped_to_type_definition",
+                            path = display_path,
+                            line = line
+                        )
+                    }
+                    GotoKind::Declaration => {
+                        t!(
+                            "lsp.jumped_to_declaration",
+                            path = display_path,
+                            line = line
+                        )
+                    }
+                };
+                self.set_status_message(message.to_string());
+            }
+            _ => {
+                self.set_status_message(
+                    t!(
+                        "lsp.found_locations",
+                        count = locations.len(),
+                        symbol = &pending.symbol
+                    )
+                    .to_string(),
+                );
+                self.show_lsp_locations_panel(kind.name(), pending.symbol, &locations);
+            }
+        }
+    }
+
+    /// The word around byte `position` in the active buffer
+    pub(super) fn word_at_cursor(&self, position: usize) -> String {
+        let buffer = &self.active_state().buffer;
+        let word_start = find_word_start(buffer, position);
+        let word_end = find_word_end(buffer, position);
+        String::from_utf8_lossy(&buffer.slice_bytes(word_start..word_end)).into_owned()
+    }
+
     /// Request LSP hover documentation at current cursor position
     pub fn request_hover(&mut self) -> AnyhowResult<()> {
         // Get the current buffer and cursor position
@@ -1230,8 +1380,8 @@ impl Editor {
         use crate::view::virtual_text::VirtualTextPosition;
         use ratatui::style::{Color, Style};
 
-        // Clear existing inlay hints
-        state.virtual_texts.clear(&mut state.marker_list);
+        // Clear existing inlay hints (virtual lines such as code lenses stay)
+        state.virtual_texts.clear_inline(&mut state.marker_list);
 
         if hints.is_empty() {
             return;
@@ -1322,18 +1472,12 @@ impl Editor {
 
     /// Request LSP find references at current cursor position
     pub(crate) fn request_references(&mut self) -> AnyhowResult<()> {
-        use crate::primitives::word_navigation::{find_word_end, find_word_start};
-
         let cursor_pos = self.active_cursors().primary().position;
-        let (line, character, symbol) = {
-            let state = self.active_state();
-            let (line, character) = state.buffer.position_to_lsp_position(cursor_pos);
-            let word_start = find_word_start(&state.buffer, cursor_pos);
-            let word_end = find_word_end(&state.buffer, cursor_pos);
-            let symbol = String::from_utf8_lossy(&state.buffer.slice_bytes(word_start..word_end))
-                .into_owned();
-            (line, character, symbol)
-        };
+        let (line, character) = self
+            .active_state()
+            .buffer
+            .position_to_lsp_position(cursor_pos);
+        let symbol = self.word_at_cursor(cursor_pos);
 
         let buffer_id = self.active_buffer();
         let request_id = self.active_window_mut().next_lsp_request_id;
@@ -1575,6 +1719,14 @@ impl Editor {
             .clear();
         self.active_window_mut().pending_code_actions = None;
 
+        // Spelling fixes for the word at the cursor come first and need no
+        // server
+        let spelling_actions = self.spelling_code_actions();
+        if !spelling_actions.is_empty() {
+            self.active_window_mut().pending_code_actions = Some(spelling_actions);
+            self.show_code_actions_popup();
+        }
+
         // Get the current buffer and cursor position
         let cursor_pos = self.active_cursors().primary().position;
         let selection_range = self.active_cursors().primary().selection_range();
@@ -1714,6 +1866,12 @@ impl Editor {
             }
         }
 
+        self.show_code_actions_popup();
+    }
+
+    /// Show the accumulated code actions, replacing the popup showing the
+    /// ones known before.
+    fn show_code_actions_popup(&mut self) {
         // Build list items from all accumulated code actions
         use crate::view::popup::{Popup, PopupListItem, PopupPosition};
         use ratatui::style::Style;
@@ -1829,7 +1987,9 @@ impl Editor {
                 self.execute_resolved_code_action(ca);
             }
             lsp_types::CodeActionOrCommand::Command(cmd) => {
-                self.send_execute_command(cmd);
+                if !self.execute_spell_check_command(&cmd) {
+                    self.send_execute_command(cmd);
+                }
             }
         }
     }
@@ -2048,6 +2208,25 @@ impl Editor {
             return Ok(());
         }
 
+        let count = locations.len();
+        let symbol = std::mem::take(&mut self.active_window_mut().pending_references_symbol);
+        self.set_status_message(
+            t!("lsp.found_references", count = count, symbol = &symbol).to_string(),
+        );
+        self.show_lsp_locations_panel("references", symbol, &locations);
+
+        Ok(())
+    }
+
+    /// Show `locations` in the Find References results list by firing the
+    /// `lsp_references` hook. `kind` tells the plugin what was asked for
+    /// ("references", "implementation", ...).
+    pub(super) fn show_lsp_locations_panel(
+        &self,
+        kind: &str,
+        symbol: String,
+        locations: &[lsp_types::Location],
+    ) {
         // Convert locations to hook args format. Each `loc.uri` is a
         // wire-side URI from the LSP, so wrap it in [`LspUri`] and run
         // it through the active authority's translation before
@@ -2080,27 +2259,22 @@ impl Editor {
             .collect();
 
         let count = lsp_locations.len();
-        let symbol = std::mem::take(&mut self.active_window_mut().pending_references_symbol);
-        self.set_status_message(
-            t!("lsp.found_references", count = count, symbol = &symbol).to_string(),
-        );
-
         // Fire the lsp_references hook so plugins can display the results
         self.plugin_manager.read().unwrap().run_hook(
             "lsp_references",
             crate::services::plugins::hooks::HookArgs::LspReferences {
                 symbol: symbol.clone(),
+                kind: kind.to_string(),
                 locations: lsp_locations,
             },
         );
 
         tracing::info!(
-            "Fired lsp_references hook with {} locations for symbol '{}'",
+            "Fired lsp_references hook with {} {} locations for symbol '{}'",
             count,
+            kind,
             symbol
         );
-
-        Ok(())
     }
 
     /// Apply LSP text edits to a buffer and return the number of changes made.
@@ -2762,6 +2936,8 @@ impl Editor {
             displaced_markers,
         };
 
+        self.adjust_client_views_for_event(buffer_id, &bulk_edit);
+
         // Add to event log
         if let Some(event_log) = self.active_window_mut().event_logs.get_mut(&buffer_id) {
             event_log.append(bulk_edit);
@@ -39,6 +39,7 @@ impl Editor {
         let line_numbers = self.active_window().is_line_numbers_visible();
         let line_wrap = self.active_window().is_line_wrap_enabled();
         let page_view = self.active_window().is_page_view();
+        let hex_view = self.is_hex_view_active();
         let file_explorer_visible = self.file_explorer_visible();
         let file_explorer_focused = self.active_window().is_file_explorer_focused();
         let mouse_capture = self.active_window_mut().mouse_enabled;
@@ -72,6 +73,11 @@ impl Editor {
         let menu_bar = self.active_window_mut().menu_bar_visible;
         let vertical_scrollbar = self.config.editor.show_vertical_scrollbar;
         let horizontal_scrollbar = self.config.editor.show_horizontal_scrollbar;
+        let minimap = self.config.editor.show_minimap;
+        let sticky_scroll = self.config.editor.sticky_scroll;
+        let breadcrumbs = self.config.editor.show_breadcrumbs;
+        let indent_guides = self.config.editor.show_indent_guides;
+        let spell_check = self.config.editor.spell_check;
 
         // File explorer state
         let show_hidden = self.active_window().is_file_explorer_showing_hidden();
@@ -104,6 +110,7 @@ impl Editor {
             .set(context_keys::PAGE_VIEW, page_view)
             // Keep backward-compatible key for existing keybindings/menus
             .set(context_keys::COMPOSE_MODE, page_view)
+            .set(context_keys::HEX_VIEW, hex_view)
             .set(context_keys::FILE_EXPLORER, file_explorer_visible)
             .set(context_keys::FILE_EXPLORER_FOCUSED, file_explorer_focused)
             .set(context_keys::MOUSE_CAPTURE, mouse_capture)
@@ -120,6 +127,11 @@ impl Editor {
             .set(context_keys::SESSION_MODE, session_mode)
             .set(context_keys::VERTICAL_SCROLLBAR, vertical_scrollbar)
             .set(context_keys::HORIZONTAL_SCROLLBAR, horizontal_scrollbar)
+            .set(context_keys::MINIMAP, minimap)
+            .set(context_keys::STICKY_SCROLL, sticky_scroll)
+            .set(context_keys::BREADCRUMBS, breadcrumbs)
+            .set(context_keys::INDENT_GUIDES, indent_guides)
+            .set(context_keys::SPELL_CHECK, spell_check)
             .set(context_keys::SCROLL_SYNC, scroll_sync)
             .set(context_keys::HAS_SAME_BUFFER_SPLITS, has_same_buffer_splits);
     }
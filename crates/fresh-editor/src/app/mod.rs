@@ -4,6 +4,7 @@ mod async_dispatch;
 mod async_messages;
 mod bookmark_actions;
 mod bookmarks;
+mod breadcrumb_actions;
 mod buffer_close;
 mod buffer_config_resolve;
 mod buffer_groups;
@@ -12,11 +13,18 @@ mod calibration_actions;
 pub mod calibration_wizard;
 mod click_geometry;
 mod click_handlers;
+mod client_views;
 mod clipboard;
+mod code_lens;
+mod code_lens_actions;
 mod composite_buffer_actions;
 mod conductor_persistence;
 mod dabbrev_actions;
+mod debug;
+mod debug_actions;
 mod diagnostic_jumps;
+mod document_symbol_actions;
+mod document_symbols;
 mod editor_accessors;
 mod editor_init;
 mod event_apply;
@@ -30,6 +38,8 @@ mod file_open_queue;
 mod file_operations;
 mod help;
 mod help_actions;
+mod hex_view_actions;
+mod hierarchy_actions;
 mod hover;
 mod input;
 mod input_dispatch;
@@ -50,8 +60,11 @@ mod mouse_input;
 mod navigation;
 mod on_save_actions;
 mod path_utils;
+mod persistent_undo;
 mod plugin_commands;
 mod plugin_dispatch;
+mod plugin_performance;
+mod plugin_permissions;
 mod popup_actions;
 mod popup_dialogs;
 mod popup_overlay_actions;
@@ -59,6 +72,7 @@ mod prompt_actions;
 mod prompt_lifecycle;
 mod recovery_actions;
 mod regex_replace;
+mod remote_control;
 mod render;
 mod scan_orchestrators;
 mod scroll_sync;
@@ -66,15 +80,25 @@ mod scrollbar_input;
 mod scrollbar_math;
 mod search_ops;
 mod search_scan;
+mod selection_expansion;
+mod selection_expansion_actions;
 mod settings_actions;
 mod settings_prompts;
 mod shell_command;
 mod smart_home;
+mod snippet_actions;
+mod snippet_session;
+mod spell_check;
+mod spell_check_actions;
 mod split_actions;
 mod stdin_stream;
 mod tab_drag;
+mod task_actions;
+mod tasks;
 mod terminal;
 mod terminal_input;
+mod terminal_links;
+mod terminal_marks;
 mod terminal_mouse;
 mod text_ops;
 mod theme_inspect;
@@ -88,6 +112,8 @@ pub mod window;
 mod window_actions;
 pub mod window_resources;
 pub mod workspace;
+mod workspace_symbol_actions;
+mod workspace_symbols;
 
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
@@ -177,7 +203,8 @@ use crate::input::buffer_mode::ModeRegistry;
 use crate::input::command_registry::CommandRegistry;
 use crate::input::keybindings::{Action, KeyContext, KeybindingResolver};
 use crate::input::quick_open::{
-    BufferProvider, CommandProvider, FileProvider, GotoLineProvider, QuickOpenRegistry,
+    BufferProvider, CommandProvider, DocumentSymbolProvider, FileProvider, GotoLineProvider,
+    QuickOpenRegistry, WorkspaceSymbolProvider,
 };
 use crate::model::cursor::Cursors;
 use crate::model::event::{Event, EventLog, LeafId, SplitDirection};
@@ -425,6 +452,10 @@ pub struct Editor {
     /// `expanded_menus_cache` can detect a registry swap via `Arc::ptr_eq`.
     theme_registry: Arc<crate::view::theme::ThemeRegistry>,
 
+    /// User and package snippets (see `services/completion/snippets.rs`),
+    /// shared into every window's completion service.
+    snippet_library: Arc<crate::services::completion::SnippetLibrary>,
+
     /// Memoised `MenuConfig` with `DynamicSubmenu` items expanded against
     /// the current theme registry.
     expanded_menus_cache: crate::view::ui::ExpandedMenusCache,
@@ -543,6 +574,17 @@ pub struct Editor {
     /// `PluginCommand::SetRemoteIndicatorState`.
     pub remote_indicator_override: Option<crate::view::ui::status_bar::RemoteIndicatorOverride>,
 
+    /// Plugin permission questions waiting to be asked, oldest first:
+    /// (plugin name, capability, description of the first use). See
+    /// `PluginCommand::RequestPluginPermission`.
+    pending_plugin_permissions:
+        std::collections::VecDeque<(String, fresh_core::permissions::Capability, String)>,
+
+    /// Views of the clients attached to this editor in session mode:
+    /// each keeps its own active split, cursors, scroll positions and
+    /// terminal size. Empty outside session mode.
+    client_views: client_views::ClientViews,
+
     /// Local filesystem for editor-internal files (log files, status
     /// log). Stays separate from `authority` because these are the
     /// editor's own private state — they live on the host disk
@@ -9,6 +9,7 @@
 
 use super::*;
 use crate::input::keybindings::Action;
+use crate::model::cursor::Position2D;
 use crate::model::event::{ContainerId, CursorId, LeafId, SplitDirection};
 use crate::services::plugins::hooks::HookArgs;
 use crate::view::popup_mouse::{popup_areas_to_layout_info, PopupHitTester};
@@ -76,6 +77,16 @@ impl Editor {
             row
         );
 
+        // Ctrl+click on a link in a live terminal grid opens it
+        if mouse_event.kind == MouseEventKind::Down(MouseButton::Left)
+            && mouse_event
+                .modifiers
+                .contains(crossterm::event::KeyModifiers::CONTROL)
+            && self.try_open_terminal_link_at(col, row)
+        {
+            return Ok(true);
+        }
+
         // Check if we should forward mouse events to the terminal
         // Forward if: in terminal mode, mouse is over terminal buffer, and terminal is in alternate screen mode
         if let Some(result) =
@@ -168,6 +179,7 @@ impl Editor {
 
                 // Stop dragging and clear drag state
                 self.active_window_mut().mouse_state.dragging_scrollbar = None;
+                self.active_window_mut().mouse_state.dragging_minimap = None;
                 self.active_window_mut().mouse_state.drag_start_row = None;
                 self.active_window_mut().mouse_state.drag_start_top_byte = None;
                 self.active_window_mut()
@@ -188,6 +200,7 @@ impl Editor {
                 self.active_window_mut().mouse_state.drag_selection_anchor = None;
                 self.active_window_mut().mouse_state.drag_selection_by_words = false;
                 self.active_window_mut().mouse_state.drag_selection_word_end = None;
+                self.active_window_mut().mouse_state.drag_block_anchor = None;
                 // Clear popup scrollbar drag state
                 self.active_window_mut()
                     .mouse_state
@@ -1421,6 +1434,15 @@ impl Editor {
         if let Some(r) = self.handle_click_file_explorer_area(col, row) {
             return r;
         }
+        if let Some(r) = self.handle_click_breadcrumbs(col, row) {
+            return r;
+        }
+        if let Some(r) = self.handle_click_sticky_scroll(col, row) {
+            return r;
+        }
+        if let Some(r) = self.handle_click_minimap(col, row) {
+            return r;
+        }
         if let Some(r) = self.handle_click_scrollbar(col, row) {
             return r;
         }
@@ -1831,6 +1853,71 @@ impl Editor {
         None
     }
 
+    /// Clicking a breadcrumb segment opens its dropdown.
+    fn handle_click_breadcrumbs(&mut self, col: u16, row: u16) -> Option<AnyhowResult<()>> {
+        let (split_id, buffer_id, segment) = self
+            .active_layout()
+            .breadcrumb_layouts
+            .iter()
+            .find(|(_, layout)| in_rect(col, row, layout.bar_area))
+            .map(|(split_id, layout)| (*split_id, layout.buffer_id, layout.hit_test(col, row)))?;
+
+        self.focus_split(split_id, buffer_id);
+        if let Some(segment) = segment {
+            self.open_breadcrumb_dropdown(segment);
+        }
+        Some(Ok(()))
+    }
+
+    /// Clicking a pinned sticky scroll row jumps to its header line and
+    /// scrolls it to the top of the split.
+    fn handle_click_sticky_scroll(&mut self, col: u16, row: u16) -> Option<AnyhowResult<()>> {
+        let (split_id, buffer_id, header) =
+            self.active_layout().sticky_scroll_areas.iter().find_map(
+                |(split_id, buffer_id, rect, headers)| {
+                    if !in_rect(col, row, *rect) {
+                        return None;
+                    }
+                    let header = *headers.get(row.saturating_sub(rect.y) as usize)?;
+                    Some((*split_id, *buffer_id, header))
+                },
+            )?;
+
+        self.focus_split(split_id, buffer_id);
+        self.goto_byte_offset(header);
+        if let Some(view_state) = self
+            .active_window_mut()
+            .split_view_states_mut()
+            .expect("active window must have a populated split layout")
+            .get_mut(&split_id)
+        {
+            view_state.viewport.top_byte = header;
+            view_state.viewport.top_view_line_offset = 0;
+            // Skip ensure_visible so the scroll position isn't undone during render
+            view_state.viewport.set_skip_ensure_visible();
+        }
+        Some(Ok(()))
+    }
+
+    fn handle_click_minimap(&mut self, col: u16, row: u16) -> Option<AnyhowResult<()>> {
+        let (split_id, buffer_id, minimap_rect, row_bytes) = self
+            .active_layout()
+            .minimap_areas
+            .iter()
+            .find(|(_, _, minimap_rect, _)| in_rect(col, row, *minimap_rect))
+            .cloned()?;
+
+        self.focus_split(split_id, buffer_id);
+        self.active_window_mut().mouse_state.dragging_minimap = Some(split_id);
+        Some(self.active_window_mut().handle_minimap_jump(
+            row,
+            split_id,
+            buffer_id,
+            minimap_rect,
+            &row_bytes,
+        ))
+    }
+
     fn handle_click_scrollbar(&mut self, col: u16, row: u16) -> Option<AnyhowResult<()>> {
         let (split_id, buffer_id, scrollbar_rect, is_on_thumb) =
             self.active_layout().split_areas.iter().find_map(
@@ -2269,6 +2356,30 @@ impl Editor {
 
     /// Handle mouse drag event
     pub(super) fn handle_mouse_drag(&mut self, col: u16, row: u16) -> AnyhowResult<()> {
+        // Dragging on the minimap scrolls like dragging along a scrollbar
+        // track: the minimap itself scrolls as the viewport moves, so its
+        // rows can't be used as stable targets mid-drag.
+        if let Some(dragging_split_id) = self.active_window().mouse_state.dragging_minimap {
+            let area = self
+                .active_layout()
+                .minimap_areas
+                .iter()
+                .find(|(split_id, ..)| *split_id == dragging_split_id)
+                .map(|(split_id, buffer_id, minimap_rect, _)| {
+                    (*split_id, *buffer_id, *minimap_rect)
+                });
+            if let Some((split_id, buffer_id, minimap_rect)) = area {
+                self.active_window_mut().handle_scrollbar_jump(
+                    col,
+                    row,
+                    split_id,
+                    buffer_id,
+                    minimap_rect,
+                )?;
+            }
+            return Ok(());
+        }
+
         // If dragging scrollbar, update scroll position
         if let Some(dragging_split_id) = self.active_window_mut().mouse_state.dragging_scrollbar {
             // Snapshot split_areas so we don't borrow `self.active_layout()` and
@@ -2522,6 +2633,9 @@ impl Editor {
         else {
             return Ok(());
         };
+        if let Some(block_anchor) = self.active_window().mouse_state.drag_block_anchor {
+            return self.handle_block_selection_drag(split_id, block_anchor, col, row);
+        }
 
         // Find the buffer and content rect for this split in one pass
         let Some((buffer_id, content_rect)) = self
@@ -2645,6 +2759,125 @@ impl Editor {
         Ok(())
     }
 
+    /// Handle Alt+drag: select the block between `block_anchor` and the
+    /// cell under the mouse
+    fn handle_block_selection_drag(
+        &mut self,
+        split_id: LeafId,
+        block_anchor: Position2D,
+        col: u16,
+        row: u16,
+    ) -> AnyhowResult<()> {
+        use crate::model::block_selection::block_position_to_byte;
+        use crate::model::event::Event;
+
+        let Some((buffer_id, content_rect)) = self
+            .active_layout()
+            .split_areas
+            .iter()
+            .find(|(sid, _, _, _, _, _)| *sid == split_id)
+            .map(|(_, bid, rect, _, _, _)| (*bid, *rect))
+        else {
+            return Ok(());
+        };
+        let Some(target) = self.block_position_at(split_id, buffer_id, content_rect, col, row)
+        else {
+            return Ok(());
+        };
+        let Some((new_position, anchor_position)) =
+            self.active_window().buffers.get(&buffer_id).map(|state| {
+                let tab_size = state.buffer_settings.tab_size;
+                (
+                    block_position_to_byte(&state.buffer, target, tab_size),
+                    block_position_to_byte(&state.buffer, block_anchor, tab_size),
+                )
+            })
+        else {
+            return Ok(());
+        };
+
+        let Some(view_state) = self
+            .active_window_mut()
+            .splits
+            .as_mut()
+            .and_then(|(_, vs)| vs.get_mut(&split_id))
+        else {
+            return Ok(());
+        };
+        let primary_cursor_id = view_state.cursors.primary_id();
+        let cursor = view_state.cursors.primary_mut();
+        if cursor.block_anchor != Some(block_anchor) {
+            cursor.start_block_selection(block_anchor.line, block_anchor.column);
+        }
+        let event = Event::MoveCursor {
+            cursor_id: primary_cursor_id,
+            old_position: cursor.position,
+            new_position,
+            old_anchor: cursor.anchor,
+            new_anchor: Some(anchor_position),
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: target.column,
+        };
+
+        if let Some(event_log) = self.active_window_mut().event_logs.get_mut(&buffer_id) {
+            event_log.append(event.clone());
+        }
+        self.active_window_mut()
+            .apply_event_to_buffer(buffer_id, split_id, &event);
+
+        Ok(())
+    }
+
+    /// Line and display column of the cell under the mouse, for block
+    /// selection. Past the end of a line the column continues into virtual
+    /// space.
+    pub(super) fn block_position_at(
+        &self,
+        split_id: LeafId,
+        buffer_id: BufferId,
+        content_rect: Rect,
+        col: u16,
+        row: u16,
+    ) -> Option<Position2D> {
+        use crate::model::block_selection::{display_column, line_text};
+
+        let cached_mappings = self
+            .active_layout()
+            .view_line_mappings
+            .get(&split_id)
+            .cloned();
+        let view_state = self.active_window().splits.as_ref()?.1.get(&split_id)?;
+        let state = self.active_window().buffers.get(&buffer_id)?;
+        let gutter_width = state.margins.left_total_width() as u16;
+        let target = super::click_geometry::screen_to_buffer_position(
+            col,
+            row,
+            content_rect,
+            gutter_width,
+            &cached_mappings,
+            view_state.viewport.top_byte,
+            true,
+            view_state.compose_width,
+        )?;
+
+        let tab_size = state.buffer_settings.tab_size;
+        let line = state.buffer.get_line_number(target);
+        let line_start = state.buffer.line_start_offset(line).unwrap_or(0);
+        let text = line_text(&state.buffer, line);
+        let byte = target.saturating_sub(line_start).min(text.len());
+        let mut column = display_column(&text, byte, tab_size);
+        if byte == text.len() {
+            let rect = super::click_geometry::adjust_content_rect_for_compose(
+                content_rect,
+                view_state.compose_width,
+            );
+            let screen_column = col.saturating_sub(rect.x).saturating_sub(gutter_width) as usize
+                + view_state.viewport.left_column;
+            column = column.max(screen_column);
+        }
+        Some(Position2D { line, column })
+    }
+
     /// Handle file explorer border drag for resizing
     pub(super) fn handle_file_explorer_border_drag(&mut self, col: u16) -> AnyhowResult<()> {
         let Some((start_col, _start_row)) =
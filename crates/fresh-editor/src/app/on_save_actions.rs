@@ -10,7 +10,8 @@ use std::time::Duration;
 
 use super::Editor;
 use crate::config::{FormatterConfig, OnSaveAction};
-use crate::model::event::Event;
+use crate::model::event::{CursorId, Event};
+use crate::model::line_diff::{self, Replacement};
 use crate::services::process_hidden::HideWindow;
 use rust_i18n::t;
 
@@ -462,63 +463,57 @@ impl Editor {
     }
 
     /// Replace the active buffer's content with new output.
+    ///
+    /// Only the parts that differ are edited (see
+    /// [`line_diff::minimal_replacements`]), so markers, folds, cursors and
+    /// decorations in unchanged regions stay where they are, and the whole
+    /// change is a single undo step.
     fn replace_buffer_with_output(&mut self, output: &str) -> Result<(), String> {
-        let cursor_id = self.active_cursors().primary_id();
-
         // Get current buffer content
         let buffer_content = self.active_state().buffer.to_string().unwrap_or_default();
 
-        // Only replace if content is different
-        if buffer_content == output {
+        let replacements = line_diff::minimal_replacements(&buffer_content, output);
+        if replacements.is_empty() {
             return Ok(());
         }
 
-        let buffer_len = buffer_content.len();
-
-        // Capture cursor position and selection state before replacement
-        let old_cursor_pos = self.active_cursors().primary().position;
-        let old_anchor = self.active_cursors().primary().anchor;
-        let old_sticky_column = self.active_cursors().primary().sticky_column;
-
-        // Delete all content and insert new
-        let delete_event = Event::Delete {
-            range: 0..buffer_len,
-            deleted_text: buffer_content,
-            cursor_id,
-        };
-        let insert_event = Event::Insert {
-            position: 0,
-            text: output.to_string(),
-            cursor_id,
-        };
-
-        // After delete+insert, cursor will be at output.len()
-        // Restore cursor to original position (or clamp to new buffer length)
-        let new_buffer_len = output.len();
-        let new_cursor_pos = old_cursor_pos.min(new_buffer_len);
-
-        // Only add MoveCursor event if position actually changes
-        let mut events = vec![delete_event, insert_event];
-        if new_cursor_pos != new_buffer_len {
-            let move_cursor_event = Event::MoveCursor {
+        // Edits carry no cursor, so no cursor jumps to them; every cursor is
+        // instead moved to where its text ended up
+        let mut events = Vec::with_capacity(replacements.len() * 2);
+        for replacement in &replacements {
+            if !replacement.range.is_empty() {
+                events.push(Event::Delete {
+                    range: replacement.range.clone(),
+                    deleted_text: buffer_content[replacement.range.clone()].to_string(),
+                    cursor_id: CursorId::UNDO_SENTINEL,
+                });
+            }
+            if !replacement.text.is_empty() {
+                events.push(Event::Insert {
+                    position: replacement.range.start,
+                    text: replacement.text.clone(),
+                    cursor_id: CursorId::UNDO_SENTINEL,
+                });
+            }
+        }
+        for (cursor_id, cursor) in self.active_cursors().iter() {
+            events.push(Event::MoveCursor {
                 cursor_id,
-                old_position: new_buffer_len, // Where cursor is after insert
-                new_position: new_cursor_pos,
-                old_anchor: None,
-                new_anchor: old_anchor.map(|a| a.min(new_buffer_len)),
-                old_sticky_column: 0,
-                new_sticky_column: old_sticky_column,
-            };
-            events.push(move_cursor_event);
+                old_position: cursor.position,
+                new_position: map_offset(&replacements, cursor.position),
+                old_anchor: cursor.anchor,
+                new_anchor: cursor.anchor.map(|a| map_offset(&replacements, a)),
+                old_sticky_column: cursor.sticky_column,
+                new_sticky_column: cursor.sticky_column,
+            });
         }
 
-        // Apply as a batch for atomic undo
-        let batch = Event::Batch {
-            events,
-            description: "On-save format".to_string(),
-        };
-        self.active_event_log_mut().append(batch.clone());
-        self.apply_event_to_active_buffer(&batch);
+        // Apply as one bulk edit for atomic undo
+        if let Some(bulk_edit) =
+            self.apply_events_as_bulk_edit(events, "On-save format".to_string())
+        {
+            self.active_event_log_mut().append(bulk_edit);
+        }
 
         Ok(())
     }
@@ -570,6 +565,25 @@ impl Editor {
     }
 }
 
+/// Where byte `offset` of the old text ends up after `replacements`.
+/// Offsets inside a replaced range keep their distance from its start,
+/// clamped to the new text.
+fn map_offset(replacements: &[Replacement], offset: usize) -> usize {
+    let mut shift: isize = 0;
+    for replacement in replacements {
+        let range = &replacement.range;
+        if range.start >= offset {
+            break;
+        }
+        if range.end > offset {
+            let new_start = (range.start as isize + shift) as usize;
+            return new_start + (offset - range.start).min(replacement.text.len());
+        }
+        shift += replacement.text.len() as isize - range.len() as isize;
+    }
+    (offset as isize + shift) as usize
+}
+
 /// Check if a command exists in the system PATH.
 fn command_exists(command: &str) -> bool {
     // Use 'which' on Unix or 'where' on Windows to check if command exists
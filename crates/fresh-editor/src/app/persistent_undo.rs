@@ -0,0 +1,94 @@
+//! Persistent undo history.
+//!
+//! With `editor.persistent_undo` enabled, the undo history of a file buffer
+//! is written to the [`PersistedUndoStore`] when the buffer is closed or the
+//! editor exits, and read back when the file is opened again. Only the
+//! history of an unmodified buffer is stored, together with the hash of the
+//! content it ends at, so it is never replayed onto different text.
+
+use std::path::{Path, PathBuf};
+
+use crate::app::Editor;
+use crate::model::event::{BufferId, Event, EventLog};
+use crate::workspace::PersistedUndoStore;
+
+impl Editor {
+    /// Store the undo history of a file buffer, if enabled.
+    pub(crate) fn save_undo_history(&mut self, buffer_id: BufferId) {
+        if !self.config.editor.persistent_undo {
+            return;
+        }
+        let Some((path, content_hash)) = self.undo_history_key(buffer_id) else {
+            return;
+        };
+        let Some(log) = self.active_window().event_logs.get(&buffer_id) else {
+            return;
+        };
+        let events = persistable_history(log);
+        let max_bytes = self.config.editor.persistent_undo_max_kb as usize * 1024;
+        let undo_dir = self.dir_context.undo_dir();
+        PersistedUndoStore::save(&undo_dir, &path, content_hash, events, max_bytes);
+    }
+
+    /// Store the undo histories of all open file buffers.
+    pub(crate) fn save_all_undo_histories(&mut self) {
+        if !self.config.editor.persistent_undo {
+            return;
+        }
+        let buffer_ids: Vec<BufferId> = self.buffers().keys().copied().collect();
+        for buffer_id in buffer_ids {
+            self.save_undo_history(buffer_id);
+        }
+    }
+
+    /// Load the stored undo history of a file that was just opened.
+    pub(crate) fn restore_undo_history(&mut self, buffer_id: BufferId, path: &Path) {
+        if !self.config.editor.persistent_undo {
+            return;
+        }
+        let Some((_, content_hash)) = self.undo_history_key(buffer_id) else {
+            return;
+        };
+        let undo_dir = self.dir_context.undo_dir();
+        let Some(events) = PersistedUndoStore::load(&undo_dir, path, &content_hash) else {
+            return;
+        };
+        if let Some(log) = self.active_window_mut().event_logs.get_mut(&buffer_id) {
+            tracing::debug!("Restored {} undo steps for {:?}", events.len(), path);
+            log.restore_history(events);
+        }
+    }
+
+    /// Path and content hash under which a buffer's history is stored.
+    /// None for buffers whose history isn't persisted: unnamed, large,
+    /// binary or unsaved ones.
+    fn undo_history_key(&mut self, buffer_id: BufferId) -> Option<(PathBuf, String)> {
+        let state = self.buffers_mut().get_mut(&buffer_id)?;
+        let buffer = &mut state.buffer;
+        if buffer.is_large_file() || buffer.is_binary() || buffer.is_modified() {
+            return None;
+        }
+        let path = buffer.file_path()?.to_path_buf();
+        let len = buffer.len();
+        let content = buffer.get_text_range_mut(0, len).ok()?;
+        Some((path, PersistedUndoStore::content_hash(&content)))
+    }
+}
+
+/// Buffer edits of a log's undo history, oldest first. The history is cut
+/// at the newest edit that can't be persisted, as nothing before it could
+/// be undone.
+fn persistable_history(log: &EventLog) -> Vec<Event> {
+    let mut events = Vec::new();
+    for event in log.undo_history().rev() {
+        if !event.modifies_buffer() {
+            continue;
+        }
+        match event.to_persistable() {
+            Some(event) => events.push(event),
+            None => break,
+        }
+    }
+    events.reverse();
+    events
+}
@@ -1040,7 +1040,7 @@ impl Editor {
         text: String,
     ) {
         let text_len = text.len();
-        if let Some(state) = self
+        let applied = if let Some(state) = self
             .windows
             .get_mut(&self.active_window)
             .map(|w| &mut w.buffers)
@@ -1055,9 +1055,12 @@ impl Editor {
             // Apply to buffer with dummy cursors (real cursors adjusted below)
             state.apply(&mut Cursors::default(), &event);
             if let Some(log) = self.active_window_mut().event_logs.get_mut(&buffer_id) {
-                log.append(event);
+                log.append(event.clone());
             }
-        }
+            Some(event)
+        } else {
+            None
+        };
         // Adjust cursors in all splits that display this buffer
         for leaf_id in self
             .windows
@@ -1077,6 +1080,9 @@ impl Editor {
                 view_state.cursors.adjust_for_edit(position, 0, text_len);
             }
         }
+        if let Some(event) = applied {
+            self.adjust_client_views_for_event(buffer_id, &event);
+        }
     }
 
     /// Handle DeleteRange command
@@ -1087,7 +1093,7 @@ impl Editor {
     ) {
         let delete_start = range.start;
         let delete_len = range.end.saturating_sub(range.start);
-        if let Some(state) = self
+        let applied = if let Some(state) = self
             .windows
             .get_mut(&self.active_window)
             .map(|w| &mut w.buffers)
@@ -1103,9 +1109,12 @@ impl Editor {
             // Apply to buffer with dummy cursors (real cursors adjusted below)
             state.apply(&mut Cursors::default(), &event);
             if let Some(log) = self.active_window_mut().event_logs.get_mut(&buffer_id) {
-                log.append(event);
+                log.append(event.clone());
             }
-        }
+            Some(event)
+        } else {
+            None
+        };
         // Adjust cursors in all splits that display this buffer
         for leaf_id in self
             .windows
@@ -1127,6 +1136,9 @@ impl Editor {
                     .adjust_for_edit(delete_start, delete_len, 0);
             }
         }
+        if let Some(event) = applied {
+            self.adjust_client_views_for_event(buffer_id, &event);
+        }
     }
 
     /// Handle InsertAtCursor command
@@ -1142,6 +1154,7 @@ impl Editor {
         let active_buf = self.active_buffer();
         self.active_window_mut()
             .apply_event_to_buffer(active_buf, split_id, &event);
+        self.adjust_client_views_for_event(active_buf, &event);
         self.active_event_log_mut().append(event);
     }
 
@@ -443,6 +443,28 @@ impl Editor {
             } => {
                 self.handle_request_highlights(buffer_id, range, request_id);
             }
+            PluginCommand::GetDocumentSymbols {
+                buffer_id,
+                request_id,
+            } => {
+                self.handle_get_document_symbols(buffer_id, request_id);
+            }
+            PluginCommand::PrepareHierarchy {
+                buffer_id,
+                position,
+                kind,
+                request_id,
+            } => {
+                self.handle_prepare_hierarchy(buffer_id, position, &kind, request_id);
+            }
+            PluginCommand::GetHierarchyChildren {
+                server,
+                direction,
+                item,
+                request_id,
+            } => {
+                self.handle_get_hierarchy_children(&server, &direction, item, request_id);
+            }
             PluginCommand::CloseSplit { split_id } => {
                 self.handle_close_split(split_id);
             }
@@ -822,6 +844,15 @@ impl Editor {
                 self.handle_send_lsp_request(language, method, params, request_id);
             }
 
+            // ==================== Debug Commands ====================
+            PluginCommand::SendDebugRequest {
+                command,
+                arguments,
+                request_id,
+            } => {
+                self.handle_send_debug_request(command, arguments, request_id);
+            }
+
             // ==================== Clipboard Commands ====================
             PluginCommand::SetClipboard { text } => {
                 self.handle_set_clipboard(text);
@@ -850,6 +881,21 @@ impl Editor {
                 self.handle_kill_host_process(process_id);
             }
 
+            PluginCommand::RequestPluginPermission {
+                plugin_name,
+                capability,
+                detail,
+            } => {
+                self.handle_request_plugin_permission(plugin_name, capability, detail);
+            }
+
+            PluginCommand::RejectCallback { callback_id, error } => {
+                self.plugin_manager
+                    .read()
+                    .unwrap()
+                    .reject_callback(callback_id, error);
+            }
+
             PluginCommand::SetAuthority { payload } => {
                 self.handle_set_authority(payload);
             }
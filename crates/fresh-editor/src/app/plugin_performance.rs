@@ -0,0 +1,155 @@
+//! The "Plugin Performance" view.
+//!
+//! Lists each plugin's cumulative CPU time and per-hook latency, as
+//! recorded by the plugin runtime's watchdog, and marks plugins that were
+//! disabled for exceeding a time or memory limit. Running the command
+//! again refreshes the view.
+
+#[cfg(not(feature = "plugins"))]
+use rust_i18n::t;
+
+use super::Editor;
+
+/// Display name of the plugin performance buffer
+#[cfg(feature = "plugins")]
+const PLUGIN_PERFORMANCE_BUFFER_NAME: &str = "*Plugin Performance*";
+
+impl Editor {
+    /// Open (or refresh) the plugin performance view.
+    pub(crate) fn show_plugin_performance(&mut self) {
+        #[cfg(feature = "plugins")]
+        {
+            use crate::primitives::text_property::TextPropertyEntry;
+
+            let stats = self.plugin_manager.read().unwrap().plugin_stats();
+            let content = format_plugin_performance(&stats);
+
+            let existing = self
+                .active_window()
+                .buffer_metadata
+                .iter()
+                .find(|(_, m)| m.display_name == PLUGIN_PERFORMANCE_BUFFER_NAME)
+                .map(|(id, _)| *id);
+            let buffer_id = existing.unwrap_or_else(|| {
+                let buffer_id = self.active_window_mut().create_virtual_buffer(
+                    PLUGIN_PERFORMANCE_BUFFER_NAME.to_string(),
+                    "special".to_string(),
+                    true,
+                );
+                if let Some(state) = self.active_window_mut().buffers.get_mut(&buffer_id) {
+                    state.editing_disabled = true;
+                    state.margins.configure_for_line_numbers(false);
+                }
+                buffer_id
+            });
+            if let Err(e) =
+                self.set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(content)])
+            {
+                tracing::error!("Failed to set plugin performance content: {}", e);
+                return;
+            }
+            self.set_active_buffer(buffer_id);
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            self.set_status_message(t!("status.plugins_not_available").to_string());
+        }
+    }
+}
+
+/// Render plugin statistics as text, busiest plugin first.
+#[cfg(feature = "plugins")]
+fn format_plugin_performance(
+    stats: &[(String, fresh_plugin_runtime::backend::watchdog::PluginStats)],
+) -> String {
+    use std::fmt::Write;
+
+    let mut content = String::from("Plugin Performance\n");
+    content.push_str("==================\n\n");
+    content.push_str("Press 'q' to close this buffer. Run the command again to refresh.\n");
+
+    if stats.is_empty() {
+        content.push_str("\nNo plugin code has run yet.\n");
+        return content;
+    }
+
+    let mut by_cpu: Vec<_> = stats.iter().collect();
+    by_cpu.sort_by(|a, b| b.1.cpu_time.cmp(&a.1.cpu_time).then(a.0.cmp(&b.0)));
+
+    for (name, plugin) in by_cpu {
+        let _ = write!(
+            content,
+            "\n{}  cpu {}  calls {}",
+            name,
+            format_ms(plugin.cpu_time),
+            plugin.calls
+        );
+        if let Some(limit) = plugin.disabled {
+            let _ = write!(content, "  DISABLED: {}", limit);
+        }
+        content.push('\n');
+
+        if plugin.hooks.is_empty() {
+            continue;
+        }
+        let width = plugin.hooks.keys().map(|h| h.len()).max().unwrap_or(0);
+        let _ = writeln!(
+            content,
+            "  {:<width$}  {:>8}  {:>10}  {:>10}",
+            "hook", "calls", "avg", "max"
+        );
+        for (hook, hook_stats) in &plugin.hooks {
+            let _ = writeln!(
+                content,
+                "  {:<width$}  {:>8}  {:>10}  {:>10}",
+                hook,
+                hook_stats.calls,
+                format_ms(hook_stats.average()),
+                format_ms(hook_stats.max)
+            );
+        }
+    }
+    content
+}
+
+#[cfg(feature = "plugins")]
+fn format_ms(duration: std::time::Duration) -> String {
+    format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(all(test, feature = "plugins"))]
+mod tests {
+    use super::*;
+    use fresh_plugin_runtime::backend::watchdog::{HookStats, LimitExceeded, PluginStats};
+    use std::time::Duration;
+
+    #[test]
+    fn test_busiest_plugin_first_with_hook_table() {
+        let quiet = PluginStats {
+            cpu_time: Duration::from_millis(1),
+            calls: 1,
+            ..Default::default()
+        };
+        let mut busy = PluginStats {
+            cpu_time: Duration::from_millis(2500),
+            calls: 3,
+            disabled: Some(LimitExceeded::Time(Duration::from_secs(2))),
+            ..Default::default()
+        };
+        busy.hooks.insert(
+            "render_start".to_string(),
+            HookStats {
+                calls: 2,
+                total: Duration::from_millis(4),
+                max: Duration::from_millis(3),
+            },
+        );
+        let text =
+            format_plugin_performance(&[("quiet".to_string(), quiet), ("busy".to_string(), busy)]);
+
+        let busy_at = text.find("busy  cpu 2500.00 ms  calls 3").unwrap();
+        assert!(busy_at < text.find("quiet  cpu 1.00 ms").unwrap());
+        assert!(text.contains("DISABLED: a call ran longer than 2s"));
+        assert!(text.contains("render_start         2     2.00 ms     3.00 ms"));
+    }
+}
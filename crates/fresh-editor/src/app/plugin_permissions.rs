@@ -0,0 +1,122 @@
+//! Asking the user for plugin package permissions.
+//!
+//! A package plugin requests each capability its manifest declares the
+//! first time it uses it (see `fresh_core::permissions`). Requests are
+//! queued and asked one at a time in a confirm prompt. Answers go into the
+//! shared permission registry and are persisted in the plugin's
+//! `plugins.<name>.grants` config, where the settings UI can revoke them.
+//! Dismissing the prompt denies the capability for this session only.
+
+use std::collections::{BTreeMap, HashMap};
+
+use fresh_core::permissions::Capability;
+use rust_i18n::t;
+
+use super::Editor;
+use crate::config::PluginConfig;
+use crate::services::plugins::PluginManager;
+use crate::view::prompt::PromptType;
+
+/// Load the persisted answers from the plugin config into the plugin
+/// runtime's permission registry.
+pub(super) fn sync_plugin_grants(
+    plugin_manager: &PluginManager,
+    plugins: &HashMap<String, PluginConfig>,
+) {
+    let Some(registry) = plugin_manager.permissions_handle() else {
+        return;
+    };
+    let grants: HashMap<String, BTreeMap<Capability, bool>> = plugins
+        .iter()
+        .filter(|(_, config)| !config.grants.is_empty())
+        .map(|(name, config)| (name.clone(), config.grants.clone()))
+        .collect();
+    if let Ok(mut state) = registry.write() {
+        state.set_grants(grants);
+    }
+}
+
+impl Editor {
+    /// Re-read the persisted answers after the config changed.
+    pub(super) fn sync_plugin_grants(&self) {
+        sync_plugin_grants(&self.plugin_manager.read().unwrap(), &self.config.plugins);
+    }
+
+    /// Queue a permission question from a plugin.
+    pub(super) fn handle_request_plugin_permission(
+        &mut self,
+        plugin_name: String,
+        capability: Capability,
+        detail: String,
+    ) {
+        self.pending_plugin_permissions
+            .push_back((plugin_name, capability, detail));
+        self.prompt_next_plugin_permission();
+    }
+
+    /// Ask the oldest queued permission question, unless another prompt
+    /// is open.
+    pub(super) fn prompt_next_plugin_permission(&mut self) {
+        if self.active_window().prompt.is_some() {
+            return;
+        }
+        let Some((plugin_name, capability, detail)) = self.pending_plugin_permissions.pop_front()
+        else {
+            return;
+        };
+        self.start_prompt(
+            t!(
+                "prompt.plugin_permission",
+                plugin = &plugin_name,
+                capability = capability.as_str(),
+                detail = &detail
+            )
+            .to_string(),
+            PromptType::ConfirmPluginPermission {
+                plugin_name,
+                capability,
+            },
+        );
+    }
+
+    /// Record the user's answer. With `persist`, the answer is saved in
+    /// the plugin's config; otherwise it lasts for this session.
+    pub(super) fn answer_plugin_permission(
+        &mut self,
+        plugin_name: &str,
+        capability: Capability,
+        allowed: bool,
+        persist: bool,
+    ) {
+        if let Some(registry) = self.plugin_manager.read().unwrap().permissions_handle() {
+            if let Ok(mut state) = registry.write() {
+                state.answer(plugin_name, capability, allowed);
+            }
+        }
+
+        let key = if allowed {
+            "status.plugin_permission_allowed"
+        } else {
+            "status.plugin_permission_denied"
+        };
+        self.set_status_message(
+            t!(key, plugin = plugin_name, capability = capability.as_str()).to_string(),
+        );
+
+        if !persist {
+            return;
+        }
+        self.config_mut()
+            .plugins
+            .entry(plugin_name.to_string())
+            .or_default()
+            .grants
+            .insert(capability, allowed);
+        let pointer = format!(
+            "/plugins/{}/grants/{}",
+            plugin_name.replace('~', "~0").replace('/', "~1"),
+            capability
+        );
+        self.persist_config_change(&pointer, serde_json::Value::Bool(allowed));
+    }
+}
@@ -4,7 +4,7 @@
 
 use super::Editor;
 use crate::model::event::Event;
-use crate::primitives::snippet::{expand_snippet, is_snippet};
+use crate::primitives::snippet::is_snippet;
 use crate::primitives::word_navigation::find_completion_word_start;
 use rust_i18n::t;
 
@@ -92,6 +92,79 @@ impl Editor {
                 PopupConfirmResult::EarlyReturn
             }
 
+            Some(PopupResolver::CodeLens) => {
+                let selected_index = self
+                    .active_state()
+                    .popups
+                    .top()
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.as_ref())
+                    .and_then(|data| data.parse::<usize>().ok());
+                self.hide_popup();
+                if let Some(index) = selected_index {
+                    let buffer_id = self.active_buffer();
+                    self.run_code_lens(buffer_id, index);
+                }
+                PopupConfirmResult::EarlyReturn
+            }
+
+            Some(PopupResolver::DebugConfiguration) => {
+                let selected_index = self
+                    .active_state()
+                    .popups
+                    .top()
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.as_ref())
+                    .and_then(|data| data.parse::<usize>().ok());
+                self.hide_popup();
+                if let Some(index) = selected_index {
+                    self.debug_start_configuration(index);
+                }
+                PopupConfirmResult::EarlyReturn
+            }
+
+            Some(PopupResolver::Task) => {
+                let name = self
+                    .active_state()
+                    .popups
+                    .top()
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.clone());
+                self.hide_popup();
+                if let Some(name) = name {
+                    self.run_task(&name);
+                }
+                PopupConfirmResult::EarlyReturn
+            }
+
+            Some(PopupResolver::Breadcrumb { segment }) => {
+                let data = self
+                    .active_state()
+                    .popups
+                    .top()
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.clone());
+                self.hide_popup();
+                if let Some(data) = data {
+                    self.confirm_breadcrumb(segment, &data);
+                }
+                PopupConfirmResult::EarlyReturn
+            }
+
+            Some(PopupResolver::SnippetChoice) => {
+                let choice = self
+                    .active_state()
+                    .popups
+                    .top()
+                    .and_then(|p| p.selected_item())
+                    .and_then(|item| item.data.clone());
+                self.hide_popup();
+                if let Some(choice) = choice {
+                    self.apply_snippet_choice(&choice);
+                }
+                PopupConfirmResult::EarlyReturn
+            }
+
             Some(PopupResolver::LspConfirm { language }) => {
                 let action = self
                     .active_state()
@@ -133,13 +206,14 @@ impl Editor {
                     .top()
                     .and_then(|p| p.selected_item())
                     .map(|item| (item.text.clone(), item.data.clone()));
+                // Hide first: a snippet may open a choice popup of its own
+                self.hide_popup();
                 if let Some((label, insert_text)) = completion_info {
                     if let Some(text) = insert_text {
                         self.insert_completion_text(text);
                     }
                     self.apply_completion_additional_edits(&label);
                 }
-                self.hide_popup();
                 PopupConfirmResult::Done
             }
 
@@ -151,16 +225,9 @@ impl Editor {
     }
 
     /// Insert completion text, replacing the word prefix at cursor.
-    /// If the text contains LSP snippet syntax, it will be expanded.
+    /// If the text contains LSP snippet syntax, a snippet session is
+    /// started on its tabstops.
     fn insert_completion_text(&mut self, text: String) {
-        // Check if this is a snippet and expand it
-        let (insert_text, cursor_offset) = if is_snippet(&text) {
-            let expanded = expand_snippet(&text);
-            (expanded.text, Some(expanded.cursor_offset))
-        } else {
-            (text, None)
-        };
-
         let (cursor_id, cursor_pos, word_start) = {
             let cursors = self.active_cursors();
             let cursor_id = cursors.primary_id();
@@ -170,6 +237,11 @@ impl Editor {
             (cursor_id, cursor_pos, word_start)
         };
 
+        if is_snippet(&text) {
+            self.insert_snippet(&text, word_start..cursor_pos);
+            return;
+        }
+
         let deleted_text = if word_start < cursor_pos {
             self.active_state_mut()
                 .get_text_range(word_start, cursor_pos)
@@ -194,39 +266,11 @@ impl Editor {
 
         let insert_event = Event::Insert {
             position: insert_pos,
-            text: insert_text.clone(),
+            text,
             cursor_id,
         };
 
         self.log_and_apply_event(&insert_event);
-
-        // If this was a snippet, position cursor at the snippet's $0 location
-        if let Some(offset) = cursor_offset {
-            let new_cursor_pos = insert_pos + offset;
-            // Get current cursor position after the insert
-            let current_pos = self.active_cursors().primary().position;
-            if current_pos != new_cursor_pos {
-                let move_event = Event::MoveCursor {
-                    cursor_id,
-                    old_position: current_pos,
-                    new_position: new_cursor_pos,
-                    old_anchor: None,
-                    new_anchor: None,
-                    old_sticky_column: 0,
-                    new_sticky_column: 0,
-                };
-                let split_id = self
-                    .windows
-                    .get(&self.active_window)
-                    .and_then(|w| w.splits.as_ref())
-                    .map(|(mgr, _)| mgr)
-                    .expect("active window must have a populated split layout")
-                    .active_split();
-                let buffer_id = self.active_buffer();
-                self.active_window_mut()
-                    .apply_event_to_buffer(buffer_id, split_id, &move_event);
-            }
-        }
     }
 
     /// Apply additional_text_edits from the accepted completion item (e.g. auto-imports).
@@ -307,6 +351,14 @@ impl Editor {
                 self.hide_popup();
             }
 
+            Some(PopupResolver::CodeLens)
+            | Some(PopupResolver::DebugConfiguration)
+            | Some(PopupResolver::Task)
+            | Some(PopupResolver::SnippetChoice)
+            | Some(PopupResolver::Breadcrumb { .. }) => {
+                self.hide_popup();
+            }
+
             Some(PopupResolver::LspConfirm { language: _ }) => {
                 self.set_status_message(t!("lsp.startup_cancelled_msg").to_string());
                 self.hide_popup();
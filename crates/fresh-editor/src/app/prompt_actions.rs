@@ -10,6 +10,7 @@ use super::BufferMetadata;
 use super::Editor;
 use crate::config_io::{ConfigLayer, ConfigResolver};
 use crate::input::keybindings::Action;
+use crate::model::event::Event;
 use crate::primitives::path_utils::expand_tilde;
 use crate::services::plugins::hooks::HookArgs;
 use crate::view::prompt::PromptType;
@@ -186,6 +187,8 @@ impl Editor {
                     }
                 }
             }
+            PromptType::HexGotoOffset => self.hex_goto_offset(&input),
+            PromptType::HexSearch => self.hex_search_bytes(&input),
             PromptType::GotoLineScanConfirm => {
                 let answer = input.trim().to_lowercase();
                 if answer == "y" || answer == "yes" {
@@ -409,6 +412,15 @@ impl Editor {
                     self.set_status_message(t!("buffer.save_cancelled").to_string());
                 }
             }
+            PromptType::ConfirmPluginPermission {
+                plugin_name,
+                capability,
+            } => {
+                let input_lower = input.trim().to_lowercase();
+                let allowed = input_lower == "a" || input_lower == "allow";
+                self.answer_plugin_permission(&plugin_name, capability, allowed, true);
+                self.prompt_next_plugin_permission();
+            }
             PromptType::ConfirmCloseBuffer { buffer_id } => {
                 if self.handle_confirm_close_buffer(&input, buffer_id) {
                     return PromptResult::EarlyReturn;
@@ -624,6 +636,9 @@ impl Editor {
             PromptType::SelectLocale => {
                 self.apply_locale(input.trim());
             }
+            PromptType::InsertSnippet => {
+                self.insert_library_snippet(input.trim());
+            }
             PromptType::CopyWithFormattingTheme => {
                 self.copy_selection_with_theme(input.trim());
             }
@@ -1622,11 +1637,14 @@ impl Editor {
     ) -> PromptResult {
         use crate::input::quick_open::QuickOpenResult;
 
+        // Whatever was chosen, the `%` search (if any) is no longer needed.
+        self.cancel_workspace_symbol_search();
+
         // Any live goto-line preview must be resolved before executing the
         // result: a GotoLine confirm accepts the preview as-is, everything
         // else (file/buffer/action/etc.) should see the pre-preview state.
         match &result {
-            QuickOpenResult::GotoLine(_) => {
+            QuickOpenResult::GotoLine(_) | QuickOpenResult::GotoOffset(_) => {
                 // Commit the preview: discard the saved snapshot without
                 // restoring, since the cursor is already at the target.
                 self.active_window_mut().goto_line_preview = None;
@@ -1685,6 +1703,15 @@ impl Editor {
                 }
                 PromptResult::Done
             }
+            QuickOpenResult::GotoOffset(offset) => {
+                self.goto_byte_offset(offset);
+                self.apply_event_to_active_buffer(&Event::Recenter);
+                PromptResult::Done
+            }
+            QuickOpenResult::OpenLocation(location) => {
+                self.jump_to_lsp_location(&location);
+                PromptResult::Done
+            }
             QuickOpenResult::None => {
                 self.set_status_message(t!("status.no_selection").to_string());
                 PromptResult::Done
@@ -1696,7 +1723,7 @@ impl Editor {
         }
     }
 
-    fn open_file_with_jump(
+    pub(super) fn open_file_with_jump(
         &mut self,
         full_path: std::path::PathBuf,
         line: Option<usize>,
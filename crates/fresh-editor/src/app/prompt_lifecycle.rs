@@ -12,9 +12,13 @@ use rust_i18n::t;
 use crate::input::command_registry::CommandRegistry;
 use crate::input::commands::Suggestion;
 use crate::input::keybindings::KeyContext;
-use crate::input::quick_open::{BufferInfo, QuickOpenContext};
+use crate::input::quick_open::{
+    BufferInfo, DocumentSymbolProvider, QuickOpenContext, WorkspaceSymbolProvider,
+};
+use crate::model::event::Event;
 use crate::services::async_bridge::AsyncMessage;
 use crate::services::plugins::PluginManager;
+use crate::types::LspFeature;
 use crate::view::prompt::{Prompt, PromptType};
 
 use super::file_open;
@@ -184,6 +188,31 @@ impl Editor {
                 .is_some()
         };
 
+        let (document_symbols, document_symbols_pending) = {
+            let buffer_id = self.active_buffer();
+            let symbols = &self.active_window().document_symbols;
+            let cached = symbols
+                .cached(buffer_id)
+                .map(|c| Arc::clone(&c.symbols))
+                .unwrap_or_default();
+            let version = self.active_state().buffer.version();
+            (
+                cached,
+                symbols.pending_request_for(buffer_id, version).is_some(),
+            )
+        };
+
+        let (workspace_symbols, workspace_symbols_pending) = self
+            .active_window()
+            .workspace_symbol_search
+            .as_ref()
+            .map(|search| (Arc::clone(&search.entries), search.is_pending()))
+            .unwrap_or_default();
+        let has_workspace_symbol_servers = self.lsp().is_some_and(|lsp| {
+            !lsp.all_handles_for_feature(LspFeature::WorkspaceSymbols)
+                .is_empty()
+        });
+
         QuickOpenContext {
             cwd: self.working_dir.display().to_string(),
             open_buffers,
@@ -204,11 +233,30 @@ impl Editor {
                 .map(|s| s.to_string()),
             has_lsp_config,
             relative_line_numbers: self.config.editor.relative_line_numbers,
+            document_symbols,
+            document_symbols_pending,
+            workspace_symbols,
+            workspace_symbols_pending,
+            has_workspace_symbol_servers,
         }
     }
 
     /// Update Quick Open suggestions based on current input, dispatching through the registry
     pub(super) fn update_quick_open_suggestions(&mut self, input: &str) {
+        let shows_symbols = self.quick_open_shows_symbols(input);
+        if shows_symbols {
+            self.refresh_document_symbols(self.active_buffer());
+        }
+        let workspace_query = self
+            .quick_open_registry
+            .get_provider_for_input(input)
+            .filter(|(provider, _)| provider.as_any().is::<WorkspaceSymbolProvider>())
+            .map(|(_, query)| query.trim().to_string());
+        match workspace_query {
+            Some(query) => self.search_workspace_symbols(&query),
+            None => self.cancel_workspace_symbol_search(),
+        }
+
         let context = self.build_quick_open_context();
         let suggestions = if let Some((provider, query)) =
             self.quick_open_registry.get_provider_for_input(input)
@@ -234,11 +282,61 @@ impl Editor {
         //
         // Relative input (`:+N`/`:-N`) is intentionally not previewed: the
         // target shifts on every digit typed, which is disorienting.
+        //
+        // The `@` symbol provider previews the selected symbol instead.
+        if shows_symbols {
+            self.preview_quick_open_symbol();
+            return;
+        }
         let input = input.trim();
         let target = Self::parse_quick_open_goto_line_target(input);
         self.apply_goto_line_preview(target);
     }
 
+    /// Whether `input` routes to the `@` document symbol provider.
+    pub(super) fn quick_open_shows_symbols(&self, input: &str) -> bool {
+        self.quick_open_registry
+            .get_provider_for_input(input)
+            .is_some_and(|(provider, _)| provider.as_any().is::<DocumentSymbolProvider>())
+    }
+
+    /// Whether `input` routes to the `%` workspace symbol provider.
+    pub(super) fn quick_open_shows_workspace_symbols(&self, input: &str) -> bool {
+        self.quick_open_registry
+            .get_provider_for_input(input)
+            .is_some_and(|(provider, _)| provider.as_any().is::<WorkspaceSymbolProvider>())
+    }
+
+    /// Live preview for the `@` provider: jump to the selected symbol (saving
+    /// the original cursor on the first jump), or restore the saved cursor
+    /// when nothing is selectable.
+    pub(super) fn preview_quick_open_symbol(&mut self) {
+        let offset = self
+            .active_window()
+            .prompt
+            .as_ref()
+            .and_then(|prompt| {
+                prompt
+                    .selected_suggestion
+                    .and_then(|i| prompt.suggestions.get(i))
+            })
+            .filter(|suggestion| !suggestion.disabled)
+            .and_then(|suggestion| suggestion.value.as_deref())
+            .and_then(|value| value.parse::<usize>().ok());
+        let Some(offset) = offset else {
+            self.restore_goto_line_preview_snapshot();
+            return;
+        };
+
+        self.save_goto_line_preview_snapshot();
+        self.goto_byte_offset(offset);
+        self.apply_event_to_active_buffer(&Event::Recenter);
+        let new_position = self.active_cursors().primary().position;
+        if let Some(snap) = self.active_window_mut().goto_line_preview.as_mut() {
+            snap.last_jump_position = new_position;
+        }
+    }
+
     /// Parse a Quick Open input string for a `:<N>` goto-line preview target.
     /// Only absolute inputs are previewed; relative inputs return `None`.
     pub(super) fn parse_quick_open_goto_line_target(input: &str) -> Option<usize> {
@@ -409,13 +507,11 @@ impl Editor {
         // Determine initial directory
         let buffer_id = self.active_buffer();
 
-        // For terminal buffers, use the terminal's initial CWD or fall back to project root
-        // This avoids showing the terminal backing file directory which is confusing for users
+        // For terminal buffers, use the shell's current (or initial) CWD or fall back to
+        // project root. This avoids showing the terminal backing file directory which is
+        // confusing for users
         let initial_dir = if self.active_window().is_terminal_buffer(buffer_id) {
-            self.active_window()
-                .get_terminal_id(buffer_id)
-                .and_then(|tid| self.active_window().terminal_manager.get(tid))
-                .and_then(|handle| handle.cwd())
+            self.active_terminal_cwd()
                 .unwrap_or_else(|| self.working_dir.clone())
         } else {
             self.active_state()
@@ -769,6 +865,7 @@ impl Editor {
                             fp.cancel_loading();
                         }
                     }
+                    self.cancel_workspace_symbol_search();
                     // Undo any live goto-line preview so the cursor returns to
                     // where it was before the prompt was opened.
                     self.restore_goto_line_preview_snapshot();
@@ -778,6 +875,13 @@ impl Editor {
                     // where it was before the prompt was opened.
                     self.restore_goto_line_preview_snapshot();
                 }
+                PromptType::ConfirmPluginPermission {
+                    plugin_name,
+                    capability,
+                } => {
+                    // Dismissing denies for this session without remembering it
+                    self.answer_plugin_permission(plugin_name, *capability, false, false);
+                }
                 _ => {}
             }
         }
@@ -804,6 +908,9 @@ impl Editor {
         if let Some(original_theme) = theme_to_restore {
             self.preview_theme(&original_theme);
         }
+
+        // Ask plugin permission questions that arrived while this prompt was open
+        self.prompt_next_plugin_permission();
     }
 
     /// Handle mouse wheel scroll in prompt with suggestions.
@@ -895,6 +1002,7 @@ impl Editor {
                     | PromptType::RestartLspServer
                     | PromptType::SelectTheme { .. }
                     | PromptType::SelectLocale
+                    | PromptType::InsertSnippet
                     | PromptType::SwitchToTab
                     | PromptType::SetLanguage
                     | PromptType::SetEncoding
@@ -1189,8 +1297,9 @@ impl Editor {
                     prompt.filter_suggestions(false);
                 }
             }
-            PromptType::SelectLocale => {
-                // Locale selection also matches on description (language names)
+            PromptType::SelectLocale | PromptType::InsertSnippet => {
+                // Locale and snippet selection also match on description
+                // (language names, snippet prefixes)
                 if let Some(prompt) = &mut self.active_window_mut().prompt {
                     prompt.filter_suggestions(true);
                 }
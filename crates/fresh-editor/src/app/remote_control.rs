@@ -0,0 +1,251 @@
+//! Remote-control requests sent over the session control socket.
+//!
+//! `fresh --cmd session send <session> <method> [params]` delivers a
+//! `ClientControl::Request` to the session server, which answers it with
+//! [`Editor::handle_remote_request`] on the view of the client that last
+//! typed. Methods:
+//!
+//! - `action` `{name, args?}` — run an action by its keybinding name
+//! - `command` `{name}` — run a command palette entry (built-in or plugin)
+//! - `buffers` — list the open buffers
+//! - `cursors` — cursors of the active buffer
+//! - `diagnostics` — stored LSP diagnostics, by file URI
+//! - `insert` `{text}` — insert text at every cursor, like a paste
+//! - `eval` `{code, typescript?}` — run a plugin snippet
+//!
+//! `subscribe` / `unsubscribe` `{events}` are handled by the server, which
+//! forwards the named plugin hooks (`after_file_save`,
+//! `diagnostics_updated`, ...) to the client as notifications.
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::{json, Value};
+
+use super::Editor;
+use crate::input::keybindings::Action;
+
+/// Name under which `eval` snippets are loaded; each snippet replaces
+/// the previous one.
+const EVAL_PLUGIN_NAME: &str = "remote-eval";
+
+impl Editor {
+    /// Answer a remote-control request. Returns the JSON result, or an
+    /// error message for the client.
+    pub fn handle_remote_request(&mut self, method: &str, params: &Value) -> Result<Value, String> {
+        match method {
+            "action" => {
+                let name = string_param(params, "name")?;
+                let args: HashMap<String, Value> = match params.get("args") {
+                    Some(Value::Object(map)) => map.clone().into_iter().collect(),
+                    Some(Value::Null) | None => HashMap::new(),
+                    Some(_) => return Err("'args' must be an object".to_string()),
+                };
+                let action = Action::from_str(name, &args)
+                    .ok_or_else(|| format!("missing arguments for action '{}'", name))?;
+                if let Action::PluginAction(ref plugin_action) = action {
+                    if !self.is_registered_plugin_action(plugin_action) {
+                        return Err(format!("unknown action '{}'", name));
+                    }
+                }
+                self.run_remote_action(action)
+            }
+            "command" => {
+                let name = string_param(params, "name")?;
+                let command = self
+                    .command_registry()
+                    .read()
+                    .unwrap()
+                    .get_all()
+                    .into_iter()
+                    .find(|c| c.name == name || c.get_localized_name() == name)
+                    .ok_or_else(|| format!("unknown command '{}'", name))?;
+                self.run_remote_action(command.action)
+            }
+            "buffers" => Ok(self.remote_buffers()),
+            "cursors" => Ok(self.remote_cursors()),
+            "diagnostics" => {
+                let mut diagnostics: Vec<_> = self.get_stored_diagnostics().iter().collect();
+                diagnostics.sort_by(|a, b| a.0.cmp(b.0));
+                Ok(Value::Array(
+                    diagnostics
+                        .into_iter()
+                        .map(|(uri, diagnostics)| json!({"uri": uri, "diagnostics": diagnostics}))
+                        .collect(),
+                ))
+            }
+            "insert" => {
+                let text = string_param(params, "text")?;
+                self.paste_text(text.to_string());
+                Ok(Value::Null)
+            }
+            "eval" => {
+                let code = string_param(params, "code")?;
+                let typescript = params
+                    .get("typescript")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(true);
+                self.plugin_manager
+                    .read()
+                    .unwrap()
+                    .load_plugin_from_source(code, EVAL_PLUGIN_NAME, typescript)
+                    .map_err(|e| e.to_string())?;
+                Ok(Value::Null)
+            }
+            _ => Err(format!("unknown method '{}'", method)),
+        }
+    }
+
+    /// Record plugin hook calls for the given hook names, to be collected
+    /// with [`Self::take_remote_notifications`].
+    pub fn set_remote_subscriptions(&self, hooks: HashSet<String>) {
+        self.plugin_manager
+            .read()
+            .unwrap()
+            .set_observed_hooks(hooks);
+    }
+
+    /// Take the subscribed hook calls since the last call, as
+    /// `(hook name, args)` pairs.
+    pub fn take_remote_notifications(&self) -> Vec<(String, Value)> {
+        self.plugin_manager.read().unwrap().take_observed_hooks()
+    }
+
+    fn run_remote_action(&mut self, action: Action) -> Result<Value, String> {
+        self.handle_action(action).map_err(|e| e.to_string())?;
+        Ok(Value::Null)
+    }
+
+    fn is_registered_plugin_action(&self, name: &str) -> bool {
+        self.command_registry()
+            .read()
+            .unwrap()
+            .get_all()
+            .iter()
+            .any(|c| matches!(&c.action, Action::PluginAction(a) if a == name))
+    }
+
+    fn remote_buffers(&self) -> Value {
+        let active = self.active_buffer();
+        let metadata = &self.active_window().buffer_metadata;
+        let mut ids: Vec<_> = self.buffers().keys().copied().collect();
+        ids.sort_by_key(|id| id.0);
+        Value::Array(
+            ids.into_iter()
+                .filter_map(|id| {
+                    let state = self.buffers().get(&id)?;
+                    let meta = metadata.get(&id);
+                    Some(json!({
+                        "id": id.0,
+                        "name": meta.map(|m| m.display_name.as_str()),
+                        "path": state.buffer.file_path().map(|p| p.display().to_string()),
+                        "modified": state.buffer.is_modified(),
+                        "length": state.buffer.len(),
+                        "language": state.language,
+                        "read_only": meta.is_some_and(|m| m.read_only),
+                        "active": id == active,
+                    }))
+                })
+                .collect(),
+        )
+    }
+
+    fn remote_cursors(&self) -> Value {
+        let buffer = &self.active_state().buffer;
+        let cursors = self.active_cursors();
+        let primary = cursors.primary().position;
+        let mut list: Vec<_> = cursors.iter().map(|(_, c)| c).collect();
+        list.sort_by_key(|c| c.position);
+        let cursors: Vec<Value> = list
+            .into_iter()
+            .map(|cursor| {
+                let (line, column) = buffer.position_to_line_col(cursor.position);
+                json!({
+                    "position": cursor.position,
+                    "line": line + 1,
+                    "column": column + 1,
+                    "selection": cursor.selection_range().map(|r| [r.start, r.end]),
+                    "primary": cursor.position == primary,
+                })
+            })
+            .collect();
+        json!({
+            "buffer_id": self.active_buffer().0,
+            "cursors": cursors,
+        })
+    }
+}
+
+fn string_param<'a>(params: &'a Value, key: &str) -> Result<&'a str, String> {
+    params
+        .get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("missing string parameter '{}'", key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor(temp: &tempfile::TempDir) -> Editor {
+        Editor::new(
+            crate::config::Config::default(),
+            80,
+            24,
+            crate::config_io::DirectoryContext::for_testing(temp.path()),
+            crate::view::color_support::ColorCapability::TrueColor,
+            std::sync::Arc::new(crate::model::filesystem::StdFileSystem),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn insert_action_and_cursor_queries() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut editor = editor(&temp);
+        editor
+            .handle_remote_request("insert", &json!({"text": "hello\nworld"}))
+            .unwrap();
+        editor
+            .handle_remote_request("action", &json!({"name": "move_line_start"}))
+            .unwrap();
+
+        let result = editor
+            .handle_remote_request("cursors", &Value::Null)
+            .unwrap();
+        let cursor = &result["cursors"][0];
+        assert_eq!(cursor["line"], 2);
+        assert_eq!(cursor["column"], 1);
+        assert_eq!(cursor["primary"], true);
+
+        let buffers = editor
+            .handle_remote_request("buffers", &Value::Null)
+            .unwrap();
+        let active: Vec<_> = buffers
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|b| b["active"] == true)
+            .collect();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0]["length"], 11);
+        assert_eq!(active[0]["modified"], true);
+    }
+
+    #[test]
+    fn bad_requests_are_reported() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut editor = editor(&temp);
+        assert!(editor
+            .handle_remote_request("action", &json!({"name": "no_such_action"}))
+            .unwrap_err()
+            .contains("unknown action"));
+        assert!(editor
+            .handle_remote_request("insert", &json!({}))
+            .unwrap_err()
+            .contains("'text'"));
+        assert!(editor
+            .handle_remote_request("frobnicate", &Value::Null)
+            .unwrap_err()
+            .contains("unknown method"));
+    }
+}
@@ -95,6 +95,10 @@ impl Editor {
             self.maybe_request_semantic_tokens_range(buffer_id, start_line, end_line);
             self.maybe_request_semantic_tokens_full_debounced(buffer_id);
             self.maybe_request_folding_ranges_debounced(buffer_id);
+            self.maybe_request_code_lens_debounced(buffer_id);
+            if self.config.editor.show_breadcrumbs {
+                self.maybe_refresh_document_symbols_debounced(buffer_id);
+            }
         }
 
         {
@@ -125,13 +129,17 @@ impl Editor {
                 .get_mut(&active_id)
                 .expect("active window must exist")
                 .buffers;
-            for (buffer_id, top_byte, height) in prep_targets {
+            for &(buffer_id, top_byte, height) in &prep_targets {
                 if let Some(state) = win_buffers.get_mut(&buffer_id) {
                     if let Err(e) = state.prepare_for_render(top_byte, height) {
                         tracing::error!("Failed to prepare buffer for render: {}", e);
                     }
                 }
             }
+            // Spell check what is now loaded and about to be drawn
+            if self.config.editor.spell_check {
+                self.update_spell_check(&prep_targets);
+            }
         }
 
         // Refresh search highlights only during incremental search (when prompt is active)
@@ -617,6 +625,10 @@ impl Editor {
             || self
                 .active_window()
                 .pending_goto_definition_request
+                .is_some()
+            || self
+                .active_window()
+                .pending_goto_locations_request
                 .is_some();
 
         // Hide the hardware cursor when menu is open, file explorer is focused, terminal mode,
@@ -667,6 +679,12 @@ impl Editor {
         // popup).
         let mut pending_hardware_cursor: Option<(u16, u16)> = None;
 
+        let breadcrumb_bars = self
+            .config
+            .editor
+            .show_breadcrumbs
+            .then(|| self.breadcrumb_bars());
+
         let _content_span = tracing::info_span!("render_content").entered();
         // Take a single mutable borrow on the active window's splits and
         // split it into (&SplitManager, &mut HashMap<...>) — Rust can
@@ -706,6 +724,9 @@ impl Editor {
             view_line_mappings,
             horizontal_scrollbar_areas,
             grouped_separator_areas,
+            minimap_areas,
+            sticky_scroll_areas,
+            breadcrumb_layouts,
         ) = SplitRenderer::render_content(
             frame,
             editor_content_area,
@@ -737,9 +758,21 @@ impl Editor {
             self.software_cursor_only,
             self.config.editor.show_vertical_scrollbar,
             self.config.editor.show_horizontal_scrollbar,
+            if self.config.editor.show_minimap {
+                self.config.editor.minimap_width
+            } else {
+                0
+            },
+            if self.config.editor.sticky_scroll {
+                self.config.editor.sticky_scroll_max_lines
+            } else {
+                0
+            },
+            breadcrumb_bars.as_ref(),
             self.config.editor.diagnostics_inline_text,
             self.config.editor.show_tilde,
             self.config.editor.highlight_current_column,
+            self.config.editor.show_indent_guides,
             __cell_theme_map_mut,
             size.width,
             &mut pending_hardware_cursor,
@@ -867,6 +900,9 @@ impl Editor {
 
         self.active_layout_mut().split_areas = split_areas;
         self.active_layout_mut().horizontal_scrollbar_areas = horizontal_scrollbar_areas;
+        self.active_layout_mut().minimap_areas = minimap_areas;
+        self.active_layout_mut().sticky_scroll_areas = sticky_scroll_areas;
+        self.active_layout_mut().breadcrumb_layouts = breadcrumb_layouts;
         self.active_layout_mut().tab_layouts = tab_layouts;
         self.active_layout_mut().close_split_areas = close_split_areas;
         self.active_layout_mut().maximize_split_areas = maximize_split_areas;
@@ -2049,13 +2085,18 @@ impl Editor {
             self.config.editor.use_terminal_bg,
             self.session_mode || !self.software_cursor_only,
             self.software_cursor_only,
-            // Scrollbars are noisy in a small preview rect; the
-            // active session's chrome is the source of truth.
+            // Scrollbars, the minimap, sticky scroll and breadcrumbs are
+            // noisy in a small preview rect; the active session's chrome
+            // is the source of truth.
             false,
             false,
+            0,
+            0,
+            None,
             self.config.editor.diagnostics_inline_text,
             false, // hide tilde markers in the preview
             self.config.editor.highlight_current_column,
+            self.config.editor.show_indent_guides,
             &mut scratch_cell_theme_map,
             inner.width,
             &mut scratch_pending_cursor,
@@ -2779,6 +2820,7 @@ impl Editor {
                 let diagnostics_inline_text = self.config.editor.diagnostics_inline_text;
                 let show_tilde = false; // preview hides tilde markers
                 let highlight_current_column = self.config.editor.highlight_current_column;
+                let show_indent_guides = self.config.editor.show_indent_guides;
                 let screen_width = frame.area().width;
 
                 let ansi_ref = self.ansi_background.as_ref();
@@ -2843,6 +2885,7 @@ impl Editor {
                         diagnostics_inline_text,
                         show_tilde,
                         highlight_current_column,
+                        show_indent_guides,
                         cell_theme_map,
                         screen_width,
                     );
@@ -3313,6 +3356,12 @@ impl Editor {
             __win_l.tab_bar_visible,
             self.config.editor.show_vertical_scrollbar,
             self.config.editor.show_horizontal_scrollbar,
+            if self.config.editor.show_minimap {
+                self.config.editor.minimap_width
+            } else {
+                0
+            },
+            self.config.editor.show_breadcrumbs,
             self.config.editor.diagnostics_inline_text,
             self.config.editor.show_tilde,
         );
@@ -1,10 +1,10 @@
 //! Mouse-driven scrollbar input on `Editor`.
 //!
 //! Mouse-wheel scrolling, horizontal panning, and the click/drag handlers
-//! for both the regular vertical scrollbar and the composite-buffer
-//! scrollbar (used in unified diff views). Pure scrollbar math lives in
-//! `super::scrollbar_math`; these methods do the side-effecting work of
-//! mutating viewports and split state.
+//! for the regular vertical scrollbar, the minimap, and the
+//! composite-buffer scrollbar (used in unified diff views). Pure scrollbar
+//! math lives in `super::scrollbar_math`; these methods do the
+//! side-effecting work of mutating viewports and split state.
 
 use anyhow::Result as AnyhowResult;
 
@@ -107,6 +107,20 @@ impl crate::app::window::Window {
             return Ok(());
         }
 
+        // Hex view scrolls whole rows of bytes
+        let buffer_len = self.buffers.get(&buffer_id).map(|s| s.buffer.len());
+        if let Some(view_state) = self
+            .splits
+            .as_mut()
+            .and_then(|(_, vs)| vs.get_mut(&target_split))
+        {
+            let rows = view_state.viewport.height as usize;
+            if let (Some(hex), Some(len)) = (view_state.hex_view.as_mut(), buffer_len) {
+                hex.scroll(delta as isize, len, rows);
+                return Ok(());
+            }
+        }
+
         // Get view_transform tokens from SplitViewState (if any)
         let view_transform_tokens = self
             .splits
@@ -563,6 +577,70 @@ impl crate::app::window::Window {
         Ok(())
     }
 
+    /// Handle a click on a split's minimap: scroll so the lines of the
+    /// clicked minimap row sit in the middle of the split.
+    pub(super) fn handle_minimap_jump(
+        &mut self,
+        row: u16,
+        split_id: LeafId,
+        buffer_id: BufferId,
+        minimap_rect: ratatui::layout::Rect,
+        row_bytes: &[usize],
+    ) -> AnyhowResult<()> {
+        let index = row.saturating_sub(minimap_rect.y) as usize;
+        // Below the last row (short buffers): jump to the end
+        let Some(&target_byte) = row_bytes.get(index).or(row_bytes.last()) else {
+            return Ok(());
+        };
+
+        let viewport_height = self
+            .splits
+            .as_ref()
+            .map(|(_, vs)| vs)
+            .expect("active window must have a populated split layout")
+            .get(&split_id)
+            .map(|vs| vs.viewport.height as usize)
+            .unwrap_or(10);
+        let large_file_threshold = self.config().editor.large_file_threshold_bytes as usize;
+
+        let top_byte = if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            let line_start = {
+                let mut iter = state.buffer.line_iterator(target_byte, 80);
+                for _ in 0..viewport_height / 2 {
+                    if iter.prev().is_none() {
+                        break;
+                    }
+                }
+                iter.current_position()
+            };
+            if state.buffer.len() <= large_file_threshold {
+                line_start.min(Self::calculate_max_scroll_position(
+                    &mut state.buffer,
+                    viewport_height,
+                ))
+            } else {
+                line_start
+            }
+        } else {
+            return Ok(());
+        };
+
+        if let Some(view_state) = self
+            .split_view_states_mut()
+            .expect("active window must have a populated split layout")
+            .get_mut(&split_id)
+        {
+            view_state.viewport.top_byte = top_byte;
+            view_state.viewport.top_view_line_offset = 0;
+            // Skip ensure_visible so the scroll position isn't undone during render
+            view_state.viewport.set_skip_ensure_visible();
+        }
+
+        self.move_cursor_to_visible_area(split_id, buffer_id);
+
+        Ok(())
+    }
+
     /// Handle scrollbar jump (click on track) for composite buffers.
     /// Maps the click ratio to a row-based scroll position.
     fn handle_composite_scrollbar_jump(
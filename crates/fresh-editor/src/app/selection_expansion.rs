@@ -0,0 +1,192 @@
+//! Self-contained history for expand/shrink selection.
+//!
+//! Each expand step records the cursors it started from so that shrink can
+//! return to exactly the previous selections instead of re-deriving them
+//! from the syntax tree. The history is only valid while the cursors still
+//! look the way the last step left them: any other cursor movement, edit,
+//! or buffer switch makes the next expand start a fresh stack.
+//!
+//! Computing the new ranges (tree-sitter, LSP `selectionRange`, word
+//! fallback) and applying them lives on `Editor` in
+//! `selection_expansion_actions.rs`.
+
+use crate::model::cursor::Cursors;
+use crate::model::event::{BufferId, CursorId};
+
+/// The selection of one cursor: `(id, anchor, position)`.
+pub(crate) type CursorSelection = (CursorId, Option<usize>, usize);
+
+/// Selections of every cursor in a buffer, in cursor-id order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SelectionSnapshot(pub Vec<CursorSelection>);
+
+impl SelectionSnapshot {
+    /// Capture the current selections of `cursors`.
+    pub(crate) fn capture(cursors: &Cursors) -> Self {
+        let mut selections: Vec<CursorSelection> = cursors
+            .iter()
+            .map(|(id, cursor)| (id, cursor.anchor, cursor.position))
+            .collect();
+        selections.sort_by_key(|(id, _, _)| id.0);
+        Self(selections)
+    }
+}
+
+/// An expand request sent to the language server, waiting for its answer.
+#[derive(Debug, Clone)]
+pub(crate) struct PendingSelectionRange {
+    pub request_id: u64,
+    pub buffer_id: BufferId,
+    /// Selections at request time; the response is dropped if they changed.
+    pub before: SelectionSnapshot,
+}
+
+#[derive(Debug)]
+struct ExpansionStack {
+    buffer_id: BufferId,
+    /// Selections before each expand step, oldest first.
+    history: Vec<SelectionSnapshot>,
+    /// Selections the last step left behind.
+    current: SelectionSnapshot,
+}
+
+/// Owner of the expand/shrink history for one window.
+#[derive(Debug, Default)]
+pub(crate) struct SelectionExpansionState {
+    stack: Option<ExpansionStack>,
+}
+
+impl SelectionExpansionState {
+    /// Record an expand step from `before` to `after` in `buffer_id`.
+    ///
+    /// Continues the existing stack when `before` is what the previous step
+    /// left behind; otherwise starts a new one.
+    pub(crate) fn push(
+        &mut self,
+        buffer_id: BufferId,
+        before: SelectionSnapshot,
+        after: SelectionSnapshot,
+    ) {
+        match &mut self.stack {
+            Some(stack) if stack.buffer_id == buffer_id && stack.current == before => {
+                stack.history.push(before);
+                stack.current = after;
+            }
+            _ => {
+                self.stack = Some(ExpansionStack {
+                    buffer_id,
+                    history: vec![before],
+                    current: after,
+                });
+            }
+        }
+    }
+
+    /// Pop the selections that preceded `current` in `buffer_id`.
+    ///
+    /// Returns `None` (and drops the stale stack) when `current` is not what
+    /// the last expand step produced.
+    pub(crate) fn pop(
+        &mut self,
+        buffer_id: BufferId,
+        current: &SelectionSnapshot,
+    ) -> Option<SelectionSnapshot> {
+        let stack = self.stack.as_mut()?;
+        if stack.buffer_id != buffer_id || &stack.current != current {
+            self.stack = None;
+            return None;
+        }
+        let previous = stack.history.pop()?;
+        if stack.history.is_empty() {
+            self.stack = None;
+        } else {
+            stack.current = previous.clone();
+        }
+        Some(previous)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snap(ranges: &[(usize, usize)]) -> SelectionSnapshot {
+        SelectionSnapshot(
+            ranges
+                .iter()
+                .enumerate()
+                .map(|(i, &(anchor, pos))| (CursorId(i), Some(anchor), pos))
+                .collect(),
+        )
+    }
+
+    /// Number of steps that can be undone by shrinking.
+    fn depth(s: &SelectionExpansionState) -> usize {
+        s.stack.as_ref().map_or(0, |stack| stack.history.len())
+    }
+
+    #[test]
+    fn default_is_empty() {
+        let mut s = SelectionExpansionState::default();
+        assert_eq!(depth(&s), 0);
+        assert_eq!(s.pop(BufferId(1), &snap(&[(0, 1)])), None);
+    }
+
+    #[test]
+    fn pop_returns_steps_in_reverse_order() {
+        let mut s = SelectionExpansionState::default();
+        let buf = BufferId(1);
+        s.push(buf, snap(&[(5, 5)]), snap(&[(4, 7)]));
+        s.push(buf, snap(&[(4, 7)]), snap(&[(2, 9)]));
+        s.push(buf, snap(&[(2, 9)]), snap(&[(0, 12)]));
+        assert_eq!(depth(&s), 3);
+
+        assert_eq!(s.pop(buf, &snap(&[(0, 12)])), Some(snap(&[(2, 9)])));
+        assert_eq!(s.pop(buf, &snap(&[(2, 9)])), Some(snap(&[(4, 7)])));
+        assert_eq!(s.pop(buf, &snap(&[(4, 7)])), Some(snap(&[(5, 5)])));
+        assert_eq!(depth(&s), 0);
+        assert_eq!(s.pop(buf, &snap(&[(5, 5)])), None);
+    }
+
+    #[test]
+    fn shrink_then_expand_continues_stack() {
+        let mut s = SelectionExpansionState::default();
+        let buf = BufferId(1);
+        s.push(buf, snap(&[(5, 5)]), snap(&[(4, 7)]));
+        s.push(buf, snap(&[(4, 7)]), snap(&[(2, 9)]));
+        assert_eq!(s.pop(buf, &snap(&[(2, 9)])), Some(snap(&[(4, 7)])));
+        s.push(buf, snap(&[(4, 7)]), snap(&[(2, 9)]));
+        assert_eq!(depth(&s), 2);
+    }
+
+    #[test]
+    fn moved_cursor_invalidates_stack() {
+        let mut s = SelectionExpansionState::default();
+        let buf = BufferId(1);
+        s.push(buf, snap(&[(5, 5)]), snap(&[(4, 7)]));
+        assert_eq!(s.pop(buf, &snap(&[(3, 3)])), None);
+        assert_eq!(depth(&s), 0);
+
+        s.push(buf, snap(&[(5, 5)]), snap(&[(4, 7)]));
+        s.push(buf, snap(&[(1, 1)]), snap(&[(0, 3)]));
+        assert_eq!(depth(&s), 1, "unrelated start resets the stack");
+    }
+
+    #[test]
+    fn other_buffer_invalidates_stack() {
+        let mut s = SelectionExpansionState::default();
+        s.push(BufferId(1), snap(&[(5, 5)]), snap(&[(4, 7)]));
+        assert_eq!(s.pop(BufferId(2), &snap(&[(4, 7)])), None);
+        assert_eq!(depth(&s), 0);
+    }
+
+    #[test]
+    fn multi_cursor_snapshots_restore_together() {
+        let mut s = SelectionExpansionState::default();
+        let buf = BufferId(1);
+        let before = snap(&[(5, 5), (20, 20)]);
+        let after = snap(&[(4, 7), (18, 24)]);
+        s.push(buf, before.clone(), after.clone());
+        assert_eq!(s.pop(buf, &after), Some(before));
+    }
+}
@@ -0,0 +1,285 @@
+//! Expand/shrink selection orchestrators.
+//!
+//! New ranges come from, in order of preference: the buffer's tree-sitter
+//! grammar (`primitives::syntax_selection`), the language server's
+//! `textDocument/selectionRange`, and finally the word-based
+//! `Action::ExpandSelection` event conversion for plain text. Every expand
+//! step is recorded in `Window::selection_expansion` so that
+//! `shrink_selection` retraces the steps exactly; without history, shrink
+//! falls back to the child syntax node around each cursor.
+
+use std::ops::Range;
+
+use anyhow::Result as AnyhowResult;
+
+use crate::app::selection_expansion::{PendingSelectionRange, SelectionSnapshot};
+use crate::input::keybindings::Action;
+use crate::model::buffer::Buffer;
+use crate::model::event::{BufferId, Event};
+use crate::primitives::highlighter::Language;
+use crate::primitives::syntax_selection::{next_enclosing_range, SyntaxWindow};
+use crate::types::LspFeature;
+
+use super::Editor;
+
+/// The byte range selected by an `(anchor, position)` pair.
+fn selected_range(anchor: Option<usize>, position: usize) -> Range<usize> {
+    match anchor {
+        Some(anchor) => anchor.min(position)..anchor.max(position),
+        None => position..position,
+    }
+}
+
+fn lsp_range_to_bytes(buffer: &Buffer, range: &lsp_types::Range) -> Range<usize> {
+    let start =
+        buffer.lsp_position_to_byte(range.start.line as usize, range.start.character as usize);
+    let end = buffer.lsp_position_to_byte(range.end.line as usize, range.end.character as usize);
+    start..end
+}
+
+impl Editor {
+    /// Grow every cursor's selection to the enclosing syntax node.
+    pub(crate) fn expand_selection(&mut self) -> AnyhowResult<()> {
+        let buffer_id = self.active_buffer();
+        let before = SelectionSnapshot::capture(self.active_cursors());
+
+        if let Some(after) =
+            self.syntax_selections(&before, |window, range, _| window.expand(range))
+        {
+            // A grammar exists; if nothing grew every cursor is already at
+            // the root node.
+            if after != before {
+                self.apply_selection_snapshot(&before, &after, "ExpandSelection");
+                self.active_window_mut()
+                    .selection_expansion
+                    .push(buffer_id, before, after);
+            }
+            return Ok(());
+        }
+
+        if self.request_selection_range(buffer_id, &before) {
+            return Ok(());
+        }
+
+        self.apply_action_as_events(Action::ExpandSelection)?;
+        let after = SelectionSnapshot::capture(self.active_cursors());
+        if after != before {
+            self.active_window_mut()
+                .selection_expansion
+                .push(buffer_id, before, after);
+        }
+        Ok(())
+    }
+
+    /// Return every cursor to the selection it had before the last expand,
+    /// or to the child syntax node around it when there is no history.
+    pub(crate) fn shrink_selection(&mut self) -> AnyhowResult<()> {
+        let buffer_id = self.active_buffer();
+        let current = SelectionSnapshot::capture(self.active_cursors());
+
+        let previous = self
+            .active_window_mut()
+            .selection_expansion
+            .pop(buffer_id, &current);
+        if let Some(previous) = previous {
+            self.apply_selection_snapshot(&current, &previous, "ShrinkSelection");
+            return Ok(());
+        }
+
+        if let Some(after) = self.syntax_selections(&current, |window, range, position| {
+            window.shrink(range, position)
+        }) {
+            self.apply_selection_snapshot(&current, &after, "ShrinkSelection");
+        }
+        Ok(())
+    }
+
+    /// Map each cursor's selection through `step` using the active buffer's
+    /// tree-sitter grammar. Cursors `step` cannot move keep their selection.
+    ///
+    /// Returns `None` when the buffer has no grammar to parse with.
+    fn syntax_selections(
+        &mut self,
+        from: &SelectionSnapshot,
+        step: impl Fn(&SyntaxWindow, Range<usize>, usize) -> Option<Range<usize>>,
+    ) -> Option<SelectionSnapshot> {
+        let state = self.active_state_mut();
+        let language: Language = *state.highlighter.language()?;
+
+        // One parse serves every cursor it covers (always the case below
+        // the full-parse threshold) and every later step until the buffer
+        // changes.
+        let cache = &mut state.syntax_selection;
+        let mut selections = Vec::with_capacity(from.0.len());
+        for &(id, anchor, position) in &from.0 {
+            let current = selected_range(anchor, position);
+            let window = cache.get_or_parse(&state.buffer, &language, current.clone())?;
+            let next = step(window, current, position);
+            selections.push(match next {
+                Some(range) => (id, Some(range.start), range.end),
+                None => (id, anchor, position),
+            });
+        }
+        Some(SelectionSnapshot(selections))
+    }
+
+    /// Ask the language server for selection ranges around every cursor.
+    /// Returns `false` when no server for this buffer supports them.
+    fn request_selection_range(&mut self, buffer_id: BufferId, before: &SelectionSnapshot) -> bool {
+        // The start of the selection is always inside it, unlike the cursor,
+        // which sits one past the end after an expand.
+        let positions: Vec<(u32, u32)> = {
+            let buffer = &self.active_state().buffer;
+            before
+                .0
+                .iter()
+                .map(|&(_, anchor, position)| {
+                    let start = selected_range(anchor, position).start;
+                    let (line, character) = buffer.position_to_lsp_position(start);
+                    (line as u32, character as u32)
+                })
+                .collect()
+        };
+        let request_id = self.active_window_mut().next_lsp_request_id;
+
+        let sent = self
+            .with_lsp_for_buffer(
+                buffer_id,
+                LspFeature::SelectionRange,
+                |handle, uri, _language| {
+                    let result =
+                        handle.selection_range(request_id, uri.as_uri().clone(), positions);
+                    if result.is_ok() {
+                        tracing::info!("Requested selection ranges for {}", uri.as_str());
+                    }
+                    result.is_ok()
+                },
+            )
+            .unwrap_or(false);
+
+        if sent {
+            let window = self.active_window_mut();
+            window.next_lsp_request_id += 1;
+            window.pending_selection_range_request = Some(PendingSelectionRange {
+                request_id,
+                buffer_id,
+                before: before.clone(),
+            });
+        }
+        sent
+    }
+
+    /// Handle LSP selection range response
+    pub(crate) fn handle_selection_range_response(
+        &mut self,
+        request_id: u64,
+        ranges: Vec<Vec<lsp_types::Range>>,
+    ) {
+        let is_pending = self
+            .active_window()
+            .pending_selection_range_request
+            .as_ref()
+            .is_some_and(|p| p.request_id == request_id);
+        if !is_pending {
+            tracing::debug!(
+                "Ignoring selection range response for outdated request {}",
+                request_id
+            );
+            return;
+        }
+        let Some(pending) = self
+            .active_window_mut()
+            .pending_selection_range_request
+            .take()
+        else {
+            return;
+        };
+
+        let current = SelectionSnapshot::capture(self.active_cursors());
+        if self.active_buffer() != pending.buffer_id || current != pending.before {
+            tracing::debug!("Cursors moved since selection range request {}", request_id);
+            return;
+        }
+
+        let after = {
+            let buffer = &self.active_state().buffer;
+            SelectionSnapshot(
+                pending
+                    .before
+                    .0
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &(id, anchor, position))| {
+                        let candidates = ranges
+                            .get(i)
+                            .into_iter()
+                            .flatten()
+                            .map(|range| lsp_range_to_bytes(buffer, range));
+                        match next_enclosing_range(candidates, &selected_range(anchor, position)) {
+                            Some(range) => (id, Some(range.start), range.end),
+                            None => (id, anchor, position),
+                        }
+                    })
+                    .collect(),
+            )
+        };
+
+        if after != current {
+            self.apply_selection_snapshot(&current, &after, "ExpandSelection");
+            self.active_window_mut()
+                .selection_expansion
+                .push(pending.buffer_id, current, after);
+        }
+    }
+
+    /// Move the active buffer's cursors from `from` to `to` as one undoable
+    /// step.
+    fn apply_selection_snapshot(
+        &mut self,
+        from: &SelectionSnapshot,
+        to: &SelectionSnapshot,
+        description: &str,
+    ) {
+        let events: Vec<Event> = {
+            let cursors = self.active_cursors();
+            from.0
+                .iter()
+                .zip(&to.0)
+                .filter(|(old, new)| old != new)
+                .filter_map(
+                    |(&(cursor_id, old_anchor, old_position), &(_, new_anchor, new_position))| {
+                        let cursor = cursors.get(cursor_id)?;
+                        Some(Event::MoveCursor {
+                            cursor_id,
+                            old_position,
+                            new_position,
+                            old_anchor,
+                            new_anchor,
+                            old_sticky_column: cursor.sticky_column,
+                            new_sticky_column: 0,
+                        })
+                    },
+                )
+                .collect()
+        };
+
+        match events.len() {
+            0 => {}
+            1 => {
+                self.log_and_apply_event(&events[0]);
+                self.track_cursor_movement(&events[0]);
+            }
+            _ => {
+                let batch = Event::Batch {
+                    events: events.clone(),
+                    description: description.to_string(),
+                };
+                self.active_event_log_mut().append(batch.clone());
+                self.apply_event_to_active_buffer(&batch);
+                for event in &events {
+                    self.track_cursor_movement(event);
+                }
+            }
+        }
+    }
+}
@@ -132,8 +132,9 @@ impl Editor {
             }
         }
 
-        // Handle plugin enable/disable changes
+        // Handle plugin enable/disable changes and revoked permissions
         self.apply_plugin_config_changes(&old_plugins);
+        self.sync_plugin_grants();
 
         // Update keybindings
         *self.keybindings.write().unwrap() = KeybindingResolver::new(&self.config);
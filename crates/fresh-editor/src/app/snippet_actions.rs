@@ -0,0 +1,469 @@
+//! Snippet insertion and snippet session navigation.
+//!
+//! Inserting a snippet resolves its variables, indents it to the current
+//! line and, when it has tabstops, starts a [`SnippetSession`]: Tab and
+//! Shift+Tab move between tabstops, each visit selects the placeholder
+//! text of every occurrence with one cursor per occurrence (so mirrors are
+//! edited together), and a choice tabstop opens a popup of its options.
+//! The session ends at the final tabstop, on Escape, or as soon as the
+//! cursor leaves the snippet.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use anyhow::Result as AnyhowResult;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use rust_i18n::t;
+
+use super::snippet_session::SnippetSession;
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::model::event::{CursorId, Event};
+use crate::primitives::snippet::parse_snippet;
+use crate::primitives::word_navigation::{find_word_end, find_word_start};
+use crate::view::prompt::{Prompt, PromptType};
+
+impl Editor {
+    /// Replace `replace` with `snippet` and, if it has tabstops, start a
+    /// snippet session on them.
+    pub(crate) fn insert_snippet(&mut self, snippet: &str, replace: Range<usize>) {
+        self.end_snippet_session();
+
+        let variables = self.snippet_variables(snippet);
+        let mut parsed = parse_snippet(snippet, &|name| variables.get(name).cloned());
+        let indent = variables
+            .get("TM_CURRENT_LINE")
+            .map(|line| {
+                let trimmed = line.trim_start_matches([' ', '\t']);
+                line[..line.len() - trimmed.len()].to_string()
+            })
+            .unwrap_or_default();
+        parsed.indent_lines(&indent);
+
+        let cursor_id = self.active_cursors().primary_id();
+        let mut events = Vec::with_capacity(2);
+        if !replace.is_empty() {
+            let deleted_text = self
+                .active_state_mut()
+                .get_text_range(replace.start, replace.end);
+            events.push(Event::Delete {
+                range: replace.clone(),
+                deleted_text,
+                cursor_id,
+            });
+        }
+        if !parsed.text.is_empty() {
+            events.push(Event::Insert {
+                position: replace.start,
+                text: parsed.text.clone(),
+                cursor_id,
+            });
+        }
+        if !events.is_empty() {
+            self.log_and_apply_event(&Event::Batch {
+                events,
+                description: t!("snippet.insert_description").to_string(),
+            });
+        }
+
+        // A snippet without tabstops ends its session right away, at `$0`
+        let buffer_id = self.active_buffer();
+        let session = SnippetSession::new(
+            &mut self.active_state_mut().marker_list,
+            buffer_id,
+            replace.start,
+            &parsed,
+        );
+        self.active_window_mut().snippet_session = Some(session);
+        self.select_snippet_tabstop(0);
+    }
+
+    /// Open a prompt listing the library snippets for the buffer's language.
+    pub(crate) fn start_insert_snippet_prompt(&mut self) {
+        let language = self.active_state().language.clone();
+        let suggestions: Vec<Suggestion> = self
+            .snippet_library
+            .for_language(&language)
+            .enumerate()
+            .map(|(index, snippet)| {
+                let prefixes = snippet.prefixes.join(", ");
+                let description = match &snippet.description {
+                    Some(description) if !prefixes.is_empty() => {
+                        format!("{} — {}", prefixes, description)
+                    }
+                    Some(description) => description.clone(),
+                    None => prefixes,
+                };
+                Suggestion {
+                    text: snippet.name.clone(),
+                    description: Some(description),
+                    value: Some(index.to_string()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+        if suggestions.is_empty() {
+            self.set_status_message(
+                t!("snippet.none_for_language", language = language).to_string(),
+            );
+            return;
+        }
+
+        self.active_window_mut().prompt = Some(Prompt::with_suggestions(
+            t!("snippet.prompt").to_string(),
+            PromptType::InsertSnippet,
+            suggestions,
+        ));
+        if let Some(prompt) = self.active_window_mut().prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Insert the library snippet chosen in the Insert Snippet prompt,
+    /// replacing the selection.
+    pub(super) fn insert_library_snippet(&mut self, value: &str) {
+        let language = self.active_state().language.clone();
+        let Some(body) = value.parse::<usize>().ok().and_then(|index| {
+            self.snippet_library
+                .for_language(&language)
+                .nth(index)
+                .map(|snippet| snippet.body.clone())
+        }) else {
+            return;
+        };
+        let cursor = self.active_cursors().primary();
+        let replace = cursor
+            .selection_range()
+            .unwrap_or(cursor.position..cursor.position);
+        self.insert_snippet(&body, replace);
+    }
+
+    /// Move to the next tabstop; reaching the final one ends the session.
+    pub(crate) fn snippet_next_tabstop(&mut self) {
+        let Some(next) = self
+            .active_window()
+            .snippet_session
+            .as_ref()
+            .map(|s| s.current() + 1)
+        else {
+            return;
+        };
+        self.select_snippet_tabstop(next);
+    }
+
+    /// Move back to the previous tabstop.
+    pub(crate) fn snippet_prev_tabstop(&mut self) {
+        let Some(current) = self
+            .active_window()
+            .snippet_session
+            .as_ref()
+            .map(|s| s.current())
+        else {
+            return;
+        };
+        if current > 0 {
+            self.select_snippet_tabstop(current - 1);
+        }
+    }
+
+    /// Stop tracking the snippet session, if any.
+    pub(crate) fn end_snippet_session(&mut self) {
+        let Some(session) = self.active_window_mut().snippet_session.take() else {
+            return;
+        };
+        if let Some(state) = self.buffers_mut().get_mut(&session.buffer_id) {
+            session.release(&mut state.marker_list);
+        }
+    }
+
+    /// End the session once the cursor has left the snippet or another
+    /// buffer became active.
+    pub(crate) fn check_snippet_session(&mut self) {
+        let Some(session) = self.active_window().snippet_session.as_ref() else {
+            return;
+        };
+        let inside = session.buffer_id == self.active_buffer() && {
+            let cursor = self.active_cursors().primary().position;
+            session
+                .bounds(&self.active_state().marker_list)
+                .is_some_and(|bounds| bounds.start <= cursor && cursor <= bounds.end)
+        };
+        if !inside {
+            self.end_snippet_session();
+        }
+    }
+
+    /// Handle Tab / Shift+Tab / Escape while a snippet session is active.
+    /// Escape ends the session and still runs its normal binding.
+    pub(super) fn try_route_snippet_key(
+        &mut self,
+        key_event: &KeyEvent,
+    ) -> Option<AnyhowResult<()>> {
+        self.check_snippet_session();
+        self.active_window().snippet_session.as_ref()?;
+
+        match key_event.code {
+            KeyCode::Tab if key_event.modifiers == KeyModifiers::NONE => {
+                self.snippet_next_tabstop();
+                Some(Ok(()))
+            }
+            KeyCode::BackTab => {
+                self.snippet_prev_tabstop();
+                Some(Ok(()))
+            }
+            KeyCode::Esc => {
+                self.end_snippet_session();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Replace every occurrence of the active tabstop with `choice`.
+    pub(crate) fn apply_snippet_choice(&mut self, choice: &str) {
+        let Some(session) = self.active_window().snippet_session.as_ref() else {
+            return;
+        };
+        let current = session.current();
+        let mut ranges = session.ranges(current, &self.active_state().marker_list);
+        ranges.sort_by_key(|r| std::cmp::Reverse(r.start));
+
+        let mut events = Vec::with_capacity(ranges.len() * 2);
+        for range in ranges {
+            if !range.is_empty() {
+                let deleted_text = self
+                    .active_state_mut()
+                    .get_text_range(range.start, range.end);
+                events.push(Event::Delete {
+                    range: range.clone(),
+                    deleted_text,
+                    cursor_id: CursorId::UNDO_SENTINEL,
+                });
+            }
+            events.push(Event::Insert {
+                position: range.start,
+                text: choice.to_string(),
+                cursor_id: CursorId::UNDO_SENTINEL,
+            });
+        }
+        self.log_and_apply_event(&Event::Batch {
+            events,
+            description: t!("snippet.choice_description").to_string(),
+        });
+        self.select_snippet_tabstop(current);
+    }
+
+    /// Make tabstop `index` active: select its occurrences, offer its
+    /// choices, or place the cursor and end the session at the final one.
+    fn select_snippet_tabstop(&mut self, index: usize) {
+        let buffer_id = self.active_buffer();
+        let Some(mut session) = self.active_window_mut().snippet_session.take() else {
+            return;
+        };
+        if session.buffer_id != buffer_id || index >= session.tabstop_count() {
+            self.active_window_mut().snippet_session = Some(session);
+            self.end_snippet_session();
+            return;
+        }
+
+        let marker_list = &mut self.active_state_mut().marker_list;
+        session.activate(index, marker_list);
+        let ranges = session.ranges(index, marker_list);
+        let choices = session.choices(index).to_vec();
+        let is_final = session.is_final(index);
+        self.active_window_mut().snippet_session = Some(session);
+
+        if ranges.is_empty() {
+            self.end_snippet_session();
+            return;
+        }
+        self.set_snippet_selections(&ranges);
+        if is_final {
+            self.end_snippet_session();
+        } else if choices.len() > 1 {
+            self.show_snippet_choices(choices);
+        }
+    }
+
+    /// Put the primary cursor on the first range and one more cursor on
+    /// each other range, selecting their text.
+    fn set_snippet_selections(&mut self, ranges: &[Range<usize>]) {
+        let cursors = self.active_cursors();
+        let primary_id = cursors.primary_id();
+        let primary = cursors.primary();
+        let mut next_id = cursors.iter().map(|(id, _)| id.0).max().unwrap_or(0) + 1;
+        let anchor = |range: &Range<usize>| (!range.is_empty()).then_some(range.start);
+
+        let mut events: Vec<Event> = cursors
+            .iter()
+            .filter(|(id, _)| *id != primary_id)
+            .map(|(cursor_id, cursor)| Event::RemoveCursor {
+                cursor_id,
+                position: cursor.position,
+                anchor: cursor.anchor,
+            })
+            .collect();
+        let Some((first, rest)) = ranges.split_first() else {
+            return;
+        };
+        events.push(Event::MoveCursor {
+            cursor_id: primary_id,
+            old_position: primary.position,
+            new_position: first.end,
+            old_anchor: primary.anchor,
+            new_anchor: anchor(first),
+            old_sticky_column: primary.sticky_column,
+            new_sticky_column: 0,
+        });
+        for range in rest {
+            events.push(Event::AddCursor {
+                cursor_id: CursorId(next_id),
+                position: range.end,
+                anchor: anchor(range),
+            });
+            next_id += 1;
+        }
+
+        // Cursor placement is not an undo step of its own
+        self.apply_event_to_active_buffer(&Event::Batch {
+            events,
+            description: t!("snippet.insert_description").to_string(),
+        });
+        let split_id = self.effective_active_split();
+        let buffer_id = self.active_buffer();
+        self.active_window_mut()
+            .ensure_cursor_visible_for_split(buffer_id, split_id);
+    }
+
+    fn show_snippet_choices(&mut self, choices: Vec<String>) {
+        use crate::view::popup::{Popup, PopupKind, PopupListItem, PopupPosition, PopupResolver};
+
+        let items = choices
+            .into_iter()
+            .map(|choice| PopupListItem {
+                text: choice.clone(),
+                detail: None,
+                icon: None,
+                data: Some(choice),
+                disabled: false,
+            })
+            .collect();
+        let theme = self.theme.read().unwrap();
+        let mut popup = Popup::list(items, &theme);
+        popup.kind = PopupKind::Action;
+        popup.title = Some(t!("snippet.choice_title").to_string());
+        popup.position = PopupPosition::BelowCursor;
+        popup.width = 40;
+        popup.max_height = 10;
+        popup.border_style = ratatui::style::Style::default().fg(theme.popup_border_fg);
+        popup.background_style = ratatui::style::Style::default().bg(theme.popup_bg);
+        // Selected row's `data` is the choice text
+        popup.resolver = PopupResolver::SnippetChoice;
+        popup.focused = true;
+        drop(theme);
+
+        self.active_state_mut().popups.show_or_replace(popup);
+    }
+
+    /// Values of the snippet variables, from the active buffer and cursor.
+    fn snippet_variables(&mut self, snippet: &str) -> HashMap<&'static str, String> {
+        let mut vars = HashMap::new();
+
+        let cursor = self.active_cursors().primary().clone();
+        let state = self.active_state_mut();
+        let (line, _) = state.buffer.position_to_line_col(cursor.position);
+        let line_text = state
+            .buffer
+            .get_line(line)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default();
+        vars.insert(
+            "TM_CURRENT_LINE",
+            line_text.trim_end_matches(['\n', '\r']).to_string(),
+        );
+        let word = find_word_start(&state.buffer, cursor.position)
+            ..find_word_end(&state.buffer, cursor.position);
+        vars.insert(
+            "TM_CURRENT_WORD",
+            state.get_text_range(word.start, word.end),
+        );
+        let selected = cursor
+            .selection_range()
+            .map(|r| state.get_text_range(r.start, r.end))
+            .unwrap_or_default();
+        vars.insert("TM_SELECTED_TEXT", selected);
+        vars.insert("TM_LINE_INDEX", line.to_string());
+        vars.insert("TM_LINE_NUMBER", (line + 1).to_string());
+
+        let path = state.buffer.file_path().map(|p| p.to_path_buf());
+        let file_name = |p: &std::path::Path| {
+            p.file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        };
+        vars.insert(
+            "TM_FILENAME",
+            path.as_deref().map(file_name).unwrap_or_default(),
+        );
+        vars.insert(
+            "TM_FILENAME_BASE",
+            path.as_deref()
+                .and_then(|p| p.file_stem())
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        );
+        vars.insert(
+            "TM_DIRECTORY",
+            path.as_deref()
+                .and_then(|p| p.parent())
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+        );
+        vars.insert(
+            "TM_FILEPATH",
+            path.as_deref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+        );
+        vars.insert(
+            "RELATIVE_FILEPATH",
+            path.as_deref()
+                .map(|p| {
+                    p.strip_prefix(&self.working_dir)
+                        .unwrap_or(p)
+                        .display()
+                        .to_string()
+                })
+                .unwrap_or_default(),
+        );
+        vars.insert("WORKSPACE_NAME", file_name(&self.working_dir));
+        vars.insert("WORKSPACE_FOLDER", self.working_dir.display().to_string());
+
+        // Reading the system clipboard can be slow; only do it when asked
+        if snippet.contains("CLIPBOARD") {
+            vars.insert("CLIPBOARD", self.clipboard.paste().unwrap_or_default());
+        }
+
+        let now = chrono::Local::now();
+        for (name, format) in [
+            ("CURRENT_YEAR", "%Y"),
+            ("CURRENT_YEAR_SHORT", "%y"),
+            ("CURRENT_MONTH", "%m"),
+            ("CURRENT_MONTH_NAME", "%B"),
+            ("CURRENT_MONTH_NAME_SHORT", "%b"),
+            ("CURRENT_DATE", "%d"),
+            ("CURRENT_DAY_NAME", "%A"),
+            ("CURRENT_DAY_NAME_SHORT", "%a"),
+            ("CURRENT_HOUR", "%H"),
+            ("CURRENT_MINUTE", "%M"),
+            ("CURRENT_SECOND", "%S"),
+            ("CURRENT_SECONDS_UNIX", "%s"),
+        ] {
+            vars.insert(name, now.format(format).to_string());
+        }
+
+        vars
+    }
+}
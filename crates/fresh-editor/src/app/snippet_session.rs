@@ -0,0 +1,152 @@
+//! State of a live snippet session.
+//!
+//! After a snippet with tabstops is inserted, its tabstops are visited with
+//! Tab / Shift+Tab. Every tabstop occurrence is a pair of markers in the
+//! buffer's marker list, so edits inside and around the snippet keep the
+//! ranges anchored. The start markers of the active tabstop are pinned
+//! (`MarkerList::set_sticky_left`) so text typed into an empty or selected
+//! placeholder lands inside it rather than before it.
+//!
+//! Navigation, choices and ending the session live on `Editor` in
+//! `snippet_actions.rs`.
+
+use std::ops::Range;
+
+use crate::model::event::BufferId;
+use crate::model::marker::{MarkerId, MarkerList};
+use crate::primitives::snippet::ParsedSnippet;
+
+#[derive(Debug)]
+struct SessionTabstop {
+    /// Start and end marker of each occurrence (several when mirrored)
+    ranges: Vec<(MarkerId, MarkerId)>,
+    choices: Vec<String>,
+}
+
+/// An inserted snippet whose tabstops are being visited.
+#[derive(Debug)]
+pub(crate) struct SnippetSession {
+    pub buffer_id: BufferId,
+    /// Bounds of the whole snippet; the session ends when the cursor
+    /// leaves them
+    start: MarkerId,
+    end: MarkerId,
+    /// In navigation order; the last one is the final `$0` position
+    tabstops: Vec<SessionTabstop>,
+    /// Index of the active tabstop
+    current: usize,
+}
+
+impl SnippetSession {
+    /// Track `snippet`, inserted at `offset` of `buffer_id`.
+    pub fn new(
+        marker_list: &mut MarkerList,
+        buffer_id: BufferId,
+        offset: usize,
+        snippet: &ParsedSnippet,
+    ) -> Self {
+        let start = marker_list.create(offset, true);
+        marker_list.set_sticky_left(start, true);
+        let end = marker_list.create(offset + snippet.text.len(), false);
+        let tabstops = snippet
+            .tabstops
+            .iter()
+            .map(|tabstop| SessionTabstop {
+                ranges: tabstop
+                    .ranges
+                    .iter()
+                    .map(|r| {
+                        (
+                            marker_list.create(offset + r.start, true),
+                            marker_list.create(offset + r.end, false),
+                        )
+                    })
+                    .collect(),
+                choices: tabstop.choices.clone(),
+            })
+            .collect();
+        Self {
+            buffer_id,
+            start,
+            end,
+            tabstops,
+            current: 0,
+        }
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn tabstop_count(&self) -> usize {
+        self.tabstops.len()
+    }
+
+    /// Whether `index` is the final (`$0`) tabstop
+    pub fn is_final(&self, index: usize) -> bool {
+        index + 1 >= self.tabstops.len()
+    }
+
+    /// Current byte range of the whole snippet
+    pub fn bounds(&self, marker_list: &MarkerList) -> Option<Range<usize>> {
+        Some(marker_list.get_position(self.start)?..marker_list.get_position(self.end)?)
+    }
+
+    /// Current byte ranges of the occurrences of tabstop `index`
+    pub fn ranges(&self, index: usize, marker_list: &MarkerList) -> Vec<Range<usize>> {
+        self.tabstops
+            .get(index)
+            .map(|tabstop| {
+                tabstop
+                    .ranges
+                    .iter()
+                    .filter_map(|&(start, end)| {
+                        let start = marker_list.get_position(start)?;
+                        let end = marker_list.get_position(end)?;
+                        Some(start..end.max(start))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn choices(&self, index: usize) -> &[String] {
+        self.tabstops
+            .get(index)
+            .map_or(&[], |tabstop| tabstop.choices.as_slice())
+    }
+
+    /// Make `index` the active tabstop: pin its start markers, and the end
+    /// markers of other tabstops that touch them, so typing at its start
+    /// grows it rather than its neighbours.
+    pub fn activate(&mut self, index: usize, marker_list: &mut MarkerList) {
+        self.current = index;
+        let active_starts: Vec<usize> = self
+            .ranges(index, marker_list)
+            .iter()
+            .map(|r| r.start)
+            .collect();
+        for (i, tabstop) in self.tabstops.iter().enumerate() {
+            for &(start, end) in &tabstop.ranges {
+                marker_list.set_sticky_left(start, i == index);
+                let touches_active = i != index
+                    && marker_list
+                        .get_position(end)
+                        .is_some_and(|pos| active_starts.contains(&pos));
+                marker_list.set_sticky_left(end, touches_active);
+            }
+        }
+    }
+
+    /// Delete the session's markers.
+    pub fn release(self, marker_list: &mut MarkerList) {
+        marker_list.delete(self.start);
+        marker_list.delete(self.end);
+        for tabstop in self.tabstops {
+            for (start, end) in tabstop.ranges {
+                marker_list.delete(start);
+                marker_list.delete(end);
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Per-window spell check state.
+//!
+//! The dictionary is loaded the first time a buffer is checked, and again
+//! when `editor.spell_check_language` changes. Each buffer remembers the
+//! range it was last checked over and the version it was checked at, so
+//! redrawing or scrolling back over checked text costs nothing; only the
+//! visible range of a buffer is ever checked. Edits push the next check
+//! back until typing pauses, while the underlines already drawn move with
+//! the text.
+//!
+//! Checking, and the spelling entries of the code actions popup, live on
+//! `Editor` in `spell_check_actions.rs`.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+use crate::model::event::BufferId;
+use crate::services::spell_check::SpellChecker;
+use crate::view::overlay::OverlayNamespace;
+
+/// Code action command replacing a misspelled word.
+/// Arguments: start and end byte offsets, and the replacement.
+pub(crate) const REPLACE_COMMAND: &str = "fresh.spellCheck.replace";
+
+/// Code action command adding a word to the workspace's word list.
+/// Arguments: the word.
+pub(crate) const ADD_WORD_COMMAND: &str = "fresh.spellCheck.addWord";
+
+/// How long edits must pause before a buffer is checked again
+const SPELL_CHECK_DEBOUNCE_MS: u64 = 300;
+
+/// Namespace of the misspelling underlines
+pub(crate) fn spell_check_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("spell-check".to_string())
+}
+
+/// The part of a buffer checked last.
+#[derive(Debug, Clone)]
+struct CheckedRange {
+    /// Buffer version the range was checked at
+    version: u64,
+    range: Range<usize>,
+}
+
+/// Owner of the spell checker of one window.
+#[derive(Default)]
+pub(crate) struct SpellCheckState {
+    /// Language of the last dictionary lookup, with the checker built from
+    /// it; `None` inside when no dictionary was found
+    checker: Option<(String, Option<SpellChecker>)>,
+    checked: HashMap<BufferId, CheckedRange>,
+    debounce: HashMap<BufferId, Instant>,
+}
+
+impl SpellCheckState {
+    /// Whether a dictionary for `language` has been looked up.
+    pub(crate) fn is_loaded(&self, language: &str) -> bool {
+        self.checker.as_ref().is_some_and(|(l, _)| l == language)
+    }
+
+    /// Replace the checker, e.g. after the language changed. Everything has
+    /// to be checked again.
+    pub(crate) fn set_checker(&mut self, language: String, checker: Option<SpellChecker>) {
+        self.checker = Some((language, checker));
+        self.checked.clear();
+    }
+
+    pub(crate) fn checker(&self) -> Option<&SpellChecker> {
+        self.checker.as_ref().and_then(|(_, c)| c.as_ref())
+    }
+
+    pub(crate) fn checker_mut(&mut self) -> Option<&mut SpellChecker> {
+        self.checker.as_mut().and_then(|(_, c)| c.as_mut())
+    }
+
+    /// Check `buffer_id` again once edits have paused.
+    pub(crate) fn schedule(&mut self, buffer_id: BufferId) {
+        self.debounce.insert(
+            buffer_id,
+            Instant::now() + Duration::from_millis(SPELL_CHECK_DEBOUNCE_MS),
+        );
+    }
+
+    /// Whether `buffer_id` was edited too recently to be checked; forgets
+    /// the deadline once it has passed.
+    pub(crate) fn is_debouncing(&mut self, buffer_id: BufferId, now: Instant) -> bool {
+        match self.debounce.get(&buffer_id) {
+            Some(&ready_at) if now < ready_at => true,
+            Some(_) => {
+                self.debounce.remove(&buffer_id);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Whether `range` of `buffer_id` at `version` is not checked yet.
+    pub(crate) fn needs_check(
+        &self,
+        buffer_id: BufferId,
+        version: u64,
+        range: &Range<usize>,
+    ) -> bool {
+        !self.checked.get(&buffer_id).is_some_and(|c| {
+            c.version == version && c.range.start <= range.start && range.end <= c.range.end
+        })
+    }
+
+    /// Record that `range` of `buffer_id` was checked at `version`. Ranges
+    /// checked at the same version are joined when they touch.
+    pub(crate) fn mark_checked(&mut self, buffer_id: BufferId, version: u64, range: Range<usize>) {
+        let range = match self.checked.get(&buffer_id) {
+            Some(c)
+                if c.version == version
+                    && c.range.start <= range.end
+                    && range.start <= c.range.end =>
+            {
+                c.range.start.min(range.start)..c.range.end.max(range.end)
+            }
+            _ => range,
+        };
+        self.checked
+            .insert(buffer_id, CheckedRange { version, range });
+    }
+
+    /// Check every buffer again, e.g. because a word was added.
+    pub(crate) fn invalidate(&mut self) {
+        self.checked.clear();
+    }
+
+    /// Forget everything about a closed buffer.
+    pub(crate) fn remove_buffer(&mut self, buffer_id: BufferId) {
+        self.checked.remove(&buffer_id);
+        self.debounce.remove(&buffer_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_ranges_grow_within_a_version() {
+        let mut state = SpellCheckState::default();
+        let buf = BufferId(1);
+        assert!(state.needs_check(buf, 1, &(0..100)));
+
+        state.mark_checked(buf, 1, 0..100);
+        assert!(!state.needs_check(buf, 1, &(10..90)));
+        assert!(state.needs_check(buf, 1, &(50..150)));
+
+        state.mark_checked(buf, 1, 50..150);
+        assert!(!state.needs_check(buf, 1, &(0..150)));
+        // An edit makes everything stale
+        assert!(state.needs_check(buf, 2, &(0..10)));
+
+        state.mark_checked(buf, 2, 200..300);
+        assert!(state.needs_check(buf, 2, &(0..10)));
+        state.invalidate();
+        assert!(state.needs_check(buf, 2, &(200..300)));
+    }
+
+    #[test]
+    fn debounce_holds_checks_until_deadline() {
+        let mut state = SpellCheckState::default();
+        let buf = BufferId(1);
+        assert!(!state.is_debouncing(buf, Instant::now()));
+
+        state.schedule(buf);
+        assert!(state.is_debouncing(buf, Instant::now()));
+        let later = Instant::now() + Duration::from_millis(SPELL_CHECK_DEBOUNCE_MS + 1);
+        assert!(!state.is_debouncing(buf, later));
+        assert!(!state.is_debouncing(buf, Instant::now()));
+    }
+}
@@ -0,0 +1,438 @@
+//! Spell check orchestrators.
+//!
+//! Before each render, the visible lines of every shown buffer are checked
+//! unless they were already checked at the current buffer version: the
+//! comments and strings of code, found through the syntax highlighter, and
+//! everything but code spans and links in Markdown, plain text and commit
+//! messages. Misspelled words get an underline overlay. The code actions
+//! popup offers the dictionary's suggestions for the word at the cursor,
+//! and adding it to the workspace's word list; those entries are run here
+//! rather than sent to a language server.
+
+use std::ops::Range;
+use std::time::Instant;
+
+use ratatui::style::{Modifier, Style};
+use rust_i18n::t;
+
+use crate::app::spell_check::{spell_check_namespace, ADD_WORD_COMMAND, REPLACE_COMMAND};
+use crate::model::event::{BufferId, Event};
+use crate::primitives::highlighter::{HighlightCategory, HighlightSpan};
+use crate::services::spell_check::{
+    dictionary_dirs, find_dictionary, is_prose_language, parse_word_list, prose_words, Dictionary,
+    SpellChecker, WORD_LIST_PATH,
+};
+use crate::view::overlay::{Overlay, OverlayFace};
+
+use super::Editor;
+
+/// Underlines sit below diagnostics and other decorations.
+const SPELL_CHECK_PRIORITY: i32 = -10;
+
+/// Most text read from a viewport, for very long lines.
+const MAX_VISIBLE_BYTES: usize = 64 * 1024;
+
+impl Editor {
+    /// Check the visible lines of each `(buffer, top byte, height)` shown,
+    /// if not done already for the buffer's current version.
+    pub(crate) fn update_spell_check(&mut self, targets: &[(BufferId, usize, u16)]) {
+        if !self.ensure_spell_checker() {
+            return;
+        }
+        let now = Instant::now();
+        for &(buffer_id, top_byte, height) in targets {
+            if !self.is_spell_checked_buffer(buffer_id)
+                || self
+                    .active_window_mut()
+                    .spell_check
+                    .is_debouncing(buffer_id, now)
+            {
+                continue;
+            }
+            let Some((version, range, bytes)) = self.visible_text(buffer_id, top_byte, height)
+            else {
+                continue;
+            };
+            if !self
+                .active_window()
+                .spell_check
+                .needs_check(buffer_id, version, &range)
+            {
+                continue;
+            }
+            self.spell_check_range(buffer_id, range.clone(), &bytes);
+            self.active_window_mut()
+                .spell_check
+                .mark_checked(buffer_id, version, range);
+        }
+    }
+
+    /// Load the dictionary of `editor.spell_check_language` unless it was
+    /// looked up already. Returns whether there is one.
+    fn ensure_spell_checker(&mut self) -> bool {
+        let language = self.config.editor.spell_check_language.clone();
+        if !self.active_window().spell_check.is_loaded(&language) {
+            let checker = self.load_spell_checker(&language);
+            if checker.is_none() {
+                self.set_status_message(
+                    t!("spell.no_dictionary", language = &language).to_string(),
+                );
+            }
+            // Underlines of another language no longer apply
+            let ns = spell_check_namespace();
+            for state in self.buffers_mut().values_mut() {
+                state.overlays.clear_namespace(&ns, &mut state.marker_list);
+            }
+            self.active_window_mut()
+                .spell_check
+                .set_checker(language, checker);
+        }
+        self.active_window().spell_check.checker().is_some()
+    }
+
+    fn load_spell_checker(&self, language: &str) -> Option<SpellChecker> {
+        let dirs = dictionary_dirs(&self.dir_context.config_dir);
+        let (aff, dic) = find_dictionary(language, &dirs)?;
+        let dictionary = match Dictionary::load(&aff, &dic) {
+            Ok(dictionary) => dictionary,
+            Err(e) => {
+                tracing::warn!("Failed to load dictionary {}: {}", dic.display(), e);
+                return None;
+            }
+        };
+        tracing::info!("Loaded {} dictionary from {}", language, dic.display());
+        let words = self
+            .authority
+            .filesystem
+            .read_file(&self.working_dir().join(WORD_LIST_PATH))
+            .map(|bytes| parse_word_list(&String::from_utf8_lossy(&bytes)))
+            .unwrap_or_default();
+        Some(SpellChecker::new(dictionary, words))
+    }
+
+    /// Whether `buffer_id` holds text the user writes: not a virtual,
+    /// read-only, binary or terminal buffer.
+    fn is_spell_checked_buffer(&self, buffer_id: BufferId) -> bool {
+        let window = self.active_window();
+        window
+            .buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|meta| !meta.is_virtual() && !meta.read_only && !meta.binary)
+            && !window.is_terminal_buffer(buffer_id)
+    }
+
+    /// The version of `buffer_id`, with the range and text of the lines a
+    /// viewport of `height` rows starting at `top_byte` shows. `None` if
+    /// that text is not loaded.
+    fn visible_text(
+        &self,
+        buffer_id: BufferId,
+        top_byte: usize,
+        height: u16,
+    ) -> Option<(u64, Range<usize>, Vec<u8>)> {
+        let buffer = &self.buffers().get(&buffer_id)?.buffer;
+        let end = buffer.len().min(top_byte.saturating_add(MAX_VISIBLE_BYTES));
+        let mut bytes = buffer.slice_bytes(top_byte..end);
+        if bytes.is_empty() {
+            return None;
+        }
+        let last_line_end = bytes
+            .iter()
+            .enumerate()
+            .filter(|&(_, &b)| b == b'\n')
+            .map(|(i, _)| i + 1)
+            .nth(usize::from(height));
+        match last_line_end {
+            Some(len) => bytes.truncate(len),
+            // Don't stop in the middle of a word
+            None if end < buffer.len() => {
+                if let Some(i) = bytes.iter().rposition(|&b| b == b'\n') {
+                    bytes.truncate(i + 1);
+                }
+            }
+            None => {}
+        }
+        Some((buffer.version(), top_byte..top_byte + bytes.len(), bytes))
+    }
+
+    /// Underline the misspelled words of `range` of `buffer_id`, whose text
+    /// is `bytes`, replacing the underlines it had.
+    fn spell_check_range(&mut self, buffer_id: BufferId, range: Range<usize>, bytes: &[u8]) {
+        let text = decode_keeping_offsets(bytes);
+        let context_bytes = self.config.editor.highlight_context_bytes;
+        let theme = self.theme.read().unwrap();
+        let face = OverlayFace::Style {
+            style: Style::default()
+                .add_modifier(Modifier::UNDERLINED)
+                .underline_color(theme.spell_error_fg),
+        };
+        let window = self
+            .windows
+            .get_mut(&self.active_window)
+            .expect("active window present");
+        let Some(state) = window.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let Some(checker) = window.spell_check.checker_mut() else {
+            return;
+        };
+
+        let spans = state.highlighter.highlight_viewport(
+            &state.buffer,
+            range.start,
+            range.end,
+            &theme,
+            context_bytes,
+        );
+        let misspelled: Vec<Range<usize>> = prose_regions(&state.language, &spans, &range)
+            .into_iter()
+            .filter_map(|region| {
+                let region_text = text.get(region.start - range.start..region.end - range.start)?;
+                let words = prose_words(region_text)
+                    .into_iter()
+                    .filter(|word| checker.is_misspelled(&region_text[word.clone()]))
+                    .map(|word| region.start + word.start..region.start + word.end)
+                    .collect::<Vec<_>>();
+                Some(words)
+            })
+            .flatten()
+            .collect();
+
+        let ns = spell_check_namespace();
+        let overlays = misspelled
+            .into_iter()
+            .map(|word| {
+                Overlay::with_namespace(&mut state.marker_list, word, face.clone(), ns.clone())
+                    .with_priority_value(SPELL_CHECK_PRIORITY)
+                    .with_theme_key("editor.spell_error_fg")
+            })
+            .collect();
+        state
+            .overlays
+            .replace_range_in_namespace(&ns, &range, overlays, &mut state.marker_list);
+    }
+
+    /// Code actions for the misspelled word at the cursor: the dictionary's
+    /// suggestions, then adding the word to the workspace's word list.
+    pub(crate) fn spelling_code_actions(
+        &mut self,
+    ) -> Vec<(String, lsp_types::CodeActionOrCommand)> {
+        if !self.config.editor.spell_check {
+            return Vec::new();
+        }
+        let position = self.active_cursors().primary().position;
+        let ns = spell_check_namespace();
+        let state = self.active_state();
+        // The cursor may also sit just past the word
+        let word_range = [position, position.saturating_sub(1)]
+            .into_iter()
+            .find_map(|pos| {
+                state
+                    .overlays
+                    .at_position(pos, &state.marker_list)
+                    .into_iter()
+                    .find(|overlay| overlay.namespace.as_ref() == Some(&ns))
+                    .map(|overlay| overlay.range(&state.marker_list))
+            })
+            .filter(|range| range.start < range.end);
+        let Some(range) = word_range else {
+            return Vec::new();
+        };
+        let word = self
+            .active_state_mut()
+            .get_text_range(range.start, range.end);
+        let Some(checker) = self.active_window().spell_check.checker() else {
+            return Vec::new();
+        };
+
+        let source = t!("spell.source").to_string();
+        let command = |title: String, id: &str, arguments: Vec<serde_json::Value>| {
+            let command = lsp_types::Command {
+                title,
+                command: id.to_string(),
+                arguments: Some(arguments),
+            };
+            (
+                source.clone(),
+                lsp_types::CodeActionOrCommand::Command(command),
+            )
+        };
+        let mut actions: Vec<_> = checker
+            .suggest(&word)
+            .into_iter()
+            .map(|suggestion| {
+                command(
+                    t!("spell.replace_title", word = &suggestion).to_string(),
+                    REPLACE_COMMAND,
+                    vec![range.start.into(), range.end.into(), suggestion.into()],
+                )
+            })
+            .collect();
+        actions.push(command(
+            t!("spell.add_word_title", word = &word).to_string(),
+            ADD_WORD_COMMAND,
+            vec![word.into()],
+        ));
+        actions
+    }
+
+    /// Run `cmd` if it is one of the spelling code actions. Returns whether
+    /// it was.
+    pub(crate) fn execute_spell_check_command(&mut self, cmd: &lsp_types::Command) -> bool {
+        let args = cmd.arguments.as_deref().unwrap_or_default();
+        match cmd.command.as_str() {
+            REPLACE_COMMAND => {
+                let offset = |i: usize| {
+                    args.get(i)
+                        .and_then(serde_json::Value::as_u64)
+                        .map(|n| n as usize)
+                };
+                let replacement = args.get(2).and_then(serde_json::Value::as_str);
+                if let (Some(start), Some(end), Some(replacement)) =
+                    (offset(0), offset(1), replacement)
+                {
+                    self.replace_misspelling(start..end, replacement.to_string());
+                }
+            }
+            ADD_WORD_COMMAND => {
+                if let Some(word) = args.first().and_then(serde_json::Value::as_str) {
+                    self.add_word_to_dictionary(word);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn replace_misspelling(&mut self, range: Range<usize>, replacement: String) {
+        if range.start > range.end || range.end > self.active_state().buffer.len() {
+            return;
+        }
+        let cursor_id = self.active_cursors().primary_id();
+        let deleted_text = self
+            .active_state_mut()
+            .get_text_range(range.start, range.end);
+        let batch = Event::Batch {
+            events: vec![
+                Event::Delete {
+                    range: range.clone(),
+                    deleted_text,
+                    cursor_id,
+                },
+                Event::Insert {
+                    position: range.start,
+                    text: replacement,
+                    cursor_id,
+                },
+            ],
+            description: "Correct spelling".to_string(),
+        };
+        self.log_and_apply_event(&batch);
+    }
+
+    /// Accept `word` in this workspace from now on, recording it in the
+    /// workspace's word list.
+    fn add_word_to_dictionary(&mut self, word: &str) {
+        if let Err(e) = self.append_to_word_list(word) {
+            tracing::warn!("Failed to add {:?} to the word list: {}", word, e);
+            self.set_status_message(
+                t!("spell.add_word_failed", word = word, error = e.to_string()).to_string(),
+            );
+            return;
+        }
+        for window in self.windows.values_mut() {
+            if let Some(checker) = window.spell_check.checker_mut() {
+                checker.add_workspace_word(word);
+            }
+            window.spell_check.invalidate();
+        }
+        self.set_status_message(t!("spell.word_added", word = word).to_string());
+    }
+
+    fn append_to_word_list(&self, word: &str) -> std::io::Result<()> {
+        let fs = &self.authority.filesystem;
+        let path = self.working_dir().join(WORD_LIST_PATH);
+        if let Some(dir) = path.parent() {
+            fs.create_dir_all(dir)?;
+        }
+        let mut text = fs
+            .read_file(&path)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default();
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(word);
+        text.push('\n');
+        fs.write_file(&path, text.as_bytes())
+    }
+
+    /// Remove every misspelling underline and forget what was checked, e.g.
+    /// because spell checking was turned off. The dictionary is looked up
+    /// again when it is turned back on.
+    pub(crate) fn clear_spell_check(&mut self) {
+        let ns = spell_check_namespace();
+        for window in self.windows.values_mut() {
+            for state in window.buffers.values_mut() {
+                state.overlays.clear_namespace(&ns, &mut state.marker_list);
+            }
+            window.spell_check = Default::default();
+        }
+    }
+}
+
+/// The parts of `range` to check: comments and strings, or in prose files
+/// everything but code spans and links.
+fn prose_regions(
+    language: &str,
+    spans: &[HighlightSpan],
+    range: &Range<usize>,
+) -> Vec<Range<usize>> {
+    let clipped = spans.iter().filter_map(|span| {
+        let clipped = span.range.start.max(range.start)..span.range.end.min(range.end);
+        (clipped.start < clipped.end).then_some((span.category, clipped))
+    });
+    let mut regions: Vec<Range<usize>> = Vec::new();
+    if is_prose_language(language) {
+        let mut start = range.start;
+        for (_, skipped) in clipped.filter(|(category, _)| {
+            matches!(
+                category,
+                Some(HighlightCategory::String | HighlightCategory::Function)
+            )
+        }) {
+            if skipped.start > start {
+                regions.push(start..skipped.start);
+            }
+            start = start.max(skipped.end);
+        }
+        if start < range.end {
+            regions.push(start..range.end);
+        }
+    } else {
+        for (_, region) in clipped.filter(|(category, _)| {
+            matches!(
+                category,
+                Some(HighlightCategory::Comment | HighlightCategory::String)
+            )
+        }) {
+            match regions.last_mut() {
+                // Comment markers and string quotes are spans of their own
+                Some(last) if last.end >= region.start => last.end = last.end.max(region.end),
+                _ => regions.push(region),
+            }
+        }
+    }
+    regions
+}
+
+/// Text of `bytes` with each invalid byte read as a space, so that offsets
+/// into it are offsets into `bytes`.
+fn decode_keeping_offsets(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        text.push_str(chunk.valid());
+        text.extend(std::iter::repeat_n(' ', chunk.invalid().len()));
+    }
+    text
+}
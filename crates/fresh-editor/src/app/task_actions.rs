@@ -0,0 +1,515 @@
+//! Task runner orchestrators.
+//!
+//! "Run Task" offers the tasks of `config.tasks` that apply to the active
+//! buffer's language and runs the chosen one under the active authority,
+//! so a task of a project opened over SSH or in a container runs there.
+//! Its stdout and stderr are collected in the background, up to
+//! `MAX_TASK_OUTPUT_BYTES` each, and handed to the task's problem matcher;
+//! the problems come back as `AsyncMessage::TaskFinished` and are shown in
+//! the quickfix dock and as diagnostics in the `task-diagnostic` overlay
+//! namespace. The output itself is shown in the task output buffer, which
+//! opens by itself when a run fails without any problem found.
+//! "Cancel Task" kills the running command.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use rust_i18n::t;
+
+use crate::app::tasks::{RunningTask, TaskProblem};
+use crate::model::event::BufferId;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::lsp::diagnostics::{
+    apply_diagnostics_in_namespace, task_diagnostic_namespace,
+};
+use crate::services::problem_matcher::{parse_problems, Problem, ProblemSeverity};
+use crate::services::remote::LongRunningSpawner;
+use crate::state::EditorState;
+
+use super::{normalize_path, Editor};
+
+/// Display name of the task output buffer
+const TASK_OUTPUT_BUFFER_NAME: &str = "*Task Output*";
+
+/// Bytes of stdout, and of stderr, kept of a run; the rest is read and
+/// dropped so the command never blocks on a full pipe.
+const MAX_TASK_OUTPUT_BYTES: usize = 1024 * 1024;
+
+impl Editor {
+    /// Run a task: directly when only one applies to the active buffer,
+    /// otherwise after picking one.
+    pub(crate) fn run_task_picker(&mut self) {
+        use crate::view::popup::{Popup, PopupKind, PopupListItem, PopupPosition, PopupResolver};
+
+        let language = self.active_state().language.clone();
+        let mut names: Vec<&String> = self
+            .config
+            .tasks
+            .iter()
+            .filter(|(_, task)| task.applies_to(&language))
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+        match names.len() {
+            0 => {
+                self.set_status_message(
+                    t!("task.none_for_language", language = language).to_string(),
+                );
+                return;
+            }
+            1 => {
+                let name = names[0].clone();
+                self.run_task(&name);
+                return;
+            }
+            _ => {}
+        }
+
+        let items = names
+            .into_iter()
+            .map(|name| {
+                let task = &self.config.tasks[name];
+                PopupListItem {
+                    text: name.clone(),
+                    detail: Some(
+                        std::iter::once(task.command.as_str())
+                            .chain(task.args.iter().map(String::as_str))
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                    ),
+                    icon: None,
+                    data: Some(name.clone()),
+                    disabled: false,
+                }
+            })
+            .collect();
+        let theme = self.theme.read().unwrap();
+        let mut popup = Popup::list(items, &theme);
+        popup.kind = PopupKind::Action;
+        popup.title = Some(t!("task.popup_title").to_string());
+        popup.position = PopupPosition::BelowCursor;
+        popup.width = 50;
+        popup.max_height = 10;
+        popup.border_style = ratatui::style::Style::default().fg(theme.popup_border_fg);
+        popup.background_style = ratatui::style::Style::default().bg(theme.popup_bg);
+        // Selected row's `data` is the task name
+        popup.resolver = PopupResolver::Task;
+        popup.focused = true;
+        drop(theme);
+
+        self.active_state_mut().popups.show_or_replace(popup);
+    }
+
+    /// Start the task `name` of `config.tasks` in the background.
+    pub(crate) fn run_task(&mut self, name: &str) {
+        if let Some(running) = &self.active_window().tasks.running {
+            let running = running.name.clone();
+            self.set_status_message(t!("task.already_running", name = running).to_string());
+            return;
+        }
+        let Some(task) = self.config.tasks.get(name).cloned() else {
+            return;
+        };
+        let Some(runtime) = self.tokio_runtime.as_ref().map(|rt| rt.handle().clone()) else {
+            self.set_status_message(
+                t!(
+                    "task.failed_to_start",
+                    name = name,
+                    error = "async runtime not available"
+                )
+                .to_string(),
+            );
+            return;
+        };
+
+        // `join` keeps an absolute `cwd` as is
+        let cwd = match &task.cwd {
+            Some(dir) => self.working_dir.join(dir),
+            None => self.working_dir.clone(),
+        };
+        let cwd = self.task_remote_path(&cwd);
+        let mut env: Vec<(String, String)> = task.env.into_iter().collect();
+        env.sort();
+        let run_id = self.active_window_mut().tasks.allocate_run_id();
+        let (kill, killed) = tokio::sync::oneshot::channel();
+        let sender = self.active_window().bridge.sender();
+        let spawner = self.authority.long_running_spawner.clone();
+        let matcher = task.problem_matcher;
+        let command = task.command;
+        let args = task.args;
+        let task_cwd = cwd.clone();
+
+        tracing::info!(
+            "Running task '{}': {} {:?} in {:?}",
+            name,
+            command,
+            args,
+            cwd
+        );
+        runtime.spawn(async move {
+            let result = run_to_completion(spawner, &command, &args, env, &task_cwd, killed).await;
+            let message = match result {
+                Ok((output, exit_code)) => AsyncMessage::TaskFinished {
+                    run_id,
+                    exit_code,
+                    problems: matcher
+                        .map(|kind| parse_problems(kind, &output))
+                        .unwrap_or_default(),
+                    output,
+                    error: None,
+                },
+                Err(error) => AsyncMessage::TaskFinished {
+                    run_id,
+                    exit_code: -1,
+                    problems: Vec::new(),
+                    output: String::new(),
+                    error: Some(error),
+                },
+            };
+            #[allow(clippy::let_underscore_must_use)]
+            let _ = sender.send(message);
+        });
+
+        self.active_window_mut().tasks.running = Some(RunningTask {
+            run_id,
+            name: name.to_string(),
+            cwd,
+            kill: Some(kill),
+        });
+        self.set_status_message(t!("task.running", name = name).to_string());
+    }
+
+    /// Kill the running task.
+    pub(crate) fn cancel_task(&mut self) {
+        let Some(running) = self.active_window_mut().tasks.running.as_mut() else {
+            self.set_status_message(t!("task.not_running").to_string());
+            return;
+        };
+        // Its exit is reported by `handle_task_finished`
+        if running.cancel() {
+            let name = running.name.clone();
+            self.set_status_message(t!("task.cancelling", name = name).to_string());
+        }
+    }
+
+    /// A task exited: replace the problems of the last run with its own.
+    /// A cancelled run only leaves its output.
+    pub(crate) fn handle_task_finished(
+        &mut self,
+        run_id: u64,
+        exit_code: i32,
+        problems: Vec<Problem>,
+        output: String,
+        error: Option<String>,
+    ) {
+        let Some(running) = self
+            .active_window_mut()
+            .tasks
+            .running
+            .take_if(|r| r.run_id == run_id)
+        else {
+            return;
+        };
+        if let Some(error) = error {
+            self.set_status_message(
+                t!("task.failed_to_start", name = running.name, error = error).to_string(),
+            );
+            return;
+        }
+
+        self.active_window_mut().tasks.output = Some(output);
+        if running.is_cancelled() {
+            self.refresh_task_output(false);
+            self.set_status_message(t!("task.cancelled", name = running.name).to_string());
+            return;
+        }
+        // Nothing to step through: the output is all there is to read
+        let failed_silently = exit_code != 0 && problems.is_empty();
+        self.refresh_task_output(failed_silently);
+
+        let problems: Vec<TaskProblem> = problems
+            .into_iter()
+            .map(|problem| TaskProblem {
+                path: self.task_host_path(&normalize_path(&running.cwd.join(&problem.file))),
+                problem,
+            })
+            .collect();
+        let errors = problems
+            .iter()
+            .filter(|p| p.problem.severity == ProblemSeverity::Error)
+            .count();
+        let warnings = problems
+            .iter()
+            .filter(|p| p.problem.severity == ProblemSeverity::Warning)
+            .count();
+        let rows: Vec<String> = problems
+            .iter()
+            .map(|p| {
+                let path = p.path.strip_prefix(&self.working_dir).unwrap_or(&p.path);
+                format!(
+                    "{}:{}:{}  {}",
+                    path.display(),
+                    p.problem.line,
+                    p.problem.column,
+                    p.problem.message
+                )
+            })
+            .collect();
+        self.active_window_mut()
+            .tasks
+            .set_problems(running.name.clone(), problems);
+        self.refresh_task_diagnostics();
+
+        if !rows.is_empty() {
+            let header = format!("Quickfix: {} ({} problems)", running.name, rows.len());
+            // Showing the list focuses the dock; stay in the editor
+            let split = self.split_manager().active_split();
+            if self.show_quickfix_in_dock(header, rows).is_some() {
+                self.split_manager_mut().set_active_split(split);
+            }
+        }
+        self.set_status_message(
+            t!(
+                "task.finished",
+                name = running.name,
+                code = exit_code,
+                errors = errors,
+                warnings = warnings
+            )
+            .to_string(),
+        );
+    }
+
+    /// Open the task output buffer.
+    pub(crate) fn show_task_output(&mut self) {
+        if self.active_window().tasks.output.is_none() {
+            self.set_status_message(t!("task.no_output").to_string());
+            return;
+        }
+        self.refresh_task_output(true);
+    }
+
+    /// Put the output of the last run in the task output buffer, if it is
+    /// open or `open` is set, and show it in the latter case.
+    fn refresh_task_output(&mut self, open: bool) {
+        use crate::primitives::text_property::TextPropertyEntry;
+
+        let Some(content) = self.active_window().tasks.output.clone() else {
+            return;
+        };
+        let existing = self
+            .active_window()
+            .buffer_metadata
+            .iter()
+            .find(|(_, m)| m.display_name == TASK_OUTPUT_BUFFER_NAME)
+            .map(|(id, _)| *id);
+        let buffer_id = match existing {
+            Some(buffer_id) => buffer_id,
+            None if open => {
+                let buffer_id = self.active_window_mut().create_virtual_buffer(
+                    TASK_OUTPUT_BUFFER_NAME.to_string(),
+                    "special".to_string(),
+                    true,
+                );
+                if let Some(state) = self.active_window_mut().buffers.get_mut(&buffer_id) {
+                    state.editing_disabled = true;
+                }
+                buffer_id
+            }
+            None => return,
+        };
+        if let Err(e) =
+            self.set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(content)])
+        {
+            tracing::error!("Failed to set task output content: {}", e);
+            return;
+        }
+        if open {
+            self.set_active_buffer(buffer_id);
+        }
+    }
+
+    /// Draw the problems of the last run in every open buffer they point
+    /// into, and clear those of earlier runs everywhere else.
+    fn refresh_task_diagnostics(&mut self) {
+        let buffers: Vec<(BufferId, PathBuf)> = self
+            .buffers()
+            .iter()
+            .filter_map(|(id, state)| state.buffer.file_path().map(|p| (*id, p.to_path_buf())))
+            .collect();
+        for (buffer_id, path) in buffers {
+            self.apply_task_problems(buffer_id, &path);
+        }
+    }
+
+    /// Draw the problems of the last run that point into `path`, the file
+    /// of `buffer_id`. Also called when a file is opened.
+    pub(crate) fn apply_task_problems(&mut self, buffer_id: BufferId, path: &Path) {
+        let problems: Vec<Problem> = self
+            .active_window()
+            .tasks
+            .problems_in(path)
+            .cloned()
+            .collect();
+        let theme = self.theme.clone();
+        let Some(state) = self.buffers_mut().get_mut(&buffer_id) else {
+            return;
+        };
+        let diagnostics: Vec<Diagnostic> = problems
+            .iter()
+            .map(|p| problem_to_diagnostic(p, state))
+            .collect();
+        apply_diagnostics_in_namespace(
+            state,
+            task_diagnostic_namespace(),
+            &diagnostics,
+            &theme.read().unwrap(),
+        );
+    }
+
+    /// Go to the next (or previous) problem of the last task run, opening
+    /// its file. Returns `false` when there are none, so error navigation
+    /// falls back to the active buffer's diagnostics.
+    pub(super) fn jump_to_task_problem(&mut self, forward: bool) -> bool {
+        let tasks = &mut self.active_window_mut().tasks;
+        let Some(index) = tasks.step(forward) else {
+            return false;
+        };
+        let count = tasks.problems.len();
+        let TaskProblem { path, problem } = tasks.problems[index].clone();
+        if let Err(e) = self.open_file(&path) {
+            self.set_status_message(t!("file.error_opening", error = e.to_string()).to_string());
+            return true;
+        }
+        self.goto_line_col(problem.line, Some(problem.column));
+        self.set_status_message(
+            t!(
+                "task.problem_position",
+                index = index + 1,
+                count = count,
+                message = problem.message
+            )
+            .to_string(),
+        );
+        true
+    }
+
+    /// Path of a host file as the task's authority sees it.
+    fn task_remote_path(&self, path: &Path) -> PathBuf {
+        self.authority
+            .path_translation
+            .as_ref()
+            .and_then(|t| t.host_to_remote(path))
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+
+    /// Path of an authority-side file on the host.
+    fn task_host_path(&self, path: &Path) -> PathBuf {
+        self.authority
+            .path_translation
+            .as_ref()
+            .and_then(|t| t.remote_to_host(path))
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+}
+
+/// A problem as a diagnostic spanning from its column to the end of its
+/// line, so the overlay is visible even when the tool gives no range.
+fn problem_to_diagnostic(problem: &Problem, state: &EditorState) -> Diagnostic {
+    let line = problem.line.saturating_sub(1);
+    let end = state
+        .buffer
+        .get_line(line)
+        .map(|bytes| {
+            String::from_utf8_lossy(&bytes)
+                .trim_end_matches(['\n', '\r'])
+                .encode_utf16()
+                .count()
+        })
+        .unwrap_or(0);
+    let start = problem.column.saturating_sub(1).min(end);
+    let severity = match problem.severity {
+        ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+        ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+        ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+    };
+    Diagnostic {
+        range: Range {
+            start: Position::new(line as u32, start as u32),
+            end: Position::new(line as u32, end.max(start + 1) as u32),
+        },
+        severity: Some(severity),
+        message: problem.message.clone(),
+        ..Default::default()
+    }
+}
+
+/// Run `command` to completion, or until `killed` fires, and return its
+/// stdout followed by its stderr, and its exit code.
+async fn run_to_completion(
+    spawner: Arc<dyn LongRunningSpawner>,
+    command: &str,
+    args: &[String],
+    env: Vec<(String, String)>,
+    cwd: &Path,
+    mut killed: tokio::sync::oneshot::Receiver<()>,
+) -> Result<(String, i32), String> {
+    use tokio::io::{AsyncRead, AsyncReadExt};
+
+    /// Read `stream` to its end, keeping the first `MAX_TASK_OUTPUT_BYTES`.
+    async fn read_capped(stream: Option<impl AsyncRead + Unpin>, bytes: &mut Vec<u8>) {
+        let Some(mut stream) = stream else {
+            return;
+        };
+        let mut chunk = [0u8; 8192];
+        loop {
+            match stream.read(&mut chunk).await {
+                Ok(0) => break,
+                Ok(n) => {
+                    let room = MAX_TASK_OUTPUT_BYTES - bytes.len();
+                    bytes.extend_from_slice(&chunk[..n.min(room)]);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to read task output: {}", e);
+                    break;
+                }
+            }
+        }
+    }
+
+    fn text(bytes: &[u8]) -> String {
+        let mut text = String::from_utf8_lossy(bytes).into_owned();
+        if bytes.len() >= MAX_TASK_OUTPUT_BYTES {
+            text.push_str("\n[output truncated]");
+        }
+        text
+    }
+
+    let mut child = spawner
+        .spawn_stdio(command, args, env, Some(cwd), None)
+        .await
+        .map_err(|e| e.to_string())?;
+    // Tasks get no input; closing stdin lets tools that read it finish
+    drop(child.take_stdin());
+    let (stdout, stderr) = (child.take_stdout(), child.take_stderr());
+    let (mut out, mut err) = (Vec::new(), Vec::new());
+    let exited = async {
+        tokio::join!(read_capped(stdout, &mut out), read_capped(stderr, &mut err));
+        child.wait().await
+    };
+    // Whatever the killed command printed so far is kept; its children
+    // may hold the pipes open, so they aren't read to the end
+    let status = tokio::select! {
+        status = exited => status,
+        _ = &mut killed => {
+            child.kill().await.map_err(|e| e.to_string())?;
+            child.wait().await
+        }
+    }
+    .map_err(|e| e.to_string())?;
+    Ok((
+        format!("{}\n{}", text(&out), text(&err)),
+        status.code().unwrap_or(-1),
+    ))
+}
@@ -0,0 +1,166 @@
+//! Per-window task runner state.
+//!
+//! At most one task runs per window, until it exits or is cancelled. When
+//! it exits, the problems its problem matcher found replace those of the
+//! previous run: they are listed in the quickfix dock, drawn as
+//! diagnostics in the buffers they point into (including buffers opened
+//! later), and stepped through by next/previous error. Its output is kept
+//! for the task output buffer. Running tasks and showing their problems
+//! lives on `Editor` in `task_actions.rs`.
+
+use std::path::{Path, PathBuf};
+
+use crate::services::problem_matcher::Problem;
+
+/// The task currently running.
+#[derive(Debug)]
+pub(crate) struct RunningTask {
+    pub run_id: u64,
+    /// Key of the task in `config.tasks`
+    pub name: String,
+    /// Directory the command runs in, as the authority sees it
+    pub cwd: PathBuf,
+    /// Kills the child process; `None` once the task was cancelled
+    pub kill: Option<tokio::sync::oneshot::Sender<()>>,
+}
+
+impl RunningTask {
+    /// Kill the child process. Returns `false` if it was already asked to.
+    pub(crate) fn cancel(&mut self) -> bool {
+        match self.kill.take() {
+            // The run may have ended meanwhile
+            Some(kill) => {
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = kill.send(());
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.kill.is_none()
+    }
+}
+
+/// A problem from the last run, with its file resolved to a host path.
+#[derive(Debug, Clone)]
+pub(crate) struct TaskProblem {
+    pub path: PathBuf,
+    pub problem: Problem,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct TaskState {
+    pub running: Option<RunningTask>,
+    next_run_id: u64,
+    /// Name of the task that produced `problems`
+    pub last_task: Option<String>,
+    /// Problems of the last finished run, in output order
+    pub problems: Vec<TaskProblem>,
+    /// Output of the last finished run: stdout, then stderr
+    pub output: Option<String>,
+    /// Index into `problems` of the problem last jumped to
+    current: Option<usize>,
+}
+
+impl TaskState {
+    pub(crate) fn allocate_run_id(&mut self) -> u64 {
+        self.next_run_id += 1;
+        self.next_run_id
+    }
+
+    /// Replace the problems with those of a new run.
+    pub(crate) fn set_problems(&mut self, task: String, problems: Vec<TaskProblem>) {
+        self.last_task = Some(task);
+        self.problems = problems;
+        self.current = None;
+    }
+
+    /// Step to the next (or previous) problem, wrapping around at the
+    /// ends. Returns its index.
+    pub(crate) fn step(&mut self, forward: bool) -> Option<usize> {
+        let len = self.problems.len();
+        if len == 0 {
+            return None;
+        }
+        let index = match (self.current, forward) {
+            (None, true) => 0,
+            (None, false) => len - 1,
+            (Some(i), true) => (i + 1) % len,
+            (Some(i), false) => (i + len - 1) % len,
+        };
+        self.current = Some(index);
+        Some(index)
+    }
+
+    /// Problems pointing into `path`.
+    pub(crate) fn problems_in<'a>(&'a self, path: &'a Path) -> impl Iterator<Item = &'a Problem> {
+        self.problems
+            .iter()
+            .filter(move |p| p.path == path)
+            .map(|p| &p.problem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::problem_matcher::ProblemSeverity;
+
+    fn task_problem(path: &str, line: usize) -> TaskProblem {
+        TaskProblem {
+            path: PathBuf::from(path),
+            problem: Problem {
+                file: path.to_string(),
+                line,
+                column: 1,
+                severity: ProblemSeverity::Error,
+                message: String::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn step_wraps_in_both_directions() {
+        let mut state = TaskState::default();
+        assert_eq!(state.step(true), None);
+
+        state.set_problems(
+            "build".to_string(),
+            vec![
+                task_problem("/src/a.rs", 1),
+                task_problem("/src/b.rs", 2),
+                task_problem("/src/a.rs", 3),
+            ],
+        );
+        assert_eq!(state.step(false), Some(2));
+        assert_eq!(state.step(true), Some(0));
+        assert_eq!(state.step(true), Some(1));
+        assert_eq!(state.step(true), Some(2));
+        assert_eq!(state.step(true), Some(0));
+        assert_eq!(state.step(false), Some(2));
+
+        let lines: Vec<usize> = state
+            .problems_in(Path::new("/src/a.rs"))
+            .map(|p| p.line)
+            .collect();
+        assert_eq!(lines, vec![1, 3]);
+    }
+
+    #[test]
+    fn cancel_kills_once() {
+        let (kill, mut killed) = tokio::sync::oneshot::channel();
+        let mut running = RunningTask {
+            run_id: 1,
+            name: "build".to_string(),
+            cwd: PathBuf::from("/src"),
+            kill: Some(kill),
+        };
+        assert!(!running.is_cancelled());
+        assert!(running.cancel());
+        assert!(running.is_cancelled());
+        assert!(!running.cancel());
+        assert_eq!(killed.try_recv(), Ok(()));
+    }
+}
@@ -101,7 +101,10 @@ impl Editor {
             .expect("active window present")
             .get(&predicted_terminal_id)
             .cloned();
-        let working_dir_for_spawn = self.working_dir.clone();
+        // A terminal opened from another one starts where that one's shell is
+        let working_dir_for_spawn = self
+            .active_terminal_cwd()
+            .unwrap_or_else(|| self.working_dir.clone());
         let wrapper_for_spawn = self.resolved_terminal_wrapper();
         match self
             .windows
@@ -144,6 +147,24 @@ impl Editor {
         }
     }
 
+    /// Working directory of the active terminal buffer's shell, if the
+    /// active buffer is a terminal and the directory exists.
+    pub(crate) fn active_terminal_cwd(&self) -> Option<std::path::PathBuf> {
+        self.terminal_cwd(self.active_buffer())
+    }
+
+    /// Working directory of a terminal buffer's shell, if it exists.
+    pub(crate) fn terminal_cwd(&self, buffer_id: BufferId) -> Option<std::path::PathBuf> {
+        let terminal_id = self.active_window().get_terminal_id(buffer_id)?;
+        let cwd = self
+            .active_window()
+            .terminal_manager
+            .get(terminal_id)?
+            .cwd()?;
+        // A shell may report a directory on another host (e.g. over ssh)
+        matches!(self.authority.filesystem.is_dir(&cwd), Ok(true)).then_some(cwd)
+    }
+
     /// Open a new terminal in the current split
     pub fn open_terminal(&mut self) {
         let Some(terminal_id) = self.spawn_terminal_session() else {
@@ -911,15 +932,33 @@ impl Editor {
                         // Clear the content area first
                         frame.render_widget(ratatui::widgets::Clear, *content_rect);
 
+                        let theme = self.theme.read().unwrap();
+                        let gutter = render::gutter_width(&state);
+                        if gutter > 0 {
+                            let exit_statuses: Vec<Option<i32>> =
+                                (0..rows).map(|row| state.exit_status_at_row(row)).collect();
+                            render::render_exit_status_gutter(
+                                &exit_statuses,
+                                *content_rect,
+                                frame.buffer_mut(),
+                                &theme,
+                            );
+                        }
+                        let terminal_rect = ratatui::layout::Rect {
+                            x: content_rect.x + gutter,
+                            width: content_rect.width.saturating_sub(gutter),
+                            ..*content_rect
+                        };
+
                         // Render terminal content with theme colors
                         render::render_terminal_content(
                             &content,
                             cursor_pos,
                             cursor_visible,
-                            *content_rect,
+                            terminal_rect,
                             frame.buffer_mut(),
-                            self.theme.read().unwrap().terminal_fg,
-                            self.theme.read().unwrap().terminal_bg,
+                            theme.terminal_fg,
+                            theme.terminal_bg,
                         );
                     }
                 }
@@ -930,12 +969,46 @@ impl Editor {
 
 /// Terminal rendering utilities
 pub mod render {
-    use crate::services::terminal::TerminalCell;
+    use crate::services::terminal::links;
+    use crate::services::terminal::{TerminalCell, TerminalState};
+    use crate::view::theme::Theme;
     use ratatui::buffer::Buffer;
     use ratatui::layout::Rect;
     use ratatui::style::{Color, Modifier, Style};
 
-    /// Render terminal content to a ratatui buffer
+    /// Columns left of the terminal grid used for exit status markers.
+    ///
+    /// Only shells with shell integration get the gutter. The grid is
+    /// already one column narrower than the content area (see
+    /// `resize_visible_terminals`), so it fits without resizing the PTY.
+    pub fn gutter_width(state: &TerminalState) -> u16 {
+        u16::from(state.has_shell_integration())
+    }
+
+    /// Render a marker beside each prompt whose command finished: `●` for
+    /// success, `✗` for a non-zero exit status.
+    pub fn render_exit_status_gutter(
+        exit_statuses: &[Option<i32>],
+        area: Rect,
+        buf: &mut Buffer,
+        theme: &Theme,
+    ) {
+        let base = Style::default().bg(theme.terminal_bg);
+        for (row, status) in exit_statuses.iter().enumerate() {
+            if row as u16 >= area.height {
+                break;
+            }
+            let (symbol, style) = match status {
+                Some(0) => ("●", base.fg(theme.file_status_added_fg)),
+                Some(_) => ("✗", base.fg(theme.diagnostic_error_fg)),
+                None => (" ", base),
+            };
+            buf.set_string(area.x, area.y + row as u16, symbol, style);
+        }
+    }
+
+    /// Render terminal content to a ratatui buffer. File locations and
+    /// URLs are underlined (see `services::terminal::links`).
     pub fn render_terminal_content(
         content: &[Vec<TerminalCell>],
         cursor_pos: (u16, u16),
@@ -951,6 +1024,11 @@ pub mod render {
             }
 
             let y = area.y + row_idx as u16;
+            let text: String = row.iter().map(|cell| cell.c).collect();
+            let link_columns: Vec<_> = links::find_links(&text)
+                .into_iter()
+                .map(|link| link.columns)
+                .collect();
 
             for (col_idx, cell) in row.iter().enumerate() {
                 if col_idx as u16 >= area.width {
@@ -978,7 +1056,7 @@ pub mod render {
                 if cell.italic {
                     style = style.add_modifier(Modifier::ITALIC);
                 }
-                if cell.underline {
+                if cell.underline || link_columns.iter().any(|cols| cols.contains(&col_idx)) {
                     style = style.add_modifier(Modifier::UNDERLINED);
                 }
                 if cell.inverse {
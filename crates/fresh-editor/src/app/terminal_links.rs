@@ -0,0 +1,149 @@
+//! Opening links found in terminal output.
+//!
+//! Ctrl+click on a `file:line:col` location or URL in a terminal (live
+//! grid or scrollback) opens it, as does "Open Link Under Cursor" in
+//! scrollback mode. Detection lives in `services::terminal::links`.
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::app::terminal::render;
+use crate::model::event::BufferId;
+use crate::primitives::path_utils::expand_tilde;
+use crate::services::terminal::links::{self, LinkTarget, TerminalLink};
+
+impl Editor {
+    /// Open the link at a screen position over a live terminal grid.
+    /// Returns `false` if there is no terminal grid or no link there.
+    pub(crate) fn try_open_terminal_link_at(&mut self, col: u16, row: u16) -> bool {
+        let window = self.active_window();
+        let hit = self.active_layout().split_areas.iter().find_map(
+            |(_, buffer_id, content_rect, _, _, _)| {
+                let inside = col >= content_rect.x
+                    && col < content_rect.x + content_rect.width
+                    && row >= content_rect.y
+                    && row < content_rect.y + content_rect.height;
+                // The active terminal in scrollback mode is rendered as a
+                // regular buffer; clicks there are handled by the buffer.
+                let live = *buffer_id != self.active_buffer() || window.terminal_mode;
+                (inside && live && window.is_terminal_buffer(*buffer_id))
+                    .then_some((*buffer_id, *content_rect))
+            },
+        );
+        let Some((buffer_id, content_rect)) = hit else {
+            return false;
+        };
+        let link = window
+            .get_terminal_id(buffer_id)
+            .and_then(|id| window.terminal_manager.get(id))
+            .and_then(|handle| {
+                let state = handle.state.lock().ok()?;
+                if state.is_alternate_screen() {
+                    return None;
+                }
+                let grid_col = col - content_rect.x;
+                let gutter = render::gutter_width(&state);
+                let column = grid_col.checked_sub(gutter)?;
+                let line: String = state
+                    .get_line(row - content_rect.y)
+                    .iter()
+                    .map(|cell| cell.c)
+                    .collect();
+                links::link_at(&line, column as usize)
+            });
+        match link {
+            Some(link) => {
+                self.open_terminal_link(buffer_id, link);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Open the link at a byte position of a terminal's scrollback buffer.
+    /// Returns `false` if there is no link there.
+    pub(crate) fn try_open_terminal_link_at_position(
+        &mut self,
+        buffer_id: BufferId,
+        position: usize,
+    ) -> bool {
+        match self.terminal_link_at_position(buffer_id, position) {
+            Some(link) => {
+                self.open_terminal_link(buffer_id, link);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Open the link under the cursor in terminal scrollback.
+    pub(crate) fn terminal_open_link_under_cursor(&mut self) {
+        let buffer_id = self.active_buffer();
+        let position = self.active_cursors().primary().position;
+        if self.active_window().terminal_mode
+            || !self.try_open_terminal_link_at_position(buffer_id, position)
+        {
+            self.set_status_message(t!("terminal.no_link").to_string());
+        }
+    }
+
+    fn terminal_link_at_position(
+        &self,
+        buffer_id: BufferId,
+        position: usize,
+    ) -> Option<TerminalLink> {
+        if !self.active_window().is_terminal_buffer(buffer_id) {
+            return None;
+        }
+        let buffer = &self.buffers().get(&buffer_id)?.buffer;
+        let line = buffer.get_line_number(position);
+        let line_start = buffer.line_start_offset(line)?;
+        let bytes = buffer.get_line(line)?;
+        let text = String::from_utf8_lossy(&bytes);
+        let byte_column = position.saturating_sub(line_start).min(text.len());
+        let column = text
+            .get(..byte_column)
+            .map_or(0, |before| before.chars().count());
+        links::link_at(text.trim_end_matches(['\r', '\n']), column)
+    }
+
+    fn open_terminal_link(&mut self, buffer_id: BufferId, link: TerminalLink) {
+        match link.target {
+            LinkTarget::Url(url) => {
+                if let Err(e) = open::that(&url) {
+                    self.set_status_message(
+                        t!("terminal.link_open_failed", error = e.to_string()).to_string(),
+                    );
+                } else {
+                    self.set_status_message(t!("terminal.opening_url", url = url).to_string());
+                }
+            }
+            LinkTarget::File(location) => {
+                let mut path = expand_tilde(&location.path.to_string_lossy());
+                if path.is_relative() {
+                    let cwd = self
+                        .terminal_cwd(buffer_id)
+                        .unwrap_or_else(|| self.working_dir.clone());
+                    path = cwd.join(path);
+                }
+                if !matches!(self.authority.filesystem.is_file(&path), Ok(true)) {
+                    self.set_status_message(
+                        t!("terminal.link_not_found", path = path.display().to_string())
+                            .to_string(),
+                    );
+                    return;
+                }
+                self.queue_file_open(
+                    path,
+                    location.line,
+                    location.column,
+                    location.end_line,
+                    location.end_column,
+                    location.message,
+                    None,
+                );
+                self.process_pending_file_opens();
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Terminal actions driven by shell integration marks.
+//!
+//! Shells with shell integration mark each prompt and the output of each
+//! command (see `crate::services::terminal::shell_integration`). These
+//! actions jump between prompts and select or copy the last command's
+//! output. Jumping and selecting happen in the terminal's scrollback
+//! view, where the mark lines are buffer lines.
+
+use std::ops::Range;
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::input::keybindings::KeyContext;
+use crate::model::event::Event;
+use crate::services::terminal::CommandMark;
+
+impl Editor {
+    /// Move to the previous (or next) prompt of the active terminal.
+    pub(crate) fn terminal_jump_to_prompt(&mut self, forward: bool) {
+        let Some((commands, base_line)) = self.active_terminal_commands() else {
+            return;
+        };
+        if commands.is_empty() {
+            self.set_status_message(t!("terminal.no_shell_integration").to_string());
+            return;
+        }
+        self.show_terminal_scrollback();
+
+        let position = self.active_cursors().primary().position;
+        let cursor_line = self.active_state().buffer.get_line_number(position);
+        let mut prompts = commands.iter().map(|c| c.prompt_line + base_line);
+        let target = if forward {
+            prompts.find(|&line| line > cursor_line)
+        } else {
+            prompts.rev().find(|&line| line < cursor_line)
+        };
+        match target {
+            Some(line) => self.goto_line_col(line + 1, Some(1)),
+            None => self.set_status_message(t!("terminal.no_more_prompts").to_string()),
+        }
+    }
+
+    /// Select the output of the last finished command in the active
+    /// terminal's scrollback.
+    pub(crate) fn terminal_select_last_output(&mut self) {
+        let Some(lines) = self
+            .active_window()
+            .get_active_terminal_state()
+            .map(|state| {
+                let base_line = state.backing_file_base_line();
+                state
+                    .last_output_lines()
+                    .map(|lines| lines.start + base_line..lines.end + base_line)
+            })
+        else {
+            return;
+        };
+        let Some(lines) = lines else {
+            self.set_status_message(t!("terminal.no_command_output").to_string());
+            return;
+        };
+        self.show_terminal_scrollback();
+        self.select_lines(lines);
+    }
+
+    /// Copy the output of the last finished command in the active
+    /// terminal to the clipboard.
+    pub(crate) fn terminal_copy_last_output(&mut self) {
+        let Some(text) = self
+            .active_window()
+            .get_active_terminal_state()
+            .and_then(|state| state.last_command_output())
+        else {
+            self.set_status_message(t!("terminal.no_command_output").to_string());
+            return;
+        };
+        let lines = text.lines().count();
+        self.clipboard.copy(text);
+        self.set_status_message(t!("terminal.output_copied", lines = lines).to_string());
+    }
+
+    /// Commands of the active terminal and the scrollback buffer line
+    /// their lines are counted from. `None` if the active buffer is not
+    /// a terminal.
+    fn active_terminal_commands(&self) -> Option<(Vec<CommandMark>, usize)> {
+        let state = self.active_window().get_active_terminal_state()?;
+        Some((state.commands().to_vec(), state.backing_file_base_line()))
+    }
+
+    /// Leave terminal mode for the read-only scrollback view.
+    fn show_terminal_scrollback(&mut self) {
+        if !self.active_window().terminal_mode {
+            return;
+        }
+        self.active_window_mut().terminal_mode = false;
+        self.active_window_mut().key_context = KeyContext::Normal;
+        let buffer_id = self.active_buffer();
+        self.active_window_mut().sync_terminal_to_buffer(buffer_id);
+    }
+
+    /// Select whole buffer lines, leaving the cursor after the last one.
+    fn select_lines(&mut self, lines: Range<usize>) {
+        let buffer = &self.active_state().buffer;
+        let Some(start) = buffer.line_start_offset(lines.start) else {
+            return;
+        };
+        let end = buffer
+            .line_start_offset(lines.end)
+            .unwrap_or_else(|| buffer.len());
+        let cursors = self.active_cursors();
+        let primary = cursors.primary();
+        let event = Event::MoveCursor {
+            cursor_id: cursors.primary_id(),
+            old_position: primary.position,
+            new_position: end,
+            old_anchor: primary.anchor,
+            new_anchor: Some(start),
+            old_sticky_column: primary.sticky_column,
+            new_sticky_column: 0,
+        };
+        let buffer_id = self.active_buffer();
+        let split_id = self.split_manager().active_split();
+        self.active_window_mut()
+            .apply_event_to_buffer(buffer_id, split_id, &event);
+    }
+}
@@ -7,6 +7,7 @@
 //! area are converted to terminal escape sequences and sent to the PTY, allowing
 //! full-screen terminal programs to receive and handle mouse input.
 
+use crate::app::terminal::render;
 use crate::app::window::Window;
 use crate::input::handler::{TerminalMouseButton, TerminalMouseEventKind};
 use crate::model::event::BufferId;
@@ -36,6 +37,18 @@ impl Window {
             return None;
         }
 
+        // The grid starts right of the exit status gutter.
+        let gutter = self
+            .get_terminal_id(buffer_id)
+            .and_then(|id| self.terminal_manager.get(id))
+            .and_then(|handle| handle.state.lock().ok().map(|s| render::gutter_width(&s)))
+            .unwrap_or(0);
+        let content_rect = Rect {
+            x: content_rect.x + gutter,
+            width: content_rect.width.saturating_sub(gutter),
+            ..content_rect
+        };
+
         // Forward the event.
         Some(self.forward_mouse_to_terminal(col, row, content_rect, mouse_event))
     }
@@ -99,6 +99,67 @@ impl Editor {
         self.set_status_message(status.to_string());
     }
 
+    /// Toggle minimap visibility
+    pub fn toggle_minimap(&mut self) {
+        let new_value = !self.config.editor.show_minimap;
+        self.config_mut().editor.show_minimap = new_value;
+        let status = if self.config.editor.show_minimap {
+            t!("toggle.minimap_shown")
+        } else {
+            t!("toggle.minimap_hidden")
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    /// Toggle sticky scroll headers
+    pub fn toggle_sticky_scroll(&mut self) {
+        let new_value = !self.config.editor.sticky_scroll;
+        self.config_mut().editor.sticky_scroll = new_value;
+        let status = if self.config.editor.sticky_scroll {
+            t!("toggle.sticky_scroll_shown")
+        } else {
+            t!("toggle.sticky_scroll_hidden")
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    /// Toggle the breadcrumb bar
+    pub fn toggle_breadcrumbs(&mut self) {
+        let new_value = !self.config.editor.show_breadcrumbs;
+        self.config_mut().editor.show_breadcrumbs = new_value;
+        let status = if self.config.editor.show_breadcrumbs {
+            t!("toggle.breadcrumbs_shown")
+        } else {
+            t!("toggle.breadcrumbs_hidden")
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    /// Toggle indentation guides
+    pub fn toggle_indent_guides(&mut self) {
+        let new_value = !self.config.editor.show_indent_guides;
+        self.config_mut().editor.show_indent_guides = new_value;
+        let status = if self.config.editor.show_indent_guides {
+            t!("toggle.indent_guides_shown")
+        } else {
+            t!("toggle.indent_guides_hidden")
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    /// Toggle spell checking
+    pub fn toggle_spell_check(&mut self) {
+        let new_value = !self.config.editor.spell_check;
+        self.config_mut().editor.spell_check = new_value;
+        let status = if self.config.editor.spell_check {
+            t!("toggle.spell_check_enabled")
+        } else {
+            self.clear_spell_check();
+            t!("toggle.spell_check_disabled")
+        };
+        self.set_status_message(status.to_string());
+    }
+
     /// Reset buffer settings (tab_size, use_tabs, auto_close, whitespace visibility) to config defaults
     pub fn reset_buffer_settings(&mut self) {
         use crate::config::WhitespaceVisibility;
@@ -248,7 +309,7 @@ impl Editor {
                 .expect("active window present")
                 .values_mut()
             {
-                state.virtual_texts.clear(&mut state.marker_list);
+                state.virtual_texts.clear_inline(&mut state.marker_list);
             }
             self.set_status_message(t!("toggle.inlay_hints_disabled").to_string());
         }
@@ -348,6 +409,9 @@ impl Editor {
         // Always reload keybindings (complex types don't implement PartialEq)
         *self.keybindings.write().unwrap() = KeybindingResolver::new(&self.config);
 
+        // Pick up plugin permission answers edited in the config file
+        self.sync_plugin_grants();
+
         // Update clipboard configuration
         self.clipboard.apply_config(&self.config.clipboard);
 
@@ -572,6 +572,15 @@ pub(super) struct LspMessageEntry {
     pub timestamp: std::time::Instant,
 }
 
+/// In-flight go-to implementation / type definition / declaration request
+#[derive(Debug, Clone)]
+pub(crate) struct PendingGotoLocations {
+    pub request_id: u64,
+    pub kind: crate::services::lsp::async_handler::GotoKind,
+    /// Word under the cursor, used to title the results list
+    pub symbol: String,
+}
+
 /// Types of UI elements that can be hovered over
 #[derive(Debug, Clone, PartialEq)]
 pub enum HoverTarget {
@@ -995,6 +1004,8 @@ pub(super) struct MouseState {
     pub dragging_scrollbar: Option<LeafId>,
     /// Whether we're currently dragging a horizontal scrollbar
     pub dragging_horizontal_scrollbar: Option<LeafId>,
+    /// Whether we're currently dragging on a split's minimap
+    pub dragging_minimap: Option<LeafId>,
     /// Initial mouse column when starting horizontal scrollbar drag
     pub drag_start_hcol: Option<u16>,
     /// Initial left_column when starting horizontal scrollbar drag
@@ -1039,6 +1050,9 @@ pub(super) struct MouseState {
     pub drag_selection_by_words: bool,
     /// The end of the initially double-clicked word (used as anchor when dragging backward)
     pub drag_selection_word_end: Option<usize>,
+    /// Block anchor (line, display column) of an Alt+drag; dragging then
+    /// selects a rectangle instead of a text range
+    pub drag_block_anchor: Option<crate::model::cursor::Position2D>,
     /// Tab drag state (for drag-to-split functionality)
     pub dragging_tab: Option<TabDragState>,
     /// Whether we're currently dragging a popup scrollbar (popup index)
@@ -1216,6 +1230,14 @@ pub(crate) struct WindowLayoutCache {
     /// Horizontal scrollbar areas per split
     /// (split_id, buffer_id, horizontal_scrollbar_rect, max_content_width, thumb_start_col, thumb_end_col)
     pub horizontal_scrollbar_areas: Vec<(LeafId, BufferId, Rect, usize, usize, usize)>,
+    /// Minimap areas per split
+    /// (split_id, buffer_id, minimap_rect, first byte of each minimap row)
+    pub minimap_areas: Vec<(LeafId, BufferId, Rect, Vec<usize>)>,
+    /// Sticky scroll rows per split
+    /// (split_id, buffer_id, pinned_rows_rect, header byte of each pinned row)
+    pub sticky_scroll_areas: Vec<(LeafId, BufferId, Rect, Vec<usize>)>,
+    /// Breadcrumb bar segments per split
+    pub breadcrumb_layouts: HashMap<LeafId, crate::view::ui::breadcrumbs::BreadcrumbLayout>,
     /// Split separator positions for drag resize
     /// (container_id, direction, x, y, length)
     pub separator_areas: Vec<(ContainerId, SplitDirection, u16, u16, u16)>,
@@ -6,7 +6,7 @@ use rust_i18n::t;
 impl Editor {
     /// Handle Undo action - revert the last edit operation.
     pub fn handle_undo(&mut self) {
-        if self.active_window().is_editing_disabled() {
+        if self.active_window().is_editing_disabled() && !self.can_hex_edit() {
             self.set_status_message(t!("buffer.editing_disabled").to_string());
             return;
         }
@@ -48,7 +48,7 @@ impl Editor {
 
     /// Handle Redo action - reapply an undone edit operation.
     pub fn handle_redo(&mut self) {
-        if self.active_window().is_editing_disabled() {
+        if self.active_window().is_editing_disabled() && !self.can_hex_edit() {
             self.set_status_message(t!("buffer.editing_disabled").to_string());
             return;
         }
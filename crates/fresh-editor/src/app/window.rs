@@ -175,9 +175,45 @@ pub struct Window {
     /// Dabbrev cycling state (Alt+/ session).
     pub dabbrev_state: Option<crate::app::DabbrevCycleState>,
 
+    /// Snippet whose tabstops are being visited with Tab / Shift+Tab.
+    pub(crate) snippet_session: Option<crate::app::snippet_session::SnippetSession>,
+
     /// Pending LSP go-to-definition request id.
     pub pending_goto_definition_request: Option<u64>,
 
+    /// Pending LSP go-to implementation / type definition / declaration request.
+    pub(crate) pending_goto_locations_request: Option<crate::app::types::PendingGotoLocations>,
+
+    /// Pending LSP call/type hierarchy requests, mapped to the plugin
+    /// request each one resolves.
+    pub(crate) pending_hierarchy_requests: std::collections::HashMap<u64, u64>,
+
+    /// Pending LSP selection-range request for expand selection.
+    pub(crate) pending_selection_range_request:
+        Option<crate::app::selection_expansion::PendingSelectionRange>,
+
+    /// Expand/shrink selection history for this window's cursors.
+    pub(crate) selection_expansion: crate::app::selection_expansion::SelectionExpansionState,
+
+    /// Document outlines (tree-sitter and LSP `documentSymbol`) per buffer.
+    pub(crate) document_symbols: crate::app::document_symbols::DocumentSymbolState,
+
+    /// LSP code lenses per buffer, with their pending requests and debounce.
+    pub(crate) code_lens: crate::app::code_lens::CodeLensState,
+
+    /// Spell checker and the checked ranges of each buffer.
+    pub(crate) spell_check: crate::app::spell_check::SpellCheckState,
+
+    /// Breakpoints of closed files and the running debug session, if any.
+    pub(crate) debug: crate::app::debug::DebugState,
+
+    /// The running task and the problems of the last one.
+    pub(crate) tasks: crate::app::tasks::TaskState,
+
+    /// Running `%` quick-open `workspace/symbol` search, if any.
+    pub(crate) workspace_symbol_search:
+        Option<crate::app::workspace_symbols::WorkspaceSymbolSearch>,
+
     /// Pending LSP find-references request id and the symbol name.
     pub pending_references_request: Option<u64>,
     pub pending_references_symbol: String,
@@ -731,17 +767,18 @@ impl Window {
     }
 
     /// True if this window has any in-flight LSP completion or
-    /// goto-definition request whose response would still be relevant.
+    /// goto request whose response would still be relevant.
     pub fn has_pending_lsp_requests(&self) -> bool {
         !self.pending_completion_requests.is_empty()
             || self.pending_goto_definition_request.is_some()
+            || self.pending_goto_locations_request.is_some()
     }
 
     /// Cancel any in-flight LSP requests on this window. Called when
     /// the user does something that would make the response stale
     /// (cursor movement, text edit, scroll). Drains the pending
-    /// completion id set, clears the goto-definition slot, and sends
-    /// `$/cancelRequest` to the appropriate server for each.
+    /// completion id set, clears the goto and selection-range slots,
+    /// and sends `$/cancelRequest` to the appropriate server for each.
     pub(crate) fn cancel_pending_lsp_requests(&mut self) {
         self.scheduled_completion_trigger = None;
         if !self.pending_completion_requests.is_empty() {
@@ -758,6 +795,21 @@ impl Window {
             );
             self.send_lsp_cancel_request(request_id);
         }
+        if let Some(pending) = self.pending_goto_locations_request.take() {
+            tracing::debug!(
+                "Canceling pending LSP {} request {}",
+                pending.kind.method(),
+                pending.request_id
+            );
+            self.send_lsp_cancel_request(pending.request_id);
+        }
+        if let Some(pending) = self.pending_selection_range_request.take() {
+            tracing::debug!(
+                "Canceling pending LSP selection-range request {}",
+                pending.request_id
+            );
+            self.send_lsp_cancel_request(pending.request_id);
+        }
     }
 
     /// Send `$/cancelRequest` to the LSP server backing the active
@@ -1492,6 +1544,8 @@ impl Window {
         // (broke auto-save / auto-recovery tests after these fields
         // moved off `Editor`).
         let now = resources.time_source.now();
+        let mut terminal_manager = crate::services::terminal::TerminalManager::new();
+        terminal_manager.set_shell_integration_dir(resources.dir_context.shell_integration_dir());
         Self {
             id,
             label,
@@ -1504,7 +1558,7 @@ impl Window {
             splits: None,
             buffers: HashMap::new(),
             buffer_metadata: HashMap::new(),
-            terminal_manager: crate::services::terminal::TerminalManager::new(),
+            terminal_manager,
             terminal_buffers: HashMap::new(),
             terminal_backing_files: HashMap::new(),
             terminal_log_files: HashMap::new(),
@@ -1518,7 +1572,18 @@ impl Window {
             completion_items: None,
             scheduled_completion_trigger: None,
             dabbrev_state: None,
+            snippet_session: None,
             pending_goto_definition_request: None,
+            pending_goto_locations_request: None,
+            pending_hierarchy_requests: std::collections::HashMap::new(),
+            pending_selection_range_request: None,
+            selection_expansion: Default::default(),
+            document_symbols: Default::default(),
+            code_lens: Default::default(),
+            spell_check: Default::default(),
+            debug: Default::default(),
+            tasks: Default::default(),
+            workspace_symbol_search: None,
             pending_references_request: None,
             pending_references_symbol: String::new(),
             pending_signature_help_request: None,
@@ -1587,7 +1652,13 @@ impl Window {
             editor_mode: None,
             prompt_histories: HashMap::new(),
             pending_close_buffer: None,
-            completion_service: crate::services::completion::CompletionService::new(),
+            completion_service: {
+                let mut service = crate::services::completion::CompletionService::new();
+                service.register(Box::new(crate::services::completion::SnippetProvider::new(
+                    Arc::clone(&resources.snippet_library),
+                )));
+                service
+            },
             lsp_diagnostic_namespace: crate::view::overlay::OverlayNamespace::from_string(
                 "lsp-diagnostic".to_string(),
             ),
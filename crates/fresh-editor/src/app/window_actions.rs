@@ -28,6 +28,7 @@ impl crate::app::Editor {
             config: std::sync::Arc::clone(&self.config),
             grammar_registry: std::sync::Arc::clone(&self.grammar_registry),
             theme_registry: std::sync::Arc::clone(&self.theme_registry),
+            snippet_library: std::sync::Arc::clone(&self.snippet_library),
             theme_cache: std::sync::Arc::clone(&self.theme_cache),
             keybindings: std::sync::Arc::clone(&self.keybindings),
             command_registry: std::sync::Arc::clone(&self.command_registry),
@@ -49,6 +49,7 @@ use crate::input::keybindings::KeybindingResolver;
 use crate::model::filesystem::FileSystem;
 use crate::primitives::grammar::GrammarRegistry;
 use crate::services::authority::Authority;
+use crate::services::completion::SnippetLibrary;
 use crate::services::fs::FsManager;
 use crate::services::time_source::SharedTimeSource;
 use crate::view::theme::ThemeRegistry;
@@ -127,6 +128,10 @@ pub struct WindowResources {
     /// Tier-2 wrap).
     pub theme_registry: Arc<ThemeRegistry>,
 
+    /// User and package snippets, loaded at startup. Each window's
+    /// completion service offers them through a `SnippetProvider`.
+    pub snippet_library: Arc<SnippetLibrary>,
+
     /// Cache of plugin-supplied theme JSONs, populated by plugin
     /// commands and read by the theme loader.
     pub theme_cache: Arc<RwLock<HashMap<String, serde_json::Value>>>,
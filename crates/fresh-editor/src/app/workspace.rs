@@ -484,7 +484,8 @@ impl Editor {
     ///
     /// Ensures all active terminals have their visible screen synced to
     /// backing files before capturing the workspace.
-    /// Also saves global file states (scroll/cursor positions per file).
+    /// Also saves global file states (scroll/cursor positions per file) and,
+    /// when enabled, the undo histories of open files.
     pub fn save_workspace(&mut self) -> Result<(), WorkspaceError> {
         // Ensure all terminal backing files have complete state before saving
         self.sync_all_terminal_backing_files();
@@ -492,6 +493,9 @@ impl Editor {
         // Save global file states for all open file buffers
         self.save_all_global_file_states();
 
+        // Save undo histories of unmodified file buffers (if enabled)
+        self.save_all_undo_histories();
+
         let workspace = self.capture_workspace();
 
         // For named sessions, save to session-scoped workspace file
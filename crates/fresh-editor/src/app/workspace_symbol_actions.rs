@@ -0,0 +1,151 @@
+//! Workspace symbol search orchestrators.
+//!
+//! The `%` quick-open provider searches every running server that supports
+//! `workspace/symbol`. Each keystroke cancels the outstanding requests of
+//! the previous query and sends the new one to all servers under a single
+//! request id; answers are merged into `Window::workspace_symbol_search`
+//! as they arrive and the quick-open list is rebuilt after each one.
+
+use crate::app::workspace_symbols::WorkspaceSymbolSearch;
+use crate::primitives::workspace_symbols::workspace_symbol_entries;
+use crate::types::LspFeature;
+use crate::view::prompt::PromptType;
+
+use super::Editor;
+
+impl Editor {
+    /// Start searching the workspace for `query`, replacing (and
+    /// cancelling) any search for a different query.
+    pub(crate) fn search_workspace_symbols(&mut self, query: &str) {
+        if self
+            .active_window()
+            .workspace_symbol_search
+            .as_ref()
+            .is_some_and(|search| search.query == query)
+        {
+            return;
+        }
+        self.cancel_workspace_symbol_search();
+        if query.is_empty() {
+            return;
+        }
+
+        let request_id = self.active_window_mut().next_lsp_request_id;
+        let Some(lsp) = self.lsp() else {
+            return;
+        };
+        let mut waiting = Vec::new();
+        for sh in lsp.all_handles_for_feature(LspFeature::WorkspaceSymbols) {
+            match sh.handle.workspace_symbols(request_id, query.to_string()) {
+                Ok(()) => waiting.push(sh.name.clone()),
+                Err(e) => tracing::warn!(
+                    "Failed to request workspace symbols from {}: {}",
+                    sh.name,
+                    e
+                ),
+            }
+        }
+        if waiting.is_empty() {
+            return;
+        }
+        tracing::info!(
+            "Requested workspace symbols for {:?} from {} server(s)",
+            query,
+            waiting.len()
+        );
+
+        let window = self.active_window_mut();
+        window.next_lsp_request_id += 1;
+        window.workspace_symbol_search = Some(WorkspaceSymbolSearch::new(
+            request_id,
+            query.to_string(),
+            waiting,
+        ));
+    }
+
+    /// Drop the current search, cancelling requests still in flight.
+    pub(crate) fn cancel_workspace_symbol_search(&mut self) {
+        let Some(search) = self.active_window_mut().workspace_symbol_search.take() else {
+            return;
+        };
+        let Some(lsp) = self.lsp() else {
+            return;
+        };
+        for server_name in &search.waiting {
+            if let Some(sh) = lsp.handle_named(server_name) {
+                if let Err(e) = sh.handle.cancel_request(search.request_id) {
+                    tracing::debug!(
+                        "Failed to cancel workspace symbol request on {}: {}",
+                        server_name,
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    /// Handle one server's `workspace/symbol` answer.
+    pub(crate) fn handle_workspace_symbols_response(
+        &mut self,
+        request_id: u64,
+        server_name: &str,
+        symbols: Option<lsp_types::WorkspaceSymbolResponse>,
+    ) {
+        if self
+            .active_window()
+            .workspace_symbol_search
+            .as_ref()
+            .is_none_or(|search| search.request_id != request_id)
+        {
+            tracing::debug!(
+                "Ignoring stale workspace symbol response {} from {}",
+                request_id,
+                server_name
+            );
+            return;
+        }
+
+        let translation = self.authority.path_translation.clone();
+        let working_dir = self.working_dir.clone();
+        let entries = symbols
+            .map(|response| {
+                workspace_symbol_entries(response, |uri| {
+                    crate::app::types::LspUri::from_wire(uri.clone())
+                        .to_host_path(translation.as_ref())
+                        .map(|path| {
+                            path.strip_prefix(&working_dir)
+                                .unwrap_or(&path)
+                                .display()
+                                .to_string()
+                        })
+                        .unwrap_or_else(|| uri.as_str().to_string())
+                })
+            })
+            .unwrap_or_default();
+
+        let Some(search) = self.active_window_mut().workspace_symbol_search.as_mut() else {
+            return;
+        };
+        let added = search.add_results(server_name, entries);
+        // The last answer also ends the "searching" state, even if it was empty
+        if added || !search.is_pending() {
+            self.refresh_quick_open_workspace_symbols();
+        }
+    }
+
+    /// Rebuild the `%` quick-open list if it is showing.
+    fn refresh_quick_open_workspace_symbols(&mut self) {
+        let Some(input) = self
+            .active_window()
+            .prompt
+            .as_ref()
+            .filter(|prompt| prompt.prompt_type == PromptType::QuickOpen)
+            .map(|prompt| prompt.input.clone())
+        else {
+            return;
+        };
+        if self.quick_open_shows_workspace_symbols(&input) {
+            self.update_quick_open_suggestions(&input);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! State of the running `%` workspace symbol search.
+//!
+//! One query is in flight at a time. It is sent to every server that
+//! supports `workspace/symbol` under a single request id; answers are
+//! merged as they arrive, dropping symbols another server already
+//! reported. A new query replaces the old one, whose outstanding requests
+//! the caller cancels.
+//!
+//! Sending, cancelling and consuming the results lives on `Editor` in
+//! `workspace_symbol_actions.rs`.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::primitives::workspace_symbols::WorkspaceSymbolEntry;
+
+#[derive(Debug, Clone)]
+pub(crate) struct WorkspaceSymbolSearch {
+    pub request_id: u64,
+    pub query: String,
+    /// Servers that have not answered yet
+    pub waiting: Vec<String>,
+    /// Merged answers, in arrival order
+    pub entries: Arc<Vec<WorkspaceSymbolEntry>>,
+    seen: HashSet<(String, String, u32)>,
+}
+
+impl WorkspaceSymbolSearch {
+    pub(crate) fn new(request_id: u64, query: String, waiting: Vec<String>) -> Self {
+        Self {
+            request_id,
+            query,
+            waiting,
+            entries: Arc::default(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Whether some server has yet to answer.
+    pub(crate) fn is_pending(&self) -> bool {
+        !self.waiting.is_empty()
+    }
+
+    /// Record the answer of `server_name`, keeping only symbols not seen
+    /// before. Returns whether any symbol was added.
+    pub(crate) fn add_results(
+        &mut self,
+        server_name: &str,
+        results: Vec<WorkspaceSymbolEntry>,
+    ) -> bool {
+        self.waiting.retain(|name| name != server_name);
+        let fresh: Vec<WorkspaceSymbolEntry> = results
+            .into_iter()
+            .filter(|entry| self.seen.insert(entry.dedup_key()))
+            .collect();
+        if fresh.is_empty() {
+            return false;
+        }
+        Arc::make_mut(&mut self.entries).extend(fresh);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{Location, Position, Range, SymbolKind};
+
+    fn entry(name: &str, path: &str, line: u32) -> WorkspaceSymbolEntry {
+        WorkspaceSymbolEntry {
+            name: name.to_string(),
+            kind: SymbolKind::STRUCT,
+            container: None,
+            location: Location {
+                uri: format!("file:///repo/{}", path).parse().unwrap(),
+                range: Range::new(Position::new(line, 0), Position::new(line, 5)),
+            },
+            display_path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn merges_servers_and_drops_duplicates() {
+        let mut search = WorkspaceSymbolSearch::new(
+            4,
+            "conf".to_string(),
+            vec!["rust-analyzer".to_string(), "ctags".to_string()],
+        );
+        assert!(search.is_pending());
+
+        assert!(search.add_results(
+            "rust-analyzer",
+            vec![entry("Config", "a.rs", 1), entry("ConfigError", "a.rs", 9)],
+        ));
+        assert!(search.is_pending());
+
+        assert!(search.add_results(
+            "ctags",
+            vec![entry("Config", "a.rs", 1), entry("Config", "b.rs", 1)],
+        ));
+        assert!(!search.is_pending());
+
+        let names: Vec<(&str, &str)> = search
+            .entries
+            .iter()
+            .map(|e| (e.name.as_str(), e.display_path.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("Config", "a.rs"), ("ConfigError", "a.rs"), ("Config", "b.rs")]
+        );
+    }
+
+    #[test]
+    fn empty_or_duplicate_answer_only_marks_server_done() {
+        let mut search =
+            WorkspaceSymbolSearch::new(1, "x".to_string(), vec!["pyright".to_string()]);
+        assert!(!search.add_results("pyright", Vec::new()));
+        assert!(!search.is_pending());
+        assert!(search.entries.is_empty());
+    }
+}
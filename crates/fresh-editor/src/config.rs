@@ -414,6 +414,16 @@ pub struct Config {
     /// Package manager settings for plugin/theme installation
     #[serde(default)]
     pub packages: PackagesConfig,
+
+    /// Debugger settings (debug adapters and launch/attach configurations)
+    #[serde(default)]
+    pub debug: DebugConfig,
+
+    /// Tasks offered by "Run Task", by name (e.g. "cargo build").
+    /// Their output is parsed by a problem matcher into the quickfix list
+    /// and gutter diagnostics.
+    #[serde(default)]
+    pub tasks: HashMap<String, TaskConfig>,
 }
 
 fn default_keybinding_map_name() -> KeybindingMapName {
@@ -872,6 +882,57 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub show_horizontal_scrollbar: bool,
 
+    /// Whether a minimap is shown at the right edge of each split pane.
+    /// The minimap is a condensed, syntax-coloured view of the buffer with
+    /// markers for diagnostics, search matches and git changes; click or
+    /// drag on it to scroll. Large files are sampled rather than scanned.
+    /// Can be toggled at runtime via command palette or keybinding.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_minimap: bool,
+
+    /// Width of the minimap in terminal columns.
+    /// The minimap is hidden in splits too narrow to fit it.
+    /// Default: 10
+    #[serde(default = "default_minimap_width")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub minimap_width: u16,
+
+    /// Whether the header lines of the scopes enclosing the top of a split
+    /// (functions, classes, impls, modules, Markdown headings) stay pinned
+    /// above its text while scrolling. Click a pinned line to jump to it.
+    /// Can be toggled at runtime via command palette or keybinding.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub sticky_scroll: bool,
+
+    /// Maximum number of header lines pinned by sticky scroll.
+    /// At most half of the split is ever covered.
+    /// Default: 5
+    #[serde(default = "default_sticky_scroll_max_lines")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub sticky_scroll_max_lines: usize,
+
+    /// Whether each split shows a breadcrumb bar below its tabs with the
+    /// file path and the symbols enclosing the cursor (`src › main.rs ›
+    /// impl Foo › fn bar`). Click a segment, or use "Focus Breadcrumbs",
+    /// to pick a sibling file or symbol from a dropdown.
+    /// Can be toggled at runtime via command palette or keybinding.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_breadcrumbs: bool,
+
+    /// Whether vertical guides are drawn at each indentation level, with
+    /// the guide of the block holding the cursor highlighted.
+    /// Can be toggled at runtime via command palette or keybinding.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_indent_guides: bool,
+
     /// Show tilde (~) markers on lines after the end of the file.
     /// These vim-style markers indicate lines that are not part of the file content.
     /// Default: true
@@ -1024,6 +1085,23 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Editing"))]
     pub ensure_final_newline_on_save: bool,
 
+    /// Underline misspelled words in comments and strings, and throughout
+    /// Markdown, plain text and commit message files.
+    /// Can be toggled at runtime via command palette or keybinding.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub spell_check: bool,
+
+    /// Hunspell dictionary used for spell checking, such as "en_US" or
+    /// "de_DE". Its `.aff` and `.dic` files are looked up in the
+    /// `dictionaries` directory of the config directory, then in the
+    /// system's Hunspell directories.
+    /// Default: "en_US"
+    #[serde(default = "default_spell_check_language")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub spell_check_language: String,
+
     // ===== Bracket Matching =====
     /// Highlight matching bracket pairs when cursor is on a bracket.
     /// Default: true
@@ -1079,6 +1157,12 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "LSP"))]
     pub enable_inlay_hints: bool,
 
+    /// Whether to show LSP code lenses (e.g. "Run test | Debug", reference
+    /// counts) as clickable lines above the code they belong to.
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "LSP"))]
+    pub enable_code_lens: bool,
+
     /// Whether to request full-document LSP semantic tokens.
     /// Range requests are still used when supported.
     /// Default: false (range-only to avoid heavy full refreshes).
@@ -1148,6 +1232,23 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Recovery"))]
     pub hot_exit: bool,
 
+    /// Whether to keep the undo history of files across editor sessions.
+    /// The history of a file is stored when its buffer is closed or the
+    /// editor exits, and restored when the file is opened again.  It is
+    /// discarded if the file was changed outside the editor in between.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub persistent_undo: bool,
+
+    /// Maximum size in KiB of the stored undo history of one file.
+    /// When the history is larger, its oldest edits are dropped.
+    /// Only effective when persistent_undo is true.
+    /// Default: 1024 KiB
+    #[serde(default = "default_persistent_undo_max_kb")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub persistent_undo_max_kb: u32,
+
     /// Whether to auto-open previously opened files (session restore) when
     /// starting Fresh in a directory.  When enabled (the default), tabs,
     /// splits, cursor positions and the file explorer state are restored
@@ -1296,6 +1397,31 @@ pub struct EditorConfig {
     #[serde(default = "default_file_tree_poll_interval")]
     #[schemars(extend("x-section" = "Performance"))]
     pub file_tree_poll_interval_ms: u64,
+
+    /// Maximum time in milliseconds a plugin's hook handler, action or
+    /// callback may run before the plugin is disabled.
+    /// Takes effect when the editor restarts.
+    /// Default: 2000ms
+    #[serde(default = "default_plugin_call_timeout")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub plugin_call_timeout_ms: u64,
+
+    /// Maximum time in milliseconds a plugin's top-level code may run
+    /// while it loads before the plugin is disabled.
+    /// Takes effect when the editor restarts.
+    /// Default: 10000ms
+    #[serde(default = "default_plugin_load_timeout")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub plugin_load_timeout_ms: u64,
+
+    /// Memory limit in megabytes of the JavaScript runtime that all
+    /// plugins share. A plugin that is running when the limit is reached
+    /// is disabled, even if other plugins hold most of the memory.
+    /// Takes effect when the editor restarts.
+    /// Default: 512
+    #[serde(default = "default_plugin_memory_limit_mb")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub plugin_memory_limit_mb: usize,
 }
 
 fn default_tab_size() -> usize {
@@ -1343,6 +1469,18 @@ fn default_scroll_offset() -> usize {
     3
 }
 
+fn default_minimap_width() -> u16 {
+    10
+}
+
+fn default_sticky_scroll_max_lines() -> usize {
+    5
+}
+
+fn default_spell_check_language() -> String {
+    "en_US".to_string()
+}
+
 fn default_highlight_timeout() -> u64 {
     5
 }
@@ -1359,6 +1497,10 @@ fn default_auto_save_interval() -> u32 {
     30 // 30 seconds between persistent auto-saves
 }
 
+fn default_persistent_undo_max_kb() -> u32 {
+    1024
+}
+
 fn default_auto_recovery_save_interval() -> u32 {
     2 // 2 seconds between recovery saves
 }
@@ -1387,6 +1529,18 @@ fn default_file_tree_poll_interval() -> u64 {
     3000 // 3 seconds between directory mtime checks
 }
 
+fn default_plugin_call_timeout() -> u64 {
+    2000
+}
+
+fn default_plugin_load_timeout() -> u64 {
+    10_000
+}
+
+fn default_plugin_memory_limit_mb() -> usize {
+    512
+}
+
 impl Default for EditorConfig {
     fn default() -> Self {
         Self {
@@ -1412,11 +1566,14 @@ impl Default for EditorConfig {
             large_file_threshold_bytes: default_large_file_threshold(),
             estimated_line_length: default_estimated_line_length(),
             enable_inlay_hints: true,
+            enable_code_lens: true,
             enable_semantic_tokens_full: false,
             diagnostics_inline_text: false,
             auto_save_enabled: false,
             auto_save_interval_secs: default_auto_save_interval(),
             hot_exit: true,
+            persistent_undo: false,
+            persistent_undo_max_kb: default_persistent_undo_max_kb(),
             restore_previous_session: true,
             skip_session_restore_when_files_passed: true,
             auto_create_empty_buffer_on_last_buffer_close: true,
@@ -1429,9 +1586,14 @@ impl Default for EditorConfig {
             auto_revert_poll_interval_ms: default_auto_revert_poll_interval(),
             read_concurrency: default_read_concurrency(),
             file_tree_poll_interval_ms: default_file_tree_poll_interval(),
+            plugin_call_timeout_ms: default_plugin_call_timeout(),
+            plugin_load_timeout_ms: default_plugin_load_timeout(),
+            plugin_memory_limit_mb: default_plugin_memory_limit_mb(),
             default_line_ending: LineEndingOption::default(),
             trim_trailing_whitespace_on_save: false,
             ensure_final_newline_on_save: false,
+            spell_check: false,
+            spell_check_language: default_spell_check_language(),
             highlight_matching_brackets: true,
             rainbow_brackets: true,
             cursor_style: CursorStyle::default(),
@@ -1451,6 +1613,12 @@ impl Default for EditorConfig {
             show_prompt_line: false,
             show_vertical_scrollbar: true,
             show_horizontal_scrollbar: false,
+            show_minimap: false,
+            minimap_width: default_minimap_width(),
+            sticky_scroll: false,
+            sticky_scroll_max_lines: default_sticky_scroll_max_lines(),
+            show_breadcrumbs: false,
+            show_indent_guides: false,
             show_tilde: true,
             use_terminal_bg: false,
             set_window_title: true,
@@ -1873,6 +2041,146 @@ impl Default for PackagesConfig {
     }
 }
 
+/// Debugger configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct DebugConfig {
+    /// Debug adapters by name (e.g. "lldb", "debugpy").
+    /// Referenced from `configurations` by that name.
+    #[serde(default)]
+    pub adapters: HashMap<String, DebugAdapterConfig>,
+
+    /// Launch/attach configurations offered by "Debug: Start"
+    #[serde(default)]
+    pub configurations: Vec<DebugConfiguration>,
+}
+
+impl DebugConfig {
+    /// Whether an adapter is configured for `language`, i.e. whether
+    /// breakpoints make sense in buffers of that language.
+    pub fn has_adapter_for_language(&self, language: &str) -> bool {
+        self.adapters
+            .values()
+            .any(|a| a.languages.iter().any(|l| l == language))
+    }
+}
+
+/// Debug adapter process configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DebugAdapterConfig {
+    /// Command to spawn the adapter (it must speak DAP over stdio)
+    pub command: String,
+
+    /// Arguments to pass to the adapter
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Environment variables to set for the adapter process
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+
+    /// Languages whose buffers get breakpoints in the gutter for this adapter
+    #[serde(default)]
+    pub languages: Vec<String>,
+}
+
+/// A named way to start a debug session
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/name"))]
+pub struct DebugConfiguration {
+    /// Name shown when picking a configuration
+    pub name: String,
+
+    /// Key of the adapter in `debug.adapters`
+    pub adapter: String,
+
+    /// Whether to start the program or attach to a running one
+    #[serde(default)]
+    pub request: DebugRequestKind,
+
+    /// Adapter-specific arguments of the launch/attach request
+    /// (e.g. `program`, `args`, `cwd`, `pid`).
+    /// `${workspaceFolder}` and `${file}` are substituted in string values.
+    #[serde(default)]
+    pub arguments: HashMap<String, serde_json::Value>,
+}
+
+/// Debug session request kind
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DebugRequestKind {
+    #[default]
+    Launch,
+    Attach,
+}
+
+impl DebugRequestKind {
+    /// The DAP command that starts the session
+    pub fn command(self) -> &'static str {
+        match self {
+            Self::Launch => "launch",
+            Self::Attach => "attach",
+        }
+    }
+}
+
+/// A command run by "Run Task"
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/command"))]
+pub struct TaskConfig {
+    /// Command to run (through the active authority, so it runs wherever
+    /// the project lives)
+    pub command: String,
+
+    /// Arguments to pass to the command
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Working directory; relative paths are resolved against the project
+    /// root, which is also the default
+    #[serde(default)]
+    pub cwd: Option<String>,
+
+    /// Environment variables to set for the command
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+
+    /// How to find errors and warnings in the output.
+    /// Without one the task only reports its exit code.
+    #[serde(default)]
+    pub problem_matcher: Option<ProblemMatcherKind>,
+
+    /// Languages whose buffers offer this task (empty: all buffers)
+    #[serde(default)]
+    pub languages: Vec<String>,
+
+    /// Whether the task is offered at all
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+impl TaskConfig {
+    /// Whether the task is offered in a buffer of `language`
+    pub fn applies_to(&self, language: &str) -> bool {
+        self.enabled && (self.languages.is_empty() || self.languages.iter().any(|l| l == language))
+    }
+}
+
+/// Output format a task's problem matcher understands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ProblemMatcherKind {
+    /// rustc and cargo (`error[E0308]: ...` followed by ` --> file:line:col`)
+    Rustc,
+    /// gcc and clang (`file:line:col: error: ...`)
+    Gcc,
+    /// TypeScript compiler (`file(line,col): error TS2322: ...`)
+    Tsc,
+    /// ESLint's default "stylish" formatter
+    Eslint,
+    /// pytest tracebacks (`file.py:line: AssertionError`)
+    Pytest,
+}
+
 // Re-export PluginConfig from fresh-core for shared type usage
 pub use fresh_core::config::PluginConfig;
 
@@ -2407,6 +2715,8 @@ impl Default for Config {
             warnings: WarningsConfig::default(),
             plugins: HashMap::new(), // Populated when scanning for plugins
             packages: PackagesConfig::default(),
+            debug: DebugConfig::default(),
+            tasks: Self::default_tasks(),
         }
     }
 }
@@ -2658,6 +2968,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: Some(context_keys::LINE_WRAP.to_string()),
                     },
+                    MenuItem::Action {
+                        label: t!("menu.view.hex_view").to_string(),
+                        action: "toggle_hex_view".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: Some(context_keys::HEX_VIEW.to_string()),
+                    },
                     MenuItem::Action {
                         label: t!("menu.view.mouse_support").to_string(),
                         action: "toggle_mouse_capture".to_string(),
@@ -2680,6 +2997,41 @@ impl MenuConfig {
                         when: None,
                         checkbox: Some(context_keys::HORIZONTAL_SCROLLBAR.to_string()),
                     },
+                    MenuItem::Action {
+                        label: t!("menu.view.minimap").to_string(),
+                        action: "toggle_minimap".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: Some(context_keys::MINIMAP.to_string()),
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.view.sticky_scroll").to_string(),
+                        action: "toggle_sticky_scroll".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: Some(context_keys::STICKY_SCROLL.to_string()),
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.view.breadcrumbs").to_string(),
+                        action: "toggle_breadcrumbs".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: Some(context_keys::BREADCRUMBS.to_string()),
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.view.indent_guides").to_string(),
+                        action: "toggle_indent_guides".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: Some(context_keys::INDENT_GUIDES.to_string()),
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.view.spell_check").to_string(),
+                        action: "toggle_spell_check".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: Some(context_keys::SPELL_CHECK.to_string()),
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.view.set_background").to_string(),
@@ -2895,6 +3247,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.selection.shrink_selection").to_string(),
+                        action: "shrink_selection".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.selection.add_cursor_above").to_string(),
@@ -2998,6 +3357,27 @@ impl MenuConfig {
                         when: Some(context_keys::LSP_AVAILABLE.to_string()),
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.lsp.goto_implementation").to_string(),
+                        action: "lsp_goto_implementation".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::LSP_AVAILABLE.to_string()),
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.lsp.goto_type_definition").to_string(),
+                        action: "lsp_goto_type_definition".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::LSP_AVAILABLE.to_string()),
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.lsp.goto_declaration").to_string(),
+                        action: "lsp_goto_declaration".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::LSP_AVAILABLE.to_string()),
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: t!("menu.lsp.find_references").to_string(),
                         action: "lsp_references".to_string(),
@@ -5357,6 +5737,56 @@ impl Config {
         HashMap::new()
     }
 
+    /// Create the default tasks: a build or check command per language,
+    /// each with the matching problem matcher.
+    fn default_tasks() -> HashMap<String, TaskConfig> {
+        let task = |command: &str, args: &[&str], matcher, languages: &[&str]| TaskConfig {
+            command: command.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            cwd: None,
+            env: HashMap::new(),
+            problem_matcher: Some(matcher),
+            languages: languages.iter().map(|l| l.to_string()).collect(),
+            enabled: true,
+        };
+        HashMap::from([
+            (
+                "cargo build".to_string(),
+                task("cargo", &["build"], ProblemMatcherKind::Rustc, &["rust"]),
+            ),
+            (
+                "cargo test".to_string(),
+                task("cargo", &["test"], ProblemMatcherKind::Rustc, &["rust"]),
+            ),
+            (
+                "make".to_string(),
+                task("make", &[], ProblemMatcherKind::Gcc, &["c", "cpp"]),
+            ),
+            (
+                "tsc".to_string(),
+                task(
+                    "npx",
+                    &["tsc", "--noEmit", "--pretty", "false"],
+                    ProblemMatcherKind::Tsc,
+                    &["typescript"],
+                ),
+            ),
+            (
+                "eslint".to_string(),
+                task(
+                    "npx",
+                    &["eslint", "."],
+                    ProblemMatcherKind::Eslint,
+                    &["javascript", "typescript"],
+                ),
+            ),
+            (
+                "pytest".to_string(),
+                task("pytest", &[], ProblemMatcherKind::Pytest, &["python"]),
+            ),
+        ])
+    }
+
     #[cfg(feature = "runtime")]
     fn populate_lsp_config(lsp: &mut HashMap<String, LspLanguageConfig>, ra_log_path: String) {
         // rust-analyzer: full mode by default (no init param restrictions, no process limits).
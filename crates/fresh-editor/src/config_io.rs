@@ -934,6 +934,11 @@ impl DirectoryContext {
         self.data_dir.join("workspaces")
     }
 
+    /// Get the persisted undo histories directory path
+    pub fn undo_dir(&self) -> std::path::PathBuf {
+        self.data_dir.join("undo")
+    }
+
     /// Get the history file path for a specific prompt type
     /// This is the generic method used by prompt_histories HashMap.
     /// history_name can be: "search", "replace", "goto_line", "plugin:custom_name", etc.
@@ -963,6 +968,11 @@ impl DirectoryContext {
         self.data_dir.join("terminals")
     }
 
+    /// Get the directory the terminal shell integration scripts are installed in
+    pub fn shell_integration_dir(&self) -> std::path::PathBuf {
+        self.data_dir.join("shell-integration")
+    }
+
     /// Get the terminal directory for a specific working directory
     pub fn terminal_dir_for(&self, working_dir: &std::path::Path) -> std::path::PathBuf {
         let encoded = crate::workspace::encode_path_for_filename(working_dir);
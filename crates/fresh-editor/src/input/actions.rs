@@ -2,8 +2,10 @@
 
 use crate::input::keybindings::Action;
 use crate::input::line_move::{move_lines, LineMoveDirection};
+use crate::model::block_selection::{
+    block_cursor_position, block_position_to_byte, line_text, next_column, prev_column, BlockRect,
+};
 use crate::model::buffer::{Buffer, LineEnding};
-use crate::model::buffer_position::{byte_to_2d, pos_2d_to_byte};
 use crate::model::cursor::{Cursor, Cursors, Position2D, SelectionMode};
 use crate::model::event::{CursorId, Event};
 use crate::primitives::display_width::{byte_offset_at_visual_column, str_width};
@@ -14,6 +16,7 @@ use crate::primitives::word_navigation::{
     find_word_start_right,
 };
 use crate::state::EditorState;
+use std::collections::HashMap;
 use std::ops::Range;
 
 /// Direction for block selection movement
@@ -234,11 +237,17 @@ fn select_each_cursor(
 }
 
 /// Handle block selection movement
+///
+/// Block columns are display columns: Left/Right step over whole tabs and
+/// wide characters, and Right continues into virtual space past the end of
+/// the line. The byte position is clamped to the line while the display
+/// column is kept in `sticky_column`.
 fn block_select_action(
     state: &mut EditorState,
     cursors: &mut Cursors,
     events: &mut Vec<Event>,
     direction: BlockDirection,
+    tab_size: usize,
 ) {
     // Get line count for bounds checking
     let total_lines = {
@@ -251,35 +260,32 @@ fn block_select_action(
     };
 
     for (cursor_id, cursor) in cursors.iter() {
-        let current_2d = byte_to_2d(&state.buffer, cursor.position);
+        let current_2d = block_cursor_position(&state.buffer, cursor, tab_size);
 
         // If not in block mode, start block selection
-        let block_anchor =
-            if cursor.selection_mode != SelectionMode::Block || cursor.block_anchor.is_none() {
-                current_2d
-            } else {
-                cursor.block_anchor.unwrap()
-            };
+        let block_anchor = match cursor.block_anchor {
+            Some(anchor) if cursor.has_block_selection() => anchor,
+            _ => current_2d,
+        };
 
         // Calculate new 2D position based on direction
         let new_2d = match direction {
             BlockDirection::Left => Position2D {
                 line: current_2d.line,
-                column: current_2d.column.saturating_sub(1),
+                column: prev_column(
+                    &line_text(&state.buffer, current_2d.line),
+                    current_2d.column,
+                    tab_size,
+                ),
+            },
+            BlockDirection::Right => Position2D {
+                line: current_2d.line,
+                column: next_column(
+                    &line_text(&state.buffer, current_2d.line),
+                    current_2d.column,
+                    tab_size,
+                ),
             },
-            BlockDirection::Right => {
-                // Get current line length to bound the column
-                let line_content = state.buffer.get_line(current_2d.line).unwrap_or_default();
-                let line_len = if line_content.last() == Some(&b'\n') {
-                    line_content.len().saturating_sub(1)
-                } else {
-                    line_content.len()
-                };
-                Position2D {
-                    line: current_2d.line,
-                    column: (current_2d.column + 1).min(line_len),
-                }
-            }
             BlockDirection::Up => {
                 if current_2d.line > 0 {
                     Position2D {
@@ -303,10 +309,10 @@ fn block_select_action(
         };
 
         // Convert new 2D position back to byte offset
-        let new_byte_pos = pos_2d_to_byte(&state.buffer, new_2d);
+        let new_byte_pos = block_position_to_byte(&state.buffer, new_2d, tab_size);
 
         // Store the byte anchor for the event system (for undo/redo compatibility)
-        let byte_anchor = pos_2d_to_byte(&state.buffer, block_anchor);
+        let byte_anchor = block_position_to_byte(&state.buffer, block_anchor, tab_size);
 
         events.push(Event::MoveCursor {
             cursor_id,
@@ -328,8 +334,8 @@ fn block_select_action(
     // This way the events will move the cursor, but the anchor remains fixed
     let buffer_ref = &state.buffer;
     cursors.map(|cursor| {
-        if cursor.selection_mode != SelectionMode::Block || cursor.block_anchor.is_none() {
-            let current_2d = byte_to_2d(buffer_ref, cursor.position);
+        if !cursor.has_block_selection() {
+            let current_2d = block_cursor_position(buffer_ref, cursor, tab_size);
             cursor.start_block_selection(current_2d.line, current_2d.column);
         }
     });
@@ -348,90 +354,111 @@ pub fn clear_block_selection_if_active(cursors: &mut Cursors) {
 /// Convert block selection to multiple cursors with normal selections.
 /// Each cursor will have a selection covering that line's portion of the block.
 /// This should be called before action processing so normal multi-cursor logic applies.
-/// Returns events to add the new cursors (if any).
-fn convert_block_selection_to_cursors(
+///
+/// Returns events to add the new cursors (if any), and the cursors on lines
+/// that end before the block's left edge, with the number of spaces needed
+/// to reach it (see [`pad_virtual_space`]).
+pub(crate) fn convert_block_selection_to_cursors(
     state: &mut EditorState,
     cursors: &mut Cursors,
-) -> Vec<Event> {
+    tab_size: usize,
+) -> (Vec<Event>, HashMap<CursorId, usize>) {
     let mut events = Vec::new();
+    let mut padding = HashMap::new();
 
     // Check if any cursor has a block selection
-    let block_info: Option<(CursorId, Position2D, Position2D)> =
+    let block_info: Option<(CursorId, BlockRect)> =
         cursors.iter().find_map(|(cursor_id, cursor)| {
-            if cursor.has_block_selection() {
-                let block_anchor = cursor.block_anchor?;
-                let cursor_2d = byte_to_2d(&state.buffer, cursor.position);
-                Some((cursor_id, block_anchor, cursor_2d))
-            } else {
-                None
-            }
+            BlockRect::from_cursor(&state.buffer, cursor, tab_size).map(|rect| (cursor_id, rect))
         });
 
-    let Some((primary_cursor_id, block_anchor, cursor_2d)) = block_info else {
-        return events;
+    let Some((primary_cursor_id, rect)) = block_info else {
+        return (events, padding);
     };
 
-    // Calculate block rectangle bounds
-    let min_line = block_anchor.line.min(cursor_2d.line);
-    let max_line = block_anchor.line.max(cursor_2d.line);
-    let min_col = block_anchor.column.min(cursor_2d.column);
-    let max_col = block_anchor.column.max(cursor_2d.column);
-
-    // Calculate cursor positions for each line
-    let mut cursor_positions: Vec<(usize, usize)> = Vec::new(); // (position, anchor)
-
-    for line in min_line..=max_line {
-        let line_start = state.buffer.line_start_offset(line).unwrap_or(0);
-        let line_content = state.buffer.get_line(line).unwrap_or_default();
-
-        // Calculate line length excluding newline
-        let line_len = if line_content.last() == Some(&b'\n') {
-            line_content.len().saturating_sub(1)
-        } else {
-            line_content.len()
-        };
-
-        // Clamp columns to actual line length
-        let actual_min_col = min_col.min(line_len);
-        let actual_max_col = max_col.min(line_len);
-
-        let anchor = line_start + actual_min_col;
-        let position = line_start + actual_max_col;
-
-        cursor_positions.push((position, anchor));
-    }
+    // Calculate cursor positions for each line: (position, anchor, padding)
+    let spans: Vec<_> = rect
+        .lines()
+        .map(|line| rect.span(&state.buffer, line, tab_size))
+        .collect();
 
     // Update the primary cursor to have a normal selection on the first line
-    if let Some((position, anchor)) = cursor_positions.first().copied() {
+    if let Some(span) = spans.first() {
         if let Some(cursor) = cursors.get_mut(primary_cursor_id) {
-            cursor.position = position;
-            cursor.anchor = if position != anchor {
-                Some(anchor)
-            } else {
+            cursor.position = span.range.end;
+            cursor.anchor = if span.range.is_empty() {
                 None
+            } else {
+                Some(span.range.start)
             };
             cursor.clear_block_selection();
         }
+        if span.padding > 0 {
+            padding.insert(primary_cursor_id, span.padding);
+        }
     }
 
     // Add new cursors for remaining lines
     let mut next_cursor_id = cursors.count();
-    for (position, anchor) in cursor_positions.into_iter().skip(1) {
+    for span in spans.into_iter().skip(1) {
         let cursor_id = CursorId(next_cursor_id);
         next_cursor_id += 1;
 
         events.push(Event::AddCursor {
             cursor_id,
-            position,
-            anchor: if position != anchor {
-                Some(anchor)
-            } else {
+            position: span.range.end,
+            anchor: if span.range.is_empty() {
                 None
+            } else {
+                Some(span.range.start)
             },
         });
+        if span.padding > 0 {
+            padding.insert(cursor_id, span.padding);
+        }
     }
 
-    events
+    (events, padding)
+}
+
+/// Pad text inserted by cursors in virtual space so it starts at the
+/// block's left edge: prefix each such cursor's first insertion with
+/// spaces and shift its explicit cursor moves past them.
+pub(crate) fn pad_virtual_space(events: &mut [Event], padding: &HashMap<CursorId, usize>) {
+    for (&padded_cursor, &width) in padding {
+        let Some(insert_position) = events.iter_mut().find_map(|event| match event {
+            Event::Insert {
+                position,
+                text,
+                cursor_id,
+            } if *cursor_id == padded_cursor => {
+                text.insert_str(0, &" ".repeat(width));
+                Some(*position)
+            }
+            _ => None,
+        }) else {
+            continue;
+        };
+        for event in events.iter_mut() {
+            if let Event::MoveCursor {
+                cursor_id,
+                new_position,
+                new_anchor,
+                ..
+            } = event
+            {
+                if *cursor_id != padded_cursor {
+                    continue;
+                }
+                if *new_position >= insert_position {
+                    *new_position += width;
+                }
+                if let Some(anchor) = new_anchor.as_mut().filter(|a| **a >= insert_position) {
+                    *anchor += width;
+                }
+            }
+        }
+    }
 }
 
 /// Get the matching close character for auto-pairing.
@@ -1941,12 +1968,15 @@ pub fn action_to_events(
 
     // Convert block selection to multi-cursor before processing editing actions
     // This allows normal multi-cursor logic to handle typing, deletion, etc.
+    let mut virtual_padding = HashMap::new();
+    let pads_virtual_space = matches!(action, Action::InsertChar(_) | Action::InsertTab);
     if action.is_editing() {
-        let cursor_events = convert_block_selection_to_cursors(state, cursors);
+        let (cursor_events, padding) = convert_block_selection_to_cursors(state, cursors, tab_size);
         for event in &cursor_events {
             state.apply(cursors, event);
         }
         events.extend(cursor_events);
+        virtual_padding = padding;
     }
 
     match action {
@@ -2738,6 +2768,9 @@ pub fn action_to_events(
         | Action::ToggleReadOnly
         | Action::TogglePageView
         | Action::SetPageWidth
+        | Action::ToggleHexView
+        | Action::HexGotoOffset
+        | Action::HexSearchBytes
         | Action::IncreaseSplitSize
         | Action::DecreaseSplitSize
         | Action::ToggleMaximizeSplit
@@ -2755,6 +2788,7 @@ pub fn action_to_events(
         | Action::SmartHome
         | Action::ToggleComment
         | Action::DabbrevExpand
+        | Action::InsertSnippet
         | Action::ToggleFold
         | Action::SetBookmark(_)
         | Action::JumpToBookmark(_)
@@ -2822,6 +2856,12 @@ pub fn action_to_events(
         | Action::TogglePromptLine
         | Action::ToggleVerticalScrollbar
         | Action::ToggleHorizontalScrollbar
+        | Action::ToggleMinimap
+        | Action::ToggleStickyScroll
+        | Action::ToggleBreadcrumbs
+        | Action::ToggleIndentGuides
+        | Action::ToggleSpellCheck
+        | Action::FocusBreadcrumbs
         | Action::FocusFileExplorer
         | Action::FocusEditor
         | Action::SetBackground
@@ -2854,16 +2894,28 @@ pub fn action_to_events(
         | Action::FileExplorerSelectAll
         | Action::LspCompletion
         | Action::LspGotoDefinition
+        | Action::LspGotoImplementation
+        | Action::LspGotoTypeDefinition
+        | Action::LspGotoDeclaration
         | Action::LspReferences
         | Action::LspRename
         | Action::LspHover
         | Action::LspSignatureHelp
         | Action::LspCodeActions
+        | Action::LspRunCodeLens
         | Action::LspRestart
         | Action::LspStop
         | Action::LspToggleForBuffer
         | Action::ToggleInlayHints
         | Action::ToggleMouseHover
+        | Action::DebugStart
+        | Action::DebugStop
+        | Action::DebugContinue
+        | Action::DebugPause
+        | Action::DebugStepOver
+        | Action::DebugStepInto
+        | Action::DebugStepOut
+        | Action::DebugToggleBreakpoint
         | Action::ToggleLineNumbers
         | Action::ToggleScrollSync
         | Action::ToggleMouseCapture
@@ -2906,6 +2958,11 @@ pub fn action_to_events(
         | Action::TerminalEscape
         | Action::ToggleKeyboardCapture
         | Action::TerminalPaste
+        | Action::TerminalPreviousPrompt
+        | Action::TerminalNextPrompt
+        | Action::TerminalSelectLastOutput
+        | Action::TerminalCopyLastOutput
+        | Action::TerminalOpenLink
         | Action::OpenSettings
         | Action::CloseSettings
         | Action::SettingsSave
@@ -2929,10 +2986,14 @@ pub fn action_to_events(
         | Action::ResetBufferSettings
         | Action::ShellCommand
         | Action::ShellCommandReplace
+        | Action::RunTask
+        | Action::CancelTask
+        | Action::ShowTaskOutput
         | Action::CalibrateInput
         | Action::EventDebug
         | Action::SuspendProcess
         | Action::LoadPluginFromBuffer
+        | Action::ShowPluginPerformance
         | Action::InitReload
         | Action::InitEdit
         | Action::InitCheck
@@ -2940,23 +3001,24 @@ pub fn action_to_events(
         | Action::AddRuler
         | Action::RemoveRuler
         | Action::CompositeNextHunk
-        | Action::CompositePrevHunk => return None,
+        | Action::CompositePrevHunk
+        | Action::ShrinkSelection => return None,
 
         // Block/rectangular selection actions
         Action::BlockSelectLeft => {
-            block_select_action(state, cursors, &mut events, BlockDirection::Left);
+            block_select_action(state, cursors, &mut events, BlockDirection::Left, tab_size);
         }
 
         Action::BlockSelectRight => {
-            block_select_action(state, cursors, &mut events, BlockDirection::Right);
+            block_select_action(state, cursors, &mut events, BlockDirection::Right, tab_size);
         }
 
         Action::BlockSelectUp => {
-            block_select_action(state, cursors, &mut events, BlockDirection::Up);
+            block_select_action(state, cursors, &mut events, BlockDirection::Up, tab_size);
         }
 
         Action::BlockSelectDown => {
-            block_select_action(state, cursors, &mut events, BlockDirection::Down);
+            block_select_action(state, cursors, &mut events, BlockDirection::Down, tab_size);
         }
 
         Action::SelectLine => {
@@ -3027,6 +3089,11 @@ pub fn action_to_events(
         }
     }
 
+    // Text typed into virtual space starts at the block's left edge
+    if pads_virtual_space {
+        pad_virtual_space(&mut events, &virtual_padding);
+    }
+
     Some(events)
 }
 
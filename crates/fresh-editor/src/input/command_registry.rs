@@ -781,6 +781,9 @@ mod tests {
             // LSP commands
             ("Show Completions", Action::LspCompletion),
             ("Go to Definition", Action::LspGotoDefinition),
+            ("Go to Implementation", Action::LspGotoImplementation),
+            ("Go to Type Definition", Action::LspGotoTypeDefinition),
+            ("Go to Declaration", Action::LspGotoDeclaration),
             ("Show Hover Info", Action::LspHover),
             ("Find References", Action::LspReferences),
             // Help commands
@@ -431,6 +431,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.shrink_selection",
+        desc_key: "cmd.shrink_selection_desc",
+        action: || Action::ShrinkSelection,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     // Multi-cursor
     CommandDef {
         name_key: "cmd.add_cursor_above",
@@ -739,6 +746,48 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal, FileExplorer, Terminal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_minimap",
+        desc_key: "cmd.toggle_minimap_desc",
+        action: || Action::ToggleMinimap,
+        contexts: &[Normal, FileExplorer, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.toggle_sticky_scroll",
+        desc_key: "cmd.toggle_sticky_scroll_desc",
+        action: || Action::ToggleStickyScroll,
+        contexts: &[Normal, FileExplorer, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.toggle_breadcrumbs",
+        desc_key: "cmd.toggle_breadcrumbs_desc",
+        action: || Action::ToggleBreadcrumbs,
+        contexts: &[Normal, FileExplorer, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.toggle_indent_guides",
+        desc_key: "cmd.toggle_indent_guides_desc",
+        action: || Action::ToggleIndentGuides,
+        contexts: &[Normal, FileExplorer, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.toggle_spell_check",
+        desc_key: "cmd.toggle_spell_check_desc",
+        action: || Action::ToggleSpellCheck,
+        contexts: &[Normal, FileExplorer, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.focus_breadcrumbs",
+        desc_key: "cmd.focus_breadcrumbs_desc",
+        action: || Action::FocusBreadcrumbs,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.focus_file_explorer",
         desc_key: "cmd.focus_file_explorer_desc",
@@ -824,6 +873,27 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.toggle_hex_view",
+        desc_key: "cmd.toggle_hex_view_desc",
+        action: || Action::ToggleHexView,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.hex_goto_offset",
+        desc_key: "cmd.hex_goto_offset_desc",
+        action: || Action::HexGotoOffset,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.hex_search_bytes",
+        desc_key: "cmd.hex_search_bytes_desc",
+        action: || Action::HexSearchBytes,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.set_page_width",
         desc_key: "cmd.set_page_width_desc",
@@ -945,6 +1015,27 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.goto_implementation",
+        desc_key: "cmd.goto_implementation_desc",
+        action: || Action::LspGotoImplementation,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.goto_type_definition",
+        desc_key: "cmd.goto_type_definition_desc",
+        action: || Action::LspGotoTypeDefinition,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.goto_declaration",
+        desc_key: "cmd.goto_declaration_desc",
+        action: || Action::LspGotoDeclaration,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.show_hover_info",
         desc_key: "cmd.show_hover_info_desc",
@@ -973,6 +1064,76 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.insert_snippet",
+        desc_key: "cmd.insert_snippet_desc",
+        action: || Action::InsertSnippet,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.run_code_lens",
+        desc_key: "cmd.run_code_lens_desc",
+        action: || Action::LspRunCodeLens,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_start",
+        desc_key: "cmd.debug_start_desc",
+        action: || Action::DebugStart,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_stop",
+        desc_key: "cmd.debug_stop_desc",
+        action: || Action::DebugStop,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_continue",
+        desc_key: "cmd.debug_continue_desc",
+        action: || Action::DebugContinue,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_pause",
+        desc_key: "cmd.debug_pause_desc",
+        action: || Action::DebugPause,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_over",
+        desc_key: "cmd.debug_step_over_desc",
+        action: || Action::DebugStepOver,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_into",
+        desc_key: "cmd.debug_step_into_desc",
+        action: || Action::DebugStepInto,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_step_out",
+        desc_key: "cmd.debug_step_out_desc",
+        action: || Action::DebugStepOut,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.debug_toggle_breakpoint",
+        desc_key: "cmd.debug_toggle_breakpoint_desc",
+        action: || Action::DebugToggleBreakpoint,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
     CommandDef {
         name_key: "cmd.start_restart_lsp",
         desc_key: "cmd.start_restart_lsp_desc",
@@ -1275,6 +1436,41 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Terminal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.terminal_previous_prompt",
+        desc_key: "cmd.terminal_previous_prompt_desc",
+        action: || Action::TerminalPreviousPrompt,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.terminal_next_prompt",
+        desc_key: "cmd.terminal_next_prompt_desc",
+        action: || Action::TerminalNextPrompt,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.terminal_select_last_output",
+        desc_key: "cmd.terminal_select_last_output_desc",
+        action: || Action::TerminalSelectLastOutput,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.terminal_copy_last_output",
+        desc_key: "cmd.terminal_copy_last_output_desc",
+        action: || Action::TerminalCopyLastOutput,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.terminal_open_link",
+        desc_key: "cmd.terminal_open_link_desc",
+        action: || Action::TerminalOpenLink,
+        contexts: &[Normal, Terminal],
+        custom_contexts: &[],
+    },
     // Shell command operations
     CommandDef {
         name_key: "cmd.shell_command",
@@ -1290,6 +1486,27 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.run_task",
+        desc_key: "cmd.run_task_desc",
+        action: || Action::RunTask,
+        contexts: &[Normal],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.cancel_task",
+        desc_key: "cmd.cancel_task_desc",
+        action: || Action::CancelTask,
+        contexts: &[],
+        custom_contexts: &[],
+    },
+    CommandDef {
+        name_key: "cmd.show_task_output",
+        desc_key: "cmd.show_task_output_desc",
+        action: || Action::ShowTaskOutput,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     // Debugging
     CommandDef {
         name_key: "cmd.event_debug",
@@ -1314,6 +1531,13 @@ static COMMAND_DEFS: &[CommandDef] = &[
         contexts: &[Normal],
         custom_contexts: &[],
     },
+    CommandDef {
+        name_key: "cmd.show_plugin_performance",
+        desc_key: "cmd.show_plugin_performance_desc",
+        action: || Action::ShowPluginPerformance,
+        contexts: &[],
+        custom_contexts: &[],
+    },
     // User init.ts
     CommandDef {
         name_key: "cmd.init_reload",
@@ -157,6 +157,9 @@ pub enum DeferredAction {
     PopupBackspace,
     /// Copy text to clipboard (from popup text selection)
     CopyToClipboard(String),
+    /// Move from a breadcrumb dropdown to the one of the segment this many
+    /// places away
+    BreadcrumbStep(isize),
 
     // File browser actions
     FileBrowserSelectPrev,
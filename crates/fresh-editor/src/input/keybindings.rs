@@ -349,6 +349,7 @@ pub enum Action {
     SelectWord,
     SelectLine,
     ExpandSelection,
+    ShrinkSelection,
 
     // Block/rectangular selection (column-wise)
     BlockSelectLeft,
@@ -428,6 +429,7 @@ pub enum Action {
     DedentSelection,
     ToggleComment,
     DabbrevExpand,
+    InsertSnippet,
     ToggleFold,
 
     // Bookmarks
@@ -503,6 +505,9 @@ pub enum Action {
     ToggleReadOnly,
     TogglePageView,
     SetPageWidth,
+    ToggleHexView,
+    HexGotoOffset,
+    HexSearchBytes,
     InspectThemeAtCursor,
     SelectTheme,
     SelectKeybindingMap,
@@ -601,6 +606,17 @@ pub enum Action {
     // Scrollbar visibility
     ToggleVerticalScrollbar,
     ToggleHorizontalScrollbar,
+    // Minimap visibility
+    ToggleMinimap,
+    // Sticky scroll headers
+    ToggleStickyScroll,
+    // Breadcrumb bar
+    ToggleBreadcrumbs,
+    FocusBreadcrumbs,
+    // Indentation guides
+    ToggleIndentGuides,
+    // Spell checking
+    ToggleSpellCheck,
     FocusFileExplorer,
     FocusEditor,
     FileExplorerUp,
@@ -633,17 +649,31 @@ pub enum Action {
     // LSP operations
     LspCompletion,
     LspGotoDefinition,
+    LspGotoImplementation,
+    LspGotoTypeDefinition,
+    LspGotoDeclaration,
     LspReferences,
     LspRename,
     LspHover,
     LspSignatureHelp,
     LspCodeActions,
+    LspRunCodeLens,
     LspRestart,
     LspStop,
     LspToggleForBuffer,
     ToggleInlayHints,
     ToggleMouseHover,
 
+    // Debugging (Debug Adapter Protocol)
+    DebugStart,
+    DebugStop,
+    DebugContinue,
+    DebugPause,
+    DebugStepOver,
+    DebugStepInto,
+    DebugStepOut,
+    DebugToggleBreakpoint,
+
     // View toggles
     ToggleLineNumbers,
     ToggleScrollSync,
@@ -711,17 +741,27 @@ pub enum Action {
     SettingsInherit,     // Set nullable setting to null (inherit value)
 
     // Terminal operations
-    OpenTerminal,          // Open a new terminal in the current split
-    CloseTerminal,         // Close the current terminal
-    FocusTerminal,         // Focus the terminal buffer (if viewing terminal, focus input)
-    TerminalEscape,        // Escape from terminal mode back to editor
-    ToggleKeyboardCapture, // Toggle keyboard capture mode (all keys go to terminal)
-    TerminalPaste,         // Paste clipboard contents into terminal as a single batch
+    OpenTerminal,             // Open a new terminal in the current split
+    CloseTerminal,            // Close the current terminal
+    FocusTerminal,            // Focus the terminal buffer (if viewing terminal, focus input)
+    TerminalEscape,           // Escape from terminal mode back to editor
+    ToggleKeyboardCapture,    // Toggle keyboard capture mode (all keys go to terminal)
+    TerminalPaste,            // Paste clipboard contents into terminal as a single batch
+    TerminalPreviousPrompt,   // Jump to the previous shell prompt (shell integration)
+    TerminalNextPrompt,       // Jump to the next shell prompt (shell integration)
+    TerminalSelectLastOutput, // Select the last command's output (shell integration)
+    TerminalCopyLastOutput,   // Copy the last command's output (shell integration)
+    TerminalOpenLink,         // Open the file location or URL under the cursor in scrollback
 
     // Shell command operations
     ShellCommand,        // Run shell command on buffer/selection, output to new buffer
     ShellCommandReplace, // Run shell command on buffer/selection, replace content
 
+    // Tasks
+    RunTask,        // Run a configured task and collect its problems
+    CancelTask,     // Kill the running task
+    ShowTaskOutput, // Show the output of the last task run
+
     // Case conversion
     ToUpperCase, // Convert selection to uppercase
     ToLowerCase, // Convert selection to lowercase
@@ -741,7 +781,8 @@ pub enum Action {
     OpenKeybindingEditor, // Open the keybinding editor modal
 
     // Plugin development
-    LoadPluginFromBuffer, // Load current buffer as a plugin
+    LoadPluginFromBuffer,  // Load current buffer as a plugin
+    ShowPluginPerformance, // Show CPU time and hook latency per plugin
 
     // User init.ts (design M4, M5, M6)
     InitReload, // Reload ~/.config/fresh/init.ts via the existing plugin pipeline
@@ -872,6 +913,7 @@ impl Action {
             "select_word" => SelectWord,
             "select_line" => SelectLine,
             "expand_selection" => ExpandSelection,
+            "shrink_selection" => ShrinkSelection,
 
             "block_select_left" => BlockSelectLeft,
             "block_select_right" => BlockSelectRight,
@@ -934,6 +976,7 @@ impl Action {
             "dedent_selection" => DedentSelection,
             "toggle_comment" => ToggleComment,
             "dabbrev_expand" => DabbrevExpand,
+            "insert_snippet" => InsertSnippet,
             "toggle_fold" => ToggleFold,
 
             "list_bookmarks" => ListBookmarks,
@@ -980,6 +1023,9 @@ impl Action {
             "toggle_read_only" => ToggleReadOnly,
             "toggle_page_view" => TogglePageView,
             "set_page_width" => SetPageWidth,
+            "toggle_hex_view" => ToggleHexView,
+            "hex_goto_offset" => HexGotoOffset,
+            "hex_search_bytes" => HexSearchBytes,
 
             "next_buffer" => NextBuffer,
             "prev_buffer" => PrevBuffer,
@@ -1049,6 +1095,12 @@ impl Action {
             "toggle_prompt_line" => TogglePromptLine,
             "toggle_vertical_scrollbar" => ToggleVerticalScrollbar,
             "toggle_horizontal_scrollbar" => ToggleHorizontalScrollbar,
+            "toggle_minimap" => ToggleMinimap,
+            "toggle_sticky_scroll" => ToggleStickyScroll,
+            "toggle_breadcrumbs" => ToggleBreadcrumbs,
+            "toggle_indent_guides" => ToggleIndentGuides,
+            "toggle_spell_check" => ToggleSpellCheck,
+            "focus_breadcrumbs" => FocusBreadcrumbs,
             "focus_file_explorer" => FocusFileExplorer,
             "focus_editor" => FocusEditor,
             "file_explorer_up" => FileExplorerUp,
@@ -1080,17 +1132,30 @@ impl Action {
 
             "lsp_completion" => LspCompletion,
             "lsp_goto_definition" => LspGotoDefinition,
+            "lsp_goto_implementation" => LspGotoImplementation,
+            "lsp_goto_type_definition" => LspGotoTypeDefinition,
+            "lsp_goto_declaration" => LspGotoDeclaration,
             "lsp_references" => LspReferences,
             "lsp_rename" => LspRename,
             "lsp_hover" => LspHover,
             "lsp_signature_help" => LspSignatureHelp,
             "lsp_code_actions" => LspCodeActions,
+            "lsp_run_code_lens" => LspRunCodeLens,
             "lsp_restart" => LspRestart,
             "lsp_stop" => LspStop,
             "lsp_toggle_for_buffer" => LspToggleForBuffer,
             "toggle_inlay_hints" => ToggleInlayHints,
             "toggle_mouse_hover" => ToggleMouseHover,
 
+            "debug_start" => DebugStart,
+            "debug_stop" => DebugStop,
+            "debug_continue" => DebugContinue,
+            "debug_pause" => DebugPause,
+            "debug_step_over" => DebugStepOver,
+            "debug_step_into" => DebugStepInto,
+            "debug_step_out" => DebugStepOut,
+            "debug_toggle_breakpoint" => DebugToggleBreakpoint,
+
             "toggle_line_numbers" => ToggleLineNumbers,
             "toggle_scroll_sync" => ToggleScrollSync,
             "toggle_mouse_capture" => ToggleMouseCapture,
@@ -1141,10 +1206,19 @@ impl Action {
             "terminal_escape" => TerminalEscape,
             "toggle_keyboard_capture" => ToggleKeyboardCapture,
             "terminal_paste" => TerminalPaste,
+            "terminal_previous_prompt" => TerminalPreviousPrompt,
+            "terminal_next_prompt" => TerminalNextPrompt,
+            "terminal_select_last_output" => TerminalSelectLastOutput,
+            "terminal_copy_last_output" => TerminalCopyLastOutput,
+            "terminal_open_link" => TerminalOpenLink,
 
             "shell_command" => ShellCommand,
             "shell_command_replace" => ShellCommandReplace,
 
+            "run_task" => RunTask,
+            "cancel_task" => CancelTask,
+            "show_task_output" => ShowTaskOutput,
+
             "to_upper_case" => ToUpperCase,
             "to_lower_case" => ToLowerCase,
             "toggle_case" => ToggleCase,
@@ -1154,6 +1228,7 @@ impl Action {
             "event_debug" => EventDebug,
             "suspend_process" => SuspendProcess,
             "load_plugin_from_buffer" => LoadPluginFromBuffer,
+            "show_plugin_performance" => ShowPluginPerformance,
             "init_reload" => InitReload,
             "init_edit" => InitEdit,
             "init_check" => InitCheck,
@@ -1309,6 +1384,7 @@ impl Action {
                 | Action::SelectWord
                 | Action::SelectLine
                 | Action::ExpandSelection
+                | Action::ShrinkSelection
                 // Block selection
                 | Action::BlockSelectLeft
                 | Action::BlockSelectRight
@@ -1676,6 +1752,11 @@ impl KeybindingResolver {
                 | Action::OpenTerminal
                 | Action::CloseTerminal
                 | Action::TerminalPaste
+                | Action::TerminalPreviousPrompt
+                | Action::TerminalNextPrompt
+                | Action::TerminalSelectLastOutput
+                | Action::TerminalCopyLastOutput
+                | Action::TerminalOpenLink
                 // File explorer
                 | Action::ToggleFileExplorer
                 // Menu bar
@@ -2189,6 +2270,7 @@ impl KeybindingResolver {
             Action::SelectWord => t!("action.select_word"),
             Action::SelectLine => t!("action.select_line"),
             Action::ExpandSelection => t!("action.expand_selection"),
+            Action::ShrinkSelection => t!("action.shrink_selection"),
             Action::BlockSelectLeft => t!("action.block_select_left"),
             Action::BlockSelectRight => t!("action.block_select_right"),
             Action::BlockSelectUp => t!("action.block_select_up"),
@@ -2246,6 +2328,7 @@ impl KeybindingResolver {
             Action::DedentSelection => t!("action.dedent_selection"),
             Action::ToggleComment => t!("action.toggle_comment"),
             Action::DabbrevExpand => std::borrow::Cow::Borrowed("Expand abbreviation (dabbrev)"),
+            Action::InsertSnippet => t!("action.insert_snippet"),
             Action::ToggleFold => t!("action.toggle_fold"),
             Action::SetBookmark(c) => t!("action.set_bookmark", key = c),
             Action::JumpToBookmark(c) => t!("action.jump_to_bookmark", key = c),
@@ -2293,6 +2376,9 @@ impl KeybindingResolver {
             Action::ToggleReadOnly => t!("action.toggle_read_only"),
             Action::TogglePageView => t!("action.toggle_page_view"),
             Action::SetPageWidth => t!("action.set_page_width"),
+            Action::ToggleHexView => t!("action.toggle_hex_view"),
+            Action::HexGotoOffset => t!("action.hex_goto_offset"),
+            Action::HexSearchBytes => t!("action.hex_search_bytes"),
             Action::NextBuffer => t!("action.next_buffer"),
             Action::PrevBuffer => t!("action.prev_buffer"),
             Action::NavigateBack => t!("action.navigate_back"),
@@ -2356,6 +2442,12 @@ impl KeybindingResolver {
             Action::TogglePromptLine => t!("action.toggle_prompt_line"),
             Action::ToggleVerticalScrollbar => t!("action.toggle_vertical_scrollbar"),
             Action::ToggleHorizontalScrollbar => t!("action.toggle_horizontal_scrollbar"),
+            Action::ToggleMinimap => t!("action.toggle_minimap"),
+            Action::ToggleStickyScroll => t!("action.toggle_sticky_scroll"),
+            Action::ToggleBreadcrumbs => t!("action.toggle_breadcrumbs"),
+            Action::ToggleIndentGuides => t!("action.toggle_indent_guides"),
+            Action::ToggleSpellCheck => t!("action.toggle_spell_check"),
+            Action::FocusBreadcrumbs => t!("action.focus_breadcrumbs"),
             Action::FocusFileExplorer => t!("action.focus_file_explorer"),
             Action::FocusEditor => t!("action.focus_editor"),
             Action::FileExplorerUp => t!("action.file_explorer_up"),
@@ -2388,16 +2480,28 @@ impl KeybindingResolver {
             Action::FileExplorerSelectAll => t!("action.file_explorer_select_all"),
             Action::LspCompletion => t!("action.lsp_completion"),
             Action::LspGotoDefinition => t!("action.lsp_goto_definition"),
+            Action::LspGotoImplementation => t!("action.lsp_goto_implementation"),
+            Action::LspGotoTypeDefinition => t!("action.lsp_goto_type_definition"),
+            Action::LspGotoDeclaration => t!("action.lsp_goto_declaration"),
             Action::LspReferences => t!("action.lsp_references"),
             Action::LspRename => t!("action.lsp_rename"),
             Action::LspHover => t!("action.lsp_hover"),
             Action::LspSignatureHelp => t!("action.lsp_signature_help"),
             Action::LspCodeActions => t!("action.lsp_code_actions"),
+            Action::LspRunCodeLens => t!("action.lsp_run_code_lens"),
             Action::LspRestart => t!("action.lsp_restart"),
             Action::LspStop => t!("action.lsp_stop"),
             Action::LspToggleForBuffer => t!("action.lsp_toggle_for_buffer"),
             Action::ToggleInlayHints => t!("action.toggle_inlay_hints"),
             Action::ToggleMouseHover => t!("action.toggle_mouse_hover"),
+            Action::DebugStart => t!("action.debug_start"),
+            Action::DebugStop => t!("action.debug_stop"),
+            Action::DebugContinue => t!("action.debug_continue"),
+            Action::DebugPause => t!("action.debug_pause"),
+            Action::DebugStepOver => t!("action.debug_step_over"),
+            Action::DebugStepInto => t!("action.debug_step_into"),
+            Action::DebugStepOut => t!("action.debug_step_out"),
+            Action::DebugToggleBreakpoint => t!("action.debug_toggle_breakpoint"),
             Action::ToggleLineNumbers => t!("action.toggle_line_numbers"),
             Action::ToggleScrollSync => t!("action.toggle_scroll_sync"),
             Action::ToggleMouseCapture => t!("action.toggle_mouse_capture"),
@@ -2449,6 +2553,11 @@ impl KeybindingResolver {
             Action::TerminalEscape => t!("action.terminal_escape"),
             Action::ToggleKeyboardCapture => t!("action.toggle_keyboard_capture"),
             Action::TerminalPaste => t!("action.terminal_paste"),
+            Action::TerminalPreviousPrompt => t!("action.terminal_previous_prompt"),
+            Action::TerminalNextPrompt => t!("action.terminal_next_prompt"),
+            Action::TerminalSelectLastOutput => t!("action.terminal_select_last_output"),
+            Action::TerminalCopyLastOutput => t!("action.terminal_copy_last_output"),
+            Action::TerminalOpenLink => t!("action.terminal_open_link"),
             Action::OpenSettings => t!("action.open_settings"),
             Action::CloseSettings => t!("action.close_settings"),
             Action::SettingsSave => t!("action.settings_save"),
@@ -2462,6 +2571,9 @@ impl KeybindingResolver {
             Action::SettingsInherit => t!("action.settings_inherit"),
             Action::ShellCommand => t!("action.shell_command"),
             Action::ShellCommandReplace => t!("action.shell_command_replace"),
+            Action::RunTask => t!("action.run_task"),
+            Action::CancelTask => t!("action.cancel_task"),
+            Action::ShowTaskOutput => t!("action.show_task_output"),
             Action::ToUpperCase => t!("action.to_uppercase"),
             Action::ToLowerCase => t!("action.to_lowercase"),
             Action::ToggleCase => t!("action.to_uppercase"),
@@ -2470,6 +2582,7 @@ impl KeybindingResolver {
             Action::EventDebug => t!("action.event_debug"),
             Action::SuspendProcess => t!("action.suspend_process"),
             Action::LoadPluginFromBuffer => "Load Plugin from Buffer".into(),
+            Action::ShowPluginPerformance => t!("action.show_plugin_performance"),
             Action::InitReload => "Reload init.ts".into(),
             Action::InitEdit => "Edit init.ts".into(),
             Action::InitCheck => "Check init.ts".into(),
@@ -4,8 +4,9 @@
 //! Inspired by VSCode's Quick Open (Ctrl+P) which supports:
 //! - Empty prefix: file finder
 //! - `>`: command palette
-//! - `#`: symbol finder (future)
-//! - `@`: go to symbol in file (future)
+//! - `#`: switch buffer
+//! - `@`: go to symbol in file
+//! - `%`: go to symbol in workspace
 //! - `:`: go to line
 //!
 //! Providers are registered with a prefix and handle suggestion generation
@@ -13,10 +14,15 @@
 
 pub mod providers;
 
-pub use providers::{BufferProvider, CommandProvider, FileProvider, GotoLineProvider};
+pub use providers::{
+    BufferProvider, CommandProvider, DocumentSymbolProvider, FileProvider, GotoLineProvider,
+    WorkspaceSymbolProvider,
+};
 
 use crate::input::commands::Suggestion;
 use crate::input::keybindings::Action;
+use crate::primitives::document_symbols::OutlineSymbol;
+use crate::primitives::workspace_symbols::WorkspaceSymbolEntry;
 use std::collections::HashMap;
 
 /// Result of confirming a selection in a provider
@@ -34,6 +40,10 @@ pub enum QuickOpenResult {
     ShowBuffer(usize),
     /// Go to a line in the current buffer
     GotoLine(GotoLineTarget),
+    /// Go to a byte offset in the current buffer
+    GotoOffset(usize),
+    /// Open the file of an LSP location and go to its start
+    OpenLocation(lsp_types::Location),
     /// Do nothing (provider handled it internally)
     None,
     /// Show an error message
@@ -110,6 +120,16 @@ pub struct QuickOpenContext {
     pub has_lsp_config: bool,
     /// Whether relative line numbers are enabled
     pub relative_line_numbers: bool,
+    /// Outline of the active buffer, possibly from an older version
+    pub document_symbols: std::sync::Arc<Vec<OutlineSymbol>>,
+    /// Whether a better outline is still expected from the language server
+    pub document_symbols_pending: bool,
+    /// Workspace symbols received so far for the current `%` query
+    pub workspace_symbols: std::sync::Arc<Vec<WorkspaceSymbolEntry>>,
+    /// Whether some language server has yet to answer the current `%` query
+    pub workspace_symbols_pending: bool,
+    /// Whether any running language server supports `workspace/symbol`
+    pub has_workspace_symbol_servers: bool,
 }
 
 /// Information about an open buffer
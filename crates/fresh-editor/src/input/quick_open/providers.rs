@@ -5,12 +5,16 @@
 //! - CommandProvider: Command palette (prefix: ">")
 //! - BufferProvider: Switch between open buffers (prefix: "#")
 //! - GotoLineProvider: Go to a specific line (prefix: ":")
+//! - DocumentSymbolProvider: Go to a symbol in the active buffer (prefix: "@")
+//! - WorkspaceSymbolProvider: Go to a symbol anywhere in the workspace (prefix: "%")
 
 use super::{
     parse_goto_line_input, GotoLineTarget, QuickOpenContext, QuickOpenProvider, QuickOpenResult,
 };
 use crate::input::commands::Suggestion;
 use crate::input::fuzzy::FuzzyMatcher;
+use crate::primitives::document_symbols::{symbol_kind_name, OutlineSymbol};
+use crate::primitives::workspace_symbols::WorkspaceSymbolEntry;
 use rust_i18n::t;
 
 // ============================================================================
@@ -255,6 +259,221 @@ impl QuickOpenProvider for GotoLineProvider {
     }
 }
 
+// ============================================================================
+// Document Symbol Provider (prefix: "@")
+// ============================================================================
+
+/// Provider for jumping to a symbol of the active buffer
+pub struct DocumentSymbolProvider;
+
+impl DocumentSymbolProvider {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn suggestion(symbol: &OutlineSymbol, indent: bool) -> Suggestion {
+        let kind = symbol_kind_name(symbol.kind);
+        let line = (symbol.line + 1).to_string();
+        let description = match &symbol.container {
+            Some(container) => t!(
+                "quick_open.symbol_in_desc",
+                kind = kind,
+                container = container,
+                line = line
+            ),
+            None => t!("quick_open.symbol_desc", kind = kind, line = line),
+        };
+        let text = if indent {
+            format!("{}{}", "  ".repeat(symbol.depth), symbol.name)
+        } else {
+            symbol.name.clone()
+        };
+        Suggestion::new(text)
+            .with_description(description.to_string())
+            .with_value(symbol.selection_range.start.to_string())
+    }
+}
+
+impl Default for DocumentSymbolProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QuickOpenProvider for DocumentSymbolProvider {
+    fn prefix(&self) -> &str {
+        "@"
+    }
+
+    fn suggestions(&self, query: &str, context: &QuickOpenContext) -> Vec<Suggestion> {
+        let symbols = &context.document_symbols;
+        let query = query.trim();
+
+        let suggestions: Vec<Suggestion> = if query.is_empty() {
+            // Whole outline in document order, indented by nesting
+            symbols
+                .iter()
+                .map(|symbol| Self::suggestion(symbol, true))
+                .collect()
+        } else {
+            let mut matcher = FuzzyMatcher::new(query);
+            let mut scored: Vec<(i32, usize)> = symbols
+                .iter()
+                .enumerate()
+                .filter_map(|(index, symbol)| {
+                    let m = matcher.match_target(&symbol.name);
+                    m.matched.then_some((m.score, index))
+                })
+                .collect();
+            // Sort by score (higher is better), then by position in the file
+            scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+            scored
+                .into_iter()
+                .map(|(_, index)| Self::suggestion(&symbols[index], false))
+                .collect()
+        };
+
+        if !suggestions.is_empty() {
+            return suggestions;
+        }
+        let hint = if context.document_symbols_pending {
+            t!("quick_open.loading_symbols")
+        } else {
+            t!("quick_open.no_symbols")
+        };
+        vec![Suggestion::disabled(hint.to_string())]
+    }
+
+    fn on_select(
+        &self,
+        suggestion: Option<&Suggestion>,
+        _query: &str,
+        _context: &QuickOpenContext,
+    ) -> QuickOpenResult {
+        suggestion
+            .and_then(|s| s.value.as_deref())
+            .and_then(|v| v.parse::<usize>().ok())
+            .map(QuickOpenResult::GotoOffset)
+            .unwrap_or(QuickOpenResult::None)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+// ============================================================================
+// Workspace Symbol Provider (prefix: "%")
+// ============================================================================
+
+/// Provider for jumping to a symbol anywhere in the workspace.
+///
+/// The editor sends the query to the language servers and streams their
+/// answers into [`QuickOpenContext::workspace_symbols`]; this provider only
+/// ranks and formats what has arrived so far.
+pub struct WorkspaceSymbolProvider;
+
+impl WorkspaceSymbolProvider {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn suggestion(entry: &WorkspaceSymbolEntry, index: usize) -> Suggestion {
+        let kind = symbol_kind_name(entry.kind);
+        let line = (entry.location.range.start.line + 1).to_string();
+        let description = match &entry.container {
+            Some(container) => t!(
+                "quick_open.workspace_symbol_in_desc",
+                kind = kind,
+                container = container,
+                path = entry.display_path,
+                line = line
+            ),
+            None => t!(
+                "quick_open.workspace_symbol_desc",
+                kind = kind,
+                path = entry.display_path,
+                line = line
+            ),
+        };
+        Suggestion::new(entry.name.clone())
+            .with_description(description.to_string())
+            .with_value(index.to_string())
+    }
+}
+
+impl Default for WorkspaceSymbolProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QuickOpenProvider for WorkspaceSymbolProvider {
+    fn prefix(&self) -> &str {
+        "%"
+    }
+
+    fn suggestions(&self, query: &str, context: &QuickOpenContext) -> Vec<Suggestion> {
+        if !context.has_workspace_symbol_servers {
+            return vec![Suggestion::disabled(
+                t!("quick_open.no_workspace_symbol_servers").to_string(),
+            )];
+        }
+        let query = query.trim();
+        if query.is_empty() {
+            return vec![Suggestion::disabled(
+                t!("quick_open.workspace_symbol_hint").to_string(),
+            )];
+        }
+
+        let symbols = &context.workspace_symbols;
+        let mut matcher = FuzzyMatcher::new(query);
+        let mut scored: Vec<(i32, usize)> = symbols
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                let m = matcher.match_target(&entry.name);
+                m.matched.then_some((m.score, index))
+            })
+            .collect();
+        // Sort by score (higher is better), then by arrival order
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        let suggestions: Vec<Suggestion> = scored
+            .into_iter()
+            .map(|(_, index)| Self::suggestion(&symbols[index], index))
+            .collect();
+
+        if !suggestions.is_empty() {
+            return suggestions;
+        }
+        let hint = if context.workspace_symbols_pending {
+            t!("quick_open.searching_workspace_symbols")
+        } else {
+            t!("quick_open.no_symbols")
+        };
+        vec![Suggestion::disabled(hint.to_string())]
+    }
+
+    fn on_select(
+        &self,
+        suggestion: Option<&Suggestion>,
+        _query: &str,
+        context: &QuickOpenContext,
+    ) -> QuickOpenResult {
+        suggestion
+            .filter(|s| !s.disabled)
+            .and_then(|s| s.value.as_deref())
+            .and_then(|v| v.parse::<usize>().ok())
+            .and_then(|index| context.workspace_symbols.get(index))
+            .map(|entry| QuickOpenResult::OpenLocation(entry.location.clone()))
+            .unwrap_or(QuickOpenResult::None)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
 // ============================================================================
 // File Provider (default, no prefix)
 // ============================================================================
@@ -967,9 +1186,38 @@ mod tests {
             buffer_mode: None,
             has_lsp_config: true,
             relative_line_numbers: false,
+            document_symbols: Default::default(),
+            document_symbols_pending: false,
+            workspace_symbols: Default::default(),
+            workspace_symbols_pending: false,
+            has_workspace_symbol_servers: true,
+        }
+    }
+
+    fn symbol(name: &str, start: usize, line: usize, depth: usize) -> OutlineSymbol {
+        OutlineSymbol {
+            name: name.to_string(),
+            detail: None,
+            kind: lsp_types::SymbolKind::FUNCTION,
+            range: start..start + 10,
+            selection_range: start + 3..start + 3 + name.len(),
+            line,
+            depth,
+            container: (depth > 0).then(|| "Parser".to_string()),
         }
     }
 
+    fn symbol_context() -> QuickOpenContext {
+        let mut context = make_test_context("/tmp");
+        context.document_symbols = std::sync::Arc::new(vec![
+            symbol("Parser", 0, 0, 0),
+            symbol("parse_expr", 20, 2, 1),
+            symbol("peek", 40, 4, 1),
+            symbol("main", 60, 7, 0),
+        ]);
+        context
+    }
+
     #[test]
     fn test_buffer_provider_suggestions() {
         let provider = BufferProvider::new();
@@ -1030,6 +1278,127 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_document_symbol_provider_lists_outline() {
+        let provider = DocumentSymbolProvider::new();
+        let context = symbol_context();
+
+        let suggestions = provider.suggestions("", &context);
+        let texts: Vec<&str> = suggestions.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["Parser", "  parse_expr", "  peek", "main"]);
+        assert!(suggestions[1]
+            .description
+            .as_deref()
+            .unwrap()
+            .contains("Parser"));
+    }
+
+    #[test]
+    fn test_document_symbol_provider_filter_and_select() {
+        let provider = DocumentSymbolProvider::new();
+        let context = symbol_context();
+
+        let suggestions = provider.suggestions("pe", &context);
+        let texts: Vec<&str> = suggestions.iter().map(|s| s.text.as_str()).collect();
+        assert!(texts.contains(&"peek"));
+        assert!(texts.contains(&"parse_expr"));
+        assert!(!texts.contains(&"main"));
+
+        let peek = suggestions.iter().find(|s| s.text == "peek");
+        match provider.on_select(peek, "pe", &context) {
+            QuickOpenResult::GotoOffset(offset) => assert_eq!(offset, 43),
+            other => panic!("expected GotoOffset, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_document_symbol_provider_empty_hints() {
+        let provider = DocumentSymbolProvider::new();
+        let mut context = make_test_context("/tmp");
+
+        let suggestions = provider.suggestions("", &context);
+        assert_eq!(suggestions.len(), 1);
+        assert!(suggestions[0].disabled);
+        assert!(matches!(
+            provider.on_select(suggestions.first(), "", &context),
+            QuickOpenResult::None
+        ));
+
+        context.document_symbols_pending = true;
+        let loading = provider.suggestions("", &context);
+        assert_ne!(loading[0].text, suggestions[0].text);
+
+        let context = symbol_context();
+        assert!(provider.suggestions("zzz", &context)[0].disabled);
+    }
+
+    fn workspace_symbol(name: &str, path: &str, line: u32) -> WorkspaceSymbolEntry {
+        let start = lsp_types::Position::new(line, 4);
+        WorkspaceSymbolEntry {
+            name: name.to_string(),
+            kind: lsp_types::SymbolKind::STRUCT,
+            container: None,
+            location: lsp_types::Location {
+                uri: format!("file:///repo/{}", path).parse().unwrap(),
+                range: lsp_types::Range::new(start, start),
+            },
+            display_path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_workspace_symbol_provider_filter_and_select() {
+        let provider = WorkspaceSymbolProvider::new();
+        let mut context = make_test_context("/tmp");
+        context.workspace_symbols = std::sync::Arc::new(vec![
+            workspace_symbol("ConfigError", "src/error.rs", 3),
+            workspace_symbol("Config", "src/config.rs", 11),
+            workspace_symbol("Parser", "src/parser.rs", 0),
+        ]);
+
+        let suggestions = provider.suggestions("config", &context);
+        let texts: Vec<&str> = suggestions.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts.len(), 2);
+        assert!(!texts.contains(&"Parser"));
+        assert!(suggestions[0]
+            .description
+            .as_deref()
+            .unwrap()
+            .contains("src/"));
+
+        let config = suggestions.iter().find(|s| s.text == "Config");
+        match provider.on_select(config, "config", &context) {
+            QuickOpenResult::OpenLocation(location) => {
+                assert_eq!(location.uri.as_str(), "file:///repo/src/config.rs");
+                assert_eq!(location.range.start.line, 11);
+            }
+            other => panic!("expected OpenLocation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_workspace_symbol_provider_hints() {
+        let provider = WorkspaceSymbolProvider::new();
+        let mut context = make_test_context("/tmp");
+
+        let hint = provider.suggestions("", &context);
+        assert!(hint[0].disabled);
+        assert!(matches!(
+            provider.on_select(hint.first(), "", &context),
+            QuickOpenResult::None
+        ));
+
+        let none_found = provider.suggestions("cfg", &context);
+        context.workspace_symbols_pending = true;
+        let searching = provider.suggestions("cfg", &context);
+        assert!(none_found[0].disabled && searching[0].disabled);
+        assert_ne!(none_found[0].text, searching[0].text);
+
+        context.has_workspace_symbol_servers = false;
+        let no_servers = provider.suggestions("cfg", &context);
+        assert_ne!(no_servers[0].text, searching[0].text);
+    }
+
     /// Signed input is always interpreted as relative — independent of the
     /// `relative_line_numbers` display setting.
     #[test]
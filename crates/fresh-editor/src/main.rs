@@ -10,6 +10,7 @@ use crossterm::event::{
     MouseEvent,
 };
 use fresh::input::key_translator::KeyTranslator;
+use fresh::primitives::file_location::{parse_file_location, FileLocation};
 #[cfg(target_os = "linux")]
 use fresh::services::gpm::{gpm_to_crossterm, GpmClient};
 use fresh::services::terminal_modes::{self, KeyboardConfig, TerminalModes};
@@ -46,7 +47,7 @@ const BEFORE_HELP_EN: &str =
 #[command(before_help = BEFORE_HELP_EN)]
 struct Cli {
     /// Run a command instead of opening files
-    /// Commands: session (list|attach|new|kill|open-file), config (show|paths), grammar (list), init
+    /// Commands: session (list|attach|new|kill|open-file|send), config (show|paths), grammar (list), init
     #[arg(long, num_args = 1.., value_name = "COMMAND", allow_hyphen_values = true)]
     cmd: Vec<String>,
 
@@ -180,6 +181,8 @@ struct Args {
     kill: Option<Option<String>>,
     /// Open files in a session without attaching (session_name, files, wait)
     open_files_in_session: Option<(Option<String>, Vec<String>, bool)>,
+    /// Send a remote-control request to a session (session_name, method, arguments)
+    send_to_session: Option<(Option<String>, String, Vec<String>)>,
     /// Launch in GUI mode
     #[cfg(feature = "gui")]
     gui: bool,
@@ -198,6 +201,26 @@ impl From<Cli> for Args {
             false
         };
 
+        // Likewise for sending a remote-control request to a session
+        let send_to_session = if !cli.cmd.is_empty() {
+            let cmd_args: Vec<&str> = cli.cmd.iter().map(|s| s.as_str()).collect();
+            match cmd_args.as_slice() {
+                ["session", "send", name, method, rest @ ..]
+                | ["s", "send", name, method, rest @ ..] => {
+                    let session = if *name == "." {
+                        None
+                    } else {
+                        Some((*name).to_string())
+                    };
+                    let rest = rest.iter().map(|s| (*s).to_string()).collect();
+                    Some((session, (*method).to_string(), rest))
+                }
+                _ => None,
+            }
+        } else {
+            None
+        };
+
         // Parse --cmd arguments to determine command
         let (
             list_sessions,
@@ -360,10 +383,14 @@ impl From<Cli> for Args {
                 ["grammar", "list"] | ["grammars", "list"] | ["grammar", "ls"] | ["grammars"] => (
                     false, None, false, None, false, false, None, cli.files, None,
                 ),
+                // Remote-control requests (handled via send_to_session above)
+                _ if send_to_session.is_some() => (
+                    false, None, false, None, false, false, None, cli.files, None,
+                ),
                 // Unknown command
                 _ => {
                     eprintln!("Unknown command: {}", cli.cmd.join(" "));
-                    eprintln!("Available commands: session (list|attach|new|kill|info|open-file), config (show|paths), grammar (list), init");
+                    eprintln!("Available commands: session (list|attach|new|kill|info|open-file|send), config (show|paths), grammar (list), init");
                     std::process::exit(1);
                 }
             }
@@ -425,25 +452,13 @@ impl From<Cli> for Args {
             session_name,
             kill,
             open_files_in_session,
+            send_to_session,
             #[cfg(feature = "gui")]
             gui: cli.gui,
         }
     }
 }
 
-/// Parsed file location from CLI argument in file:line:col format
-/// Also supports range selections (file:L-EL or file:L:C-EL:EC) and
-/// hover messages (file:L@"message").
-#[derive(Debug)]
-struct FileLocation {
-    path: PathBuf,
-    line: Option<usize>,
-    column: Option<usize>,
-    end_line: Option<usize>,
-    end_column: Option<usize>,
-    message: Option<String>,
-}
-
 /// Parsed remote location from CLI argument.
 ///
 /// Accepts two wire forms — both produce the same struct:
@@ -876,21 +891,6 @@ fn handle_first_run_setup(
     Ok(())
 }
 
-/// Parse a file path that may include line/column, range, and message information.
-/// Supports formats:
-/// - file.txt
-/// - file.txt:10
-/// - file.txt:10:5
-/// - file.txt:13-16           (line range)
-/// - file.txt:13:17-21:1      (full range with columns)
-/// - file.txt:10@"message"    (position + hover message)
-/// - file.txt:13-16@"message" (range + hover message)
-/// - /path/to/file.txt:10:5
-///
-/// For Windows paths like C:\path\file.txt:10:5, we handle the drive letter
-/// prefix properly using std::path APIs.
-///
-/// If the full path exists as a file, it's used as-is (handles files with colons in name).
 /// Build FileRequest structs from CLI file arguments, resolving paths relative to `working_dir`.
 /// Directories are silently skipped.
 fn build_file_requests(
@@ -922,171 +922,6 @@ fn build_file_requests(
     requests
 }
 
-fn parse_file_location(input: &str) -> FileLocation {
-    use std::path::{Component, Path};
-
-    let empty = FileLocation {
-        path: PathBuf::from(input),
-        line: None,
-        column: None,
-        end_line: None,
-        end_column: None,
-        message: None,
-    };
-
-    let full_path = PathBuf::from(input);
-
-    // If the full path exists as a file, use it directly
-    // This handles edge cases like files named "foo:10"
-    if full_path.is_file() {
-        return FileLocation {
-            path: full_path,
-            ..empty
-        };
-    }
-
-    // Extract message from @"..." suffix (before parsing positions)
-    let (input_no_msg, message) = extract_message_suffix(input);
-
-    // Check if the path has a Windows drive prefix using std::path
-    let has_prefix = Path::new(input_no_msg)
-        .components()
-        .next()
-        .map(|c| matches!(c, Component::Prefix(_)))
-        .unwrap_or(false);
-
-    // Calculate where to start looking for :line:col
-    // For Windows paths with prefix (e.g., "C:"), skip past the drive letter and colon
-    let search_start = if has_prefix {
-        // Find the first colon (the drive letter separator) and skip it
-        input_no_msg.find(':').map(|i| i + 1).unwrap_or(0)
-    } else {
-        0
-    };
-
-    // Find the last colon(s) that could be line:col
-    let suffix = &input_no_msg[search_start..];
-
-    // Check if there's a range (contains '-' in the location suffix, not in the path)
-    // We need to find the first colon that starts the location suffix, then check for '-'
-    if let Some(first_colon) = suffix.find(':') {
-        let location_part = &suffix[first_colon + 1..];
-        if location_part.contains('-') {
-            // Range syntax: try to parse as L-EL or L:C-EL:EC
-            let path_part = &suffix[..first_colon];
-            let path_str = if has_prefix {
-                format!("{}{}", &input_no_msg[..search_start], path_part)
-            } else {
-                path_part.to_string()
-            };
-
-            if let Some(result) =
-                parse_range(location_part, PathBuf::from(path_str), message.clone())
-            {
-                return result;
-            }
-        }
-    }
-
-    // No range — fall back to standard :line or :line:col parsing
-    let parts: Vec<&str> = suffix.rsplitn(3, ':').collect();
-
-    match parts.as_slice() {
-        [maybe_col, maybe_line, rest] => {
-            if let (Ok(line), Ok(col)) = (maybe_line.parse::<usize>(), maybe_col.parse::<usize>()) {
-                let path_str = if has_prefix {
-                    format!("{}{}", &input_no_msg[..search_start], rest)
-                } else {
-                    rest.to_string()
-                };
-                return FileLocation {
-                    path: PathBuf::from(path_str),
-                    line: Some(line),
-                    column: Some(col),
-                    message,
-                    ..empty
-                };
-            }
-        }
-        [maybe_line, rest] => {
-            if let Ok(line) = maybe_line.parse::<usize>() {
-                let path_str = if has_prefix {
-                    format!("{}{}", &input_no_msg[..search_start], rest)
-                } else {
-                    rest.to_string()
-                };
-                return FileLocation {
-                    path: PathBuf::from(path_str),
-                    line: Some(line),
-                    message,
-                    ..empty
-                };
-            }
-        }
-        _ => {}
-    }
-
-    // No valid suffix found, treat the whole thing as a path
-    FileLocation {
-        path: PathBuf::from(input_no_msg),
-        message,
-        ..empty
-    }
-}
-
-/// Extract a @"message" suffix from a file location string.
-/// Returns (remaining_input, optional_message).
-fn extract_message_suffix(input: &str) -> (&str, Option<String>) {
-    // Look for @" pattern — the message is everything between the quotes
-    if let Some(at_pos) = input.rfind("@\"") {
-        if input.ends_with('"') && input.len() > at_pos + 2 {
-            let msg = &input[at_pos + 2..input.len() - 1];
-            // Unescape \" within the message
-            let msg = msg.replace("\\\"", "\"");
-            return (&input[..at_pos], Some(msg));
-        }
-    }
-    (input, None)
-}
-
-/// Parse a range location suffix like "13-16" or "13:17-21:1".
-/// Returns a FileLocation if successful.
-fn parse_range(location: &str, path: PathBuf, message: Option<String>) -> Option<FileLocation> {
-    let parts: Vec<&str> = location.splitn(2, '-').collect();
-    if parts.len() != 2 {
-        return None;
-    }
-
-    let start_part = parts[0];
-    let end_part = parts[1];
-
-    // Parse start: either "L" or "L:C"
-    let (start_line, start_col) = parse_line_col(start_part)?;
-    // Parse end: either "EL" or "EL:EC"
-    let (end_line, end_col) = parse_line_col(end_part)?;
-
-    Some(FileLocation {
-        path,
-        line: Some(start_line),
-        column: start_col,
-        end_line: Some(end_line),
-        end_column: end_col,
-        message,
-    })
-}
-
-/// Parse "L" or "L:C" into (line, optional_column).
-fn parse_line_col(s: &str) -> Option<(usize, Option<usize>)> {
-    if let Some((line_str, col_str)) = s.split_once(':') {
-        let line = line_str.parse::<usize>().ok()?;
-        let col = col_str.parse::<usize>().ok()?;
-        Some((line, Some(col)))
-    } else {
-        let line = s.parse::<usize>().ok()?;
-        Some((line, None))
-    }
-}
-
 /// Split a remote `path[:line[:col]]` tail.  Only strips the numeric
 /// suffixes — anything non-numeric keeps the full string as the path
 /// (no partial stripping).  Shared between scp-style and `ssh://`
@@ -2896,6 +2731,115 @@ fn run_open_files_command(
     Ok(())
 }
 
+/// Build the params of a remote-control request from the command line.
+///
+/// A single JSON object is sent as is. Otherwise the arguments fill the
+/// method's main parameter, so `send . action save` and
+/// `send . subscribe after_file_save` need no quoting.
+fn remote_request_params(method: &str, arguments: &[String]) -> serde_json::Value {
+    use serde_json::{json, Value};
+
+    if let [single] = arguments {
+        if let Ok(value @ Value::Object(_)) = serde_json::from_str(single) {
+            return value;
+        }
+    }
+    let joined = arguments.join(" ");
+    match method {
+        _ if arguments.is_empty() => Value::Null,
+        "action" | "command" => json!({ "name": joined }),
+        "insert" => json!({ "text": joined }),
+        "eval" => json!({ "code": joined }),
+        "subscribe" | "unsubscribe" => json!({ "events": arguments }),
+        _ => Value::Null,
+    }
+}
+
+/// Send a remote-control request to a running session and print the
+/// result as JSON. After `subscribe`, keep printing notifications (one
+/// JSON object per line) until the session ends.
+fn run_send_command(
+    session_name: Option<&str>,
+    method: &str,
+    arguments: &[String],
+) -> AnyhowResult<()> {
+    use fresh::server::protocol::{
+        ClientControl, ClientHello, ServerControl, TermSize, PROTOCOL_VERSION,
+    };
+
+    let socket_paths = resolve_session(session_name)?;
+    socket_paths.cleanup_if_stale();
+    if !socket_paths.is_server_alive() {
+        anyhow::bail!("No session running. Start one with: fresh -a");
+    }
+
+    let conn = fresh::server::ipc::ClientConnection::connect(&socket_paths)?;
+    let hello = ClientHello::new(TermSize::new(80, 24)); // Size doesn't matter, we're not rendering
+    conn.write_control(&serde_json::to_string(&ClientControl::Hello(hello))?)?;
+    let response = conn
+        .read_control()?
+        .ok_or_else(|| anyhow::anyhow!("Server closed connection during handshake"))?;
+    match serde_json::from_str::<ServerControl>(&response)? {
+        ServerControl::Hello(server_hello) => {
+            if server_hello.protocol_version != PROTOCOL_VERSION {
+                anyhow::bail!(
+                    "Version mismatch: server is v{}",
+                    server_hello.server_version
+                );
+            }
+        }
+        ServerControl::VersionMismatch(mismatch) => {
+            anyhow::bail!("Version mismatch: server is v{}", mismatch.server_version);
+        }
+        ServerControl::Error { message } => anyhow::bail!("Server error: {}", message),
+        _ => anyhow::bail!("Unexpected server response"),
+    }
+
+    let request = ClientControl::Request {
+        id: 1,
+        method: method.to_string(),
+        params: remote_request_params(method, arguments),
+    };
+    conn.write_control(&serde_json::to_string(&request)?)?;
+
+    let mut subscribed = false;
+    while let Some(line) = conn.read_control()? {
+        let Ok(msg) = serde_json::from_str::<ServerControl>(&line) else {
+            continue;
+        };
+        match msg {
+            ServerControl::Response {
+                id: 1,
+                result,
+                error,
+            } => {
+                if let Some(error) = error {
+                    anyhow::bail!("{}", error);
+                }
+                match result {
+                    Some(result) if !result.is_null() && method != "subscribe" => {
+                        println!("{}", serde_json::to_string_pretty(&result)?);
+                    }
+                    _ => {}
+                }
+                if method != "subscribe" {
+                    return Ok(());
+                }
+                subscribed = true;
+            }
+            ServerControl::Notification { event, params } if subscribed => {
+                println!(
+                    "{}",
+                    serde_json::json!({ "event": event, "params": params })
+                );
+            }
+            ServerControl::Quit { .. } => break,
+            _ => {} // Ignore other messages
+        }
+    }
+    Ok(())
+}
+
 /// Attach to an existing session, starting a server if needed
 fn run_attach_command(args: &Args) -> AnyhowResult<()> {
     run_attach(args.session_name.as_deref(), &args.files)
@@ -3129,6 +3073,7 @@ fn is_interactive_launch(args: &Args) -> bool {
         && !args.list_sessions
         && args.kill.is_none()
         && args.open_files_in_session.is_none()
+        && args.send_to_session.is_none()
         && args.init.is_none()
         && !args.list_grammars
         && !args.dump_config
@@ -3197,6 +3142,9 @@ fn run_if_subcommand(args: &Args) -> Option<AnyhowResult<()>> {
             *wait,
         ));
     }
+    if let Some((session_name, method, arguments)) = &args.send_to_session {
+        return Some(run_send_command(session_name.as_deref(), method, arguments));
+    }
     if args.attach {
         return Some(run_attach_command(args));
     }
@@ -4252,6 +4200,7 @@ fn coalesce_mouse_moves(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fresh::primitives::file_location::extract_message_suffix;
 
     #[test]
     fn test_parse_file_location_simple_path() {
@@ -4639,6 +4588,24 @@ mod tests {
         assert!(extract_ssh_url_from_files(&files).is_err());
     }
 
+    #[test]
+    fn test_remote_request_params_shorthand_and_json() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            remote_request_params("action", &args(&["save"])),
+            serde_json::json!({"name": "save"})
+        );
+        assert_eq!(
+            remote_request_params("subscribe", &args(&["after_file_save", "buffer_closed"])),
+            serde_json::json!({"events": ["after_file_save", "buffer_closed"]})
+        );
+        assert_eq!(
+            remote_request_params("insert", &args(&[r#"{"text": "hi"}"#])),
+            serde_json::json!({"text": "hi"})
+        );
+        assert!(remote_request_params("buffers", &[]).is_null());
+    }
+
     #[test]
     fn test_parse_ssh_url_arg_accepts_valid_url() {
         let rl = parse_ssh_url_arg("ssh://alice@host:2222/path").unwrap();
@@ -0,0 +1,236 @@
+//! Geometry of rectangular (block) selections.
+//!
+//! A block spans a range of lines and a range of *display* columns, so its
+//! edges stay straight across tabs and double-width characters. It may
+//! extend past the end of short lines ("virtual space"); text typed or
+//! pasted there is padded with spaces up to the block's left edge.
+//!
+//! The block anchor is stored on the cursor as a [`Position2D`] in display
+//! columns. The cursor's own display column is its `sticky_column`, which
+//! may lie beyond the end of the line while the byte position is clamped to
+//! the line end.
+
+use std::ops::{Range, RangeInclusive};
+
+use crate::model::buffer::Buffer;
+use crate::model::cursor::{Cursor, Position2D, SelectionMode};
+use crate::primitives::display_width::char_width;
+
+/// A block of lines (inclusive) by display columns (end-exclusive).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockRect {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub start_col: usize,
+    pub end_col: usize,
+}
+
+/// The part of one line covered by a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineSpan {
+    /// Absolute byte range of the covered text; empty when the line ends
+    /// before the block's left edge
+    pub range: Range<usize>,
+    /// Spaces needed at `range.start` to reach the block's left edge
+    pub padding: usize,
+}
+
+impl BlockRect {
+    /// The block selected by `cursor`, if it is in block mode.
+    pub fn from_cursor(buffer: &Buffer, cursor: &Cursor, tab_size: usize) -> Option<Self> {
+        if !cursor.has_block_selection() {
+            return None;
+        }
+        let anchor = cursor.block_anchor?;
+        let current = block_cursor_position(buffer, cursor, tab_size);
+        Some(Self {
+            start_line: anchor.line.min(current.line),
+            end_line: anchor.line.max(current.line),
+            start_col: anchor.column.min(current.column),
+            end_col: anchor.column.max(current.column),
+        })
+    }
+
+    pub fn lines(&self) -> RangeInclusive<usize> {
+        self.start_line..=self.end_line
+    }
+
+    /// The part of `line` covered by this block.
+    pub fn span(&self, buffer: &Buffer, line: usize, tab_size: usize) -> LineSpan {
+        line_span(buffer, line, self.start_col, self.end_col, tab_size)
+    }
+}
+
+/// Line and display column of a cursor. A block-mode cursor at the end of
+/// its line reports its `sticky_column` when that lies in virtual space.
+pub fn block_cursor_position(buffer: &Buffer, cursor: &Cursor, tab_size: usize) -> Position2D {
+    let line = buffer.get_line_number(cursor.position);
+    let line_start = buffer.line_start_offset(line).unwrap_or(0);
+    let text = line_text(buffer, line);
+    let byte = cursor.position.saturating_sub(line_start).min(text.len());
+    let mut column = display_column(&text, byte, tab_size);
+    if byte == text.len() && cursor.selection_mode == SelectionMode::Block {
+        column = column.max(cursor.sticky_column);
+    }
+    Position2D { line, column }
+}
+
+/// Byte offset of a block position, clamped to the end of the line when
+/// the column lies in virtual space.
+pub fn block_position_to_byte(buffer: &Buffer, pos: Position2D, tab_size: usize) -> usize {
+    let line_start = buffer.line_start_offset(pos.line).unwrap_or(0);
+    line_start + byte_at_column(&line_text(buffer, pos.line), pos.column, tab_size)
+}
+
+/// Text of a line without its line ending.
+pub fn line_text(buffer: &Buffer, line: usize) -> String {
+    let bytes = buffer.get_line(line).unwrap_or_default();
+    let mut text = String::from_utf8_lossy(&bytes).into_owned();
+    if text.ends_with('\n') {
+        text.pop();
+    }
+    if text.ends_with('\r') {
+        text.pop();
+    }
+    text
+}
+
+/// Display width of `c` when it starts at column `col`; tabs run to the
+/// next tab stop.
+fn cell_width(c: char, col: usize, tab_size: usize) -> usize {
+    if c == '\t' {
+        let tab_size = tab_size.max(1);
+        tab_size - col % tab_size
+    } else {
+        char_width(c)
+    }
+}
+
+/// Display column at byte offset `byte` of `text`.
+pub fn display_column(text: &str, byte: usize, tab_size: usize) -> usize {
+    let mut col = 0;
+    for (i, c) in text.char_indices() {
+        if i >= byte {
+            break;
+        }
+        col += cell_width(c, col, tab_size);
+    }
+    col
+}
+
+/// Byte offset of the first character starting at or after display column
+/// `column`, or `text.len()` when the text ends before it.
+pub fn byte_at_column(text: &str, column: usize, tab_size: usize) -> usize {
+    let mut col = 0;
+    for (i, c) in text.char_indices() {
+        if col >= column {
+            return i;
+        }
+        col += cell_width(c, col, tab_size);
+    }
+    text.len()
+}
+
+/// The column one character to the right of `column`. Past the end of the
+/// text this steps one cell into virtual space.
+pub fn next_column(text: &str, column: usize, tab_size: usize) -> usize {
+    let mut col = 0;
+    for c in text.chars() {
+        let next = col + cell_width(c, col, tab_size);
+        if next > column {
+            return next;
+        }
+        col = next;
+    }
+    column + 1
+}
+
+/// The column one character to the left of `column`: the start of the
+/// character covering `column - 1`.
+pub fn prev_column(text: &str, column: usize, tab_size: usize) -> usize {
+    let target = column.saturating_sub(1);
+    let mut col = 0;
+    for c in text.chars() {
+        let next = col + cell_width(c, col, tab_size);
+        if next > target {
+            return col;
+        }
+        col = next;
+    }
+    target
+}
+
+/// The part of `line` covered by display columns `start_col..end_col`: the
+/// characters starting inside that range.
+pub fn line_span(
+    buffer: &Buffer,
+    line: usize,
+    start_col: usize,
+    end_col: usize,
+    tab_size: usize,
+) -> LineSpan {
+    let line_start = buffer.line_start_offset(line).unwrap_or(0);
+    let text = line_text(buffer, line);
+    let start = byte_at_column(&text, start_col, tab_size);
+    let end = byte_at_column(&text, end_col, tab_size).max(start);
+    let width = display_column(&text, text.len(), tab_size);
+    LineSpan {
+        range: line_start + start..line_start + end,
+        padding: start_col.saturating_sub(width),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_respect_tabs_and_wide_chars() {
+        // "a\tb": tab runs from column 1 to the tab stop at 4
+        assert_eq!(display_column("a\tb", 2, 4), 4);
+        assert_eq!(byte_at_column("a\tb", 2, 4), 2);
+        // "你好x": each CJK character is two cells wide
+        assert_eq!(display_column("你好x", 6, 4), 4);
+        assert_eq!(byte_at_column("你好x", 1, 4), 3);
+        assert_eq!(next_column("你好x", 0, 4), 2);
+        assert_eq!(prev_column("你好x", 4, 4), 2);
+        assert_eq!(prev_column("你好x", 3, 4), 2);
+        // Virtual space past the end of the text
+        assert_eq!(next_column("ab", 2, 4), 3);
+        assert_eq!(prev_column("ab", 5, 4), 4);
+    }
+
+    #[test]
+    fn spans_pad_short_lines() {
+        let buffer = Buffer::from_str_test("abcdef\nab\n你好世界");
+        let rect = BlockRect {
+            start_line: 0,
+            end_line: 2,
+            start_col: 3,
+            end_col: 5,
+        };
+        assert_eq!(
+            rect.span(&buffer, 0, 4),
+            LineSpan {
+                range: 3..5,
+                padding: 0
+            }
+        );
+        // "ab" ends one column short of the block
+        assert_eq!(
+            rect.span(&buffer, 1, 4),
+            LineSpan {
+                range: 9..9,
+                padding: 1
+            }
+        );
+        // "世" starts at column 4, inside the block; "好" starts before it
+        assert_eq!(
+            rect.span(&buffer, 2, 4),
+            LineSpan {
+                range: 16..19,
+                padding: 0
+            }
+        );
+    }
+}
@@ -194,6 +194,67 @@ pub struct BufferSnapshot {
     pub next_buffer_id: usize,
 }
 
+impl BufferSnapshot {
+    /// Bytes of the snapshot's content in `offset..offset + bytes`.
+    /// Returns None if any buffer in the range is unloaded
+    pub fn get_text_range(&self, offset: usize, bytes: usize) -> Option<Vec<u8>> {
+        read_text_range(&self.piece_tree, &self.buffers, offset, bytes)
+    }
+}
+
+/// Read `bytes` bytes at `offset` through a piece tree.
+/// Returns None if any buffer in the range is unloaded
+fn read_text_range(
+    piece_tree: &PieceTree,
+    buffers: &[StringBuffer],
+    offset: usize,
+    bytes: usize,
+) -> Option<Vec<u8>> {
+    if bytes == 0 {
+        return Some(Vec::new());
+    }
+
+    let mut result = Vec::with_capacity(bytes);
+    let end_offset = offset + bytes;
+    let mut collected = 0;
+
+    // Use the efficient piece iterator (single O(log n) traversal + O(N) iteration)
+    for piece_view in piece_tree.iter_pieces_in_range(offset, end_offset) {
+        let buffer_id = piece_view.location.buffer_id();
+        if let Some(buffer) = buffers.get(buffer_id) {
+            // Calculate the range to read from this piece
+            let piece_start_in_doc = piece_view.doc_offset;
+            let piece_end_in_doc = piece_view.doc_offset + piece_view.bytes;
+
+            // Clip to the requested range
+            let read_start = offset.max(piece_start_in_doc);
+            let read_end = end_offset.min(piece_end_in_doc);
+
+            if read_end > read_start {
+                let offset_in_piece = read_start - piece_start_in_doc;
+                let bytes_to_read = read_end - read_start;
+
+                let buffer_start = piece_view.buffer_offset + offset_in_piece;
+                let buffer_end = buffer_start + bytes_to_read;
+
+                // Return None if buffer is unloaded (type-safe)
+                let data = buffer.get_data()?;
+
+                if buffer_end <= data.len() {
+                    result.extend_from_slice(&data[buffer_start..buffer_end]);
+                    collected += bytes_to_read;
+
+                    if collected >= bytes {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    Some(result)
+}
+
 impl TextBuffer {
     /// Create a new text buffer with the given filesystem implementation.
     /// Note: large_file_threshold is ignored in the new implementation
@@ -1169,49 +1230,7 @@ impl TextBuffer {
     /// Returns None if any buffer in the range is unloaded
     /// PRIVATE: External code should use get_text_range_mut() which handles lazy loading
     fn get_text_range(&self, offset: usize, bytes: usize) -> Option<Vec<u8>> {
-        if bytes == 0 {
-            return Some(Vec::new());
-        }
-
-        let mut result = Vec::with_capacity(bytes);
-        let end_offset = offset + bytes;
-        let mut collected = 0;
-
-        // Use the efficient piece iterator (single O(log n) traversal + O(N) iteration)
-        for piece_view in self.piece_tree.iter_pieces_in_range(offset, end_offset) {
-            let buffer_id = piece_view.location.buffer_id();
-            if let Some(buffer) = self.buffers.get(buffer_id) {
-                // Calculate the range to read from this piece
-                let piece_start_in_doc = piece_view.doc_offset;
-                let piece_end_in_doc = piece_view.doc_offset + piece_view.bytes;
-
-                // Clip to the requested range
-                let read_start = offset.max(piece_start_in_doc);
-                let read_end = end_offset.min(piece_end_in_doc);
-
-                if read_end > read_start {
-                    let offset_in_piece = read_start - piece_start_in_doc;
-                    let bytes_to_read = read_end - read_start;
-
-                    let buffer_start = piece_view.buffer_offset + offset_in_piece;
-                    let buffer_end = buffer_start + bytes_to_read;
-
-                    // Return None if buffer is unloaded (type-safe)
-                    let data = buffer.get_data()?;
-
-                    if buffer_end <= data.len() {
-                        result.extend_from_slice(&data[buffer_start..buffer_end]);
-                        collected += bytes_to_read;
-
-                        if collected >= bytes {
-                            break;
-                        }
-                    }
-                }
-            }
-        }
-
-        Some(result)
+        read_text_range(&self.piece_tree, &self.buffers, offset, bytes)
     }
 
     /// Get text from a byte offset range with lazy loading
@@ -29,13 +29,15 @@ pub struct Cursor {
     pub anchor: Option<usize>,
 
     /// Desired column for vertical navigation
-    /// When moving up/down, try to stay in this column
+    /// When moving up/down, try to stay in this column.
+    /// In block mode this is the cursor's display column, which may lie
+    /// past the end of the line (see `model::block_selection`)
     pub sticky_column: usize,
 
     /// Selection mode (normal or block)
     pub selection_mode: SelectionMode,
 
-    /// Block selection anchor position (line, column) for rectangular selections
+    /// Block selection anchor position (line, display column) for rectangular selections
     /// Only used when selection_mode is Block
     pub block_anchor: Option<Position2D>,
 
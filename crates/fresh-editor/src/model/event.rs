@@ -500,6 +500,83 @@ impl Event {
         }
     }
 
+    /// Self-contained form of a buffer edit for persisting undo history.
+    ///
+    /// `BulkEdit` snapshots are not serialized, so a bulk edit is rewritten
+    /// as a batch of plain deletes and inserts; batches keep only their
+    /// buffer edits. Returns None when the edit can't be represented (no
+    /// snapshots, or text that isn't UTF-8) and for non-buffer events.
+    pub fn to_persistable(&self) -> Option<Self> {
+        match self {
+            Self::Insert { .. } | Self::Delete { .. } => Some(self.clone()),
+            Self::Batch {
+                events,
+                description,
+            } => {
+                let events = events
+                    .iter()
+                    .filter(|e| e.modifies_buffer())
+                    .map(|e| e.to_persistable())
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Self::Batch {
+                    events,
+                    description: description.clone(),
+                })
+            }
+            Self::BulkEdit {
+                old_snapshot: Some(old_snapshot),
+                new_snapshot: Some(new_snapshot),
+                description,
+                edits,
+                ..
+            } => {
+                // Edits are sorted descending by position in the old content
+                // and must not overlap to be replayed one by one
+                if edits.is_empty()
+                    || edits
+                        .windows(2)
+                        .any(|w| w[1].0 + w[1].1 > w[0].0 || w[1].0 == w[0].0)
+                {
+                    return None;
+                }
+                let text_at = |snapshot: &BufferSnapshot, offset: usize, len: usize| {
+                    String::from_utf8(snapshot.get_text_range(offset, len)?).ok()
+                };
+
+                // Position of each edit in the new content: shifted by the
+                // edits before it
+                let mut shift: isize = edits
+                    .iter()
+                    .map(|&(_, del, ins)| ins as isize - del as isize)
+                    .sum();
+                let mut events = Vec::with_capacity(edits.len() * 2);
+                for &(pos, del_len, ins_len) in edits {
+                    shift -= ins_len as isize - del_len as isize;
+                    let new_pos = pos.checked_add_signed(shift)?;
+                    if del_len > 0 {
+                        events.push(Self::Delete {
+                            range: pos..pos + del_len,
+                            deleted_text: text_at(old_snapshot, pos, del_len)?,
+                            cursor_id: CursorId::UNDO_SENTINEL,
+                        });
+                    }
+                    if ins_len > 0 {
+                        events.push(Self::Insert {
+                            position: pos,
+                            text: text_at(new_snapshot, new_pos, ins_len)?,
+                            cursor_id: CursorId::UNDO_SENTINEL,
+                        });
+                    }
+                }
+                Some(Self::Batch {
+                    events,
+                    description: description.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+
     /// Returns true if this event modifies the buffer content
     pub fn modifies_buffer(&self) -> bool {
         match self {
@@ -931,6 +1008,24 @@ impl EventLog {
         self.snapshots.clear();
     }
 
+    /// Events undo can walk back through from the current position, oldest
+    /// first.
+    pub fn undo_history(&self) -> impl DoubleEndedIterator<Item = &Event> {
+        self.entries[..self.current_index]
+            .iter()
+            .map(|entry| &entry.event)
+    }
+
+    /// Replace the log with `events` restored from an earlier session.
+    /// The buffer content is the result of applying them, so the end of the
+    /// restored history is the saved position and nothing can be redone.
+    pub fn restore_history(&mut self, events: Vec<Event>) {
+        self.entries = events.into_iter().map(LogEntry::new).collect();
+        self.current_index = self.entries.len();
+        self.snapshots.clear();
+        self.saved_at_index = Some(self.current_index);
+    }
+
     /// Save event log to JSON Lines format
     pub fn save_to_file(&self, path: &std::path::Path) -> std::io::Result<()> {
         use std::io::Write;
@@ -1326,4 +1421,84 @@ mod tests {
             "Should not be at saved position after undo + new edit"
         );
     }
+
+    #[test]
+    fn test_restored_history_is_saved_and_undoable() {
+        let mut log = EventLog::new();
+        log.append(Event::Insert {
+            position: 0,
+            text: "stale".to_string(),
+            cursor_id: CursorId(0),
+        });
+
+        log.restore_history(vec![
+            Event::Insert {
+                position: 0,
+                text: "ab".to_string(),
+                cursor_id: CursorId(0),
+            },
+            Event::Delete {
+                range: 0..1,
+                deleted_text: "a".to_string(),
+                cursor_id: CursorId(0),
+            },
+        ]);
+        assert!(log.is_at_saved_position());
+        assert!(!log.can_redo());
+        assert_eq!(log.undo_history().count(), 2);
+
+        let undone = log.undo();
+        assert!(matches!(
+            undone.as_slice(),
+            [(Event::Insert { position: 0, text, .. }, _)] if text == "a"
+        ));
+        assert!(!log.is_at_saved_position());
+    }
+
+    #[test]
+    fn test_bulk_edit_persists_as_plain_edits() {
+        use crate::model::buffer::TextBuffer;
+
+        // "one two three" -> "1 two 3!!": replace both ends in one bulk edit
+        let mut buffer = TextBuffer::from_str_test("one two three");
+        let old_snapshot = buffer.snapshot_buffer_state();
+        buffer.apply_bulk_edits(&[(8, 5, "3!!"), (0, 3, "1")]);
+        let new_snapshot = buffer.snapshot_buffer_state();
+        let bulk = Event::BulkEdit {
+            old_snapshot: Some(old_snapshot),
+            new_snapshot: Some(new_snapshot),
+            old_cursors: vec![],
+            new_cursors: vec![],
+            description: "Replace".to_string(),
+            edits: vec![(8, 5, 3), (0, 3, 1)],
+            displaced_markers: vec![],
+        };
+
+        let Some(Event::Batch { events, .. }) = bulk.to_persistable() else {
+            panic!("bulk edit should convert to a batch");
+        };
+        let mut text = "one two three".to_string();
+        for event in &events {
+            match event {
+                Event::Delete {
+                    range,
+                    deleted_text,
+                    ..
+                } => {
+                    assert_eq!(&text[range.clone()], deleted_text);
+                    text.replace_range(range.clone(), "");
+                }
+                Event::Insert {
+                    position, text: t, ..
+                } => text.insert_str(*position, t),
+                other => panic!("unexpected event {other:?}"),
+            }
+        }
+        assert_eq!(text, "1 two 3!!");
+
+        // Without snapshots there is nothing to rebuild the text from
+        let serialized: Event =
+            serde_json::from_str(&serde_json::to_string(&bulk).unwrap()).unwrap();
+        assert!(serialized.to_persistable().is_none());
+    }
 }
@@ -3,7 +3,13 @@
 //! This module provides a simple but robust diff algorithm that correctly handles
 //! insertions, deletions, and modifications. It uses a longest common subsequence (LCS)
 //! approach to identify which lines are unchanged, then marks the ranges that differ.
+//!
+//! [`minimal_replacements`] turns two versions of a text into the smallest set
+//! of byte-range replacements that rewrites one into the other, so tool output
+//! (formatters, on-save actions) can be applied without touching the parts of
+//! the buffer that did not change.
 
+use std::collections::HashMap;
 use std::ops::Range;
 
 /// Type of change detected for a line range
@@ -299,6 +305,212 @@ pub fn merge_ranges(ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
     merged
 }
 
+/// Replace the bytes in `range` of the old text with `text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    /// Byte range in the old text
+    pub range: Range<usize>,
+    /// New text for the range
+    pub text: String,
+}
+
+/// Most line insertions plus deletions the line diff of
+/// [`minimal_replacements`] searches for. Myers' algorithm keeps O(D²)
+/// state, so past this the differing middle is replaced as one block.
+const MAX_LINE_EDIT_COST: usize = 1000;
+
+/// Compute the replacements that turn `old` into `new`, sorted by position
+/// and non-overlapping.
+///
+/// Identical leading and trailing lines are skipped first, which keeps the
+/// common case of a formatter touching a few lines of a large file cheap.
+/// The remaining lines are diffed with Myers' algorithm; each changed hunk
+/// is then narrowed to the characters that differ, line by line when the
+/// hunk replaces as many lines as it removes.
+pub fn minimal_replacements(old: &str, new: &str) -> Vec<Replacement> {
+    if old == new {
+        return vec![];
+    }
+
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+    let mut old_offsets = Vec::with_capacity(old_lines.len() + 1);
+    let mut offset = 0;
+    for line in &old_lines {
+        old_offsets.push(offset);
+        offset += line.len();
+    }
+    old_offsets.push(offset);
+
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old_lines[prefix..old_lines.len() - suffix];
+    let new_middle = &new_lines[prefix..new_lines.len() - suffix];
+
+    // Compare lines by interned id rather than by content
+    let mut ids = HashMap::new();
+    let old_ids = intern_lines(old_middle, &mut ids);
+    let new_ids = intern_lines(new_middle, &mut ids);
+
+    let mut matches = myers_matches(&old_ids, &new_ids, MAX_LINE_EDIT_COST).unwrap_or_default();
+    // Sentinel closing the last hunk
+    matches.push((old_middle.len(), new_middle.len()));
+
+    let mut replacements = Vec::new();
+    let (mut old_idx, mut new_idx) = (0, 0);
+    for (old_match, new_match) in matches {
+        if old_match > old_idx || new_match > new_idx {
+            let removed = &old_middle[old_idx..old_match];
+            let added = &new_middle[new_idx..new_match];
+            let start = old_offsets[prefix + old_idx];
+            if removed.len() == added.len() {
+                let mut line_start = start;
+                for (old_line, new_line) in removed.iter().zip(added) {
+                    replacements.extend(narrow_replacement(old_line, new_line, line_start));
+                    line_start += old_line.len();
+                }
+            } else {
+                replacements.extend(narrow_replacement(
+                    &removed.concat(),
+                    &added.concat(),
+                    start,
+                ));
+            }
+        }
+        old_idx = old_match + 1;
+        new_idx = new_match + 1;
+    }
+    replacements
+}
+
+/// Map each line to a small id shared by equal lines.
+fn intern_lines<'a>(lines: &[&'a str], ids: &mut HashMap<&'a str, u32>) -> Vec<u32> {
+    lines
+        .iter()
+        .map(|line| {
+            let next = ids.len() as u32;
+            *ids.entry(line).or_insert(next)
+        })
+        .collect()
+}
+
+/// The replacement of `old` (starting at byte `base`) by `new`, without
+/// their common leading and trailing characters.
+fn narrow_replacement(old: &str, new: &str, base: usize) -> Option<Replacement> {
+    if old == new {
+        return None;
+    }
+    let mut prefix = old
+        .bytes()
+        .zip(new.bytes())
+        .take_while(|(a, b)| a == b)
+        .count();
+    while !old.is_char_boundary(prefix) {
+        prefix -= 1;
+    }
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let mut suffix = old
+        .bytes()
+        .rev()
+        .zip(new.bytes().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    while !old.is_char_boundary(old.len() - suffix) {
+        suffix -= 1;
+    }
+    Some(Replacement {
+        range: base + prefix..base + old.len() - suffix,
+        text: new[prefix..new.len() - suffix].to_string(),
+    })
+}
+
+/// Matching index pairs of a shortest edit script between `a` and `b`
+/// (Myers' O(ND) algorithm), or `None` if the script needs more than
+/// `max_cost` insertions and deletions.
+fn myers_matches(a: &[u32], b: &[u32], max_cost: usize) -> Option<Vec<(usize, usize)>> {
+    let n = a.len() as isize;
+    let m = b.len() as isize;
+    let max = (n + m).min(max_cost as isize);
+    let offset = max + 1;
+    // Furthest x reached on each diagonal k = x - y, indexed by k + offset
+    let mut v = vec![0isize; 2 * max as usize + 3];
+    // Diagonals -d..=d of `v` after each step d, for backtracking
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=max {
+        let mut k = -d;
+        while k <= d {
+            let idx = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx] = x;
+            if x >= n && y >= m {
+                return Some(myers_backtrack(&trace, d, n, m));
+            }
+            k += 2;
+        }
+        trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+    }
+    None
+}
+
+/// Walk the trace of [`myers_matches`] back from `(n, m)` and collect the
+/// diagonal (matching) moves.
+fn myers_backtrack(trace: &[Vec<isize>], cost: isize, n: isize, m: isize) -> Vec<(usize, usize)> {
+    let mut matches = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for d in (1..=cost).rev() {
+        let prev = &trace[(d - 1) as usize];
+        let furthest = |k: isize| prev[(k + d - 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && furthest(k - 1) < furthest(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = furthest(prev_k);
+        let prev_y = prev_x - prev_k;
+        // Point after the insertion or deletion of this step
+        let (step_x, step_y) = if prev_k == k + 1 {
+            (prev_x, prev_y + 1)
+        } else {
+            (prev_x + 1, prev_y)
+        };
+        while x > step_x && y > step_y {
+            x -= 1;
+            y -= 1;
+            matches.push((x as usize, y as usize));
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    while x > 0 && y > 0 {
+        x -= 1;
+        y -= 1;
+        matches.push((x as usize, y as usize));
+    }
+    matches.reverse();
+    matches
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -486,6 +698,85 @@ mod tests {
         assert!(!diff.equal);
         assert_eq!(diff.changed_lines, vec![0..1]);
     }
+
+    /// Apply sorted replacements to `old`
+    pub(super) fn apply_replacements(old: &str, replacements: &[Replacement]) -> String {
+        let mut result = String::new();
+        let mut pos = 0;
+        for replacement in replacements {
+            assert!(replacement.range.start >= pos, "replacements overlap");
+            result.push_str(&old[pos..replacement.range.start]);
+            result.push_str(&replacement.text);
+            pos = replacement.range.end;
+        }
+        result.push_str(&old[pos..]);
+        result
+    }
+
+    #[test]
+    fn test_minimal_replacements_identical() {
+        assert!(minimal_replacements("a\nb\n", "a\nb\n").is_empty());
+    }
+
+    #[test]
+    fn test_minimal_replacements_only_touch_changed_characters() {
+        let old = "fn main() {\nlet x=1;\n    foo();\n}\n";
+        let new = "fn main() {\n    let x = 1;\n    foo();\n}\n";
+        let replacements = minimal_replacements(old, new);
+
+        assert_eq!(
+            replacements,
+            vec![Replacement {
+                range: 12..18,
+                text: "    let x = ".to_string(),
+            }]
+        );
+        assert_eq!(apply_replacements(old, &replacements), new);
+    }
+
+    #[test]
+    fn test_minimal_replacements_inserted_and_deleted_lines() {
+        let old = "a\nb\nc\nd\n";
+        let new = "a\nx\nb\nd\n";
+        let replacements = minimal_replacements(old, new);
+
+        assert_eq!(
+            replacements,
+            vec![
+                Replacement {
+                    range: 2..2,
+                    text: "x\n".to_string(),
+                },
+                Replacement {
+                    range: 4..6,
+                    text: String::new(),
+                },
+            ]
+        );
+        assert_eq!(apply_replacements(old, &replacements), new);
+    }
+
+    #[test]
+    fn test_minimal_replacements_keep_multibyte_characters_whole() {
+        let old = "caf\u{e9}\n";
+        let new = "caf\u{e8}\n";
+        let replacements = minimal_replacements(old, new);
+
+        assert_eq!(replacements.len(), 1);
+        assert_eq!(replacements[0].range, 3..5);
+        assert_eq!(apply_replacements(old, &replacements), new);
+    }
+
+    #[test]
+    fn test_minimal_replacements_fall_back_past_edit_cost_limit() {
+        let old: String = (0..3000).map(|i| format!("line {}\n", i)).collect();
+        let new: String = (0..3000).map(|i| format!("line {} \n", i)).collect();
+        let replacements = minimal_replacements(&old, &new);
+
+        // Still one small edit per line, not one giant replacement
+        assert_eq!(replacements.len(), 3000);
+        assert_eq!(apply_replacements(&old, &replacements), new);
+    }
 }
 
 #[cfg(test)]
@@ -577,6 +868,16 @@ mod proptests {
             }
         }
 
+        /// Applying the replacements always reproduces the new text
+        #[test]
+        fn minimal_replacements_reproduce_new_text(
+            old in "[ab\u{e9} \n]{0,40}",
+            new in "[ab\u{e9} \n]{0,40}"
+        ) {
+            let replacements = minimal_replacements(&old, &new);
+            prop_assert_eq!(tests::apply_replacements(&old, &replacements), new);
+        }
+
         /// Changed line ranges should not overlap and should be sorted
         #[test]
         fn changed_lines_are_sorted_and_non_overlapping(
@@ -10,7 +10,7 @@
 ///
 /// The Vec-based implementation is kept for compatibility and simplicity in
 /// situations where marker count is low (<100).
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::model::marker_tree::IntervalTree;
 
@@ -48,6 +48,9 @@ pub struct MarkerList {
     /// Track affinity for compatibility (though IntervalTree handles this through intervals)
     /// We don't strictly need this for the tree, but keep it for API compatibility
     _affinity_map: HashMap<MarkerId, bool>,
+
+    /// Markers that strictly keep left affinity (see `set_sticky_left`)
+    sticky_left: HashSet<MarkerId>,
 }
 
 impl MarkerList {
@@ -56,6 +59,7 @@ impl MarkerList {
         Self {
             tree: IntervalTree::new(),
             _affinity_map: HashMap::new(),
+            sticky_left: HashSet::new(),
         }
     }
 
@@ -97,6 +101,21 @@ impl MarkerList {
     pub fn delete(&mut self, id: MarkerId) {
         self.tree.delete(id.0);
         self._affinity_map.remove(&id);
+        self.sticky_left.remove(&id);
+    }
+
+    /// Make text inserted exactly at a marker's position go after it.
+    ///
+    /// The affinity passed to `create` is advisory: point markers move right
+    /// on an insert at their position. Ranges that must keep text typed at
+    /// their start out of whatever precedes them (snippet tabstops) pin
+    /// their start marker here. Each insert costs O(k) for k sticky markers.
+    pub fn set_sticky_left(&mut self, id: MarkerId, sticky: bool) {
+        if sticky {
+            self.sticky_left.insert(id);
+        } else {
+            self.sticky_left.remove(&id);
+        }
     }
 
     /// Move a marker to a new byte position, preserving its ID and affinity.
@@ -159,7 +178,16 @@ impl MarkerList {
             return;
         }
 
+        let pinned: Vec<MarkerId> = self
+            .sticky_left
+            .iter()
+            .copied()
+            .filter(|&id| self.get_position(id) == Some(position))
+            .collect();
         self.tree.adjust_for_edit(position as u64, length as i64);
+        for id in pinned {
+            self.set_position(id, position);
+        }
     }
 
     /// Adjust all markers for a deletion
@@ -407,6 +435,28 @@ mod tests {
         list.check_invariants().unwrap();
     }
 
+    #[test]
+    fn test_insert_at_sticky_left_marker() {
+        let mut list = MarkerList::new();
+        let start = list.create(10, true);
+        let end = list.create(10, false);
+        list.set_sticky_left(start, true);
+
+        // The empty range [10, 10] grows to cover the inserted text
+        list.adjust_for_insert(10, 5);
+        assert_eq!(list.get_position(start), Some(10));
+        assert_eq!(list.get_position(end), Some(15));
+
+        // Inserts elsewhere still shift it
+        list.adjust_for_insert(0, 2);
+        assert_eq!(list.get_position(start), Some(12));
+
+        list.set_sticky_left(start, false);
+        list.adjust_for_insert(12, 1);
+        assert_eq!(list.get_position(start), Some(13));
+        list.check_invariants().unwrap();
+    }
+
     #[test]
     fn test_insert_at_marker_right_affinity() {
         let mut list = MarkerList::new();
@@ -2,6 +2,7 @@
 //!
 //! This module contains pure data structures with minimal external dependencies.
 
+pub mod block_selection;
 pub mod buffer;
 pub mod buffer_position;
 pub mod composite_buffer;
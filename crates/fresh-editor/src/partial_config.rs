@@ -4,13 +4,15 @@
 //! enabling a 4-level overlay architecture (System → User → Project → Session).
 
 use crate::config::{
-    ClipboardConfig, CursorStyle, FileBrowserConfig, FileExplorerConfig, FormatterConfig,
-    Keybinding, KeybindingMapName, KeymapConfig, LanguageConfig, LineEndingOption, OnSaveAction,
-    PluginConfig, TerminalConfig, ThemeName, WarningsConfig,
+    ClipboardConfig, CursorStyle, DebugAdapterConfig, DebugConfiguration, FileBrowserConfig,
+    FileExplorerConfig, FormatterConfig, Keybinding, KeybindingMapName, KeymapConfig,
+    LanguageConfig, LineEndingOption, OnSaveAction, PluginConfig, TaskConfig, TerminalConfig,
+    ThemeName, WarningsConfig,
 };
 use crate::types::LspLanguageConfig;
+use fresh_core::permissions::Capability;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Trait for merging configuration layers.
 /// Higher precedence values (self) override lower precedence (other).
@@ -92,6 +94,8 @@ pub struct PartialConfig {
     pub warnings: Option<PartialWarningsConfig>,
     pub plugins: Option<HashMap<String, PartialPluginConfig>>,
     pub packages: Option<PartialPackagesConfig>,
+    pub debug: Option<PartialDebugConfig>,
+    pub tasks: Option<HashMap<String, TaskConfig>>,
 }
 
 impl Merge for PartialConfig {
@@ -109,6 +113,7 @@ impl Merge for PartialConfig {
         merge_partial(&mut self.terminal, &other.terminal);
         merge_partial(&mut self.warnings, &other.warnings);
         merge_partial(&mut self.packages, &other.packages);
+        merge_partial(&mut self.debug, &other.debug);
 
         // Lists: higher precedence replaces (per design doc)
         self.keybindings.merge_from(&other.keybindings);
@@ -119,6 +124,7 @@ impl Merge for PartialConfig {
         self.default_language.merge_from(&other.default_language);
         merge_hashmap(&mut self.lsp, &other.lsp);
         merge_hashmap(&mut self.universal_lsp, &other.universal_lsp);
+        merge_hashmap(&mut self.tasks, &other.tasks);
         merge_hashmap_recursive(&mut self.plugins, &other.plugins);
 
         self.active_keybinding_map
@@ -161,6 +167,7 @@ pub struct PartialEditorConfig {
     pub large_file_threshold_bytes: Option<u64>,
     pub estimated_line_length: Option<usize>,
     pub enable_inlay_hints: Option<bool>,
+    pub enable_code_lens: Option<bool>,
     pub enable_semantic_tokens_full: Option<bool>,
     pub diagnostics_inline_text: Option<bool>,
     pub recovery_enabled: Option<bool>,
@@ -168,6 +175,8 @@ pub struct PartialEditorConfig {
     pub auto_save_enabled: Option<bool>,
     pub auto_save_interval_secs: Option<u32>,
     pub hot_exit: Option<bool>,
+    pub persistent_undo: Option<bool>,
+    pub persistent_undo_max_kb: Option<u32>,
     pub restore_previous_session: Option<bool>,
     pub skip_session_restore_when_files_passed: Option<bool>,
     pub auto_create_empty_buffer_on_last_buffer_close: Option<bool>,
@@ -178,9 +187,14 @@ pub struct PartialEditorConfig {
     pub auto_revert_poll_interval_ms: Option<u64>,
     pub read_concurrency: Option<usize>,
     pub file_tree_poll_interval_ms: Option<u64>,
+    pub plugin_call_timeout_ms: Option<u64>,
+    pub plugin_load_timeout_ms: Option<u64>,
+    pub plugin_memory_limit_mb: Option<usize>,
     pub default_line_ending: Option<LineEndingOption>,
     pub trim_trailing_whitespace_on_save: Option<bool>,
     pub ensure_final_newline_on_save: Option<bool>,
+    pub spell_check: Option<bool>,
+    pub spell_check_language: Option<String>,
     pub highlight_matching_brackets: Option<bool>,
     pub rainbow_brackets: Option<bool>,
     pub cursor_style: Option<CursorStyle>,
@@ -200,6 +214,12 @@ pub struct PartialEditorConfig {
     pub show_prompt_line: Option<bool>,
     pub show_vertical_scrollbar: Option<bool>,
     pub show_horizontal_scrollbar: Option<bool>,
+    pub show_minimap: Option<bool>,
+    pub minimap_width: Option<u16>,
+    pub sticky_scroll: Option<bool>,
+    pub sticky_scroll_max_lines: Option<usize>,
+    pub show_breadcrumbs: Option<bool>,
+    pub show_indent_guides: Option<bool>,
     pub show_tilde: Option<bool>,
     pub use_terminal_bg: Option<bool>,
     pub set_window_title: Option<bool>,
@@ -242,6 +262,7 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.estimated_line_length);
         self.enable_inlay_hints
             .merge_from(&other.enable_inlay_hints);
+        self.enable_code_lens.merge_from(&other.enable_code_lens);
         self.enable_semantic_tokens_full
             .merge_from(&other.enable_semantic_tokens_full);
         self.diagnostics_inline_text
@@ -253,6 +274,9 @@ impl Merge for PartialEditorConfig {
         self.auto_save_interval_secs
             .merge_from(&other.auto_save_interval_secs);
         self.hot_exit.merge_from(&other.hot_exit);
+        self.persistent_undo.merge_from(&other.persistent_undo);
+        self.persistent_undo_max_kb
+            .merge_from(&other.persistent_undo_max_kb);
         self.restore_previous_session
             .merge_from(&other.restore_previous_session);
         self.skip_session_restore_when_files_passed
@@ -272,12 +296,21 @@ impl Merge for PartialEditorConfig {
         self.read_concurrency.merge_from(&other.read_concurrency);
         self.file_tree_poll_interval_ms
             .merge_from(&other.file_tree_poll_interval_ms);
+        self.plugin_call_timeout_ms
+            .merge_from(&other.plugin_call_timeout_ms);
+        self.plugin_load_timeout_ms
+            .merge_from(&other.plugin_load_timeout_ms);
+        self.plugin_memory_limit_mb
+            .merge_from(&other.plugin_memory_limit_mb);
         self.default_line_ending
             .merge_from(&other.default_line_ending);
         self.trim_trailing_whitespace_on_save
             .merge_from(&other.trim_trailing_whitespace_on_save);
         self.ensure_final_newline_on_save
             .merge_from(&other.ensure_final_newline_on_save);
+        self.spell_check.merge_from(&other.spell_check);
+        self.spell_check_language
+            .merge_from(&other.spell_check_language);
         self.highlight_matching_brackets
             .merge_from(&other.highlight_matching_brackets);
         self.rainbow_brackets.merge_from(&other.rainbow_brackets);
@@ -310,6 +343,14 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.show_vertical_scrollbar);
         self.show_horizontal_scrollbar
             .merge_from(&other.show_horizontal_scrollbar);
+        self.show_minimap.merge_from(&other.show_minimap);
+        self.minimap_width.merge_from(&other.minimap_width);
+        self.sticky_scroll.merge_from(&other.sticky_scroll);
+        self.sticky_scroll_max_lines
+            .merge_from(&other.sticky_scroll_max_lines);
+        self.show_breadcrumbs.merge_from(&other.show_breadcrumbs);
+        self.show_indent_guides
+            .merge_from(&other.show_indent_guides);
         self.show_tilde.merge_from(&other.show_tilde);
         self.use_terminal_bg.merge_from(&other.use_terminal_bg);
         self.set_window_title.merge_from(&other.set_window_title);
@@ -438,6 +479,23 @@ impl Merge for PartialPackagesConfig {
     }
 }
 
+/// Partial debugger configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialDebugConfig {
+    pub adapters: Option<HashMap<String, DebugAdapterConfig>>,
+    pub configurations: Option<Vec<DebugConfiguration>>,
+}
+
+impl Merge for PartialDebugConfig {
+    fn merge_from(&mut self, other: &Self) {
+        // Adapters merge by name; configurations are a list, so the
+        // higher layer replaces it.
+        merge_hashmap(&mut self.adapters, &other.adapters);
+        self.configurations.merge_from(&other.configurations);
+    }
+}
+
 /// Partial plugin configuration.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
@@ -446,12 +504,15 @@ pub struct PartialPluginConfig {
     pub enabled: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<std::path::PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grants: Option<BTreeMap<Capability, bool>>,
 }
 
 impl Merge for PartialPluginConfig {
     fn merge_from(&mut self, other: &Self) {
         self.enabled.merge_from(&other.enabled);
         self.path.merge_from(&other.path);
+        self.grants.merge_from(&other.grants);
     }
 }
 
@@ -532,6 +593,7 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             large_file_threshold_bytes: Some(cfg.large_file_threshold_bytes),
             estimated_line_length: Some(cfg.estimated_line_length),
             enable_inlay_hints: Some(cfg.enable_inlay_hints),
+            enable_code_lens: Some(cfg.enable_code_lens),
             enable_semantic_tokens_full: Some(cfg.enable_semantic_tokens_full),
             diagnostics_inline_text: Some(cfg.diagnostics_inline_text),
             recovery_enabled: Some(cfg.recovery_enabled),
@@ -539,6 +601,8 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             auto_save_enabled: Some(cfg.auto_save_enabled),
             auto_save_interval_secs: Some(cfg.auto_save_interval_secs),
             hot_exit: Some(cfg.hot_exit),
+            persistent_undo: Some(cfg.persistent_undo),
+            persistent_undo_max_kb: Some(cfg.persistent_undo_max_kb),
             restore_previous_session: Some(cfg.restore_previous_session),
             skip_session_restore_when_files_passed: Some(
                 cfg.skip_session_restore_when_files_passed,
@@ -553,9 +617,14 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             auto_revert_poll_interval_ms: Some(cfg.auto_revert_poll_interval_ms),
             read_concurrency: Some(cfg.read_concurrency),
             file_tree_poll_interval_ms: Some(cfg.file_tree_poll_interval_ms),
+            plugin_call_timeout_ms: Some(cfg.plugin_call_timeout_ms),
+            plugin_load_timeout_ms: Some(cfg.plugin_load_timeout_ms),
+            plugin_memory_limit_mb: Some(cfg.plugin_memory_limit_mb),
             default_line_ending: Some(cfg.default_line_ending.clone()),
             trim_trailing_whitespace_on_save: Some(cfg.trim_trailing_whitespace_on_save),
             ensure_final_newline_on_save: Some(cfg.ensure_final_newline_on_save),
+            spell_check: Some(cfg.spell_check),
+            spell_check_language: Some(cfg.spell_check_language.clone()),
             highlight_matching_brackets: Some(cfg.highlight_matching_brackets),
             rainbow_brackets: Some(cfg.rainbow_brackets),
             cursor_style: Some(cfg.cursor_style),
@@ -577,6 +646,12 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             show_prompt_line: Some(cfg.show_prompt_line),
             show_vertical_scrollbar: Some(cfg.show_vertical_scrollbar),
             show_horizontal_scrollbar: Some(cfg.show_horizontal_scrollbar),
+            show_minimap: Some(cfg.show_minimap),
+            minimap_width: Some(cfg.minimap_width),
+            sticky_scroll: Some(cfg.sticky_scroll),
+            sticky_scroll_max_lines: Some(cfg.sticky_scroll_max_lines),
+            show_breadcrumbs: Some(cfg.show_breadcrumbs),
+            show_indent_guides: Some(cfg.show_indent_guides),
             show_tilde: Some(cfg.show_tilde),
             use_terminal_bg: Some(cfg.use_terminal_bg),
             set_window_title: Some(cfg.set_window_title),
@@ -636,6 +711,7 @@ impl PartialEditorConfig {
             enable_inlay_hints: self
                 .enable_inlay_hints
                 .unwrap_or(defaults.enable_inlay_hints),
+            enable_code_lens: self.enable_code_lens.unwrap_or(defaults.enable_code_lens),
             enable_semantic_tokens_full: self
                 .enable_semantic_tokens_full
                 .unwrap_or(defaults.enable_semantic_tokens_full),
@@ -651,6 +727,10 @@ impl PartialEditorConfig {
                 .auto_save_interval_secs
                 .unwrap_or(defaults.auto_save_interval_secs),
             hot_exit: self.hot_exit.unwrap_or(defaults.hot_exit),
+            persistent_undo: self.persistent_undo.unwrap_or(defaults.persistent_undo),
+            persistent_undo_max_kb: self
+                .persistent_undo_max_kb
+                .unwrap_or(defaults.persistent_undo_max_kb),
             restore_previous_session: self
                 .restore_previous_session
                 .unwrap_or(defaults.restore_previous_session),
@@ -679,6 +759,15 @@ impl PartialEditorConfig {
             file_tree_poll_interval_ms: self
                 .file_tree_poll_interval_ms
                 .unwrap_or(defaults.file_tree_poll_interval_ms),
+            plugin_call_timeout_ms: self
+                .plugin_call_timeout_ms
+                .unwrap_or(defaults.plugin_call_timeout_ms),
+            plugin_load_timeout_ms: self
+                .plugin_load_timeout_ms
+                .unwrap_or(defaults.plugin_load_timeout_ms),
+            plugin_memory_limit_mb: self
+                .plugin_memory_limit_mb
+                .unwrap_or(defaults.plugin_memory_limit_mb),
             default_line_ending: self
                 .default_line_ending
                 .unwrap_or(defaults.default_line_ending.clone()),
@@ -688,6 +777,10 @@ impl PartialEditorConfig {
             ensure_final_newline_on_save: self
                 .ensure_final_newline_on_save
                 .unwrap_or(defaults.ensure_final_newline_on_save),
+            spell_check: self.spell_check.unwrap_or(defaults.spell_check),
+            spell_check_language: self
+                .spell_check_language
+                .unwrap_or_else(|| defaults.spell_check_language.clone()),
             highlight_matching_brackets: self
                 .highlight_matching_brackets
                 .unwrap_or(defaults.highlight_matching_brackets),
@@ -731,6 +824,16 @@ impl PartialEditorConfig {
             show_horizontal_scrollbar: self
                 .show_horizontal_scrollbar
                 .unwrap_or(defaults.show_horizontal_scrollbar),
+            show_minimap: self.show_minimap.unwrap_or(defaults.show_minimap),
+            minimap_width: self.minimap_width.unwrap_or(defaults.minimap_width),
+            sticky_scroll: self.sticky_scroll.unwrap_or(defaults.sticky_scroll),
+            sticky_scroll_max_lines: self
+                .sticky_scroll_max_lines
+                .unwrap_or(defaults.sticky_scroll_max_lines),
+            show_breadcrumbs: self.show_breadcrumbs.unwrap_or(defaults.show_breadcrumbs),
+            show_indent_guides: self
+                .show_indent_guides
+                .unwrap_or(defaults.show_indent_guides),
             show_tilde: self.show_tilde.unwrap_or(defaults.show_tilde),
             use_terminal_bg: self.use_terminal_bg.unwrap_or(defaults.use_terminal_bg),
             set_window_title: self.set_window_title.unwrap_or(defaults.set_window_title),
@@ -889,11 +992,36 @@ impl PartialPackagesConfig {
     }
 }
 
+impl From<&crate::config::DebugConfig> for PartialDebugConfig {
+    fn from(cfg: &crate::config::DebugConfig) -> Self {
+        Self {
+            adapters: Some(cfg.adapters.clone()),
+            configurations: Some(cfg.configurations.clone()),
+        }
+    }
+}
+
+impl PartialDebugConfig {
+    pub fn resolve(self, defaults: &crate::config::DebugConfig) -> crate::config::DebugConfig {
+        let mut adapters = defaults.adapters.clone();
+        if let Some(partial_adapters) = self.adapters {
+            adapters.extend(partial_adapters);
+        }
+        crate::config::DebugConfig {
+            adapters,
+            configurations: self
+                .configurations
+                .unwrap_or_else(|| defaults.configurations.clone()),
+        }
+    }
+}
+
 impl From<&PluginConfig> for PartialPluginConfig {
     fn from(cfg: &PluginConfig) -> Self {
         Self {
             enabled: Some(cfg.enabled),
             path: cfg.path.clone(),
+            grants: Some(cfg.grants.clone()),
         }
     }
 }
@@ -903,6 +1031,7 @@ impl PartialPluginConfig {
         PluginConfig {
             enabled: self.enabled.unwrap_or(defaults.enabled),
             path: self.path.or_else(|| defaults.path.clone()),
+            grants: self.grants.unwrap_or_else(|| defaults.grants.clone()),
         }
     }
 }
@@ -1023,20 +1152,21 @@ impl From<&crate::config::Config> for PartialConfig {
                     .collect(),
             ),
             warnings: Some(PartialWarningsConfig::from(&cfg.warnings)),
-            // Only include plugins that differ from defaults
+            // Only include plugins that differ from defaults or have grants
             // Path is auto-discovered at runtime and should never be saved
             plugins: {
                 let default_plugin = crate::config::PluginConfig::default();
                 let non_default_plugins: HashMap<String, PartialPluginConfig> = cfg
                     .plugins
                     .iter()
-                    .filter(|(_, v)| v.enabled != default_plugin.enabled)
+                    .filter(|(_, v)| v.enabled != default_plugin.enabled || !v.grants.is_empty())
                     .map(|(k, v)| {
                         (
                             k.clone(),
                             PartialPluginConfig {
                                 enabled: Some(v.enabled),
                                 path: None, // Don't save path - it's auto-discovered
+                                grants: (!v.grants.is_empty()).then(|| v.grants.clone()),
                             },
                         )
                     })
@@ -1048,6 +1178,8 @@ impl From<&crate::config::Config> for PartialConfig {
                 }
             },
             packages: Some(PartialPackagesConfig::from(&cfg.packages)),
+            debug: Some(PartialDebugConfig::from(&cfg.debug)),
+            tasks: Some(cfg.tasks.clone()),
         }
     }
 }
@@ -1154,6 +1286,16 @@ impl PartialConfig {
             result
         };
 
+        // Resolve tasks HashMap - merge with defaults, a task replaces a
+        // default of the same name
+        let tasks = {
+            let mut result = defaults.tasks.clone();
+            if let Some(partial_tasks) = self.tasks {
+                result.extend(partial_tasks);
+            }
+            result
+        };
+
         crate::config::Config {
             version: self.version.unwrap_or(defaults.version),
             theme: self.theme.unwrap_or_else(|| defaults.theme.clone()),
@@ -1203,6 +1345,11 @@ impl PartialConfig {
                 .packages
                 .map(|e| e.resolve(&defaults.packages))
                 .unwrap_or_else(|| defaults.packages.clone()),
+            debug: self
+                .debug
+                .map(|e| e.resolve(&defaults.debug))
+                .unwrap_or_else(|| defaults.debug.clone()),
+            tasks,
         }
     }
 }
@@ -1423,6 +1570,76 @@ mod tests {
         assert_eq!(rust.auto_indent, Some(false)); // Filled from lower
     }
 
+    #[test]
+    fn merge_debug_config_merges_adapters_and_replaces_configurations() {
+        let adapter = |command: &str| DebugAdapterConfig {
+            command: command.to_string(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            languages: vec!["rust".to_string()],
+        };
+        let configuration = |name: &str| DebugConfiguration {
+            name: name.to_string(),
+            adapter: "lldb".to_string(),
+            request: crate::config::DebugRequestKind::Launch,
+            arguments: HashMap::new(),
+        };
+        let mut higher = PartialConfig {
+            debug: Some(PartialDebugConfig {
+                adapters: Some(HashMap::from([("lldb".to_string(), adapter("lldb-dap"))])),
+                configurations: Some(vec![configuration("project")]),
+            }),
+            ..Default::default()
+        };
+        let lower = PartialConfig {
+            debug: Some(PartialDebugConfig {
+                adapters: Some(HashMap::from([
+                    ("lldb".to_string(), adapter("lldb-vscode")),
+                    ("debugpy".to_string(), adapter("python3")),
+                ])),
+                configurations: Some(vec![configuration("user")]),
+            }),
+            ..Default::default()
+        };
+
+        higher.merge_from(&lower);
+
+        let debug = higher.resolve().debug;
+        assert_eq!(debug.adapters["lldb"].command, "lldb-dap"); // Higher wins
+        assert!(debug.adapters.contains_key("debugpy")); // Filled from lower
+        assert_eq!(debug.configurations.len(), 1);
+        assert_eq!(debug.configurations[0].name, "project");
+        assert!(debug.has_adapter_for_language("rust"));
+        assert!(!debug.has_adapter_for_language("python"));
+    }
+
+    #[test]
+    fn resolve_tasks_keeps_defaults_and_overrides_by_name() {
+        let task = |command: &str| TaskConfig {
+            command: command.to_string(),
+            args: Vec::new(),
+            cwd: None,
+            env: HashMap::new(),
+            problem_matcher: None,
+            languages: Vec::new(),
+            enabled: true,
+        };
+        let partial = PartialConfig {
+            tasks: Some(HashMap::from([
+                ("cargo build".to_string(), task("cross")),
+                ("lint".to_string(), task("./lint.sh")),
+            ])),
+            ..Default::default()
+        };
+
+        let tasks = partial.resolve().tasks;
+        assert_eq!(tasks["cargo build"].command, "cross"); // Replaced
+        assert_eq!(tasks["lint"].command, "./lint.sh"); // Added
+        assert_eq!(tasks["tsc"].command, "npx"); // Default kept
+        assert!(tasks["lint"].applies_to("python"));
+        assert!(!tasks["tsc"].applies_to("python"));
+    }
+
     #[test]
     fn resolve_fills_defaults() {
         let partial = PartialConfig {
@@ -1536,6 +1753,7 @@ mod tests {
             PluginConfig {
                 enabled: true, // Default value
                 path: Some(std::path::PathBuf::from("/path/to/plugin.ts")),
+                ..Default::default()
             },
         );
 
@@ -1557,6 +1775,7 @@ mod tests {
             PluginConfig {
                 enabled: true,
                 path: Some(std::path::PathBuf::from("/path/to/enabled.ts")),
+                ..Default::default()
             },
         );
         config.plugins.insert(
@@ -1564,6 +1783,7 @@ mod tests {
             PluginConfig {
                 enabled: false, // Not default!
                 path: Some(std::path::PathBuf::from("/path/to/disabled.ts")),
+                ..Default::default()
             },
         );
 
@@ -1587,6 +1807,23 @@ mod tests {
         assert!(disabled.path.is_none(), "Path should not be serialized");
     }
 
+    #[test]
+    fn plugins_with_grants_are_serialized() {
+        // Permission answers must survive a save even for enabled plugins
+        let mut config = crate::config::Config::default();
+        let mut plugin = PluginConfig::new_with_path("/packages/p/main.ts".into());
+        plugin.grants.insert(Capability::Process, true);
+        config.plugins.insert("p".to_string(), plugin);
+
+        let plugins = PartialConfig::from(&config).plugins.unwrap();
+        let saved = &plugins["p"];
+        assert_eq!(
+            saved.grants,
+            Some(BTreeMap::from([(Capability::Process, true)]))
+        );
+        assert!(saved.path.is_none());
+    }
+
     #[test]
     fn plugin_path_never_serialized() {
         // Even for disabled plugins, path should never be serialized
@@ -1596,6 +1833,7 @@ mod tests {
             PluginConfig {
                 enabled: false,
                 path: Some(std::path::PathBuf::from("/some/path/plugin.ts")),
+                ..Default::default()
             },
         );
 
@@ -1618,6 +1856,7 @@ mod tests {
                 PartialPluginConfig {
                     enabled: Some(false),
                     path: None,
+                    ..Default::default()
                 },
             )])),
             ..Default::default()
@@ -1646,6 +1885,7 @@ mod tests {
                 PartialPluginConfig {
                     enabled: Some(false), // User disabled
                     path: None,
+                    ..Default::default()
                 },
             )])),
             ..Default::default()
@@ -1657,6 +1897,7 @@ mod tests {
                 PartialPluginConfig {
                     enabled: Some(true), // Lower layer has it enabled
                     path: None,
+                    ..Default::default()
                 },
             )])),
             ..Default::default()
@@ -1683,6 +1924,7 @@ mod tests {
             PluginConfig {
                 enabled: true,
                 path: Some(std::path::PathBuf::from("/a.ts")),
+                ..Default::default()
             },
         );
         config.plugins.insert(
@@ -1690,6 +1932,7 @@ mod tests {
             PluginConfig {
                 enabled: false,
                 path: Some(std::path::PathBuf::from("/b.ts")),
+                ..Default::default()
             },
         );
         config.plugins.insert(
@@ -1697,6 +1940,7 @@ mod tests {
             PluginConfig {
                 enabled: true,
                 path: Some(std::path::PathBuf::from("/c.ts")),
+                ..Default::default()
             },
         );
 
@@ -0,0 +1,147 @@
+//! Code lens layout.
+//!
+//! All lenses starting on the same source line are drawn as one virtual
+//! line above it, indented like the source line and separated by ` | `
+//! (rust-analyzer's "▶︎ Run Test | Debug" followed by "3 references").
+//! The helpers here group lenses by line, lay that virtual line out, and
+//! map a column of it back to the lens drawn there so clicks can run it.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use lsp_types::CodeLens;
+
+/// Text drawn between two lenses on the same line
+pub const SEPARATOR: &str = " | ";
+
+/// Title of a lens, or `None` while it still needs `codeLens/resolve`.
+pub fn lens_title(lens: &CodeLens) -> Option<&str> {
+    lens.command
+        .as_ref()
+        .map(|command| command.title.as_str())
+        .filter(|title| !title.is_empty())
+}
+
+/// Indices of `lenses` grouped by the line they start on, each group in
+/// the order the lenses appear on that line.
+pub fn group_by_line(lenses: &[CodeLens]) -> BTreeMap<u32, Vec<usize>> {
+    let mut lines: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+    for (index, lens) in lenses.iter().enumerate() {
+        lines.entry(lens.range.start.line).or_default().push(index);
+    }
+    for indices in lines.values_mut() {
+        indices.sort_by_key(|&i| lenses[i].range.start.character);
+    }
+    lines
+}
+
+/// The virtual line showing the lenses of one source line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeLensLine {
+    pub text: String,
+    /// Index of each shown lens with the char range of its title in `text`
+    pub spans: Vec<(usize, Range<usize>)>,
+}
+
+impl CodeLensLine {
+    /// Lay out the lenses at `indices`, skipping unresolved ones.
+    ///
+    /// Returns `None` if none of them has a title yet.
+    pub fn layout(indent_width: usize, lenses: &[CodeLens], indices: &[usize]) -> Option<Self> {
+        let mut text = " ".repeat(indent_width);
+        let mut column = indent_width;
+        let mut spans = Vec::new();
+        for &index in indices {
+            let Some(title) = lenses.get(index).and_then(lens_title) else {
+                continue;
+            };
+            if !spans.is_empty() {
+                text.push_str(SEPARATOR);
+                column += SEPARATOR.chars().count();
+            }
+            let width = title.chars().count();
+            text.push_str(title);
+            spans.push((index, column..column + width));
+            column += width;
+        }
+        (!spans.is_empty()).then_some(Self { text, spans })
+    }
+
+    /// Lens drawn at char `column`. The indent and the separators belong
+    /// to no lens.
+    pub fn lens_at(&self, column: usize) -> Option<usize> {
+        self.spans
+            .iter()
+            .find(|(_, range)| range.contains(&column))
+            .map(|(index, _)| *index)
+    }
+
+    /// Indices of the shown lenses, left to right.
+    pub fn lenses(&self) -> impl Iterator<Item = usize> + '_ {
+        self.spans.iter().map(|(index, _)| *index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{Command, Position, Range as LspRange};
+
+    fn lens(line: u32, character: u32, title: Option<&str>) -> CodeLens {
+        CodeLens {
+            range: LspRange::new(
+                Position::new(line, character),
+                Position::new(line, character + 3),
+            ),
+            command: title.map(|title| Command {
+                title: title.to_string(),
+                command: "test.command".to_string(),
+                arguments: None,
+            }),
+            data: None,
+        }
+    }
+
+    #[test]
+    fn groups_by_line_in_column_order() {
+        let lenses = vec![
+            lens(4, 8, Some("Debug")),
+            lens(1, 0, Some("2 references")),
+            lens(4, 4, Some("Run Test")),
+        ];
+        let groups = group_by_line(&lenses);
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), vec![1, 4]);
+        assert_eq!(groups[&4], vec![2, 0]);
+    }
+
+    #[test]
+    fn layout_indents_and_separates_titles() {
+        let lenses = vec![lens(0, 4, Some("Run Test")), lens(0, 4, Some("Debug"))];
+        let line = CodeLensLine::layout(4, &lenses, &[0, 1]).unwrap();
+        assert_eq!(line.text, "    Run Test | Debug");
+        assert_eq!(line.spans, vec![(0, 4..12), (1, 15..20)]);
+    }
+
+    #[test]
+    fn layout_skips_unresolved_lenses() {
+        let lenses = vec![lens(0, 0, None), lens(0, 0, Some("Run"))];
+        let line = CodeLensLine::layout(0, &lenses, &[0, 1]).unwrap();
+        assert_eq!(line.text, "Run");
+        assert_eq!(line.lenses().collect::<Vec<_>>(), vec![1]);
+
+        assert!(CodeLensLine::layout(0, &lenses, &[0]).is_none());
+    }
+
+    #[test]
+    fn lens_at_ignores_indent_and_separators() {
+        let lenses = vec![lens(0, 2, Some("▶︎ Run")), lens(0, 2, Some("Debug"))];
+        let line = CodeLensLine::layout(2, &lenses, &[0, 1]).unwrap();
+        // "  ▶︎ Run | Debug": the title counts chars, not bytes
+        assert_eq!(line.lens_at(0), None);
+        assert_eq!(line.lens_at(2), Some(0));
+        assert_eq!(line.lens_at(7), Some(0));
+        assert_eq!(line.lens_at(9), None);
+        assert_eq!(line.lens_at(11), Some(1));
+        assert_eq!(line.lens_at(16), None);
+    }
+}
@@ -0,0 +1,397 @@
+//! Document symbols for the outline panel and `@` quick open.
+//!
+//! Symbols come from the language server's `textDocument/documentSymbol`
+//! when one is attached, and otherwise from the grammar's tree-sitter tags
+//! query (`@definition.<kind>` captures named by `@name`). Both sources are
+//! normalized into a flat, document-ordered list of [`OutlineSymbol`]s where
+//! nesting is expressed by `depth`, which is what both consumers want: the
+//! quick-open list filters it, the outline panel indents it.
+
+use crate::model::buffer::Buffer;
+use crate::primitives::highlighter::Language;
+use fresh_languages::tree_sitter::{Parser, Query, QueryCursor, StreamingIterator};
+use lsp_types::{DocumentSymbolResponse, SymbolKind};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Buffers larger than this are not parsed for tags; the outline stays
+/// empty unless a language server provides symbols.
+const MAX_TAGS_PARSE_BYTES: usize = 2 * 1024 * 1024;
+
+/// One entry of a document outline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineSymbol {
+    pub name: String,
+    /// Extra information shown next to the name (e.g. a signature)
+    pub detail: Option<String>,
+    pub kind: SymbolKind,
+    /// Byte range of the whole definition
+    pub range: Range<usize>,
+    /// Byte range of the symbol's name, where jumps land
+    pub selection_range: Range<usize>,
+    /// 0-based line of `selection_range.start`
+    pub line: usize,
+    /// Nesting level; top-level symbols have depth 0
+    pub depth: usize,
+    /// Name of the enclosing symbol, if any
+    pub container: Option<String>,
+}
+
+/// Short lowercase label for a symbol kind ("function", "struct", ...).
+pub fn symbol_kind_name(kind: SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::FILE => "file",
+        SymbolKind::MODULE => "module",
+        SymbolKind::NAMESPACE => "namespace",
+        SymbolKind::PACKAGE => "package",
+        SymbolKind::CLASS => "class",
+        SymbolKind::METHOD => "method",
+        SymbolKind::PROPERTY => "property",
+        SymbolKind::FIELD => "field",
+        SymbolKind::CONSTRUCTOR => "constructor",
+        SymbolKind::ENUM => "enum",
+        SymbolKind::INTERFACE => "interface",
+        SymbolKind::FUNCTION => "function",
+        SymbolKind::VARIABLE => "variable",
+        SymbolKind::CONSTANT => "constant",
+        SymbolKind::STRING => "string",
+        SymbolKind::NUMBER => "number",
+        SymbolKind::BOOLEAN => "boolean",
+        SymbolKind::ARRAY => "array",
+        SymbolKind::OBJECT => "object",
+        SymbolKind::KEY => "key",
+        SymbolKind::NULL => "null",
+        SymbolKind::ENUM_MEMBER => "enum member",
+        SymbolKind::STRUCT => "struct",
+        SymbolKind::EVENT => "event",
+        SymbolKind::OPERATOR => "operator",
+        SymbolKind::TYPE_PARAMETER => "type parameter",
+        _ => "symbol",
+    }
+}
+
+/// Map the `<kind>` of a `@definition.<kind>` tags capture to a symbol kind.
+fn tag_kind(kind: &str) -> SymbolKind {
+    match kind {
+        "class" => SymbolKind::CLASS,
+        "method" => SymbolKind::METHOD,
+        "interface" => SymbolKind::INTERFACE,
+        "module" => SymbolKind::MODULE,
+        "constant" => SymbolKind::CONSTANT,
+        "field" => SymbolKind::FIELD,
+        "type" => SymbolKind::STRUCT,
+        // "function", "macro" and anything grammar-specific
+        _ => SymbolKind::FUNCTION,
+    }
+}
+
+/// Extracts outline symbols with tree-sitter tags queries.
+///
+/// Compiled queries are cached per language; a cached `None` records a
+/// language without a usable tags query so it is not retried.
+#[derive(Default)]
+pub struct SyntaxSymbolExtractor {
+    queries: HashMap<Language, Option<Query>>,
+}
+
+impl SyntaxSymbolExtractor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Outline of `buffer` according to `language`'s tags query.
+    ///
+    /// Returns `None` when the language has no grammar or tags query, or
+    /// the buffer is too large to parse.
+    pub fn symbols(&mut self, buffer: &Buffer, language: &Language) -> Option<Vec<OutlineSymbol>> {
+        if buffer.len() > MAX_TAGS_PARSE_BYTES {
+            return None;
+        }
+        let ts_language = language.tree_sitter_language()?;
+        let query = self
+            .queries
+            .entry(*language)
+            .or_insert_with(|| {
+                let source = language.tags_query()?;
+                match Query::new(&ts_language, &source) {
+                    Ok(query) => Some(query),
+                    Err(e) => {
+                        tracing::warn!("Failed to compile tags query for {}: {}", language, e);
+                        None
+                    }
+                }
+            })
+            .as_ref()?;
+
+        let mut parser = Parser::new();
+        if parser.set_language(&ts_language).is_err() {
+            tracing::warn!("Failed to set language {} for symbol parser", language);
+            return None;
+        }
+        let source = buffer.slice_bytes(0..buffer.len());
+        let tree = parser.parse(&source, None)?;
+
+        let capture_names = query.capture_names();
+        let mut symbols: Vec<OutlineSymbol> = Vec::new();
+        let mut seen: HashMap<Range<usize>, usize> = HashMap::new();
+        let mut query_cursor = QueryCursor::new();
+        let mut matches = query_cursor.matches(query, tree.root_node(), source.as_slice());
+        while let Some(m) = matches.next() {
+            let mut definition = None;
+            let mut name = None;
+            for capture in m.captures {
+                let capture_name = capture_names[capture.index as usize];
+                if let Some(kind) = capture_name.strip_prefix("definition.") {
+                    definition = Some((capture.node, kind));
+                } else if capture_name == "name" {
+                    name = Some(capture.node);
+                }
+            }
+            let (Some((node, kind)), Some(name_node)) = (definition, name) else {
+                continue;
+            };
+            // Several patterns can match the same node (a Rust method is
+            // both `definition.method` and `definition.function`); the
+            // first, more specific, pattern wins.
+            if seen.contains_key(&node.byte_range()) {
+                continue;
+            }
+            let Ok(text) = std::str::from_utf8(&source[name_node.byte_range()]) else {
+                continue;
+            };
+            seen.insert(node.byte_range(), symbols.len());
+            symbols.push(OutlineSymbol {
+                name: text.to_string(),
+                detail: None,
+                kind: tag_kind(kind),
+                range: node.byte_range(),
+                selection_range: name_node.byte_range(),
+                line: name_node.start_position().row,
+                depth: 0,
+                container: None,
+            });
+        }
+
+        assign_nesting(&mut symbols);
+        Some(symbols)
+    }
+}
+
+/// Convert a `textDocument/documentSymbol` response into outline symbols.
+pub fn lsp_symbols(buffer: &Buffer, response: DocumentSymbolResponse) -> Vec<OutlineSymbol> {
+    let to_bytes = |range: &lsp_types::Range| {
+        let start =
+            buffer.lsp_position_to_byte(range.start.line as usize, range.start.character as usize);
+        let end =
+            buffer.lsp_position_to_byte(range.end.line as usize, range.end.character as usize);
+        start..end.max(start)
+    };
+
+    match response {
+        DocumentSymbolResponse::Nested(roots) => {
+            let mut symbols = Vec::new();
+            // Depth-first, keeping the server's order among siblings.
+            let mut stack: Vec<(lsp_types::DocumentSymbol, usize, Option<String>)> = roots
+                .into_iter()
+                .rev()
+                .map(|symbol| (symbol, 0, None))
+                .collect();
+            while let Some((symbol, depth, container)) = stack.pop() {
+                let children = symbol.children.unwrap_or_default();
+                for child in children.into_iter().rev() {
+                    stack.push((child, depth + 1, Some(symbol.name.clone())));
+                }
+                symbols.push(OutlineSymbol {
+                    detail: symbol.detail.filter(|d| !d.is_empty()),
+                    kind: symbol.kind,
+                    range: to_bytes(&symbol.range),
+                    selection_range: to_bytes(&symbol.selection_range),
+                    line: symbol.selection_range.start.line as usize,
+                    depth,
+                    container,
+                    name: symbol.name,
+                });
+            }
+            symbols
+        }
+        DocumentSymbolResponse::Flat(infos) => {
+            let mut symbols: Vec<OutlineSymbol> = infos
+                .into_iter()
+                .map(|info| {
+                    let range = to_bytes(&info.location.range);
+                    OutlineSymbol {
+                        name: info.name,
+                        detail: None,
+                        kind: info.kind,
+                        selection_range: range.clone(),
+                        range,
+                        line: info.location.range.start.line as usize,
+                        depth: 0,
+                        container: None,
+                    }
+                })
+                .collect();
+            assign_nesting(&mut symbols);
+            symbols
+        }
+    }
+}
+
+/// Sort `symbols` into document order and derive `depth` and `container`
+/// from range containment.
+fn assign_nesting(symbols: &mut [OutlineSymbol]) {
+    symbols.sort_by(|a, b| {
+        a.range
+            .start
+            .cmp(&b.range.start)
+            .then(b.range.end.cmp(&a.range.end))
+    });
+    // Indices of the symbols enclosing the current one, outermost first.
+    let mut enclosing: Vec<usize> = Vec::new();
+    for i in 0..symbols.len() {
+        while let Some(&parent) = enclosing.last() {
+            if symbols[parent].range.end >= symbols[i].range.end {
+                break;
+            }
+            enclosing.pop();
+        }
+        symbols[i].depth = enclosing.len();
+        symbols[i].container = enclosing.last().map(|&parent| symbols[parent].name.clone());
+        enclosing.push(i);
+    }
+}
+
+/// Index of the innermost symbol whose range contains `offset`.
+pub fn symbol_at(symbols: &[OutlineSymbol], offset: usize) -> Option<usize> {
+    symbols
+        .iter()
+        .enumerate()
+        .filter(|(_, symbol)| symbol.range.start <= offset && offset <= symbol.range.end)
+        .max_by_key(|(_, symbol)| symbol.depth)
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUST_SOURCE: &str = "\
+struct Point {
+    x: i32,
+}
+
+impl Point {
+    fn norm(&self) -> i32 {
+        self.x
+    }
+}
+
+fn main() {}
+";
+
+    fn names(symbols: &[OutlineSymbol]) -> Vec<(&str, usize)> {
+        symbols.iter().map(|s| (s.name.as_str(), s.depth)).collect()
+    }
+
+    #[test]
+    fn tags_query_finds_definitions_in_document_order() {
+        let buffer = Buffer::from_str_test(RUST_SOURCE);
+        let symbols = SyntaxSymbolExtractor::new()
+            .symbols(&buffer, &Language::Rust)
+            .unwrap();
+        assert_eq!(
+            names(&symbols),
+            vec![("Point", 0), ("norm", 0), ("main", 0)]
+        );
+
+        let norm = &symbols[1];
+        assert_eq!(norm.kind, SymbolKind::METHOD, "method pattern wins");
+        assert_eq!(&RUST_SOURCE[norm.selection_range.clone()], "norm");
+        assert_eq!(norm.line, 5);
+        assert_eq!(symbols[0].kind, SymbolKind::CLASS);
+        assert_eq!(symbols[2].kind, SymbolKind::FUNCTION);
+    }
+
+    #[test]
+    fn nested_definitions_get_depth_and_container() {
+        let source = "mod outer {\n    fn inner() {}\n}\n";
+        let buffer = Buffer::from_str_test(source);
+        let symbols = SyntaxSymbolExtractor::new()
+            .symbols(&buffer, &Language::Rust)
+            .unwrap();
+        assert_eq!(names(&symbols), vec![("outer", 0), ("inner", 1)]);
+        assert_eq!(symbols[1].container.as_deref(), Some("outer"));
+    }
+
+    #[test]
+    fn language_without_tags_query_has_no_symbols() {
+        let buffer = Buffer::from_str_test("{\"a\": 1}");
+        assert_eq!(
+            SyntaxSymbolExtractor::new().symbols(&buffer, &Language::Json),
+            None
+        );
+    }
+
+    fn lsp_range(start: (u32, u32), end: (u32, u32)) -> lsp_types::Range {
+        lsp_types::Range::new(
+            lsp_types::Position::new(start.0, start.1),
+            lsp_types::Position::new(end.0, end.1),
+        )
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn nested_lsp_symbols_flatten_depth_first() {
+        let buffer = Buffer::from_str_test(RUST_SOURCE);
+        let symbol = |name: &str, range, selection, children| lsp_types::DocumentSymbol {
+            name: name.to_string(),
+            detail: None,
+            kind: SymbolKind::FUNCTION,
+            tags: None,
+            deprecated: None,
+            range,
+            selection_range: selection,
+            children,
+        };
+        let response = DocumentSymbolResponse::Nested(vec![
+            symbol(
+                "impl Point",
+                lsp_range((4, 0), (8, 1)),
+                lsp_range((4, 5), (4, 10)),
+                Some(vec![symbol(
+                    "norm",
+                    lsp_range((5, 4), (7, 5)),
+                    lsp_range((5, 7), (5, 11)),
+                    None,
+                )]),
+            ),
+            symbol(
+                "main",
+                lsp_range((10, 0), (10, 12)),
+                lsp_range((10, 3), (10, 7)),
+                None,
+            ),
+        ]);
+
+        let symbols = lsp_symbols(&buffer, response);
+        assert_eq!(
+            names(&symbols),
+            vec![("impl Point", 0), ("norm", 1), ("main", 0)]
+        );
+        assert_eq!(symbols[1].container.as_deref(), Some("impl Point"));
+        assert_eq!(&RUST_SOURCE[symbols[1].selection_range.clone()], "norm");
+        assert_eq!(symbols[1].line, 5);
+    }
+
+    #[test]
+    fn symbol_at_picks_innermost() {
+        let buffer = Buffer::from_str_test(RUST_SOURCE);
+        let symbols = SyntaxSymbolExtractor::new()
+            .symbols(&buffer, &Language::Rust)
+            .unwrap();
+        let inside_norm = RUST_SOURCE.find("self.x").unwrap();
+        assert_eq!(symbol_at(&symbols, inside_norm), Some(1));
+        let between = RUST_SOURCE.find("impl").unwrap();
+        assert_eq!(symbol_at(&symbols, between), None);
+    }
+}
@@ -0,0 +1,200 @@
+//! `file:line:col` locations
+//!
+//! Shared by the command line (`fresh src/main.rs:10:5`) and by anything
+//! else that turns user-visible text into a file position, such as links
+//! in terminal output.
+
+use std::path::PathBuf;
+
+/// A file path with an optional position, in `file:line:col` format.
+/// Also supports range selections (file:L-EL or file:L:C-EL:EC) and
+/// hover messages (file:L@"message").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileLocation {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub end_line: Option<usize>,
+    pub end_column: Option<usize>,
+    pub message: Option<String>,
+}
+
+/// Parse a file path that may include line/column, range, and message information.
+/// Supports formats:
+/// - file.txt
+/// - file.txt:10
+/// - file.txt:10:5
+/// - file.txt:13-16           (line range)
+/// - file.txt:13:17-21:1      (full range with columns)
+/// - file.txt:10@"message"    (position + hover message)
+/// - file.txt:13-16@"message" (range + hover message)
+/// - /path/to/file.txt:10:5
+///
+/// For Windows paths like C:\path\file.txt:10:5, we handle the drive letter
+/// prefix properly using std::path APIs.
+///
+/// If the full path exists as a file, it's used as-is (handles files with colons in name).
+pub fn parse_file_location(input: &str) -> FileLocation {
+    use std::path::{Component, Path};
+
+    let empty = FileLocation {
+        path: PathBuf::from(input),
+        line: None,
+        column: None,
+        end_line: None,
+        end_column: None,
+        message: None,
+    };
+
+    let full_path = PathBuf::from(input);
+
+    // If the full path exists as a file, use it directly
+    // This handles edge cases like files named "foo:10"
+    if full_path.is_file() {
+        return FileLocation {
+            path: full_path,
+            ..empty
+        };
+    }
+
+    // Extract message from @"..." suffix (before parsing positions)
+    let (input_no_msg, message) = extract_message_suffix(input);
+
+    // Check if the path has a Windows drive prefix using std::path
+    let has_prefix = Path::new(input_no_msg)
+        .components()
+        .next()
+        .map(|c| matches!(c, Component::Prefix(_)))
+        .unwrap_or(false);
+
+    // Calculate where to start looking for :line:col
+    // For Windows paths with prefix (e.g., "C:"), skip past the drive letter and colon
+    let search_start = if has_prefix {
+        // Find the first colon (the drive letter separator) and skip it
+        input_no_msg.find(':').map(|i| i + 1).unwrap_or(0)
+    } else {
+        0
+    };
+
+    // Find the last colon(s) that could be line:col
+    let suffix = &input_no_msg[search_start..];
+
+    // Check if there's a range (contains '-' in the location suffix, not in the path)
+    // We need to find the first colon that starts the location suffix, then check for '-'
+    if let Some(first_colon) = suffix.find(':') {
+        let location_part = &suffix[first_colon + 1..];
+        if location_part.contains('-') {
+            // Range syntax: try to parse as L-EL or L:C-EL:EC
+            let path_part = &suffix[..first_colon];
+            let path_str = if has_prefix {
+                format!("{}{}", &input_no_msg[..search_start], path_part)
+            } else {
+                path_part.to_string()
+            };
+
+            if let Some(result) =
+                parse_range(location_part, PathBuf::from(path_str), message.clone())
+            {
+                return result;
+            }
+        }
+    }
+
+    // No range — fall back to standard :line or :line:col parsing
+    let parts: Vec<&str> = suffix.rsplitn(3, ':').collect();
+
+    match parts.as_slice() {
+        [maybe_col, maybe_line, rest] => {
+            if let (Ok(line), Ok(col)) = (maybe_line.parse::<usize>(), maybe_col.parse::<usize>()) {
+                let path_str = if has_prefix {
+                    format!("{}{}", &input_no_msg[..search_start], rest)
+                } else {
+                    rest.to_string()
+                };
+                return FileLocation {
+                    path: PathBuf::from(path_str),
+                    line: Some(line),
+                    column: Some(col),
+                    message,
+                    ..empty
+                };
+            }
+        }
+        [maybe_line, rest] => {
+            if let Ok(line) = maybe_line.parse::<usize>() {
+                let path_str = if has_prefix {
+                    format!("{}{}", &input_no_msg[..search_start], rest)
+                } else {
+                    rest.to_string()
+                };
+                return FileLocation {
+                    path: PathBuf::from(path_str),
+                    line: Some(line),
+                    message,
+                    ..empty
+                };
+            }
+        }
+        _ => {}
+    }
+
+    // No valid suffix found, treat the whole thing as a path
+    FileLocation {
+        path: PathBuf::from(input_no_msg),
+        message,
+        ..empty
+    }
+}
+
+/// Extract a @"message" suffix from a file location string.
+/// Returns (remaining_input, optional_message).
+pub fn extract_message_suffix(input: &str) -> (&str, Option<String>) {
+    // Look for @" pattern — the message is everything between the quotes
+    if let Some(at_pos) = input.rfind("@\"") {
+        if input.ends_with('"') && input.len() > at_pos + 2 {
+            let msg = &input[at_pos + 2..input.len() - 1];
+            // Unescape \" within the message
+            let msg = msg.replace("\\\"", "\"");
+            return (&input[..at_pos], Some(msg));
+        }
+    }
+    (input, None)
+}
+
+/// Parse a range location suffix like "13-16" or "13:17-21:1".
+/// Returns a FileLocation if successful.
+fn parse_range(location: &str, path: PathBuf, message: Option<String>) -> Option<FileLocation> {
+    let parts: Vec<&str> = location.splitn(2, '-').collect();
+    if parts.len() != 2 {
+        return None;
+    }
+
+    let start_part = parts[0];
+    let end_part = parts[1];
+
+    // Parse start: either "L" or "L:C"
+    let (start_line, start_col) = parse_line_col(start_part)?;
+    // Parse end: either "EL" or "EL:EC"
+    let (end_line, end_col) = parse_line_col(end_part)?;
+
+    Some(FileLocation {
+        path,
+        line: Some(start_line),
+        column: start_col,
+        end_line: Some(end_line),
+        end_column: end_col,
+        message,
+    })
+}
+
+/// Parse "L" or "L:C" into (line, optional_column).
+fn parse_line_col(s: &str) -> Option<(usize, Option<usize>)> {
+    if let Some((line_str, col_str)) = s.split_once(':') {
+        let line = line_str.parse::<usize>().ok()?;
+        let col = col_str.parse::<usize>().ok()?;
+        Some((line, Some(col)))
+    } else {
+        let line = s.parse::<usize>().ok()?;
+        Some((line, None))
+    }
+}
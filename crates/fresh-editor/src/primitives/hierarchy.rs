@@ -0,0 +1,287 @@
+//! Call and type hierarchies.
+//!
+//! A hierarchy is explored one level at a time: `prepare*Hierarchy` returns
+//! the items at a position, then `callHierarchy/incomingCalls`,
+//! `callHierarchy/outgoingCalls`, `typeHierarchy/supertypes` or
+//! `typeHierarchy/subtypes` return the neighbours of one item. All answers
+//! are normalized into [`HierarchyEntry`]s here. Each entry keeps the item
+//! exactly as the server sent it, since expanding it means sending it back.
+
+use lsp_types::{CallHierarchyItem, Location, Range, SymbolKind, Uri};
+use serde_json::Value;
+
+/// Which hierarchy to prepare at a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HierarchyKind {
+    Call,
+    Type,
+}
+
+impl HierarchyKind {
+    /// Parse the name used by plugins ("call" or "type").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "call" => Some(HierarchyKind::Call),
+            "type" => Some(HierarchyKind::Type),
+            _ => None,
+        }
+    }
+
+    /// LSP method that resolves the items at a position
+    pub fn prepare_method(self) -> &'static str {
+        match self {
+            HierarchyKind::Call => "textDocument/prepareCallHierarchy",
+            HierarchyKind::Type => "textDocument/prepareTypeHierarchy",
+        }
+    }
+}
+
+/// Which neighbours of an item to ask for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HierarchyDirection {
+    /// Functions calling the item
+    Incoming,
+    /// Functions the item calls
+    Outgoing,
+    Supertypes,
+    Subtypes,
+}
+
+impl HierarchyDirection {
+    /// Parse the name used by plugins ("incoming", "outgoing",
+    /// "supertypes" or "subtypes").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "incoming" => Some(HierarchyDirection::Incoming),
+            "outgoing" => Some(HierarchyDirection::Outgoing),
+            "supertypes" => Some(HierarchyDirection::Supertypes),
+            "subtypes" => Some(HierarchyDirection::Subtypes),
+            _ => None,
+        }
+    }
+
+    /// LSP method of the request
+    pub fn method(self) -> &'static str {
+        match self {
+            HierarchyDirection::Incoming => "callHierarchy/incomingCalls",
+            HierarchyDirection::Outgoing => "callHierarchy/outgoingCalls",
+            HierarchyDirection::Supertypes => "typeHierarchy/supertypes",
+            HierarchyDirection::Subtypes => "typeHierarchy/subtypes",
+        }
+    }
+
+    /// Hierarchy the direction belongs to
+    pub fn kind(self) -> HierarchyKind {
+        match self {
+            HierarchyDirection::Incoming | HierarchyDirection::Outgoing => HierarchyKind::Call,
+            HierarchyDirection::Supertypes | HierarchyDirection::Subtypes => HierarchyKind::Type,
+        }
+    }
+}
+
+/// One node of a call or type hierarchy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HierarchyEntry {
+    /// The item as sent by the server, to pass back when expanding it
+    pub item: Value,
+    pub name: String,
+    pub kind: SymbolKind,
+    pub detail: Option<String>,
+    pub uri: Uri,
+    /// Range of the item's name
+    pub selection_range: Range,
+    /// Where the calls happen, for incoming and outgoing calls. Incoming
+    /// call sites are in the caller, outgoing ones in the expanded item.
+    pub call_sites: Vec<Location>,
+}
+
+impl HierarchyEntry {
+    /// Build an entry from a raw item. Call and type hierarchy items share
+    /// the same shape, so both are read as a `CallHierarchyItem`.
+    fn from_item(item: Value, call_sites: Vec<Location>) -> Option<Self> {
+        let parsed: CallHierarchyItem = serde_json::from_value(item.clone()).ok()?;
+        Some(Self {
+            item,
+            name: parsed.name,
+            kind: parsed.kind,
+            detail: parsed.detail.filter(|d| !d.is_empty()),
+            uri: parsed.uri,
+            selection_range: parsed.selection_range,
+            call_sites,
+        })
+    }
+}
+
+/// Normalize a `prepareCallHierarchy` / `prepareTypeHierarchy` answer.
+pub fn prepared_entries(result: Value) -> Vec<HierarchyEntry> {
+    items(result)
+        .into_iter()
+        .filter_map(|item| HierarchyEntry::from_item(item, Vec::new()))
+        .collect()
+}
+
+/// Normalize the answer to a `direction` request made for `parent`.
+pub fn child_entries(
+    direction: HierarchyDirection,
+    parent: &Value,
+    result: Value,
+) -> Vec<HierarchyEntry> {
+    match direction {
+        HierarchyDirection::Supertypes | HierarchyDirection::Subtypes => prepared_entries(result),
+        HierarchyDirection::Incoming => items(result)
+            .into_iter()
+            .filter_map(|mut call| {
+                let from = call.get_mut("from")?.take();
+                let uri: Uri = serde_json::from_value(from.get("uri")?.clone()).ok()?;
+                let sites = call_sites(&call, &uri);
+                HierarchyEntry::from_item(from, sites)
+            })
+            .collect(),
+        HierarchyDirection::Outgoing => {
+            let Some(parent_uri) = parent
+                .get("uri")
+                .and_then(|uri| serde_json::from_value::<Uri>(uri.clone()).ok())
+            else {
+                return Vec::new();
+            };
+            items(result)
+                .into_iter()
+                .filter_map(|mut call| {
+                    let to = call.get_mut("to")?.take();
+                    let sites = call_sites(&call, &parent_uri);
+                    HierarchyEntry::from_item(to, sites)
+                })
+                .collect()
+        }
+    }
+}
+
+/// The elements of an array answer; `null` means no results.
+fn items(result: Value) -> Vec<Value> {
+    match result {
+        Value::Array(items) => items,
+        _ => Vec::new(),
+    }
+}
+
+/// The `fromRanges` of an incoming or outgoing call, located in `uri`.
+fn call_sites(call: &Value, uri: &Uri) -> Vec<Location> {
+    call.get("fromRanges")
+        .and_then(|ranges| serde_json::from_value::<Vec<Range>>(ranges.clone()).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|range| Location {
+            uri: uri.clone(),
+            range,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Position;
+    use serde_json::json;
+
+    fn item(name: &str, path: &str, line: u32) -> Value {
+        json!({
+            "name": name,
+            "kind": 12,
+            "detail": "",
+            "uri": format!("file:///repo/{}", path),
+            "range": {
+                "start": { "line": line, "character": 0 },
+                "end": { "line": line + 3, "character": 1 }
+            },
+            "selectionRange": {
+                "start": { "line": line, "character": 3 },
+                "end": { "line": line, "character": 3 + name.len() }
+            },
+            "data": { "id": name }
+        })
+    }
+
+    fn range(line: u32, start: u32, end: u32) -> Value {
+        json!({
+            "start": { "line": line, "character": start },
+            "end": { "line": line, "character": end }
+        })
+    }
+
+    #[test]
+    fn prepared_items_keep_the_raw_item() {
+        let entries = prepared_entries(json!([item("parse", "src/parser.rs", 10)]));
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "parse");
+        assert_eq!(entries[0].kind, SymbolKind::FUNCTION);
+        assert_eq!(entries[0].detail, None, "empty detail is dropped");
+        assert_eq!(entries[0].selection_range.start, Position::new(10, 3));
+        assert_eq!(entries[0].item["data"]["id"], "parse");
+        assert!(entries[0].call_sites.is_empty());
+
+        assert!(prepared_entries(Value::Null).is_empty());
+    }
+
+    #[test]
+    fn incoming_call_sites_are_in_the_caller() {
+        let parent = item("parse", "src/parser.rs", 10);
+        let result = json!([{
+            "from": item("main", "src/main.rs", 2),
+            "fromRanges": [range(4, 8, 13), range(7, 4, 9)]
+        }]);
+        let entries = child_entries(HierarchyDirection::Incoming, &parent, result);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "main");
+        let sites: Vec<(&str, u32)> = entries[0]
+            .call_sites
+            .iter()
+            .map(|l| (l.uri.as_str(), l.range.start.line))
+            .collect();
+        assert_eq!(
+            sites,
+            vec![
+                ("file:///repo/src/main.rs", 4),
+                ("file:///repo/src/main.rs", 7)
+            ]
+        );
+    }
+
+    #[test]
+    fn outgoing_call_sites_are_in_the_expanded_item() {
+        let parent = item("parse", "src/parser.rs", 10);
+        let result = json!([{
+            "to": item("tokenize", "src/lexer.rs", 30),
+            "fromRanges": [range(12, 4, 12)]
+        }]);
+        let entries = child_entries(HierarchyDirection::Outgoing, &parent, result);
+        assert_eq!(entries[0].name, "tokenize");
+        assert_eq!(entries[0].uri.as_str(), "file:///repo/src/lexer.rs");
+        assert_eq!(
+            entries[0].call_sites[0].uri.as_str(),
+            "file:///repo/src/parser.rs"
+        );
+        assert_eq!(entries[0].call_sites[0].range.start.line, 12);
+    }
+
+    #[test]
+    fn malformed_items_are_skipped() {
+        let parent = item("Shape", "src/shape.rs", 0);
+        let result = json!([{ "name": "broken" }, item("Circle", "src/circle.rs", 5)]);
+        let entries = child_entries(HierarchyDirection::Subtypes, &parent, result);
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["Circle"]);
+    }
+
+    #[test]
+    fn plugin_names() {
+        assert_eq!(HierarchyKind::from_name("call"), Some(HierarchyKind::Call));
+        assert_eq!(HierarchyKind::from_name("calls"), None);
+        let subtypes = HierarchyDirection::from_name("subtypes").unwrap();
+        assert_eq!(subtypes.method(), "typeHierarchy/subtypes");
+        assert_eq!(subtypes.kind(), HierarchyKind::Type);
+        assert_eq!(
+            HierarchyDirection::from_name("incoming").map(HierarchyDirection::kind),
+            Some(HierarchyKind::Call)
+        );
+    }
+}
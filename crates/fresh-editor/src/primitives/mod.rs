@@ -16,6 +16,7 @@
 
 // Pure modules - available for both runtime and WASM
 pub mod display_width;
+pub mod file_location;
 pub mod glob_match;
 pub mod grapheme;
 pub mod line_wrapping;
@@ -58,8 +59,14 @@ pub mod textmate_engine;
 // Runtime-only modules (depend on tree-sitter)
 // These provide enhanced features using AST analysis
 #[cfg(feature = "runtime")]
+pub mod code_lens;
+#[cfg(feature = "runtime")]
 pub mod detected_language;
 #[cfg(feature = "runtime")]
+pub mod document_symbols;
+#[cfg(feature = "runtime")]
+pub mod hierarchy;
+#[cfg(feature = "runtime")]
 pub mod highlight_engine;
 #[cfg(feature = "runtime")]
 pub mod highlighter;
@@ -67,3 +74,9 @@ pub mod highlighter;
 pub mod indent;
 #[cfg(feature = "runtime")]
 pub mod reference_highlighter;
+#[cfg(feature = "runtime")]
+pub mod syntax_scopes;
+#[cfg(feature = "runtime")]
+pub mod syntax_selection;
+#[cfg(feature = "runtime")]
+pub mod workspace_symbols;
@@ -1,12 +1,20 @@
 //! LSP snippet parser and expander.
 //!
-//! Parses LSP snippet syntax and expands it to plain text with cursor positioning.
-//! Supports:
+//! Parses LSP snippet syntax into plain text plus the tabstops a snippet
+//! session navigates. Supports:
 //! - `$0` - final cursor position
-//! - `$n` - tabstops (expanded as empty, cursor goes to $0 or end)
-//! - `${n:text}` - tabstops with default text (uses the default)
-//! - `${n|choice1,choice2|}` - choices (uses first choice)
+//! - `$n` - tabstops; repeated numbers mirror each other
+//! - `${n:text}` - tabstops with default text (may nest)
+//! - `${n|choice1,choice2|}` - choices (the first one is inserted)
+//! - `$VAR`, `${VAR}`, `${VAR:default}` - variables, resolved by the caller
 //! - `\\$` - escaped dollar sign
+//!
+//! Transforms (`${n/regex/format/}`) are parsed but not applied: a
+//! transformed tabstop mirrors its tabstop unchanged, and a transformed
+//! variable inserts the variable's value.
+
+use std::collections::HashMap;
+use std::ops::Range;
 
 /// Result of expanding a snippet
 #[derive(Debug, Clone, PartialEq)]
@@ -17,6 +25,56 @@ pub struct ExpandedSnippet {
     pub cursor_offset: usize,
 }
 
+/// A snippet with variables resolved, ready to insert
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedSnippet {
+    /// The expanded plain text
+    pub text: String,
+    /// Tabstops in navigation order: `$1`, `$2`, ..., then the final `$0`
+    /// (at the end of the text when the snippet has none)
+    pub tabstops: Vec<SnippetTabstop>,
+}
+
+/// One tabstop of a parsed snippet
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnippetTabstop {
+    /// Tabstop number (0 for the final cursor position)
+    pub index: u32,
+    /// Byte ranges in the text of every occurrence; more than one when
+    /// the tabstop is mirrored
+    pub ranges: Vec<Range<usize>>,
+    /// Options of a `${n|a,b|}` choice, empty otherwise
+    pub choices: Vec<String>,
+}
+
+impl ParsedSnippet {
+    /// Offset of the final cursor position (`$0`)
+    pub fn final_offset(&self) -> usize {
+        self.tabstops
+            .last()
+            .and_then(|t| t.ranges.first())
+            .map_or(self.text.len(), |r| r.start)
+    }
+
+    /// Prefix every line after the first with `indent`, so a multi-line
+    /// snippet lines up with the line it is inserted into.
+    pub fn indent_lines(&mut self, indent: &str) {
+        if indent.is_empty() || !self.text.contains('\n') {
+            return;
+        }
+        // Line starts shift by the indents inserted before them
+        let shift = |offset: usize, text: &str| {
+            offset + indent.len() * text[..offset].matches('\n').count()
+        };
+        for tabstop in &mut self.tabstops {
+            for range in &mut tabstop.ranges {
+                *range = shift(range.start, &self.text)..shift(range.end, &self.text);
+            }
+        }
+        self.text = self.text.replace('\n', &format!("\n{}", indent));
+    }
+}
+
 /// Expand an LSP snippet to plain text
 ///
 /// # Examples
@@ -34,141 +92,365 @@ pub struct ExpandedSnippet {
 /// assert_eq!(result.cursor_offset, 8); // cursor at end (no $0)
 /// ```
 pub fn expand_snippet(snippet: &str) -> ExpandedSnippet {
-    let mut result = String::new();
-    let mut cursor_offset: Option<usize> = None;
-    let mut chars = snippet.chars().peekable();
+    let parsed = parse_snippet(snippet, &|_| None);
+    ExpandedSnippet {
+        cursor_offset: parsed.final_offset(),
+        text: parsed.text,
+    }
+}
 
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            // Escape sequence
-            if let Some(&next) = chars.peek() {
-                if next == '$' || next == '\\' || next == '}' {
-                    result.push(chars.next().unwrap());
-                    continue;
-                }
+/// Parse an LSP snippet, resolving variables through `resolve_variable`.
+///
+/// A variable that resolves to nothing or to an empty value inserts its
+/// default; an unknown one without a default inserts its name.
+pub fn parse_snippet(
+    snippet: &str,
+    resolve_variable: &dyn Fn(&str) -> Option<String>,
+) -> ParsedSnippet {
+    let nodes = Parser {
+        chars: snippet.chars().collect(),
+        pos: 0,
+    }
+    .parse_nodes(false);
+
+    let mut defaults = HashMap::new();
+    collect_defaults(&nodes, &mut defaults);
+    let mut renderer = Renderer {
+        text: String::new(),
+        occurrences: Vec::new(),
+        choices: HashMap::new(),
+        defaults: &defaults,
+        mirroring: Vec::new(),
+        resolve_variable,
+    };
+    renderer.render(&nodes, true);
+
+    let mut by_index: HashMap<u32, Vec<Range<usize>>> = HashMap::new();
+    for (index, range) in renderer.occurrences {
+        by_index.entry(index).or_default().push(range);
+    }
+    let mut tabstops: Vec<SnippetTabstop> = by_index
+        .into_iter()
+        .map(|(index, mut ranges)| {
+            ranges.sort_by_key(|r| r.start);
+            SnippetTabstop {
+                index,
+                ranges,
+                choices: renderer.choices.remove(&index).unwrap_or_default(),
             }
-            result.push(c);
-        } else if c == '$' {
-            // Snippet placeholder
-            if let Some(&next) = chars.peek() {
-                if next == '{' {
-                    // ${...} syntax
-                    chars.next(); // consume '{'
-                    let (expanded, is_final) = parse_brace_placeholder(&mut chars);
-                    if is_final {
-                        cursor_offset = Some(result.len());
-                    }
-                    result.push_str(&expanded);
-                } else if next.is_ascii_digit() {
-                    // $n syntax
-                    let mut num = String::new();
-                    while let Some(&d) = chars.peek() {
-                        if d.is_ascii_digit() {
-                            num.push(chars.next().unwrap());
-                        } else {
-                            break;
+        })
+        .collect();
+    // `$0` goes last
+    tabstops.sort_by_key(|t| (t.index == 0, t.index));
+    if tabstops.last().is_none_or(|t| t.index != 0) {
+        let end = renderer.text.len();
+        tabstops.push(SnippetTabstop {
+            index: 0,
+            ranges: vec![end..end],
+            choices: Vec::new(),
+        });
+    }
+
+    ParsedSnippet {
+        text: renderer.text,
+        tabstops,
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Text(String),
+    Tabstop {
+        index: u32,
+        /// Children of a `${n:...}` placeholder
+        placeholder: Option<Vec<Node>>,
+        choices: Vec<String>,
+    },
+    Variable {
+        name: String,
+        default: Option<Vec<Node>>,
+    },
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    /// Parse until the end, or (inside a placeholder) an unescaped `}`,
+    /// which is left unconsumed.
+    fn parse_nodes(&mut self, in_placeholder: bool) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            match c {
+                '}' if in_placeholder => break,
+                '\\' => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(next @ ('$' | '}' | '\\')) => {
+                            text.push(next);
+                            self.pos += 1;
                         }
+                        _ => text.push('\\'),
                     }
-                    if num == "0" {
-                        cursor_offset = Some(result.len());
+                }
+                '$' => {
+                    self.pos += 1;
+                    match self.parse_dollar() {
+                        Some(node) => {
+                            if !text.is_empty() {
+                                nodes.push(Node::Text(std::mem::take(&mut text)));
+                            }
+                            nodes.push(node);
+                        }
+                        None => text.push('$'),
                     }
-                    // Other tabstops expand to nothing
-                } else {
-                    // Not a valid placeholder, keep the $
-                    result.push(c);
                 }
-            } else {
-                result.push(c);
+                _ => {
+                    text.push(c);
+                    self.pos += 1;
+                }
             }
-        } else {
-            result.push(c);
         }
+        if !text.is_empty() {
+            nodes.push(Node::Text(text));
+        }
+        nodes
     }
 
-    ExpandedSnippet {
-        cursor_offset: cursor_offset.unwrap_or(result.len()),
-        text: result,
+    /// Parse what follows a `$`. On malformed syntax, rewinds and returns
+    /// None so the `$` is kept as text.
+    fn parse_dollar(&mut self) -> Option<Node> {
+        let start = self.pos;
+        let node = self.try_parse_dollar();
+        if node.is_none() {
+            self.pos = start;
+        }
+        node
     }
-}
 
-/// Parse a ${...} placeholder
-/// Returns (expanded_text, is_final_cursor)
-fn parse_brace_placeholder(chars: &mut std::iter::Peekable<std::str::Chars>) -> (String, bool) {
-    let mut content = String::new();
-    let mut depth = 1;
+    fn try_parse_dollar(&mut self) -> Option<Node> {
+        if let Some(index) = self.parse_int() {
+            return Some(Node::Tabstop {
+                index,
+                placeholder: None,
+                choices: Vec::new(),
+            });
+        }
+        if let Some(name) = self.parse_name() {
+            return Some(Node::Variable {
+                name,
+                default: None,
+            });
+        }
+        if self.peek() != Some('{') {
+            return None;
+        }
+        self.pos += 1;
 
-    // Collect everything until matching '}'
-    while let Some(c) = chars.next() {
-        if c == '{' {
-            depth += 1;
-            content.push(c);
-        } else if c == '}' {
-            depth -= 1;
-            if depth == 0 {
-                break;
-            }
-            content.push(c);
-        } else if c == '\\' {
-            // Handle escapes inside braces
-            if let Some(&next) = chars.peek() {
-                if next == '$' || next == '\\' || next == '}' || next == '|' {
-                    content.push(chars.next().unwrap());
-                    continue;
+        if let Some(index) = self.parse_int() {
+            let mut placeholder = None;
+            let mut choices = Vec::new();
+            match self.peek()? {
+                '}' => {}
+                ':' => {
+                    self.pos += 1;
+                    placeholder = Some(self.parse_nodes(true));
+                }
+                '|' => {
+                    self.pos += 1;
+                    choices = self.parse_choices()?;
                 }
+                '/' => self.skip_transform()?,
+                _ => return None,
             }
-            content.push(c);
-        } else {
-            content.push(c);
+            self.expect('}')?;
+            return Some(Node::Tabstop {
+                index,
+                placeholder,
+                choices,
+            });
         }
+
+        let name = self.parse_name()?;
+        let mut default = None;
+        match self.peek()? {
+            '}' => {}
+            ':' => {
+                self.pos += 1;
+                default = Some(self.parse_nodes(true));
+            }
+            '/' => self.skip_transform()?,
+            _ => return None,
+        }
+        self.expect('}')?;
+        Some(Node::Variable { name, default })
     }
 
-    // Parse the content: n or n:default or n|choices|
-    let (tabstop, default) = parse_placeholder_content(&content);
+    fn expect(&mut self, c: char) -> Option<()> {
+        (self.peek()? == c).then(|| self.pos += 1)
+    }
 
-    let is_final = tabstop == Some(0);
-    (default, is_final)
-}
+    fn parse_int(&mut self) -> Option<u32> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let digits: String = self.chars[start..self.pos].iter().collect();
+        digits.parse().ok()
+    }
 
-/// Parse placeholder content like "1", "1:default", or "1|a,b,c|"
-/// Returns (tabstop_number, default_text)
-fn parse_placeholder_content(content: &str) -> (Option<u32>, String) {
-    // Find the tabstop number
-    let mut chars = content.chars().peekable();
-    let mut num_str = String::new();
-
-    while let Some(&c) = chars.peek() {
-        if c.is_ascii_digit() {
-            num_str.push(chars.next().unwrap());
-        } else {
-            break;
+    fn parse_name(&mut self) -> Option<String> {
+        if !self
+            .peek()
+            .is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        {
+            return None;
         }
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c == '_' || c.is_ascii_alphanumeric())
+        {
+            self.pos += 1;
+        }
+        Some(self.chars[start..self.pos].iter().collect())
     }
 
-    let tabstop = num_str.parse::<u32>().ok();
+    /// Parse `a,b,c|` (after the opening `|`)
+    fn parse_choices(&mut self) -> Option<Vec<String>> {
+        let mut choices = Vec::new();
+        let mut current = String::new();
+        loop {
+            let c = self.peek()?;
+            self.pos += 1;
+            match c {
+                '\\' => match self.peek() {
+                    Some(next @ ('$' | '}' | '\\' | ',' | '|')) => {
+                        current.push(next);
+                        self.pos += 1;
+                    }
+                    _ => current.push('\\'),
+                },
+                ',' => choices.push(std::mem::take(&mut current)),
+                '|' => {
+                    choices.push(current);
+                    return Some(choices);
+                }
+                _ => current.push(c),
+            }
+        }
+    }
 
-    // Check what follows
-    match chars.peek() {
-        Some(':') => {
-            // Default text: ${n:default}
-            chars.next(); // consume ':'
-            let default: String = chars.collect();
-            // Recursively expand nested snippets in default
-            let expanded = expand_snippet(&default);
-            (tabstop, expanded.text)
+    /// Skip `/regex/format/options` up to (not including) the closing `}`
+    fn skip_transform(&mut self) -> Option<()> {
+        let mut depth = 0;
+        loop {
+            match self.peek()? {
+                '\\' => self.pos += 1,
+                '{' => depth += 1,
+                '}' if depth == 0 => return Some(()),
+                '}' => depth -= 1,
+                _ => {}
+            }
+            self.pos += 1;
         }
-        Some('|') => {
-            // Choices: ${n|choice1,choice2|}
-            chars.next(); // consume '|'
-            let choices: String = chars.collect();
-            // Remove trailing '|' if present
-            let choices = choices.trim_end_matches('|');
-            // Use first choice
-            let first_choice = choices.split(',').next().unwrap_or("");
-            (tabstop, first_choice.to_string())
+    }
+}
+
+/// First occurrence of each tabstop that defines its text; plain `$n`
+/// occurrences mirror it.
+fn collect_defaults<'a>(nodes: &'a [Node], defaults: &mut HashMap<u32, &'a Node>) {
+    for node in nodes {
+        match node {
+            Node::Tabstop {
+                index,
+                placeholder,
+                choices,
+            } => {
+                if placeholder.is_some() || !choices.is_empty() {
+                    defaults.entry(*index).or_insert(node);
+                }
+                if let Some(children) = placeholder {
+                    collect_defaults(children, defaults);
+                }
+            }
+            Node::Variable {
+                default: Some(children),
+                ..
+            } => collect_defaults(children, defaults),
+            _ => {}
         }
-        _ => {
-            // Just a tabstop number, no default
-            (tabstop, String::new())
+    }
+}
+
+struct Renderer<'a> {
+    text: String,
+    /// Every recorded tabstop occurrence, in text order
+    occurrences: Vec<(u32, Range<usize>)>,
+    choices: HashMap<u32, Vec<String>>,
+    defaults: &'a HashMap<u32, &'a Node>,
+    /// Tabstops whose text is being copied into a mirror (guards cycles)
+    mirroring: Vec<u32>,
+    resolve_variable: &'a dyn Fn(&str) -> Option<String>,
+}
+
+impl Renderer<'_> {
+    /// Append `nodes`; tabstops inside mirrored text are not recorded.
+    fn render(&mut self, nodes: &[Node], record: bool) {
+        for node in nodes {
+            match node {
+                Node::Text(text) => self.text.push_str(text),
+                Node::Tabstop {
+                    index,
+                    placeholder,
+                    choices,
+                } => {
+                    let start = self.text.len();
+                    let defaults = self.defaults;
+                    if let Some(children) = placeholder {
+                        self.render(children, record);
+                    } else if let Some(first) = choices.first() {
+                        self.text.push_str(first);
+                    } else if let Some(Node::Tabstop {
+                        placeholder,
+                        choices,
+                        ..
+                    }) = defaults.get(index).copied()
+                    {
+                        if !self.mirroring.contains(index) {
+                            self.mirroring.push(*index);
+                            match placeholder {
+                                Some(children) => self.render(children, false),
+                                None => self.text.push_str(choices.first().map_or("", |c| c)),
+                            }
+                            self.mirroring.pop();
+                        }
+                    }
+                    if record {
+                        self.occurrences.push((*index, start..self.text.len()));
+                        if !choices.is_empty() {
+                            self.choices
+                                .entry(*index)
+                                .or_insert_with(|| choices.clone());
+                        }
+                    }
+                }
+                Node::Variable { name, default } => {
+                    match ((self.resolve_variable)(name), default) {
+                        (Some(value), _) if !value.is_empty() => self.text.push_str(&value),
+                        (_, Some(children)) => self.render(children, record),
+                        (Some(_), None) => {}
+                        (None, None) => self.text.push_str(name),
+                    }
+                }
+            }
         }
     }
 }
@@ -277,4 +559,67 @@ mod tests {
         assert_eq!(result.text, "foo()");
         assert_eq!(result.cursor_offset, 5);
     }
+
+    fn no_variables(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn test_tabstop_ranges_in_navigation_order() {
+        let parsed = parse_snippet("fn ${1:name}(${2:args}) { $0 }", &no_variables);
+        assert_eq!(parsed.text, "fn name(args) {  }");
+        let order: Vec<u32> = parsed.tabstops.iter().map(|t| t.index).collect();
+        assert_eq!(order, vec![1, 2, 0]);
+        assert_eq!(parsed.tabstops[0].ranges, vec![3..7]);
+        assert_eq!(parsed.tabstops[1].ranges, vec![8..12]);
+        assert_eq!(parsed.tabstops[2].ranges, vec![16..16]);
+    }
+
+    #[test]
+    fn test_mirrors_copy_the_placeholder() {
+        let parsed = parse_snippet("$1 = ${1:x}; use($1)", &no_variables);
+        assert_eq!(parsed.text, "x = x; use(x)");
+        assert_eq!(parsed.tabstops[0].ranges, vec![0..1, 4..5, 11..12]);
+        // Implicit final tabstop at the end
+        assert_eq!(parsed.tabstops[1].ranges, vec![13..13]);
+    }
+
+    #[test]
+    fn test_choices_are_kept() {
+        let parsed = parse_snippet("${1|a,b\\,c|}", &no_variables);
+        assert_eq!(parsed.text, "a");
+        assert_eq!(parsed.tabstops[0].choices, vec!["a", "b,c"]);
+    }
+
+    #[test]
+    fn test_variables() {
+        let resolve = |name: &str| match name {
+            "TM_FILENAME" => Some("main.rs".to_string()),
+            "TM_SELECTED_TEXT" => Some(String::new()),
+            _ => None,
+        };
+        let parsed = parse_snippet(
+            "// $TM_FILENAME ${UNKNOWN:fallback} ${TM_FILENAME/(.*)/$1/} $OTHER",
+            &resolve,
+        );
+        assert_eq!(parsed.text, "// main.rs fallback main.rs OTHER");
+
+        let parsed = parse_snippet("[$TM_SELECTED_TEXT|${TM_SELECTED_TEXT:none}]", &resolve);
+        assert_eq!(parsed.text, "[|none]");
+    }
+
+    #[test]
+    fn test_malformed_placeholder_is_text() {
+        let parsed = parse_snippet("a ${1 b $", &no_variables);
+        assert_eq!(parsed.text, "a ${1 b $");
+    }
+
+    #[test]
+    fn test_indent_lines_shifts_ranges() {
+        let mut parsed = parse_snippet("if ${1:x} {\n\t$0\n}", &no_variables);
+        parsed.indent_lines("    ");
+        assert_eq!(parsed.text, "if x {\n    \t\n    }");
+        assert_eq!(parsed.tabstops[0].ranges, vec![3..4]);
+        assert_eq!(parsed.final_offset(), 12);
+    }
 }
@@ -0,0 +1,200 @@
+//! Block-opening definitions found in the tree-sitter syntax tree.
+//!
+//! A scope is a definition whose body spans several lines: a function,
+//! method, class, impl, trait, module, struct or enum. Grammars name these
+//! nodes consistently enough (`function_item`, `class_definition`,
+//! `method_declaration`, `struct_specifier`, Ruby's bare `class`, ...) that
+//! they are recognized by node kind rather than with a per-language query.
+//!
+//! Used by sticky scroll to find the scopes enclosing the top of a split.
+//! Only a [`SyntaxWindow`] around the top is parsed, so the cost of
+//! reparsing after an edit doesn't grow with the buffer. Scopes opening
+//! above the window are left to the caller's other scope sources.
+
+use crate::model::buffer::Buffer;
+use crate::primitives::highlighter::Language;
+use crate::primitives::syntax_selection::SyntaxWindow;
+use std::ops::Range;
+
+/// Size of the blocks the buffer is split into for parsing. The block
+/// holding the top of the split is parsed together with one block of
+/// context on each side, see [`scope_window`].
+pub const SCOPE_BLOCK_BYTES: usize = 32 * 1024;
+
+/// First word of the node kinds that open a scope.
+const SCOPE_KEYWORDS: &[&str] = &[
+    "function",
+    "method",
+    "constructor",
+    "class",
+    "impl",
+    "trait",
+    "interface",
+    "mod",
+    "module",
+    "namespace",
+    "struct",
+    "enum",
+    "object",
+    "record",
+];
+
+/// Rest of the node kinds that open a scope. `function_item` qualifies,
+/// `function_signature_item` and `method_invocation` do not.
+const SCOPE_SUFFIXES: &[&str] = &["item", "definition", "declaration", "specifier"];
+
+/// A multi-line scope in the syntax tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxScope {
+    /// Byte range of the whole definition
+    pub range: Range<usize>,
+    /// 0-based line the definition starts on
+    pub start_line: usize,
+    /// 0-based last line of the definition
+    pub end_line: usize,
+}
+
+/// The block holding byte `top`. Scopes are parsed per block so that
+/// scrolling within one reuses them.
+pub fn scope_block(top: usize) -> Range<usize> {
+    let start = top - top % SCOPE_BLOCK_BYTES;
+    start..start + SCOPE_BLOCK_BYTES
+}
+
+/// The bytes parsed for the scopes of `block`.
+pub fn scope_window(block: &Range<usize>) -> Range<usize> {
+    block.start.saturating_sub(SCOPE_BLOCK_BYTES)..block.end.saturating_add(SCOPE_BLOCK_BYTES)
+}
+
+/// Whether a node of this kind opens a scope.
+pub fn is_scope_kind(kind: &str) -> bool {
+    let (head, rest) = kind.split_once('_').unwrap_or((kind, ""));
+    SCOPE_KEYWORDS.contains(&head) && (rest.is_empty() || SCOPE_SUFFIXES.contains(&rest))
+}
+
+/// The multi-line scopes of `buffer` that open within `block` and the
+/// blocks next to it, in document order, outer scopes before the scopes
+/// they contain. Scopes still open at the end of the parsed window end
+/// with it.
+///
+/// Returns `None` when the language has no grammar or the buffer has no
+/// line index to place the scopes with.
+pub fn syntax_scopes(
+    buffer: &Buffer,
+    language: &Language,
+    block: Range<usize>,
+) -> Option<Vec<SyntaxScope>> {
+    let window = SyntaxWindow::parse_around(buffer, language, block, SCOPE_BLOCK_BYTES)?;
+    let first_line = buffer.offset_to_position(window.offset())?.line;
+
+    let mut scopes = Vec::new();
+    let mut cursor = window.root_node().walk();
+    loop {
+        let node = cursor.node();
+        let start = node.start_position();
+        let end = node.end_position();
+        // A node ending at column 0 ends with the previous line's newline
+        let end_line = if end.column == 0 && end.row > start.row {
+            end.row - 1
+        } else {
+            end.row
+        };
+        let multi_line = end_line > start.row;
+        if multi_line && is_scope_kind(node.kind()) {
+            // A scope cut off by the start of the window has lost its header
+            let cut = window.offset() > 0 && node.start_byte() == 0;
+            if !cut {
+                let range = node.byte_range();
+                scopes.push(SyntaxScope {
+                    range: range.start + window.offset()..range.end + window.offset(),
+                    start_line: first_line + start.row,
+                    end_line: first_line + end_line,
+                });
+            }
+        }
+        // Nodes on a single line cannot contain multi-line scopes
+        if multi_line && cursor.goto_first_child() {
+            continue;
+        }
+        loop {
+            if cursor.goto_next_sibling() {
+                break;
+            }
+            if !cursor.goto_parent() {
+                return Some(scopes);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scope_kinds() {
+        for kind in [
+            "function_item",
+            "impl_item",
+            "mod_item",
+            "class_definition",
+            "method_declaration",
+            "struct_specifier",
+            "class",
+            "module",
+        ] {
+            assert!(is_scope_kind(kind), "{kind}");
+        }
+        for kind in [
+            "function_signature_item",
+            "method_invocation",
+            "call_expression",
+            "block",
+            "let_declaration",
+        ] {
+            assert!(!is_scope_kind(kind), "{kind}");
+        }
+    }
+
+    #[test]
+    fn nested_rust_scopes_outermost_first() {
+        let source = "\
+impl Point {
+    fn norm(&self) -> i32 {
+        self.x
+    }
+
+    fn zero() -> Self { Self { x: 0 } }
+}
+";
+        let buffer = Buffer::from_str_test(source);
+        let scopes = syntax_scopes(&buffer, &Language::Rust, scope_block(0)).unwrap();
+        let lines: Vec<_> = scopes.iter().map(|s| (s.start_line, s.end_line)).collect();
+        // The one-line `zero` has no body to scroll through
+        assert_eq!(lines, vec![(0, 6), (1, 3)]);
+        assert!(source[scopes[1].range.clone()].starts_with("fn norm"));
+    }
+
+    #[test]
+    fn scopes_far_into_a_buffer_are_placed_by_line() {
+        // Enough filler that the window starts well into the buffer
+        let filler = "// filler lines\n".repeat(3 * SCOPE_BLOCK_BYTES / 16);
+        let source = format!("{filler}fn far() {{\n    1\n}}\n");
+        let buffer = Buffer::from_str_test(&source);
+        let fn_start = filler.len();
+        let first_line = filler.lines().count();
+
+        let scopes = syntax_scopes(&buffer, &Language::Rust, scope_block(fn_start)).unwrap();
+        assert_eq!(scopes.len(), 1);
+        assert_eq!(scopes[0].range.start, fn_start);
+        assert_eq!(
+            (scopes[0].start_line, scopes[0].end_line),
+            (first_line, first_line + 2)
+        );
+
+        // Blocks more than one block away don't see it
+        assert!(syntax_scopes(&buffer, &Language::Rust, scope_block(0))
+            .unwrap()
+            .is_empty());
+    }
+}
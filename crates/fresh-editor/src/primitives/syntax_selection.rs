@@ -0,0 +1,325 @@
+//! Syntax-aware expand/shrink selection.
+//!
+//! Expanding a selection picks the smallest tree-sitter node that strictly
+//! contains it; shrinking picks the largest node inside the selection that
+//! still contains the cursor. Repeated expansion therefore walks outward
+//! through the syntax tree: identifier → call → statement → block → item.
+//!
+//! The editor keeps its own history stack on top of this so that shrinking
+//! after a series of expansions returns to exactly the previous ranges (see
+//! `app/selection_expansion.rs`). The structural shrink here is only used
+//! when there is no history to pop.
+//!
+//! # Performance
+//! Buffers up to [`MAX_FULL_PARSE_BYTES`] are parsed whole. Larger buffers
+//! parse a window of [`WINDOW_CONTEXT_BYTES`] around the selection. Nodes
+//! that reach the edge of a partial window are ignored rather than reported
+//! with truncated ranges. [`SyntaxWindowCache`] keeps the last parse, so
+//! repeated steps on an unchanged buffer don't parse it again.
+
+use crate::model::buffer::Buffer;
+use crate::primitives::highlighter::Language;
+use fresh_languages::tree_sitter::{Node, Parser, Tree};
+use std::ops::Range;
+
+/// Buffers at or below this size are parsed in full.
+const MAX_FULL_PARSE_BYTES: usize = 1024 * 1024;
+
+/// Context parsed on each side of the selection for larger buffers.
+const WINDOW_CONTEXT_BYTES: usize = 64 * 1024;
+
+/// A parse tree covering some window of a buffer.
+pub struct SyntaxWindow {
+    tree: Tree,
+    /// Buffer offset of the first parsed byte.
+    offset: usize,
+    /// Number of parsed bytes.
+    len: usize,
+    /// Whether the window spans the whole buffer.
+    complete: bool,
+}
+
+impl SyntaxWindow {
+    /// Parse the part of `buffer` around `range` with `language`'s grammar.
+    ///
+    /// Returns `None` if the language has no tree-sitter grammar or parsing
+    /// fails.
+    pub fn parse(buffer: &Buffer, language: &Language, range: Range<usize>) -> Option<Self> {
+        let context_bytes = if buffer.len() <= MAX_FULL_PARSE_BYTES {
+            buffer.len()
+        } else {
+            WINDOW_CONTEXT_BYTES
+        };
+        Self::parse_around(buffer, language, range, context_bytes)
+    }
+
+    /// Parse `range` and up to `context_bytes` on each side of it, whatever
+    /// the size of `buffer`.
+    pub fn parse_around(
+        buffer: &Buffer,
+        language: &Language,
+        range: Range<usize>,
+        context_bytes: usize,
+    ) -> Option<Self> {
+        let ts_language = language.tree_sitter_language()?;
+        let mut parser = Parser::new();
+        if parser.set_language(&ts_language).is_err() {
+            tracing::warn!("Failed to set language {} for syntax parser", language);
+            return None;
+        }
+
+        let buffer_len = buffer.len();
+        let window = range.start.saturating_sub(context_bytes).min(buffer_len)
+            ..range.end.saturating_add(context_bytes).min(buffer_len);
+
+        let source = buffer.slice_bytes(window.clone());
+        let tree = parser.parse(&source, None)?;
+        Some(Self {
+            tree,
+            offset: window.start,
+            len: source.len(),
+            complete: window.start == 0 && window.end == buffer_len,
+        })
+    }
+
+    /// Root of the window's syntax tree. Its byte offsets are relative to
+    /// [`Self::offset`] and its rows to the line holding it.
+    pub fn root_node(&self) -> Node<'_> {
+        self.tree.root_node()
+    }
+
+    /// Buffer offset of the first parsed byte.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Whether `range` lies inside the parsed window.
+    pub fn covers(&self, range: &Range<usize>) -> bool {
+        self.complete || (range.start >= self.offset && range.end <= self.offset + self.len)
+    }
+
+    /// Smallest syntax node that strictly contains `range`.
+    pub fn expand(&self, range: Range<usize>) -> Option<Range<usize>> {
+        if !self.covers(&range) {
+            return None;
+        }
+        let local = self.to_local(&range);
+        let mut node = self
+            .tree
+            .root_node()
+            .descendant_for_byte_range(local.start, local.end)?;
+        loop {
+            let node_range = node.byte_range();
+            if strictly_contains(&node_range, &local) {
+                return self.buffer_range(node_range);
+            }
+            node = node.parent()?;
+        }
+    }
+
+    /// Largest syntax node strictly inside `range` that contains `cursor`.
+    pub fn shrink(&self, range: Range<usize>, cursor: usize) -> Option<Range<usize>> {
+        if range.is_empty() || !self.covers(&range) {
+            return None;
+        }
+        let local = self.to_local(&range);
+        // A selection made by expanding has its cursor at the end, one past
+        // the last selected byte; look at the byte before it instead.
+        let cursor = cursor.clamp(range.start, range.end - 1) - self.offset;
+
+        let mut node = self
+            .tree
+            .root_node()
+            .descendant_for_byte_range(cursor, cursor)?;
+        let mut best: Option<Node> = None;
+        loop {
+            let node_range = node.byte_range();
+            if !strictly_contains(&local, &node_range) {
+                break;
+            }
+            if !node_range.is_empty() {
+                best = Some(node);
+            }
+            match node.parent() {
+                Some(parent) => node = parent,
+                None => break,
+            }
+        }
+        best.and_then(|n| self.buffer_range(n.byte_range()))
+    }
+
+    fn to_local(&self, range: &Range<usize>) -> Range<usize> {
+        range.start - self.offset..range.end - self.offset
+    }
+
+    /// Convert a window-local range to buffer offsets, rejecting ranges that
+    /// touch the edge of a partial window (they may be truncated).
+    pub fn buffer_range(&self, range: Range<usize>) -> Option<Range<usize>> {
+        if !self.complete {
+            let at_start_edge = range.start == 0 && self.offset > 0;
+            let at_end_edge = range.end >= self.len;
+            if at_start_edge || at_end_edge {
+                return None;
+            }
+        }
+        Some(range.start + self.offset..range.end + self.offset)
+    }
+}
+
+/// The last [`SyntaxWindow`] parsed for a buffer, reused until the buffer
+/// changes or a selection leaves it.
+#[derive(Default)]
+pub struct SyntaxWindowCache {
+    /// Buffer version and language the window was parsed for
+    key: Option<(u64, Language)>,
+    window: Option<SyntaxWindow>,
+}
+
+impl SyntaxWindowCache {
+    /// A window of `buffer` around `range`, parsed with `language` unless
+    /// the cached one is still current. A partial window is only reused
+    /// while `range` stays well inside it, so that the nodes enclosing
+    /// `range` are not cut off by its edge.
+    pub fn get_or_parse(
+        &mut self,
+        buffer: &Buffer,
+        language: &Language,
+        range: Range<usize>,
+    ) -> Option<&SyntaxWindow> {
+        let key = Some((buffer.version(), *language));
+        let margin = WINDOW_CONTEXT_BYTES / 2;
+        let needed =
+            range.start.saturating_sub(margin)..range.end.saturating_add(margin).min(buffer.len());
+        let current = self.key == key && self.window.as_ref().is_some_and(|w| w.covers(&needed));
+        if !current {
+            self.window = SyntaxWindow::parse(buffer, language, range);
+            self.key = key;
+        }
+        self.window.as_ref()
+    }
+}
+
+/// Smallest of `candidates` that strictly contains `current`.
+///
+/// Used for LSP `textDocument/selectionRange` results, which arrive as a
+/// chain of ranges from innermost to outermost.
+pub fn next_enclosing_range(
+    candidates: impl IntoIterator<Item = Range<usize>>,
+    current: &Range<usize>,
+) -> Option<Range<usize>> {
+    candidates
+        .into_iter()
+        .filter(|candidate| strictly_contains(candidate, current))
+        .min_by_key(|candidate| candidate.len())
+}
+
+/// `outer` contains `inner` and is larger than it.
+fn strictly_contains(outer: &Range<usize>, inner: &Range<usize>) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end && outer.len() > inner.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUST_SOURCE: &str = "fn main() {\n    let total = add(1, 2);\n}\n";
+
+    fn parse(text: &str) -> (Buffer, SyntaxWindow) {
+        let buffer = Buffer::from_str_test(text);
+        let window = SyntaxWindow::parse(&buffer, &Language::Rust, 0..0).unwrap();
+        (buffer, window)
+    }
+
+    fn text_of(text: &str, range: Range<usize>) -> &str {
+        &text[range]
+    }
+
+    #[test]
+    fn expand_from_cursor_selects_identifier() {
+        let (_buffer, window) = parse(RUST_SOURCE);
+        let pos = RUST_SOURCE.find("add").unwrap() + 1;
+        let range = window.expand(pos..pos).unwrap();
+        assert_eq!(text_of(RUST_SOURCE, range), "add");
+    }
+
+    #[test]
+    fn repeated_expand_walks_outward() {
+        let (_buffer, window) = parse(RUST_SOURCE);
+        let pos = RUST_SOURCE.find("add").unwrap();
+        let ident = window.expand(pos..pos).unwrap();
+        let call = window.expand(ident.clone()).unwrap();
+        assert_eq!(text_of(RUST_SOURCE, call.clone()), "add(1, 2)");
+        let mut range = call;
+        let mut previous_len = range.len();
+        while let Some(next) = window.expand(range.clone()) {
+            assert!(next.len() > previous_len, "expansion must grow");
+            previous_len = next.len();
+            range = next;
+        }
+        assert_eq!(range, 0..RUST_SOURCE.len());
+    }
+
+    #[test]
+    fn expand_fails_at_root() {
+        let (_buffer, window) = parse(RUST_SOURCE);
+        assert_eq!(window.expand(0..RUST_SOURCE.len()), None);
+    }
+
+    #[test]
+    fn shrink_picks_child_containing_cursor() {
+        let (_buffer, window) = parse(RUST_SOURCE);
+        let start = RUST_SOURCE.find("add(1, 2)").unwrap();
+        let call = start..start + "add(1, 2)".len();
+        // Cursor at the end of the selection, as left by expansion.
+        let shrunk = window.shrink(call.clone(), call.end).unwrap();
+        assert_eq!(text_of(RUST_SOURCE, shrunk), "(1, 2)");
+        // Cursor at the start picks the callee.
+        let shrunk = window.shrink(call.clone(), call.start).unwrap();
+        assert_eq!(text_of(RUST_SOURCE, shrunk), "add");
+    }
+
+    #[test]
+    fn shrink_of_single_token_is_none() {
+        let (_buffer, window) = parse(RUST_SOURCE);
+        let start = RUST_SOURCE.find("total").unwrap();
+        assert_eq!(window.shrink(start..start + 5, start), None);
+    }
+
+    #[test]
+    fn cached_window_is_parsed_again_after_an_edit() {
+        let mut buffer = Buffer::from_str_test(RUST_SOURCE);
+        let mut cache = SyntaxWindowCache::default();
+        let pos = RUST_SOURCE.find("add").unwrap();
+        let window = cache
+            .get_or_parse(&buffer, &Language::Rust, pos..pos)
+            .unwrap();
+        assert_eq!(window.expand(pos..pos), Some(pos..pos + 3));
+
+        buffer.insert(0, "// c\n");
+        let pos = pos + "// c\n".len();
+        let window = cache
+            .get_or_parse(&buffer, &Language::Rust, pos..pos)
+            .unwrap();
+        assert_eq!(window.expand(pos..pos), Some(pos..pos + 3));
+    }
+
+    #[test]
+    fn parse_empty_buffer() {
+        let buffer = Buffer::from_str_test("");
+        assert!(SyntaxWindow::parse(&buffer, &Language::Python, 0..0).is_some());
+    }
+
+    #[test]
+    fn next_enclosing_range_picks_smallest_strict_superset() {
+        let candidates = vec![4..6, 2..10, 0..20, 4..8];
+        assert_eq!(
+            next_enclosing_range(candidates.clone(), &(4..6)),
+            Some(4..8)
+        );
+        assert_eq!(
+            next_enclosing_range(candidates.clone(), &(4..8)),
+            Some(2..10)
+        );
+        assert_eq!(next_enclosing_range(candidates, &(0..20)), None);
+    }
+}
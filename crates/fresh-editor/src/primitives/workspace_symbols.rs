@@ -0,0 +1,145 @@
+//! Workspace symbols for the `%` quick open.
+//!
+//! `workspace/symbol` answers come in two shapes (`SymbolInformation` and
+//! the newer `WorkspaceSymbol`); both are normalized into
+//! [`WorkspaceSymbolEntry`]s here. Merging answers from several servers
+//! and discarding duplicates is done by the caller.
+
+use lsp_types::{Location, OneOf, SymbolKind, Uri, WorkspaceSymbolResponse};
+
+/// One workspace symbol, ready to display and jump to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceSymbolEntry {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Name of the enclosing symbol or module, if the server reports one
+    pub container: Option<String>,
+    pub location: Location,
+    /// Path shown to the user, usually relative to the workspace root
+    pub display_path: String,
+}
+
+impl WorkspaceSymbolEntry {
+    /// Identity used to drop the same symbol reported by several servers.
+    pub fn dedup_key(&self) -> (String, String, u32) {
+        (
+            self.name.clone(),
+            self.location.uri.as_str().to_string(),
+            self.location.range.start.line,
+        )
+    }
+}
+
+/// Normalize a `workspace/symbol` response.
+///
+/// `display_path` turns a symbol's URI into the path shown to the user.
+/// Symbols whose location only names a file (`WorkspaceLocation`) point
+/// at its start, since this client does not resolve them.
+pub fn workspace_symbol_entries(
+    response: WorkspaceSymbolResponse,
+    display_path: impl Fn(&Uri) -> String,
+) -> Vec<WorkspaceSymbolEntry> {
+    let entry = |name: String, kind, container: Option<String>, location: Location| {
+        WorkspaceSymbolEntry {
+            display_path: display_path(&location.uri),
+            name,
+            kind,
+            container: container.filter(|c| !c.is_empty()),
+            location,
+        }
+    };
+
+    match response {
+        WorkspaceSymbolResponse::Flat(symbols) => symbols
+            .into_iter()
+            .map(|s| entry(s.name, s.kind, s.container_name, s.location))
+            .collect(),
+        WorkspaceSymbolResponse::Nested(symbols) => symbols
+            .into_iter()
+            .map(|s| {
+                let location = match s.location {
+                    OneOf::Left(location) => location,
+                    OneOf::Right(workspace_location) => Location {
+                        uri: workspace_location.uri,
+                        range: lsp_types::Range::default(),
+                    },
+                };
+                entry(s.name, s.kind, s.container_name, location)
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{Position, Range, SymbolInformation, WorkspaceLocation, WorkspaceSymbol};
+
+    fn uri(path: &str) -> Uri {
+        format!("file:///repo/{}", path).parse().unwrap()
+    }
+
+    fn display(uri: &Uri) -> String {
+        uri.as_str().trim_start_matches("file:///repo/").to_string()
+    }
+
+    #[allow(deprecated)]
+    fn information(name: &str, path: &str, line: u32) -> SymbolInformation {
+        SymbolInformation {
+            name: name.to_string(),
+            kind: SymbolKind::STRUCT,
+            tags: None,
+            deprecated: None,
+            location: Location {
+                uri: uri(path),
+                range: Range::new(Position::new(line, 4), Position::new(line, 10)),
+            },
+            container_name: Some(String::new()),
+        }
+    }
+
+    #[test]
+    fn flat_response_keeps_locations() {
+        let entries = workspace_symbol_entries(
+            WorkspaceSymbolResponse::Flat(vec![information("Config", "src/config.rs", 12)]),
+            display,
+        );
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "Config");
+        assert_eq!(entries[0].display_path, "src/config.rs");
+        assert_eq!(entries[0].location.range.start, Position::new(12, 4));
+        assert_eq!(entries[0].container, None, "empty container is dropped");
+    }
+
+    #[test]
+    fn nested_response_without_range_points_at_file_start() {
+        let entries = workspace_symbol_entries(
+            WorkspaceSymbolResponse::Nested(vec![WorkspaceSymbol {
+                name: "parse".to_string(),
+                kind: SymbolKind::FUNCTION,
+                tags: None,
+                container_name: Some("parser".to_string()),
+                location: OneOf::Right(WorkspaceLocation {
+                    uri: uri("src/parser.rs"),
+                }),
+                data: None,
+            }]),
+            display,
+        );
+        assert_eq!(entries[0].container.as_deref(), Some("parser"));
+        assert_eq!(entries[0].location.range, Range::default());
+    }
+
+    #[test]
+    fn dedup_key_ignores_column_and_kind() {
+        let a = workspace_symbol_entries(
+            WorkspaceSymbolResponse::Flat(vec![information("Config", "src/config.rs", 12)]),
+            display,
+        );
+        let mut other = information("Config", "src/config.rs", 12);
+        other.kind = SymbolKind::CLASS;
+        other.location.range.start.character = 0;
+        let b = workspace_symbol_entries(WorkspaceSymbolResponse::Flat(vec![other]), display);
+        assert_eq!(a[0].dedup_key(), b[0].dedup_key());
+    }
+}
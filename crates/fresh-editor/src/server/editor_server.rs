@@ -3,8 +3,12 @@
 //! This module bridges the Editor with the server infrastructure:
 //! - Creates Editor with CaptureBackend for rendering
 //! - Processes input events from clients
-//! - Broadcasts rendered output to all clients
+//! - Renders each client's own view (see `app::client_views`) and sends
+//!   it to that client
+//! - Answers remote-control requests (see `app::remote_control`) and
+//!   forwards subscribed events
 
+use std::collections::HashSet;
 use std::io;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -68,10 +72,10 @@ pub struct EditorServer {
     listener: ServerListener,
     clients: Vec<ConnectedClient>,
     editor: Option<Editor>,
-    terminal: Option<Terminal<CaptureBackend>>,
     last_client_activity: Instant,
     shutdown: Arc<AtomicBool>,
-    /// Effective terminal size (from the primary/first client)
+    /// Terminal size the editor is built with (from the first client).
+    /// Each client's own size lives on its `ConnectedClient`.
     term_size: TermSize,
     /// Index of the client that most recently provided input (for per-client detach)
     last_input_client: Option<usize>,
@@ -151,11 +155,14 @@ impl ClientDataWriter {
     }
 }
 
-/// A connected client with its own input parser
+/// A connected client with its own input parser and view
 struct ConnectedClient {
     conn: ServerConnection,
     /// Background writer for non-blocking data output
     data_writer: ClientDataWriter,
+    /// Render target sized to this client's terminal; keeps the last
+    /// frame sent to this client so only changes are sent.
+    terminal: Terminal<CaptureBackend>,
     term_size: TermSize,
     env: std::collections::HashMap<String, Option<String>>,
     id: u64,
@@ -164,6 +171,11 @@ struct ConnectedClient {
     needs_full_render: bool,
     /// If set, this client is waiting for a --wait completion signal
     wait_id: Option<u64>,
+    /// Set once the client sends a remote-control request; such clients
+    /// have no view and are never rendered to
+    remote: bool,
+    /// Plugin hooks forwarded to this client as notifications
+    subscriptions: HashSet<String>,
 }
 
 impl EditorServer {
@@ -196,7 +208,6 @@ impl EditorServer {
             listener,
             clients: Vec::new(),
             editor: None,
-            terminal: None,
             last_client_activity: Instant::now(),
             shutdown: Arc::new(AtomicBool::new(false)),
             term_size: TermSize::new(80, 24), // Default until first client connects
@@ -266,17 +277,21 @@ impl EditorServer {
                         Ok(client) => {
                             tracing::info!("Client {} connected", client.id);
 
-                            // Initialize editor on first-ever client, or update size if reconnecting
+                            // Initialize editor on first-ever client
                             if self.editor.is_none() {
-                                // First time - initialize editor
                                 self.term_size = client.term_size;
                                 self.initialize_editor()?;
-                            } else if self.clients.is_empty() {
-                                // Reconnecting after all clients disconnected - update terminal size
-                                if self.term_size != client.term_size {
-                                    self.term_size = client.term_size;
-                                    self.update_terminal_size()?;
-                                }
+                            }
+                            // The client starts from a copy of the live
+                            // view; the first one (or the first after all
+                            // others left) takes the view over.
+                            if let Some(ref mut editor) = self.editor {
+                                editor.attach_client_view(
+                                    client.id,
+                                    client.label(),
+                                    client.term_size.cols,
+                                    client.term_size.rows,
+                                );
                             }
                             // Note: full redraw is handled via client.needs_full_render flag
 
@@ -352,6 +367,9 @@ impl EditorServer {
                     if idx < self.clients.len() {
                         tracing::info!("Client {} requested detach", self.clients[idx].id);
                         let client = self.clients.remove(idx);
+                        if let Some(ref mut editor) = self.editor {
+                            editor.detach_client_view(client.id);
+                        }
                         let teardown = terminal_teardown_sequences();
                         // Best-effort: client may already be disconnected
                         #[allow(clippy::let_underscore_must_use)]
@@ -407,17 +425,15 @@ impl EditorServer {
                 continue;
             }
 
-            // Handle resize
             if resize_occurred {
-                self.update_terminal_size()?;
                 needs_render = true;
             }
 
             // Process input events
             if !input_events.is_empty() {
                 self.last_client_activity = Instant::now();
-                for event in input_events {
-                    if self.handle_event(event)? {
+                for (client_id, event) in input_events {
+                    if self.handle_event(client_id, event)? {
                         needs_render = true;
                     }
                 }
@@ -432,6 +448,24 @@ impl EditorServer {
                     needs_render = true;
                 }
 
+                // Forward subscribed events to remote-control clients
+                for (event, params) in editor.take_remote_notifications() {
+                    let msg = serde_json::to_string(&ServerControl::Notification {
+                        event: event.clone(),
+                        params,
+                    })
+                    .unwrap_or_default();
+                    for client in self
+                        .clients
+                        .iter()
+                        .filter(|c| c.subscriptions.contains(&event))
+                    {
+                        // Best-effort: client may already be disconnected
+                        #[allow(clippy::let_underscore_must_use)]
+                        let _ = client.conn.write_control(&msg);
+                    }
+                }
+
                 // Process completed --wait operations
                 for wait_id in editor.take_completed_waits() {
                     if let Some(client_id) = self.waiting_clients.remove(&wait_id) {
@@ -524,11 +558,7 @@ impl EditorServer {
     /// Build a fresh `Editor` instance using the current configuration
     /// and stored authority.  Shared between first-boot initialization
     /// and post-restart rebuild.
-    fn build_editor_instance(&self) -> io::Result<(Editor, Terminal<CaptureBackend>)> {
-        let backend = CaptureBackend::new(self.term_size.cols, self.term_size.rows);
-        let terminal = Terminal::new(backend)
-            .map_err(|e| io::Error::other(format!("Failed to create terminal: {}", e)))?;
-
+    fn build_editor_instance(&self) -> io::Result<Editor> {
         // The Editor constructor still takes a filesystem; the real
         // authority is installed via `set_boot_authority` right after
         // construction so plugins and init.ts load against the correct
@@ -568,7 +598,7 @@ impl EditorServer {
         });
         editor.set_session_name(Some(session_display_name));
 
-        Ok((editor, terminal))
+        Ok(editor)
     }
 
     /// Initialize the editor on first client connection.
@@ -578,7 +608,7 @@ impl EditorServer {
     /// session.  Subsequent rebuilds (on authority/working-dir change)
     /// go through [`rebuild_editor`].
     pub fn initialize_editor(&mut self) -> io::Result<()> {
-        let (mut editor, terminal) = self.build_editor_instance()?;
+        let mut editor = self.build_editor_instance()?;
 
         // Restore workspace and recovery data (mirrors the standalone startup
         // path in handle_first_run_setup in main.rs).
@@ -615,7 +645,6 @@ impl EditorServer {
             tracing::warn!("Failed to start recovery session: {}", e);
         }
 
-        self.terminal = Some(terminal);
         self.editor = Some(editor);
 
         tracing::info!(
@@ -660,10 +689,9 @@ impl EditorServer {
             }
         }
 
-        // Drop old editor + terminal.  Drop impls shut down PTYs, LSP
-        // servers, and plugin threads.
+        // Drop old editor.  Drop impls shut down PTYs, LSP servers, and
+        // plugin threads.
         self.editor = None;
-        self.terminal = None;
 
         // Apply the pending changes before building the next editor.
         if let Some(dir) = new_working_dir {
@@ -678,7 +706,7 @@ impl EditorServer {
             self.current_authority = auth;
         }
 
-        let (mut editor, terminal) = self.build_editor_instance()?;
+        let mut editor = self.build_editor_instance()?;
 
         // Bring buffers back under the new backend.  `try_restore_workspace`
         // reads the workspace file we wrote above and re-opens the
@@ -693,14 +721,23 @@ impl EditorServer {
             tracing::warn!("Rebuild: failed to start recovery session: {}", e);
         }
 
-        self.terminal = Some(terminal);
-        self.editor = Some(editor);
-
         // Force every attached client to repaint from scratch — the
-        // previous frame described the old editor's screen.
+        // previous frame described the old editor's screen.  Client views
+        // start over from the restored layout.
         for client in &mut self.clients {
             client.needs_full_render = true;
+            if client.wait_id.is_some() || client.remote {
+                continue;
+            }
+            editor.attach_client_view(
+                client.id,
+                client.label(),
+                client.term_size.cols,
+                client.term_size.rows,
+            );
         }
+        self.editor = Some(editor);
+        self.sync_subscriptions();
 
         tracing::info!(
             "Rebuild: complete, {} clients kept attached",
@@ -793,21 +830,30 @@ impl EditorServer {
         // Create background writer for non-blocking render output
         let data_writer = ClientDataWriter::new(conn.data.clone(), client_id);
 
+        let backend = CaptureBackend::new(hello.term_size.cols, hello.term_size.rows);
+        let terminal = Terminal::new(backend)
+            .map_err(|e| io::Error::other(format!("Failed to create terminal: {}", e)))?;
+
         Ok(ConnectedClient {
             conn,
             data_writer,
+            terminal,
             term_size: hello.term_size,
             env: hello.env,
             id: client_id,
             input_parser: InputParser::new(),
             needs_full_render: true,
             wait_id: None,
+            remote: false,
+            subscriptions: HashSet::new(),
         })
     }
 
     /// Process messages from connected clients
-    /// Returns (input_events, resize_occurred, index of client that provided input)
-    fn process_clients(&mut self) -> io::Result<(Vec<Event>, bool, Option<usize>)> {
+    /// Returns (input events with the id of the client that sent them,
+    /// resize_occurred, index of client that provided input)
+    #[allow(clippy::type_complexity)]
+    fn process_clients(&mut self) -> io::Result<(Vec<(u64, Event)>, bool, Option<usize>)> {
         let mut disconnected = Vec::new();
         let mut input_source_client: Option<usize> = None;
         let mut input_events = Vec::new();
@@ -822,8 +868,9 @@ impl EditorServer {
             match client.conn.read_data(&mut buf) {
                 Ok(0) => {
                     tracing::debug!("[server] Client {} data stream closed (EOF)", client.id);
-                    // Don't disconnect waiting clients on data EOF - they're not sending data
-                    if client.wait_id.is_none() {
+                    // Don't disconnect waiting or remote-control clients on
+                    // data EOF - they're not sending data
+                    if client.wait_id.is_none() && !client.remote {
                         disconnected.push(idx);
                     }
                     data_eof = true;
@@ -844,7 +891,7 @@ impl EditorServer {
                     if !events.is_empty() {
                         input_source_client = Some(idx);
                     }
-                    input_events.extend(events);
+                    input_events.extend(events.into_iter().map(|event| (client.id, event)));
                 }
                 Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
                     // No data available
@@ -930,8 +977,9 @@ impl EditorServer {
                 continue;
             }
 
-            // Always process OpenFiles - it's a one-shot command from clients that disconnect immediately
-            if let ClientControl::OpenFiles { .. } = msg {
+            // Always process OpenFiles and requests - they're one-shot commands
+            // from clients that may disconnect immediately
+            if let ClientControl::OpenFiles { .. } | ClientControl::Request { .. } = msg {
                 // Fall through to process it
             } else if disconnected.contains(&idx) {
                 // Skip other messages from disconnected clients
@@ -945,11 +993,11 @@ impl EditorServer {
                 ClientControl::Resize { cols, rows } => {
                     if let Some(client) = self.clients.get_mut(idx) {
                         client.term_size = TermSize::new(cols, rows);
-                        // Update server size to match first client
-                        if idx == 0 {
-                            self.term_size = TermSize::new(cols, rows);
-                            resize_occurred = true;
+                        client.terminal.backend_mut().resize(cols, rows);
+                        if let Some(ref mut editor) = self.editor {
+                            editor.set_client_view_size(client.id, cols, rows);
                         }
+                        resize_occurred = true;
                     }
                 }
                 ClientControl::Ping => {
@@ -1005,12 +1053,25 @@ impl EditorServer {
                             if let Some(client) = self.clients.get_mut(idx) {
                                 self.waiting_clients.insert(wait_id, client.id);
                                 client.wait_id = Some(wait_id);
+                                // Waiting clients show nothing, so they
+                                // have no view or caret of their own.
+                                editor.detach_client_view(client.id);
                             }
                         }
 
                         resize_occurred = true; // Force re-render
                     }
                 }
+                ClientControl::Request { id, method, params } => {
+                    let response = self.handle_request(idx, id, &method, &params);
+                    if let Some(client) = self.clients.get(idx) {
+                        let msg = serde_json::to_string(&response).unwrap_or_default();
+                        // Best-effort: client may already be disconnected
+                        #[allow(clippy::let_underscore_must_use)]
+                        let _ = client.conn.write_control(&msg);
+                    }
+                    resize_occurred = true; // Force re-render
+                }
                 ClientControl::Quit => unreachable!(), // Handled above
             }
         }
@@ -1028,8 +1089,13 @@ impl EditorServer {
         disconnected.dedup();
 
         // Remove disconnected clients
+        let mut subscriber_left = false;
         for idx in disconnected.into_iter().rev() {
             let client = self.clients.remove(idx);
+            subscriber_left |= !client.subscriptions.is_empty();
+            if let Some(ref mut editor) = self.editor {
+                editor.detach_client_view(client.id);
+            }
             // Clean up --wait tracking if this client was waiting
             if let Some(wait_id) = client.wait_id {
                 self.waiting_clients.remove(&wait_id);
@@ -1048,29 +1114,124 @@ impl EditorServer {
             }
         }
 
+        if subscriber_left {
+            self.sync_subscriptions();
+        }
+
         Ok((input_events, resize_occurred, input_source_client))
     }
 
-    /// Update terminal size after resize
-    fn update_terminal_size(&mut self) -> io::Result<()> {
-        if let Some(ref mut terminal) = self.terminal {
-            let backend = terminal.backend_mut();
-            backend.resize(self.term_size.cols, self.term_size.rows);
+    /// Answer a remote-control request from the client at `idx`.
+    ///
+    /// `subscribe` and `unsubscribe` change which events are forwarded to
+    /// the client; every other method runs on the editor, in the view of
+    /// the client that last typed.
+    fn handle_request(
+        &mut self,
+        idx: usize,
+        id: u64,
+        method: &str,
+        params: &serde_json::Value,
+    ) -> ServerControl {
+        if let Some(client) = self.clients.get_mut(idx) {
+            if !client.remote {
+                client.remote = true;
+                if let Some(ref mut editor) = self.editor {
+                    editor.detach_client_view(client.id);
+                }
+            }
+        }
+        let result = match method {
+            "subscribe" | "unsubscribe" => self.update_subscriptions(idx, method, params),
+            _ => match self.editor {
+                None => Err("editor is not running".to_string()),
+                Some(ref mut editor) => {
+                    let typist = self
+                        .last_input_client
+                        .and_then(|i| self.clients.get(i))
+                        .filter(|c| !c.remote && c.wait_id.is_none())
+                        .map(|c| c.id);
+                    if let Some(typist) = typist {
+                        editor.switch_client_view(typist);
+                    }
+                    editor.handle_remote_request(method, params)
+                }
+            },
+        };
+        match result {
+            Ok(result) => ServerControl::Response {
+                id,
+                result: Some(result),
+                error: None,
+            },
+            Err(error) => ServerControl::Response {
+                id,
+                result: None,
+                error: Some(error),
+            },
         }
+    }
 
-        if let Some(ref mut editor) = self.editor {
-            editor.resize(self.term_size.cols, self.term_size.rows);
+    /// Add or remove (`method`) the hooks named in `params.events` to the
+    /// subscriptions of the client at `idx`. Returns the client's
+    /// subscriptions afterwards.
+    fn update_subscriptions(
+        &mut self,
+        idx: usize,
+        method: &str,
+        params: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        let events: Vec<String> = params
+            .get("events")
+            .and_then(|e| serde_json::from_value(e.clone()).ok())
+            .ok_or_else(|| "missing string array parameter 'events'".to_string())?;
+        let client = self
+            .clients
+            .get_mut(idx)
+            .ok_or_else(|| "client disconnected".to_string())?;
+        if method == "subscribe" {
+            client.subscriptions.extend(events);
+        } else {
+            for event in &events {
+                client.subscriptions.remove(event);
+            }
         }
+        let mut subscribed: Vec<String> = client.subscriptions.iter().cloned().collect();
+        subscribed.sort();
+        self.sync_subscriptions();
+        Ok(serde_json::json!(subscribed))
+    }
 
-        Ok(())
+    /// Tell the editor which hooks any client is subscribed to
+    fn sync_subscriptions(&self) {
+        if let Some(ref editor) = self.editor {
+            let hooks = self
+                .clients
+                .iter()
+                .flat_map(|c| c.subscriptions.iter().cloned())
+                .collect();
+            editor.set_remote_subscriptions(hooks);
+        }
     }
 
-    /// Handle an input event
-    fn handle_event(&mut self, event: Event) -> io::Result<bool> {
+    /// Handle an input event from the client with id `client_id`
+    fn handle_event(&mut self, client_id: u64, event: Event) -> io::Result<bool> {
         let Some(ref mut editor) = self.editor else {
             return Ok(false);
         };
 
+        // Input acts on the sending client's own view.  Mouse hit-testing
+        // reads the layout of the last rendered frame, so lay out this
+        // client's frame first when the view changed hands.
+        if editor.switch_client_view(client_id) && matches!(event, Event::Mouse(_)) {
+            if let Some(client) = self.clients.iter_mut().find(|c| c.id == client_id) {
+                client
+                    .terminal
+                    .draw(|frame| editor.render(frame))
+                    .map_err(|e| io::Error::other(e.to_string()))?;
+            }
+        }
+
         match event {
             Event::Key(key_event) => {
                 if key_event.kind == KeyEventKind::Press {
@@ -1097,50 +1258,39 @@ impl EditorServer {
         }
     }
 
-    /// Render the editor and broadcast output to all clients
+    /// Render each client's view and send it to that client
     fn render_and_broadcast(&mut self) -> io::Result<()> {
         let Some(ref mut editor) = self.editor else {
             return Ok(());
         };
 
-        let Some(ref mut terminal) = self.terminal else {
-            return Ok(());
-        };
-
-        // Check if any client needs a full render (e.g., newly connected)
-        let any_needs_full = self.clients.iter().any(|c| c.needs_full_render);
-        if any_needs_full {
-            tracing::info!(
-                "Full render requested for {} client(s)",
-                self.clients.iter().filter(|c| c.needs_full_render).count()
-            );
-            // Force full redraw by invalidating terminal state
-            terminal.backend_mut().reset_style_state();
-            // Best-effort terminal clear for full redraw
-            #[allow(clippy::let_underscore_must_use)]
-            let _ = terminal.clear();
-        }
-
         // Take any pending escape sequences (e.g., cursor style changes)
         let pending_sequences = editor.take_pending_escape_sequences();
 
-        // Render to capture backend
-        terminal
-            .draw(|frame| editor.render(frame))
-            .map_err(|e| io::Error::other(e.to_string()))?;
-
-        // Get the captured output
-        let output = terminal.backend_mut().take_buffer();
-
-        if output.is_empty() && pending_sequences.is_empty() {
-            return Ok(());
-        }
-
-        // Broadcast to all clients via non-blocking writer threads (skip waiting clients)
+        // Send to clients via non-blocking writer threads (skip waiting and
+        // remote-control clients)
         for client in &mut self.clients {
-            if client.wait_id.is_some() {
+            if client.wait_id.is_some() || client.remote {
                 continue;
             }
+
+            let terminal = &mut client.terminal;
+            if client.needs_full_render {
+                tracing::info!("Full render requested for client {}", client.id);
+                // Force full redraw by invalidating terminal state
+                terminal.backend_mut().reset_style_state();
+                // Best-effort terminal clear for full redraw
+                #[allow(clippy::let_underscore_must_use)]
+                let _ = terminal.clear();
+            }
+
+            // Render this client's view to its capture backend
+            editor.switch_client_view(client.id);
+            terminal
+                .draw(|frame| editor.render(frame))
+                .map_err(|e| io::Error::other(e.to_string()))?;
+            let output = terminal.backend_mut().take_buffer();
+
             // Combine pending sequences and output into a single frame
             let frame = if !pending_sequences.is_empty() && !output.is_empty() {
                 let mut combined = Vec::with_capacity(pending_sequences.len() + output.len());
@@ -1150,7 +1300,7 @@ impl EditorServer {
             } else if !pending_sequences.is_empty() {
                 pending_sequences.clone()
             } else {
-                output.clone()
+                output
             };
 
             if !frame.is_empty() && !client.data_writer.try_write(&frame) {
@@ -1167,6 +1317,9 @@ impl EditorServer {
     fn disconnect_all_clients(&mut self, reason: &str) -> io::Result<()> {
         let teardown = terminal_teardown_sequences();
         for client in &mut self.clients {
+            if let Some(ref mut editor) = self.editor {
+                editor.detach_client_view(client.id);
+            }
             // Best-effort: client may already be disconnected
             #[allow(clippy::let_underscore_must_use)]
             let _ = client.data_writer.try_write(&teardown);
@@ -1184,6 +1337,17 @@ impl EditorServer {
 }
 
 impl ConnectedClient {
+    /// Label shown next to this client's carets in other clients' views
+    fn label(&self) -> String {
+        let user = self
+            .env
+            .get("USER")
+            .and_then(|v| v.as_deref())
+            .filter(|u| !u.is_empty())
+            .unwrap_or("client");
+        format!("{}#{}", user, self.id)
+    }
+
     /// Get the client's TERM environment variable
     #[allow(dead_code)]
     pub fn term(&self) -> Option<&str> {
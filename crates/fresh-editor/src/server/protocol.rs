@@ -33,7 +33,8 @@ pub struct ClientHello {
     /// Initial terminal size
     pub term_size: TermSize,
     /// Environment variables relevant for rendering
-    /// Keys: TERM, COLORTERM, LANG, LC_ALL
+    /// Keys: TERM, COLORTERM, LANG, LC_ALL, USER (labels the client's
+    /// carets in other clients' views)
     pub env: HashMap<String, Option<String>>,
 }
 
@@ -43,7 +44,7 @@ impl ClientHello {
         let mut env = HashMap::new();
 
         // Collect terminal-relevant environment variables
-        for key in &["TERM", "COLORTERM", "LANG", "LC_ALL"] {
+        for key in &["TERM", "COLORTERM", "LANG", "LC_ALL", "USER"] {
             env.insert(key.to_string(), std::env::var(key).ok());
         }
 
@@ -121,6 +122,14 @@ pub enum ClientControl {
         #[serde(default)]
         wait: bool,
     },
+    /// Remote-control call, answered by a `ServerControl::Response` with
+    /// the same `id`. See `app/remote_control.rs` for the methods.
+    Request {
+        id: u64,
+        method: String,
+        #[serde(default)]
+        params: serde_json::Value,
+    },
 }
 
 /// A file to open with optional line/column position, range, and hover message
@@ -173,6 +182,20 @@ pub enum ServerControl {
     /// keeps running so the editor state is preserved and picked up cleanly
     /// when the client resumes.
     SuspendClient,
+    /// Answer to a `ClientControl::Request`: `result` on success,
+    /// `error` otherwise
+    Response {
+        id: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        result: Option<serde_json::Value>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// An editor event (plugin hook) this client subscribed to
+    Notification {
+        event: String,
+        params: serde_json::Value,
+    },
 }
 
 /// Wrapper for control channel messages (used for JSON serialization)
@@ -301,6 +324,11 @@ mod tests {
                 }],
                 wait: false,
             },
+            ClientControl::Request {
+                id: 1,
+                method: "action".to_string(),
+                params: serde_json::json!({"name": "save"}),
+            },
         ];
 
         for variant in variants {
@@ -331,6 +359,15 @@ mod tests {
                 use_system_clipboard: true,
             },
             ServerControl::SuspendClient,
+            ServerControl::Response {
+                id: 1,
+                result: Some(serde_json::json!({"ok": true})),
+                error: None,
+            },
+            ServerControl::Notification {
+                event: "after_file_save".to_string(),
+                params: serde_json::json!({"buffer_id": 1}),
+            },
         ];
 
         for variant in variants {
@@ -338,4 +375,18 @@ mod tests {
             let _: ServerControl = serde_json::from_str(&json).unwrap();
         }
     }
+
+    #[test]
+    fn test_request_params_default_to_null() {
+        let msg: ClientControl =
+            serde_json::from_str(r#"{"type":"request","id":7,"method":"buffers"}"#).unwrap();
+        match msg {
+            ClientControl::Request { id, method, params } => {
+                assert_eq!(id, 7);
+                assert_eq!(method, "buffers");
+                assert!(params.is_null());
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
 }
@@ -1264,6 +1264,74 @@ mod integration_tests {
         teardown_editor_server_e2e(conn, shutdown_handle, server_handle, socket_paths, temp_dir);
     }
 
+    /// E2E test: a second connection drives the session with remote-control
+    /// requests, which act on the view of the client that typed last.
+    #[test]
+    fn test_remote_control_requests() {
+        let (conn, mut output, shutdown_handle, server_handle, socket_paths, temp_dir) =
+            setup_editor_server_e2e("remote-ctrl");
+
+        conn.write_data(b"abc").unwrap();
+        read_until_contains(&conn, &mut output, "abc");
+
+        let remote = ClientConnection::connect(&socket_paths).expect("Failed to connect");
+        let hello = ClientHello::new(TermSize::new(80, 24));
+        remote
+            .write_control(&serde_json::to_string(&ClientControl::Hello(hello)).unwrap())
+            .unwrap();
+        let response = remote.read_control().unwrap().unwrap();
+        assert!(matches!(
+            serde_json::from_str::<ServerControl>(&response).unwrap(),
+            ServerControl::Hello(_)
+        ));
+
+        let mut ctrl_buf = Vec::new();
+        let mut request = |id: u64, method: &str, params: serde_json::Value| {
+            let msg = ClientControl::Request {
+                id,
+                method: method.to_string(),
+                params,
+            };
+            remote
+                .write_control(&serde_json::to_string(&msg).unwrap())
+                .unwrap();
+            wait_for_control(
+                &remote,
+                &mut ctrl_buf,
+                Duration::from_secs(5),
+                |ctrl| match ctrl {
+                    ServerControl::Response {
+                        id: got,
+                        result,
+                        error,
+                    } if *got == id => Some((result.clone(), error.clone())),
+                    _ => None,
+                },
+            )
+            .expect("Timed out waiting for Response")
+        };
+
+        let (_, error) = request(1, "insert", serde_json::json!({"text": "XYZ"}));
+        assert_eq!(error, None);
+        read_until_contains(&conn, &mut output, "abcXYZ");
+
+        let (result, _) = request(2, "cursors", serde_json::Value::Null);
+        assert_eq!(result.unwrap()["cursors"][0]["column"], 7);
+
+        let (result, _) = request(
+            3,
+            "subscribe",
+            serde_json::json!({"events": ["after_file_save"]}),
+        );
+        assert_eq!(result.unwrap(), serde_json::json!(["after_file_save"]));
+
+        let (_, error) = request(4, "no_such_method", serde_json::Value::Null);
+        assert!(error.unwrap().contains("unknown method"));
+
+        drop(remote);
+        teardown_editor_server_e2e(conn, shutdown_handle, server_handle, socket_paths, temp_dir);
+    }
+
     /// Authority transitions in session mode must rebuild the editor in
     /// place (not shut the daemon down).  This test drives the rebuild
     /// path directly against an `EditorServer`, without running the
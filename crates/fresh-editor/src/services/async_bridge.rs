@@ -67,6 +67,42 @@ pub enum AsyncMessage {
         locations: Vec<Location>,
     },
 
+    /// LSP go-to implementation / type definition / declaration response
+    LspGotoLocations {
+        request_id: u64,
+        kind: crate::services::lsp::async_handler::GotoKind,
+        locations: Vec<Location>,
+    },
+
+    /// LSP call/type hierarchy response (prepared items or the neighbours
+    /// of an item)
+    LspHierarchy {
+        request_id: u64,
+        server_name: String,
+        entries: Vec<crate::primitives::hierarchy::HierarchyEntry>,
+    },
+
+    /// LSP selection range response: one innermost-first chain per
+    /// requested position
+    LspSelectionRanges {
+        request_id: u64,
+        ranges: Vec<Vec<lsp_types::Range>>,
+    },
+
+    /// LSP document symbol response; `None` if the request failed
+    LspDocumentSymbols {
+        request_id: u64,
+        symbols: Option<lsp_types::DocumentSymbolResponse>,
+    },
+
+    /// LSP workspace symbol response from one server; `None` if the
+    /// request failed or was cancelled
+    LspWorkspaceSymbols {
+        request_id: u64,
+        server_name: String,
+        symbols: Option<lsp_types::WorkspaceSymbolResponse>,
+    },
+
     /// LSP rename response
     LspRename {
         request_id: u64,
@@ -149,6 +185,20 @@ pub enum AsyncMessage {
         ranges: Vec<FoldingRange>,
     },
 
+    /// LSP code lens response (textDocument/codeLens)
+    LspCodeLenses {
+        request_id: u64,
+        uri: String,
+        server_name: String,
+        lenses: Vec<lsp_types::CodeLens>,
+    },
+
+    /// LSP codeLens/resolve response; `None` if the request failed
+    LspCodeLensResolved {
+        request_id: u64,
+        lens: Option<lsp_types::CodeLens>,
+    },
+
     /// LSP semantic tokens response (full, full/delta, or range)
     LspSemanticTokens {
         request_id: u64,
@@ -164,6 +214,9 @@ pub enum AsyncMessage {
     /// Client should re-pull diagnostics for all open documents
     LspDiagnosticRefresh { language: String },
 
+    /// LSP server requests code lens refresh (workspace/codeLens/refresh)
+    LspCodeLensRefresh { language: String },
+
     /// File changed externally (future: file watching)
     FileChanged { path: String },
 
@@ -269,6 +322,43 @@ pub enum AsyncMessage {
         result: Result<Value, String>,
     },
 
+    /// Debug adapter answered a request
+    DapResponse {
+        session_id: u64,
+        request_seq: i64,
+        command: String,
+        success: bool,
+        /// Error message when `success` is false
+        message: Option<String>,
+        body: Option<Value>,
+    },
+
+    /// Debug adapter sent an event (`stopped`, `output`, ...)
+    DapEvent {
+        session_id: u64,
+        event: String,
+        body: Option<Value>,
+    },
+
+    /// Debug adapter exited or could not be started
+    DapExited {
+        session_id: u64,
+        /// `None` when the adapter closed its output normally
+        error: Option<String>,
+    },
+
+    /// A task started by "Run Task" exited
+    TaskFinished {
+        run_id: u64,
+        exit_code: i32,
+        /// Problems its problem matcher found in stdout and stderr
+        problems: Vec<crate::services::problem_matcher::Problem>,
+        /// Its stdout followed by its stderr, each cut off at a limit
+        output: String,
+        /// Why the command could not be run at all
+        error: Option<String>,
+    },
+
     /// Plugin process completed with output
     PluginProcessOutput {
         /// Unique ID for this process (to match with callback)
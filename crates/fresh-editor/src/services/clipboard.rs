@@ -99,6 +99,8 @@ pub struct Clipboard {
     session_mode: bool,
     /// Clipboard data pending delivery to clients (session mode only)
     pending_clipboard: Option<PendingClipboard>,
+    /// Text of the last block (rectangular) copy, pasted back column-wise
+    block: Option<String>,
 }
 
 impl Clipboard {
@@ -111,6 +113,7 @@ impl Clipboard {
             use_system_clipboard: true,
             session_mode: false,
             pending_clipboard: None,
+            block: None,
         }
     }
 
@@ -146,6 +149,7 @@ impl Clipboard {
     /// Returns true if successful, false otherwise.
     pub fn copy_html(&mut self, html: &str, plain_text: &str) -> bool {
         self.internal = plain_text.to_string();
+        self.block = None;
 
         if !self.use_system_clipboard {
             return false;
@@ -189,6 +193,7 @@ impl Clipboard {
     /// Methods can be disabled via clipboard configuration.
     pub fn copy(&mut self, text: String) {
         self.internal = text.clone();
+        self.block = None;
 
         // In session mode, the server process has no terminal or display server.
         // Queue the text for delivery to clients via a control message instead.
@@ -204,6 +209,18 @@ impl Clipboard {
         copy_to_system_clipboard(&text, self.use_osc52, self.use_system_clipboard);
     }
 
+    /// Copy the text of a block selection. Pasting the same text back is
+    /// done column-wise (see [`Self::is_block`]).
+    pub fn copy_block(&mut self, text: String) {
+        self.copy(text.clone());
+        self.block = Some(text);
+    }
+
+    /// Whether `text` is the last block copy, still on the clipboard
+    pub fn is_block(&self, text: &str) -> bool {
+        self.block.as_deref() == Some(text)
+    }
+
     /// Get text from clipboard, preferring system clipboard
     ///
     /// Tries system clipboard first, falls back to internal clipboard.
@@ -251,6 +268,7 @@ impl Clipboard {
     /// Set the internal clipboard content without updating system clipboard
     pub fn set_internal(&mut self, text: String) {
         self.internal = text;
+        self.block = None;
     }
 
     /// Get text from internal clipboard only (ignores system clipboard)
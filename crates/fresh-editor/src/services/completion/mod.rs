@@ -15,6 +15,9 @@
 //!  │  │ (async)    │ │ (sync)   │ │ (sync)        │  │
 //!  │  └────────────┘ └──────────┘ └───────────────┘  │
 //!  │  ┌────────────────────────────────────────────┐  │
+//!  │  │  snippets (sync, user + package files)     │  │
+//!  │  └────────────────────────────────────────────┘  │
+//!  │  ┌────────────────────────────────────────────┐  │
 //!  │  │  TS plugin providers  (async via QuickJS)  │  │
 //!  │  └────────────────────────────────────────────┘  │
 //!  │                                                  │
@@ -50,6 +53,7 @@ pub mod buffer_words;
 pub mod dabbrev;
 pub mod provider;
 pub mod service;
+pub mod snippets;
 
 // Re-export the main types that the Editor needs.
 pub use provider::{
@@ -57,3 +61,4 @@ pub use provider::{
     OtherBufferSlice, ProviderResult,
 };
 pub use service::CompletionService;
+pub use snippets::{SnippetLibrary, SnippetProvider};
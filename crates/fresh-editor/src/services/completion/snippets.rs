@@ -0,0 +1,464 @@
+//! User-defined snippets in VS Code's snippet file format.
+//!
+//! Snippets are loaded once at startup from:
+//! - `<config_dir>/snippets/<language>.json` — snippets for one language
+//! - `<config_dir>/snippets/*.code-snippets` — snippets for every language,
+//!   or for the languages listed in each snippet's `scope`
+//! - snippet files contributed by installed packages (`fresh.snippets` in
+//!   the package manifest, see `services/packages.rs`)
+//!
+//! A file maps snippet names to definitions; `prefix` and `body` may be a
+//! string or a list of strings (body lines are joined with newlines).
+//! Comments and trailing commas are accepted, as in VS Code:
+//!
+//! ```json
+//! {
+//!   // Print to stdout
+//!   "Print line": {
+//!     "prefix": ["pl", "println"],
+//!     "body": ["println!(\"$1\");", "$0"],
+//!     "description": "println! macro",
+//!   }
+//! }
+//! ```
+//!
+//! The [`SnippetProvider`] offers snippets whose prefix matches the word
+//! being typed; the "Insert Snippet" command lists every snippet for the
+//! buffer's language.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use super::provider::{
+    case_mismatch_penalty, smart_case_matches, CompletionCandidate, CompletionContext,
+    CompletionProvider, CompletionSourceId, ProviderResult,
+};
+
+/// Maximum number of candidates returned.
+const MAX_CANDIDATES: usize = 20;
+
+/// Base score of a snippet candidate; ranks snippets above buffer words.
+const SNIPPET_SCORE: i64 = 1_000_000;
+
+/// A snippet loaded from a snippet file.
+#[derive(Debug, Clone)]
+pub struct Snippet {
+    /// Key of the snippet in its file
+    pub name: String,
+    /// Words that offer the snippet as a completion
+    pub prefixes: Vec<String>,
+    /// Snippet text, in LSP snippet syntax
+    pub body: String,
+    pub description: Option<String>,
+    /// Languages the snippet applies to; `None` for all
+    languages: Option<Vec<String>>,
+}
+
+impl Snippet {
+    /// Whether the snippet is offered in buffers of `language`.
+    pub fn applies_to(&self, language: &str) -> bool {
+        self.languages
+            .as_ref()
+            .is_none_or(|languages| languages.iter().any(|l| l == language))
+    }
+}
+
+/// A string or a list of strings, as VS Code accepts for `prefix` and `body`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Default for OneOrMany {
+    fn default() -> Self {
+        Self::Many(Vec::new())
+    }
+}
+
+impl OneOrMany {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            Self::One(s) => vec![s],
+            Self::Many(v) => v,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SnippetDefinition {
+    #[serde(default)]
+    prefix: OneOrMany,
+    body: OneOrMany,
+    #[serde(default)]
+    description: Option<String>,
+    /// Comma-separated language ids (only meaningful in `.code-snippets`)
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+/// All loaded snippets.
+#[derive(Debug, Default)]
+pub struct SnippetLibrary {
+    snippets: Vec<Snippet>,
+}
+
+impl SnippetLibrary {
+    /// Load the user's snippet files from `config_dir`, then the files
+    /// contributed by packages.
+    pub fn load(config_dir: &Path, package_files: &[(Option<String>, PathBuf)]) -> Self {
+        let mut library = Self::default();
+
+        let snippets_dir = config_dir.join("snippets");
+        if let Ok(entries) = std::fs::read_dir(&snippets_dir) {
+            let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+            paths.sort();
+            for path in paths {
+                let language = match path.extension().and_then(|e| e.to_str()) {
+                    Some("json") => path.file_stem().and_then(|s| s.to_str()),
+                    Some("code-snippets") => None,
+                    _ => continue,
+                };
+                let language = language.map(str::to_owned);
+                library.load_file(&path, language.as_deref());
+            }
+        }
+
+        for (language, path) in package_files {
+            let language = language.as_deref().or_else(|| {
+                // A package's `<language>.json` is scoped like the user's
+                (path.extension().and_then(|e| e.to_str()) == Some("json"))
+                    .then(|| path.file_stem().and_then(|s| s.to_str()))
+                    .flatten()
+            });
+            library.load_file(path, language);
+        }
+
+        tracing::info!("Loaded {} snippets", library.snippets.len());
+        library
+    }
+
+    /// Load one snippet file, logging (and skipping) it if it is invalid.
+    fn load_file(&mut self, path: &Path, language: Option<&str>) {
+        let content = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::warn!("Failed to read snippet file {:?}: {}", path, e);
+                return;
+            }
+        };
+        if let Err(e) = self.add_from_str(&content, language) {
+            tracing::warn!("Failed to parse snippet file {:?}: {}", path, e);
+        }
+    }
+
+    /// Add the snippets of a snippet file's content. `language` scopes
+    /// them all to one language; otherwise each snippet's `scope` applies.
+    pub fn add_from_str(
+        &mut self,
+        content: &str,
+        language: Option<&str>,
+    ) -> Result<(), serde_json::Error> {
+        let definitions: BTreeMap<String, SnippetDefinition> =
+            serde_json::from_str(&strip_jsonc(content))?;
+        for (name, definition) in definitions {
+            let languages = match (language, &definition.scope) {
+                (Some(language), _) => Some(vec![language.to_string()]),
+                (None, Some(scope)) => Some(
+                    scope
+                        .split(',')
+                        .map(|l| l.trim().to_string())
+                        .filter(|l| !l.is_empty())
+                        .collect(),
+                ),
+                (None, None) => None,
+            };
+            self.snippets.push(Snippet {
+                name,
+                prefixes: definition.prefix.into_vec(),
+                body: definition.body.into_vec().join("\n"),
+                description: definition.description,
+                languages,
+            });
+        }
+        Ok(())
+    }
+
+    /// Snippets offered in buffers of `language`.
+    pub fn for_language<'a>(&'a self, language: &'a str) -> impl Iterator<Item = &'a Snippet> {
+        self.snippets.iter().filter(move |s| s.applies_to(language))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snippets.is_empty()
+    }
+}
+
+/// Remove `//` and `/* */` comments and trailing commas from JSON text.
+fn strip_jsonc(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                out.push(' ');
+            }
+            _ => out.push(c),
+        }
+    }
+
+    // Drop commas directly followed by a closing bracket
+    let mut result = String::with_capacity(out.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in out.char_indices() {
+        if in_string {
+            in_string = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' {
+            let next = out[i + 1..].trim_start().chars().next();
+            if matches!(next, Some('}' | ']')) {
+                continue;
+            }
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Completion provider offering snippets by prefix.
+pub struct SnippetProvider {
+    library: Arc<SnippetLibrary>,
+}
+
+impl SnippetProvider {
+    pub fn new(library: Arc<SnippetLibrary>) -> Self {
+        Self { library }
+    }
+}
+
+impl CompletionProvider for SnippetProvider {
+    fn id(&self) -> CompletionSourceId {
+        CompletionSourceId("snippets".into())
+    }
+
+    fn display_name(&self) -> &str {
+        "Snippets"
+    }
+
+    fn is_enabled(&self, ctx: &CompletionContext) -> bool {
+        !ctx.prefix.is_empty() && ctx.language_id.is_some() && !self.library.is_empty()
+    }
+
+    fn provide(&self, ctx: &CompletionContext, _buffer_window: &[u8]) -> ProviderResult {
+        let Some(language) = ctx.language_id.as_deref() else {
+            return ProviderResult::Ready(Vec::new());
+        };
+        let candidates = self
+            .library
+            .for_language(language)
+            .filter_map(|snippet| {
+                let prefix = snippet
+                    .prefixes
+                    .iter()
+                    .find(|p| smart_case_matches(p, &ctx.prefix, ctx.prefix_has_uppercase))?;
+                let mut score = SNIPPET_SCORE
+                    + case_mismatch_penalty(prefix, &ctx.prefix, ctx.prefix_has_uppercase);
+                // Shorter prefixes are closer to what was typed
+                score -= prefix.len().saturating_sub(ctx.prefix.len()) as i64;
+                Some(CompletionCandidate {
+                    label: prefix.clone(),
+                    insert_text: Some(snippet.body.clone()),
+                    detail: Some(
+                        snippet
+                            .description
+                            .clone()
+                            .unwrap_or_else(|| snippet.name.clone()),
+                    ),
+                    icon: Some("s".to_string()),
+                    score,
+                    source: None,
+                    is_snippet: true,
+                    provider_data: None,
+                })
+            })
+            .take(MAX_CANDIDATES)
+            .collect();
+        ProviderResult::Ready(candidates)
+    }
+
+    fn priority(&self) -> u32 {
+        10
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(prefix: &str, language: &str) -> CompletionContext {
+        CompletionContext {
+            prefix: prefix.into(),
+            cursor_byte: prefix.len(),
+            word_start_byte: 0,
+            buffer_len: prefix.len(),
+            is_large_file: false,
+            scan_range: 0..prefix.len(),
+            viewport_top_byte: 0,
+            viewport_bottom_byte: prefix.len(),
+            language_id: Some(language.into()),
+            word_chars_extra: String::new(),
+            prefix_has_uppercase: prefix.chars().any(|c| c.is_uppercase()),
+            other_buffers: Vec::new(),
+        }
+    }
+
+    fn labels(provider: &SnippetProvider, ctx: &CompletionContext) -> Vec<String> {
+        match provider.provide(ctx, b"") {
+            ProviderResult::Ready(candidates) => {
+                let mut labels: Vec<String> = candidates.into_iter().map(|c| c.label).collect();
+                labels.sort();
+                labels
+            }
+            ProviderResult::Pending(_) => panic!("snippets are synchronous"),
+        }
+    }
+
+    #[test]
+    fn parses_vscode_format_with_comments() {
+        let mut library = SnippetLibrary::default();
+        library
+            .add_from_str(
+                r#"{
+                    // A comment
+                    "Print line": {
+                        "prefix": ["pl", "println"],
+                        "body": ["println!(\"$1 // not a comment\");", "$0"],
+                        /* block */ "description": "println! macro",
+                    },
+                }"#,
+                Some("rust"),
+            )
+            .unwrap();
+
+        let snippet = library.for_language("rust").next().unwrap();
+        assert_eq!(snippet.name, "Print line");
+        assert_eq!(snippet.prefixes, vec!["pl", "println"]);
+        assert_eq!(snippet.body, "println!(\"$1 // not a comment\");\n$0");
+        assert_eq!(snippet.description.as_deref(), Some("println! macro"));
+        assert_eq!(library.for_language("python").count(), 0);
+    }
+
+    #[test]
+    fn code_snippets_use_scope() {
+        let mut library = SnippetLibrary::default();
+        library
+            .add_from_str(
+                r#"{
+                    "Header": { "prefix": "hdr", "body": "// header" },
+                    "Log": { "prefix": "log", "body": "console.log($1)", "scope": "javascript, typescript" }
+                }"#,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(library.for_language("typescript").count(), 2);
+        assert_eq!(library.for_language("rust").count(), 1);
+    }
+
+    #[test]
+    fn provider_matches_prefix_for_language() {
+        let mut library = SnippetLibrary::default();
+        library
+            .add_from_str(
+                r##"{
+                    "Function": { "prefix": "fn", "body": "fn ${1:name}() {\n\t$0\n}" },
+                    "Format": { "prefix": "format", "body": "format!(\"$1\")" },
+                    "Test": { "prefix": "test", "body": "#[test]" }
+                }"##,
+                Some("rust"),
+            )
+            .unwrap();
+        let provider = SnippetProvider::new(Arc::new(library));
+
+        assert_eq!(labels(&provider, &ctx("f", "rust")), vec!["fn", "format"]);
+        assert!(labels(&provider, &ctx("f", "python")).is_empty());
+
+        let ProviderResult::Ready(candidates) = provider.provide(&ctx("te", "rust"), b"") else {
+            panic!("snippets are synchronous");
+        };
+        assert!(candidates[0].is_snippet);
+        assert_eq!(candidates[0].insert_text.as_deref(), Some("#[test]"));
+        assert_eq!(candidates[0].detail.as_deref(), Some("Test"));
+    }
+
+    #[test]
+    fn load_reads_config_and_package_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let snippets_dir = temp_dir.path().join("snippets");
+        std::fs::create_dir_all(&snippets_dir).unwrap();
+        std::fs::write(
+            snippets_dir.join("rust.json"),
+            r#"{ "Main": { "prefix": "main", "body": "fn main() {}" } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            snippets_dir.join("team.code-snippets"),
+            r#"{ "Todo": { "prefix": "todo", "body": "TODO($1)" } }"#,
+        )
+        .unwrap();
+        let package_file = temp_dir.path().join("python.json");
+        std::fs::write(
+            &package_file,
+            r#"{ "Main": { "prefix": "main", "body": "if __name__ == \"__main__\":" } }"#,
+        )
+        .unwrap();
+
+        let library = SnippetLibrary::load(temp_dir.path(), &[(None, package_file)]);
+        let names = |language| {
+            library
+                .for_language(language)
+                .map(|s| s.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("rust"), vec!["Main", "Todo"]);
+        assert_eq!(names("python"), vec!["Todo", "Main"]);
+        assert_eq!(names("go"), vec!["Todo"]);
+    }
+}
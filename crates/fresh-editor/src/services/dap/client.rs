@@ -0,0 +1,286 @@
+//! Debug adapter process and I/O.
+//!
+//! [`DapHandle::spawn`] starts the adapter in a Tokio task and returns at
+//! once; requests are queued on a channel and written by that task, and a
+//! reader task forwards everything the adapter says to the main loop via
+//! the `AsyncBridge`. Sequence numbers are handed out synchronously so the
+//! caller can record what each request was for before the answer arrives.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+use serde_json::Value;
+use tokio::io::BufReader;
+use tokio::sync::mpsc;
+
+use crate::config::DebugAdapterConfig;
+use crate::services::async_bridge::{AsyncBridge, AsyncMessage};
+use crate::services::dap::protocol::{Incoming, Request, ReverseResponse};
+use crate::services::remote::LongRunningSpawner;
+use crate::services::stdio_framing;
+
+/// Work for the writer task.
+#[derive(Debug)]
+enum DapCommand {
+    /// Serialized message to write to the adapter's stdin
+    Send(String),
+    /// Stop the adapter process
+    Shutdown,
+}
+
+/// Sync handle to a running debug adapter.
+pub struct DapHandle {
+    session_id: u64,
+    adapter_name: String,
+    seq: Arc<AtomicI64>,
+    command_tx: mpsc::UnboundedSender<DapCommand>,
+}
+
+// Sends to the async bridge are best-effort: a closed receiver means the
+// editor is shutting down.
+#[allow(clippy::let_underscore_must_use)]
+impl DapHandle {
+    /// Spawn the adapter described by `config` under the active authority.
+    ///
+    /// Spawn failures are not returned here but reported as
+    /// `AsyncMessage::DapExited` with an error, like every later failure,
+    /// so the editor has a single place that tears a session down.
+    pub fn spawn(
+        runtime: &tokio::runtime::Handle,
+        session_id: u64,
+        adapter_name: &str,
+        config: &DebugAdapterConfig,
+        async_bridge: &AsyncBridge,
+        long_running_spawner: Arc<dyn LongRunningSpawner>,
+    ) -> Self {
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
+        let seq = Arc::new(AtomicI64::new(1));
+        let async_tx = async_bridge.sender();
+        let command = config.command.clone();
+        let args = config.args.clone();
+        let mut env_pairs: Vec<(String, String)> = config
+            .env
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        env_pairs.sort();
+        let log_path = crate::services::log_dirs::dap_log_path(adapter_name);
+        let reader_seq = seq.clone();
+        let reader_tx = command_tx.clone();
+
+        runtime.spawn(async move {
+            if !long_running_spawner.command_exists(&command).await {
+                let _ = async_tx.send(AsyncMessage::DapExited {
+                    session_id,
+                    error: Some(format!(
+                        "Debug adapter executable '{}' not found in the active authority's PATH",
+                        command
+                    )),
+                });
+                return;
+            }
+
+            let mut child = match long_running_spawner
+                .spawn_stdio(&command, &args, env_pairs, None, None)
+                .await
+            {
+                Ok(child) => child,
+                Err(e) => {
+                    let _ = async_tx.send(AsyncMessage::DapExited {
+                        session_id,
+                        error: Some(format!(
+                            "Failed to spawn debug adapter '{}': {}",
+                            command, e
+                        )),
+                    });
+                    return;
+                }
+            };
+
+            let (Some(mut stdin), Some(stdout)) = (child.take_stdin(), child.take_stdout()) else {
+                let _ = async_tx.send(AsyncMessage::DapExited {
+                    session_id,
+                    error: Some("Failed to get debug adapter stdio".to_string()),
+                });
+                return;
+            };
+
+            if let Some(stderr) = child.take_stderr() {
+                tokio::spawn(copy_stderr_to_log(stderr, log_path));
+            }
+
+            tokio::spawn(read_messages(
+                BufReader::new(stdout),
+                session_id,
+                async_tx,
+                reader_seq,
+                reader_tx,
+            ));
+
+            let mut command_rx = command_rx;
+            while let Some(cmd) = command_rx.recv().await {
+                match cmd {
+                    DapCommand::Send(json) => {
+                        tracing::trace!("DAP -> {}", json);
+                        if let Err(e) = stdio_framing::write_message(&mut stdin, &json).await {
+                            tracing::warn!("Writing to debug adapter failed: {}", e);
+                            break;
+                        }
+                    }
+                    DapCommand::Shutdown => break,
+                }
+            }
+
+            // Give the adapter a moment to exit on its own after
+            // `disconnect`, then make sure it is gone.
+            drop(stdin);
+            if tokio::time::timeout(std::time::Duration::from_millis(500), child.wait())
+                .await
+                .is_err()
+            {
+                let _ = child.kill().await;
+            }
+        });
+
+        Self {
+            session_id,
+            adapter_name: adapter_name.to_string(),
+            seq,
+            command_tx,
+        }
+    }
+
+    pub fn session_id(&self) -> u64 {
+        self.session_id
+    }
+
+    pub fn adapter_name(&self) -> &str {
+        &self.adapter_name
+    }
+
+    /// Queue a request and return its sequence number.
+    pub fn request(&self, command: &str, arguments: Option<&Value>) -> Result<i64, String> {
+        let seq = self.seq.fetch_add(1, Ordering::Relaxed);
+        let json = serde_json::to_string(&Request::new(seq, command, arguments))
+            .map_err(|e| format!("Failed to serialize DAP request: {}", e))?;
+        self.command_tx
+            .send(DapCommand::Send(json))
+            .map_err(|_| "Debug adapter is not running".to_string())?;
+        Ok(seq)
+    }
+
+    /// Stop the adapter. Pending requests are dropped.
+    pub fn shutdown(&self) {
+        let _ = self.command_tx.send(DapCommand::Shutdown);
+    }
+}
+
+impl Drop for DapHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Forward every message from the adapter to the main loop until EOF.
+#[allow(clippy::let_underscore_must_use)]
+async fn read_messages(
+    mut stdout: BufReader<tokio::process::ChildStdout>,
+    session_id: u64,
+    async_tx: std::sync::mpsc::Sender<AsyncMessage>,
+    seq: Arc<AtomicI64>,
+    command_tx: mpsc::UnboundedSender<DapCommand>,
+) {
+    let error = loop {
+        let json = match stdio_framing::read_message(&mut stdout, "Debug adapter").await {
+            Ok(json) => json,
+            Err(e) => break e,
+        };
+        tracing::trace!("DAP <- {}", json);
+
+        let message: Incoming = match serde_json::from_str(&json) {
+            Ok(message) => message,
+            Err(e) => {
+                tracing::warn!("Ignoring malformed DAP message: {} ({})", e, json);
+                continue;
+            }
+        };
+
+        match message {
+            Incoming::Response {
+                request_seq,
+                success,
+                command,
+                message,
+                body,
+            } => {
+                let _ = async_tx.send(AsyncMessage::DapResponse {
+                    session_id,
+                    request_seq,
+                    command,
+                    success,
+                    message,
+                    body,
+                });
+            }
+            Incoming::Event { event, body } => {
+                let _ = async_tx.send(AsyncMessage::DapEvent {
+                    session_id,
+                    event,
+                    body,
+                });
+            }
+            Incoming::Request {
+                seq: request_seq,
+                command,
+            } => {
+                // Reverse requests (`runInTerminal`, `startDebugging`) are
+                // optional; declining lets the adapter fall back.
+                tracing::debug!("Declining DAP reverse request '{}'", command);
+                let response = ReverseResponse {
+                    seq: seq.fetch_add(1, Ordering::Relaxed),
+                    kind: "response",
+                    request_seq,
+                    success: false,
+                    command: &command,
+                    message: Some("not supported"),
+                };
+                if let Ok(json) = serde_json::to_string(&response) {
+                    let _ = command_tx.send(DapCommand::Send(json));
+                }
+            }
+        }
+    };
+
+    tracing::info!("Debug adapter session {} ended: {}", session_id, error);
+    let error = if error.ends_with("(EOF)") {
+        None
+    } else {
+        Some(error)
+    };
+    let _ = async_tx.send(AsyncMessage::DapExited { session_id, error });
+}
+
+async fn copy_stderr_to_log(stderr: tokio::process::ChildStderr, log_path: std::path::PathBuf) {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
+
+    let mut file = match tokio::fs::File::create(&log_path).await {
+        Ok(f) => f,
+        Err(e) => {
+            tracing::warn!("Could not create DAP stderr log {:?}: {}", log_path, e);
+            return;
+        }
+    };
+    let mut reader = BufReader::new(stderr);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match reader.read_line(&mut line).await {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {
+                if file.write_all(line.as_bytes()).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
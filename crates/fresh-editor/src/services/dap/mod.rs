@@ -0,0 +1,25 @@
+//! DAP (Debug Adapter Protocol) client
+//!
+//! Debug adapters speak the same `Content-Length`-framed JSON over stdio as
+//! language servers (see [`crate::services::stdio_framing`]), so the client
+//! here mirrors the LSP one in shape but is much smaller: the protocol has
+//! no document synchronisation and every interesting exchange is a plain
+//! request/response or an event.
+//!
+//! # Architecture
+//!
+//! - [`client::DapHandle`]: sync handle owned by the editor. Spawning goes
+//!   through the authority's `LongRunningSpawner`, so an adapter runs on
+//!   the SSH host or inside the container just like a language server.
+//! - A reader task forwards every response and event to the main loop as
+//!   `AsyncMessage::DapResponse` / `AsyncMessage::DapEvent`; the editor
+//!   matches responses to its own requests by `request_seq`.
+//! - [`protocol`]: the subset of DAP message and body types the editor
+//!   looks at. Anything else is passed through as raw JSON (plugins get
+//!   the raw bodies via `editor.sendDebugRequest`).
+//!
+//! Session state (breakpoints, the stopped location, the launch handshake)
+//! lives on the editor side in `app/debug.rs`.
+
+pub mod client;
+pub mod protocol;
@@ -0,0 +1,244 @@
+//! Debug Adapter Protocol message types.
+//!
+//! Only the parts of the specification the editor itself interprets are
+//! typed here. Field names follow the specification (camelCase on the
+//! wire); unknown fields are ignored so newer adapters keep working.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A request sent from the editor to the adapter.
+#[derive(Debug, Clone, Serialize)]
+pub struct Request<'a> {
+    pub seq: i64,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub command: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<&'a Value>,
+}
+
+impl<'a> Request<'a> {
+    pub fn new(seq: i64, command: &'a str, arguments: Option<&'a Value>) -> Self {
+        Self {
+            seq,
+            kind: "request",
+            command,
+            arguments,
+        }
+    }
+}
+
+/// Answer to a request the adapter sent to the editor ("reverse request",
+/// e.g. `runInTerminal`).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReverseResponse<'a> {
+    pub seq: i64,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub request_seq: i64,
+    pub success: bool,
+    pub command: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<&'a str>,
+}
+
+/// Any message received from the adapter.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Incoming {
+    #[serde(rename_all = "camelCase")]
+    Response {
+        request_seq: i64,
+        success: bool,
+        command: String,
+        #[serde(default)]
+        message: Option<String>,
+        #[serde(default)]
+        body: Option<Value>,
+    },
+    Event {
+        event: String,
+        #[serde(default)]
+        body: Option<Value>,
+    },
+    Request {
+        seq: i64,
+        command: String,
+    },
+}
+
+/// The capabilities the editor acts on (from the `initialize` response).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    #[serde(default)]
+    pub supports_configuration_done_request: bool,
+    #[serde(default)]
+    pub supports_terminate_request: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Source {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// A breakpoint as sent in `setBreakpoints` (1-based line).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SourceBreakpoint {
+    pub line: usize,
+}
+
+/// A breakpoint as confirmed by the adapter.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Breakpoint {
+    #[serde(default)]
+    pub verified: bool,
+    #[serde(default)]
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetBreakpointsResponseBody {
+    #[serde(default)]
+    pub breakpoints: Vec<Breakpoint>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackFrame {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub source: Option<Source>,
+    /// 1-based line (the editor initializes adapters with `linesStartAt1`)
+    pub line: usize,
+    #[serde(default)]
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackTraceResponseBody {
+    #[serde(default)]
+    pub stack_frames: Vec<StackFrame>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoppedEventBody {
+    pub reason: String,
+    #[serde(default)]
+    pub thread_id: Option<i64>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinuedEventBody {
+    pub thread_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputEventBody {
+    #[serde(default)]
+    pub category: Option<String>,
+    pub output: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitedEventBody {
+    pub exit_code: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn request_serializes_with_type_tag() {
+        let args = json!({ "threadId": 1 });
+        let value = serde_json::to_value(Request::new(3, "next", Some(&args))).unwrap();
+        assert_eq!(
+            value,
+            json!({ "seq": 3, "type": "request", "command": "next", "arguments": { "threadId": 1 } })
+        );
+
+        let value = serde_json::to_value(Request::new(4, "threads", None)).unwrap();
+        assert!(value.get("arguments").is_none());
+    }
+
+    #[test]
+    fn incoming_messages_are_told_apart_by_type() {
+        let response: Incoming = serde_json::from_value(json!({
+            "seq": 5, "type": "response", "request_seq": 2, "success": true,
+            "command": "initialize", "body": { "supportsConfigurationDoneRequest": true }
+        }))
+        .unwrap();
+        match response {
+            Incoming::Response {
+                request_seq, body, ..
+            } => {
+                assert_eq!(request_seq, 2);
+                let caps: Capabilities = serde_json::from_value(body.unwrap()).unwrap();
+                assert!(caps.supports_configuration_done_request);
+                assert!(!caps.supports_terminate_request);
+            }
+            other => panic!("expected response, got {:?}", other),
+        }
+
+        let event: Incoming = serde_json::from_value(json!({
+            "seq": 6, "type": "event", "event": "stopped",
+            "body": { "reason": "breakpoint", "threadId": 1, "allThreadsStopped": true }
+        }))
+        .unwrap();
+        match event {
+            Incoming::Event { event, body } => {
+                assert_eq!(event, "stopped");
+                let stopped: StoppedEventBody = serde_json::from_value(body.unwrap()).unwrap();
+                assert_eq!(stopped.reason, "breakpoint");
+                assert_eq!(stopped.thread_id, Some(1));
+            }
+            other => panic!("expected event, got {:?}", other),
+        }
+
+        let request: Incoming = serde_json::from_value(json!({
+            "seq": 7, "type": "request", "command": "runInTerminal", "arguments": {}
+        }))
+        .unwrap();
+        assert!(matches!(request, Incoming::Request { seq: 7, .. }));
+    }
+
+    #[test]
+    fn stack_frames_without_source_parse() {
+        let body: StackTraceResponseBody = serde_json::from_value(json!({
+            "stackFrames": [
+                { "id": 1, "name": "main", "line": 4, "column": 1,
+                  "source": { "path": "/src/main.rs" } },
+                { "id": 2, "name": "<native>", "line": 0 }
+            ],
+            "totalFrames": 2
+        }))
+        .unwrap();
+        assert_eq!(body.stack_frames.len(), 2);
+        assert_eq!(
+            body.stack_frames[0]
+                .source
+                .as_ref()
+                .and_then(|s| s.path.as_deref()),
+            Some("/src/main.rs")
+        );
+        assert!(body.stack_frames[1].source.is_none());
+    }
+}
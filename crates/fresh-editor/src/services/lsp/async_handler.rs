@@ -11,11 +11,13 @@
 //! - LspHandle: Sync handle that can send commands to the task
 //! - Uses tokio channels for command/response communication
 
+use crate::primitives::hierarchy::{self, HierarchyDirection, HierarchyKind};
 use crate::services::async_bridge::{
     AsyncBridge, AsyncMessage, LspMessageType, LspProgressValue, LspSemanticTokensResponse,
     LspServerStatus,
 };
 use crate::services::process_limits::ProcessLimits;
+use crate::services::stdio_framing;
 use lsp_types::{
     notification::{
         DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
@@ -39,7 +41,7 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 use std::sync::{mpsc as std_mpsc, Arc, Mutex};
 use std::time::{Duration, Instant};
-use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::io::BufReader;
 use tokio::process::{ChildStdin, ChildStdout};
 use tokio::sync::{mpsc, oneshot};
 
@@ -262,14 +264,17 @@ impl LspClientState {
 fn create_client_capabilities() -> ClientCapabilities {
     use lsp_types::{
         CodeActionClientCapabilities, CodeActionKindLiteralSupport, CodeActionLiteralSupport,
+        CodeLensClientCapabilities, CodeLensWorkspaceClientCapabilities,
         CompletionClientCapabilities, DiagnosticClientCapabilities, DiagnosticTag,
-        DynamicRegistrationClientCapabilities, FoldingRangeCapability,
-        FoldingRangeClientCapabilities, FoldingRangeKind, FoldingRangeKindCapability,
-        GeneralClientCapabilities, GotoCapability, HoverClientCapabilities,
-        InlayHintClientCapabilities, MarkupKind, PublishDiagnosticsClientCapabilities,
-        RenameClientCapabilities, SignatureHelpClientCapabilities, TagSupport,
+        DocumentSymbolClientCapabilities, DynamicRegistrationClientCapabilities,
+        FoldingRangeCapability, FoldingRangeClientCapabilities, FoldingRangeKind,
+        FoldingRangeKindCapability, GeneralClientCapabilities, GotoCapability,
+        HoverClientCapabilities, InlayHintClientCapabilities, MarkupKind,
+        PublishDiagnosticsClientCapabilities, RenameClientCapabilities,
+        SelectionRangeClientCapabilities, SignatureHelpClientCapabilities, TagSupport,
         TextDocumentClientCapabilities, TextDocumentSyncClientCapabilities,
         WorkspaceClientCapabilities, WorkspaceEditClientCapabilities,
+        WorkspaceSymbolClientCapabilities,
     };
 
     ClientCapabilities {
@@ -284,6 +289,10 @@ fn create_client_capabilities() -> ClientCapabilities {
                 ..Default::default()
             }),
             workspace_folders: Some(true),
+            symbol: Some(WorkspaceSymbolClientCapabilities::default()),
+            code_lens: Some(CodeLensWorkspaceClientCapabilities {
+                refresh_support: Some(true),
+            }),
             ..Default::default()
         }),
         text_document: Some(TextDocumentClientCapabilities {
@@ -305,7 +314,22 @@ fn create_client_capabilities() -> ClientCapabilities {
                 link_support: Some(true),
                 ..Default::default()
             }),
+            implementation: Some(GotoCapability {
+                link_support: Some(true),
+                ..Default::default()
+            }),
+            type_definition: Some(GotoCapability {
+                link_support: Some(true),
+                ..Default::default()
+            }),
+            declaration: Some(GotoCapability {
+                link_support: Some(true),
+                ..Default::default()
+            }),
             references: Some(DynamicRegistrationClientCapabilities::default()),
+            call_hierarchy: Some(lsp_types::CallHierarchyClientCapabilities::default()),
+            type_hierarchy: Some(lsp_types::TypeHierarchyClientCapabilities::default()),
+            code_lens: Some(CodeLensClientCapabilities::default()),
             code_action: Some(CodeActionClientCapabilities {
                 // Without `codeActionLiteralSupport`, rust-analyzer (and
                 // servers that follow the same spec branch) returns `null`
@@ -365,6 +389,13 @@ fn create_client_capabilities() -> ClientCapabilities {
                 }),
                 ..Default::default()
             }),
+            selection_range: Some(SelectionRangeClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
+            document_symbol: Some(DocumentSymbolClientCapabilities {
+                hierarchical_document_symbol_support: Some(true),
+                ..Default::default()
+            }),
             semantic_tokens: Some(SemanticTokensClientCapabilities {
                 dynamic_registration: Some(true),
                 requests: SemanticTokensClientCapabilitiesRequests {
@@ -421,7 +452,16 @@ fn create_client_capabilities() -> ClientCapabilities {
         }),
         // Enable rust-analyzer experimental features
         experimental: Some(serde_json::json!({
-            "serverStatusNotification": true
+            "serverStatusNotification": true,
+            // rust-analyzer only emits "Run", "Debug" and reference-count
+            // lenses for commands the client says it can run.
+            "commands": {
+                "commands": [
+                    "rust-analyzer.runSingle",
+                    "rust-analyzer.debugSingle",
+                    "rust-analyzer.showReferences"
+                ]
+            }
         })),
         ..Default::default()
     }
@@ -490,6 +530,18 @@ fn extract_capability_summary(caps: &ServerCapabilities) -> ServerCapabilitySumm
             lsp_types::OneOf::Left(v) => *v,
             lsp_types::OneOf::Right(_) => true,
         }),
+        implementation: bool_or_options(&caps.implementation_provider, |p| match p {
+            lsp_types::ImplementationProviderCapability::Simple(v) => *v,
+            _ => true,
+        }),
+        type_definition: bool_or_options(&caps.type_definition_provider, |p| match p {
+            lsp_types::TypeDefinitionProviderCapability::Simple(v) => *v,
+            _ => true,
+        }),
+        declaration: bool_or_options(&caps.declaration_provider, |p| match p {
+            lsp_types::DeclarationCapability::Simple(v) => *v,
+            _ => true,
+        }),
         references: bool_or_options(&caps.references_provider, |p| match p {
             lsp_types::OneOf::Left(v) => *v,
             lsp_types::OneOf::Right(_) => true,
@@ -525,6 +577,10 @@ fn extract_capability_summary(caps: &ServerCapabilities) -> ServerCapabilitySumm
             lsp_types::OneOf::Left(v) => *v,
             lsp_types::OneOf::Right(_) => true,
         }),
+        selection_range: bool_or_options(&caps.selection_range_provider, |p| match p {
+            lsp_types::SelectionRangeProviderCapability::Simple(v) => *v,
+            _ => true,
+        }),
         code_action: bool_or_options(&caps.code_action_provider, |p| match p {
             lsp_types::CodeActionProviderCapability::Simple(v) => *v,
             lsp_types::CodeActionProviderCapability::Options(_) => true,
@@ -543,15 +599,99 @@ fn extract_capability_summary(caps: &ServerCapabilities) -> ServerCapabilitySumm
             lsp_types::OneOf::Left(v) => *v,
             lsp_types::OneOf::Right(_) => true,
         }),
+        call_hierarchy: bool_or_options(&caps.call_hierarchy_provider, |p| match p {
+            lsp_types::CallHierarchyServerCapability::Simple(v) => *v,
+            _ => true,
+        }),
+        // `ServerCapabilities` has no `typeHierarchyProvider` field; it is
+        // read from the raw initialize answer instead.
+        type_hierarchy: false,
+        code_lens: caps.code_lens_provider.is_some(),
+        code_lens_resolve: caps
+            .code_lens_provider
+            .as_ref()
+            .and_then(|p| p.resolve_provider)
+            .unwrap_or(false),
+        execute_commands: caps
+            .execute_command_provider
+            .as_ref()
+            .map(|p| p.commands.clone())
+            .unwrap_or_default(),
         diagnostics: caps.diagnostic_provider.is_some(),
     }
 }
 
+/// Whether the raw initialize answer advertises `typeHierarchyProvider`.
+fn advertises_type_hierarchy(initialize_result: &Value) -> bool {
+    initialize_result
+        .pointer("/capabilities/typeHierarchyProvider")
+        .is_some_and(|p| !matches!(p, Value::Null | Value::Bool(false)))
+}
+
 /// Helper: check an `Option<T>` capability field using a predicate.
 fn bool_or_options<T>(opt: &Option<T>, check: impl FnOnce(&T) -> bool) -> bool {
     opt.as_ref().is_some_and(check)
 }
 
+/// Parse a definition-like response, which can be a `Location`, a
+/// `Location[]` or a `LocationLink[]` (links point at their target's
+/// selection range).
+fn parse_locations(result: Value) -> Vec<lsp_types::Location> {
+    if let Ok(loc) = serde_json::from_value::<lsp_types::Location>(result.clone()) {
+        vec![loc]
+    } else if let Ok(locs) = serde_json::from_value::<Vec<lsp_types::Location>>(result.clone()) {
+        locs
+    } else if let Ok(links) = serde_json::from_value::<Vec<lsp_types::LocationLink>>(result) {
+        links
+            .into_iter()
+            .map(|link| lsp_types::Location {
+                uri: link.target_uri,
+                range: link.target_selection_range,
+            })
+            .collect()
+    } else {
+        vec![]
+    }
+}
+
+/// Navigation requests shaped like `textDocument/definition`: a position
+/// goes in, one or more locations come back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GotoKind {
+    Implementation,
+    TypeDefinition,
+    Declaration,
+}
+
+impl GotoKind {
+    /// LSP method of the request
+    pub fn method(self) -> &'static str {
+        match self {
+            GotoKind::Implementation => "textDocument/implementation",
+            GotoKind::TypeDefinition => "textDocument/typeDefinition",
+            GotoKind::Declaration => "textDocument/declaration",
+        }
+    }
+
+    /// Name reported to plugins (`lsp_references` hook `kind`)
+    pub fn name(self) -> &'static str {
+        match self {
+            GotoKind::Implementation => "implementation",
+            GotoKind::TypeDefinition => "type_definition",
+            GotoKind::Declaration => "declaration",
+        }
+    }
+
+    /// Feature used to pick the server that answers the request
+    pub fn feature(self) -> crate::types::LspFeature {
+        match self {
+            GotoKind::Implementation => crate::types::LspFeature::Implementation,
+            GotoKind::TypeDefinition => crate::types::LspFeature::TypeDefinition,
+            GotoKind::Declaration => crate::types::LspFeature::Declaration,
+        }
+    }
+}
+
 /// Commands sent from the main loop to the LSP task
 #[derive(Debug)]
 enum LspCommand {
@@ -603,6 +743,44 @@ enum LspCommand {
         character: u32,
     },
 
+    /// Request implementation, type definition or declaration locations
+    GotoLocations {
+        request_id: u64,
+        kind: GotoKind,
+        uri: Uri,
+        line: u32,
+        character: u32,
+    },
+
+    /// Request the call or type hierarchy items at a position
+    PrepareHierarchy {
+        request_id: u64,
+        kind: HierarchyKind,
+        uri: Uri,
+        line: u32,
+        character: u32,
+    },
+
+    /// Request the neighbours of a hierarchy item
+    HierarchyChildren {
+        request_id: u64,
+        direction: HierarchyDirection,
+        item: Value,
+    },
+
+    /// Request selection ranges (one chain per position)
+    SelectionRange {
+        request_id: u64,
+        uri: Uri,
+        positions: Vec<(u32, u32)>,
+    },
+
+    /// Request the outline of a document
+    DocumentSymbols { request_id: u64, uri: Uri },
+
+    /// Search symbols across the workspace
+    WorkspaceSymbols { request_id: u64, query: String },
+
     /// Request rename
     Rename {
         request_id: u64,
@@ -669,6 +847,15 @@ enum LspCommand {
     /// Request folding ranges for a document
     FoldingRange { request_id: u64, uri: Uri },
 
+    /// Request code lenses for a document
+    CodeLens { request_id: u64, uri: Uri },
+
+    /// Resolve the command of a code lens (codeLens/resolve)
+    CodeLensResolve {
+        request_id: u64,
+        lens: Box<lsp_types::CodeLens>,
+    },
+
     /// Request semantic tokens for the entire document
     SemanticTokensFull { request_id: u64, uri: Uri },
 
@@ -917,20 +1104,10 @@ impl LspState {
         let json =
             serde_json::to_string(message).map_err(|e| format!("Serialization error: {}", e))?;
 
-        let content = format!("Content-Length: {}\r\n\r\n{}", json.len(), json);
-
-        tracing::trace!("Writing LSP message to stdin ({} bytes)", content.len());
+        tracing::trace!("Writing LSP message to stdin ({} bytes)", json.len());
 
         let mut stdin = self.stdin.lock().await;
-        stdin
-            .write_all(content.as_bytes())
-            .await
-            .map_err(|e| format!("Failed to write to stdin: {}", e))?;
-
-        stdin
-            .flush()
-            .await
-            .map_err(|e| format!("Failed to flush stdin: {}", e))?;
+        stdio_framing::write_message(&mut *stdin, &json).await?;
 
         tracing::trace!("Successfully sent LSP message");
 
@@ -1108,9 +1285,12 @@ impl LspState {
             ..Default::default()
         };
 
-        let result: InitializeResult = self
+        let response: Value = self
             .send_request_sequential(Initialize::METHOD, Some(params), pending)
             .await?;
+        let type_hierarchy = advertises_type_hierarchy(&response);
+        let result: InitializeResult = serde_json::from_value(response)
+            .map_err(|e| format!("Invalid initialize result: {}", e))?;
 
         tracing::info!(
             "LSP initialize result: position_encoding={:?}",
@@ -1124,7 +1304,8 @@ impl LspState {
 
         self.initialized.store(true, Ordering::SeqCst);
 
-        let capabilities = extract_capability_summary(&result.capabilities);
+        let mut capabilities = extract_capability_summary(&result.capabilities);
+        capabilities.type_hierarchy = type_hierarchy;
 
         // Notify main loop
         let _ = self.async_tx.send(AsyncMessage::LspInitialized {
@@ -1397,29 +1578,7 @@ impl LspState {
             .await
         {
             Ok(result) => {
-                // Parse the definition response (can be Location, Vec<Location>, or LocationLink)
-                let locations = if let Ok(loc) =
-                    serde_json::from_value::<lsp_types::Location>(result.clone())
-                {
-                    vec![loc]
-                } else if let Ok(locs) =
-                    serde_json::from_value::<Vec<lsp_types::Location>>(result.clone())
-                {
-                    locs
-                } else if let Ok(links) =
-                    serde_json::from_value::<Vec<lsp_types::LocationLink>>(result)
-                {
-                    // Convert LocationLink to Location
-                    links
-                        .into_iter()
-                        .map(|link| lsp_types::Location {
-                            uri: link.target_uri,
-                            range: link.target_selection_range,
-                        })
-                        .collect()
-                } else {
-                    vec![]
-                };
+                let locations = parse_locations(result);
 
                 // Send to main loop
                 let _ = self.async_tx.send(AsyncMessage::LspGotoDefinition {
@@ -1440,6 +1599,279 @@ impl LspState {
         }
     }
 
+    /// Handle implementation / type definition / declaration request
+    async fn handle_goto_locations(
+        &self,
+        request_id: u64,
+        kind: GotoKind,
+        uri: Uri,
+        line: u32,
+        character: u32,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::GotoDefinitionParams;
+
+        tracing::trace!(
+            "LSP: {} request at {}:{}:{}",
+            kind.method(),
+            uri.as_str(),
+            line,
+            character
+        );
+
+        // Implementation, type definition and declaration all take the
+        // same params as definition.
+        let params = GotoDefinitionParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position: Position { line, character },
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        let (locations, result) = match self
+            .send_request_sequential::<_, Value>(kind.method(), Some(params), pending)
+            .await
+        {
+            Ok(value) => (parse_locations(value), Ok(())),
+            Err(e) => {
+                tracing::debug!("{} request failed: {}", kind.method(), e);
+                (Vec::new(), Err(e))
+            }
+        };
+        let _ = self.async_tx.send(AsyncMessage::LspGotoLocations {
+            request_id,
+            kind,
+            locations,
+        });
+        result
+    }
+
+    /// Handle prepareCallHierarchy / prepareTypeHierarchy request
+    async fn handle_prepare_hierarchy(
+        &self,
+        request_id: u64,
+        kind: HierarchyKind,
+        uri: Uri,
+        line: u32,
+        character: u32,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        tracing::trace!(
+            "LSP: {} request at {}:{}:{}",
+            kind.prepare_method(),
+            uri.as_str(),
+            line,
+            character
+        );
+
+        let params = TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri },
+            position: Position { line, character },
+        };
+
+        let (entries, result) = match self
+            .send_request_sequential::<_, Value>(kind.prepare_method(), Some(params), pending)
+            .await
+        {
+            Ok(value) => (hierarchy::prepared_entries(value), Ok(())),
+            Err(e) => {
+                tracing::debug!("{} request failed: {}", kind.prepare_method(), e);
+                (Vec::new(), Err(e))
+            }
+        };
+        let _ = self.async_tx.send(AsyncMessage::LspHierarchy {
+            request_id,
+            server_name: (*self.server_name).clone(),
+            entries,
+        });
+        result
+    }
+
+    /// Handle incoming/outgoing calls and supertypes/subtypes requests
+    async fn handle_hierarchy_children(
+        &self,
+        request_id: u64,
+        direction: HierarchyDirection,
+        item: Value,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        tracing::trace!("LSP: {} request", direction.method());
+
+        let params = serde_json::json!({ "item": item });
+        let (entries, result) = match self
+            .send_request_sequential::<_, Value>(direction.method(), Some(params), pending)
+            .await
+        {
+            Ok(value) => (hierarchy::child_entries(direction, &item, value), Ok(())),
+            Err(e) => {
+                tracing::debug!("{} request failed: {}", direction.method(), e);
+                (Vec::new(), Err(e))
+            }
+        };
+        let _ = self.async_tx.send(AsyncMessage::LspHierarchy {
+            request_id,
+            server_name: (*self.server_name).clone(),
+            entries,
+        });
+        result
+    }
+
+    /// Handle selection range request
+    async fn handle_selection_range(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        positions: Vec<(u32, u32)>,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::{SelectionRange, SelectionRangeParams};
+
+        tracing::trace!(
+            "LSP: selection range request for {} ({} positions)",
+            uri.as_str(),
+            positions.len()
+        );
+
+        let params = SelectionRangeParams {
+            text_document: TextDocumentIdentifier { uri },
+            positions: positions
+                .into_iter()
+                .map(|(line, character)| Position { line, character })
+                .collect(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        match self
+            .send_request_sequential::<_, Option<Vec<SelectionRange>>>(
+                "textDocument/selectionRange",
+                Some(params),
+                pending,
+            )
+            .await
+        {
+            Ok(result) => {
+                // Flatten each parent-linked chain into innermost-first ranges
+                let ranges = result
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|selection| {
+                        let mut chain = Vec::new();
+                        let mut current = Some(Box::new(selection));
+                        while let Some(node) = current {
+                            chain.push(node.range);
+                            current = node.parent;
+                        }
+                        chain
+                    })
+                    .collect();
+
+                let _ = self
+                    .async_tx
+                    .send(AsyncMessage::LspSelectionRanges { request_id, ranges });
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("Selection range request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspSelectionRanges {
+                    request_id,
+                    ranges: Vec::new(),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Handle document symbol request
+    async fn handle_document_symbols(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::{DocumentSymbolParams, DocumentSymbolResponse};
+
+        tracing::trace!("LSP: document symbol request for {}", uri.as_str());
+
+        let params = DocumentSymbolParams {
+            text_document: TextDocumentIdentifier { uri },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        match self
+            .send_request_sequential::<_, Option<DocumentSymbolResponse>>(
+                "textDocument/documentSymbol",
+                Some(params),
+                pending,
+            )
+            .await
+        {
+            Ok(result) => {
+                let _ = self.async_tx.send(AsyncMessage::LspDocumentSymbols {
+                    request_id,
+                    symbols: Some(result.unwrap_or(DocumentSymbolResponse::Nested(Vec::new()))),
+                });
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("Document symbol request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspDocumentSymbols {
+                    request_id,
+                    symbols: None,
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Handle workspace symbol request
+    ///
+    /// Tracked under `request_id` so a newer query can cancel it.
+    async fn handle_workspace_symbols(
+        &self,
+        request_id: u64,
+        query: String,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::{WorkspaceSymbolParams, WorkspaceSymbolResponse};
+
+        tracing::trace!("LSP: workspace symbol request for {:?}", query);
+
+        let params = WorkspaceSymbolParams {
+            query,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        let (symbols, result) = match self
+            .send_request_sequential_tracked::<_, Option<WorkspaceSymbolResponse>>(
+                "workspace/symbol",
+                Some(params),
+                pending,
+                Some(request_id),
+            )
+            .await
+        {
+            Ok(response) => (
+                Some(response.unwrap_or(WorkspaceSymbolResponse::Flat(Vec::new()))),
+                Ok(()),
+            ),
+            Err(e) => {
+                tracing::debug!("Workspace symbol request failed: {}", e);
+                (None, Err(e))
+            }
+        };
+        let _ = self.async_tx.send(AsyncMessage::LspWorkspaceSymbols {
+            request_id,
+            server_name: (*self.server_name).clone(),
+            symbols,
+        });
+        result
+    }
+
     /// Handle rename request
     async fn handle_rename(
         &self,
@@ -2323,6 +2755,92 @@ impl LspState {
         }
     }
 
+    /// Handle code lens request
+    async fn handle_code_lens(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        use lsp_types::CodeLensParams;
+
+        tracing::trace!("LSP: code lens request for {}", uri.as_str());
+
+        let params = CodeLensParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        match self
+            .send_request_sequential::<_, Option<Vec<lsp_types::CodeLens>>>(
+                "textDocument/codeLens",
+                Some(params),
+                pending,
+            )
+            .await
+        {
+            Ok(lenses) => {
+                let lenses = lenses.unwrap_or_default();
+                tracing::trace!(
+                    "LSP: received {} code lenses for {}",
+                    lenses.len(),
+                    uri.as_str()
+                );
+                let _ = self.async_tx.send(AsyncMessage::LspCodeLenses {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    server_name: (*self.server_name).clone(),
+                    lenses,
+                });
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("Code lens request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspCodeLenses {
+                    request_id,
+                    uri: uri.as_str().to_string(),
+                    server_name: (*self.server_name).clone(),
+                    lenses: Vec::new(),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Handle codeLens/resolve request
+    async fn handle_code_lens_resolve(
+        &self,
+        request_id: u64,
+        lens: lsp_types::CodeLens,
+        pending: &PendingRequests,
+    ) -> Result<(), String> {
+        match self
+            .send_request_sequential::<_, lsp_types::CodeLens>(
+                "codeLens/resolve",
+                Some(lens),
+                pending,
+            )
+            .await
+        {
+            Ok(resolved) => {
+                let _ = self.async_tx.send(AsyncMessage::LspCodeLensResolved {
+                    request_id,
+                    lens: Some(resolved),
+                });
+                Ok(())
+            }
+            Err(e) => {
+                tracing::debug!("codeLens/resolve failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspCodeLensResolved {
+                    request_id,
+                    lens: None,
+                });
+                Err(e)
+            }
+        }
+    }
+
     async fn handle_semantic_tokens_full(
         &self,
         request_id: u64,
@@ -3108,6 +3626,118 @@ impl LspTask {
                         });
                     }
                 }
+                LspCommand::GotoLocations {
+                    request_id,
+                    kind,
+                    uri,
+                    line,
+                    character,
+                } => {
+                    if initialized {
+                        tracing::info!("Processing {} request for {}", kind.method(), uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_goto_locations(request_id, kind, uri, line, character, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, sending empty locations");
+                        let _ = state.async_tx.send(AsyncMessage::LspGotoLocations {
+                            request_id,
+                            kind,
+                            locations: vec![],
+                        });
+                    }
+                }
+                LspCommand::PrepareHierarchy {
+                    request_id,
+                    kind,
+                    uri,
+                    line,
+                    character,
+                } => {
+                    if initialized {
+                        tracing::info!(
+                            "Processing {} request for {}",
+                            kind.prepare_method(),
+                            uri.as_str()
+                        );
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_prepare_hierarchy(request_id, kind, uri, line, character, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, sending empty hierarchy");
+                        let _ = state.async_tx.send(AsyncMessage::LspHierarchy {
+                            request_id,
+                            server_name: server_name.clone(),
+                            entries: vec![],
+                        });
+                    }
+                }
+                LspCommand::HierarchyChildren {
+                    request_id,
+                    direction,
+                    item,
+                } => {
+                    if initialized {
+                        tracing::info!("Processing {} request", direction.method());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_hierarchy_children(request_id, direction, item, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, sending empty hierarchy");
+                        let _ = state.async_tx.send(AsyncMessage::LspHierarchy {
+                            request_id,
+                            server_name: server_name.clone(),
+                            entries: vec![],
+                        });
+                    }
+                }
+                LspCommand::SelectionRange {
+                    request_id,
+                    uri,
+                    positions,
+                } => {
+                    if initialized {
+                        tracing::info!("Processing SelectionRange request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_selection_range(request_id, uri, positions, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, sending empty selection ranges");
+                        let _ = state.async_tx.send(AsyncMessage::LspSelectionRanges {
+                            request_id,
+                            ranges: Vec::new(),
+                        });
+                    }
+                }
+                LspCommand::DocumentSymbols { request_id, uri } => {
+                    if initialized {
+                        tracing::info!("Processing DocumentSymbols request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_document_symbols(request_id, uri, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, document symbols unavailable");
+                        let _ = state.async_tx.send(AsyncMessage::LspDocumentSymbols {
+                            request_id,
+                            symbols: None,
+                        });
+                    }
+                }
+                LspCommand::WorkspaceSymbols { request_id, query } => {
+                    if initialized {
+                        tracing::info!("Processing WorkspaceSymbols request for {:?}", query);
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_workspace_symbols(request_id, query, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, workspace symbols unavailable");
+                        let _ = state.async_tx.send(AsyncMessage::LspWorkspaceSymbols {
+                            request_id,
+                            server_name: server_name.clone(),
+                            symbols: None,
+                        });
+                    }
+                }
                 LspCommand::Rename {
                     request_id,
                     uri,
@@ -3281,6 +3911,34 @@ impl LspTask {
                         });
                     }
                 }
+                LspCommand::CodeLens { request_id, uri } => {
+                    if initialized {
+                        tracing::info!("Processing CodeLens request for {}", uri.as_str());
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_code_lens(request_id, uri, &p)
+                            .await);
+                    } else {
+                        tracing::trace!("LSP not initialized, cannot get code lenses");
+                        let _ = state.async_tx.send(AsyncMessage::LspCodeLenses {
+                            request_id,
+                            uri: uri.as_str().to_string(),
+                            server_name: server_name.clone(),
+                            lenses: Vec::new(),
+                        });
+                    }
+                }
+                LspCommand::CodeLensResolve { request_id, lens } => {
+                    if initialized {
+                        spawn_request!(state, pending, |s, p| s
+                            .handle_code_lens_resolve(request_id, *lens, &p)
+                            .await);
+                    } else {
+                        let _ = state.async_tx.send(AsyncMessage::LspCodeLensResolved {
+                            request_id,
+                            lens: None,
+                        });
+                    }
+                }
                 LspCommand::SemanticTokensFull { request_id, uri } => {
                     if initialized {
                         tracing::info!("Processing SemanticTokens request for {}", uri.as_str());
@@ -3484,46 +4142,7 @@ impl LspTask {
 async fn read_message_from_stdout(
     stdout: &mut BufReader<ChildStdout>,
 ) -> Result<JsonRpcMessage, String> {
-    // Read headers
-    let mut content_length: Option<usize> = None;
-
-    loop {
-        let mut line = String::new();
-        let bytes_read = stdout
-            .read_line(&mut line)
-            .await
-            .map_err(|e| format!("Failed to read from stdout: {}", e))?;
-
-        // EOF detected - LSP server closed stdout
-        if bytes_read == 0 {
-            return Err("LSP server closed stdout (EOF)".to_string());
-        }
-
-        if line == "\r\n" {
-            break;
-        }
-
-        if let Some(len_str) = line.strip_prefix("Content-Length: ") {
-            content_length = Some(
-                len_str
-                    .trim()
-                    .parse()
-                    .map_err(|e| format!("Invalid Content-Length: {}", e))?,
-            );
-        }
-    }
-
-    let content_length =
-        content_length.ok_or_else(|| "Missing Content-Length header".to_string())?;
-
-    // Read content
-    let mut content = vec![0u8; content_length];
-    stdout
-        .read_exact(&mut content)
-        .await
-        .map_err(|e| format!("Failed to read content: {}", e))?;
-
-    let json = String::from_utf8(content).map_err(|e| format!("Invalid UTF-8: {}", e))?;
+    let json = stdio_framing::read_message(stdout, "LSP server").await?;
 
     tracing::trace!("Received LSP message: {}", json);
 
@@ -3630,6 +4249,16 @@ async fn handle_message_dispatch(
                             "closureReturnTypeHints": {
                                 "enable": "always"
                             }
+                        },
+                        // "Run | Debug" lenses are on by default; reference
+                        // counts are not.
+                        "lens": {
+                            "references": {
+                                "adt": { "enable": true },
+                                "enumVariant": { "enable": true },
+                                "method": { "enable": true },
+                                "trait": { "enable": true }
+                            }
                         }
                     });
 
@@ -3673,6 +4302,23 @@ async fn handle_message_dispatch(
                         error: None,
                     }
                 }
+                "workspace/codeLens/refresh" => {
+                    // Server's lenses went stale (e.g. the project finished
+                    // loading); re-request them for all open documents
+                    tracing::info!(
+                        "LSP ({}) requested code lens refresh (workspace/codeLens/refresh)",
+                        language
+                    );
+                    let _ = async_tx.send(AsyncMessage::LspCodeLensRefresh {
+                        language: language.to_string(),
+                    });
+                    JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        id: request.id,
+                        result: Some(Value::Null),
+                        error: None,
+                    }
+                }
                 "workspace/applyEdit" => {
                     // Server asks client to apply a workspace edit (e.g. during executeCommand)
                     tracing::info!("LSP ({}) received workspace/applyEdit request", language);
@@ -3727,16 +4373,11 @@ async fn handle_message_dispatch(
             // Write response directly to stdin (avoids deadlock when main loop is waiting for LSP response)
             let json = serde_json::to_string(&response)
                 .map_err(|e| format!("Failed to serialize response: {}", e))?;
-            let message = format!("Content-Length: {}\r\n\r\n{}", json.len(), json);
 
             let mut stdin = stdin_writer.lock().await;
-            use tokio::io::AsyncWriteExt;
-            if let Err(e) = stdin.write_all(message.as_bytes()).await {
+            if let Err(e) = stdio_framing::write_message(&mut *stdin, &json).await {
                 tracing::error!("Failed to write server response: {}", e);
             }
-            if let Err(e) = stdin.flush().await {
-                tracing::error!("Failed to flush server response: {}", e);
-            }
             tracing::trace!("Sent response to server request id={}", response.id);
         }
     }
@@ -4361,6 +5002,92 @@ impl LspHandle {
             .map_err(|_| "Failed to send goto_definition command".to_string())
     }
 
+    /// Request implementation, type definition or declaration locations
+    pub fn goto_locations(
+        &self,
+        request_id: u64,
+        kind: GotoKind,
+        uri: Uri,
+        line: u32,
+        character: u32,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::GotoLocations {
+                request_id,
+                kind,
+                uri,
+                line,
+                character,
+            })
+            .map_err(|_| "Failed to send goto_locations command".to_string())
+    }
+
+    /// Request the call or type hierarchy items at a position
+    pub fn prepare_hierarchy(
+        &self,
+        request_id: u64,
+        kind: HierarchyKind,
+        uri: Uri,
+        line: u32,
+        character: u32,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::PrepareHierarchy {
+                request_id,
+                kind,
+                uri,
+                line,
+                character,
+            })
+            .map_err(|_| "Failed to send prepare_hierarchy command".to_string())
+    }
+
+    /// Request the callers, callees, supertypes or subtypes of `item`
+    pub fn hierarchy_children(
+        &self,
+        request_id: u64,
+        direction: HierarchyDirection,
+        item: Value,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::HierarchyChildren {
+                request_id,
+                direction,
+                item,
+            })
+            .map_err(|_| "Failed to send hierarchy_children command".to_string())
+    }
+
+    /// Request selection ranges at the given `(line, character)` positions
+    pub fn selection_range(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        positions: Vec<(u32, u32)>,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::SelectionRange {
+                request_id,
+                uri,
+                positions,
+            })
+            .map_err(|_| "Failed to send selection_range command".to_string())
+    }
+
+    /// Request the outline of a document
+    pub fn document_symbols(&self, request_id: u64, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DocumentSymbols { request_id, uri })
+            .map_err(|_| "Failed to send document_symbols command".to_string())
+    }
+
+    /// Search symbols across the workspace
+    pub fn workspace_symbols(&self, request_id: u64, query: String) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::WorkspaceSymbols { request_id, query })
+            .map_err(|_| "Failed to send workspace_symbols command".to_string())
+    }
+
     /// Request rename
     pub fn rename(
         &self,
@@ -4618,6 +5345,30 @@ impl LspHandle {
             .map_err(|_| "Failed to send folding_range command".to_string())
     }
 
+    /// Request code lenses for a document
+    pub fn code_lens(&self, request_id: u64, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::CodeLens { request_id, uri })
+            .map_err(|_| "Failed to send code_lens command".to_string())
+    }
+
+    /// Resolve the command of a code lens (codeLens/resolve)
+    ///
+    /// Only call this when the lens has no command and the server
+    /// supports resolveProvider.
+    pub fn code_lens_resolve(
+        &self,
+        request_id: u64,
+        lens: lsp_types::CodeLens,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::CodeLensResolve {
+                request_id,
+                lens: Box::new(lens),
+            })
+            .map_err(|_| "Failed to send code_lens_resolve command".to_string())
+    }
+
     /// Request semantic tokens for an entire document
     pub fn semantic_tokens_full(&self, request_id: u64, uri: Uri) -> Result<(), String> {
         self.command_tx
@@ -5245,6 +5996,33 @@ mod tests {
         assert!(debug_str.contains("Shutdown"));
     }
 
+    #[test]
+    fn test_parse_locations_accepts_all_response_shapes() {
+        let range = serde_json::json!({
+            "start": { "line": 3, "character": 4 },
+            "end": { "line": 3, "character": 9 }
+        });
+        let location = serde_json::json!({ "uri": "file:///a.rs", "range": range });
+
+        assert_eq!(parse_locations(location.clone()).len(), 1);
+        assert_eq!(
+            parse_locations(serde_json::json!([location, location])).len(),
+            2
+        );
+        assert!(parse_locations(Value::Null).is_empty());
+
+        let links = parse_locations(serde_json::json!([{
+            "targetUri": "file:///b.rs",
+            "targetRange": {
+                "start": { "line": 1, "character": 0 },
+                "end": { "line": 8, "character": 1 }
+            },
+            "targetSelectionRange": range
+        }]));
+        assert_eq!(links[0].uri.as_str(), "file:///b.rs");
+        assert_eq!(links[0].range.start, Position::new(3, 4));
+    }
+
     #[test]
     fn test_lsp_client_state_can_initialize_from_starting() {
         // This test verifies that the state machine allows initialization from the Starting state.
@@ -17,6 +17,18 @@ pub fn lsp_diagnostic_namespace() -> OverlayNamespace {
     OverlayNamespace::from_string("lsp-diagnostic".to_string())
 }
 
+/// Namespace for diagnostics a task's problem matcher found in its output.
+/// Kept apart from the LSP namespace so a server update doesn't wipe them.
+pub fn task_diagnostic_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("task-diagnostic".to_string())
+}
+
+/// Whether overlays in `namespace` are diagnostics, from either source
+pub fn is_diagnostic_namespace(namespace: Option<&OverlayNamespace>) -> bool {
+    namespace
+        .is_some_and(|ns| *ns == lsp_diagnostic_namespace() || *ns == task_diagnostic_namespace())
+}
+
 /// Cache for diagnostic hash to avoid redundant updates, keyed by file path.
 /// This prevents diagnostics from one buffer from invalidating another buffer's cache.
 static DIAGNOSTIC_CACHE: LazyLock<Mutex<HashMap<String, u64>>> =
@@ -224,9 +236,17 @@ pub fn apply_diagnostics_to_state(
     diagnostics: &[Diagnostic],
     theme: &crate::view::theme::Theme,
 ) {
-    let ns = lsp_diagnostic_namespace();
+    apply_diagnostics_in_namespace(state, lsp_diagnostic_namespace(), diagnostics, theme);
+}
 
-    // Clear all existing LSP diagnostic overlays using namespace
+/// Replace the diagnostic overlays in `ns` with `diagnostics`
+pub fn apply_diagnostics_in_namespace(
+    state: &mut EditorState,
+    ns: OverlayNamespace,
+    diagnostics: &[Diagnostic],
+    theme: &crate::view::theme::Theme,
+) {
+    // Clear all existing diagnostic overlays using namespace
     state.overlays.clear_namespace(&ns, &mut state.marker_list);
 
     // Add overlays for all current diagnostics
@@ -199,6 +199,9 @@ pub struct ServerCapabilitySummary {
     pub completion_resolve: bool,
     pub completion_trigger_characters: Vec<String>,
     pub definition: bool,
+    pub implementation: bool,
+    pub type_definition: bool,
+    pub declaration: bool,
     pub references: bool,
     pub document_formatting: bool,
     pub document_range_formatting: bool,
@@ -211,10 +214,17 @@ pub struct ServerCapabilitySummary {
     pub semantic_tokens_range: bool,
     pub semantic_tokens_legend: Option<SemanticTokensLegend>,
     pub document_highlight: bool,
+    pub selection_range: bool,
     pub code_action: bool,
     pub code_action_resolve: bool,
     pub document_symbols: bool,
     pub workspace_symbols: bool,
+    pub call_hierarchy: bool,
+    pub type_hierarchy: bool,
+    pub code_lens: bool,
+    pub code_lens_resolve: bool,
+    /// Commands the server runs through `workspace/executeCommand`
+    pub execute_commands: Vec<String>,
     pub diagnostics: bool,
 }
 
@@ -249,6 +259,9 @@ impl ServerHandle {
             LspFeature::Hover => self.capabilities.hover,
             LspFeature::Completion => self.capabilities.completion,
             LspFeature::Definition => self.capabilities.definition,
+            LspFeature::Implementation => self.capabilities.implementation,
+            LspFeature::TypeDefinition => self.capabilities.type_definition,
+            LspFeature::Declaration => self.capabilities.declaration,
             LspFeature::References => self.capabilities.references,
             LspFeature::Format => {
                 self.capabilities.document_formatting || self.capabilities.document_range_formatting
@@ -261,9 +274,13 @@ impl ServerHandle {
                 self.capabilities.semantic_tokens_full || self.capabilities.semantic_tokens_range
             }
             LspFeature::DocumentHighlight => self.capabilities.document_highlight,
+            LspFeature::SelectionRange => self.capabilities.selection_range,
             LspFeature::CodeAction => self.capabilities.code_action,
             LspFeature::DocumentSymbols => self.capabilities.document_symbols,
             LspFeature::WorkspaceSymbols => self.capabilities.workspace_symbols,
+            LspFeature::CallHierarchy => self.capabilities.call_hierarchy,
+            LspFeature::TypeHierarchy => self.capabilities.type_hierarchy,
+            LspFeature::CodeLens => self.capabilities.code_lens,
             LspFeature::Diagnostics => self.capabilities.diagnostics,
         }
     }
@@ -852,6 +869,20 @@ impl LspManager {
             .collect()
     }
 
+    /// Get every running handle that allows a given feature, whatever its
+    /// language. For workspace-wide requests such as `workspace/symbol`.
+    pub fn all_handles_for_feature(&self, feature: LspFeature) -> Vec<&ServerHandle> {
+        self.handles
+            .iter()
+            .filter(|sh| sh.feature_filter.allows(feature) && sh.has_capability(feature))
+            .collect()
+    }
+
+    /// Get a running handle by server name.
+    pub fn handle_named(&self, server_name: &str) -> Option<&ServerHandle> {
+        self.handles.iter().find(|sh| sh.name == server_name)
+    }
+
     /// Consult the spawn throttle for `language` and, on `Allow`, record
     /// the attempt.
     ///
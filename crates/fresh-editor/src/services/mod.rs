@@ -8,6 +8,7 @@ pub mod authority;
 pub mod clipboard;
 pub mod completion;
 pub mod counters;
+pub mod dap;
 pub mod file_watcher;
 pub mod fs;
 #[cfg(target_os = "linux")]
@@ -17,13 +18,16 @@ pub mod log_dirs;
 pub mod lsp;
 pub mod packages;
 pub mod plugins;
+pub mod problem_matcher;
 pub mod process_hidden;
 pub mod process_limits;
 pub mod recovery;
 pub mod release_checker;
 pub mod remote;
 pub mod signal_handler;
+pub mod spell_check;
 pub mod status_log;
+pub mod stdio_framing;
 pub mod styled_html;
 pub mod telemetry;
 pub mod terminal;
@@ -129,6 +129,16 @@ pub struct FreshManifestConfig {
     /// Plugin definitions (for bundles)
     #[serde(default)]
     pub plugins: Vec<BundlePlugin>,
+
+    /// Snippet files in VS Code format (for language packs and bundles)
+    #[serde(default)]
+    pub snippets: Vec<BundleSnippets>,
+
+    /// Capabilities the package's plugins need. Each is asked for the
+    /// first time it is used; a plugin package without this block may
+    /// ask for any capability.
+    #[serde(default)]
+    pub permissions: Option<fresh_core::permissions::PluginPermissions>,
 }
 
 /// Grammar file configuration within a package manifest.
@@ -261,6 +271,19 @@ pub struct BundlePlugin {
     pub entry: String,
 }
 
+/// A snippet file entry within a package manifest.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct BundleSnippets {
+    /// Language the snippets apply to. Defaults to the package name for
+    /// language packs; `.code-snippets` files may name languages per snippet
+    /// with `scope` instead.
+    #[serde(default)]
+    pub language: Option<String>,
+
+    /// Snippet JSON file path relative to package
+    pub path: String,
+}
+
 /// A theme entry within a package manifest.
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct BundleTheme {
@@ -347,6 +370,9 @@ pub struct PackageScanResult {
     pub bundle_plugin_dirs: Vec<PathBuf>,
     /// Bundle theme directories (for theme loader to scan)
     pub bundle_theme_dirs: Vec<PathBuf>,
+    /// Snippet files, with the language they apply to (if the manifest
+    /// names one)
+    pub snippet_files: Vec<(Option<String>, PathBuf)>,
 }
 
 /// Scan all installed packages and collect configs, grammars, plugin dirs, and theme dirs.
@@ -354,7 +380,8 @@ pub struct PackageScanResult {
 /// This replaces the JS `loadInstalledPackages()` function, running synchronously
 /// during editor startup before plugin loading. The scan covers:
 /// - `languages/packages/` — language packs with grammar, language config, LSP config
-/// - `bundles/packages/` — bundles with multiple languages, plugins, and themes
+/// - `bundles/packages/` — bundles with multiple languages, plugins, themes
+///   and snippets
 pub fn scan_installed_packages(config_dir: &Path) -> PackageScanResult {
     let mut result = PackageScanResult::default();
 
@@ -371,12 +398,13 @@ pub fn scan_installed_packages(config_dir: &Path) -> PackageScanResult {
     }
 
     tracing::info!(
-        "[package-scan] Found {} language configs, {} LSP configs, {} grammars, {} bundle plugin dirs, {} bundle theme dirs",
+        "[package-scan] Found {} language configs, {} LSP configs, {} grammars, {} bundle plugin dirs, {} bundle theme dirs, {} snippet files",
         result.language_configs.len(),
         result.lsp_configs.len(),
         result.additional_grammars.len(),
         result.bundle_plugin_dirs.len(),
         result.bundle_theme_dirs.len(),
+        result.snippet_files.len(),
     );
 
     result
@@ -406,7 +434,7 @@ fn scan_language_packs(dir: &Path, result: &mut PackageScanResult) {
 
 /// Process a single language pack manifest.
 fn process_language_pack(
-    _pkg_dir: &Path,
+    pkg_dir: &Path,
     manifest: &PackageManifest,
     result: &mut PackageScanResult,
 ) {
@@ -433,6 +461,12 @@ fn process_language_pack(
             .lsp_configs
             .push((lang_id.clone(), lsp_config.to_lsp_config()));
     }
+
+    // Snippets default to the pack's own language
+    for snippets in &fresh.snippets {
+        let language = snippets.language.clone().unwrap_or_else(|| lang_id.clone());
+        collect_snippet_file(pkg_dir, Some(language), &snippets.path, manifest, result);
+    }
 }
 
 /// Scan bundles from `bundles/packages/`.
@@ -515,6 +549,37 @@ fn process_bundle(pkg_dir: &Path, manifest: &PackageManifest, result: &mut Packa
     if !fresh.themes.is_empty() {
         result.bundle_theme_dirs.push(pkg_dir.to_path_buf());
     }
+
+    // Bundle snippets
+    for snippets in &fresh.snippets {
+        collect_snippet_file(
+            pkg_dir,
+            snippets.language.clone(),
+            &snippets.path,
+            manifest,
+            result,
+        );
+    }
+}
+
+/// Record a package's snippet file if it exists.
+fn collect_snippet_file(
+    pkg_dir: &Path,
+    language: Option<String>,
+    path: &str,
+    manifest: &PackageManifest,
+    result: &mut PackageScanResult,
+) {
+    let snippet_path = pkg_dir.join(path);
+    if snippet_path.is_file() {
+        result.snippet_files.push((language, snippet_path));
+    } else {
+        tracing::warn!(
+            "[package-scan] Snippet file not found in package '{}': {:?}",
+            manifest.name,
+            snippet_path
+        );
+    }
 }
 
 /// Read and parse a package.json manifest, returning None on any error.
@@ -843,6 +908,51 @@ mod tests {
         assert_eq!(result.bundle_theme_dirs[0], bundle_dir);
     }
 
+    #[test]
+    fn test_scan_snippets() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_dir = temp_dir.path();
+
+        let lang_dir = config_dir.join("languages/packages/hare");
+        std::fs::create_dir_all(&lang_dir).unwrap();
+        std::fs::write(lang_dir.join("hare.json"), "{}").unwrap();
+        std::fs::write(
+            lang_dir.join("package.json"),
+            r#"{
+                "name": "hare",
+                "fresh": { "snippets": [{ "path": "hare.json" }] }
+            }"#,
+        )
+        .unwrap();
+
+        let bundle_dir = config_dir.join("bundles/packages/team-snippets");
+        std::fs::create_dir_all(&bundle_dir).unwrap();
+        std::fs::write(bundle_dir.join("all.code-snippets"), "{}").unwrap();
+        std::fs::write(
+            bundle_dir.join("package.json"),
+            r#"{
+                "name": "team-snippets",
+                "type": "bundle",
+                "fresh": {
+                    "snippets": [
+                        { "path": "all.code-snippets" },
+                        { "language": "rust", "path": "missing.json" }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let result = scan_installed_packages(config_dir);
+        assert_eq!(
+            result.snippet_files,
+            vec![
+                (Some("hare".to_string()), lang_dir.join("hare.json")),
+                (None, bundle_dir.join("all.code-snippets")),
+            ]
+        );
+    }
+
     #[test]
     fn test_scan_skips_malformed_manifest() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -5,17 +5,22 @@
 //! disabled, all methods are no-ops, avoiding the need for cfg attributes
 //! scattered throughout the codebase.
 
+use crate::config::EditorConfig;
 use crate::config_io::DirectoryContext;
 use crate::input::command_registry::CommandRegistry;
 use fresh_core::config::PluginConfig;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 
 #[cfg(feature = "plugins")]
 use super::bridge::EditorServiceBridge;
 #[cfg(feature = "plugins")]
+use fresh_plugin_runtime::backend::watchdog::Limits;
+#[cfg(feature = "plugins")]
 use fresh_plugin_runtime::PluginThreadHandle;
+#[cfg(feature = "plugins")]
+use std::time::Duration;
 
 /// Unified plugin manager that abstracts over the plugin system.
 ///
@@ -33,18 +38,31 @@ pub struct PluginManager {
     /// integration tests in `tests/` can use it without an extra
     /// feature flag.
     pending_injected_commands: Vec<super::api::PluginCommand>,
+    /// Hooks observed on behalf of remote-control subscribers. Filled by
+    /// `run_hook` whether or not a plugin runtime is active.
+    observed_hooks: Mutex<ObservedHooks>,
+}
+
+/// Hook names to record in [`PluginManager::run_hook`], and the calls
+/// recorded since the last [`PluginManager::take_observed_hooks`].
+#[derive(Default)]
+struct ObservedHooks {
+    names: HashSet<String>,
+    calls: Vec<(String, serde_json::Value)>,
 }
 
 impl PluginManager {
     /// Create a new plugin manager.
     ///
-    /// When `plugins` feature is enabled and `enable` is true, spawns the plugin thread.
+    /// When `plugins` feature is enabled and `enable` is true, spawns the plugin thread,
+    /// with the plugin time budgets and memory limit from `editor_config`.
     /// Otherwise, creates a no-op manager.
     pub fn new(
         enable: bool,
         command_registry: Arc<RwLock<CommandRegistry>>,
         dir_context: DirectoryContext,
         theme_cache: Arc<RwLock<HashMap<String, serde_json::Value>>>,
+        editor_config: &EditorConfig,
     ) -> Self {
         #[cfg(feature = "plugins")]
         {
@@ -54,11 +72,19 @@ impl PluginManager {
                     dir_context,
                     theme_cache,
                 });
-                match PluginThreadHandle::spawn(services) {
+                let limits = Limits {
+                    call: Duration::from_millis(editor_config.plugin_call_timeout_ms),
+                    load: Duration::from_millis(editor_config.plugin_load_timeout_ms),
+                    memory: editor_config
+                        .plugin_memory_limit_mb
+                        .saturating_mul(1024 * 1024),
+                };
+                match PluginThreadHandle::spawn(services, limits) {
                     Ok(handle) => {
                         return Self {
                             inner: Some(handle),
                             pending_injected_commands: Vec::new(),
+                            observed_hooks: Mutex::default(),
                         }
                     }
                     Err(e) => {
@@ -73,6 +99,7 @@ impl PluginManager {
             Self {
                 inner: None,
                 pending_injected_commands: Vec::new(),
+                observed_hooks: Mutex::default(),
             }
         }
 
@@ -81,12 +108,14 @@ impl PluginManager {
             let _ = command_registry; // Suppress unused warning
             let _ = dir_context; // Suppress unused warning
             let _ = theme_cache; // Suppress unused warning
+            let _ = editor_config; // Suppress unused warning
             if enable {
                 tracing::warn!("Plugins requested but compiled without plugin support");
             }
             Self {
                 _phantom: std::marker::PhantomData,
                 pending_injected_commands: Vec::new(),
+                observed_hooks: Mutex::default(),
             }
         }
     }
@@ -243,6 +272,14 @@ impl PluginManager {
 
     /// Run a hook (fire-and-forget).
     pub fn run_hook(&self, hook_name: &str, args: super::hooks::HookArgs) {
+        {
+            let mut observed = self.observed_hooks.lock().unwrap();
+            if observed.names.contains(hook_name) {
+                let params =
+                    super::hooks::hook_args_to_json(&args).unwrap_or(serde_json::Value::Null);
+                observed.calls.push((hook_name.to_string(), params));
+            }
+        }
         #[cfg(feature = "plugins")]
         {
             if let Some(ref manager) = self.inner {
@@ -255,6 +292,20 @@ impl PluginManager {
         }
     }
 
+    /// Replace the set of hooks whose calls are recorded for
+    /// [`Self::take_observed_hooks`]. Calls of hooks no longer observed
+    /// are dropped.
+    pub fn set_observed_hooks(&self, names: HashSet<String>) {
+        let mut observed = self.observed_hooks.lock().unwrap();
+        observed.calls.retain(|(name, _)| names.contains(name));
+        observed.names = names;
+    }
+
+    /// Take the observed hook calls, oldest first, as `(hook name, args)`.
+    pub fn take_observed_hooks(&self) -> Vec<(String, serde_json::Value)> {
+        std::mem::take(&mut self.observed_hooks.lock().unwrap().calls)
+    }
+
     /// Deliver a response to a pending async plugin operation.
     pub fn deliver_response(&self, response: super::api::PluginResponse) {
         #[cfg(feature = "plugins")]
@@ -332,6 +383,30 @@ impl PluginManager {
         None
     }
 
+    /// Package plugin sandboxes and capability grants shared with the
+    /// plugin runtime. The editor records the user's answers here.
+    #[cfg(feature = "plugins")]
+    pub fn permissions_handle(&self) -> Option<fresh_core::permissions::PermissionRegistry> {
+        self.inner.as_ref().map(|m| m.permissions_handle())
+    }
+
+    /// Permission registry accessor (no-op build).
+    #[cfg(not(feature = "plugins"))]
+    pub fn permissions_handle(&self) -> Option<fresh_core::permissions::PermissionRegistry> {
+        None
+    }
+
+    /// Per-plugin CPU time and hook latency, sorted by plugin name.
+    #[cfg(feature = "plugins")]
+    pub fn plugin_stats(
+        &self,
+    ) -> Vec<(String, fresh_plugin_runtime::backend::watchdog::PluginStats)> {
+        self.inner
+            .as_ref()
+            .map(|m| m.plugin_stats())
+            .unwrap_or_default()
+    }
+
     /// Execute a plugin action asynchronously.
     #[cfg(feature = "plugins")]
     pub fn execute_action_async(
@@ -0,0 +1,416 @@
+//! Problem matchers: find errors and warnings in build and test output.
+//!
+//! Each [`ProblemMatcherKind`] preset knows the output format of one tool
+//! family. Formats that split a problem over several lines (rustc's
+//! header and ` --> ` location, ESLint's file heading, pytest's `E`
+//! lines) are parsed with a little state carried from line to line.
+//! Paths are returned as printed; resolving them against the task's
+//! working directory is up to the caller.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::config::ProblemMatcherKind;
+
+/// How bad a problem is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProblemSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl ProblemSeverity {
+    fn parse(s: &str) -> Self {
+        match s {
+            "error" => Self::Error,
+            "warning" => Self::Warning,
+            _ => Self::Info,
+        }
+    }
+}
+
+/// One problem found in a task's output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// Path as the tool printed it (absolute or relative to its cwd)
+    pub file: String,
+    /// 1-indexed line
+    pub line: usize,
+    /// 1-indexed column; 1 when the tool only reports a line
+    pub column: usize,
+    pub severity: ProblemSeverity,
+    pub message: String,
+}
+
+static ANSI_ESCAPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]").unwrap());
+
+static RUSTC_HEADER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(error|warning)(?:\[(\w+)\])?: (.+)$").unwrap());
+static RUSTC_SUBHEADER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:note|help)(?:\[\w+\])?: ").unwrap());
+static RUSTC_LOCATION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*--> (.+):(\d+):(\d+)$").unwrap());
+static RUSTC_PANIC: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^thread '.*' panicked at (.+):(\d+):(\d+):?$").unwrap());
+
+static GCC: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(.+?):(\d+):(?:(\d+):)? (?:fatal )?(error|warning|note): (.+)$").unwrap()
+});
+
+static TSC: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(.+?)\((\d+),(\d+)\): (error|warning|message) (TS\d+): (.+)$").unwrap()
+});
+static TSC_PRETTY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(.+?):(\d+):(\d+) - (error|warning|message) (TS\d+): (.+)$").unwrap()
+});
+
+static ESLINT_ENTRY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s+(\d+):(\d+)\s+(error|warning)\s+(.+?)(?:\s{2,}(\S+))?$").unwrap()
+});
+
+static PYTEST_LOCATION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(.+\.py):(\d+): (.+)$").unwrap());
+static PYTEST_ERROR_LINE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^E\s+(.+)$").unwrap());
+
+/// Find the problems `kind` recognizes in `output`.
+pub fn parse_problems(kind: ProblemMatcherKind, output: &str) -> Vec<Problem> {
+    let output = ANSI_ESCAPE.replace_all(output, "");
+    let lines = output.lines().map(|l| l.trim_end_matches('\r'));
+    match kind {
+        ProblemMatcherKind::Rustc => parse_rustc(lines),
+        ProblemMatcherKind::Gcc => parse_gcc(lines),
+        ProblemMatcherKind::Tsc => parse_tsc(lines),
+        ProblemMatcherKind::Eslint => parse_eslint(lines),
+        ProblemMatcherKind::Pytest => parse_pytest(lines),
+    }
+}
+
+fn number(s: &str) -> usize {
+    s.parse().unwrap_or(1).max(1)
+}
+
+fn parse_rustc<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    // Header waiting for its ` --> ` line
+    let mut pending: Option<(ProblemSeverity, String)> = None;
+    // Panic location waiting for the message on the next line
+    let mut panic_at: Option<(String, usize, usize)> = None;
+    for line in lines {
+        if let Some((file, line_no, column)) = panic_at.take() {
+            problems.push(Problem {
+                file,
+                line: line_no,
+                column,
+                severity: ProblemSeverity::Error,
+                message: line.trim().to_string(),
+            });
+            continue;
+        }
+        if let Some(caps) = RUSTC_HEADER.captures(line) {
+            let message = match caps.get(2) {
+                Some(code) => format!("{} [{}]", &caps[3], code.as_str()),
+                None => caps[3].to_string(),
+            };
+            pending = Some((ProblemSeverity::parse(&caps[1]), message));
+        } else if RUSTC_SUBHEADER.is_match(line) {
+            pending = None;
+        } else if let Some(caps) = RUSTC_LOCATION.captures(line) {
+            if let Some((severity, message)) = pending.take() {
+                problems.push(Problem {
+                    file: caps[1].to_string(),
+                    line: number(&caps[2]),
+                    column: number(&caps[3]),
+                    severity,
+                    message,
+                });
+            }
+        } else if let Some(caps) = RUSTC_PANIC.captures(line) {
+            pending = None;
+            panic_at = Some((caps[1].to_string(), number(&caps[2]), number(&caps[3])));
+        }
+    }
+    problems
+}
+
+fn parse_gcc<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<Problem> {
+    lines
+        .filter_map(|line| GCC.captures(line))
+        .map(|caps| Problem {
+            file: caps[1].to_string(),
+            line: number(&caps[2]),
+            column: caps.get(3).map_or(1, |c| number(c.as_str())),
+            severity: ProblemSeverity::parse(&caps[4]),
+            message: caps[5].to_string(),
+        })
+        .collect()
+}
+
+fn parse_tsc<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<Problem> {
+    lines
+        .filter_map(|line| TSC.captures(line).or_else(|| TSC_PRETTY.captures(line)))
+        .map(|caps| Problem {
+            file: caps[1].to_string(),
+            line: number(&caps[2]),
+            column: number(&caps[3]),
+            severity: ProblemSeverity::parse(&caps[4]),
+            message: format!("{} [{}]", &caps[6], &caps[5]),
+        })
+        .collect()
+}
+
+fn parse_eslint<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut file: Option<String> = None;
+    for line in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(caps) = ESLINT_ENTRY.captures(line) {
+            let Some(file) = &file else {
+                continue;
+            };
+            let message = match caps.get(5) {
+                Some(rule) => format!("{} [{}]", &caps[4], rule.as_str()),
+                None => caps[4].to_string(),
+            };
+            problems.push(Problem {
+                file: file.clone(),
+                line: number(&caps[1]),
+                column: number(&caps[2]),
+                severity: ProblemSeverity::parse(&caps[3]),
+                message,
+            });
+        } else if !line.starts_with(char::is_whitespace) && !line.starts_with('✖') {
+            // Stylish prints each file's path on its own line before its problems
+            file = Some(line.to_string());
+        }
+    }
+    problems
+}
+
+fn parse_pytest<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    // First `E` line of the current failure, the most useful message
+    let mut error_line: Option<String> = None;
+    for line in lines {
+        if let Some(caps) = PYTEST_ERROR_LINE.captures(line) {
+            error_line.get_or_insert_with(|| caps[1].trim().to_string());
+        } else if let Some(caps) = PYTEST_LOCATION.captures(line) {
+            // Intermediate frames read `file.py:12: in helper`
+            if caps[3].starts_with("in ") {
+                continue;
+            }
+            problems.push(Problem {
+                file: caps[1].to_string(),
+                line: number(&caps[2]),
+                column: 1,
+                severity: ProblemSeverity::Error,
+                message: error_line.take().unwrap_or_else(|| caps[3].to_string()),
+            });
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem(
+        file: &str,
+        line: usize,
+        column: usize,
+        severity: ProblemSeverity,
+        message: &str,
+    ) -> Problem {
+        Problem {
+            file: file.to_string(),
+            line,
+            column,
+            severity,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rustc() {
+        let output = "\
+   Compiling demo v0.1.0 (/work/demo)
+warning: unused variable: `x`
+ --> src/main.rs:2:9
+  |
+2 |     let x = 1;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_x`
+  |
+  = note: `#[warn(unused_variables)]` on by default
+
+error[E0308]: mismatched types
+  --> src/lib.rs:10:18
+   |
+note: function defined here
+  --> src/lib.rs:3:4
+error: could not compile `demo` (bin \"demo\") due to 1 previous error
+thread 'tests::it_works' panicked at src/lib.rs:20:9:
+assertion `left == right` failed
+";
+        assert_eq!(
+            parse_problems(ProblemMatcherKind::Rustc, output),
+            vec![
+                problem(
+                    "src/main.rs",
+                    2,
+                    9,
+                    ProblemSeverity::Warning,
+                    "unused variable: `x`"
+                ),
+                problem(
+                    "src/lib.rs",
+                    10,
+                    18,
+                    ProblemSeverity::Error,
+                    "mismatched types [E0308]"
+                ),
+                problem(
+                    "src/lib.rs",
+                    20,
+                    9,
+                    ProblemSeverity::Error,
+                    "assertion `left == right` failed"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gcc_and_clang() {
+        let output = "\
+main.c: In function 'main':
+main.c:4:5: error: 'y' undeclared (first use in this function)
+\x1b[1mutil.c:12:1: \x1b[35mwarning:\x1b[0m control reaches end of non-void function
+ld.c:7: fatal error: missing.h: No such file or directory
+";
+        assert_eq!(
+            parse_problems(ProblemMatcherKind::Gcc, output),
+            vec![
+                problem(
+                    "main.c",
+                    4,
+                    5,
+                    ProblemSeverity::Error,
+                    "'y' undeclared (first use in this function)"
+                ),
+                problem(
+                    "util.c",
+                    12,
+                    1,
+                    ProblemSeverity::Warning,
+                    "control reaches end of non-void function"
+                ),
+                problem(
+                    "ld.c",
+                    7,
+                    1,
+                    ProblemSeverity::Error,
+                    "missing.h: No such file or directory"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tsc_plain_and_pretty() {
+        let output = "\
+src/app.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.
+src/util.ts:8:1 - error TS1005: ';' expected.
+";
+        assert_eq!(
+            parse_problems(ProblemMatcherKind::Tsc, output),
+            vec![
+                problem(
+                    "src/app.ts",
+                    3,
+                    7,
+                    ProblemSeverity::Error,
+                    "Type 'string' is not assignable to type 'number'. [TS2322]"
+                ),
+                problem(
+                    "src/util.ts",
+                    8,
+                    1,
+                    ProblemSeverity::Error,
+                    "';' expected. [TS1005]"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eslint_stylish() {
+        let output = "
+/work/src/index.js
+  1:10  error    'foo' is defined but never used  no-unused-vars
+  2:1   warning  Unexpected console statement     no-console
+
+✖ 2 problems (1 error, 1 warning)
+";
+        assert_eq!(
+            parse_problems(ProblemMatcherKind::Eslint, output),
+            vec![
+                problem(
+                    "/work/src/index.js",
+                    1,
+                    10,
+                    ProblemSeverity::Error,
+                    "'foo' is defined but never used [no-unused-vars]"
+                ),
+                problem(
+                    "/work/src/index.js",
+                    2,
+                    1,
+                    ProblemSeverity::Warning,
+                    "Unexpected console statement [no-console]"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pytest() {
+        let output = "\
+___________________________ test_add ___________________________
+
+    def test_add():
+>       assert helper(1) == 3
+E       assert 2 == 3
+E        +  where 2 = helper(1)
+
+tests/test_math.py:5: AssertionError
+___________________________ test_div ___________________________
+tests/test_math.py:9: in test_div
+    divide(1, 0)
+src/math.py:2: ZeroDivisionError
+";
+        assert_eq!(
+            parse_problems(ProblemMatcherKind::Pytest, output),
+            vec![
+                problem(
+                    "tests/test_math.py",
+                    5,
+                    1,
+                    ProblemSeverity::Error,
+                    "assert 2 == 3"
+                ),
+                problem(
+                    "src/math.py",
+                    2,
+                    1,
+                    ProblemSeverity::Error,
+                    "ZeroDivisionError"
+                ),
+            ]
+        );
+    }
+}
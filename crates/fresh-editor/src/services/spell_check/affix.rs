@@ -0,0 +1,339 @@
+//! Hunspell affix files (`.aff`).
+//!
+//! Only what single-word checking and suggestions need is read: the flag
+//! format and aliases, prefix and suffix rules with their conditions, the
+//! `TRY` and `REP` suggestion hints and the flags marking words as
+//! forbidden, affix-only, compound-only or not to be suggested. Compound
+//! rules, conversion tables and morphology are ignored.
+
+use std::collections::HashMap;
+
+/// A flag, whatever its written form.
+pub type Flag = u32;
+
+/// How flags are written (`FLAG`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum FlagMode {
+    /// One character per flag
+    #[default]
+    Char,
+    /// Two characters per flag
+    Long,
+    /// Comma-separated decimal numbers
+    Num,
+}
+
+impl FlagMode {
+    fn parse(self, text: &str) -> Vec<Flag> {
+        match self {
+            Self::Char => text.chars().map(Flag::from).collect(),
+            Self::Long => {
+                let chars: Vec<char> = text.chars().collect();
+                chars
+                    .chunks(2)
+                    .map(|pair| pair.iter().fold(0, |flag, &c| (flag << 16) | Flag::from(c)))
+                    .collect()
+            }
+            Self::Num => text
+                .split(',')
+                .filter_map(|n| n.trim().parse().ok())
+                .collect(),
+        }
+    }
+}
+
+/// One character of an affix condition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CondChar {
+    /// `.`
+    Any,
+    Is(char),
+    /// `[abc]`, or `[^abc]` when negated
+    OneOf {
+        chars: Vec<char>,
+        negated: bool,
+    },
+}
+
+impl CondChar {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Is(expected) => c == *expected,
+            Self::OneOf { chars, negated } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+/// What the stem must start (prefixes) or end (suffixes) with for a rule
+/// to apply.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Condition(Vec<CondChar>);
+
+impl Condition {
+    fn parse(text: &str) -> Self {
+        let mut parts = Vec::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            parts.push(match c {
+                '.' => CondChar::Any,
+                '[' => {
+                    let mut set: Vec<char> = chars.by_ref().take_while(|&c| c != ']').collect();
+                    let negated = set.first() == Some(&'^');
+                    if negated {
+                        set.remove(0);
+                    }
+                    CondChar::OneOf {
+                        chars: set,
+                        negated,
+                    }
+                }
+                c => CondChar::Is(c),
+            });
+        }
+        // A lone `.` is the usual way of writing "no condition"
+        if parts == [CondChar::Any] {
+            parts.clear();
+        }
+        Self(parts)
+    }
+
+    fn matches_start(&self, stem: &str) -> bool {
+        let mut chars = stem.chars();
+        self.0
+            .iter()
+            .all(|part| chars.next().is_some_and(|c| part.matches(c)))
+    }
+
+    fn matches_end(&self, stem: &str) -> bool {
+        let mut chars = stem.chars().rev();
+        self.0
+            .iter()
+            .rev()
+            .all(|part| chars.next().is_some_and(|c| part.matches(c)))
+    }
+}
+
+/// A prefix or suffix rule: remove `strip` from the stem, then add `add`.
+#[derive(Debug, Clone)]
+pub struct AffixRule {
+    pub flag: Flag,
+    /// Whether the rule combines with rules of the other kind
+    pub cross: bool,
+    pub strip: String,
+    pub add: String,
+    pub condition: Condition,
+    /// Flags of rules that may be applied on top of this one
+    pub continuation: Vec<Flag>,
+}
+
+impl AffixRule {
+    /// The stem a word with this prefix was made from.
+    pub fn prefix_stem(&self, word: &str) -> Option<String> {
+        let rest = word.strip_prefix(self.add.as_str())?;
+        if rest.is_empty() {
+            return None;
+        }
+        let stem = format!("{}{rest}", self.strip);
+        self.condition.matches_start(&stem).then_some(stem)
+    }
+
+    /// The stem a word with this suffix was made from.
+    pub fn suffix_stem(&self, word: &str) -> Option<String> {
+        let rest = word.strip_suffix(self.add.as_str())?;
+        if rest.is_empty() {
+            return None;
+        }
+        let stem = format!("{rest}{}", self.strip);
+        self.condition.matches_end(&stem).then_some(stem)
+    }
+}
+
+/// The parts of an affix file used for checking and suggestions.
+#[derive(Debug, Clone, Default)]
+pub struct AffixFile {
+    flag_mode: FlagMode,
+    /// Flag sets that `AF` numbers stand for
+    aliases: Vec<Vec<Flag>>,
+    pub prefixes: Vec<AffixRule>,
+    pub suffixes: Vec<AffixRule>,
+    /// Characters to try when suggesting, most frequent first
+    pub try_chars: Vec<char>,
+    /// Common misspellings, as (wrong, right) pairs
+    pub replacements: Vec<(String, String)>,
+    pub forbidden: Option<Flag>,
+    pub need_affix: Option<Flag>,
+    pub only_in_compound: Option<Flag>,
+    pub no_suggest: Option<Flag>,
+}
+
+impl AffixFile {
+    /// Parse the text of an affix file.
+    pub fn parse(text: &str) -> Self {
+        let mut aff = Self::default();
+        // Whether each affix class combines with the other kind
+        let mut cross: HashMap<(bool, Flag), bool> = HashMap::new();
+        let mut in_alias_table = false;
+        for line in text.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let Some(&keyword) = fields.first() else {
+                continue;
+            };
+            let arg = fields.get(1).copied().unwrap_or_default();
+            match keyword {
+                "FLAG" => {
+                    aff.flag_mode = match arg {
+                        "long" => FlagMode::Long,
+                        "num" => FlagMode::Num,
+                        _ => FlagMode::Char,
+                    }
+                }
+                // `AF <count>` heads the table; entries follow in order
+                "AF" if in_alias_table => {
+                    let flags = aff.flag_mode.parse(arg);
+                    aff.aliases.push(flags);
+                }
+                "AF" => in_alias_table = true,
+                "TRY" => aff.try_chars = arg.chars().collect(),
+                "REP" if fields.len() >= 3 => aff
+                    .replacements
+                    .push((arg.replace('_', " "), fields[2].replace('_', " "))),
+                "FORBIDDENWORD" => aff.forbidden = aff.single_flag(arg),
+                "NEEDAFFIX" | "PSEUDOROOT" => aff.need_affix = aff.single_flag(arg),
+                "ONLYINCOMPOUND" => aff.only_in_compound = aff.single_flag(arg),
+                "NOSUGGEST" => aff.no_suggest = aff.single_flag(arg),
+                "PFX" | "SFX" if fields.len() >= 4 => {
+                    let is_prefix = keyword == "PFX";
+                    let Some(flag) = aff.single_flag(arg) else {
+                        continue;
+                    };
+                    let is_header = fields.len() == 4
+                        && matches!(fields[2], "Y" | "N")
+                        && fields[3].parse::<usize>().is_ok();
+                    if is_header {
+                        cross.insert((is_prefix, flag), fields[2] == "Y");
+                        continue;
+                    }
+                    let (add, continuation) = match fields[3].split_once('/') {
+                        Some((add, flags)) => (add, aff.flags(flags)),
+                        None => (fields[3], Vec::new()),
+                    };
+                    let rule = AffixRule {
+                        flag,
+                        cross: cross.get(&(is_prefix, flag)).copied().unwrap_or(false),
+                        strip: zero_is_empty(fields[2]),
+                        add: zero_is_empty(add),
+                        condition: Condition::parse(fields.get(4).copied().unwrap_or(".")),
+                        continuation,
+                    };
+                    if is_prefix {
+                        aff.prefixes.push(rule);
+                    } else {
+                        aff.suffixes.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+        aff
+    }
+
+    /// Flags written in `text`, resolving `AF` aliases.
+    pub fn flags(&self, text: &str) -> Vec<Flag> {
+        if !self.aliases.is_empty() {
+            if let Ok(index) = text.parse::<usize>() {
+                return index
+                    .checked_sub(1)
+                    .and_then(|i| self.aliases.get(i))
+                    .cloned()
+                    .unwrap_or_default();
+            }
+        }
+        self.flag_mode.parse(text)
+    }
+
+    fn single_flag(&self, text: &str) -> Option<Flag> {
+        self.flag_mode.parse(text).first().copied()
+    }
+}
+
+/// `0` stands for the empty string in strip and add fields.
+fn zero_is_empty(field: &str) -> String {
+    if field == "0" {
+        String::new()
+    } else {
+        field.to_string()
+    }
+}
+
+/// The encoding named by the `SET` line of an affix file, if any.
+pub fn declared_encoding(aff: &[u8]) -> Option<String> {
+    aff.split(|&b| b == b'\n').find_map(|line| {
+        let line = std::str::from_utf8(line).ok()?.trim();
+        let encoding = line.strip_prefix("SET")?.trim();
+        (!encoding.is_empty()).then(|| encoding.to_ascii_uppercase())
+    })
+}
+
+/// Decode a dictionary file written in `encoding`. Only UTF-8 and the
+/// Latin-1 family are decoded exactly; others are read as UTF-8.
+pub fn decode(bytes: &[u8], encoding: Option<&str>) -> String {
+    match encoding {
+        Some("ISO8859-1" | "ISO-8859-1" | "ISO8859-15" | "ISO-8859-15") => {
+            bytes.iter().map(|&b| char::from(b)).collect()
+        }
+        Some(other) if other != "UTF-8" => {
+            tracing::warn!("Unsupported dictionary encoding {other}, reading as UTF-8");
+            String::from_utf8_lossy(bytes).into_owned()
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_and_conditions() {
+        let aff = AffixFile::parse(
+            "SET UTF-8\nTRY esiar\nREP 1\nREP alot a_lot\n\
+             SFX D Y 2\nSFX D 0 d e\nSFX D y ied [^aeiou]y\n\
+             PFX U N 1\nPFX U 0 un/D .\n",
+        );
+        assert_eq!(aff.try_chars, vec!['e', 's', 'i', 'a', 'r']);
+        assert_eq!(aff.replacements, vec![("alot".into(), "a lot".into())]);
+        assert_eq!(aff.suffixes.len(), 2);
+        assert!(aff.suffixes[0].cross);
+        assert!(!aff.prefixes[0].cross);
+        assert_eq!(aff.prefixes[0].continuation, vec![Flag::from('D')]);
+
+        let ied = &aff.suffixes[1];
+        assert_eq!(ied.suffix_stem("carried").as_deref(), Some("carry"));
+        // `[^aeiou]y`: "played" does not come from "playy"
+        assert_eq!(ied.suffix_stem("played"), None);
+        assert_eq!(aff.prefixes[0].prefix_stem("undo").as_deref(), Some("do"));
+        assert_eq!(aff.prefixes[0].prefix_stem("un"), None);
+    }
+
+    #[test]
+    fn long_numeric_and_aliased_flags() {
+        let long = AffixFile::parse("FLAG long\nNOSUGGEST Aa\n");
+        assert_eq!(long.flags("AaBb").len(), 2);
+        assert_eq!(long.no_suggest, long.flags("Aa").first().copied());
+
+        let num = AffixFile::parse("FLAG num\n");
+        assert_eq!(num.flags("12,7"), vec![12, 7]);
+
+        let aliased = AffixFile::parse("AF 2\nAF AB\nAF C\n");
+        assert_eq!(aliased.flags("2"), vec![Flag::from('C')]);
+    }
+
+    #[test]
+    fn latin1_files_are_decoded() {
+        let aff = b"SET ISO8859-1\n";
+        let encoding = declared_encoding(aff);
+        assert_eq!(encoding.as_deref(), Some("ISO8859-1"));
+        assert_eq!(decode(b"caf\xe9", encoding.as_deref()), "café");
+    }
+}
@@ -0,0 +1,392 @@
+//! A Hunspell dictionary: the word list (`.dic`) and its affix rules.
+//!
+//! Words are checked by undoing at most one prefix and one suffix (or two
+//! suffixes, when the first allows the second) and looking up the stem, so
+//! the list is never expanded in memory. Suggestions come from the `REP`
+//! table, single edits using the `TRY` characters, splitting the word in
+//! two and, failing those, the stems within two edits of the word.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::affix::{declared_encoding, decode, AffixFile, Flag};
+
+/// Suggestions offered for one word, at most.
+pub const MAX_SUGGESTIONS: usize = 8;
+
+/// A loaded Hunspell dictionary.
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    affixes: AffixFile,
+    /// Stems with the flags of all their entries
+    words: HashMap<String, Vec<Flag>>,
+}
+
+impl Dictionary {
+    /// Read `<stem>.aff` and `<stem>.dic`.
+    pub fn load(aff_path: &Path, dic_path: &Path) -> std::io::Result<Self> {
+        let aff = std::fs::read(aff_path)?;
+        let dic = std::fs::read(dic_path)?;
+        let encoding = declared_encoding(&aff);
+        Ok(Self::parse(
+            &decode(&aff, encoding.as_deref()),
+            &decode(&dic, encoding.as_deref()),
+        ))
+    }
+
+    /// Build a dictionary from the text of its affix and word files.
+    pub fn parse(aff: &str, dic: &str) -> Self {
+        let affixes = AffixFile::parse(aff);
+        let mut words: HashMap<String, Vec<Flag>> = HashMap::new();
+        // The first line is the approximate word count
+        for line in dic.lines().skip(1) {
+            // Lines starting with a tab are comments; morphology follows
+            // the word after whitespace
+            let Some(entry) = line.split(['\t', ' ']).next().filter(|e| !e.is_empty()) else {
+                continue;
+            };
+            let (word, flags) = match split_flags(entry) {
+                Some((word, flags)) => (word, affixes.flags(flags)),
+                None => (entry.replace("\\/", "/"), Vec::new()),
+            };
+            words.entry(word).or_default().extend(flags);
+        }
+        Self { affixes, words }
+    }
+
+    /// Whether `word` is spelled correctly. A capitalized or all-caps
+    /// word is also accepted in the case it has in the dictionary.
+    pub fn check(&self, word: &str) -> bool {
+        if self.check_exact(word) {
+            return true;
+        }
+        let lower = word.to_lowercase();
+        if lower == word {
+            return false;
+        }
+        let mut chars = word.chars();
+        let first_upper = chars.next().is_some_and(char::is_uppercase);
+        let rest: String = chars.collect();
+        if first_upper && rest.to_lowercase() == rest {
+            return self.check_exact(&lower);
+        }
+        if word.to_uppercase() == word {
+            return self.check_exact(&lower) || self.check_exact(&capitalize(&lower));
+        }
+        false
+    }
+
+    /// Whether `word` is known in exactly this case.
+    fn check_exact(&self, word: &str) -> bool {
+        if let Some(flags) = self.words.get(word) {
+            if self.has(flags, self.affixes.forbidden) {
+                return false;
+            }
+            if !self.has(flags, self.affixes.need_affix)
+                && !self.has(flags, self.affixes.only_in_compound)
+            {
+                return true;
+            }
+        }
+        self.affixed(word)
+    }
+
+    fn has(&self, flags: &[Flag], flag: Option<Flag>) -> bool {
+        flag.is_some_and(|flag| flags.contains(&flag))
+    }
+
+    /// Whether `stem` is a word carrying all of `needed`.
+    fn stem_allows(&self, stem: &str, needed: &[Flag]) -> bool {
+        self.words.get(stem).is_some_and(|flags| {
+            needed.iter().all(|flag| flags.contains(flag))
+                && !self.has(flags, self.affixes.forbidden)
+                && !self.has(flags, self.affixes.only_in_compound)
+        })
+    }
+
+    /// Whether `word` is a stem with affixes it allows.
+    fn affixed(&self, word: &str) -> bool {
+        let aff = &self.affixes;
+        for suffix in &aff.suffixes {
+            let Some(stem) = suffix.suffix_stem(word) else {
+                continue;
+            };
+            if self.stem_allows(&stem, &[suffix.flag]) {
+                return true;
+            }
+            // A second suffix allowed by the first
+            for inner in aff
+                .suffixes
+                .iter()
+                .filter(|s| s.continuation.contains(&suffix.flag))
+            {
+                if let Some(root) = inner.suffix_stem(&stem) {
+                    if self.stem_allows(&root, &[inner.flag]) {
+                        return true;
+                    }
+                }
+            }
+            if !suffix.cross {
+                continue;
+            }
+            for prefix in aff.prefixes.iter().filter(|p| p.cross) {
+                if let Some(root) = prefix.prefix_stem(&stem) {
+                    if self.stem_allows(&root, &[prefix.flag, suffix.flag]) {
+                        return true;
+                    }
+                }
+            }
+        }
+        aff.prefixes.iter().any(|prefix| {
+            prefix
+                .prefix_stem(word)
+                .is_some_and(|stem| self.stem_allows(&stem, &[prefix.flag]))
+        })
+    }
+
+    /// Likely intended spellings of `word`, best first.
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let mut candidates: Vec<String> = Vec::new();
+        for (wrong, right) in &self.affixes.replacements {
+            for (at, _) in word.match_indices(wrong.as_str()) {
+                candidates.push(format!(
+                    "{}{right}{}",
+                    &word[..at],
+                    &word[at + wrong.len()..]
+                ));
+            }
+        }
+        candidates.extend(self.single_edits(word));
+        // Two words run together
+        for (at, _) in word.char_indices().skip(1) {
+            let (left, right) = word.split_at(at);
+            if left.chars().count() > 1 && self.check(left) && self.check(right) {
+                candidates.push(format!("{left} {right}"));
+            }
+        }
+
+        let mut found: Vec<String> = Vec::new();
+        let mut keep = |candidates: Vec<String>| {
+            for candidate in candidates {
+                if candidate != word && !found.contains(&candidate) && self.suggestible(&candidate)
+                {
+                    found.push(candidate);
+                }
+            }
+            found.is_empty()
+        };
+        if keep(candidates) {
+            keep(self.near_stems(word));
+        }
+        found.truncate(MAX_SUGGESTIONS);
+        found
+    }
+
+    /// Whether `word` may be offered as a suggestion.
+    fn suggestible(&self, word: &str) -> bool {
+        word.split(' ').all(|part| self.check(part))
+            && !self
+                .words
+                .get(word)
+                .is_some_and(|flags| self.has(flags, self.affixes.no_suggest))
+    }
+
+    /// Words one deletion, swap, replacement or insertion away from `word`.
+    fn single_edits(&self, word: &str) -> Vec<String> {
+        let chars: Vec<char> = word.chars().collect();
+        let try_chars: Vec<char> = if self.affixes.try_chars.is_empty() {
+            ('a'..='z').collect()
+        } else {
+            self.affixes.try_chars.clone()
+        };
+        let join = |chars: &[char]| chars.iter().collect::<String>();
+        let mut edits = Vec::new();
+        for i in 0..chars.len() {
+            if i + 1 < chars.len() {
+                let mut swapped = chars.clone();
+                swapped.swap(i, i + 1);
+                edits.push(join(&swapped));
+            }
+            let mut removed = chars.clone();
+            removed.remove(i);
+            edits.push(join(&removed));
+            for &c in &try_chars {
+                if c != chars[i] {
+                    let mut replaced = chars.clone();
+                    replaced[i] = c;
+                    edits.push(join(&replaced));
+                }
+            }
+        }
+        for i in 0..=chars.len() {
+            for &c in &try_chars {
+                let mut inserted = chars.clone();
+                inserted.insert(i, c);
+                edits.push(join(&inserted));
+            }
+        }
+        edits.retain(|edit| self.check(edit));
+        edits
+    }
+
+    /// Stems within two edits of `word`, closest first.
+    fn near_stems(&self, word: &str) -> Vec<String> {
+        let lower = word.to_lowercase();
+        let len = lower.chars().count();
+        let mut near: Vec<(usize, &String)> = self
+            .words
+            .keys()
+            .filter(|stem| stem.chars().count().abs_diff(len) <= 2)
+            .filter_map(|stem| {
+                let distance = edit_distance(&lower, &stem.to_lowercase());
+                (distance <= 2).then_some((distance, stem))
+            })
+            .collect();
+        near.sort();
+        near.into_iter()
+            .map(|(_, stem)| stem.clone())
+            .take(MAX_SUGGESTIONS)
+            .collect()
+    }
+}
+
+/// Split a `.dic` entry at the slash before its flags; `\/` is a slash
+/// within the word.
+fn split_flags(entry: &str) -> Option<(String, &str)> {
+    let mut escaped = false;
+    for (i, c) in entry.char_indices() {
+        match c {
+            '\\' => escaped = !escaped,
+            '/' if !escaped && i > 0 => {
+                return Some((entry[..i].replace("\\/", "/"), &entry[i + 1..]));
+            }
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// `word` with its first letter in upper case.
+pub fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Edits (insertions, deletions, replacements and swaps of neighbours)
+/// turning `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // Rows for the two previous prefixes of `a` and the current one
+    let mut before: Vec<usize> = Vec::new();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut current = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        before = std::mem::replace(&mut previous, current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "\
+SET UTF-8
+TRY esianrtolcdugmphbyfvkwzESIANRTOLCDUGMPHBYFVKWZ'
+NOSUGGEST !
+REP 1
+REP alot a_lot
+PFX U Y 1
+PFX U 0 un .
+SFX S Y 3
+SFX S y ies [^aeiou]y
+SFX S 0 s [^sxyz]
+SFX S 0 es [sxz]
+SFX D Y 3
+SFX D 0 d e
+SFX D y ied [^aeiou]y
+SFX D 0 ed [^ey]
+SFX N Y 2
+SFX N y iness/S [^aeiou]y
+SFX N 0 ness/S [^y]
+";
+
+    const DIC: &str = "\
+8
+a
+lot
+kind/UN
+carry/SD
+happy/N
+Paris
+colour
+damn/!
+";
+
+    fn dictionary() -> Dictionary {
+        Dictionary::parse(AFF, DIC)
+    }
+
+    #[test]
+    fn words_and_affixed_forms() {
+        let dict = dictionary();
+        for word in ["kind", "unkind", "carries", "carried", "happiness", "Paris"] {
+            assert!(dict.check(word), "{word} should be known");
+        }
+        // `kindnesses` takes `S` on top of `N`; `unkindness` crosses `U`
+        // with `N`
+        assert!(dict.check("kindnesses"));
+        assert!(dict.check("unkindness"));
+        for word in ["kinds", "carrys", "uncarry", "paris", "colur"] {
+            assert!(!dict.check(word), "{word} should be unknown");
+        }
+    }
+
+    #[test]
+    fn case_of_the_first_letter_and_shouting() {
+        let dict = dictionary();
+        assert!(dict.check("Carried"));
+        assert!(dict.check("CARRIED"));
+        assert!(dict.check("PARIS"));
+        assert!(!dict.check("cArried"));
+    }
+
+    #[test]
+    fn suggestions() {
+        let dict = dictionary();
+        assert_eq!(dict.suggest("colur"), vec!["colour"]);
+        assert_eq!(
+            dict.suggest("carreid").first().map(String::as_str),
+            Some("carried")
+        );
+        assert!(dict.suggest("alot").contains(&"a lot".to_string()));
+        // Not offered, though known
+        assert!(dict.check("damn"));
+        assert!(!dict.suggest("damm").contains(&"damn".to_string()));
+        // Nothing within one edit: the closest stems
+        assert_eq!(
+            dict.suggest("culor").first().map(String::as_str),
+            Some("colour")
+        );
+    }
+
+    #[test]
+    fn distance_counts_swaps_once() {
+        assert_eq!(edit_distance("colour", "colour"), 0);
+        assert_eq!(edit_distance("coluor", "colour"), 1);
+        assert_eq!(edit_distance("colr", "colour"), 2);
+    }
+}
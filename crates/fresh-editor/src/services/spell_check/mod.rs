@@ -0,0 +1,268 @@
+//! Spell checking of prose: comments and strings in code, and whole
+//! Markdown, plain text and commit message buffers.
+//!
+//! Dictionaries are Hunspell `.aff`/`.dic` pairs read from disk, looked up
+//! by language (e.g. `en_US`) in the user's `dictionaries` config
+//! directory and then the usual system locations. Nothing is downloaded.
+//! Words added by the user go to a per-workspace word list.
+//!
+//! Which parts of a buffer are prose, and keeping the underlines of the
+//! visible range up to date, is up to the editor (`app/spell_check.rs`).
+
+pub mod affix;
+pub mod dictionary;
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+pub use dictionary::Dictionary;
+
+/// Languages whose buffers are prose throughout, apart from code spans.
+pub const PROSE_LANGUAGES: &[&str] = &["markdown", "text", "git-commit"];
+
+/// The per-workspace word list, relative to the workspace root.
+pub const WORD_LIST_PATH: &str = ".fresh/dictionary.txt";
+
+/// System directories holding Hunspell dictionaries.
+const SYSTEM_DICTIONARY_DIRS: &[&str] = &[
+    "/usr/share/hunspell",
+    "/usr/share/myspell",
+    "/usr/share/myspell/dicts",
+    "/usr/local/share/hunspell",
+    "/opt/homebrew/share/hunspell",
+    "/Library/Spelling",
+];
+
+/// Whether buffers in `language` are checked throughout.
+pub fn is_prose_language(language: &str) -> bool {
+    PROSE_LANGUAGES.contains(&language)
+}
+
+/// Directories searched for dictionaries, in order: `<config>/dictionaries`,
+/// `~/Library/Spelling`, then the system directories.
+pub fn dictionary_dirs(config_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![config_dir.join("dictionaries")];
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join("Library").join("Spelling"));
+    }
+    dirs.extend(SYSTEM_DICTIONARY_DIRS.iter().map(PathBuf::from));
+    dirs
+}
+
+/// The `.aff` and `.dic` files of `language` in the first of `dirs`
+/// having both. `en-US` and `en_US` name the same dictionary.
+pub fn find_dictionary(language: &str, dirs: &[PathBuf]) -> Option<(PathBuf, PathBuf)> {
+    let names = [language.replace('-', "_"), language.replace('_', "-")];
+    dirs.iter().find_map(|dir| {
+        names.iter().find_map(|name| {
+            let aff = dir.join(format!("{name}.aff"));
+            let dic = dir.join(format!("{name}.dic"));
+            (aff.is_file() && dic.is_file()).then_some((aff, dic))
+        })
+    })
+}
+
+/// Words of a word list file: one per line, `#` starting a comment.
+pub fn parse_word_list(text: &str) -> HashSet<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// A dictionary with the workspace's own words, remembering its verdicts.
+#[derive(Debug)]
+pub struct SpellChecker {
+    dictionary: Dictionary,
+    workspace_words: HashSet<String>,
+    verdicts: HashMap<String, bool>,
+}
+
+impl SpellChecker {
+    pub fn new(dictionary: Dictionary, workspace_words: HashSet<String>) -> Self {
+        Self {
+            dictionary,
+            workspace_words,
+            verdicts: HashMap::new(),
+        }
+    }
+
+    /// Whether `word` is neither in the dictionary nor the workspace's
+    /// words. Typographic apostrophes count as `'`.
+    pub fn is_misspelled(&mut self, word: &str) -> bool {
+        if let Some(&verdict) = self.verdicts.get(word) {
+            return verdict;
+        }
+        let normalized = word.replace('’', "'");
+        let known = self.workspace_words.contains(&normalized)
+            || self.workspace_words.contains(&normalized.to_lowercase())
+            || self.dictionary.check(&normalized);
+        self.verdicts.insert(word.to_string(), !known);
+        !known
+    }
+
+    /// Replacements for `word`, in its case.
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let normalized = word.replace('’', "'");
+        let mut chars = normalized.chars();
+        let capitalized = chars.next().is_some_and(char::is_uppercase);
+        let shouted = capitalized && normalized.to_uppercase() == normalized;
+        let lower = normalized.to_lowercase();
+        let mut suggestions = self.dictionary.suggest(&normalized);
+        if suggestions.is_empty() && lower != normalized {
+            suggestions = self.dictionary.suggest(&lower);
+        }
+        let mut seen = HashSet::new();
+        suggestions
+            .into_iter()
+            .map(|s| {
+                if shouted {
+                    s.to_uppercase()
+                } else if capitalized {
+                    dictionary::capitalize(&s)
+                } else {
+                    s
+                }
+            })
+            .filter(|s| seen.insert(s.clone()))
+            .collect()
+    }
+
+    /// Accept `word` from now on.
+    pub fn add_workspace_word(&mut self, word: &str) {
+        self.workspace_words.insert(word.replace('’', "'"));
+        self.verdicts.clear();
+    }
+}
+
+/// Byte ranges of the words in `text` worth checking.
+///
+/// Words are runs of letters, with apostrophes between letters. Left out
+/// are words of one letter, words with inner capitals (identifiers,
+/// acronyms) or underscores, words inside backticks, and everything in
+/// chunks of non-space text that look like code, paths, URLs, e-mail
+/// addresses or numbers.
+pub fn prose_words(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        // Inline code does not run past the end of a line
+        let mut in_code = false;
+        for (start, chunk) in chunks(line) {
+            let chunk_start = offset + start;
+            if looks_like_code(chunk) {
+                in_code ^= chunk.matches('`').count() % 2 == 1;
+                continue;
+            }
+            let mut word_start: Option<usize> = None;
+            let mut chars = chunk.char_indices().peekable();
+            while let Some((i, c)) = chars.next() {
+                let next = chars.peek().map(|&(_, next)| next);
+                let inner_apostrophe = matches!(c, '\'' | '’')
+                    && word_start.is_some()
+                    && next.is_some_and(char::is_alphabetic);
+                if c.is_alphabetic() || c == '_' || inner_apostrophe {
+                    word_start.get_or_insert(i);
+                    continue;
+                }
+                if let Some(begin) = word_start.take() {
+                    if !in_code {
+                        push_word(&mut words, chunk, begin..i, chunk_start);
+                    }
+                }
+                if c == '`' {
+                    in_code = !in_code;
+                }
+            }
+            if let Some(begin) = word_start {
+                if !in_code {
+                    push_word(&mut words, chunk, begin..chunk.len(), chunk_start);
+                }
+            }
+        }
+        offset += line.len();
+    }
+    words
+}
+
+/// Whitespace-separated chunks of `line`, with their offsets.
+fn chunks(line: &str) -> Vec<(usize, &str)> {
+    let mut chunks = Vec::new();
+    let mut start = None;
+    for (i, c) in line.char_indices() {
+        if !c.is_whitespace() {
+            start.get_or_insert(i);
+        } else if let Some(begin) = start.take() {
+            chunks.push((begin, &line[begin..i]));
+        }
+    }
+    if let Some(begin) = start {
+        chunks.push((begin, &line[begin..]));
+    }
+    chunks
+}
+
+/// Whether a chunk of non-space text is code, a path, a URL, an e-mail
+/// address or has digits in it, rather than words.
+fn looks_like_code(chunk: &str) -> bool {
+    chunk.contains("://")
+        || chunk.contains("::")
+        || chunk.contains(['@', '/', '\\'])
+        || chunk.contains(|c: char| c.is_ascii_digit())
+        // `file.rs`, `self.field`, `e.g.`
+        || chunk
+            .split('.')
+            .skip(1)
+            .any(|after| after.starts_with(char::is_alphanumeric))
+}
+
+fn push_word(words: &mut Vec<Range<usize>>, chunk: &str, range: Range<usize>, chunk_start: usize) {
+    let word = &chunk[range.clone()];
+    let mut chars = word.chars();
+    let long_enough = chars.next().is_some() && chars.next().is_some();
+    let inner_capital = word.chars().skip(1).any(char::is_uppercase);
+    if long_enough && !inner_capital && !word.contains('_') {
+        words.push(chunk_start + range.start..chunk_start + range.end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<&str> {
+        prose_words(text).into_iter().map(|r| &text[r]).collect()
+    }
+
+    #[test]
+    fn prose_words_skip_code_looking_text() {
+        assert_eq!(
+            words("Don’t recieve the `raw_value` here, see https://example.com"),
+            vec!["Don’t", "recieve", "the", "here", "see"]
+        );
+        assert_eq!(
+            words("call parseHTML() or self.parse on src/main.rs, e.g. utf-8 I think"),
+            vec!["call", "or", "on", "think"]
+        );
+        assert_eq!(words("an `inline code` span"), vec!["an", "span"]);
+        // An unclosed backtick is forgotten at the end of its line
+        assert_eq!(words("an `unclosed\nspan ends"), vec!["an", "span", "ends"]);
+    }
+
+    #[test]
+    fn workspace_words_and_suggestion_case() {
+        let dictionary =
+            Dictionary::parse("TRY abcdefghijklmnopqrstuvwxyz\n", "2\ncolour\nquick\n");
+        let mut checker = SpellChecker::new(dictionary, parse_word_list("# words\nratatui\n"));
+        assert!(!checker.is_misspelled("Colour"));
+        assert!(checker.is_misspelled("colur"));
+        assert!(!checker.is_misspelled("Ratatui"));
+        assert_eq!(checker.suggest("Colur"), vec!["Colour"]);
+        assert_eq!(checker.suggest("QUIKC"), vec!["QUICK"]);
+
+        checker.add_workspace_word("colur");
+        assert!(!checker.is_misspelled("colur"));
+    }
+}
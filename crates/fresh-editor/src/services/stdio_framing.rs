@@ -0,0 +1,116 @@
+//! `Content-Length` framing for JSON messages over a child's stdio.
+//!
+//! The Language Server Protocol and the Debug Adapter Protocol share the
+//! same base protocol: every JSON payload is preceded by a header block
+//! (`Content-Length: <bytes>\r\n`, optionally other headers) terminated by
+//! an empty line. The LSP and DAP clients both read and write through the
+//! helpers here so the two stay byte-for-byte compatible.
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Wrap `json` in a header block.
+pub(crate) fn frame(json: &str) -> String {
+    format!("Content-Length: {}\r\n\r\n{}", json.len(), json)
+}
+
+/// Write one framed message and flush it.
+pub(crate) async fn write_message<W>(writer: &mut W, json: &str) -> Result<(), String>
+where
+    W: AsyncWrite + Unpin,
+{
+    writer
+        .write_all(frame(json).as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write to stdin: {}", e))?;
+    writer
+        .flush()
+        .await
+        .map_err(|e| format!("Failed to flush stdin: {}", e))
+}
+
+/// Read one framed message and return its JSON text.
+///
+/// `peer` names the other side in the EOF error (e.g. "LSP server").
+pub(crate) async fn read_message<R>(reader: &mut R, peer: &str) -> Result<String, String>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut content_length: Option<usize> = None;
+
+    loop {
+        let mut line = String::new();
+        let bytes_read = reader
+            .read_line(&mut line)
+            .await
+            .map_err(|e| format!("Failed to read from stdout: {}", e))?;
+
+        // EOF detected - the peer closed stdout
+        if bytes_read == 0 {
+            return Err(format!("{} closed stdout (EOF)", peer));
+        }
+
+        if line == "\r\n" {
+            break;
+        }
+
+        if let Some(len_str) = line.strip_prefix("Content-Length: ") {
+            content_length = Some(
+                len_str
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("Invalid Content-Length: {}", e))?,
+            );
+        }
+    }
+
+    let content_length =
+        content_length.ok_or_else(|| "Missing Content-Length header".to_string())?;
+
+    let mut content = vec![0u8; content_length];
+    reader
+        .read_exact(&mut content)
+        .await
+        .map_err(|e| format!("Failed to read content: {}", e))?;
+
+    String::from_utf8(content).map_err(|e| format!("Invalid UTF-8: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::BufReader;
+
+    #[tokio::test]
+    async fn round_trips_messages() {
+        let (mut writer, reader) = tokio::io::duplex(1024);
+        let mut reader = BufReader::new(reader);
+
+        write_message(&mut writer, r#"{"seq":1}"#).await.unwrap();
+        write_message(&mut writer, r#"{"text":"héllo"}"#)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            read_message(&mut reader, "peer").await.unwrap(),
+            r#"{"seq":1}"#
+        );
+        assert_eq!(
+            read_message(&mut reader, "peer").await.unwrap(),
+            r#"{"text":"héllo"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_other_headers_and_reports_eof() {
+        let input = b"Content-Type: application/vscode-jsonrpc\r\nContent-Length: 2\r\n\r\n{}";
+        let mut reader = BufReader::new(&input[..]);
+
+        assert_eq!(read_message(&mut reader, "peer").await.unwrap(), "{}");
+        assert_eq!(
+            read_message(&mut reader, "Debug adapter")
+                .await
+                .unwrap_err(),
+            "Debug adapter closed stdout (EOF)"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! Links in terminal output
+//!
+//! Compilers, test runners and linters print locations like
+//! `src/foo.rs:42:7`, and many tools print URLs. [`find_links`] picks
+//! them out of one line of terminal text so they can be underlined on the
+//! grid and opened with Ctrl+click or from the scrollback view.
+//!
+//! File locations use the same `file:line:col` syntax as the command line
+//! (see [`crate::primitives::file_location`]). A location only counts as a
+//! link when it has a line number and the path looks like a path, which
+//! keeps timestamps (`12:30:45`) and `host:port` pairs out.
+
+use std::ops::Range;
+
+use crate::primitives::file_location::{parse_file_location, FileLocation};
+
+/// Characters that surround a link without being part of it, as in
+/// `(src/main.rs:3:1)` or `"https://example.com".`
+const LEADING_PUNCTUATION: &[char] = &['(', '[', '{', '<', '"', '\'', '`'];
+const TRAILING_PUNCTUATION: &[char] = &[')', ']', '}', '>', '"', '\'', '`', ',', ';', '.', ':'];
+
+/// What a link points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// A file location; relative paths are relative to the terminal's cwd
+    File(FileLocation),
+    /// A web URL
+    Url(String),
+}
+
+/// A link found in a line of terminal text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalLink {
+    /// Columns (character indices) the link covers
+    pub columns: Range<usize>,
+    pub target: LinkTarget,
+}
+
+/// Find all links in a line of terminal text.
+pub fn find_links(line: &str) -> Vec<TerminalLink> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut links = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        if chars[start].is_whitespace() {
+            start += 1;
+            continue;
+        }
+        let mut end = start;
+        while end < chars.len() && !chars[end].is_whitespace() {
+            end += 1;
+        }
+        if let Some(link) = link_in_word(&chars, start..end) {
+            links.push(link);
+        }
+        start = end;
+    }
+    links
+}
+
+/// The link covering `column` in a line of terminal text, if any.
+pub fn link_at(line: &str, column: usize) -> Option<TerminalLink> {
+    find_links(line)
+        .into_iter()
+        .find(|link| link.columns.contains(&column))
+}
+
+/// Interpret one whitespace-separated word, minus surrounding punctuation.
+fn link_in_word(chars: &[char], mut columns: Range<usize>) -> Option<TerminalLink> {
+    while columns.start < columns.end && LEADING_PUNCTUATION.contains(&chars[columns.start]) {
+        columns.start += 1;
+    }
+    while columns.end > columns.start && TRAILING_PUNCTUATION.contains(&chars[columns.end - 1]) {
+        columns.end -= 1;
+    }
+    if columns.is_empty() {
+        return None;
+    }
+    let word: String = chars[columns.clone()].iter().collect();
+
+    if word.starts_with("https://") || word.starts_with("http://") {
+        return Some(TerminalLink {
+            columns,
+            target: LinkTarget::Url(word),
+        });
+    }
+
+    if word.contains("://") {
+        return None;
+    }
+    let location = parse_file_location(&word);
+    location.line?;
+    let path = location.path.to_string_lossy();
+    let looks_like_path = path.chars().any(|c| c.is_alphabetic())
+        && (path.contains('/') || path.contains('\\') || path.contains('.'));
+    looks_like_path.then_some(TerminalLink {
+        columns,
+        target: LinkTarget::File(location),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn file_link(path: &str, line: usize, column: Option<usize>) -> LinkTarget {
+        LinkTarget::File(FileLocation {
+            path: PathBuf::from(path),
+            line: Some(line),
+            column,
+            end_line: None,
+            end_column: None,
+            message: None,
+        })
+    }
+
+    #[test]
+    fn test_compiler_locations() {
+        let links = find_links("  --> src/foo.rs:42:7");
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].columns, 6..21);
+        assert_eq!(links[0].target, file_link("src/foo.rs", 42, Some(7)));
+
+        // gcc style, with the trailing colon before the message
+        let links = find_links("main.c:3:1: error: expected ';'");
+        assert_eq!(links[0].columns, 0..10);
+        assert_eq!(links[0].target, file_link("main.c", 3, Some(1)));
+
+        // Stack trace in parentheses
+        let links = find_links("    at run (/srv/app/index.js:10)");
+        assert_eq!(links[0].target, file_link("/srv/app/index.js", 10, None));
+    }
+
+    #[test]
+    fn test_urls() {
+        let links = find_links("see <https://example.com/docs?a=1>.");
+        assert_eq!(
+            links,
+            vec![TerminalLink {
+                columns: 5..33,
+                target: LinkTarget::Url("https://example.com/docs?a=1".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_non_links() {
+        assert!(find_links("at 12:30:45 connect localhost:8080 or README.md").is_empty());
+        assert!(find_links("ftp://example.com:21").is_empty());
+    }
+
+    #[test]
+    fn test_link_at_column() {
+        let line = "a.rs:1 b.rs:2";
+        assert_eq!(
+            link_at(line, 9).map(|l| l.target),
+            Some(file_link("b.rs", 2, None))
+        );
+        assert!(link_at(line, 6).is_none());
+    }
+}
@@ -18,6 +18,7 @@
 //! to append any new scrollback lines to the backing file. This ensures scrollback is
 //! written incrementally as lines scroll off screen, avoiding O(n) work on mode switches.
 
+use super::shell_integration;
 use super::term::TerminalState;
 use crate::services::async_bridge::AsyncBridge;
 use crate::services::authority::TerminalWrapper;
@@ -99,9 +100,14 @@ impl TerminalHandle {
         (self.cols, self.rows)
     }
 
-    /// Get the working directory configured for the terminal
+    /// Get the terminal's working directory: the one its shell last
+    /// reported through shell integration, or the one it started in
     pub fn cwd(&self) -> Option<std::path::PathBuf> {
-        self.cwd.clone()
+        self.state
+            .lock()
+            .ok()
+            .and_then(|state| state.reported_cwd().map(|p| p.to_path_buf()))
+            .or_else(|| self.cwd.clone())
     }
 
     /// Get the shell executable path used for this terminal
@@ -118,6 +124,8 @@ pub struct TerminalManager {
     next_id: usize,
     /// Async bridge for sending notifications to main loop
     async_bridge: Option<AsyncBridge>,
+    /// Where the shell integration scripts are installed for local shells
+    shell_integration_dir: Option<std::path::PathBuf>,
 }
 
 impl TerminalManager {
@@ -127,6 +135,7 @@ impl TerminalManager {
             terminals: HashMap::new(),
             next_id: 0,
             async_bridge: None,
+            shell_integration_dir: None,
         }
     }
 
@@ -135,6 +144,12 @@ impl TerminalManager {
         self.async_bridge = Some(bridge);
     }
 
+    /// Set the directory to install the shell integration scripts into.
+    /// Local shells find it in `$FRESH_SHELL_INTEGRATION_DIR`.
+    pub fn set_shell_integration_dir(&mut self, dir: std::path::PathBuf) {
+        self.shell_integration_dir = Some(dir);
+    }
+
     /// Peek at the next terminal ID that would be assigned.
     pub fn next_terminal_id(&self) -> TerminalId {
         TerminalId(self.next_id)
@@ -221,6 +236,21 @@ impl TerminalManager {
             // The built-in emulator is alacritty-based so xterm-256color is appropriate.
             cmd.env("TERM", "xterm-256color");
 
+            // Point local shells at the integration scripts. Wrappers that
+            // re-parent the shell run it where these files don't exist.
+            if !skip_cwd {
+                if let Some(ref dir) = self.shell_integration_dir {
+                    match shell_integration::install_scripts(dir) {
+                        Ok(()) => {
+                            cmd.env(shell_integration::SCRIPTS_DIR_ENV, dir.as_os_str());
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to install shell integration scripts: {}", e)
+                        }
+                    }
+                }
+            }
+
             // On Windows, set additional environment variables that help with ConPTY
             #[cfg(windows)]
             {
@@ -253,8 +283,12 @@ impl TerminalManager {
             if let Some(ref p) = backing_path {
                 if let Ok(metadata) = std::fs::metadata(p) {
                     if metadata.len() > 0 {
+                        // Shell integration marks count lines from the
+                        // start of this session's output
+                        let lines = count_lines(p);
                         if let Ok(mut s) = state.lock() {
                             s.set_backing_file_history_end(metadata.len());
+                            s.set_backing_file_base_line(lines);
                         }
                     }
                 }
@@ -638,6 +672,23 @@ pub(crate) fn strip_verbatim_prefix(path: &std::path::Path) -> Cow<'_, std::path
     }
 }
 
+/// Number of lines in a file, or 0 if it can't be read.
+fn count_lines(path: &std::path::Path) -> usize {
+    let Ok(file) = std::fs::File::open(path) else {
+        return 0;
+    };
+    let mut reader = std::io::BufReader::new(file);
+    let mut buf = [0u8; 64 * 1024];
+    let mut lines = 0;
+    while let Ok(n) = reader.read(&mut buf) {
+        if n == 0 {
+            break;
+        }
+        lines += buf[..n].iter().filter(|&&b| b == b'\n').count();
+    }
+    lines
+}
+
 /// Detect the user's shell
 pub fn detect_shell() -> String {
     // Try $SHELL environment variable first
@@ -44,12 +44,16 @@
 //!
 //! - `term.rs`: Terminal state and incremental streaming methods
 //! - `manager.rs`: PTY lifecycle and read loop with streaming
+//! - `shell_integration.rs`: OSC 133 / OSC 7 marks from integrated shells
+//! - `links.rs`: File locations and URLs in terminal output
 //! - `../app/terminal.rs`: Mode switching logic
 //! - `../app/session.rs`: Session save/restore integration
 
+pub mod links;
 pub mod manager;
 pub mod pty;
+pub mod shell_integration;
 pub mod term;
 
 pub use manager::{detect_shell, TerminalId, TerminalManager};
-pub use term::{TerminalCell, TerminalState};
+pub use term::{CommandMark, TerminalCell, TerminalState};
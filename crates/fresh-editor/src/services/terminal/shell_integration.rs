@@ -0,0 +1,244 @@
+//! Shell integration marks (OSC 133 / OSC 7)
+//!
+//! Shells that source one of the bundled integration scripts (see
+//! [`SCRIPTS`]) announce where prompts, typed commands and command
+//! output start, the exit status of each command (`OSC 133 ; A/B/C/D`)
+//! and their working directory (`OSC 7 ; file://host/path`).
+//!
+//! `alacritty_terminal` silently drops OSC sequences it does not know,
+//! so [`MarkScanner`] watches the raw PTY stream for them. It reports
+//! the offset just past each sequence, which lets `TerminalState` feed
+//! the emulator up to that point and record the mark at the cursor
+//! position the shell had when it emitted it.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable pointing spawned shells at the directory the
+/// integration scripts are installed in.
+pub const SCRIPTS_DIR_ENV: &str = "FRESH_SHELL_INTEGRATION_DIR";
+
+/// Integration scripts by file name, installed into the data directory
+/// so shells can source them.
+pub const SCRIPTS: &[(&str, &str)] = &[
+    (
+        "fresh.bash",
+        include_str!("../../../shell-integration/fresh.bash"),
+    ),
+    (
+        "fresh.zsh",
+        include_str!("../../../shell-integration/fresh.zsh"),
+    ),
+    (
+        "fresh.fish",
+        include_str!("../../../shell-integration/fresh.fish"),
+    ),
+];
+
+/// Longest OSC payload we buffer; anything longer is not one of ours.
+const MAX_OSC_LEN: usize = 4096;
+
+/// A shell integration mark found in the PTY output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShellMark {
+    /// `OSC 133 ; A` — the prompt is about to be drawn
+    PromptStart,
+    /// `OSC 133 ; B` — the prompt is drawn, the user types the command
+    CommandStart,
+    /// `OSC 133 ; C` — the command runs, its output follows
+    OutputStart,
+    /// `OSC 133 ; D [; exit]` — the command finished
+    CommandFinished(Option<i32>),
+    /// `OSC 7 ; file://host/path` — the shell's working directory
+    Cwd(PathBuf),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum ScanState {
+    #[default]
+    Ground,
+    Escape,
+    Osc,
+    OscEscape,
+}
+
+/// Incremental scanner for shell integration marks. Sequences may be
+/// split across PTY reads.
+#[derive(Debug, Default)]
+pub struct MarkScanner {
+    state: ScanState,
+    payload: Vec<u8>,
+    /// The current OSC grew past `MAX_OSC_LEN` and is being skipped
+    overflow: bool,
+}
+
+impl MarkScanner {
+    /// Scan a chunk of PTY output. Returns each mark with the offset in
+    /// `data` just past the sequence that carried it.
+    pub fn scan(&mut self, data: &[u8]) -> Vec<(usize, ShellMark)> {
+        let mut marks = Vec::new();
+        for (i, &byte) in data.iter().enumerate() {
+            self.state = match (self.state, byte) {
+                (ScanState::Ground, 0x1b) => ScanState::Escape,
+                (ScanState::Ground, _) => ScanState::Ground,
+                (ScanState::Escape, b']') => {
+                    self.payload.clear();
+                    self.overflow = false;
+                    ScanState::Osc
+                }
+                (ScanState::Escape, 0x1b) => ScanState::Escape,
+                (ScanState::Escape, _) => ScanState::Ground,
+                (ScanState::Osc, 0x07) | (ScanState::OscEscape, b'\\') => {
+                    if let Some(mark) = self.finish() {
+                        marks.push((i + 1, mark));
+                    }
+                    ScanState::Ground
+                }
+                (ScanState::Osc, 0x1b) => ScanState::OscEscape,
+                (ScanState::Osc, _) => {
+                    if self.payload.len() < MAX_OSC_LEN {
+                        self.payload.push(byte);
+                    } else {
+                        self.overflow = true;
+                    }
+                    ScanState::Osc
+                }
+                // ESC inside an OSC that is not ST aborts it; the ESC
+                // may start a new sequence
+                (ScanState::OscEscape, b']') => {
+                    self.payload.clear();
+                    self.overflow = false;
+                    ScanState::Osc
+                }
+                (ScanState::OscEscape, 0x1b) => ScanState::Escape,
+                (ScanState::OscEscape, _) => ScanState::Ground,
+            };
+        }
+        marks
+    }
+
+    fn finish(&mut self) -> Option<ShellMark> {
+        if self.overflow {
+            return None;
+        }
+        let payload = std::str::from_utf8(&self.payload).ok()?;
+        parse_osc(payload)
+    }
+}
+
+/// Parse the payload of an OSC sequence (without `ESC ]` and the
+/// terminator) as a shell integration mark.
+fn parse_osc(payload: &str) -> Option<ShellMark> {
+    let (command, rest) = payload.split_once(';')?;
+    match command {
+        "133" => {
+            let mut fields = rest.split(';');
+            match fields.next()? {
+                "A" => Some(ShellMark::PromptStart),
+                "B" => Some(ShellMark::CommandStart),
+                "C" => Some(ShellMark::OutputStart),
+                "D" => Some(ShellMark::CommandFinished(
+                    fields.next().and_then(|code| code.trim().parse().ok()),
+                )),
+                _ => None,
+            }
+        }
+        "7" => parse_file_url(rest).map(ShellMark::Cwd),
+        _ => None,
+    }
+}
+
+/// Path of a `file://host/path` URL; the host is ignored.
+fn parse_file_url(url: &str) -> Option<PathBuf> {
+    let rest = url.strip_prefix("file://")?;
+    let path = &rest[rest.find('/')?..];
+    let decoded = percent_decode(path)?;
+    // `file://host/C:/Users/...` on Windows
+    #[cfg(windows)]
+    let decoded = match decoded.strip_prefix('/') {
+        Some(p) if p.as_bytes().get(1) == Some(&b':') => p.to_string(),
+        _ => decoded,
+    };
+    Some(PathBuf::from(decoded))
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = s.get(i + 1..i + 3)?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+/// Write the integration scripts into `dir`, leaving files that are
+/// already up to date alone.
+pub fn install_scripts(dir: &Path) -> io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    for (name, contents) in SCRIPTS {
+        let path = dir.join(name);
+        if std::fs::read_to_string(&path).ok().as_deref() != Some(*contents) {
+            std::fs::write(&path, contents)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_prompt_cycle() {
+        let mut scanner = MarkScanner::default();
+        let data = b"\x1b]133;A\x07$ \x1b]133;B\x07ls\r\n\x1b]133;C\x07a b\r\n\x1b]133;D;2\x1b\\";
+        let marks = scanner.scan(data);
+        let kinds: Vec<&ShellMark> = marks.iter().map(|(_, m)| m).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                &ShellMark::PromptStart,
+                &ShellMark::CommandStart,
+                &ShellMark::OutputStart,
+                &ShellMark::CommandFinished(Some(2)),
+            ]
+        );
+        // Offsets point just past each sequence
+        assert_eq!(&data[..marks[0].0], b"\x1b]133;A\x07");
+        assert_eq!(marks[3].0, data.len());
+    }
+
+    #[test]
+    fn test_scan_sequence_split_across_reads() {
+        let mut scanner = MarkScanner::default();
+        assert!(scanner.scan(b"out\x1b]13").is_empty());
+        assert!(scanner.scan(b"3;D").is_empty());
+        assert_eq!(
+            scanner.scan(b"\x07next"),
+            vec![(1, ShellMark::CommandFinished(None))]
+        );
+    }
+
+    #[test]
+    fn test_scan_ignores_other_sequences() {
+        let mut scanner = MarkScanner::default();
+        assert!(scanner
+            .scan(b"\x1b]0;title\x07\x1b[31mred\x1b[0m\x1b]8;;http://x\x1b\\")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_scan_cwd() {
+        let mut scanner = MarkScanner::default();
+        let marks = scanner.scan(b"\x1b]7;file://host/home/me/my%20dir\x07");
+        assert_eq!(marks[0].1, ShellMark::Cwd(PathBuf::from("/home/me/my dir")));
+    }
+}
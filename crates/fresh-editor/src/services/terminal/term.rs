@@ -26,6 +26,14 @@
 //!
 //! `backing_file_history_end` tracks the byte offset where scrollback ends in the
 //! backing file, used for truncation when re-entering terminal mode.
+//!
+//! ## Shell Integration
+//!
+//! Shell integration marks (see `super::shell_integration`) are recorded as
+//! [`CommandMark`]s on absolute lines: scrollback history lines first, then
+//! the screen. Because every history line is streamed to the backing file
+//! as one line, an absolute line plus `backing_file_base_line` is also the
+//! line of the scrollback buffer loaded from that file.
 
 use alacritty_terminal::event::{Event, EventListener};
 use alacritty_terminal::grid::Scroll;
@@ -34,11 +42,18 @@ use alacritty_terminal::term::test::TermSize;
 use alacritty_terminal::term::{Config as TermConfig, Term, TermMode};
 use alacritty_terminal::vte::ansi::Processor;
 use std::io::{self, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
+use super::shell_integration::{MarkScanner, ShellMark};
+
 // Keep a generous scrollback so sync-to-buffer can include deep history.
 const SCROLLBACK_LINES: usize = 200_000;
 
+/// Most commands remembered per terminal; older ones are forgotten.
+const MAX_COMMAND_MARKS: usize = 10_000;
+
 /// Event listener that captures PtyWrite events for sending back to the PTY.
 ///
 /// When the terminal emulator needs to respond to queries (like DSR cursor position
@@ -88,6 +103,17 @@ pub struct TerminalState {
     backing_file_history_end: u64,
     /// Queue of data to write back to the PTY (for DSR responses, etc.)
     pty_write_queue: Arc<Mutex<Vec<String>>>,
+    /// Finds shell integration marks in the PTY output
+    mark_scanner: MarkScanner,
+    /// Commands located by shell integration marks, oldest first
+    commands: Vec<CommandMark>,
+    /// Working directory last reported by the shell (OSC 7)
+    reported_cwd: Option<PathBuf>,
+    /// Lines the backing file already had when this terminal started
+    /// (session restore)
+    backing_file_base_line: usize,
+    /// History size after the last output, to notice cleared scrollback
+    last_history_size: usize,
 }
 
 impl TerminalState {
@@ -112,6 +138,11 @@ impl TerminalState {
             synced_history_lines: 0,
             backing_file_history_end: 0,
             pty_write_queue,
+            mark_scanner: MarkScanner::default(),
+            commands: Vec::new(),
+            reported_cwd: None,
+            backing_file_base_line: 0,
+            last_history_size: 0,
         }
     }
 
@@ -129,7 +160,17 @@ impl TerminalState {
 
     /// Process output from the PTY
     pub fn process_output(&mut self, data: &[u8]) {
-        self.parser.advance(&mut self.term, data);
+        // Feed the emulator up to each mark so it is recorded where the
+        // shell's cursor was when it emitted it
+        let mut start = 0;
+        for (end, mark) in self.mark_scanner.scan(data) {
+            self.parser.advance(&mut self.term, &data[start..end]);
+            self.forget_marks_if_history_cleared();
+            self.apply_mark(mark);
+            start = end;
+        }
+        self.parser.advance(&mut self.term, &data[start..]);
+        self.forget_marks_if_history_cleared();
         self.dirty = true;
     }
 
@@ -338,6 +379,164 @@ impl TerminalState {
         self.dirty = true;
     }
 
+    // =========================================================================
+    // Shell integration
+    // =========================================================================
+
+    /// Commands located by shell integration marks, oldest first.
+    pub fn commands(&self) -> &[CommandMark] {
+        &self.commands
+    }
+
+    /// Whether the shell has sent any shell integration marks.
+    pub fn has_shell_integration(&self) -> bool {
+        !self.commands.is_empty()
+    }
+
+    /// Working directory the shell last reported, if it reports one.
+    pub fn reported_cwd(&self) -> Option<&Path> {
+        self.reported_cwd.as_deref()
+    }
+
+    /// Absolute line of the cursor.
+    pub fn cursor_line(&self) -> usize {
+        self.history_size() + self.term.grid().cursor.point.line.0.max(0) as usize
+    }
+
+    /// Absolute line shown on the top row, accounting for scrolling.
+    pub fn screen_top_line(&self) -> usize {
+        self.history_size()
+            .saturating_sub(self.term.grid().display_offset())
+    }
+
+    /// Exit status of the command whose prompt is on display `row`, if
+    /// that command ran and finished.
+    pub fn exit_status_at_row(&self, row: u16) -> Option<i32> {
+        let line = self.screen_top_line() + row as usize;
+        let index = self.commands.partition_point(|c| c.prompt_line < line);
+        self.commands[index..]
+            .iter()
+            .take_while(|c| c.prompt_line == line)
+            .filter(|c| c.output_line.is_some())
+            .find_map(|c| c.exit_code)
+    }
+
+    /// Output lines of the most recent command that ran and finished.
+    pub fn last_output_lines(&self) -> Option<Range<usize>> {
+        self.commands
+            .iter()
+            .rev()
+            .find_map(|c| c.output_line.and(c.output_lines()))
+    }
+
+    /// Plain text of the most recent finished command's output.
+    pub fn last_command_output(&self) -> Option<String> {
+        let lines = self.last_output_lines()?;
+        let mut text = String::new();
+        for line in lines {
+            let (row, wrapped) = self.line_text(line)?;
+            text.push_str(&row);
+            if !wrapped {
+                text.push('\n');
+            }
+        }
+        Some(text)
+    }
+
+    /// Lines the backing file already had when this terminal started.
+    pub fn backing_file_base_line(&self) -> usize {
+        self.backing_file_base_line
+    }
+
+    /// Set the number of lines the backing file had when this terminal
+    /// started (session restore).
+    pub fn set_backing_file_base_line(&mut self, lines: usize) {
+        self.backing_file_base_line = lines;
+    }
+
+    /// Text of an absolute line with trailing blanks trimmed, and whether
+    /// it soft-wraps into the next line.
+    fn line_text(&self, line: usize) -> Option<(String, bool)> {
+        use alacritty_terminal::term::cell::Flags;
+
+        let index = line as i64 - self.history_size() as i64;
+        if index >= self.rows as i64 {
+            return None;
+        }
+        let row_data = &self.term.grid()[Line(index as i32)];
+        let mut text: String = (0..self.cols as usize)
+            .map(|col| &row_data[Column(col)])
+            .filter(|cell| !cell.flags.contains(Flags::WIDE_CHAR_SPACER))
+            .map(|cell| cell.c)
+            .collect();
+        text.truncate(text.trim_end_matches([' ', '\0']).len());
+        let wrapped = self.cols > 0
+            && row_data[Column(self.cols as usize - 1)]
+                .flags
+                .contains(Flags::WRAPLINE);
+        Some((text, wrapped))
+    }
+
+    fn apply_mark(&mut self, mark: ShellMark) {
+        if let ShellMark::Cwd(path) = mark {
+            self.reported_cwd = Some(path);
+            return;
+        }
+        // Full-screen programs on the alternate screen have no lines to mark
+        if self.is_alternate_screen() {
+            return;
+        }
+        let line = self.cursor_line();
+        // A mark emitted mid-line belongs after that line's text
+        let next_line = if self.cursor_position().0 > 0 {
+            line + 1
+        } else {
+            line
+        };
+        let current = self.commands.last_mut().filter(|c| !c.is_finished());
+        match mark {
+            ShellMark::PromptStart => {
+                if self.commands.len() >= MAX_COMMAND_MARKS {
+                    self.commands.remove(0);
+                }
+                self.commands.push(CommandMark {
+                    prompt_line: line,
+                    input_line: None,
+                    output_line: None,
+                    end_line: None,
+                    exit_code: None,
+                });
+            }
+            ShellMark::CommandStart => {
+                if let Some(command) = current {
+                    command.input_line = Some(line);
+                }
+            }
+            ShellMark::OutputStart => {
+                if let Some(command) = current {
+                    command.output_line = Some(next_line);
+                }
+            }
+            ShellMark::CommandFinished(exit_code) => {
+                if let Some(command) = current {
+                    command.end_line = Some(next_line);
+                    command.exit_code = exit_code;
+                }
+            }
+            ShellMark::Cwd(_) => {}
+        }
+    }
+
+    /// Clearing the scrollback (e.g. `clear`) shifts every absolute line,
+    /// so the marks no longer point at their commands.
+    fn forget_marks_if_history_cleared(&mut self) {
+        let history = self.history_size();
+        if history < self.last_history_size {
+            self.commands.clear();
+        }
+        self.last_history_size = history;
+    }
+
     // =========================================================================
     // Terminal mode flags
     // =========================================================================
@@ -595,6 +794,36 @@ impl TerminalState {
     }
 }
 
+/// A prompt and the command run from it, located by shell integration
+/// marks. Lines are absolute (see the module docs).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandMark {
+    /// Line the prompt starts on
+    pub prompt_line: usize,
+    /// Line the command was typed on
+    pub input_line: Option<usize>,
+    /// Line the command's output starts on
+    pub output_line: Option<usize>,
+    /// Line after the command's output
+    pub end_line: Option<usize>,
+    /// Exit status, when the shell reported one
+    pub exit_code: Option<i32>,
+}
+
+impl CommandMark {
+    /// Whether the command has finished.
+    pub fn is_finished(&self) -> bool {
+        self.end_line.is_some()
+    }
+
+    /// Lines of the command's output, once it has finished.
+    pub fn output_lines(&self) -> Option<Range<usize>> {
+        let start = self.output_line.or(self.input_line.map(|l| l + 1))?;
+        let end = self.end_line?;
+        Some(start..end.max(start))
+    }
+}
+
 /// A single cell in the terminal grid
 #[derive(Debug, Clone)]
 pub struct TerminalCell {
@@ -764,6 +993,35 @@ mod tests {
         assert_eq!(state.last_visible_line(), "");
     }
 
+    /// A prompt/command/output cycle announced with OSC 133 is recorded
+    /// on the lines the shell's cursor was on.
+    #[test]
+    fn test_shell_integration_marks_command() {
+        let mut state = TerminalState::new(80, 24);
+        state.process_output(b"\x1b]7;file://host/tmp/x\x07\x1b]133;A\x07$ \x1b]133;B\x07");
+        state.process_output(b"ls\r\n\x1b]133;C\x07a.txt\r\nb.txt\r\n");
+        state.process_output(b"\x1b]133;D;1\x07\x1b]133;A\x07$ ");
+
+        assert_eq!(state.reported_cwd(), Some(Path::new("/tmp/x")));
+        assert_eq!(
+            state.commands()[0],
+            CommandMark {
+                prompt_line: 0,
+                input_line: Some(0),
+                output_line: Some(1),
+                end_line: Some(3),
+                exit_code: Some(1),
+            }
+        );
+        assert_eq!(state.commands()[1].prompt_line, 3);
+        assert_eq!(state.exit_status_at_row(0), Some(1));
+        assert_eq!(state.exit_status_at_row(3), None);
+        assert_eq!(
+            state.last_command_output().as_deref(),
+            Some("a.txt\nb.txt\n")
+        );
+    }
+
     #[test]
     fn test_flush_new_scrollback_no_history() {
         // When there's no scrollback history, flush should return 0
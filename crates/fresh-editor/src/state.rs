@@ -14,6 +14,7 @@ use crate::primitives::grammar::GrammarRegistry;
 use crate::primitives::highlight_engine::HighlightEngine;
 use crate::primitives::indent::IndentCalculator;
 use crate::primitives::reference_highlighter::ReferenceHighlighter;
+use crate::primitives::syntax_selection::SyntaxWindowCache;
 use crate::primitives::text_property::TextPropertyManager;
 use crate::view::bracket_highlight_overlay::BracketHighlightOverlay;
 use crate::view::conceal::ConcealManager;
@@ -25,6 +26,7 @@ use crate::view::popup::{
 };
 use crate::view::reference_highlight_overlay::ReferenceHighlightOverlay;
 use crate::view::soft_break::SoftBreakManager;
+use crate::view::sticky_scroll::StickyScopeCache;
 use crate::view::virtual_text::VirtualTextManager;
 use anyhow::Result;
 use ratatui::style::{Color, Style};
@@ -217,6 +219,14 @@ pub struct EditorState {
     /// (issue #1571).
     pub folding_ranges: LspFoldRanges,
 
+    /// Sticky scroll scopes (syntax tree or Markdown headings) around the
+    /// viewport, recomputed when the buffer version or the window changes
+    pub sticky_scopes: StickyScopeCache,
+
+    /// Last syntax tree parsed for expand/shrink selection, reused until
+    /// the buffer version changes
+    pub syntax_selection: SyntaxWindowCache,
+
     /// The detected language ID for this buffer (e.g., "rust", "csharp", "text").
     /// Used for LSP config lookup and internal identification.
     pub language: String,
@@ -295,6 +305,8 @@ impl EditorState {
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
             semantic_tokens: None,
             folding_ranges: LspFoldRanges::new(),
+            sticky_scopes: StickyScopeCache::default(),
+            syntax_selection: SyntaxWindowCache::default(),
             language: "text".to_string(),
             display_name: "Text".to_string(),
             line_wrap_cache: crate::view::line_wrap_cache::LineWrapCache::default(),
@@ -23,6 +23,7 @@ pub mod context_keys {
     pub const PAGE_VIEW: &str = "page_view";
     /// Backward-compatible alias for PAGE_VIEW
     pub const COMPOSE_MODE: &str = "compose_mode";
+    pub const HEX_VIEW: &str = "hex_view";
     pub const FILE_EXPLORER: &str = "file_explorer";
     pub const MENU_BAR: &str = "menu_bar";
     pub const FILE_EXPLORER_FOCUSED: &str = "file_explorer_focused";
@@ -39,6 +40,11 @@ pub mod context_keys {
     pub const SESSION_MODE: &str = "session_mode";
     pub const VERTICAL_SCROLLBAR: &str = "vertical_scrollbar";
     pub const HORIZONTAL_SCROLLBAR: &str = "horizontal_scrollbar";
+    pub const MINIMAP: &str = "minimap";
+    pub const STICKY_SCROLL: &str = "sticky_scroll";
+    pub const BREADCRUMBS: &str = "breadcrumbs";
+    pub const INDENT_GUIDES: &str = "indent_guides";
+    pub const SPELL_CHECK: &str = "spell_check";
     pub const SCROLL_SYNC: &str = "scroll_sync";
     pub const HAS_SAME_BUFFER_SPLITS: &str = "has_same_buffer_splits";
     pub const KEYMAP_DEFAULT: &str = "keymap_default";
@@ -115,8 +121,14 @@ pub enum LspFeature {
     WorkspaceSymbols,
     /// Hover information (exclusive: first eligible server wins)
     Hover,
-    /// Go to definition, declaration, type definition, implementation (exclusive)
+    /// Go to definition (exclusive)
     Definition,
+    /// Go to implementation (exclusive)
+    Implementation,
+    /// Go to type definition (exclusive)
+    TypeDefinition,
+    /// Go to declaration (exclusive)
+    Declaration,
     /// Find references (exclusive)
     References,
     /// Document formatting and range formatting (exclusive)
@@ -133,6 +145,14 @@ pub enum LspFeature {
     SemanticTokens,
     /// Document highlight (exclusive)
     DocumentHighlight,
+    /// Selection ranges for expand/shrink selection (exclusive)
+    SelectionRange,
+    /// Incoming and outgoing calls (exclusive)
+    CallHierarchy,
+    /// Supertypes and subtypes (exclusive)
+    TypeHierarchy,
+    /// Code lenses such as "Run test" or reference counts (exclusive)
+    CodeLens,
 }
 
 impl LspFeature {
@@ -420,6 +440,12 @@ mod tests {
 
         assert!(!LspFeature::Hover.is_merged());
         assert!(!LspFeature::Definition.is_merged());
+        assert!(!LspFeature::Implementation.is_merged());
+        assert!(!LspFeature::CallHierarchy.is_merged());
+        assert!(!LspFeature::TypeHierarchy.is_merged());
+        assert!(!LspFeature::CodeLens.is_merged());
+        assert!(!LspFeature::TypeDefinition.is_merged());
+        assert!(!LspFeature::Declaration.is_merged());
         assert!(!LspFeature::References.is_merged());
         assert!(!LspFeature::Format.is_merged());
         assert!(!LspFeature::Rename.is_merged());
@@ -428,6 +454,7 @@ mod tests {
         assert!(!LspFeature::FoldingRange.is_merged());
         assert!(!LspFeature::SemanticTokens.is_merged());
         assert!(!LspFeature::DocumentHighlight.is_merged());
+        assert!(!LspFeature::SelectionRange.is_merged());
     }
 
     #[test]
@@ -436,7 +436,7 @@ pub mod indent_folding {
     }
 
     /// Measure leading indent of a line given as a byte slice (no trailing `\n`).
-    fn slice_indent(line: &[u8], tab_size: usize) -> (usize, bool) {
+    pub(crate) fn slice_indent(line: &[u8], tab_size: usize) -> (usize, bool) {
         let mut indent = 0;
         let mut all_blank = true;
         for &b in line {
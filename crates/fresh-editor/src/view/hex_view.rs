@@ -0,0 +1,226 @@
+//! Hex editor view of a buffer.
+//!
+//! A split can show its buffer as rows of [`BYTES_PER_ROW`] bytes with an
+//! offset, hex and ASCII column instead of as text. The view keeps only its
+//! scroll row and the editing column here; the byte under the cursor is the
+//! primary cursor of the split, so switching between text and hex view keeps
+//! the position. Bytes are read per visible row from the piece tree, which
+//! keeps multi-GB files lazily loaded.
+
+use crate::model::buffer::Buffer;
+
+/// Bytes shown per row
+pub const BYTES_PER_ROW: usize = 16;
+
+/// Bytes read per step when searching for a byte pattern
+const SEARCH_CHUNK_BYTES: usize = 1024 * 1024;
+
+/// Column that receives typed input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HexColumn {
+    /// Hex digits overwrite the nibbles of the cursor byte
+    #[default]
+    Hex,
+    /// Printable characters overwrite the cursor byte
+    Ascii,
+}
+
+/// Per-split hex view state of one buffer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HexViewState {
+    /// First row shown
+    pub top_row: usize,
+    pub column: HexColumn,
+    /// Whether the next hex digit goes into the low nibble of the cursor byte
+    pub low_nibble: bool,
+}
+
+impl HexViewState {
+    /// Rows needed to show `len` bytes; an empty buffer still shows one row.
+    pub fn row_count(len: usize) -> usize {
+        len.div_ceil(BYTES_PER_ROW).max(1)
+    }
+
+    /// Scroll so the row of `offset` is within `visible_rows` rows.
+    pub fn ensure_visible(&mut self, offset: usize, visible_rows: usize) {
+        let row = offset / BYTES_PER_ROW;
+        let visible_rows = visible_rows.max(1);
+        if row < self.top_row {
+            self.top_row = row;
+        } else if row >= self.top_row + visible_rows {
+            self.top_row = row + 1 - visible_rows;
+        }
+    }
+
+    /// Scroll by `delta` rows without moving past the last row.
+    pub fn scroll(&mut self, delta: isize, len: usize, visible_rows: usize) {
+        let max_top = Self::row_count(len).saturating_sub(visible_rows.max(1));
+        self.top_row = self.top_row.saturating_add_signed(delta).min(max_top);
+    }
+}
+
+/// Replace the high or low nibble of `byte` with the hex digit `digit`.
+pub fn set_nibble(byte: u8, low: bool, digit: u8) -> u8 {
+    if low {
+        (byte & 0xF0) | (digit & 0x0F)
+    } else {
+        (byte & 0x0F) | (digit << 4)
+    }
+}
+
+/// Character shown for `byte` in the ASCII column.
+pub fn ascii_char(byte: u8) -> char {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        byte as char
+    } else {
+        '.'
+    }
+}
+
+/// Parse a goto-offset input: hex with a `0x` prefix or `h` suffix,
+/// decimal otherwise.
+pub fn parse_offset(input: &str) -> Option<usize> {
+    let input = input.trim().replace('_', "");
+    if let Some(hex) = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+        .or_else(|| input.strip_suffix(['h', 'H']))
+    {
+        usize::from_str_radix(hex, 16).ok()
+    } else {
+        input.parse().ok()
+    }
+}
+
+/// Parse a byte search pattern.
+///
+/// Either hex bytes (`de ad be ef`, `DEADBEEF`, `0x7f 0x45`) or, inside
+/// double quotes, literal text (`"ELF"`).
+pub fn parse_byte_pattern(input: &str) -> Option<Vec<u8>> {
+    let input = input.trim();
+    if let Some(text) = input
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    {
+        return (!text.is_empty()).then(|| text.as_bytes().to_vec());
+    }
+
+    let digits: String = input
+        .split_whitespace()
+        .map(|token| {
+            token
+                .strip_prefix("0x")
+                .or_else(|| token.strip_prefix("0X"))
+                .unwrap_or(token)
+        })
+        .collect();
+    if digits.is_empty() || digits.len() % 2 != 0 {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Offset of the first occurrence of `pattern` at or after `from`, wrapping
+/// around to the start of the buffer.
+pub fn find_bytes(buffer: &mut Buffer, pattern: &[u8], from: usize) -> Option<usize> {
+    if pattern.is_empty() {
+        return None;
+    }
+    let len = buffer.len();
+    let from = from.min(len);
+    find_bytes_in(buffer, pattern, from, len)
+        .or_else(|| find_bytes_in(buffer, pattern, 0, (from + pattern.len() - 1).min(len)))
+}
+
+/// First occurrence of `pattern` lying entirely in `start..end`, read in
+/// chunks so large files are not loaded at once.
+fn find_bytes_in(buffer: &mut Buffer, pattern: &[u8], start: usize, end: usize) -> Option<usize> {
+    let mut pos = start;
+    while pos + pattern.len() <= end {
+        // Overlap chunks so matches across a chunk boundary are found
+        let chunk_end = (pos + SEARCH_CHUNK_BYTES + pattern.len() - 1).min(end);
+        let bytes = buffer.get_text_range_mut(pos, chunk_end - pos).ok()?;
+        if let Some(index) = bytes
+            .windows(pattern.len())
+            .position(|window| window == pattern)
+        {
+            return Some(pos + index);
+        }
+        pos += SEARCH_CHUNK_BYTES;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_offset() {
+        assert_eq!(parse_offset("1024"), Some(1024));
+        assert_eq!(parse_offset("0x1F"), Some(31));
+        assert_eq!(parse_offset(" ffh "), Some(255));
+        assert_eq!(parse_offset("0x_ff_ff"), Some(0xffff));
+        assert_eq!(parse_offset("zz"), None);
+    }
+
+    #[test]
+    fn test_parse_byte_pattern() {
+        assert_eq!(
+            parse_byte_pattern("de ad BE ef"),
+            Some(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(
+            parse_byte_pattern("7f454c46"),
+            Some(vec![0x7f, b'E', b'L', b'F'])
+        );
+        assert_eq!(parse_byte_pattern("0x7f 0x45"), Some(vec![0x7f, 0x45]));
+        assert_eq!(parse_byte_pattern("\"ELF\""), Some(b"ELF".to_vec()));
+        assert_eq!(parse_byte_pattern("abc"), None);
+        assert_eq!(parse_byte_pattern("zz"), None);
+        assert_eq!(parse_byte_pattern(""), None);
+    }
+
+    #[test]
+    fn test_set_nibble() {
+        assert_eq!(set_nibble(0x12, false, 0xA), 0xA2);
+        assert_eq!(set_nibble(0x12, true, 0xA), 0x1A);
+    }
+
+    #[test]
+    fn test_ensure_visible_and_scroll() {
+        let mut state = HexViewState::default();
+        state.ensure_visible(BYTES_PER_ROW * 30, 10);
+        assert_eq!(state.top_row, 21);
+        state.ensure_visible(BYTES_PER_ROW * 5, 10);
+        assert_eq!(state.top_row, 5);
+
+        // 40 rows of data, 10 visible: can't scroll past row 30
+        state.scroll(100, BYTES_PER_ROW * 40, 10);
+        assert_eq!(state.top_row, 30);
+        state.scroll(-100, BYTES_PER_ROW * 40, 10);
+        assert_eq!(state.top_row, 0);
+    }
+
+    #[test]
+    fn test_find_bytes_wraps_and_spans_chunks() {
+        let mut content = vec![b'a'; SEARCH_CHUNK_BYTES + 8];
+        content[SEARCH_CHUNK_BYTES - 1..SEARCH_CHUNK_BYTES + 2].copy_from_slice(b"xyz");
+        content[4..7].copy_from_slice(b"xyz");
+        let mut buffer = Buffer::from_bytes(
+            content,
+            std::sync::Arc::new(crate::model::filesystem::StdFileSystem),
+        );
+
+        assert_eq!(find_bytes(&mut buffer, b"xyz", 0), Some(4));
+        assert_eq!(
+            find_bytes(&mut buffer, b"xyz", 5),
+            Some(SEARCH_CHUNK_BYTES - 1)
+        );
+        assert_eq!(find_bytes(&mut buffer, b"xyz", SEARCH_CHUNK_BYTES), Some(4));
+        assert_eq!(find_bytes(&mut buffer, b"q", 0), None);
+    }
+}
@@ -0,0 +1,290 @@
+//! Indentation guides: vertical lines at each indentation level of a
+//! block, with the guide of the block holding the cursor highlighted.
+//!
+//! A line's level is the width of its leading whitespace, with tabs
+//! expanded to the buffer's tab size, and it shows a guide every tab size
+//! columns below that. Blank lines take the shallower level of the lines
+//! around them, so guides run unbroken through the blank lines of a block.
+//!
+//! Lines are looked up by source byte, so the renderer
+//! (`split_rendering/post_pass.rs`) places guides on wrapped, folded and
+//! concealed rows without special handling.
+
+use crate::model::buffer::Buffer;
+use crate::view::folding::indent_folding::slice_indent;
+use std::ops::Range;
+
+/// Bytes read on each side of the viewport, to bridge blank lines at its
+/// edges and find the extent of the cursor's block. Blocks reaching
+/// further are cut off at the edge of this window.
+const CONTEXT_BYTES: usize = 16 * 1024;
+
+/// One line, as far as guides are concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuideLine {
+    /// First byte of the line
+    pub start: usize,
+    /// First byte after the leading whitespace; past the line ending for
+    /// a blank line
+    pub indent_end: usize,
+    /// Indentation in columns, bridged over blank lines
+    pub level: usize,
+}
+
+impl GuideLine {
+    /// Whether `byte` is part of the line's leading whitespace.
+    pub fn in_indent(&self, byte: usize) -> bool {
+        self.start <= byte && byte < self.indent_end
+    }
+}
+
+/// The guide of the innermost block holding the cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveGuide {
+    pub column: usize,
+    /// Bytes of the lines the guide runs along
+    pub range: Range<usize>,
+}
+
+impl ActiveGuide {
+    /// Whether the guide at `column` of the line holding `byte` is this one.
+    pub fn contains(&self, column: usize, byte: usize) -> bool {
+        column == self.column && self.range.contains(&byte)
+    }
+}
+
+/// Guides of the lines around a split's viewport.
+#[derive(Debug, Clone)]
+pub struct IndentGuides {
+    lines: Vec<GuideLine>,
+    /// End of the last line
+    end: usize,
+    /// Columns between guides
+    step: usize,
+    active: Option<ActiveGuide>,
+}
+
+impl IndentGuides {
+    /// Guides of the lines around `visible`, the bytes shown in a split
+    /// whose cursor is at `cursor`.
+    pub fn around(buffer: &Buffer, visible: Range<usize>, cursor: usize, tab_size: usize) -> Self {
+        let len = buffer.len();
+        let start = visible.start.saturating_sub(CONTEXT_BYTES);
+        let end = visible.end.saturating_add(CONTEXT_BYTES).min(len);
+        let mut bytes = buffer.slice_bytes(start..end);
+        let mut offset = start;
+        if bytes.len() != end.saturating_sub(start) {
+            // Part of a large file is not loaded: make do with what is shown
+            offset = visible.start.min(len);
+            bytes = buffer.slice_bytes(offset..visible.end.min(len));
+        }
+        // Start at a line boundary
+        let skip = match bytes.iter().position(|&b| b == b'\n') {
+            Some(newline) if offset > 0 => newline + 1,
+            _ => 0,
+        };
+        Self::from_bytes(&bytes[skip..], offset + skip, cursor, tab_size)
+    }
+
+    /// Guides of the lines in `bytes`, which start at buffer offset
+    /// `offset`.
+    fn from_bytes(bytes: &[u8], offset: usize, cursor: usize, tab_size: usize) -> Self {
+        let step = tab_size.max(1);
+        let mut lines = Vec::new();
+        // Indentation of each line, `None` for blank lines
+        let mut indents = Vec::new();
+        let mut pos = offset;
+        for line in bytes.split_inclusive(|&b| b == b'\n') {
+            let text = line.strip_suffix(b"\n").unwrap_or(line);
+            let (indent, blank) = slice_indent(text, step);
+            let whitespace = if blank {
+                line.len()
+            } else {
+                text.iter()
+                    .take_while(|&&b| b == b' ' || b == b'\t')
+                    .count()
+            };
+            lines.push(GuideLine {
+                start: pos,
+                indent_end: pos + whitespace,
+                level: indent,
+            });
+            indents.push((!blank).then_some(indent));
+            pos += line.len();
+        }
+
+        // Indentation of the next non-blank line after each line
+        let mut below = vec![None; lines.len()];
+        let mut next = None;
+        for (slot, indent) in below.iter_mut().zip(&indents).rev() {
+            *slot = next;
+            if indent.is_some() {
+                next = *indent;
+            }
+        }
+        let mut above = None;
+        for (i, line) in lines.iter_mut().enumerate() {
+            match indents[i] {
+                Some(indent) => above = Some(indent),
+                None => line.level = above.unwrap_or(0).min(below[i].unwrap_or(0)),
+            }
+        }
+
+        let mut guides = Self {
+            lines,
+            end: pos,
+            step,
+            active: None,
+        };
+        guides.active = guides.find_active(cursor, &indents, &below);
+        guides
+    }
+
+    /// Columns between guides.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// The line holding `byte`, if it was read.
+    pub fn line_at(&self, byte: usize) -> Option<GuideLine> {
+        if byte >= self.end {
+            return None;
+        }
+        let index = self
+            .lines
+            .partition_point(|line| line.start <= byte)
+            .checked_sub(1)?;
+        Some(self.lines[index])
+    }
+
+    /// Columns of the guides shown on `line`.
+    pub fn columns(&self, line: &GuideLine) -> impl Iterator<Item = usize> {
+        (0..line.level).step_by(self.step)
+    }
+
+    /// The guide of the block holding the cursor, if it was read.
+    pub fn active(&self) -> Option<&ActiveGuide> {
+        self.active.as_ref()
+    }
+
+    /// The guide of the innermost block holding the line at `cursor`. A
+    /// line followed by deeper lines opens a block, so on such a header
+    /// it is the guide of the block it opens.
+    fn find_active(
+        &self,
+        cursor: usize,
+        indents: &[Option<usize>],
+        below: &[Option<usize>],
+    ) -> Option<ActiveGuide> {
+        if cursor > self.end {
+            return None;
+        }
+        let lines = &self.lines;
+        let index = lines
+            .partition_point(|line| line.start <= cursor)
+            .checked_sub(1)?;
+        let level = lines[index].level;
+        let body = below[index].filter(|&next| indents[index].is_some() && next > level);
+        let (column, mut first) = match body {
+            Some(body) => {
+                let column = level.next_multiple_of(self.step);
+                if column >= body {
+                    return None;
+                }
+                (column, index + 1)
+            }
+            None if level > 0 => ((level - 1) / self.step * self.step, index),
+            None => return None,
+        };
+
+        let inside = |line: &GuideLine| line.level > column;
+        while body.is_none() && first > 0 && inside(&lines[first - 1]) {
+            first -= 1;
+        }
+        let mut last = first;
+        while last + 1 < lines.len() && inside(&lines[last + 1]) {
+            last += 1;
+        }
+        if first >= lines.len() || !inside(&lines[first]) {
+            return None;
+        }
+        let end = lines.get(last + 1).map_or(self.end, |line| line.start);
+        Some(ActiveGuide {
+            column,
+            range: lines[first].start..end,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &[u8] = b"fn f() {\n    if x {\n        a();\n\n        b();\n    }\n}\n";
+
+    /// Start of line `n` of `SOURCE`
+    fn line_start(n: usize) -> usize {
+        SOURCE
+            .split_inclusive(|&b| b == b'\n')
+            .take(n)
+            .map(<[u8]>::len)
+            .sum()
+    }
+
+    fn levels(guides: &IndentGuides) -> Vec<usize> {
+        guides.lines.iter().map(|line| line.level).collect()
+    }
+
+    #[test]
+    fn blank_lines_bridge_the_block() {
+        let guides = IndentGuides::from_bytes(SOURCE, 0, 0, 4);
+        assert_eq!(levels(&guides), vec![0, 4, 8, 8, 8, 4, 0]);
+        let blank = guides.line_at(line_start(3)).unwrap();
+        assert!(blank.in_indent(line_start(3)));
+        assert_eq!(guides.columns(&blank).collect::<Vec<_>>(), vec![0, 4]);
+
+        let body = guides.line_at(line_start(2) + 9).unwrap();
+        assert!(body.in_indent(line_start(2) + 7));
+        assert!(!body.in_indent(line_start(2) + 8));
+    }
+
+    #[test]
+    fn active_guide_follows_the_cursor() {
+        // Inside the `if` body: its guide, over all of the body
+        let guides = IndentGuides::from_bytes(SOURCE, 0, line_start(4) + 9, 4);
+        assert_eq!(
+            guides.active(),
+            Some(&ActiveGuide {
+                column: 4,
+                range: line_start(2)..line_start(5),
+            })
+        );
+
+        // On the `if` header: the guide of the block it opens
+        let guides = IndentGuides::from_bytes(SOURCE, 0, line_start(1) + 4, 4);
+        assert_eq!(guides.active().unwrap().column, 4);
+
+        // On `}` closing the `if`: the guide of the function body
+        let guides = IndentGuides::from_bytes(SOURCE, 0, line_start(5) + 4, 4);
+        assert_eq!(
+            guides.active(),
+            Some(&ActiveGuide {
+                column: 0,
+                range: line_start(1)..line_start(6),
+            })
+        );
+
+        // At top level there is none
+        let guides = IndentGuides::from_bytes(SOURCE, 0, line_start(6), 4);
+        assert_eq!(guides.active(), None);
+    }
+
+    #[test]
+    fn tabs_expand_to_the_tab_size() {
+        let guides = IndentGuides::from_bytes(b"a\n\t\tb\n", 0, 0, 2);
+        let line = guides.line_at(2).unwrap();
+        assert_eq!(line.level, 4);
+        assert_eq!(line.indent_end, 4);
+        assert_eq!(guides.columns(&line).collect::<Vec<_>>(), vec![0, 2]);
+    }
+}
@@ -407,6 +407,22 @@ impl MarginManager {
         }
     }
 
+    /// Marker IDs and current byte positions of all indicators in `namespace`,
+    /// ordered by position.
+    pub fn line_indicator_positions(&self, namespace: &str) -> Vec<(MarkerId, usize)> {
+        let mut positions: Vec<(MarkerId, usize)> = self
+            .line_indicators
+            .iter()
+            .filter(|(_, indicators)| indicators.contains_key(namespace))
+            .filter_map(|(&marker_id, _)| {
+                let marker_id = MarkerId(marker_id);
+                Some((marker_id, self.indicator_markers.get_position(marker_id)?))
+            })
+            .collect();
+        positions.sort_by_key(|&(_, pos)| pos);
+        positions
+    }
+
     /// Get the line indicator for a specific line number
     ///
     /// This looks up all indicators whose markers resolve to the given line.
@@ -1040,4 +1056,33 @@ mod tests {
         assert!(manager.get_line_indicator(7, byte_to_line).is_some());
         assert!(manager.get_line_indicator(9, byte_to_line).is_some());
     }
+
+    #[test]
+    fn test_line_indicator_positions_filters_namespace() {
+        let mut manager = MarginManager::new();
+
+        let late = manager.set_line_indicator(
+            line_to_byte(7),
+            "debug-breakpoint".to_string(),
+            LineIndicator::new("●", Color::Red, 20),
+        );
+        manager.set_line_indicator(
+            line_to_byte(5),
+            "git-gutter".to_string(),
+            LineIndicator::new("│", Color::Green, 10),
+        );
+        let early = manager.set_line_indicator(
+            line_to_byte(2),
+            "debug-breakpoint".to_string(),
+            LineIndicator::new("●", Color::Red, 20),
+        );
+
+        manager.adjust_for_insert(0, 10);
+
+        assert_eq!(
+            manager.line_indicator_positions("debug-breakpoint"),
+            vec![(early, line_to_byte(3)), (late, line_to_byte(8))]
+        );
+        assert!(manager.line_indicator_positions("other").is_empty());
+    }
 }
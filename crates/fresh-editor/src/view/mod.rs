@@ -61,6 +61,10 @@ pub mod file_browser_input;
 #[cfg(feature = "runtime")]
 pub mod file_tree;
 #[cfg(feature = "runtime")]
+pub mod hex_view;
+#[cfg(feature = "runtime")]
+pub mod indent_guides;
+#[cfg(feature = "runtime")]
 pub mod keybinding_editor;
 #[cfg(feature = "runtime")]
 pub mod markdown;
@@ -81,4 +85,6 @@ pub mod reference_highlight_overlay;
 #[cfg(feature = "runtime")]
 pub mod split;
 #[cfg(feature = "runtime")]
+pub mod sticky_scroll;
+#[cfg(feature = "runtime")]
 pub mod stream;
@@ -105,6 +105,15 @@ pub enum PopupResolver {
     /// `Editor::pending_code_actions` (heavy `lsp_types` payload stays
     /// there to keep the view crate free of LSP types).
     CodeAction,
+    /// LSP code-lens chooser for a line with several lenses. Selected
+    /// row's `data` is the lens index in the active buffer's code lenses.
+    CodeLens,
+    /// Debug configuration chooser. Selected row's `data` is the index of
+    /// the configuration in `config.debug.configurations`.
+    DebugConfiguration,
+    /// Task chooser. Selected row's `data` is the task's key in
+    /// `config.tasks`.
+    Task,
     /// Plugin-requested action popup (`editor.showActionPopup`). Confirm
     /// fires `action_popup_result` with this popup's id and the selected
     /// row's `data` as the action id.
@@ -114,6 +123,12 @@ pub enum PopupResolver {
     /// Confirm dispatches the selected row's `data` through
     /// `handle_remote_indicator_action`.
     RemoteIndicator,
+    /// Choice tabstop of a snippet session. Selected row's `data` is the
+    /// choice text, which replaces the tabstop.
+    SnippetChoice,
+    /// Dropdown of a breadcrumb bar segment. Selected row's `data` is a
+    /// path for path segments and a byte offset for symbol segments.
+    Breadcrumb { segment: usize },
 }
 
 /// Content of a popup window
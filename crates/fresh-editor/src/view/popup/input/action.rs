@@ -5,10 +5,12 @@
 //! - Enter: execute the selected action
 //! - Arrow keys: navigate the action list
 //! - Tab/Shift+Tab: navigate the action list
+//! - Left/Right: move to the neighbouring breadcrumb segment (breadcrumb
+//!   dropdowns only)
 
 use super::base::{try_handle_shared, SharedHandleResult};
 use crate::input::handler::{DeferredAction, InputContext, InputResult};
-use crate::view::popup::Popup;
+use crate::view::popup::{Popup, PopupResolver};
 use crossterm::event::{KeyCode, KeyEvent};
 
 /// Handle input for action popups
@@ -60,6 +62,16 @@ pub fn handle_action_input(
             InputResult::Consumed
         }
 
+        // Breadcrumb dropdowns step between the bar's segments
+        KeyCode::Left | KeyCode::Right
+            if event.modifiers.is_empty()
+                && matches!(popup.resolver, PopupResolver::Breadcrumb { .. }) =>
+        {
+            let delta = if event.code == KeyCode::Left { -1 } else { 1 };
+            ctx.defer(DeferredAction::BreadcrumbStep(delta));
+            InputResult::Consumed
+        }
+
         // Consume all other keys (modal behavior)
         _ => InputResult::Consumed,
     }
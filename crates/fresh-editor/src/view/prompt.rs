@@ -47,6 +47,10 @@ pub enum PromptType {
     GotoLine,
     /// Go to a specific byte offset (large file without line index scan)
     GotoByteOffset,
+    /// Go to a byte offset in the hex view (decimal or hex)
+    HexGotoOffset,
+    /// Find a byte pattern in the hex view
+    HexSearch,
     /// Confirm whether to scan a large file for exact line numbers before Go To Line
     GotoLineScanConfirm,
     /// Choose an ANSI background file
@@ -99,6 +103,8 @@ pub enum PromptType {
     SelectCursorStyle,
     /// Select a UI locale/language (select from list)
     SelectLocale,
+    /// Insert a snippet from the snippet library (select from list)
+    InsertSnippet,
     /// Select a theme for copy with formatting
     CopyWithFormattingTheme,
     /// Confirm reverting a modified file
@@ -113,6 +119,11 @@ pub enum PromptType {
     ConfirmOverwriteFile { path: std::path::PathBuf },
     /// Confirm creating parent directories for a save target
     ConfirmCreateDirectory { path: std::path::PathBuf },
+    /// Ask whether a plugin package may use a capability
+    ConfirmPluginPermission {
+        plugin_name: String,
+        capability: fresh_core::permissions::Capability,
+    },
     /// Confirm closing a modified buffer (save/discard/cancel)
     /// Stores buffer_id to close after user confirms
     ConfirmCloseBuffer {
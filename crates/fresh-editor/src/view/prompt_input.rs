@@ -160,10 +160,12 @@ impl InputHandler for Prompt {
                         ) {
                             ctx.defer(DeferredAction::PreviewThemeFromPrompt);
                         }
-                        // For plugin prompts, notify about selection change (for live preview)
+                        // For plugin and Quick Open prompts, notify about selection
+                        // change (for live preview)
                         if matches!(
                             self.prompt_type,
                             crate::view::prompt::PromptType::Plugin { .. }
+                                | crate::view::prompt::PromptType::QuickOpen
                         ) {
                             ctx.defer(DeferredAction::PromptSelectionChanged {
                                 selected_index: new_selected,
@@ -204,10 +206,12 @@ impl InputHandler for Prompt {
                         ) {
                             ctx.defer(DeferredAction::PreviewThemeFromPrompt);
                         }
-                        // For plugin prompts, notify about selection change (for live preview)
+                        // For plugin and Quick Open prompts, notify about selection
+                        // change (for live preview)
                         if matches!(
                             self.prompt_type,
                             crate::view::prompt::PromptType::Plugin { .. }
+                                | crate::view::prompt::PromptType::QuickOpen
                         ) {
                             ctx.defer(DeferredAction::PromptSelectionChanged {
                                 selected_index: new_selected,
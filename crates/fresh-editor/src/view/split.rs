@@ -28,6 +28,7 @@ use crate::model::cursor::Cursors;
 use crate::model::event::{BufferId, ContainerId, LeafId, SplitDirection, SplitId};
 use crate::model::marker::MarkerList;
 use crate::view::folding::FoldManager;
+use crate::view::hex_view::HexViewState;
 use crate::view::ui::view_pipeline::Layout;
 use crate::view::viewport::Viewport;
 use crate::{services::plugins::api::ViewTransformPayload, state::ViewMode};
@@ -180,6 +181,9 @@ pub struct BufferViewState {
 
     /// Collapsed folding ranges for this buffer/view.
     pub folds: FoldManager,
+
+    /// Hex editor view of the buffer; `None` shows it as text.
+    pub hex_view: Option<HexViewState>,
 }
 
 impl BufferViewState {
@@ -214,6 +218,7 @@ impl BufferViewState {
             view_transform_stale: false,
             plugin_state: std::collections::HashMap::new(),
             folds: FoldManager::new(),
+            hex_view: None,
         }
     }
 
@@ -271,6 +276,7 @@ impl Clone for BufferViewState {
             plugin_state: self.plugin_state.clone(),
             // Fold markers are per-view; clones start with no folded ranges.
             folds: FoldManager::new(),
+            hex_view: self.hex_view.clone(),
         }
     }
 }
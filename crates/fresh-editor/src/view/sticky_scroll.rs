@@ -0,0 +1,489 @@
+//! Sticky scroll: the header lines of the scopes enclosing the top of a
+//! split, pinned over its first rows.
+//!
+//! A scope is known by its header line (where it opens) and its body. While
+//! the first unpinned row of a split lies in the body of a scope whose
+//! header has scrolled out of view, that header is pinned; nested scopes
+//! stack below it, outermost first, up to a configured number of rows.
+//! Rows are compared by the source bytes they show, so wrapped, folded and
+//! concealed lines need no special handling.
+//!
+//! Scopes come from, in order of preference:
+//! 1. the tree-sitter syntax tree (functions, classes, impls, modules, ...),
+//!    or the heading structure of a Markdown buffer;
+//! 2. the language server's folding ranges, for languages without a
+//!    grammar;
+//! 3. indentation, read from a bounded window around the viewport, which
+//!    also covers buffers too large to parse.
+//!
+//! The first source is parsed in a window around the viewport (Markdown
+//! headings are read from the whole buffer), and its result is cached per
+//! buffer version and window in [`StickyScopeCache`]. Scopes opening above
+//! a window are taken from the folding ranges, or found by reading back
+//! for the less indented lines that enclose the window.
+
+use crate::model::buffer::Buffer;
+use crate::view::folding::indent_folding::slice_indent;
+use lsp_types::{FoldingRange, FoldingRangeKind};
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Bytes read on each side of the viewport by the indentation fallback.
+const INDENT_WINDOW_BYTES: usize = 32 * 1024;
+
+/// How far above a window the headers of the scopes enclosing it are
+/// looked for.
+const MAX_ENCLOSING_SCAN_BYTES: usize = 1024 * 1024;
+
+/// Bytes read at a time while looking back for enclosing headers.
+const ENCLOSING_CHUNK_BYTES: usize = 16 * 1024;
+
+/// A non-blank line: its start, its end including the newline, and its
+/// indentation.
+type IndentLine = (usize, usize, usize);
+
+/// A scope that can be pinned: its header line and its body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StickyScope {
+    /// First byte of the header line
+    pub header_start: usize,
+    /// First byte after the header line
+    pub body_start: usize,
+    /// End of the body: the start of the scope's closing line, or of the
+    /// first line after the scope
+    pub body_end: usize,
+}
+
+impl StickyScope {
+    /// Whether a row showing `byte` lies in this scope's body.
+    pub fn contains(&self, byte: usize) -> bool {
+        self.body_start <= byte && byte < self.body_end
+    }
+
+    /// The scope spanning lines `start_line..=end_line`, where the last
+    /// line closes the body. `None` if a line is past the end of the
+    /// buffer or the scope has no body.
+    pub fn from_lines(buffer: &Buffer, start_line: usize, end_line: usize) -> Option<Self> {
+        let scope = Self {
+            header_start: buffer.line_start_offset(start_line)?,
+            body_start: buffer.line_start_offset(start_line + 1)?,
+            body_end: buffer.line_start_offset(end_line)?,
+        };
+        (scope.body_start < scope.body_end).then_some(scope)
+    }
+}
+
+/// Syntax tree or Markdown heading scopes of one version of a buffer,
+/// around one window of it.
+#[derive(Debug, Default)]
+pub struct StickyScopeCache {
+    /// Buffer version and window start the scopes were computed for
+    key: Option<(u64, usize)>,
+    /// `None` when the buffer has no such scope source
+    scopes: Option<Arc<Vec<StickyScope>>>,
+}
+
+impl StickyScopeCache {
+    /// The scopes of buffer version `version` around the window starting
+    /// at `window_start`, computed with `compute` if the buffer changed or
+    /// the window moved since they were last cached.
+    pub fn get_or_compute(
+        &mut self,
+        version: u64,
+        window_start: usize,
+        compute: impl FnOnce() -> Option<Vec<StickyScope>>,
+    ) -> Option<Arc<Vec<StickyScope>>> {
+        let key = Some((version, window_start));
+        if self.key != key {
+            self.scopes = compute().map(Arc::new);
+            self.key = key;
+        }
+        self.scopes.clone()
+    }
+}
+
+/// Header starts of the scopes to pin over a split, outermost first.
+///
+/// `row_bytes` holds the first source byte shown on each row, top to
+/// bottom. With `k` headers pinned, row `k` is the first one left visible;
+/// the next header is that of the outermost scope containing it that is
+/// nested in the last pinned scope. At most `max_rows` headers are pinned.
+pub fn sticky_headers(scopes: &[StickyScope], row_bytes: &[usize], max_rows: usize) -> Vec<usize> {
+    let mut pinned: Vec<&StickyScope> = Vec::new();
+    while pinned.len() < max_rows {
+        let Some(&byte) = row_bytes.get(pinned.len()) else {
+            break;
+        };
+        let parent = pinned.last();
+        let next = scopes
+            .iter()
+            .filter(|scope| scope.contains(byte))
+            .filter(|scope| {
+                parent.is_none_or(|parent| {
+                    scope.header_start > parent.header_start && scope.body_end <= parent.body_end
+                })
+            })
+            .min_by_key(|scope| scope.header_start);
+        match next {
+            Some(scope) => pinned.push(scope),
+            None => break,
+        }
+    }
+    pinned.iter().map(|scope| scope.header_start).collect()
+}
+
+/// Scopes from the language server's folding ranges. Comment and import
+/// folds are not scopes.
+pub fn folding_range_scopes(buffer: &Buffer, ranges: &[FoldingRange]) -> Vec<StickyScope> {
+    ranges
+        .iter()
+        .filter(|range| {
+            !matches!(
+                range.kind,
+                Some(FoldingRangeKind::Comment) | Some(FoldingRangeKind::Imports)
+            )
+        })
+        .filter_map(|range| {
+            StickyScope::from_lines(buffer, range.start_line as usize, range.end_line as usize)
+        })
+        .collect()
+}
+
+/// Sections of a Markdown document: each ATX heading (`#` to `######`)
+/// opens a section that runs to the next heading of the same or a higher
+/// level. Headings inside fenced code blocks are ignored.
+pub fn markdown_heading_scopes(text: &[u8]) -> Vec<StickyScope> {
+    let mut scopes = Vec::new();
+    // (level, header_start, body_start) of the enclosing headings
+    let mut open: Vec<(usize, usize, usize)> = Vec::new();
+    let mut fence: Option<u8> = None;
+    let mut pos = 0;
+    for line in text.split_inclusive(|&b| b == b'\n') {
+        let start = pos;
+        pos += line.len();
+        let trimmed = trim_indent(line);
+        if let Some(marker) = fence_marker(trimmed) {
+            match fence {
+                Some(open_marker) if open_marker == marker => fence = None,
+                Some(_) => {}
+                None => fence = Some(marker),
+            }
+            continue;
+        }
+        if fence.is_some() {
+            continue;
+        }
+        let Some(level) = heading_level(trimmed) else {
+            continue;
+        };
+        while let Some(&(open_level, header_start, body_start)) = open.last() {
+            if open_level < level {
+                break;
+            }
+            open.pop();
+            scopes.push(StickyScope {
+                header_start,
+                body_start,
+                body_end: start,
+            });
+        }
+        open.push((level, start, pos));
+    }
+    for (_, header_start, body_start) in open {
+        scopes.push(StickyScope {
+            header_start,
+            body_start,
+            body_end: text.len(),
+        });
+    }
+    scopes.retain(|scope| scope.body_start < scope.body_end);
+    scopes.sort_by_key(|scope| scope.header_start);
+    scopes
+}
+
+/// Up to three spaces of indentation, as Markdown allows before a heading
+/// or fence.
+fn trim_indent(line: &[u8]) -> &[u8] {
+    let spaces = line.iter().take(3).take_while(|&&b| b == b' ').count();
+    &line[spaces..]
+}
+
+fn fence_marker(line: &[u8]) -> Option<u8> {
+    [b'`', b'~']
+        .into_iter()
+        .find(|&marker| line.len() >= 3 && line[..3].iter().all(|&b| b == marker))
+}
+
+fn heading_level(line: &[u8]) -> Option<usize> {
+    let level = line.iter().take_while(|&&b| b == b'#').count();
+    let rest = &line[level..];
+    let separated = rest.is_empty() || matches!(rest[0], b' ' | b'\t' | b'\r' | b'\n');
+    ((1..=6).contains(&level) && separated).then_some(level)
+}
+
+/// Scopes implied by indentation around `byte`: a line followed by more
+/// deeply indented lines opens a scope that ends at the next line indented
+/// no deeper than it. [`INDENT_WINDOW_BYTES`] on each side of `byte` are
+/// read, and scopes still open at the end of that window extend to it.
+/// Scopes opening above the window are found with
+/// [`enclosing_indent_scopes`].
+pub fn indent_scopes(buffer: &mut Buffer, byte: usize, tab_size: usize) -> Vec<StickyScope> {
+    let start = byte.saturating_sub(INDENT_WINDOW_BYTES);
+    let end = byte.saturating_add(INDENT_WINDOW_BYTES).min(buffer.len());
+    let Some(lines) = read_indent_lines(buffer, start..end, tab_size) else {
+        return Vec::new();
+    };
+    let mut scopes = enclosing_scopes(buffer, &lines, end, tab_size);
+    scopes.extend(indent_scopes_in(&lines, end));
+    scopes.sort_by_key(|scope| scope.header_start);
+    scopes
+}
+
+/// Scopes implied by indentation that open above the first or the last
+/// line of `window` and are still open there: the nearest lines above it
+/// that are less indented than every line in between. Their ends are
+/// looked for in `window`; scopes still open at its end extend to it.
+///
+/// Reads back until a line at column 0, at most
+/// [`MAX_ENCLOSING_SCAN_BYTES`].
+pub fn enclosing_indent_scopes(
+    buffer: &mut Buffer,
+    window: Range<usize>,
+    tab_size: usize,
+) -> Vec<StickyScope> {
+    let end = window.end.min(buffer.len());
+    let Some(lines) = read_indent_lines(buffer, window.start..end, tab_size) else {
+        return Vec::new();
+    };
+    let mut scopes = enclosing_scopes(buffer, &lines, end, tab_size);
+    let last = &lines[lines.len().saturating_sub(1)..];
+    for scope in enclosing_scopes(buffer, last, end, tab_size) {
+        if !scopes.iter().any(|s| s.header_start == scope.header_start) {
+            scopes.push(scope);
+        }
+    }
+    scopes.sort_by_key(|scope| scope.header_start);
+    scopes
+}
+
+/// The non-blank lines of `range`, from the first line starting in it.
+fn read_indent_lines(
+    buffer: &mut Buffer,
+    range: Range<usize>,
+    tab_size: usize,
+) -> Option<Vec<IndentLine>> {
+    let bytes = buffer
+        .get_text_range_mut(range.start, range.end.saturating_sub(range.start))
+        .ok()?;
+    // Start at a line boundary
+    let skip = match bytes.iter().position(|&b| b == b'\n') {
+        Some(newline) if range.start > 0 => newline + 1,
+        _ => 0,
+    };
+    Some(indent_lines(&bytes[skip..], range.start + skip, tab_size))
+}
+
+/// The non-blank lines in `bytes`, which start at buffer offset `offset`.
+fn indent_lines(bytes: &[u8], offset: usize, tab_size: usize) -> Vec<IndentLine> {
+    let mut lines = Vec::new();
+    let mut pos = offset;
+    for line in bytes.split_inclusive(|&b| b == b'\n') {
+        let (indent, blank) = slice_indent(line.strip_suffix(b"\n").unwrap_or(line), tab_size);
+        if !blank {
+            lines.push((pos, pos + line.len(), indent));
+        }
+        pos += line.len();
+    }
+    lines
+}
+
+/// Scopes opening above the first of `lines`, which run to `end`, and
+/// enclosing it.
+fn enclosing_scopes(
+    buffer: &mut Buffer,
+    lines: &[IndentLine],
+    end: usize,
+    tab_size: usize,
+) -> Vec<StickyScope> {
+    let Some(&(first_start, _, first_indent)) = lines.first() else {
+        return Vec::new();
+    };
+    enclosing_headers(buffer, first_start, first_indent, tab_size)
+        .into_iter()
+        .filter_map(|(header_start, body_start, indent)| {
+            let body_end = lines
+                .iter()
+                .find(|&&(_, _, line_indent)| line_indent <= indent)
+                .map_or(end, |&(line_start, _, _)| line_start);
+            (body_start < body_end).then_some(StickyScope {
+                header_start,
+                body_start,
+                body_end,
+            })
+        })
+        .collect()
+}
+
+/// Lines above the line start `from` that are less indented than `indent`
+/// and every line between them and `from`, nearest first.
+fn enclosing_headers(
+    buffer: &mut Buffer,
+    from: usize,
+    mut indent: usize,
+    tab_size: usize,
+) -> Vec<IndentLine> {
+    let mut headers = Vec::new();
+    let limit = from.saturating_sub(MAX_ENCLOSING_SCAN_BYTES);
+    // Everything from `end` on has been read; it is a line start
+    let mut end = from;
+    let mut chunk = ENCLOSING_CHUNK_BYTES;
+    while indent > 0 && end > limit {
+        let start = end.saturating_sub(chunk).max(limit);
+        let Ok(bytes) = buffer.get_text_range_mut(start, end - start) else {
+            break;
+        };
+        // Only whole lines: the first one may begin before the chunk. The
+        // last byte is the newline ending the line above `end`.
+        let skip = if start == 0 {
+            0
+        } else {
+            match bytes[..bytes.len().saturating_sub(1)].iter().position(|&b| b == b'\n') {
+                Some(newline) => newline + 1,
+                // A line longer than the chunk: read more of it
+                None if start > limit => {
+                    chunk *= 2;
+                    continue;
+                }
+                None => break,
+            }
+        };
+        let lines = indent_lines(&bytes[skip..], start + skip, tab_size);
+        for &(line_start, line_end, line_indent) in lines.iter().rev() {
+            if line_indent < indent {
+                headers.push((line_start, line_end, line_indent));
+                indent = line_indent;
+                if indent == 0 {
+                    break;
+                }
+            }
+        }
+        if start == limit {
+            break;
+        }
+        end = start + skip;
+    }
+    headers
+}
+
+/// Indentation scopes opening on `lines`, which run to `end`.
+fn indent_scopes_in(lines: &[IndentLine], end: usize) -> Vec<StickyScope> {
+    let mut scopes = Vec::new();
+    // A header opens a scope if the next non-blank line is indented deeper
+    let close = |header: usize, body_end: usize, scopes: &mut Vec<StickyScope>| {
+        let (header_start, header_end, indent) = lines[header];
+        if lines
+            .get(header + 1)
+            .is_some_and(|&(next_start, _, next_indent)| {
+                next_indent > indent && next_start < body_end
+            })
+        {
+            scopes.push(StickyScope {
+                header_start,
+                body_start: header_end,
+                body_end,
+            });
+        }
+    };
+    // Indices into `lines` of the lines that may still open a scope
+    let mut open: Vec<usize> = Vec::new();
+    for (i, &(line_start, _, indent)) in lines.iter().enumerate() {
+        while let Some(&header) = open.last() {
+            if lines[header].2 < indent {
+                break;
+            }
+            open.pop();
+            close(header, line_start, &mut scopes);
+        }
+        open.push(i);
+    }
+    for header in open {
+        close(header, end, &mut scopes);
+    }
+    scopes.sort_by_key(|scope| scope.header_start);
+    scopes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope(header_start: usize, body_start: usize, body_end: usize) -> StickyScope {
+        StickyScope {
+            header_start,
+            body_start,
+            body_end,
+        }
+    }
+
+    #[test]
+    fn headers_stack_outermost_first() {
+        // impl at 0 (body 10..100), fn at 20 (body 30..80)
+        let scopes = [scope(0, 10, 100), scope(20, 30, 80)];
+        // Top row deep inside both: both pinned, impl first
+        assert_eq!(sticky_headers(&scopes, &[40, 50, 60], 5), vec![0, 20]);
+        // The second row is past the end of fn: only impl is pinned
+        assert_eq!(sticky_headers(&scopes, &[70, 85, 90], 5), vec![0]);
+        // Row count limit
+        assert_eq!(sticky_headers(&scopes, &[40, 50, 60], 1), vec![0]);
+        // Top row is the impl header itself: nothing to pin
+        assert_eq!(
+            sticky_headers(&scopes, &[0, 10, 20], 5),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn markdown_sections_nest_by_level() {
+        let text = b"# A\ntext\n## B\n```\n# not a heading\n```\n# C\nmore\n";
+        let scopes = markdown_heading_scopes(text);
+        let b = 9;
+        let c = 38;
+        assert_eq!(
+            scopes,
+            vec![
+                scope(0, 4, c),
+                scope(b, b + 5, c),
+                scope(c, c + 4, text.len())
+            ]
+        );
+    }
+
+    #[test]
+    fn indentation_scopes_end_at_dedent() {
+        let text = b"class A:\n    def f():\n        pass\n\n    x = 1\ny = 2\n";
+        let scopes = indent_scopes_in(&indent_lines(text, 0, 4), text.len());
+        let def = 9;
+        let x = 36;
+        let y = 46;
+        assert_eq!(scopes, vec![scope(0, def, y), scope(def, def + 13, x)]);
+    }
+
+    #[test]
+    fn scopes_longer_than_the_window_are_found() {
+        let body = "    fn f() {\n        1\n    }\n".repeat(5000);
+        let text = format!("x\nmod outer {{\n{body}}}\n");
+        let mut buffer = Buffer::from_str_test(&text);
+        let outer_body = 14;
+
+        // Opening far above the window
+        let scopes = indent_scopes(&mut buffer, 100_000, 4);
+        let window_end = 100_000 + INDENT_WINDOW_BYTES;
+        assert!(scopes.contains(&scope(2, outer_body, window_end)));
+        let scopes = enclosing_indent_scopes(&mut buffer, 80_000..120_000, 4);
+        assert!(scopes.contains(&scope(2, outer_body, 120_000)));
+
+        // Opening in the window and ending past it
+        let scopes = enclosing_indent_scopes(&mut buffer, 0..50_000, 4);
+        assert!(scopes.contains(&scope(2, outer_body, 50_000)));
+    }
+}
@@ -455,6 +455,15 @@ pub struct EditorColors {
     /// Whitespace indicator foreground color (for tab arrows and space dots)
     #[serde(default = "default_whitespace_indicator_fg")]
     pub whitespace_indicator_fg: ColorDef,
+    /// Indentation guide color
+    #[serde(default = "default_indent_guide_fg")]
+    pub indent_guide_fg: ColorDef,
+    /// Color of the indentation guide of the block holding the cursor
+    #[serde(default = "default_indent_guide_active_fg")]
+    pub indent_guide_active_fg: ColorDef,
+    /// Underline color of misspelled words
+    #[serde(default = "default_spell_error_fg")]
+    pub spell_error_fg: ColorDef,
     /// Background color for lines after end-of-file (optional override).
     /// When not set, computed as a slightly contrasting shade of `bg`
     /// (lighter for dark themes, darker for light themes) to give post-EOF
@@ -503,6 +512,15 @@ fn default_ruler_bg() -> ColorDef {
 fn default_whitespace_indicator_fg() -> ColorDef {
     ColorDef::Rgb(70, 70, 70) // Subdued dark gray, subtle but visible
 }
+fn default_indent_guide_fg() -> ColorDef {
+    ColorDef::Rgb(60, 60, 60) // Just above the default editor bg
+}
+fn default_indent_guide_active_fg() -> ColorDef {
+    ColorDef::Rgb(110, 110, 110)
+}
+fn default_spell_error_fg() -> ColorDef {
+    ColorDef::Rgb(220, 80, 80)
+}
 
 /// UI element colors (tabs, menus, status bar, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -1141,6 +1159,13 @@ pub struct Theme {
     // Whitespace indicator color (tab arrows, space dots)
     pub whitespace_indicator_fg: Color,
 
+    // Indentation guide colors
+    pub indent_guide_fg: Color,
+    pub indent_guide_active_fg: Color,
+
+    // Spell check underline color
+    pub spell_error_fg: Color,
+
     // Diff highlighting colors
     pub diff_add_bg: Color,
     pub diff_remove_bg: Color,
@@ -1319,6 +1344,9 @@ impl From<ThemeFile> for Theme {
                 .unwrap_or_else(|| shade_toward_contrast(file.editor.bg.clone().into(), 10)),
             ruler_bg: file.editor.ruler_bg.into(),
             whitespace_indicator_fg: file.editor.whitespace_indicator_fg.into(),
+            indent_guide_fg: file.editor.indent_guide_fg.into(),
+            indent_guide_active_fg: file.editor.indent_guide_active_fg.into(),
+            spell_error_fg: file.editor.spell_error_fg.into(),
             diff_add_bg: file.editor.diff_add_bg.clone().into(),
             diff_remove_bg: file.editor.diff_remove_bg.clone().into(),
             diff_modify_bg: file.editor.diff_modify_bg.into(),
@@ -1522,6 +1550,9 @@ impl From<Theme> for ThemeFile {
                 diff_modify_bg: theme.diff_modify_bg.into(),
                 ruler_bg: theme.ruler_bg.into(),
                 whitespace_indicator_fg: theme.whitespace_indicator_fg.into(),
+                indent_guide_fg: theme.indent_guide_fg.into(),
+                indent_guide_active_fg: theme.indent_guide_active_fg.into(),
+                spell_error_fg: theme.spell_error_fg.into(),
                 after_eof_bg: Some(theme.after_eof_bg.into()),
             },
             ui: UiColors {
@@ -1814,6 +1845,9 @@ impl Theme {
                 "diff_modify_bg" => Some(self.diff_modify_bg),
                 "ruler_bg" => Some(self.ruler_bg),
                 "whitespace_indicator_fg" => Some(self.whitespace_indicator_fg),
+                "indent_guide_fg" => Some(self.indent_guide_fg),
+                "indent_guide_active_fg" => Some(self.indent_guide_active_fg),
+                "spell_error_fg" => Some(self.spell_error_fg),
                 _ => None,
             },
             "ui" => match field {
@@ -1918,6 +1952,9 @@ impl Theme {
                 "diff_modify_bg" => Some(&mut self.diff_modify_bg),
                 "ruler_bg" => Some(&mut self.ruler_bg),
                 "whitespace_indicator_fg" => Some(&mut self.whitespace_indicator_fg),
+                "indent_guide_fg" => Some(&mut self.indent_guide_fg),
+                "indent_guide_active_fg" => Some(&mut self.indent_guide_active_fg),
+                "spell_error_fg" => Some(&mut self.spell_error_fg),
                 _ => None,
             },
             "ui" => match field {
@@ -0,0 +1,307 @@
+//! Breadcrumb bar: a row at the top of each split showing where its cursor
+//! is, as `dir › file › impl Foo › fn bar`.
+//!
+//! The path part is the buffer's file path, relative to the working
+//! directory when it lies inside it. The symbol part is the chain of outline
+//! symbols enclosing the cursor: the language server's document symbols, or
+//! the grammar's tree-sitter tags. Each segment opens a dropdown listing the
+//! entries next to it; opening and resolving those lives in
+//! `app/breadcrumb_actions.rs`.
+
+use crate::model::buffer::Buffer;
+use crate::model::event::BufferId;
+use crate::primitives::display_width::str_width;
+use crate::primitives::document_symbols::OutlineSymbol;
+use crate::view::theme::Theme;
+use crate::view::ui::layout::point_in_rect;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+use std::path::{Component, Path, PathBuf};
+
+/// Drawn between segments.
+pub const SEPARATOR: &str = " › ";
+
+/// Drawn in place of the leading segments when the bar is too narrow.
+const ELLIPSIS: &str = "…";
+
+/// Longest definition header used as a symbol label; longer ones (or ones
+/// that cannot be read) show just the symbol's name.
+const MAX_LABEL_BYTES: usize = 80;
+
+/// What a breadcrumb segment stands for, and so what its dropdown lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreadcrumbTarget {
+    /// A directory or file; the dropdown lists the entries of its parent
+    /// directory
+    Path(PathBuf),
+    /// Index of a symbol in the buffer's outline; the dropdown lists the
+    /// symbols sharing its container
+    Symbol(usize),
+    /// Name of a buffer without a file, with no dropdown
+    Label,
+}
+
+/// One segment of a breadcrumb bar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreadcrumbSegment {
+    pub label: String,
+    pub target: BreadcrumbTarget,
+}
+
+/// What to draw in one split's breadcrumb bar.
+#[derive(Debug, Clone, Default)]
+pub struct BreadcrumbBar {
+    pub segments: Vec<BreadcrumbSegment>,
+    /// Segment whose dropdown is open
+    pub focused: Option<usize>,
+}
+
+/// Hit areas of a rendered breadcrumb bar.
+#[derive(Debug, Clone)]
+pub struct BreadcrumbLayout {
+    pub buffer_id: BufferId,
+    /// The full bar area
+    pub bar_area: Rect,
+    /// (segment index, area) of each drawn segment; leading segments may
+    /// have been dropped for lack of room
+    pub segments: Vec<(usize, Rect)>,
+}
+
+impl BreadcrumbLayout {
+    /// Index of the segment at the given position.
+    pub fn hit_test(&self, x: u16, y: u16) -> Option<usize> {
+        self.segments
+            .iter()
+            .find(|(_, area)| point_in_rect(*area, x, y))
+            .map(|(index, _)| *index)
+    }
+
+    /// Area of a drawn segment, where its dropdown is anchored.
+    pub fn segment_area(&self, index: usize) -> Option<Rect> {
+        self.segments
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, area)| *area)
+    }
+}
+
+/// Segments for the components of `path`, relative to `root` when `path`
+/// lies inside it.
+pub fn path_segments(path: &Path, root: &Path) -> Vec<BreadcrumbSegment> {
+    let (mut current, relative) = match path.strip_prefix(root) {
+        Ok(relative) if !relative.as_os_str().is_empty() => (root.to_path_buf(), relative),
+        _ => (PathBuf::new(), path),
+    };
+    relative
+        .components()
+        .filter_map(|component| {
+            current.push(component);
+            match component {
+                Component::Normal(name) => Some(BreadcrumbSegment {
+                    label: name.to_string_lossy().into_owned(),
+                    target: BreadcrumbTarget::Path(current.clone()),
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Indices of the symbols whose range contains `offset`, outermost first.
+pub fn enclosing_symbols(symbols: &[OutlineSymbol], offset: usize) -> Vec<usize> {
+    let mut chain: Vec<usize> = symbols
+        .iter()
+        .enumerate()
+        .filter(|(_, symbol)| symbol.range.start <= offset && offset <= symbol.range.end)
+        .map(|(index, _)| index)
+        .collect();
+    chain.sort_by_key(|&index| symbols[index].depth);
+    chain
+}
+
+/// Indices of the symbols sharing the container of `symbols[index]`
+/// (itself included), in document order.
+pub fn sibling_symbols(symbols: &[OutlineSymbol], index: usize) -> Vec<usize> {
+    let depth = symbols[index].depth;
+    // The container is the closest earlier symbol one level up; its
+    // descendants follow it.
+    let start = symbols[..index]
+        .iter()
+        .rposition(|symbol| symbol.depth < depth)
+        .map_or(0, |container| container + 1);
+    symbols[start..]
+        .iter()
+        .enumerate()
+        .take_while(|(_, symbol)| symbol.depth >= depth)
+        .filter(|(_, symbol)| symbol.depth == depth)
+        .map(|(offset, _)| start + offset)
+        .collect()
+}
+
+/// Label of a symbol segment: its definition up to and including the name
+/// (`impl Foo`, `pub fn bar`, `class Baz`), or just the name.
+pub fn symbol_label(buffer: &Buffer, symbol: &OutlineSymbol) -> String {
+    let start = symbol.range.start;
+    let end = symbol.selection_range.end;
+    if start <= symbol.selection_range.start && end - start <= MAX_LABEL_BYTES {
+        let bytes = buffer.slice_bytes(start..end);
+        // Attributes and decorators sit on lines of their own
+        let line = bytes.rsplit(|&b| b == b'\n').next().unwrap_or_default();
+        let label = String::from_utf8_lossy(line)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if label.ends_with(symbol.name.as_str()) {
+            return label;
+        }
+    }
+    symbol.name.clone()
+}
+
+/// Draw a breadcrumb bar into `area`, a single row.
+///
+/// Returns the area of each drawn segment. Leading segments are replaced by
+/// an ellipsis when the bar does not fit.
+pub fn render_breadcrumbs(
+    frame: &mut Frame,
+    area: Rect,
+    bar: &BreadcrumbBar,
+    theme: &Theme,
+) -> Vec<(usize, Rect)> {
+    let base = Style::default().bg(theme.editor_bg);
+    let dim = base.fg(theme.line_number_fg);
+    let available = (area.width as usize).saturating_sub(1);
+
+    let widths: Vec<usize> = bar
+        .segments
+        .iter()
+        .map(|segment| str_width(&segment.label))
+        .collect();
+    let separator_width = str_width(SEPARATOR);
+    let total = |first: usize| -> usize {
+        let shown = &widths[first..];
+        shown.iter().sum::<usize>() + separator_width * shown.len().saturating_sub(1)
+    };
+    // Drop leading segments until the rest fits after an ellipsis, but
+    // always keep the last one
+    let mut first = 0;
+    while first + 1 < widths.len() && total(first) > available {
+        first += 1;
+    }
+    let elided = first > 0;
+
+    let mut spans = vec![Span::styled(" ", base)];
+    let mut x = area.x + 1;
+    let mut areas = Vec::new();
+    if elided {
+        spans.push(Span::styled(ELLIPSIS, dim));
+        spans.push(Span::styled(SEPARATOR, dim));
+        x += (str_width(ELLIPSIS) + separator_width) as u16;
+    }
+    for (index, segment) in bar.segments.iter().enumerate().skip(first) {
+        if index > first {
+            spans.push(Span::styled(SEPARATOR, dim));
+            x = x.saturating_add(separator_width as u16);
+        }
+        let style = if bar.focused == Some(index) {
+            Style::default()
+                .fg(theme.popup_selection_fg)
+                .bg(theme.popup_selection_bg)
+        } else if matches!(segment.target, BreadcrumbTarget::Path(_))
+            && index + 1 < bar.segments.len()
+            && matches!(bar.segments[index + 1].target, BreadcrumbTarget::Path(_))
+        {
+            // Directories are dimmed; the file and its symbols are not
+            dim
+        } else {
+            base.fg(theme.editor_fg)
+        };
+        spans.push(Span::styled(segment.label.clone(), style));
+        let width = widths[index] as u16;
+        let right = area.x + area.width;
+        if x < right {
+            areas.push((index, Rect::new(x, area.y, width.min(right - x), 1)));
+        }
+        x = x.saturating_add(width);
+    }
+
+    frame.render_widget(Paragraph::new(Line::from(spans)).style(base), area);
+    areas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::SymbolKind;
+
+    fn symbol(name: &str, range: std::ops::Range<usize>, depth: usize) -> OutlineSymbol {
+        OutlineSymbol {
+            name: name.to_string(),
+            detail: None,
+            kind: SymbolKind::FUNCTION,
+            selection_range: range.start..range.start + name.len(),
+            range,
+            line: 0,
+            depth,
+            container: None,
+        }
+    }
+
+    #[test]
+    fn path_relative_to_root() {
+        let segments = path_segments(Path::new("/work/src/app/main.rs"), Path::new("/work"));
+        let labels: Vec<_> = segments.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, vec!["src", "app", "main.rs"]);
+        assert_eq!(
+            segments[1].target,
+            BreadcrumbTarget::Path(PathBuf::from("/work/src/app"))
+        );
+
+        // Outside the root: the whole path
+        let segments = path_segments(Path::new("/etc/hosts"), Path::new("/work"));
+        assert_eq!(
+            segments,
+            vec![
+                BreadcrumbSegment {
+                    label: "etc".to_string(),
+                    target: BreadcrumbTarget::Path(PathBuf::from("/etc")),
+                },
+                BreadcrumbSegment {
+                    label: "hosts".to_string(),
+                    target: BreadcrumbTarget::Path(PathBuf::from("/etc/hosts")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn symbol_chain_and_siblings() {
+        // impl A { fn a1, fn a2 }  fn b
+        let symbols = vec![
+            symbol("A", 0..100, 0),
+            symbol("a1", 10..40, 1),
+            symbol("a2", 50..90, 1),
+            symbol("b", 110..150, 0),
+        ];
+        assert_eq!(enclosing_symbols(&symbols, 60), vec![0, 2]);
+        assert_eq!(enclosing_symbols(&symbols, 105), Vec::<usize>::new());
+        assert_eq!(sibling_symbols(&symbols, 2), vec![1, 2]);
+        assert_eq!(sibling_symbols(&symbols, 0), vec![0, 3]);
+    }
+
+    #[test]
+    fn labels_include_the_definition_keyword() {
+        let source = "#[test]\npub fn bar() {}\n";
+        let buffer = Buffer::from_str_test(source);
+        let mut bar = symbol("bar", 0..source.len() - 1, 0);
+        bar.selection_range = 15..18;
+        assert_eq!(symbol_label(&buffer, &bar), "pub fn bar");
+
+        // A selection range outside the definition falls back to the name
+        bar.selection_range = 0..3;
+        assert_eq!(symbol_label(&buffer, &bar), "bar");
+    }
+}
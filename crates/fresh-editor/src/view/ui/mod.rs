@@ -4,6 +4,7 @@
 //! separated into focused submodules:
 //! - `menu` - Menu bar rendering
 //! - `tabs` - Tab bar rendering for multiple buffers
+//! - `breadcrumbs` - Per-split breadcrumb bar (file path and enclosing symbols)
 //! - `status_bar` - Status bar and prompt/minibuffer display
 //! - `suggestions` - Autocomplete and command palette UI
 //! - `split_rendering` - Split pane layout and rendering
@@ -22,6 +23,8 @@ pub mod view_pipeline;
 
 // Runtime-only modules (depend on state, services, input, etc.)
 #[cfg(feature = "runtime")]
+pub mod breadcrumbs;
+#[cfg(feature = "runtime")]
 pub mod expanded_menus_cache;
 #[cfg(feature = "runtime")]
 pub mod file_browser;
@@ -0,0 +1,148 @@
+//! Hex view rendering: offset, hex and ASCII columns for a buffer shown
+//! with [`HexViewState`].
+//!
+//! Only the bytes of the visible rows are read from the buffer, so this
+//! stays cheap on large lazily-loaded files.
+
+use crate::model::buffer::Buffer;
+use crate::view::hex_view::{ascii_char, HexColumn, HexViewState, BYTES_PER_ROW};
+use crate::view::theme::Theme;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+/// Columns between the offset and the hex bytes, and between the hex bytes
+/// and the ASCII column
+const COLUMN_GAP: usize = 2;
+
+/// Screen column of byte `index` of a row, relative to the start of the
+/// hex column. Bytes take three cells; the two halves of a row are
+/// separated by one extra space.
+fn hex_cell(index: usize) -> usize {
+    index * 3 + usize::from(index >= BYTES_PER_ROW / 2)
+}
+
+/// Width of the hex column including the trailing separator
+const HEX_COLUMN_WIDTH: usize = BYTES_PER_ROW * 3 + 1;
+
+/// Render `buffer` as a hex dump into `area`.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn render_hex_view(
+    frame: &mut Frame,
+    area: Rect,
+    buffer: &mut Buffer,
+    hex: &mut HexViewState,
+    cursor: usize,
+    is_active: bool,
+    hide_cursor: bool,
+    theme: &Theme,
+    pending_hardware_cursor: &mut Option<(u16, u16)>,
+) {
+    let len = buffer.len();
+    let visible_rows = area.height as usize;
+    hex.top_row = hex
+        .top_row
+        .min(HexViewState::row_count(len).saturating_sub(1));
+
+    let start = hex.top_row * BYTES_PER_ROW;
+    let end = (start + visible_rows * BYTES_PER_ROW).min(len);
+    let bytes = if end > start {
+        buffer
+            .get_text_range_mut(start, end - start)
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    // At least 8 digits, more for files past 4 GiB
+    let offset_width = format!("{:X}", len).len().max(8);
+    let hex_x = offset_width + COLUMN_GAP;
+    let ascii_x = hex_x + HEX_COLUMN_WIDTH + COLUMN_GAP - 1;
+
+    let base_style = Style::default().fg(theme.editor_fg).bg(theme.editor_bg);
+    let offset_style = Style::default()
+        .fg(theme.line_number_fg)
+        .bg(theme.editor_bg);
+    let cursor_style = base_style.bg(theme.selection_bg);
+
+    let mut lines = Vec::with_capacity(visible_rows);
+    for (row_index, row_bytes) in bytes.chunks(BYTES_PER_ROW).enumerate() {
+        let row_start = start + row_index * BYTES_PER_ROW;
+        let mut spans = vec![
+            Span::styled(
+                format!("{:0width$X}", row_start, width = offset_width),
+                offset_style,
+            ),
+            Span::styled(" ".repeat(COLUMN_GAP), base_style),
+        ];
+        for index in 0..BYTES_PER_ROW {
+            let text = match row_bytes.get(index) {
+                Some(byte) => format!("{:02X}", byte),
+                None => "  ".to_string(),
+            };
+            let style = if row_start + index == cursor {
+                cursor_style
+            } else {
+                base_style
+            };
+            spans.push(Span::styled(text, style));
+            let gap = hex_cell(index + 1) - hex_cell(index) - 2;
+            spans.push(Span::styled(" ".repeat(gap), base_style));
+        }
+        spans.push(Span::styled(" ".repeat(COLUMN_GAP - 1), base_style));
+        for (index, &byte) in row_bytes.iter().enumerate() {
+            let style = if row_start + index == cursor {
+                cursor_style
+            } else {
+                base_style
+            };
+            spans.push(Span::styled(ascii_char(byte).to_string(), style));
+        }
+        lines.push(Line::from(spans));
+    }
+    if lines.is_empty() {
+        // Empty buffer: show the offset of the (only) row
+        lines.push(Line::from(Span::styled(
+            format!("{:0width$X}", 0, width = offset_width),
+            offset_style,
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines).style(base_style), area);
+
+    if !is_active || hide_cursor {
+        return;
+    }
+    let cursor_row = cursor / BYTES_PER_ROW;
+    if cursor_row < hex.top_row || cursor_row >= hex.top_row + visible_rows {
+        return;
+    }
+    let index = cursor % BYTES_PER_ROW;
+    let column = match hex.column {
+        HexColumn::Hex => hex_x + hex_cell(index) + usize::from(hex.low_nibble),
+        HexColumn::Ascii => ascii_x + index,
+    };
+    if column < area.width as usize {
+        *pending_hardware_cursor = Some((
+            area.x + column as u16,
+            area.y + (cursor_row - hex.top_row) as u16,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_cells_leave_a_gap_between_row_halves() {
+        assert_eq!(hex_cell(0), 0);
+        assert_eq!(hex_cell(7), 21);
+        assert_eq!(hex_cell(8), 25);
+        assert_eq!(hex_cell(15), 46);
+        // The row ends with one separator after the last byte
+        assert_eq!(hex_cell(BYTES_PER_ROW), HEX_COLUMN_WIDTH);
+    }
+}
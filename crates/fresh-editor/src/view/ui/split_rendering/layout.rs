@@ -33,11 +33,16 @@ pub(super) struct ComposeLayout {
     pub right_pad: u16,
 }
 
-/// Rectangle partitioning for one split: tabs, content, vertical scrollbar,
-/// horizontal scrollbar.
+/// Rectangle partitioning for one split: tabs, breadcrumbs, content,
+/// minimap, vertical scrollbar, horizontal scrollbar.
 pub(super) struct SplitLayout {
     pub tabs_rect: Rect,
+    /// Below the tabs; zero-height when the breadcrumb bar is off.
+    pub breadcrumb_rect: Rect,
     pub content_rect: Rect,
+    /// Between the content and the vertical scrollbar; zero-width when the
+    /// minimap is off or the split is too narrow for it.
+    pub minimap_rect: Rect,
     pub scrollbar_rect: Rect,
     pub horizontal_scrollbar_rect: Rect,
 }
@@ -56,15 +61,27 @@ pub(super) struct ViewPreferences {
     pub highlight_current_line: bool,
 }
 
-/// Partition a split area into tabs / content / scrollbar rectangles.
+/// Partition a split area into tabs / breadcrumbs / content / minimap /
+/// scrollbar rectangles. `minimap_width` is 0 when the minimap is off.
 pub(super) fn split_layout(
     split_area: Rect,
     tab_bar_visible: bool,
+    show_breadcrumbs: bool,
     show_vertical_scrollbar: bool,
     show_horizontal_scrollbar: bool,
+    minimap_width: u16,
 ) -> SplitLayout {
     let tabs_height = if tab_bar_visible { 1u16 } else { 0u16 };
+    let breadcrumb_height = if show_breadcrumbs { 1u16 } else { 0u16 };
+    // Rows above the content
+    let header_height = tabs_height + breadcrumb_height;
     let scrollbar_width = if show_vertical_scrollbar { 1u16 } else { 0u16 };
+    // Keep at least three times the minimap's width for the text itself
+    let minimap_width = if split_area.width >= minimap_width.saturating_mul(4) {
+        minimap_width
+    } else {
+        0
+    };
     let hscrollbar_height = if show_horizontal_scrollbar {
         1u16
     } else {
@@ -72,34 +89,51 @@ pub(super) fn split_layout(
     };
 
     let tabs_rect = Rect::new(split_area.x, split_area.y, split_area.width, tabs_height);
-    let content_rect = Rect::new(
+    let breadcrumb_rect = Rect::new(
         split_area.x,
         split_area.y + tabs_height,
-        split_area.width.saturating_sub(scrollbar_width),
+        split_area.width,
+        breadcrumb_height.min(split_area.height.saturating_sub(tabs_height)),
+    );
+    let content_rect = Rect::new(
+        split_area.x,
+        split_area.y + header_height,
+        split_area
+            .width
+            .saturating_sub(scrollbar_width)
+            .saturating_sub(minimap_width),
         split_area
             .height
-            .saturating_sub(tabs_height)
+            .saturating_sub(header_height)
             .saturating_sub(hscrollbar_height),
     );
+    let minimap_rect = Rect::new(
+        content_rect.x + content_rect.width,
+        content_rect.y,
+        minimap_width,
+        content_rect.height,
+    );
     let scrollbar_rect = Rect::new(
         split_area.x + split_area.width.saturating_sub(scrollbar_width),
-        split_area.y + tabs_height,
+        split_area.y + header_height,
         scrollbar_width,
         split_area
             .height
-            .saturating_sub(tabs_height)
+            .saturating_sub(header_height)
             .saturating_sub(hscrollbar_height),
     );
     let horizontal_scrollbar_rect = Rect::new(
         split_area.x,
         split_area.y + split_area.height.saturating_sub(hscrollbar_height),
-        split_area.width.saturating_sub(scrollbar_width),
+        content_rect.width,
         hscrollbar_height,
     );
 
     SplitLayout {
         tabs_rect,
+        breadcrumb_rect,
         content_rect,
+        minimap_rect,
         scrollbar_rect,
         horizontal_scrollbar_rect,
     }
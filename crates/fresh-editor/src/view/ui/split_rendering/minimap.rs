@@ -0,0 +1,409 @@
+//! Minimap: a condensed view of the buffer beside the vertical scrollbar.
+//!
+//! Each minimap row covers four buffer lines. Its cells are braille
+//! characters whose dots stand for `CHARS_PER_DOT` columns of text, raised
+//! where those columns hold anything but whitespace and coloured by the
+//! syntax highlighter. The leftmost column carries line indicators (git
+//! changes, unsaved edits), the rightmost one diagnostics; rows with search
+//! matches and the rows currently on screen get a tinted background.
+//!
+//! Small buffers are shown line by line, scrolling proportionally when they
+//! are taller than the minimap. Buffers over the large-file threshold are
+//! sampled instead: each row reads a few lines at an evenly spaced byte
+//! offset, so the piece tree is never scanned as a whole, and no syntax
+//! colours are computed.
+
+use super::folding::diff_indicators_for_viewport;
+use super::style::inline_diagnostic_style;
+use crate::primitives::display_width::char_width;
+use crate::primitives::highlighter::HighlightSpan;
+use crate::state::EditorState;
+use crate::view::theme::Theme;
+use crate::view::viewport::Viewport;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::Frame;
+use std::collections::HashMap;
+
+/// Buffer lines per minimap row: the four dot rows of a braille cell.
+const LINES_PER_ROW: usize = 4;
+
+/// Text columns represented by one braille dot.
+const CHARS_PER_DOT: usize = 4;
+
+/// Braille dot bits by dot row and dot column.
+const BRAILLE_DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// One minimap row: up to four consecutive lines starting at `start`.
+struct MinimapRow {
+    start: usize,
+    end: usize,
+    /// `(line start byte, text)` of each line
+    lines: Vec<(usize, String)>,
+}
+
+/// Draw the minimap for a buffer into `rect`. `visible_end` is the last
+/// byte shown in the split, used for the viewport band.
+///
+/// Returns the first byte of each drawn row, top to bottom, for mouse hit
+/// testing.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn render_minimap(
+    frame: &mut Frame,
+    state: &mut EditorState,
+    viewport: &Viewport,
+    visible_end: usize,
+    rect: Rect,
+    theme: &Theme,
+    large_file_threshold_bytes: u64,
+    estimated_line_length: usize,
+    highlight_context_bytes: usize,
+) -> Vec<usize> {
+    let height = rect.height as usize;
+    if height == 0 || rect.width < 3 {
+        return Vec::new();
+    }
+
+    let buffer_len = state.buffer.len();
+    let large_file = buffer_len > large_file_threshold_bytes as usize;
+    let rows = if large_file {
+        sample_rows(state, height, estimated_line_length)
+    } else {
+        line_rows(state, viewport, height, estimated_line_length)
+    };
+    let span_start = rows.first().map_or(0, |row| row.start);
+    let span_end = if large_file {
+        buffer_len
+    } else {
+        rows.last().map_or(0, |row| row.end)
+    };
+    // Row covering `byte`: each row extends to the start of the next one,
+    // so markers between sampled rows land on the row above them.
+    let row_at = |byte: usize| -> Option<usize> {
+        if byte < span_start || byte > span_end {
+            return None;
+        }
+        rows.partition_point(|row| row.start <= byte).checked_sub(1)
+    };
+
+    let mut diagnostics: HashMap<usize, i32> = HashMap::new();
+    let mut search_rows = vec![false; rows.len()];
+    {
+        use crate::services::lsp::diagnostics::is_diagnostic_namespace;
+        for (overlay, range) in
+            state
+                .overlays
+                .query_viewport(span_start, span_end, &state.marker_list)
+        {
+            let Some(row) = row_at(range.start) else {
+                continue;
+            };
+            if is_diagnostic_namespace(overlay.namespace.as_ref()) {
+                let priority = diagnostics.entry(row).or_insert(overlay.priority);
+                *priority = (*priority).max(overlay.priority);
+            } else if overlay
+                .namespace
+                .as_ref()
+                .is_some_and(|ns| ns.as_str() == "search")
+            {
+                search_rows[row] = true;
+            }
+        }
+    }
+
+    let mut indicators: HashMap<usize, (Color, i32)> = HashMap::new();
+    let mut line_indicators: Vec<_> = state
+        .margins
+        .get_indicators_for_viewport(span_start, span_end, |byte| byte)
+        .into_iter()
+        .collect();
+    if !large_file {
+        line_indicators.extend(diff_indicators_for_viewport(state, span_start, span_end));
+    }
+    for (byte, indicator) in line_indicators {
+        if let Some(row) = row_at(byte) {
+            let entry = indicators
+                .entry(row)
+                .or_insert((indicator.color, indicator.priority));
+            if indicator.priority > entry.1 {
+                *entry = (indicator.color, indicator.priority);
+            }
+        }
+    }
+
+    let mut spans = if large_file || rows.is_empty() {
+        Vec::new()
+    } else {
+        state.highlighter.highlight_viewport(
+            &state.buffer,
+            span_start,
+            span_end,
+            theme,
+            highlight_context_bytes,
+        )
+    };
+    spans.sort_by_key(|span| span.range.start);
+    let mut colors = SpanCursor::new(&spans);
+
+    let top = viewport.top_byte;
+    let tab_size = state.buffer_settings.tab_size.max(1);
+    let cells = rect.width as usize - 2;
+    let buf = frame.buffer_mut();
+    for y in 0..height {
+        let screen_y = rect.y + y as u16;
+        let Some(row) = rows.get(y) else {
+            for x in 0..rect.width {
+                buf[(rect.x + x, screen_y)]
+                    .set_symbol(" ")
+                    .set_style(Style::default().bg(theme.editor_bg));
+            }
+            continue;
+        };
+        let row_end = rows.get(y + 1).map_or(span_end, |next| next.start);
+        let bg = if search_rows[y] {
+            theme.search_match_bg
+        } else if row.start <= visible_end && row_end > top {
+            theme.selection_bg
+        } else {
+            theme.editor_bg
+        };
+
+        let (marker, marker_fg) = match indicators.get(&y) {
+            Some((color, _)) => ("▎", *color),
+            None => (" ", theme.editor_fg),
+        };
+        buf[(rect.x, screen_y)]
+            .set_symbol(marker)
+            .set_style(Style::default().fg(marker_fg).bg(bg));
+
+        let mut dots = vec![0u8; cells];
+        let mut fgs: Vec<Option<Color>> = vec![None; cells];
+        for (dot_row, (line_start, text)) in row.lines.iter().enumerate() {
+            let mut col = 0;
+            for (offset, ch) in text.char_indices() {
+                let width = if ch == '\t' {
+                    tab_size - col % tab_size
+                } else {
+                    char_width(ch)
+                };
+                let dot_col = col / CHARS_PER_DOT;
+                let cell = dot_col / 2;
+                if cell >= cells {
+                    break;
+                }
+                if !ch.is_whitespace() {
+                    dots[cell] |= BRAILLE_DOTS[dot_row][dot_col % 2];
+                    if fgs[cell].is_none() {
+                        fgs[cell] = Some(
+                            colors
+                                .color_at(line_start + offset)
+                                .unwrap_or(theme.editor_fg),
+                        );
+                    }
+                }
+                col += width;
+            }
+        }
+        for (i, (bits, fg)) in dots.iter().zip(&fgs).enumerate() {
+            let mut utf8 = [0u8; 4];
+            let symbol: &str = if *bits == 0 {
+                " "
+            } else {
+                braille(*bits).encode_utf8(&mut utf8)
+            };
+            buf[(rect.x + 1 + i as u16, screen_y)]
+                .set_symbol(symbol)
+                .set_style(Style::default().fg(fg.unwrap_or(theme.editor_fg)).bg(bg));
+        }
+
+        let (marker, marker_fg) = match diagnostics.get(&y) {
+            Some(priority) => (
+                "▐",
+                inline_diagnostic_style(*priority, theme)
+                    .fg
+                    .unwrap_or(theme.diagnostic_error_fg),
+            ),
+            None => (" ", theme.editor_fg),
+        };
+        buf[(rect.x + rect.width - 1, screen_y)]
+            .set_symbol(marker)
+            .set_style(Style::default().fg(marker_fg).bg(bg));
+    }
+
+    rows.iter().map(|row| row.start).collect()
+}
+
+/// The braille character with the given dots raised.
+fn braille(bits: u8) -> char {
+    char::from_u32(0x2800 + bits as u32).unwrap_or(' ')
+}
+
+/// Rows of a small buffer: consecutive lines from the first line that
+/// keeps the viewport band on screen.
+fn line_rows(
+    state: &mut EditorState,
+    viewport: &Viewport,
+    height: usize,
+    estimated_line_length: usize,
+) -> Vec<MinimapRow> {
+    let buffer_len = state.buffer.len();
+    let total_lines = if buffer_len > 0 {
+        state.buffer.get_line_number(buffer_len - 1) + 1
+    } else {
+        1
+    };
+    let top_line = state
+        .buffer
+        .get_line_number(viewport.top_byte.min(buffer_len));
+    let first_line = first_minimap_line(
+        total_lines,
+        top_line,
+        viewport.height as usize,
+        height * LINES_PER_ROW,
+    );
+    let start = state.buffer.line_start_offset(first_line).unwrap_or(0);
+
+    let mut iter = state.buffer.line_iterator(start, estimated_line_length);
+    let mut rows = Vec::with_capacity(height);
+    while rows.len() < height {
+        let mut lines = Vec::with_capacity(LINES_PER_ROW);
+        while lines.len() < LINES_PER_ROW {
+            match iter.next_line() {
+                Some(line) => lines.push(line),
+                None => break,
+            }
+        }
+        let Some((last_start, last_text)) = lines.last() else {
+            break;
+        };
+        let end = last_start + last_text.len();
+        rows.push(MinimapRow {
+            start: lines[0].0,
+            end,
+            lines,
+        });
+    }
+    rows
+}
+
+/// Rows of a large buffer: for each row, the lines following an evenly
+/// spaced byte offset. Only a bounded chunk is read per row; the partial
+/// line at the offset is skipped unless the chunk holds no line break.
+fn sample_rows(
+    state: &mut EditorState,
+    height: usize,
+    estimated_line_length: usize,
+) -> Vec<MinimapRow> {
+    let buffer_len = state.buffer.len();
+    let chunk_len = estimated_line_length.max(1) * (LINES_PER_ROW + 1) * 2;
+    let mut rows: Vec<MinimapRow> = Vec::with_capacity(height);
+    for y in 0..height {
+        let offset = (buffer_len as u128 * y as u128 / height as u128) as usize;
+        let Ok(chunk) = state.buffer.get_text_range_mut(offset, chunk_len) else {
+            break;
+        };
+        let skip = match chunk.iter().position(|&b| b == b'\n') {
+            Some(newline) if offset > 0 => newline + 1,
+            _ => 0,
+        };
+        let start = offset + skip;
+        if start >= buffer_len || rows.last().is_some_and(|prev| start < prev.end) {
+            continue;
+        }
+        let mut lines = Vec::with_capacity(LINES_PER_ROW);
+        let mut pos = start;
+        for line in chunk[skip..]
+            .split_inclusive(|&b| b == b'\n')
+            .take(LINES_PER_ROW)
+        {
+            lines.push((pos, String::from_utf8_lossy(line).into_owned()));
+            pos += line.len();
+        }
+        rows.push(MinimapRow {
+            start,
+            end: pos,
+            lines,
+        });
+    }
+    rows
+}
+
+/// First line shown when the buffer has more lines than the minimap holds.
+/// The minimap scrolls in proportion to the viewport, so both ends of the
+/// buffer are reachable and the viewport's lines stay inside the minimap.
+fn first_minimap_line(
+    total_lines: usize,
+    top_line: usize,
+    viewport_lines: usize,
+    capacity: usize,
+) -> usize {
+    if total_lines <= capacity {
+        return 0;
+    }
+    let max_first = total_lines - capacity;
+    let max_top = total_lines.saturating_sub(viewport_lines).max(1);
+    top_line.min(max_top) * max_first / max_top
+}
+
+/// Looks up syntax colours for increasing byte offsets in spans sorted by
+/// start. Nested spans start later than their parents, so the most
+/// recently entered span that still covers the offset is the innermost.
+struct SpanCursor<'a> {
+    spans: &'a [HighlightSpan],
+    next: usize,
+    active: Vec<&'a HighlightSpan>,
+}
+
+impl<'a> SpanCursor<'a> {
+    fn new(spans: &'a [HighlightSpan]) -> Self {
+        Self {
+            spans,
+            next: 0,
+            active: Vec::new(),
+        }
+    }
+
+    fn color_at(&mut self, byte: usize) -> Option<Color> {
+        while let Some(span) = self.spans.get(self.next) {
+            if span.range.start > byte {
+                break;
+            }
+            self.active.push(span);
+            self.next += 1;
+        }
+        self.active.retain(|span| span.range.end > byte);
+        self.active.last().map(|span| span.color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimap_scrolls_in_proportion_to_the_viewport() {
+        // Fits entirely: never scrolls
+        assert_eq!(first_minimap_line(50, 30, 20, 80), 0);
+        // 200 lines, 80 shown, 20 on screen: top and bottom map to the ends
+        assert_eq!(first_minimap_line(200, 0, 20, 80), 0);
+        assert_eq!(first_minimap_line(200, 180, 20, 80), 120);
+        // Halfway through the buffer, the viewport stays inside the minimap
+        let first = first_minimap_line(200, 90, 20, 80);
+        assert!(first <= 90 && 90 + 20 <= first + 80);
+    }
+
+    #[test]
+    fn span_cursor_prefers_innermost_span() {
+        let span = |range: std::ops::Range<usize>, color| HighlightSpan {
+            range,
+            color,
+            category: None,
+        };
+        let spans = vec![span(0..10, Color::Red), span(2..4, Color::Blue)];
+        let mut cursor = SpanCursor::new(&spans);
+        assert_eq!(cursor.color_at(1), Some(Color::Red));
+        assert_eq!(cursor.color_at(3), Some(Color::Blue));
+        assert_eq!(cursor.color_at(5), Some(Color::Red));
+        assert_eq!(cursor.color_at(12), None);
+    }
+}
@@ -3,8 +3,9 @@
 //! This module is organized into two tiers:
 //!
 //! - **Self-contained leaves** (`spans`, `style`, `char_style`, `base_tokens`,
-//!   `transforms`, `view_data`, `folding`, `scrollbar`, `layout`, `gutter`,
-//!   `post_pass`) — none of these depend on any shared render-time carrier.
+//!   `transforms`, `view_data`, `folding`, `scrollbar`, `minimap`,
+//!   `sticky_scroll`, `layout`, `gutter`, `hex`, `post_pass`) — none of
+//!   these depend on any shared render-time carrier.
 //! - **Orchestration** (`orchestration::*`) — the only files that share
 //!   `SelectionContext` / `DecorationContext`. Quarantined in a subdirectory
 //!   so the coupling is visible from `ls` alone.
@@ -16,11 +17,14 @@ pub(crate) mod base_tokens;
 mod char_style;
 mod folding;
 mod gutter;
+mod hex;
 mod layout;
+mod minimap;
 mod orchestration;
 mod post_pass;
 mod scrollbar;
 mod spans;
+mod sticky_scroll;
 mod style;
 pub(crate) mod transforms;
 mod view_data;
@@ -87,9 +91,13 @@ impl SplitRenderer {
         software_cursor_only: bool,
         show_vertical_scrollbar: bool,
         show_horizontal_scrollbar: bool,
+        minimap_width: u16,
+        sticky_scroll_max_lines: usize,
+        breadcrumbs: Option<&HashMap<LeafId, crate::view::ui::breadcrumbs::BreadcrumbBar>>,
         diagnostics_inline_text: bool,
         show_tilde: bool,
         highlight_current_column: bool,
+        show_indent_guides: bool,
         cell_theme_map: &mut Vec<crate::app::types::CellThemeInfo>,
         screen_width: u16,
         pending_hardware_cursor: &mut Option<(u16, u16)>,
@@ -107,6 +115,9 @@ impl SplitRenderer {
             u16,
             u16,
         )>,
+        Vec<(LeafId, BufferId, Rect, Vec<usize>)>,
+        Vec<(LeafId, BufferId, Rect, Vec<usize>)>,
+        HashMap<LeafId, crate::view::ui::breadcrumbs::BreadcrumbLayout>,
     ) {
         orchestration::render_content(
             frame,
@@ -139,9 +150,13 @@ impl SplitRenderer {
             software_cursor_only,
             show_vertical_scrollbar,
             show_horizontal_scrollbar,
+            minimap_width,
+            sticky_scroll_max_lines,
+            breadcrumbs,
             diagnostics_inline_text,
             show_tilde,
             highlight_current_column,
+            show_indent_guides,
             cell_theme_map,
             screen_width,
             pending_hardware_cursor,
@@ -165,6 +180,8 @@ impl SplitRenderer {
         tab_bar_visible: bool,
         show_vertical_scrollbar: bool,
         show_horizontal_scrollbar: bool,
+        minimap_width: u16,
+        show_breadcrumbs: bool,
         diagnostics_inline_text: bool,
         show_tilde: bool,
     ) -> HashMap<LeafId, Vec<ViewLineMapping>> {
@@ -184,6 +201,8 @@ impl SplitRenderer {
             tab_bar_visible,
             show_vertical_scrollbar,
             show_horizontal_scrollbar,
+            minimap_width,
+            show_breadcrumbs,
             diagnostics_inline_text,
             show_tilde,
         )
@@ -227,6 +246,7 @@ impl SplitRenderer {
         diagnostics_inline_text: bool,
         show_tilde: bool,
         highlight_current_column: bool,
+        show_indent_guides: bool,
         cell_theme_map: &mut Vec<crate::app::types::CellThemeInfo>,
         screen_width: u16,
     ) -> Vec<crate::app::types::ViewLineMapping> {
@@ -270,6 +290,7 @@ impl SplitRenderer {
             diagnostics_inline_text,
             show_tilde,
             highlight_current_column,
+            show_indent_guides,
             cell_theme_map,
             screen_width,
             &mut sink,
@@ -17,9 +17,14 @@ use std::ops::Range;
 /// Per-viewport selection state used by the render loop.
 pub(crate) struct SelectionContext {
     pub ranges: Vec<Range<usize>>,
+    /// Block selections as (start_line, start_col, end_line, end_col), in
+    /// display columns with `end_col` exclusive
     pub block_rects: Vec<(usize, usize, usize, usize)>,
     pub cursor_positions: Vec<usize>,
     pub primary_cursor_position: usize,
+    /// Columns the primary cursor sits past the end of its line, when a
+    /// block selection extends into virtual space
+    pub primary_cursor_virtual_cols: usize,
 }
 
 /// Per-viewport decorations (overlays, diagnostics, indicators, virtual text).
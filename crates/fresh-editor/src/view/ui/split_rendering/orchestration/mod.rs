@@ -17,22 +17,27 @@ pub(super) mod render_composite;
 pub(super) mod render_line;
 
 use super::base_tokens::build_base_tokens;
+use super::hex::render_hex_view;
 use super::layout::{
     render_separator, resolve_view_preferences, split_buffers_for_tabs, split_layout,
     sync_viewport_to_content, SplitLayout,
 };
+use super::minimap::render_minimap;
 use super::scrollbar::{
     compute_max_line_length, render_composite_scrollbar, render_horizontal_scrollbar,
     render_scrollbar, scrollbar_line_counts,
 };
+use super::sticky_scroll::render_sticky_scroll;
 use crate::app::types::ViewLineMapping;
 use crate::app::BufferMetadata;
 use crate::model::buffer::Buffer;
 use crate::model::event::{BufferId, EventLog, LeafId, SplitDirection};
 use crate::primitives::ansi_background::AnsiBackground;
-use crate::state::EditorState;
+use crate::state::{EditorState, ViewMode};
 use crate::view::folding::FoldManager;
+use crate::view::hex_view::HexViewState;
 use crate::view::split::SplitManager;
+use crate::view::ui::breadcrumbs::{render_breadcrumbs, BreadcrumbBar, BreadcrumbLayout};
 use crate::view::ui::tabs::TabsRenderer;
 use ratatui::layout::Rect;
 use ratatui::style::Style;
@@ -48,6 +53,9 @@ use std::collections::HashMap;
 
 /// # Returns
 /// * Vec of (split_id, buffer_id, content_rect, scrollbar_rect, thumb_start, thumb_end) for mouse handling
+/// * Vec of (split_id, buffer_id, minimap_rect, first byte of each minimap row) for minimap clicks
+/// * Vec of (split_id, buffer_id, sticky_rect, header byte of each pinned row) for sticky scroll clicks
+/// * Breadcrumb bar hit areas per split
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 pub(crate) fn render_content(
@@ -84,9 +92,13 @@ pub(crate) fn render_content(
     software_cursor_only: bool,
     show_vertical_scrollbar: bool,
     show_horizontal_scrollbar: bool,
+    minimap_width: u16,
+    sticky_scroll_max_lines: usize,
+    breadcrumbs: Option<&HashMap<LeafId, BreadcrumbBar>>,
     diagnostics_inline_text: bool,
     show_tilde: bool,
     highlight_current_column: bool,
+    show_indent_guides: bool,
     cell_theme_map: &mut Vec<crate::app::types::CellThemeInfo>,
     screen_width: u16,
     pending_hardware_cursor: &mut Option<(u16, u16)>,
@@ -104,6 +116,9 @@ pub(crate) fn render_content(
         u16,
         u16,
     )>, // hit areas for separators inside active Grouped subtrees
+    Vec<(LeafId, BufferId, Rect, Vec<usize>)>,          // minimap areas
+    Vec<(LeafId, BufferId, Rect, Vec<usize>)>,          // sticky scroll areas
+    HashMap<LeafId, BreadcrumbLayout>,                  // breadcrumb bars
 ) {
     let _span = tracing::trace_span!("render_content").entered();
 
@@ -152,8 +167,10 @@ pub(crate) fn render_content(
                 let main_layout = split_layout(
                     *split_area,
                     split_tab_bar_visible,
+                    false,
                     show_vertical_scrollbar,
                     show_horizontal_scrollbar,
+                    0,
                 );
                 let inner_leaves = grouped.get_leaves_with_rects(main_layout.content_rect);
                 visible_buffers.push((
@@ -203,6 +220,9 @@ pub(crate) fn render_content(
     let mut close_split_areas = Vec::new();
     let mut maximize_split_areas = Vec::new();
     let mut view_line_mappings: HashMap<LeafId, Vec<ViewLineMapping>> = HashMap::new();
+    let mut minimap_areas: Vec<(LeafId, BufferId, Rect, Vec<usize>)> = Vec::new();
+    let mut sticky_scroll_areas: Vec<(LeafId, BufferId, Rect, Vec<usize>)> = Vec::new();
+    let mut breadcrumb_layouts: HashMap<LeafId, BreadcrumbLayout> = HashMap::new();
 
     // Render each split.
     for (main_split_id, split_id, buffer_id, split_area, kind) in visible_buffers {
@@ -211,13 +231,12 @@ pub(crate) fn render_content(
         let skip_content = kind == RenderKind::GroupTabBarOnly;
         let _ = main_split_id; // no longer needed below, kept for clarity
 
-        // Suppress chrome (tab bar) for splits in buffer groups
-        let split_tab_bar_visible = !is_inner_group_leaf
-            && tab_bar_visible
-            && !split_view_states
-                .as_deref()
-                .and_then(|svs| svs.get(&split_id))
-                .is_some_and(|vs| vs.suppress_chrome);
+        // Suppress chrome (tab bar, breadcrumbs) for splits in buffer groups
+        let suppress_chrome = split_view_states
+            .as_deref()
+            .and_then(|svs| svs.get(&split_id))
+            .is_some_and(|vs| vs.suppress_chrome);
+        let split_tab_bar_visible = !is_inner_group_leaf && tab_bar_visible && !suppress_chrome;
         // Hide tildes per-split (e.g., for buffer group panels)
         let split_show_tilde = show_tilde
             && !split_view_states
@@ -230,11 +249,32 @@ pub(crate) fn render_content(
         // scrollbar — their content is pinned to the panel size.
         let is_non_scrollable = buffers.get(&buffer_id).is_some_and(|s| !s.scrollable);
         let panel_show_vscroll = show_vertical_scrollbar && !is_non_scrollable;
+        // The minimap, sticky scroll and breadcrumbs only accompany the
+        // regular text view: not composite or hex views, placeholders, or
+        // group panels.
+        let is_text_view = !(is_non_scrollable
+            || buffers
+                .get(&buffer_id)
+                .is_some_and(|s| s.is_composite_buffer)
+            || buffer_metadata
+                .get(&buffer_id)
+                .is_some_and(|m| m.synthetic_placeholder)
+            || split_view_states
+                .as_deref()
+                .and_then(|svs| svs.get(&split_id))
+                .is_some_and(|vs| vs.hex_view.is_some()));
+        let split_minimap_width = if is_text_view { minimap_width } else { 0 };
+        let split_breadcrumbs = breadcrumbs.is_some()
+            && is_text_view
+            && !is_inner_group_leaf
+            && !skip_content
+            && !suppress_chrome;
 
         let layout = if is_inner_group_leaf {
             // Inner leaf: split_area IS the content rect already.
             SplitLayout {
                 tabs_rect: Rect::new(split_area.x, split_area.y, 0, 0),
+                breadcrumb_rect: Rect::new(split_area.x, split_area.y, 0, 0),
                 content_rect: Rect::new(
                     split_area.x,
                     split_area.y,
@@ -249,14 +289,17 @@ pub(crate) fn render_content(
                     if panel_show_vscroll { 1 } else { 0 },
                     split_area.height,
                 ),
+                minimap_rect: Rect::new(split_area.x, split_area.y, 0, 0),
                 horizontal_scrollbar_rect: Rect::new(0, 0, 0, 0),
             }
         } else {
             split_layout(
                 split_area,
                 split_tab_bar_visible,
+                split_breadcrumbs,
                 show_vertical_scrollbar && !is_non_scrollable,
                 show_horizontal_scrollbar && !is_non_scrollable,
+                split_minimap_width,
             )
         };
         let (split_buffers, tab_scroll_offset) = if is_inner_group_leaf {
@@ -362,6 +405,19 @@ pub(crate) fn render_content(
             }
         }
 
+        if let Some(breadcrumbs) = breadcrumbs.filter(|_| split_breadcrumbs) {
+            let bar = breadcrumbs.get(&split_id).cloned().unwrap_or_default();
+            let segments = render_breadcrumbs(frame, layout.breadcrumb_rect, &bar, theme);
+            breadcrumb_layouts.insert(
+                split_id,
+                BreadcrumbLayout {
+                    buffer_id,
+                    bar_area: layout.breadcrumb_rect,
+                    segments,
+                },
+            );
+        }
+
         // For GroupTabBarOnly entries we've already rendered the tab bar;
         // skip buffer content rendering so the group's inner leaves can
         // draw into the content rect without being overwritten.
@@ -499,6 +555,57 @@ pub(crate) fn render_content(
                 continue;
             }
 
+            // Hex view replaces the text pipeline with a byte dump
+            if let Some(view_state) = split_view_states
+                .as_deref_mut()
+                .and_then(|svs| svs.get_mut(&split_id))
+                .filter(|vs| vs.hex_view.is_some())
+            {
+                view_state
+                    .viewport
+                    .resize(layout.content_rect.width, layout.content_rect.height);
+                let cursor = view_state.cursors.primary().position;
+                let hex = view_state
+                    .hex_view
+                    .as_mut()
+                    .expect("hex view checked above");
+                render_hex_view(
+                    frame,
+                    layout.content_rect,
+                    &mut state.buffer,
+                    hex,
+                    cursor,
+                    is_active,
+                    hide_cursor,
+                    theme,
+                    pending_hardware_cursor,
+                );
+
+                let (thumb_start, thumb_end) = if panel_show_vscroll {
+                    render_composite_scrollbar(
+                        frame,
+                        layout.scrollbar_rect,
+                        HexViewState::row_count(state.buffer.len()),
+                        hex.top_row,
+                        layout.content_rect.height as usize,
+                        is_active,
+                        theme,
+                    )
+                } else {
+                    (0, 0)
+                };
+                split_areas.push((
+                    split_id,
+                    buffer_id,
+                    layout.content_rect,
+                    layout.scrollbar_rect,
+                    thumb_start,
+                    thumb_end,
+                ));
+                view_line_mappings.insert(split_id, Vec::new());
+                continue;
+            }
+
             // Get viewport from SplitViewState (authoritative source)
             // We need to get it mutably for sync operations
             // Use as_deref() to get Option<&HashMap> for read-only operations
@@ -568,6 +675,8 @@ pub(crate) fn render_content(
                 .map(|vs| &mut vs.folds)
                 .unwrap_or(&mut empty_folds);
 
+            let source_view = view_prefs.view_mode == ViewMode::Source;
+
             let _render_buf_span = tracing::trace_span!("render_buffer_in_split").entered();
             let split_view_mappings = render_buffer_in_split(
                 frame,
@@ -600,6 +709,7 @@ pub(crate) fn render_content(
                 diagnostics_inline_text,
                 split_show_tilde,
                 highlight_current_column && state.show_cursors,
+                show_indent_guides && is_text_view && source_view,
                 cell_theme_map,
                 screen_width,
                 pending_hardware_cursor,
@@ -607,6 +717,53 @@ pub(crate) fn render_content(
 
             drop(_render_buf_span);
 
+            // Page view centres its text, away from where the pinned
+            // lines would be drawn.
+            if sticky_scroll_max_lines > 0 && is_text_view && source_view {
+                let _span = tracing::trace_span!("render_sticky_scroll").entered();
+                let headers = render_sticky_scroll(
+                    frame,
+                    state,
+                    &viewport,
+                    &split_view_mappings,
+                    split_cursors.primary().position,
+                    layout.content_rect,
+                    sticky_scroll_max_lines,
+                    theme,
+                    highlight_context_bytes,
+                );
+                if !headers.is_empty() {
+                    let rect = Rect::new(
+                        layout.content_rect.x,
+                        layout.content_rect.y,
+                        layout.content_rect.width,
+                        headers.len() as u16,
+                    );
+                    sticky_scroll_areas.push((split_id, buffer_id, rect, headers));
+                }
+            }
+
+            if layout.minimap_rect.width > 0 {
+                let _span = tracing::trace_span!("render_minimap").entered();
+                let visible_end = split_view_mappings
+                    .iter()
+                    .map(|m| m.line_end_byte)
+                    .max()
+                    .unwrap_or(viewport.top_byte);
+                let row_bytes = render_minimap(
+                    frame,
+                    state,
+                    &viewport,
+                    visible_end,
+                    layout.minimap_rect,
+                    theme,
+                    large_file_threshold_bytes,
+                    estimated_line_length,
+                    highlight_context_bytes,
+                );
+                minimap_areas.push((split_id, buffer_id, layout.minimap_rect, row_bytes));
+            }
+
             // Store view line mappings for mouse click handling
             view_line_mappings.insert(split_id, split_view_mappings);
 
@@ -734,8 +891,10 @@ pub(crate) fn render_content(
                 let main_layout = split_layout(
                     *split_area,
                     split_tab_bar_visible,
+                    false,
                     show_vertical_scrollbar,
                     show_horizontal_scrollbar,
+                    0,
                 );
                 if let crate::view::split::SplitNode::Grouped { layout, .. } = grouped {
                     for (id, direction, x, y, length) in
@@ -757,6 +916,9 @@ pub(crate) fn render_content(
         view_line_mappings,
         horizontal_scrollbar_areas,
         grouped_separator_areas,
+        minimap_areas,
+        sticky_scroll_areas,
+        breadcrumb_layouts,
     )
 }
 /// Layout-only path: computes view_line_mappings for all visible splits
@@ -779,6 +941,8 @@ pub(crate) fn compute_content_layout(
     tab_bar_visible: bool,
     show_vertical_scrollbar: bool,
     show_horizontal_scrollbar: bool,
+    minimap_width: u16,
+    show_breadcrumbs: bool,
     diagnostics_inline_text: bool,
     show_tilde: bool,
 ) -> HashMap<LeafId, Vec<ViewLineMapping>> {
@@ -789,17 +953,19 @@ pub(crate) fn compute_content_layout(
     for (split_id, buffer_id, split_area) in visible_buffers {
         let is_active = split_id == active_split_id;
 
-        // Suppress chrome (tab bar) for splits in buffer groups
-        let split_tab_bar_visible = tab_bar_visible
-            && !split_view_states
-                .get(&split_id)
-                .is_some_and(|vs| vs.suppress_chrome);
+        // Suppress chrome (tab bar, breadcrumbs) for splits in buffer groups
+        let suppress_chrome = split_view_states
+            .get(&split_id)
+            .is_some_and(|vs| vs.suppress_chrome);
+        let split_tab_bar_visible = tab_bar_visible && !suppress_chrome;
 
         let layout = split_layout(
             split_area,
             split_tab_bar_visible,
+            show_breadcrumbs && !suppress_chrome,
             show_vertical_scrollbar,
             show_horizontal_scrollbar,
+            minimap_width,
         );
 
         let state = match buffers.get_mut(&buffer_id) {
@@ -6,6 +6,7 @@
 use super::super::folding::{diff_indicators_for_viewport, fold_indicators_for_viewport};
 use super::super::style::inline_diagnostic_style;
 use super::contexts::{DecorationContext, SelectionContext};
+use crate::model::block_selection::{block_cursor_position, display_column, line_text, BlockRect};
 use crate::model::cursor::{Cursors, SelectionMode};
 use crate::state::{EditorState, ViewMode};
 use crate::view::folding::FoldManager;
@@ -24,6 +25,7 @@ pub(crate) fn selection_context(state: &EditorState, cursors: &Cursors) -> Selec
             block_rects: Vec::new(),
             cursor_positions: Vec::new(),
             primary_cursor_position: cursors.primary().position,
+            primary_cursor_virtual_cols: 0,
         };
     }
 
@@ -43,39 +45,35 @@ pub(crate) fn selection_context(state: &EditorState, cursors: &Cursors) -> Selec
     // over selections in monotonic byte order.
     ranges.sort_by_key(|r| r.start);
 
+    let tab_size = state.buffer_settings.tab_size;
     let mut block_rects: Vec<(usize, usize, usize, usize)> = cursors
         .iter()
-        .filter_map(|(_, cursor)| {
-            if cursor.selection_mode == SelectionMode::Block {
-                if let Some(anchor) = cursor.block_anchor {
-                    let cur_line = state.buffer.get_line_number(cursor.position);
-                    let cur_line_start = state.buffer.line_start_offset(cur_line).unwrap_or(0);
-                    let cur_col = cursor.position.saturating_sub(cur_line_start);
-
-                    Some((
-                        anchor.line.min(cur_line),
-                        anchor.column.min(cur_col),
-                        anchor.line.max(cur_line),
-                        anchor.column.max(cur_col),
-                    ))
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        })
+        .filter_map(|(_, cursor)| BlockRect::from_cursor(&state.buffer, cursor, tab_size))
+        .map(|rect| (rect.start_line, rect.start_col, rect.end_line, rect.end_col))
         .collect();
     // Sort by start_line for the render loop's per-line active-set sweep.
     block_rects.sort_by_key(|(start_line, _, _, _)| *start_line);
 
     let cursor_positions: Vec<usize> = cursors.iter().map(|(_, cursor)| cursor.position).collect();
 
+    // A block-mode cursor keeps its display column past the end of a short
+    // line; the byte position stops at the line end
+    let primary = cursors.primary();
+    let primary_cursor_virtual_cols = if primary.has_block_selection() {
+        let pos = block_cursor_position(&state.buffer, primary, tab_size);
+        let text = line_text(&state.buffer, pos.line);
+        pos.column
+            .saturating_sub(display_column(&text, text.len(), tab_size))
+    } else {
+        0
+    };
+
     SelectionContext {
         ranges,
         block_rects,
         cursor_positions,
-        primary_cursor_position: cursors.primary().position,
+        primary_cursor_position: primary.position,
+        primary_cursor_virtual_cols,
     }
 }
 
@@ -178,12 +176,13 @@ pub(crate) fn decoration_context(
     let mut overlay_position_index: Vec<usize> = (0..viewport_overlays.len()).collect();
     overlay_position_index.sort_by_key(|&i| viewport_overlays[i].1.start);
 
-    // Use the lsp-diagnostic namespace to identify diagnostic overlays.
-    let diagnostic_ns = crate::services::lsp::diagnostics::lsp_diagnostic_namespace();
+    // Use the diagnostic namespaces (LSP and task output) to identify
+    // diagnostic overlays.
+    use crate::services::lsp::diagnostics::is_diagnostic_namespace;
     let diagnostic_lines: HashSet<usize> = viewport_overlays
         .iter()
         .filter_map(|(overlay, range)| {
-            if overlay.namespace.as_ref() == Some(&diagnostic_ns) {
+            if is_diagnostic_namespace(overlay.namespace.as_ref()) {
                 return Some(indent_folding::find_line_start_byte(
                     &state.buffer,
                     range.start,
@@ -197,7 +196,7 @@ pub(crate) fn decoration_context(
     let diagnostic_inline_texts: HashMap<usize, (String, Style)> = if diagnostics_inline_text {
         let mut by_line: HashMap<usize, (String, Style, i32)> = HashMap::new();
         for (overlay, range) in &viewport_overlays {
-            if overlay.namespace.as_ref() != Some(&diagnostic_ns) {
+            if !is_diagnostic_namespace(overlay.namespace.as_ref()) {
                 continue;
             }
             if let Some(ref message) = overlay.message {
@@ -12,7 +12,8 @@ use super::super::layout::{
     calculate_compose_layout, calculate_view_anchor, calculate_viewport_end, ComposeLayout,
 };
 use super::super::post_pass::{
-    apply_background_to_lines, render_column_guides, render_cursor_column_bg, render_ruler_bg,
+    apply_background_to_lines, render_column_guides, render_cursor_column_bg, render_indent_guides,
+    render_ruler_bg,
 };
 use super::super::view_data::build_view_data;
 use super::contexts::SelectionContext;
@@ -398,6 +399,7 @@ pub(crate) fn draw_buffer_in_split(
     rulers: &[usize],
     compose_column_guides: Option<Vec<u16>>,
     highlight_current_column: bool,
+    show_indent_guides: bool,
     pending_hardware_cursor: &mut Option<(u16, u16)>,
 ) {
     let render_area = layout_output.render_area;
@@ -471,6 +473,23 @@ pub(crate) fn draw_buffer_in_split(
         );
     }
 
+    // Render indentation guides into the blank cells of each row
+    if show_indent_guides {
+        render_indent_guides(
+            frame,
+            &state.buffer,
+            state.buffer_settings.tab_size,
+            cursors.primary().position,
+            &layout_output.view_line_mappings,
+            render_area,
+            gutter_width,
+            layout_output.render_output.content_lines_rendered,
+            layout_output.left_column,
+            theme.indent_guide_fg,
+            theme.indent_guide_active_fg,
+        );
+    }
+
     // Highlight the cursor column (same bg tint as the current line) when
     // `highlight_current_column` is enabled and the split is active.
     if highlight_current_column && is_active && !hide_cursor {
@@ -572,6 +591,7 @@ pub(crate) fn render_buffer_in_split(
     diagnostics_inline_text: bool,
     show_tilde: bool,
     highlight_current_column: bool,
+    show_indent_guides: bool,
     cell_theme_map: &mut Vec<CellThemeInfo>,
     screen_width: u16,
     pending_hardware_cursor: &mut Option<(u16, u16)>,
@@ -619,6 +639,7 @@ pub(crate) fn render_buffer_in_split(
         rulers,
         compose_column_guides,
         highlight_current_column,
+        show_indent_guides,
         pending_hardware_cursor,
     );
 
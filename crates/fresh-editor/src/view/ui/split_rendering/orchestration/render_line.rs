@@ -385,7 +385,6 @@ pub(crate) fn render_view_lines(input: LineRenderInput<'_>) -> LineRenderOutput
         );
 
         // Check if this line has any selected text
-        let mut byte_index = 0; // Byte offset in line_content string
         let mut display_char_idx = 0usize; // Character index in text (for char_source_bytes)
         let mut col_offset = 0usize; // Visual column position
 
@@ -512,7 +511,6 @@ pub(crate) fn render_view_lines(input: LineRenderInput<'_>) -> LineRenderOutput
                                 have_cursor = true;
                             }
                         }
-                        byte_index += ch.len_utf8();
                         display_char_idx += 1;
                         // Note: col_offset not incremented - ANSI chars have 0 visual width
                         continue;
@@ -524,7 +522,7 @@ pub(crate) fn render_view_lines(input: LineRenderInput<'_>) -> LineRenderOutput
             };
 
             // Performance: skip expensive style calculations for characters beyond visible range
-            // Use visible_char_count (not byte_index) since ANSI codes don't take up visible space
+            // Use visible_char_count (not byte offsets) since ANSI codes don't take up visible space
             if visible_char_count > max_chars_to_process {
                 // Fast path: skip remaining characters without processing
                 // This is critical for performance with very long lines (e.g., 100KB single line)
@@ -558,24 +556,22 @@ pub(crate) fn render_view_lines(input: LineRenderInput<'_>) -> LineRenderOutput
                 // Also check for block/rectangular selections (uses gutter_num which is
                 // the line number for small files — block_rects stores line numbers).
                 // Block active set is refreshed once per line as `gutter_num` advances.
-                if block_last_line != Some(gutter_num) {
-                    active_block.retain(|&i| block_selections[i].2 >= gutter_num);
-                    while block_next_idx < block_selections.len() {
-                        let (start_line, _, _, _) = block_selections[block_next_idx];
-                        if start_line > gutter_num {
-                            break;
-                        }
-                        if block_selections[block_next_idx].2 >= gutter_num {
-                            active_block.push(block_next_idx);
-                        }
-                        block_next_idx += 1;
-                    }
-                    block_last_line = Some(gutter_num);
-                }
-                let is_in_block_selection = active_block.iter().any(|&i| {
-                    let (_, start_col, _, end_col) = block_selections[i];
-                    byte_index >= start_col && byte_index <= end_col
-                });
+                // Block columns are display columns, so they are compared
+                // against `col_offset` on the line's first visual row.
+                refresh_active_blocks(
+                    block_selections,
+                    &mut active_block,
+                    &mut block_next_idx,
+                    &mut block_last_line,
+                    gutter_num,
+                );
+                let is_in_block_selection = !is_continuation
+                    && active_block.iter().any(|&i| {
+                        let (_, start_col, _, end_col) = block_selections[i];
+                        col_offset >= start_col
+                            && (col_offset < end_col
+                                || (start_col == end_col && col_offset == start_col))
+                    });
 
                 // For primary cursor in active split, terminal hardware cursor provides
                 // visual indication, so we can still show selection background.
@@ -871,7 +867,6 @@ pub(crate) fn render_view_lines(input: LineRenderInput<'_>) -> LineRenderOutput
                 }
             }
 
-            byte_index += ch.len_utf8();
             display_char_idx += 1; // Increment character index for next lookup
                                    // col_offset tracks visual column position (for indexing into visual_to_char).
                                    // We read the per-char visual column that view_pipeline assigned so that
@@ -993,6 +988,56 @@ pub(crate) fn render_view_lines(input: LineRenderInput<'_>) -> LineRenderOutput
             }
         }
 
+        // Block selections reaching past the end of this line: paint the
+        // part that lies in virtual space. Only for unwrapped lines, whose
+        // single row holds every column of the line.
+        let line_wraps = view_lines
+            .get(view_iter_idx)
+            .is_some_and(|next| next.line_start.is_continuation());
+        if !is_continuation && !line_wraps && !block_selections.is_empty() {
+            refresh_active_blocks(
+                block_selections,
+                &mut active_block,
+                &mut block_next_idx,
+                &mut block_last_line,
+                gutter_num,
+            );
+            let text_end_col = col_offset.saturating_sub(usize::from(line_has_newline));
+            let block = active_block
+                .iter()
+                .map(|&i| block_selections[i])
+                .filter(|&(_, start_col, _, end_col)| end_col.max(start_col + 1) > text_end_col)
+                .max_by_key(|&(_, _, _, end_col)| end_col);
+            if let Some((_, start_col, _, end_col)) = block {
+                span_acc.flush(&mut line_spans, &mut line_view_map);
+                let content_width = render_area.width.saturating_sub(gutter_width as u16) as usize;
+                let used: usize = line_spans.iter().map(|span| span.width()).sum::<usize>();
+                let used = used.saturating_sub(gutter_width);
+                let view_end = left_col + content_width;
+                let screen_col = left_col + used;
+                let fill_start = start_col.max(text_end_col).max(screen_col).min(view_end);
+                let fill_end = end_col.max(start_col + 1).min(view_end);
+                if fill_start > screen_col {
+                    let gap_bg = if is_on_cursor_line && highlight_current_line && is_active {
+                        theme.current_line_bg
+                    } else {
+                        theme.editor_bg
+                    };
+                    line_spans.push(Span::styled(
+                        " ".repeat(fill_start - screen_col),
+                        Style::default().bg(gap_bg),
+                    ));
+                }
+                if fill_end > fill_start {
+                    line_spans.push(Span::styled(
+                        " ".repeat(fill_end - fill_start),
+                        Style::default().bg(theme.selection_bg),
+                    ));
+                }
+                rendered_cols = rendered_cols.max(fill_end.max(fill_start) - left_col);
+            }
+        }
+
         // ViewLines are already wrapped (Break tokens became newlines in ViewLineIterator)
         // so each line is one visual line - no need to wrap again
         let current_y = lines.len() as u16;
@@ -1525,6 +1570,13 @@ pub(crate) fn render_view_lines(input: LineRenderInput<'_>) -> LineRenderOutput
         lines.push(Line::styled(eof_line, eof_style));
     }
 
+    // A block-mode cursor in virtual space sits past its line's last byte
+    if have_cursor && selection.primary_cursor_virtual_cols > 0 {
+        cursor_screen_x = cursor_screen_x
+            .saturating_add(selection.primary_cursor_virtual_cols as u16)
+            .min(render_area.width.saturating_sub(1));
+    }
+
     LineRenderOutput {
         lines,
         cursor: have_cursor.then_some((cursor_screen_x, cursor_screen_y)),
@@ -1533,3 +1585,30 @@ pub(crate) fn render_view_lines(input: LineRenderInput<'_>) -> LineRenderOutput
         view_line_mappings,
     }
 }
+
+/// Bring the block-selection active set up to `line`: drop blocks ending
+/// above it and pick up those starting at or above it. `block_selections`
+/// is sorted by start line.
+fn refresh_active_blocks(
+    block_selections: &[(usize, usize, usize, usize)],
+    active_block: &mut Vec<usize>,
+    block_next_idx: &mut usize,
+    block_last_line: &mut Option<usize>,
+    line: usize,
+) {
+    if *block_last_line == Some(line) {
+        return;
+    }
+    active_block.retain(|&i| block_selections[i].2 >= line);
+    while *block_next_idx < block_selections.len() {
+        let (start_line, _, end_line, _) = block_selections[*block_next_idx];
+        if start_line > line {
+            break;
+        }
+        if end_line >= line {
+            active_block.push(*block_next_idx);
+        }
+        *block_next_idx += 1;
+    }
+    *block_last_line = Some(line);
+}
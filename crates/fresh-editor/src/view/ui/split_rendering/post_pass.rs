@@ -5,7 +5,9 @@
 
 use super::spans::compress_chars;
 use crate::app::types::ViewLineMapping;
+use crate::model::buffer::Buffer;
 use crate::primitives::ansi_background::AnsiBackground;
+use crate::view::indent_guides::IndentGuides;
 use crate::view::overlay::Overlay;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Style};
@@ -95,6 +97,86 @@ pub(super) fn render_ruler_bg(
     }
 }
 
+/// Draw indentation guides into the rendered rows of a buffer.
+///
+/// Guides only go in the leading run of indentation and empty cells of a
+/// row, never over text, so a row continuing a wrapped line shows just the
+/// guides its hanging indent leaves room for. The guide of the block
+/// holding `cursor` is drawn in `active_color`.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn render_indent_guides(
+    frame: &mut Frame,
+    buffer: &Buffer,
+    tab_size: usize,
+    cursor: usize,
+    view_line_mappings: &[ViewLineMapping],
+    render_area: Rect,
+    gutter_width: usize,
+    content_height: usize,
+    left_column: usize,
+    color: Color,
+    active_color: Color,
+) {
+    let rows = &view_line_mappings[..content_height
+        .min(render_area.height as usize)
+        .min(view_line_mappings.len())];
+    let Some(start) = rows
+        .iter()
+        .filter_map(|line| line.char_source_bytes.iter().flatten().next())
+        .min()
+    else {
+        return;
+    };
+    let end = rows
+        .iter()
+        .map(|line| line.line_end_byte)
+        .max()
+        .unwrap_or(*start);
+    let guides = IndentGuides::around(buffer, *start..end + 1, cursor, tab_size);
+
+    let content_x = render_area.x + gutter_width as u16;
+    let width = (render_area.width as usize).saturating_sub(gutter_width);
+    let buf = frame.buffer_mut();
+    for (row, mapping) in rows.iter().enumerate() {
+        // Rows of virtual text belong to no line
+        let Some(line) = mapping
+            .char_source_bytes
+            .iter()
+            .flatten()
+            .next()
+            .and_then(|&byte| guides.line_at(byte))
+        else {
+            continue;
+        };
+        let y = render_area.y + row as u16;
+        let leading = (0..width)
+            .take_while(
+                |&col| match mapping.char_source_bytes.get(col).copied().flatten() {
+                    Some(byte) => line.in_indent(byte),
+                    None => buf[(content_x + col as u16, y)].symbol() == " ",
+                },
+            )
+            .count();
+        for column in guides.columns(&line) {
+            let Some(col) = column.checked_sub(left_column) else {
+                continue;
+            };
+            if col >= leading {
+                break;
+            }
+            let fg = if guides
+                .active()
+                .is_some_and(|active| active.contains(column, line.start))
+            {
+                active_color
+            } else {
+                color
+            };
+            buf[(content_x + col as u16, y)].set_symbol("│").set_fg(fg);
+        }
+    }
+}
+
 /// Post-process the rendered frame to apply OSC 8 hyperlink escape sequences
 /// for any overlays that have a URL set.
 ///
@@ -0,0 +1,262 @@
+//! Sticky scroll rows: the header lines of the scopes enclosing the top of
+//! a split, drawn over its first rows after the buffer itself.
+//!
+//! Scope discovery and header selection live in
+//! [`crate::view::sticky_scroll`]; this module picks the scope source for a
+//! buffer and draws the pinned lines with their gutter. Headers are never
+//! pinned over the row holding the primary cursor, so the cursor stays
+//! visible.
+
+use super::spans::span_color_at;
+use crate::app::types::ViewLineMapping;
+use crate::primitives::display_width::char_width;
+use crate::primitives::syntax_scopes::{scope_block, scope_window, syntax_scopes};
+use crate::state::EditorState;
+use crate::view::sticky_scroll::{
+    enclosing_indent_scopes, folding_range_scopes, indent_scopes, markdown_heading_scopes,
+    sticky_headers, StickyScope,
+};
+use crate::view::theme::Theme;
+use crate::view::viewport::Viewport;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::Frame;
+use std::sync::Arc;
+
+/// Headers further than this above the end of the viewport are drawn
+/// without syntax colours, so pinning the header of a long scope never
+/// parses more than this.
+const MAX_HIGHLIGHT_SPAN_BYTES: usize = 64 * 1024;
+
+/// Markdown buffers larger than this are not scanned for headings.
+const MAX_HEADING_SCAN_BYTES: usize = 256 * 1024;
+
+/// Longest header prefix read from the buffer.
+const MAX_HEADER_BYTES: usize = 4096;
+
+/// Draw the sticky scroll rows of a split over the top of `rect`, its
+/// content area. `view_lines` are the rows just rendered there and
+/// `visible_end` the last byte they show.
+///
+/// Returns the header start byte of each pinned row, top to bottom, for
+/// mouse hit testing.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn render_sticky_scroll(
+    frame: &mut Frame,
+    state: &mut EditorState,
+    viewport: &Viewport,
+    view_lines: &[ViewLineMapping],
+    cursor: usize,
+    rect: Rect,
+    max_rows: usize,
+    theme: &Theme,
+    highlight_context_bytes: usize,
+) -> Vec<usize> {
+    let row_bytes: Vec<usize> = view_lines
+        .iter()
+        .map(|line| {
+            line.char_source_bytes
+                .iter()
+                .flatten()
+                .next()
+                .copied()
+                .unwrap_or(line.line_end_byte)
+        })
+        .collect();
+    let cursor_row = view_lines.iter().position(|line| {
+        line.line_end_byte == cursor || line.char_source_bytes.contains(&Some(cursor))
+    });
+    // Leave at least half of the split, and the cursor, uncovered
+    let max_rows = max_rows
+        .min(rect.height as usize / 2)
+        .min(cursor_row.unwrap_or(usize::MAX));
+    let Some(&top) = row_bytes.first() else {
+        return Vec::new();
+    };
+    if max_rows == 0 {
+        return Vec::new();
+    }
+
+    let scopes = scopes_around(state, top);
+    let headers = sticky_headers(&scopes, &row_bytes, max_rows);
+    let Some(&first_header) = headers.first() else {
+        return Vec::new();
+    };
+
+    let visible_end = view_lines
+        .iter()
+        .map(|line| line.line_end_byte)
+        .max()
+        .unwrap_or(top);
+    // Covering the viewport too keeps the highlighter's cache valid for
+    // the next frame.
+    let spans = if visible_end.saturating_sub(first_header) <= MAX_HIGHLIGHT_SPAN_BYTES {
+        state.highlighter.highlight_viewport(
+            &state.buffer,
+            first_header,
+            visible_end,
+            theme,
+            highlight_context_bytes,
+        )
+    } else {
+        Vec::new()
+    };
+    let mut span_cursor = 0;
+
+    let byte_offset_mode = state.buffer.line_count().is_none();
+    let margin = &state.margins.left_config;
+    let tab_size = state.buffer_settings.tab_size.max(1);
+    let left_column = if viewport.line_wrap_enabled {
+        0
+    } else {
+        viewport.left_column
+    };
+
+    for (row, &header) in headers.iter().enumerate() {
+        let y = rect.y + row as u16;
+        let last = row + 1 == headers.len();
+        let mut base = Style::default().bg(theme.editor_bg);
+        // Underline the last pinned row to separate it from the text
+        if last {
+            base = base.add_modifier(Modifier::UNDERLINED);
+        }
+
+        let mut cells: Vec<(String, Style)> = Vec::new();
+        if margin.enabled {
+            let number = if byte_offset_mode {
+                header
+            } else {
+                state.buffer.get_line_number(header) + 1
+            };
+            let gutter_style = base.fg(theme.line_number_fg);
+            cells.push((" ".to_string(), gutter_style));
+            for ch in format!("{:>width$}", number, width = margin.width).chars() {
+                cells.push((ch.to_string(), gutter_style));
+            }
+            if margin.show_separator {
+                for ch in margin.separator.chars() {
+                    cells.push((ch.to_string(), gutter_style));
+                }
+            }
+        }
+
+        let text = header_text(state, header);
+        let mut col = 0;
+        for (offset, ch) in text.char_indices() {
+            let width = if ch == '\t' {
+                tab_size - col % tab_size
+            } else {
+                char_width(ch)
+            };
+            let fg =
+                span_color_at(&spans, &mut span_cursor, header + offset).unwrap_or(theme.editor_fg);
+            let style = base.fg(fg);
+            for i in 0..width {
+                if col + i >= left_column {
+                    let symbol = if ch == '\t' || i > 0 {
+                        " ".to_string()
+                    } else {
+                        ch.to_string()
+                    };
+                    cells.push((symbol, style));
+                }
+            }
+            col += width;
+        }
+
+        let buf = frame.buffer_mut();
+        for x in 0..rect.width {
+            let cell = &mut buf[(rect.x + x, y)];
+            match cells.get(x as usize) {
+                Some((symbol, style)) => {
+                    cell.set_symbol(symbol).set_style(*style);
+                }
+                None => {
+                    cell.set_symbol(" ").set_style(base.fg(theme.editor_fg));
+                }
+            }
+        }
+    }
+
+    headers
+}
+
+/// Scopes to pin for a buffer whose viewport starts at `top`: its syntax
+/// tree around the viewport or Markdown headings, else the language
+/// server's folding ranges, else indentation around the viewport.
+///
+/// The syntax tree is only parsed in a window around the viewport. Scopes
+/// that open above the window, or that open in it but end past it, are
+/// taken from the folding ranges when there are any, else from
+/// indentation.
+fn scopes_around(state: &mut EditorState, top: usize) -> Arc<Vec<StickyScope>> {
+    let version = state.buffer.version();
+    let buffer = &mut state.buffer;
+    let language = state.highlighter.language();
+    let tab_size = state.buffer_settings.tab_size;
+    let is_markdown = state.language == "markdown";
+    let block = if is_markdown { 0..0 } else { scope_block(top) };
+    let window = scope_window(&block);
+    let syntax = state
+        .sticky_scopes
+        .get_or_compute(version, block.start, || {
+            if is_markdown {
+                return (buffer.len() <= MAX_HEADING_SCAN_BYTES)
+                    .then(|| markdown_heading_scopes(&buffer.slice_bytes(0..buffer.len())));
+            }
+            let mut scopes: Vec<StickyScope> = syntax_scopes(buffer, language?, block)?
+                .iter()
+                .filter_map(|scope| {
+                    StickyScope::from_lines(buffer, scope.start_line, scope.end_line)
+                })
+                .collect();
+            if !scopes.is_empty() {
+                for scope in enclosing_indent_scopes(buffer, window.clone(), tab_size) {
+                    if !scopes.iter().any(|s| s.header_start == scope.header_start) {
+                        scopes.push(scope);
+                    }
+                }
+            }
+            Some(scopes)
+        });
+
+    let folding_ranges = state
+        .folding_ranges
+        .resolved(&state.buffer, &state.marker_list);
+    let folding_scopes = folding_range_scopes(&state.buffer, &folding_ranges);
+    if let Some(scopes) = syntax.filter(|scopes| !scopes.is_empty()) {
+        if is_markdown || folding_scopes.is_empty() {
+            return scopes;
+        }
+        // Scopes reaching outside the window are the language server's
+        let window_end = window.end.min(state.buffer.len());
+        let inside =
+            |scope: &StickyScope| scope.header_start >= window.start && scope.body_end < window_end;
+        let mut merged: Vec<StickyScope> = folding_scopes
+            .into_iter()
+            .filter(|scope| !inside(scope))
+            .collect();
+        for scope in scopes.iter().filter(|scope| inside(scope)) {
+            if !merged.iter().any(|s| s.header_start == scope.header_start) {
+                merged.push(*scope);
+            }
+        }
+        return Arc::new(merged);
+    }
+    if !folding_scopes.is_empty() {
+        return Arc::new(folding_scopes);
+    }
+
+    Arc::new(indent_scopes(&mut state.buffer, top, tab_size))
+}
+
+/// Text of the header line starting at `header`, without its line ending.
+fn header_text(state: &mut EditorState, header: usize) -> String {
+    let bytes = state
+        .buffer
+        .get_text_range_mut(header, MAX_HEADER_BYTES)
+        .unwrap_or_default();
+    let line = bytes.split(|&b| b == b'\n').next().unwrap_or_default();
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    String::from_utf8_lossy(line).into_owned()
+}
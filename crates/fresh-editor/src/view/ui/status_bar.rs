@@ -807,9 +807,10 @@ impl StatusBarRenderer {
                 let mut error_count = 0usize;
                 let mut warning_count = 0usize;
                 let mut info_count = 0usize;
-                let diagnostic_ns = crate::services::lsp::diagnostics::lsp_diagnostic_namespace();
                 for overlay in diagnostics {
-                    if overlay.namespace.as_ref() == Some(&diagnostic_ns) {
+                    if crate::services::lsp::diagnostics::is_diagnostic_namespace(
+                        overlay.namespace.as_ref(),
+                    ) {
                         match overlay.priority {
                             100 => error_count += 1,
                             50 => warning_count += 1,
@@ -340,6 +340,7 @@ impl VirtualTextManager {
                 namespace: None,
             },
         );
+        self.bump_version();
 
         id
     }
@@ -507,6 +508,36 @@ impl VirtualTextManager {
         }
     }
 
+    /// Clear all inline (BeforeChar/AfterChar) entries, keeping virtual
+    /// lines.
+    ///
+    /// Inlay hints are replaced wholesale on every refresh; this keeps
+    /// that from also wiping virtual lines such as code lenses.
+    pub fn clear_inline(&mut self, marker_list: &mut MarkerList) {
+        let to_remove: Vec<VirtualTextId> = self
+            .texts
+            .iter()
+            .filter(|(_, vtext)| vtext.position.is_inline())
+            .map(|(id, _)| *id)
+            .collect();
+
+        let removed = !to_remove.is_empty();
+        for id in to_remove {
+            if let Some(vtext) = self.texts.remove(&id) {
+                marker_list.delete(vtext.marker_id);
+            }
+        }
+        if removed {
+            self.bump_version();
+        }
+    }
+
+    /// Current byte position of an entry, following edits since it was added
+    pub fn position(&self, marker_list: &MarkerList, id: VirtualTextId) -> Option<usize> {
+        let vtext = self.texts.get(&id)?;
+        marker_list.get_position(vtext.marker_id)
+    }
+
     /// Remove all virtual text entries whose marker position lies within the
     /// half-open byte range `[start, end)`.
     ///
@@ -853,6 +884,36 @@ mod tests {
         assert_eq!(marker_list.marker_count(), 0);
     }
 
+    #[test]
+    fn test_clear_inline_keeps_lines() {
+        let mut marker_list = MarkerList::new();
+        let mut manager = VirtualTextManager::new();
+
+        manager.add(
+            &mut marker_list,
+            10,
+            ": i32".to_string(),
+            hint_style(),
+            VirtualTextPosition::AfterChar,
+            0,
+        );
+        let line = manager.add_line(
+            &mut marker_list,
+            0,
+            "Run | Debug".to_string(),
+            hint_style(),
+            VirtualTextPosition::LineAbove,
+            VirtualTextNamespace::from_string("lenses".to_string()),
+            0,
+        );
+
+        manager.clear_inline(&mut marker_list);
+
+        assert_eq!(manager.len(), 1);
+        assert_eq!(marker_list.marker_count(), 1);
+        assert_eq!(manager.position(&marker_list, line), Some(0));
+    }
+
     #[test]
     fn test_query_range() {
         let mut marker_list = MarkerList::new();
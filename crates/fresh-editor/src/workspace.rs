@@ -30,6 +30,7 @@ use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::input::input_history::get_data_dir;
+use crate::model::event::Event;
 
 /// Current workspace file format version
 pub const WORKSPACE_VERSION: u32 = 1;
@@ -539,6 +540,171 @@ impl PersistedFileWorkspace {
     }
 }
 
+// ============================================================================
+// Persistent undo history (per-file, opt-in)
+// ============================================================================
+
+/// Current persisted undo history version
+pub const UNDO_HISTORY_VERSION: u32 = 1;
+
+/// Undo history of one file, stored at `{undo_dir}/{encoded_path}.json`,
+/// where `undo_dir` is [`DirectoryContext::undo_dir`].
+///
+/// [`DirectoryContext::undo_dir`]: crate::config_io::DirectoryContext::undo_dir
+///
+/// The history only applies to the exact content it was recorded against:
+/// `content_hash` is the SHA-256 of the file as it was saved, so a file
+/// changed outside the editor invalidates it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedUndoHistory {
+    /// Schema version for future migrations
+    pub version: u32,
+
+    /// SHA-256 (hex) of the file content the history ends at
+    pub content_hash: String,
+
+    /// Buffer-modifying events, oldest first
+    pub events: Vec<Event>,
+
+    /// Timestamp when last saved (Unix epoch seconds)
+    pub saved_at: u64,
+}
+
+/// Per-file storage of undo histories, keyed by absolute path like
+/// [`PersistedFileWorkspace`].
+pub struct PersistedUndoStore;
+
+impl PersistedUndoStore {
+    /// Get the history file path for a source file
+    fn history_file_path(undo_dir: &Path, source_path: &Path) -> PathBuf {
+        let canonical = source_path
+            .canonicalize()
+            .unwrap_or_else(|_| source_path.to_path_buf());
+        let filename = format!("{}.json", encode_path_for_filename(&canonical));
+        undo_dir.join(filename)
+    }
+
+    /// Hash file content the way histories are keyed
+    pub fn content_hash(content: &[u8]) -> String {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(content);
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Load the history of a file whose current content hashes to
+    /// `content_hash`. A history recorded against different content is
+    /// stale and gets deleted.
+    pub fn load(undo_dir: &Path, path: &Path, content_hash: &str) -> Option<Vec<Event>> {
+        let history_path = Self::history_file_path(undo_dir, path);
+        let content = std::fs::read_to_string(&history_path).ok()?;
+        let persisted: PersistedUndoHistory = match serde_json::from_str(&content) {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::debug!("Ignoring unreadable undo history for {:?}: {}", path, e);
+                return None;
+            }
+        };
+
+        if persisted.version > UNDO_HISTORY_VERSION {
+            return None;
+        }
+        if persisted.content_hash != content_hash {
+            tracing::debug!("Undo history for {:?} is stale, discarding", path);
+            Self::remove(undo_dir, path);
+            return None;
+        }
+
+        Some(persisted.events)
+    }
+
+    /// Save the history of a file (atomic write). The oldest events are
+    /// dropped until the serialized history fits in `max_bytes`; an empty
+    /// history removes the stored one.
+    pub fn save(
+        undo_dir: &Path,
+        path: &Path,
+        content_hash: String,
+        events: Vec<Event>,
+        max_bytes: usize,
+    ) {
+        let events = cap_history(events, max_bytes);
+        if events.is_empty() {
+            Self::remove(undo_dir, path);
+            return;
+        }
+
+        let history_path = Self::history_file_path(undo_dir, path);
+
+        if let Some(parent) = history_path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                tracing::warn!("Failed to create undo history dir: {}", e);
+                return;
+            }
+        }
+
+        let persisted = PersistedUndoHistory {
+            version: UNDO_HISTORY_VERSION,
+            content_hash,
+            events,
+            saved_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+        let content = match serde_json::to_string(&persisted) {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::warn!("Failed to serialize undo history: {}", e);
+                return;
+            }
+        };
+
+        // Write atomically: temp file + rename
+        let temp_path = history_path.with_extension("json.tmp");
+
+        let write_result = (|| -> io::Result<()> {
+            let mut file = std::fs::File::create(&temp_path)?;
+            file.write_all(content.as_bytes())?;
+            file.sync_all()?;
+            std::fs::rename(&temp_path, &history_path)?;
+            Ok(())
+        })();
+
+        if let Err(e) = write_result {
+            tracing::warn!("Failed to save undo history for {:?}: {}", path, e);
+        } else {
+            tracing::trace!("Undo history saved for {:?}", path);
+        }
+    }
+
+    /// Delete the stored history of a file, if any
+    pub fn remove(undo_dir: &Path, path: &Path) {
+        let history_path = Self::history_file_path(undo_dir, path);
+        if history_path.exists() {
+            if let Err(e) = std::fs::remove_file(&history_path) {
+                tracing::debug!("Failed to remove undo history {:?}: {}", history_path, e);
+            }
+        }
+    }
+}
+
+/// Keep the newest events whose serialized size fits in `max_bytes`
+fn cap_history(mut events: Vec<Event>, max_bytes: usize) -> Vec<Event> {
+    let mut total = 0;
+    let mut keep_from = events.len();
+    for (i, event) in events.iter().enumerate().rev() {
+        let size = serde_json::to_vec(event).map_or(usize::MAX, |v| v.len() + 1);
+        total = total.saturating_add(size);
+        if total > max_bytes {
+            break;
+        }
+        keep_from = i;
+    }
+    events.drain(..keep_from);
+    events
+}
+
 // ============================================================================
 // Workspace file management
 // ============================================================================
@@ -1351,4 +1517,23 @@ mod tests {
         let restored: FileExplorerState = serde_json::from_str(json).unwrap();
         assert_eq!(restored.width, crate::config::ExplorerWidth::Percent(30));
     }
+
+    #[test]
+    fn test_undo_history_cap_keeps_newest_events() {
+        let insert = |text: &str| Event::Insert {
+            position: 0,
+            text: text.to_string(),
+            cursor_id: crate::model::event::CursorId(0),
+        };
+        let events = vec![insert("first"), insert("second"), insert("third")];
+        let one = serde_json::to_vec(&events[2]).unwrap().len() + 1;
+
+        let capped = cap_history(events.clone(), one * 2 + 1);
+        assert_eq!(capped.len(), 2);
+        assert!(matches!(&capped[0], Event::Insert { text, .. } if text == "second"));
+        assert!(matches!(&capped[1], Event::Insert { text, .. } if text == "third"));
+
+        assert_eq!(cap_history(events.clone(), usize::MAX).len(), 3);
+        assert!(cap_history(events, 1).is_empty());
+    }
 }
@@ -0,0 +1,171 @@
+//! Fake debug adapter for E2E testing
+//!
+//! A Bash script that speaks just enough of the Debug Adapter Protocol to
+//! drive a session: it accepts `launch`, remembers the first breakpoint it
+//! is given and, after `configurationDone`, reports a stop on that line.
+//! Steps stop one line further down; `continue` ends the debuggee.
+
+/// A fake debug adapter script for testing
+pub struct FakeDapAdapter;
+
+impl FakeDapAdapter {
+    /// Write the adapter script into `dir` and return its path.
+    pub fn write_script(dir: &std::path::Path) -> anyhow::Result<std::path::PathBuf> {
+        let script = r#"#!/bin/bash
+
+read_message() {
+    local content_length=0
+    while IFS= read -r line; do
+        line="${line%$'\r'}"
+        if [ -z "$line" ]; then
+            break
+        fi
+        case "$line" in
+            Content-Length:*)
+                content_length="${line#Content-Length:}"
+                content_length="${content_length// /}"
+                ;;
+        esac
+    done
+
+    if [ "$content_length" -gt 0 ] 2>/dev/null; then
+        dd bs=1 count="$content_length" 2>/dev/null
+    fi
+}
+
+SEQ=0
+send_message() {
+    local message="$1"
+    local length=${#message}
+    printf "Content-Length: %d\r\n\r\n%s" "$length" "$message"
+}
+
+respond() {
+    SEQ=$((SEQ + 1))
+    send_message '{"seq":'$SEQ',"type":"response","request_seq":'$1',"success":true,"command":"'$2'","body":'$3'}'
+}
+
+event() {
+    SEQ=$((SEQ + 1))
+    send_message '{"seq":'$SEQ',"type":"event","event":"'$1'","body":'$2'}'
+}
+
+stopped() {
+    event "stopped" '{"reason":"'$1'","threadId":1,"allThreadsStopped":true}'
+}
+
+BP_PATH=""
+BP_LINE=0
+
+while true; do
+    msg=$(read_message)
+
+    if [ -z "$msg" ]; then
+        break
+    fi
+
+    command=$(echo "$msg" | grep -o '"command":"[^"]*"' | head -1 | cut -d'"' -f4)
+    seq=$(echo "$msg" | grep -o '"seq":[0-9]*' | head -1 | cut -d':' -f2)
+
+    case "$command" in
+    "initialize")
+        respond $seq "$command" '{"supportsConfigurationDoneRequest":true}'
+        ;;
+    "launch")
+        respond $seq "$command" '{}'
+        event "initialized" '{}'
+        ;;
+    "setBreakpoints")
+        line=$(echo "$msg" | grep -o '"line":[0-9]*' | head -1 | cut -d':' -f2)
+        if [ -n "$line" ]; then
+            BP_PATH=$(echo "$msg" | grep -o '"path":"[^"]*"' | head -1 | cut -d'"' -f4)
+            BP_LINE=$line
+            respond $seq "$command" '{"breakpoints":[{"verified":true,"line":'$line'}]}'
+        else
+            respond $seq "$command" '{"breakpoints":[]}'
+        fi
+        ;;
+    "configurationDone")
+        respond $seq "$command" '{}'
+        event "output" '{"category":"stdout","output":"fake debuggee started\n"}'
+        if [ "$BP_LINE" -gt 0 ]; then
+            stopped "breakpoint"
+        fi
+        ;;
+    "threads")
+        respond $seq "$command" '{"threads":[{"id":1,"name":"main"}]}'
+        ;;
+    "stackTrace")
+        respond $seq "$command" '{"stackFrames":[{"id":1000,"name":"main","line":'$BP_LINE',"column":1,"source":{"path":"'$BP_PATH'"}}],"totalFrames":1}'
+        ;;
+    "scopes")
+        respond $seq "$command" '{"scopes":[{"name":"Locals","variablesReference":1,"expensive":false}]}'
+        ;;
+    "variables")
+        respond $seq "$command" '{"variables":[{"name":"answer","value":"42","variablesReference":0}]}'
+        ;;
+    "evaluate")
+        respond $seq "$command" '{"result":"42","variablesReference":0}'
+        ;;
+    "next"|"stepIn"|"stepOut")
+        respond $seq "$command" '{}'
+        BP_LINE=$((BP_LINE + 1))
+        stopped "step"
+        ;;
+    "pause")
+        respond $seq "$command" '{}'
+        stopped "pause"
+        ;;
+    "continue")
+        respond $seq "$command" '{"allThreadsContinued":true}'
+        event "exited" '{"exitCode":0}'
+        event "terminated" '{}'
+        ;;
+    "disconnect")
+        respond $seq "$command" '{}'
+        break
+        ;;
+    *)
+        respond $seq "$command" '{}'
+        ;;
+    esac
+done
+"#;
+
+        let script_path = dir.join("fake_dap_adapter.sh");
+        std::fs::write(&script_path, script)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = std::fs::metadata(&script_path)?.permissions();
+            perms.set_mode(0o755);
+            std::fs::set_permissions(&script_path, perms)?;
+        }
+
+        Ok(script_path)
+    }
+
+    /// Debugger config that starts the fake adapter for Rust files.
+    pub fn config(script_path: &std::path::Path) -> fresh::config::DebugConfig {
+        let mut debug = fresh::config::DebugConfig::default();
+        debug.adapters.insert(
+            "fake".to_string(),
+            fresh::config::DebugAdapterConfig {
+                command: script_path.to_string_lossy().to_string(),
+                args: vec![],
+                env: Default::default(),
+                languages: vec!["rust".to_string()],
+            },
+        );
+        debug
+            .configurations
+            .push(fresh::config::DebugConfiguration {
+                name: "Run fake".to_string(),
+                adapter: "fake".to_string(),
+                request: fresh::config::DebugRequestKind::Launch,
+                arguments: Default::default(),
+            });
+        debug
+    }
+}
@@ -5,6 +5,9 @@
 pub mod blog_showcase;
 #[cfg(test)]
 #[allow(dead_code)]
+pub mod fake_dap;
+#[cfg(test)]
+#[allow(dead_code)]
 pub mod fake_lsp;
 #[cfg(test)]
 #[allow(dead_code)]
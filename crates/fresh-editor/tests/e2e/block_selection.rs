@@ -1,5 +1,5 @@
 use crate::common::harness::EditorTestHarness;
-use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
 /// Test basic block selection with Alt+Shift+Down creates visible selection
 #[test]
@@ -542,3 +542,250 @@ fn test_block_selection_copy_copies_rectangular_region() {
         "Block selection copy should produce exactly the rectangular region"
     );
 }
+
+/// Typing into a block that extends past the end of short lines pads
+/// those lines with spaces up to the block's column
+#[test]
+fn test_block_typing_pads_short_lines() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("abcdef\nab\nabcdef").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    for _ in 0..4 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+
+    // Zero-width block at column 4 across all three lines; line 2 ends at
+    // column 2, so its part of the block lies in virtual space
+    for _ in 0..2 {
+        harness
+            .send_key(KeyCode::Down, KeyModifiers::ALT | KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    harness.type_text("|").unwrap();
+
+    harness.assert_buffer_content("abcd|ef\nab  |\nabcd|ef");
+}
+
+/// Block columns are display columns: a double-width character occupies
+/// two of them
+#[test]
+fn test_block_copy_with_wide_characters() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().set_clipboard_for_test("".to_string());
+
+    harness.type_text("你好ab\nxxxxab").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+
+    // Select columns 0..4: both CJK characters on line 1, four x's on line 2
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .unwrap();
+    for _ in 0..4 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    harness
+        .send_key(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    assert_eq!(
+        harness.editor_mut().clipboard_content_for_test(),
+        "你好\nxxxx"
+    );
+}
+
+/// A block cut and pasted elsewhere is inserted column-wise, one line of
+/// the block per buffer line
+#[test]
+fn test_block_cut_paste_round_trip() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().set_clipboard_for_test("".to_string());
+
+    harness.type_text("12ab\n34cd\n").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+
+    // Select the 2x2 block "12" / "34" and cut it
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .unwrap();
+    for _ in 0..2 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    harness
+        .send_key(KeyCode::Char('x'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("ab\ncd\n");
+
+    // Paste it at the end of the first line: the rows land on consecutive
+    // lines at the same column
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Char('v'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    harness.assert_buffer_content("ab12\ncd34\n");
+}
+
+/// The selection background covers the part of a block in virtual space
+#[test]
+fn test_block_selection_renders_virtual_space() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("0123456789\n01\n0123456789").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    for _ in 0..5 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+    for _ in 0..2 {
+        harness
+            .send_key(KeyCode::Down, KeyModifiers::ALT | KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    for _ in 0..2 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    harness.render().unwrap();
+
+    let selection_bg = harness.editor().theme().selection_bg;
+    let (content_first_row, _) = harness.content_area_rows();
+    let short_line_row = content_first_row as u16 + 1;
+    let gutter_width = harness.editor().active_state().margins.left_total_width() as u16;
+    let buffer = harness.buffer();
+
+    // Columns 5 and 6 of the two-character line are selected, column 4 is not
+    for (col, selected) in [(4, false), (5, true), (6, true), (7, false)] {
+        let cell = &buffer.content[buffer.index_of(gutter_width + col, short_line_row)];
+        assert_eq!(
+            cell.bg == selection_bg,
+            selected,
+            "column {} of the short line",
+            col
+        );
+    }
+}
+
+/// Alt+drag selects a rectangle
+#[test]
+fn test_alt_drag_creates_block_selection() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().set_clipboard_for_test("".to_string());
+
+    harness
+        .type_text("AAAA BBBB CCCC\nAAAA BBBB CCCC\nAAAA BBBB CCCC")
+        .unwrap();
+    harness.render().unwrap();
+
+    let (content_first_row, _) = harness.content_area_rows();
+    let first_row = content_first_row as u16;
+    let gutter_width = harness.editor().active_state().margins.left_total_width() as u16;
+
+    harness
+        .send_mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: gutter_width + 5,
+            row: first_row,
+            modifiers: KeyModifiers::ALT,
+        })
+        .unwrap();
+    harness
+        .send_mouse(MouseEvent {
+            kind: MouseEventKind::Drag(MouseButton::Left),
+            column: gutter_width + 9,
+            row: first_row + 2,
+            modifiers: KeyModifiers::ALT,
+        })
+        .unwrap();
+    harness
+        .send_mouse(MouseEvent {
+            kind: MouseEventKind::Up(MouseButton::Left),
+            column: gutter_width + 9,
+            row: first_row + 2,
+            modifiers: KeyModifiers::ALT,
+        })
+        .unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(
+        harness.editor_mut().clipboard_content_for_test(),
+        "BBBB\nBBBB\nBBBB"
+    );
+}
+
+/// Cutting a block with nothing in it leaves one cursor per line, and
+/// undoing a block cut returns to the block's corners
+#[test]
+fn test_block_cut_cursors_and_undo() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.editor_mut().set_clipboard_for_test("".to_string());
+
+    harness.type_text("abcdef\nabcdef").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    for _ in 0..4 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+
+    // A zero-width block deletes nothing
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('x'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("abcdef\nabcdef");
+    assert_eq!(harness.editor().active_cursors().count(), 2);
+
+    // Cut the 2x2 block "ab" / "ab" and undo it
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .unwrap();
+    for _ in 0..2 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    harness
+        .send_key(KeyCode::Char('x'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("cdef\ncdef");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("abcdef\nabcdef");
+    let primary = *harness.editor().active_cursors().primary();
+    assert_eq!(primary.anchor, Some(0));
+    assert_eq!(primary.position, 9);
+}
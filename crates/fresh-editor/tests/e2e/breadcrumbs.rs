@@ -0,0 +1,152 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::view::popup::PopupResolver;
+
+const SHAPES: &str = "\
+mod shapes {
+    fn area() -> f64 {
+        1.0
+    }
+
+    fn perimeter() -> f64 {
+        4.0
+    }
+}
+
+fn main() {}
+";
+
+fn breadcrumbs_config() -> Config {
+    let mut config = Config::default();
+    config.editor.show_breadcrumbs = true;
+    config
+}
+
+/// A project with `src/shapes.rs` and `src/util.rs`, with `shapes.rs`
+/// open and the cursor inside `area`
+fn open_shapes(config: Config) -> EditorTestHarness {
+    let mut harness = EditorTestHarness::with_temp_project_and_config(100, 24, config).unwrap();
+    let src = harness.project_dir().unwrap().join("src");
+    std::fs::create_dir_all(&src).unwrap();
+    std::fs::write(src.join("shapes.rs"), SHAPES).unwrap();
+    std::fs::write(src.join("util.rs"), "fn helper() {}\n").unwrap();
+    harness.open_file(&src.join("shapes.rs")).unwrap();
+    harness
+        .editor_mut()
+        .goto_byte_offset(SHAPES.find("1.0").unwrap());
+    harness.render().unwrap();
+    harness.render().unwrap();
+    harness
+}
+
+/// Row and column at which `text` is drawn
+fn find_on_screen(harness: &EditorTestHarness, text: &str) -> Option<(u16, u16)> {
+    (0..24).find_map(|row| {
+        let line = harness.get_row_text(row);
+        let byte = line.find(text)?;
+        Some((line[..byte].chars().count() as u16, row))
+    })
+}
+
+fn open_dropdown_items(harness: &EditorTestHarness) -> Vec<String> {
+    let popup = harness
+        .editor()
+        .active_state()
+        .popups
+        .top()
+        .expect("a dropdown should be open");
+    assert!(matches!(popup.resolver, PopupResolver::Breadcrumb { .. }));
+    match &popup.content {
+        fresh::view::popup::PopupContent::List { items, .. } => {
+            items.iter().map(|item| item.text.clone()).collect()
+        }
+        other => panic!("expected a list, got {other:?}"),
+    }
+}
+
+/// The bar shows the project-relative path and the symbols around the
+/// cursor; it is off by default
+#[test]
+fn test_breadcrumbs_show_path_and_symbols() {
+    let harness = open_shapes(Config::default());
+    assert!(find_on_screen(&harness, "shapes.rs ›").is_none());
+
+    let harness = open_shapes(breadcrumbs_config());
+    let screen = harness.screen_to_string();
+    assert!(
+        screen.contains("src › shapes.rs › mod shapes › fn area"),
+        "breadcrumbs missing:\n{screen}"
+    );
+}
+
+/// Clicking a symbol segment lists its siblings; choosing one jumps to it
+#[test]
+fn test_breadcrumbs_symbol_dropdown_jumps() {
+    let mut harness = open_shapes(breadcrumbs_config());
+    let (col, row) = find_on_screen(&harness, "fn area").unwrap();
+    harness.mouse_click(col + 1, row).unwrap();
+    harness.render().unwrap();
+    assert_eq!(open_dropdown_items(&harness), vec!["area", "perimeter"]);
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert!(harness.editor().active_state().popups.top().is_none());
+    assert_eq!(harness.cursor_position(), SHAPES.find("perimeter").unwrap());
+    let screen = harness.screen_to_string();
+    assert!(
+        screen.contains("mod shapes › fn perimeter"),
+        "bar should follow the cursor:\n{screen}"
+    );
+}
+
+/// Clicking the file segment lists the files next to it; choosing one
+/// opens it
+#[test]
+fn test_breadcrumbs_path_dropdown_opens_sibling_file() {
+    let mut harness = open_shapes(breadcrumbs_config());
+    let (col, row) = find_on_screen(&harness, "shapes.rs ›").unwrap();
+    harness.mouse_click(col + 1, row).unwrap();
+    harness.render().unwrap();
+    assert_eq!(open_dropdown_items(&harness), vec!["shapes.rs", "util.rs"]);
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("src › util.rs"))
+        .unwrap();
+    harness.assert_screen_contains("fn helper() {}");
+}
+
+/// "Focus Breadcrumbs" opens the innermost dropdown; Left moves outwards
+#[test]
+fn test_breadcrumbs_keyboard_focus_and_step() {
+    let mut harness = open_shapes(breadcrumbs_config());
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text("Focus Breadcrumbs").unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(open_dropdown_items(&harness), vec!["area", "perimeter"]);
+
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_eq!(open_dropdown_items(&harness), vec!["shapes", "main"]);
+
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_eq!(open_dropdown_items(&harness), vec!["shapes.rs", "util.rs"]);
+
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    assert!(harness.editor().active_state().popups.top().is_none());
+}
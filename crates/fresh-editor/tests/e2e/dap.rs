@@ -0,0 +1,108 @@
+//! E2E tests for the debugger (Debug Adapter Protocol client)
+
+use crate::common::fake_dap::FakeDapAdapter;
+use crate::common::harness::EditorTestHarness;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+const SOURCE: &str = "fn main() {\n    let answer = 42;\n    println!(\"{answer}\");\n}\n";
+
+fn harness_with_fake_adapter(
+    temp_dir: &tempfile::TempDir,
+) -> anyhow::Result<(EditorTestHarness, std::path::PathBuf)> {
+    let script = FakeDapAdapter::write_script(temp_dir.path())?;
+    let test_file = temp_dir.path().join("main.rs");
+    std::fs::write(&test_file, SOURCE)?;
+
+    let mut config = fresh::config::Config::default();
+    config.debug = FakeDapAdapter::config(&script);
+
+    let harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        24,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+    Ok((harness, test_file))
+}
+
+/// Number of rows showing `symbol` in the gutter's indicator column.
+fn gutter_rows_with(harness: &EditorTestHarness, symbol: char) -> usize {
+    harness
+        .screen_to_string()
+        .lines()
+        .filter(|line| line.starts_with(symbol))
+        .count()
+}
+
+#[test]
+fn test_toggle_breakpoint_in_gutter() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (mut harness, test_file) = harness_with_fake_adapter(&temp_dir)?;
+    harness.open_file(&test_file)?;
+    harness.render()?;
+    assert_eq!(gutter_rows_with(&harness, '●'), 0);
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::F(9), KeyModifiers::NONE)?;
+    harness.render()?;
+    assert_eq!(gutter_rows_with(&harness, '●'), 1);
+
+    harness.send_key(KeyCode::F(9), KeyModifiers::NONE)?;
+    harness.render()?;
+    assert_eq!(gutter_rows_with(&harness, '●'), 0);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(windows, ignore)] // The fake adapter is a Bash script
+fn test_debug_session_stops_at_breakpoint_and_steps() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let (mut harness, test_file) = harness_with_fake_adapter(&temp_dir)?;
+    harness.open_file(&test_file)?;
+    harness.render()?;
+
+    // Breakpoint on `let answer = 42;`
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::F(9), KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE)?;
+
+    harness.send_key(KeyCode::F(5), KeyModifiers::CONTROL)?;
+    harness.wait_until(|h| h.get_status_bar().contains("Paused (breakpoint)"))?;
+    harness.render()?;
+    assert_eq!(gutter_rows_with(&harness, '▶'), 1);
+    let cursor = harness.cursor_position();
+    let line = harness
+        .editor()
+        .active_state()
+        .buffer
+        .get_line_number(cursor);
+    assert_eq!(line, 1, "cursor should be on the breakpoint line");
+
+    // Stepping moves the current line; the breakpoint stays
+    harness.send_key(KeyCode::F(10), KeyModifiers::CONTROL)?;
+    harness.wait_until(|h| h.get_status_bar().contains("Paused (step)"))?;
+    let cursor = harness.cursor_position();
+    let line = harness
+        .editor()
+        .active_state()
+        .buffer
+        .get_line_number(cursor);
+    assert_eq!(line, 2, "cursor should follow the step");
+    assert_eq!(gutter_rows_with(&harness, '▶'), 1);
+    assert_eq!(gutter_rows_with(&harness, '●'), 1);
+
+    harness.send_key(KeyCode::F(5), KeyModifiers::SHIFT)?;
+    harness.wait_until(|h| h.get_status_bar().contains("Debug session ended"))?;
+    assert_eq!(gutter_rows_with(&harness, '▶'), 0);
+    Ok(())
+}
+
+#[test]
+fn test_debug_start_without_configurations() -> anyhow::Result<()> {
+    let mut harness = EditorTestHarness::new(100, 24)?;
+    harness.send_key(KeyCode::F(5), KeyModifiers::CONTROL)?;
+    harness.render()?;
+    assert!(harness.get_status_bar().contains("No debug configurations"));
+    Ok(())
+}
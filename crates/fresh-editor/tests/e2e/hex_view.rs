@@ -0,0 +1,126 @@
+//! E2E tests for the hex view of binary files
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// ELF-like header: the NUL bytes make the file binary
+const DATA: &[u8] = &[
+    0x7F, 0x45, 0x4C, 0x46, 0x02, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x3E, 0x00, 0xDE, 0xAD, 0xBE, 0xEF,
+];
+
+fn open_binary(temp_dir: &TempDir) -> (EditorTestHarness, std::path::PathBuf) {
+    let path = temp_dir.path().join("data.bin");
+    std::fs::write(&path, DATA).unwrap();
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+    (harness, path)
+}
+
+fn run_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(name).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_hex_view_shows_offset_hex_and_ascii_columns() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut harness, _) = open_binary(&temp_dir);
+
+    run_command(&mut harness, "Toggle Hex View");
+    harness
+        .assert_screen_contains("00000000  7F 45 4C 46 02 01 01 00  00 00 00 00 00 00 00 00  .ELF");
+    harness.assert_screen_contains("00000010  02 00 3E 00 DE AD BE EF");
+
+    run_command(&mut harness, "Toggle Hex View");
+    harness.assert_screen_not_contains("00000010");
+}
+
+#[test]
+fn test_hex_view_overwrites_nibbles_and_saves_bytes() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut harness, path) = open_binary(&temp_dir);
+    run_command(&mut harness, "Toggle Hex View");
+
+    // Second byte: 0x45 -> 0xAB, then the third byte's high nibble -> 0x1C
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("ab1").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("00000000  7F AB 1C 46");
+    assert_eq!(harness.cursor_position(), 2);
+
+    // ASCII column overwrites whole bytes
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.type_text("Z").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    let mut expected = DATA.to_vec();
+    expected[1] = 0xAB;
+    expected[2] = b'Z';
+    assert_eq!(std::fs::read(&path).unwrap(), expected);
+}
+
+#[test]
+fn test_hex_edit_is_undoable() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut harness, _) = open_binary(&temp_dir);
+    run_command(&mut harness, "Toggle Hex View");
+
+    harness.type_text("00").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("00000000  00 45 4C 46");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("00000000  7F 45 4C 46");
+}
+
+#[test]
+fn test_hex_goto_offset_and_find_bytes() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut harness, _) = open_binary(&temp_dir);
+
+    run_command(&mut harness, "Hex View: Go to Offset");
+    harness.type_text("0x12").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), 0x12);
+
+    run_command(&mut harness, "Hex View: Find Bytes");
+    harness.type_text("de ad").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), 0x14);
+    harness.assert_screen_contains("Found at offset 00000014");
+
+    // Wraps around to the start of the file
+    run_command(&mut harness, "Hex View: Find Bytes");
+    harness.type_text("\"ELF\"").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), 1);
+}
@@ -0,0 +1,118 @@
+use crate::common::fixtures::TestFixture;
+use crate::common::harness::EditorTestHarness;
+use fresh::config::Config;
+
+const SOURCE: &str = "\
+fn main() {
+    if ready {
+        start();
+
+        finish();
+    }
+}
+";
+
+fn guides_config() -> Config {
+    let mut config = Config::default();
+    config.editor.show_indent_guides = true;
+    config
+}
+
+fn open_source(config: Config, width: u16, source: &str) -> (EditorTestHarness, TestFixture) {
+    let mut harness = EditorTestHarness::with_config(width, 24, config).unwrap();
+    let fixture = harness
+        .load_buffer_from_text_named("guides.rs", source)
+        .unwrap();
+    harness.render().unwrap();
+    (harness, fixture)
+}
+
+/// Row and column at which `text` is drawn
+fn find_on_screen(harness: &EditorTestHarness, text: &str) -> (u16, u16) {
+    (0..24)
+        .find_map(|row| {
+            let line = harness.get_row_text(row);
+            let byte = line.find(text)?;
+            Some((line[..byte].chars().count() as u16, row))
+        })
+        .unwrap_or_else(|| panic!("{text:?} not on screen:\n{}", harness.screen_to_string()))
+}
+
+/// Guides sit at each indentation level and run through blank lines; none
+/// are drawn by default
+#[test]
+fn test_indent_guides_drawn_at_each_level() {
+    let (harness, _fixture) = open_source(Config::default(), 80, SOURCE);
+    let (col, row) = find_on_screen(&harness, "start();");
+    assert_eq!(harness.get_cell(col - 8, row).as_deref(), Some(" "));
+
+    let (harness, _fixture) = open_source(guides_config(), 80, SOURCE);
+    let (col, row) = find_on_screen(&harness, "start();");
+    let screen = harness.screen_to_string();
+    for guide in [col - 8, col - 4] {
+        assert_eq!(
+            harness.get_cell(guide, row).as_deref(),
+            Some("│"),
+            "guide missing at column {guide}:\n{screen}"
+        );
+        // The blank line inside the block is bridged
+        assert_eq!(harness.get_cell(guide, row + 1).as_deref(), Some("│"));
+    }
+    // Closing braces are left alone, and top-level lines have no guide
+    let (_, brace_row) = find_on_screen(&harness, "}");
+    assert_eq!(harness.get_cell(col - 8, brace_row).as_deref(), Some("│"));
+    assert_eq!(harness.get_cell(col - 4, brace_row).as_deref(), Some("}"));
+    assert_eq!(
+        harness.get_cell(col - 8, brace_row + 1).as_deref(),
+        Some("}")
+    );
+}
+
+/// The guide of the block holding the cursor gets the active color
+#[test]
+fn test_indent_guides_highlight_cursor_block() {
+    let (mut harness, _fixture) = open_source(guides_config(), 80, SOURCE);
+    harness
+        .editor_mut()
+        .goto_byte_offset(SOURCE.find("finish").unwrap());
+    harness.render().unwrap();
+
+    let (col, row) = find_on_screen(&harness, "start();");
+    let (inactive, active) = {
+        let theme = harness.editor().theme();
+        (theme.indent_guide_fg, theme.indent_guide_active_fg)
+    };
+    let fg = |x: u16, y: u16| harness.get_cell_style(x, y).and_then(|style| style.fg);
+    assert_eq!(fg(col - 4, row), Some(active));
+    assert_eq!(fg(col - 4, row + 1), Some(active));
+    assert_eq!(fg(col - 8, row), Some(inactive));
+
+    // On the `if` header, the guide of the block it opens stays active
+    harness
+        .editor_mut()
+        .goto_byte_offset(SOURCE.find("ready").unwrap());
+    harness.render().unwrap();
+    let fg = |x: u16, y: u16| harness.get_cell_style(x, y).and_then(|style| style.fg);
+    assert_eq!(fg(col - 4, row), Some(active));
+    assert_eq!(fg(col - 8, row), Some(inactive));
+}
+
+/// Rows continuing a wrapped line keep their text; guides only fill the
+/// hanging indent
+#[test]
+fn test_indent_guides_leave_wrapped_text_alone() {
+    let words: Vec<String> = (0..30).map(|i| format!("word{i}")).collect();
+    let source = format!("fn main() {{\n    {}\n}}\n", words.join(" "));
+    let (harness, _fixture) = open_source(guides_config(), 50, &source);
+    let screen = harness.screen_to_string();
+    for word in &words {
+        assert!(
+            screen.contains(word.as_str()),
+            "{word} overwritten:\n{screen}"
+        );
+    }
+    let (col, row) = find_on_screen(&harness, "word0 ");
+    assert_eq!(harness.get_cell(col - 4, row).as_deref(), Some("│"));
+    // The continuation row is indented like its line, with the guide in it
+    assert_eq!(harness.get_cell(col - 4, row + 1).as_deref(), Some("│"));
+}
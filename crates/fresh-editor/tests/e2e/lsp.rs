@@ -5921,9 +5921,25 @@ fn test_completion_snippet_with_default() -> anyhow::Result<()> {
         "Snippet should expand with default text"
     );
 
-    // Verify cursor is at $0 position (after the 4 spaces on line 2)
+    // The first placeholder is selected
+    let primary = harness.editor().active_cursors().primary().clone();
+    assert_eq!(
+        primary.selection_range(),
+        Some(3..7),
+        "Placeholder selected"
+    );
+
+    // Tab visits $2, then the final $0 position (after the 4 spaces on line 2)
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE)?;
+    let cursor_pos = harness.editor().active_cursors().primary().position;
+    assert_eq!(cursor_pos, 8, "Cursor should be at $2 position");
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE)?;
     let cursor_pos = harness.editor().active_cursors().primary().position;
     assert_eq!(cursor_pos, 16, "Cursor should be at $0 position");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "fn name() {\n    \n}"
+    );
 
     Ok(())
 }
@@ -0,0 +1,113 @@
+use crate::common::harness::EditorTestHarness;
+use fresh::config::Config;
+
+/// Terminal width used by these tests. The minimap (10 columns by default)
+/// sits just left of the 1-column vertical scrollbar.
+const WIDTH: u16 = 80;
+const MINIMAP_X: std::ops::Range<u16> = (WIDTH - 11)..(WIDTH - 1);
+
+fn minimap_config() -> Config {
+    let mut config = Config::default();
+    config.editor.show_minimap = true;
+    config
+}
+
+fn numbered_lines(count: usize) -> String {
+    (0..count)
+        .map(|i| format!("fn line_{i}() {{ call(); }}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_braille(cell: &str) -> bool {
+    cell.chars()
+        .next()
+        .is_some_and(|c| ('\u{2801}'..='\u{28FF}').contains(&c))
+}
+
+/// Number of braille cells drawn in the minimap columns.
+fn minimap_braille_cells(harness: &EditorTestHarness) -> usize {
+    let (first_row, last_row) = harness.content_area_rows();
+    let mut count = 0;
+    for row in first_row..=last_row {
+        for col in MINIMAP_X {
+            if harness
+                .get_cell(col, row as u16)
+                .is_some_and(|cell| is_braille(&cell))
+            {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+/// The minimap is off by default and draws braille cells once enabled
+#[test]
+fn test_minimap_renders_when_enabled() {
+    let mut harness = EditorTestHarness::new(WIDTH, 24).unwrap();
+    harness.load_buffer_from_text(&numbered_lines(200)).unwrap();
+    harness.render().unwrap();
+    assert_eq!(minimap_braille_cells(&harness), 0);
+
+    let mut harness = EditorTestHarness::with_config(WIDTH, 24, minimap_config()).unwrap();
+    harness.load_buffer_from_text(&numbered_lines(200)).unwrap();
+    harness.render().unwrap();
+    assert!(
+        minimap_braille_cells(&harness) > 0,
+        "minimap should draw braille cells:\n{}",
+        harness.screen_to_string()
+    );
+    // The text itself is narrowed rather than drawn under the minimap
+    harness.assert_screen_contains("fn line_0() { call(); }");
+}
+
+/// Clicking near the bottom of the minimap scrolls the buffer there
+#[test]
+fn test_minimap_click_scrolls_viewport() {
+    let mut harness = EditorTestHarness::with_config(WIDTH, 24, minimap_config()).unwrap();
+    harness.load_buffer_from_text(&numbered_lines(200)).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.top_line_number(), 0);
+
+    let (_, last_row) = harness.content_area_rows();
+    harness
+        .mouse_click(MINIMAP_X.start + 4, last_row as u16)
+        .unwrap();
+
+    // Each minimap row holds four lines, so the last of ~20 rows is far
+    // past the first screenful
+    let top = harness.top_line_number();
+    assert!(top > 40, "expected to scroll down, top line is {top}");
+    // The cursor follows into the new viewport
+    let cursor_line = harness
+        .editor()
+        .active_state()
+        .buffer
+        .get_line_number(harness.cursor_position());
+    assert!(cursor_line >= top);
+}
+
+/// Large files are sampled rather than scanned, and still scroll on click
+#[test]
+fn test_minimap_samples_large_files() {
+    let mut config = minimap_config();
+    config.editor.large_file_threshold_bytes = 500;
+    let mut harness = EditorTestHarness::with_config(WIDTH, 24, config).unwrap();
+    harness
+        .load_buffer_from_text(&numbered_lines(2000))
+        .unwrap();
+    harness.render().unwrap();
+    assert!(minimap_braille_cells(&harness) > 0);
+
+    let (first_row, last_row) = harness.content_area_rows();
+    let middle = (first_row + last_row) / 2;
+    harness
+        .mouse_click(MINIMAP_X.start + 4, middle as u16)
+        .unwrap();
+    let top_byte = harness.top_byte();
+    assert!(
+        top_byte > 0,
+        "clicking the middle of the minimap should scroll a large file"
+    );
+}
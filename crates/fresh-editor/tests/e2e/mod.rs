@@ -9,6 +9,7 @@ pub mod basic;
 pub mod binary_file;
 pub mod block_selection;
 pub mod blog_showcases;
+pub mod breadcrumbs;
 pub mod buffer_groups;
 pub mod buffer_lifecycle;
 pub mod buffer_settings_commands;
@@ -25,6 +26,7 @@ pub mod ctrl_end_wrapped;
 pub mod cursor_style_rendering;
 pub mod cursor_under_popup;
 pub mod dabbrev_completion;
+pub mod dap;
 pub mod dock_panel_routing;
 pub mod document_model;
 pub mod duplicate_line;
@@ -44,10 +46,12 @@ pub mod goto_matching_bracket;
 #[cfg(feature = "gui")]
 pub mod gui;
 pub mod hanging_wrap_indent;
+pub mod hex_view;
 pub mod horizontal_scrollbar;
 pub mod hot_exit_flows;
 pub mod hot_exit_recovery_lsp_sync;
 pub mod indent_dedent;
+pub mod indent_guides;
 pub mod inline_diagnostics;
 pub mod issue_1147_wrapped_line_nav;
 pub mod issue_1288_word_select_whitespace;
@@ -123,6 +127,7 @@ pub mod menu_bar;
 pub mod menu_cursor_bleed;
 pub mod menu_tab_color_bleed;
 pub mod merge_conflict;
+pub mod minimap;
 pub mod mouse;
 pub mod movement;
 pub mod multi_file_opening;
@@ -132,6 +137,7 @@ pub mod on_save_actions;
 pub mod open_folder;
 pub mod overlay_extend_to_line_end;
 pub mod paste;
+pub mod persistent_undo;
 #[cfg(feature = "plugins")]
 pub mod plugins;
 pub mod popup_selection;
@@ -183,7 +189,10 @@ pub mod side_by_side_diff_scroll;
 pub mod slow_filesystem;
 pub mod smart_editing;
 pub mod smart_home;
+pub mod snippet_library;
+pub mod snippet_session;
 pub mod sort_lines;
+pub mod spell_check;
 pub mod split_focus_tab_click;
 pub mod split_tabs;
 pub mod split_view;
@@ -192,6 +201,7 @@ pub mod split_view_markdown_compose;
 pub mod status_bar_config;
 pub mod status_bar_message_click;
 pub mod stdin_input;
+pub mod sticky_scroll;
 pub mod sudo_save_prompt;
 #[cfg(unix)]
 pub mod symlinks;
@@ -202,6 +212,7 @@ pub mod tab_config;
 pub mod tab_drag;
 pub mod tab_indent_selection;
 pub mod tab_scrolling;
+pub mod task_runner;
 pub mod terminal;
 pub mod terminal_close;
 pub mod terminal_resize;
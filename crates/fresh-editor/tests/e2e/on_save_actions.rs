@@ -583,6 +583,52 @@ fn test_trim_trailing_whitespace_on_save() {
     assert_eq!(disk_content, "line 1\nline 2\nline 3\n");
 }
 
+/// Whitespace cleanup only edits the lines that change, so the cursor keeps
+/// its place in the text and the cleanup undoes in one step
+#[test]
+fn test_trim_trailing_whitespace_keeps_cursor_in_place() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let file_path = project_dir.join("test.rs");
+    std::fs::write(&file_path, "line 1   \nline 2\nline 3\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.trim_trailing_whitespace_on_save = true;
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, config, project_dir).unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    // Put the cursor before the "3" of the last line
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    for _ in 0..5 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+    assert_eq!(harness.cursor_position(), 22);
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("line 1\nline 2\nline 3\n");
+    // Three bytes were removed before the cursor
+    assert_eq!(harness.cursor_position(), 19);
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("line 1   \nline 2\nline 3\n");
+}
+
 /// Test ensure_final_newline_on_save adds newline at end
 #[test]
 fn test_ensure_final_newline_on_save() {
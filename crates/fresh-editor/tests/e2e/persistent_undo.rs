@@ -0,0 +1,102 @@
+//! E2E tests for undo history persisted across editor sessions
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn persistent_undo_config(enabled: bool) -> Config {
+    let mut config = Config::default();
+    config.editor.persistent_undo = enabled;
+    config
+}
+
+/// An editor session; sessions sharing `dir_context` share stored histories.
+fn session(config: Config, dir_context: &DirectoryContext) -> EditorTestHarness {
+    EditorTestHarness::create(
+        80,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_shared_dir_context(dir_context.clone()),
+    )
+    .unwrap()
+}
+
+/// Open `path`, append a line, save and shut the editor down.
+fn edit_and_exit(path: &Path, config: Config, dir_context: &DirectoryContext) {
+    let mut harness = session(config, dir_context);
+    harness.open_file(path).unwrap();
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("added").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(std::fs::read_to_string(path).unwrap(), "original\nadded");
+    harness.shutdown(true).unwrap();
+}
+
+fn reopen_and_undo_all(
+    path: &Path,
+    config: Config,
+    dir_context: &DirectoryContext,
+) -> EditorTestHarness {
+    let mut harness = session(config, dir_context);
+    harness.open_file(path).unwrap();
+    for _ in 0..10 {
+        harness
+            .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+            .unwrap();
+    }
+    harness.render().unwrap();
+    harness
+}
+
+#[test]
+fn test_undo_history_survives_restart() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("notes.txt");
+    std::fs::write(&path, "original\n").unwrap();
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+
+    edit_and_exit(&path, persistent_undo_config(true), &dir_context);
+
+    let harness = reopen_and_undo_all(&path, persistent_undo_config(true), &dir_context);
+    assert_eq!(harness.get_buffer_content().unwrap(), "original\n");
+}
+
+#[test]
+fn test_undo_history_discarded_when_file_changed_on_disk() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("notes.txt");
+    std::fs::write(&path, "original\n").unwrap();
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+
+    edit_and_exit(&path, persistent_undo_config(true), &dir_context);
+    std::fs::write(&path, "rewritten elsewhere\n").unwrap();
+
+    let harness = reopen_and_undo_all(&path, persistent_undo_config(true), &dir_context);
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "rewritten elsewhere\n"
+    );
+}
+
+#[test]
+fn test_undo_history_not_kept_by_default() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("notes.txt");
+    std::fs::write(&path, "original\n").unwrap();
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+
+    edit_and_exit(&path, persistent_undo_config(false), &dir_context);
+
+    // Even with the option turned on later, nothing was stored
+    let harness = reopen_and_undo_all(&path, persistent_undo_config(true), &dir_context);
+    assert_eq!(harness.get_buffer_content().unwrap(), "original\nadded");
+}
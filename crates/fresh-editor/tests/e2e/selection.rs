@@ -530,6 +530,81 @@ fn test_expand_selection_no_initial_selection() {
     );
 }
 
+/// Test syntax-aware expand (Alt+O) and shrink (Alt+I) in a Rust file
+#[test]
+fn test_expand_shrink_selection_syntax_nodes() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("main.rs");
+    std::fs::write(&file_path, "fn main() {\n    let total = add(1, 2);\n}\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    // Put the cursor inside "add" on the second line
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    for _ in 0..17 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+    let start_position = harness.editor().active_cursors().primary().position;
+
+    let selected = |harness: &mut EditorTestHarness| {
+        let range = harness
+            .editor()
+            .active_cursors()
+            .primary()
+            .selection_range();
+        range.map(|r| {
+            harness
+                .editor_mut()
+                .active_state_mut()
+                .get_text_range(r.start, r.end)
+        })
+    };
+
+    harness
+        .send_key(KeyCode::Char('o'), KeyModifiers::ALT)
+        .unwrap();
+    assert_eq!(selected(&mut harness).as_deref(), Some("add"));
+
+    harness
+        .send_key(KeyCode::Char('o'), KeyModifiers::ALT)
+        .unwrap();
+    assert_eq!(selected(&mut harness).as_deref(), Some("add(1, 2)"));
+
+    harness
+        .send_key(KeyCode::Char('o'), KeyModifiers::ALT)
+        .unwrap();
+    assert_eq!(
+        selected(&mut harness).as_deref(),
+        Some("let total = add(1, 2);")
+    );
+
+    // Shrink retraces the expansion steps exactly
+    harness
+        .send_key(KeyCode::Char('i'), KeyModifiers::ALT)
+        .unwrap();
+    assert_eq!(selected(&mut harness).as_deref(), Some("add(1, 2)"));
+
+    harness
+        .send_key(KeyCode::Char('i'), KeyModifiers::ALT)
+        .unwrap();
+    assert_eq!(selected(&mut harness).as_deref(), Some("add"));
+
+    harness
+        .send_key(KeyCode::Char('i'), KeyModifiers::ALT)
+        .unwrap();
+    assert_eq!(selected(&mut harness), None);
+    assert_eq!(
+        harness.editor().active_cursors().primary().position,
+        start_position
+    );
+}
+
 /// Test expand selection performance with moderately large buffer
 /// This test ensures that selection operations don't read the entire buffer
 #[test]
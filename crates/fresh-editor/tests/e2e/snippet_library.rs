@@ -0,0 +1,109 @@
+//! E2E tests for user-defined snippets loaded from the config directory
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+use tempfile::TempDir;
+
+const RUST_SNIPPETS: &str = r##"{
+    // Comments are allowed, as in VS Code
+    "Main": {
+        "prefix": "main",
+        "body": ["fn main() {", "\t$0", "}"]
+    },
+    "Test": {
+        "prefix": ["test", "tfn"],
+        "body": ["#[test]", "fn ${1:name}() {", "\t$0", "}"],
+        "description": "Test function",
+    },
+}"##;
+
+/// Harness with `snippets/rust.json` in its config dir and an open `main.rs`.
+fn snippet_harness(temp_dir: &TempDir) -> EditorTestHarness {
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+    let snippets_dir = dir_context.config_dir.join("snippets");
+    std::fs::create_dir_all(&snippets_dir).unwrap();
+    std::fs::write(snippets_dir.join("rust.json"), RUST_SNIPPETS).unwrap();
+
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    let file = project_dir.join("main.rs");
+    std::fs::write(&file, "").unwrap();
+
+    let mut config = Config::default();
+    config.keybindings.push(fresh::config::Keybinding {
+        key: " ".to_string(),
+        modifiers: vec!["ctrl".to_string()],
+        keys: vec![],
+        action: "lsp_completion".to_string(),
+        args: std::collections::HashMap::new(),
+        when: None,
+    });
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(project_dir)
+            .with_shared_dir_context(dir_context),
+    )
+    .unwrap();
+    harness.open_file(&file).unwrap();
+    harness
+}
+
+#[test]
+fn test_snippet_offered_by_prefix() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = snippet_harness(&temp_dir);
+
+    harness.type_text("tes").unwrap();
+    harness
+        .send_key(KeyCode::Char(' '), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Test function");
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "#[test]\nfn name() {\n\t\n}"
+    );
+    assert_eq!(
+        harness
+            .editor()
+            .active_cursors()
+            .primary()
+            .selection_range(),
+        Some(11..15)
+    );
+}
+
+#[test]
+fn test_insert_snippet_command() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = snippet_harness(&temp_dir);
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Insert Snippet").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Test");
+
+    // Filter the list by prefix and pick the match
+    harness.type_text("tfn").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "#[test]\nfn name() {\n\t\n}"
+    );
+}
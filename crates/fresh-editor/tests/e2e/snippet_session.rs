@@ -0,0 +1,138 @@
+//! E2E tests for snippet sessions: tabstop navigation, mirrors, choices
+//! and variables
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::model::event::{
+    Event, PopupContentData, PopupData, PopupKindHint, PopupListItemData, PopupPositionData,
+};
+
+/// Offer `snippet` as the only completion and accept it.
+fn complete_with_snippet(harness: &mut EditorTestHarness, snippet: &str) {
+    harness
+        .apply_event(Event::ShowPopup {
+            popup: PopupData {
+                kind: PopupKindHint::Completion,
+                title: Some("Completion".to_string()),
+                description: None,
+                transient: false,
+                content: PopupContentData::List {
+                    items: vec![PopupListItemData {
+                        text: "snippet".to_string(),
+                        detail: None,
+                        icon: None,
+                        data: Some(snippet.to_string()),
+                    }],
+                    selected: 0,
+                },
+                position: PopupPositionData::BelowCursor,
+                width: 50,
+                max_height: 15,
+                bordered: true,
+            },
+        })
+        .unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+fn selection(harness: &EditorTestHarness) -> Option<std::ops::Range<usize>> {
+    harness
+        .editor()
+        .active_cursors()
+        .primary()
+        .selection_range()
+}
+
+#[test]
+fn test_tab_cycles_placeholders_and_mirrors_edit_together() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("le").unwrap();
+    complete_with_snippet(&mut harness, "let ${1:x} = ${2:1}; use($1);$0");
+
+    assert_eq!(harness.get_buffer_content().unwrap(), "let x = 1; use(x);");
+    assert_eq!(selection(&harness), Some(4..5));
+
+    // Typing replaces the placeholder and its mirror
+    harness.type_text("val").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "let val = 1; use(val);"
+    );
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    assert_eq!(selection(&harness), Some(10..11));
+    assert_eq!(harness.editor().active_cursors().count(), 1);
+
+    harness
+        .send_key(KeyCode::BackTab, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(selection(&harness), Some(4..7));
+
+    // Past the last placeholder the cursor lands on $0 and Tab is a Tab again
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.editor().active_cursors().primary().position, 22);
+    assert_eq!(selection(&harness), None);
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    assert_ne!(
+        harness.get_buffer_content().unwrap(),
+        "let val = 1; use(val);"
+    );
+}
+
+#[test]
+fn test_choice_tabstop_opens_popup() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("co").unwrap();
+    complete_with_snippet(&mut harness, "color: ${1|red,green,blue|};");
+
+    assert_eq!(harness.get_buffer_content().unwrap(), "color: red;");
+    harness.assert_screen_contains("green");
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "color: green;");
+    assert_eq!(selection(&harness), Some(7..12));
+}
+
+#[test]
+fn test_session_ends_when_cursor_leaves_snippet() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("x = f").unwrap();
+    complete_with_snippet(&mut harness, "f(${1:a}, ${2:b})");
+    assert_eq!(harness.get_buffer_content().unwrap(), "x = f(a, b)");
+
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+
+    // Tab indented instead of selecting the next placeholder
+    assert_eq!(selection(&harness), None);
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .ends_with("x = f(a, b)"));
+    assert_ne!(harness.get_buffer_content().unwrap(), "x = f(a, b)");
+}
+
+#[test]
+fn test_snippet_variables_and_indentation() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("first").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("    ln").unwrap();
+    complete_with_snippet(&mut harness, "line $TM_LINE_NUMBER {\n\t$0\n}");
+
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "first\n    line 2 {\n    \t\n    }"
+    );
+}
@@ -0,0 +1,128 @@
+use crate::common::fixtures::TestFixture;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::style::Modifier;
+
+const AFFIXES: &str = "SET UTF-8\nTRY esianrtolcdugmphbyfvkwzxjq\nSFX S Y 1\nSFX S 0 s .\n";
+const WORDS: &str =
+    "12\nabout\nand\nhere\nmessage\nnote/S\nquick\nreceived\nreturn/S\nsome\nthe\nvalue\nword/S\n";
+
+const SOURCE: &str = "\
+// Returns the recieved value
+fn main() {
+    let recieved = \"a quick mesage\";
+}
+";
+
+/// An editor with an `en_US` dictionary in its config directory and spell
+/// checking turned on, showing `content` as `filename`
+fn open_checked(filename: &str, content: &str) -> (EditorTestHarness, TestFixture) {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let dictionaries = harness
+        .temp_dir_path()
+        .unwrap()
+        .join("config")
+        .join("dictionaries");
+    std::fs::create_dir_all(&dictionaries).unwrap();
+    std::fs::write(dictionaries.join("en_US.aff"), AFFIXES).unwrap();
+    std::fs::write(dictionaries.join("en_US.dic"), WORDS).unwrap();
+
+    let fixture = harness
+        .load_buffer_from_text_named(filename, content)
+        .unwrap();
+    harness.editor_mut().toggle_spell_check();
+    harness.render().unwrap();
+    (harness, fixture)
+}
+
+/// Whether the first cell of `text` on screen is underlined
+fn is_underlined(harness: &EditorTestHarness, text: &str) -> bool {
+    let (col, row) = harness
+        .find_text_on_screen(text)
+        .unwrap_or_else(|| panic!("{text:?} not on screen:\n{}", harness.screen_to_string()));
+    harness
+        .get_cell_style(col, row)
+        .is_some_and(|style| style.add_modifier.contains(Modifier::UNDERLINED))
+}
+
+/// Comments and strings are checked; code is not, and nothing is
+/// underlined while spell checking is off
+#[test]
+fn test_spell_check_underlines_comments_and_strings() {
+    let (mut harness, _fixture) = open_checked("main.rs", SOURCE);
+    assert!(is_underlined(&harness, "recieved value"));
+    assert!(is_underlined(&harness, "mesage"));
+    assert!(!is_underlined(&harness, "Returns"));
+    assert!(!is_underlined(&harness, "quick"));
+    assert!(!is_underlined(&harness, "recieved ="));
+
+    harness.editor_mut().toggle_spell_check();
+    harness.render().unwrap();
+    assert!(!is_underlined(&harness, "recieved value"));
+}
+
+/// Markdown is prose throughout, apart from inline code
+#[test]
+fn test_spell_check_markdown_skips_code_spans() {
+    let (harness, _fixture) = open_checked(
+        "notes.md",
+        "# Notes\n\nSome mispeled words about `codez` here.\n",
+    );
+    assert!(is_underlined(&harness, "mispeled"));
+    assert!(!is_underlined(&harness, "Notes"));
+    assert!(!is_underlined(&harness, "words"));
+    assert!(!is_underlined(&harness, "codez"));
+}
+
+/// The code actions popup offers the dictionary's suggestions
+#[test]
+fn test_spell_check_code_action_replaces_word() {
+    let (mut harness, _fixture) = open_checked("main.rs", SOURCE);
+    harness
+        .editor_mut()
+        .goto_byte_offset(SOURCE.find("recieved").unwrap() + 2);
+    harness
+        .send_key(KeyCode::Char('.'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Change to \"received\"");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert!(content.starts_with("// Returns the received value\n"));
+    // Code is never changed
+    assert!(content.contains("let recieved ="));
+}
+
+/// Adding a word records it in the workspace's word list and removes its
+/// underline
+#[test]
+fn test_spell_check_add_word_to_workspace_dictionary() {
+    let text = "Some words about ratatui here.\n";
+    let (mut harness, _fixture) = open_checked("notes.md", text);
+    assert!(is_underlined(&harness, "ratatui"));
+
+    harness
+        .editor_mut()
+        .goto_byte_offset(text.find("ratatui").unwrap());
+    harness
+        .send_key(KeyCode::Char('.'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Add \"ratatui\" to workspace dictionary");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    let word_list = harness
+        .editor()
+        .working_dir()
+        .join(".fresh")
+        .join("dictionary.txt");
+    assert_eq!(std::fs::read_to_string(word_list).unwrap(), "ratatui\n");
+    assert!(!is_underlined(&harness, "ratatui"));
+}
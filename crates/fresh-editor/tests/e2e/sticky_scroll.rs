@@ -0,0 +1,119 @@
+use crate::common::harness::EditorTestHarness;
+use fresh::config::Config;
+
+fn sticky_config() -> Config {
+    let mut config = Config::default();
+    config.editor.sticky_scroll = true;
+    config
+}
+
+/// An impl holding one long method
+fn rust_source() -> String {
+    let body: Vec<String> = (0..80)
+        .map(|i| format!("        let v{i} = {i};"))
+        .collect();
+    format!(
+        "impl Widget {{\n    fn draw(&self) {{\n{}\n    }}\n}}\n",
+        body.join("\n")
+    )
+}
+
+/// Scroll the view (not the cursor) well past the first screenful
+fn scroll_down(harness: &mut EditorTestHarness) {
+    let (first_row, _) = harness.content_area_rows();
+    for _ in 0..8 {
+        harness.mouse_scroll_down(10, first_row as u16 + 2).unwrap();
+    }
+    harness.render().unwrap();
+    assert!(harness.top_line_number() > 5, "expected to scroll down");
+}
+
+/// The impl and fn headers are pinned, outermost first, once scrolled
+/// out of view; nothing is pinned when sticky scroll is off (the default)
+#[test]
+fn test_sticky_scroll_pins_enclosing_scopes() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text_named("widget.rs", &rust_source())
+        .unwrap();
+    scroll_down(&mut harness);
+    let (first_row, _) = harness.content_area_rows();
+    assert!(!harness
+        .get_row_text(first_row as u16)
+        .contains("impl Widget"));
+
+    let mut harness = EditorTestHarness::with_config(80, 24, sticky_config()).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text_named("widget.rs", &rust_source())
+        .unwrap();
+    scroll_down(&mut harness);
+    let (first_row, _) = harness.content_area_rows();
+    let screen = harness.screen_to_string();
+    assert!(
+        harness
+            .get_row_text(first_row as u16)
+            .contains("impl Widget {"),
+        "impl header should be pinned:\n{screen}"
+    );
+    assert!(
+        harness
+            .get_row_text(first_row as u16 + 1)
+            .contains("fn draw(&self) {"),
+        "fn header should be pinned below it:\n{screen}"
+    );
+    // The rows below are the body, not more headers
+    assert!(harness.get_row_text(first_row as u16 + 2).contains("let v"));
+}
+
+/// Clicking a pinned header moves the cursor to it and scrolls it to the top
+#[test]
+fn test_sticky_scroll_click_jumps_to_header() {
+    let mut harness = EditorTestHarness::with_config(80, 24, sticky_config()).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text_named("widget.rs", &rust_source())
+        .unwrap();
+    scroll_down(&mut harness);
+
+    let (first_row, _) = harness.content_area_rows();
+    harness.mouse_click(20, first_row as u16 + 1).unwrap();
+
+    let cursor_line = harness
+        .editor()
+        .active_state()
+        .buffer
+        .get_line_number(harness.cursor_position());
+    assert_eq!(cursor_line, 1);
+    assert_eq!(harness.top_line_number(), 1);
+}
+
+/// Markdown headings nest by level
+#[test]
+fn test_sticky_scroll_markdown_headings() {
+    let mut harness = EditorTestHarness::with_config(80, 24, sticky_config()).unwrap();
+    let text: Vec<String> = (0..80).map(|i| format!("Paragraph {i}.")).collect();
+    let source = format!("# Guide\n\n## Install\n\n{}\n", text.join("\n"));
+    let _fixture = harness
+        .load_buffer_from_text_named("guide.md", &source)
+        .unwrap();
+    scroll_down(&mut harness);
+
+    let (first_row, _) = harness.content_area_rows();
+    assert!(harness.get_row_text(first_row as u16).contains("# Guide"));
+    assert!(harness
+        .get_row_text(first_row as u16 + 1)
+        .contains("## Install"));
+}
+
+/// Buffers without a grammar fall back to indentation
+#[test]
+fn test_sticky_scroll_indentation_fallback() {
+    let mut harness = EditorTestHarness::with_config(80, 24, sticky_config()).unwrap();
+    let items: Vec<String> = (0..80).map(|i| format!("    item {i}")).collect();
+    let source = format!("section:\n{}\n", items.join("\n"));
+    let _fixture = harness.load_buffer_from_text(&source).unwrap();
+    scroll_down(&mut harness);
+
+    let (first_row, _) = harness.content_area_rows();
+    assert!(harness.get_row_text(first_row as u16).contains("section:"));
+    assert!(harness.get_row_text(first_row as u16 + 1).contains("item"));
+}
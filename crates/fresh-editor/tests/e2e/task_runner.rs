@@ -0,0 +1,131 @@
+//! E2E tests for "Run Task" and its problem matchers
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use fresh::config::{Config, ProblemMatcherKind, TaskConfig};
+use fresh::input::keybindings::Action;
+use std::collections::HashMap;
+use tempfile::TempDir;
+
+/// Harness whose only task, `check`, runs `script` with a gcc problem
+/// matcher, with `src/main.rs` open.
+fn task_harness(temp_dir: &TempDir, script: &str) -> EditorTestHarness {
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir_all(project_dir.join("src")).unwrap();
+    let file = project_dir.join("src/main.rs");
+    std::fs::write(&file, "fn main() {\n    let x = 1;\n}\n").unwrap();
+
+    let mut config = Config::default();
+    config.tasks = HashMap::from([(
+        "check".to_string(),
+        TaskConfig {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            cwd: None,
+            env: HashMap::new(),
+            problem_matcher: Some(ProblemMatcherKind::Gcc),
+            languages: vec![],
+            enabled: true,
+        },
+    )]);
+    let mut harness = EditorTestHarness::create(
+        100,
+        30,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(project_dir),
+    )
+    .unwrap();
+    harness.open_file(&file).unwrap();
+    harness
+}
+
+/// Waits until the status bar message contains `text`.
+fn wait_for_status(harness: &mut EditorTestHarness, text: &str) {
+    harness
+        .wait_until(|h| {
+            h.editor()
+                .get_status_message()
+                .is_some_and(|m| m.contains(text))
+        })
+        .unwrap();
+}
+
+#[test]
+#[cfg_attr(not(unix), ignore = "Task uses sh")]
+fn test_task_problems_feed_quickfix_and_error_navigation() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = task_harness(
+        &temp_dir,
+        "echo 'src/main.rs:2:5: error: unused variable x'; exit 1",
+    );
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::RunTask);
+    harness
+        .wait_until(|h| {
+            h.editor()
+                .get_status_message()
+                .is_some_and(|m| m.contains("exited with code 1"))
+        })
+        .unwrap();
+    assert_eq!(
+        harness.editor().get_status_message().unwrap(),
+        "Task 'check' exited with code 1: 1 errors, 0 warnings"
+    );
+    harness.assert_screen_contains("src/main.rs:2:5  unused variable x");
+
+    // F8 goes to the task's problem: line 2, column 5
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::JumpToNextError);
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), 16);
+    assert_eq!(
+        harness.editor().get_status_message().unwrap(),
+        "Problem 1/1: unused variable x"
+    );
+}
+
+#[test]
+#[cfg_attr(not(unix), ignore = "Task uses sh")]
+fn test_task_output_shown_when_no_problems_found() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = task_harness(&temp_dir, "echo 'linker exploded' >&2; exit 2");
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::RunTask);
+    wait_for_status(&mut harness, "exited with code 2");
+    harness.render().unwrap();
+    harness.assert_screen_contains("*Task Output*");
+    harness.assert_screen_contains("linker exploded");
+}
+
+#[test]
+#[cfg_attr(not(unix), ignore = "Task uses sh")]
+fn test_cancel_task_allows_running_again() {
+    let temp_dir = TempDir::new().unwrap();
+    // `sleep` keeps the output pipes open after `sh` is killed
+    let mut harness = task_harness(&temp_dir, "sleep 30; echo done");
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::RunTask);
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::CancelTask);
+    wait_for_status(&mut harness, "Task 'check' cancelled");
+
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::RunTask);
+    assert_eq!(
+        harness.editor().get_status_message().unwrap(),
+        "Running task 'check'..."
+    );
+    harness
+        .editor_mut()
+        .dispatch_action_for_tests(Action::CancelTask);
+    wait_for_status(&mut harness, "Task 'check' cancelled");
+}
@@ -527,6 +527,94 @@ impl Language {
         }
     }
 
+    /// Get the raw tree-sitter grammar for this language.
+    ///
+    /// Used by structural features (syntax-aware selection, symbol
+    /// outlines) that walk the parse tree directly instead of consuming
+    /// highlight events. Returns `None` when the grammar's feature is
+    /// not enabled.
+    pub fn tree_sitter_language(&self) -> Option<tree_sitter::Language> {
+        match self {
+            #[cfg(feature = "tree-sitter-rust")]
+            Self::Rust => Some(tree_sitter_rust::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-python")]
+            Self::Python => Some(tree_sitter_python::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-javascript")]
+            Self::JavaScript => Some(tree_sitter_javascript::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-typescript")]
+            Self::TypeScript => Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+            #[cfg(feature = "tree-sitter-html")]
+            Self::HTML => Some(tree_sitter_html::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-css")]
+            Self::CSS => Some(tree_sitter_css::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-c")]
+            Self::C => Some(tree_sitter_c::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-cpp")]
+            Self::Cpp => Some(tree_sitter_cpp::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-go")]
+            Self::Go => Some(tree_sitter_go::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-json")]
+            Self::Json | Self::Jsonc => Some(tree_sitter_json::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-java")]
+            Self::Java => Some(tree_sitter_java::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-c-sharp")]
+            Self::CSharp => Some(tree_sitter_c_sharp::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-php")]
+            Self::Php => Some(tree_sitter_php::LANGUAGE_PHP.into()),
+            #[cfg(feature = "tree-sitter-ruby")]
+            Self::Ruby => Some(tree_sitter_ruby::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-bash")]
+            Self::Bash => Some(tree_sitter_bash::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-lua")]
+            Self::Lua => Some(tree_sitter_lua::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-pascal")]
+            Self::Pascal => Some(tree_sitter_pascal::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-odin")]
+            Self::Odin => Some(tree_sitter_odin::LANGUAGE.into()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Get the tree-sitter tags query for this language.
+    ///
+    /// Tags queries mark definitions with `@definition.<kind>` and their
+    /// names with `@name`; they back the symbol outline when no language
+    /// server is attached. Returns `None` for grammars that ship no tags
+    /// query or whose feature is not enabled.
+    pub fn tags_query(&self) -> Option<String> {
+        match self {
+            #[cfg(feature = "tree-sitter-rust")]
+            Self::Rust => Some(tree_sitter_rust::TAGS_QUERY.to_string()),
+            #[cfg(feature = "tree-sitter-python")]
+            Self::Python => Some(tree_sitter_python::TAGS_QUERY.to_string()),
+            #[cfg(feature = "tree-sitter-javascript")]
+            Self::JavaScript => Some(tree_sitter_javascript::TAGS_QUERY.to_string()),
+            // TypeScript's tags only cover TS-specific syntax; JavaScript's
+            // cover the rest, as with the highlight queries.
+            #[cfg(all(feature = "tree-sitter-typescript", feature = "tree-sitter-javascript"))]
+            Self::TypeScript => Some(format!(
+                "{}\n{}",
+                tree_sitter_typescript::TAGS_QUERY,
+                tree_sitter_javascript::TAGS_QUERY
+            )),
+            #[cfg(feature = "tree-sitter-c")]
+            Self::C => Some(tree_sitter_c::TAGS_QUERY.to_string()),
+            #[cfg(feature = "tree-sitter-cpp")]
+            Self::Cpp => Some(tree_sitter_cpp::TAGS_QUERY.to_string()),
+            #[cfg(feature = "tree-sitter-go")]
+            Self::Go => Some(tree_sitter_go::TAGS_QUERY.to_string()),
+            #[cfg(feature = "tree-sitter-java")]
+            Self::Java => Some(tree_sitter_java::TAGS_QUERY.to_string()),
+            #[cfg(feature = "tree-sitter-php")]
+            Self::Php => Some(tree_sitter_php::TAGS_QUERY.to_string()),
+            #[cfg(feature = "tree-sitter-ruby")]
+            Self::Ruby => Some(tree_sitter_ruby::TAGS_QUERY.to_string()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
     /// Map tree-sitter highlight index to a highlight category
     pub fn highlight_category(&self, index: usize) -> Option<HighlightCategory> {
         match self {
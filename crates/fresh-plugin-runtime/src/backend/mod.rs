@@ -4,6 +4,7 @@
 //! Currently implements QuickJS with oxc transpilation.
 
 pub mod quickjs_backend;
+pub mod watchdog;
 
 pub use quickjs_backend::{
     has_fatal_js_error, set_panic_on_js_errors, take_fatal_js_error, PendingResponses,
@@ -94,6 +94,10 @@ use fresh_core::api::{
 };
 use fresh_core::command::Command;
 use fresh_core::overlay::OverlayNamespace;
+use fresh_core::permissions::{
+    is_protected_setting, plugin_name, spawn_uses_network, Access, PermissionRegistry, Request,
+    Sandbox,
+};
 use fresh_core::text_property::TextPropertyEntry;
 use fresh_core::{BufferId, SplitId};
 use fresh_parser_js::{
@@ -107,6 +111,9 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{mpsc, Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use super::watchdog::{self, LimitExceeded, Limits, PluginStatsRegistry};
 
 /// Plugin-API exports map shared across every `JsEditorApi` /
 /// `QuickJsBackend` instance on a single runtime. Maps an export name to
@@ -450,9 +457,11 @@ fn format_js_error(
 fn log_js_error(ctx: &rquickjs::Ctx<'_>, err: rquickjs::Error, context: &str) {
     let error = format_js_error(ctx, err, context);
     tracing::error!("{}", error);
+    watchdog::note_error(&error.to_string());
 
-    // When enabled, panic on JS errors to make them visible and fail fast
-    if should_panic_on_js_errors() {
+    // When enabled, panic on JS errors to make them visible and fail fast.
+    // Errors raised by the watchdog are expected, not plugin bugs.
+    if should_panic_on_js_errors() && !watchdog::limit_hit() {
         panic!("JavaScript error in {}: {}", context, error);
     }
 }
@@ -526,7 +535,8 @@ fn run_pending_jobs_checked(ctx: &rquickjs::Ctx<'_>, context: &str) -> usize {
                 format!("{:?}", exc)
             };
             tracing::error!("Unhandled JS exception during {}: {}", context, error_msg);
-            if should_panic_on_js_errors() {
+            watchdog::note_error(&error_msg);
+            if should_panic_on_js_errors() && !watchdog::limit_hit() {
                 panic!("Unhandled JS exception during {}: {}", context, error_msg);
             }
         }
@@ -554,7 +564,8 @@ fn run_pending_jobs_checked(ctx: &rquickjs::Ctx<'_>, context: &str) -> usize {
             context,
             error_msg
         );
-        if should_panic_on_js_errors() {
+        watchdog::note_error(&error_msg);
+        if should_panic_on_js_errors() && !watchdog::limit_hit() {
             panic!(
                 "Unhandled JS exception after running jobs in {}: {}",
                 context, error_msg
@@ -799,6 +810,9 @@ pub struct JsEditorApi {
     /// side drains via `_searchHandleTake`.
     #[qjs(skip_trace)]
     search_handles: SearchHandleRegistry,
+    /// Capability sandboxes and grants for package plugins
+    #[qjs(skip_trace)]
+    permissions: PermissionRegistry,
     pub plugin_name: String,
 }
 
@@ -820,6 +834,144 @@ impl JsEditorApi {
         self.plugin_name.clone()
     }
 
+    /// The editor's working directory, which `${workspace}` scopes expand to
+    /// and project searches cover.
+    #[plugin_api(skip)]
+    #[qjs(skip)]
+    fn working_dir(&self) -> PathBuf {
+        self.state_snapshot
+            .read()
+            .map(|s| s.working_dir.clone())
+            .unwrap_or_default()
+    }
+
+    /// Check an operation against the plugin's package permissions.
+    /// The first use of a declared capability asks the user and fails;
+    /// later uses succeed once the user has allowed it.
+    #[plugin_api(skip)]
+    #[qjs(skip)]
+    fn permit(&self, request: Request<'_>) -> Result<(), String> {
+        let workspace = self.working_dir();
+        let access = match self.permissions.read() {
+            Ok(state) => state.check(&self.plugin_name, &request, &workspace),
+            Err(_) => Access::Denied("permission state is unavailable".to_string()),
+        };
+        let capability = request.capability();
+        let error = match access {
+            Access::Allowed => return Ok(()),
+            Access::Denied(reason) => format!(
+                "Plugin '{}' may not {}: {}",
+                self.plugin_name, request, reason
+            ),
+            Access::Ask => {
+                let first = self
+                    .permissions
+                    .write()
+                    .map(|mut state| state.start_asking(&self.plugin_name, capability))
+                    .unwrap_or(false);
+                if first {
+                    let _ = self
+                        .command_sender
+                        .send(PluginCommand::RequestPluginPermission {
+                            plugin_name: self.plugin_name.clone(),
+                            capability,
+                            detail: request.to_string(),
+                        });
+                }
+                format!(
+                    "Plugin '{}' may not {} until the user allows {} access",
+                    self.plugin_name, request, capability
+                )
+            }
+        };
+        tracing::warn!("{}", error);
+        Err(error)
+    }
+
+    /// `permit` for async operations: a refused operation rejects its
+    /// callback so the plugin's promise fails instead of never settling.
+    #[plugin_api(skip)]
+    #[qjs(skip)]
+    fn permit_callback(&self, request: Request<'_>, callback_id: u64) -> bool {
+        self.settle_refused(self.permit(request), callback_id)
+    }
+
+    /// `permit` for running `command`: commands that can reach the network
+    /// need the network capability as well.
+    #[plugin_api(skip)]
+    #[qjs(skip)]
+    fn permit_spawn(&self, command: &str, args: &[String]) -> Result<(), String> {
+        self.permit(Request::Spawn(command))?;
+        if spawn_uses_network(command, args) {
+            self.permit(Request::Network)?;
+        }
+        Ok(())
+    }
+
+    /// `permit_spawn` for async operations, like `permit_callback`.
+    #[plugin_api(skip)]
+    #[qjs(skip)]
+    fn permit_spawn_callback(&self, command: &str, args: &[String], callback_id: u64) -> bool {
+        self.settle_refused(self.permit_spawn(command, args), callback_id)
+    }
+
+    /// A terminal runs whatever is typed into it, so it needs the network
+    /// capability as well.
+    #[plugin_api(skip)]
+    #[qjs(skip)]
+    fn permit_terminal(&self) -> Result<(), String> {
+        self.permit(Request::Terminal)?;
+        self.permit(Request::Network)
+    }
+
+    /// Refuse an operation sandboxed plugins may not perform at all,
+    /// because no capability bounds what it can do.
+    #[plugin_api(skip)]
+    #[qjs(skip)]
+    fn refuse_if_sandboxed(&self, operation: &str) -> Result<(), String> {
+        let sandboxed = self
+            .permissions
+            .read()
+            .map_or(true, |state| state.is_sandboxed(&self.plugin_name));
+        if !sandboxed {
+            return Ok(());
+        }
+        let error = format!(
+            "Plugin '{}' may not {}: not available to plugin packages",
+            self.plugin_name, operation
+        );
+        tracing::warn!("{}", error);
+        Err(error)
+    }
+
+    /// Reject the callback of a refused async operation so the plugin's
+    /// promise fails instead of never settling. Returns whether the
+    /// operation may go ahead.
+    #[plugin_api(skip)]
+    #[qjs(skip)]
+    fn settle_refused(&self, result: Result<(), String>, callback_id: u64) -> bool {
+        match result {
+            Ok(()) => true,
+            Err(error) => {
+                let _ = self.command_sender.send(PluginCommand::RejectCallback {
+                    callback_id: JsCallbackId::new(callback_id),
+                    error,
+                });
+                false
+            }
+        }
+    }
+
+    /// Whether a background process was started by this plugin.
+    #[plugin_api(skip)]
+    #[qjs(skip)]
+    fn owns_background_process(&self, process_id: u64) -> bool {
+        self.plugin_tracked_state
+            .borrow()
+            .get(&self.plugin_name)
+            .is_some_and(|state| state.background_process_ids.contains(&process_id))
+    }
+
     /// Publish a typed API surface under `name`. Another plugin (typically
     /// `init.ts`) can reach it later via `getPluginApi(name)`. Calling
     /// again with the same `name` replaces the previous registration
@@ -950,12 +1102,18 @@ impl JsEditorApi {
     // === Clipboard ===
 
     pub fn copy_to_clipboard(&self, text: String) {
+        if self.permit(Request::Clipboard).is_err() {
+            return;
+        }
         let _ = self
             .command_sender
             .send(PluginCommand::SetClipboard { text });
     }
 
     pub fn set_clipboard(&self, text: String) {
+        if self.permit(Request::Clipboard).is_err() {
+            return;
+        }
         let _ = self
             .command_sender
             .send(PluginCommand::SetClipboard { text });
@@ -1100,8 +1258,15 @@ impl JsEditorApi {
             .is_ok()
     }
 
-    /// Execute a built-in action
+    /// Execute a built-in action. Not available to plugin packages: actions
+    /// save, run tasks and copy without going through their permissions.
     pub fn execute_action(&self, action_name: String) -> bool {
+        if self
+            .refuse_if_sandboxed(&format!("run the action '{}'", action_name))
+            .is_err()
+        {
+            return false;
+        }
         self.command_sender
             .send(PluginCommand::ExecuteAction { action_name })
             .is_ok()
@@ -1193,6 +1358,9 @@ impl JsEditorApi {
     /// Save a buffer to a specific file path
     /// Used by :w filename to save unnamed buffers or save-as
     pub fn save_buffer_to_path(&self, buffer_id: u32, path: String) -> bool {
+        if self.permit(Request::Write(Path::new(&path))).is_err() {
+            return false;
+        }
         self.command_sender
             .send(PluginCommand::SaveBufferToPath {
                 buffer_id: BufferId(buffer_id as usize),
@@ -1448,6 +1616,9 @@ impl JsEditorApi {
 
     /// Open a file, optionally at a specific line/column
     pub fn open_file(&self, path: String, line: Option<u32>, column: Option<u32>) -> bool {
+        if self.permit(Request::Read(Path::new(&path))).is_err() {
+            return false;
+        }
         self.command_sender
             .send(PluginCommand::OpenFileAtLocation {
                 path: PathBuf::from(path),
@@ -1469,6 +1640,9 @@ impl JsEditorApi {
         path: String,
         window_id: rquickjs::function::Opt<u64>,
     ) -> bool {
+        if self.permit(Request::Read(Path::new(&path))).is_err() {
+            return false;
+        }
         self.command_sender
             .send(PluginCommand::OpenFileInBackground {
                 path: PathBuf::from(path),
@@ -1479,6 +1653,9 @@ impl JsEditorApi {
 
     /// Open a file in a specific split
     pub fn open_file_in_split(&self, split_id: u32, path: String, line: u32, column: u32) -> bool {
+        if self.permit(Request::Read(Path::new(&path))).is_err() {
+            return false;
+        }
         self.command_sender
             .send(PluginCommand::OpenFileInSplit {
                 split_id: split_id as usize,
@@ -1610,6 +1787,7 @@ impl JsEditorApi {
 
     /// Get an environment variable
     pub fn get_env(&self, name: String) -> Option<String> {
+        self.permit(Request::Env(&name)).ok()?;
         std::env::var(&name).ok()
     }
 
@@ -1762,17 +1940,21 @@ impl JsEditorApi {
 
     /// Check if file exists
     pub fn file_exists(&self, path: String) -> bool {
-        Path::new(&path).exists()
+        self.permit(Request::Read(Path::new(&path))).is_ok() && Path::new(&path).exists()
     }
 
     /// Read file contents
     pub fn read_file(&self, path: String) -> Option<String> {
+        self.permit(Request::Read(Path::new(&path))).ok()?;
         std::fs::read_to_string(&path).ok()
     }
 
     /// Write file contents
     pub fn write_file(&self, path: String, content: String) -> bool {
         let p = Path::new(&path);
+        if self.permit(Request::Write(p)).is_err() {
+            return false;
+        }
         if let Some(parent) = p.parent() {
             if !parent.exists() && std::fs::create_dir_all(parent).is_err() {
                 return false;
@@ -1790,7 +1972,11 @@ impl JsEditorApi {
     ) -> rquickjs::Result<Value<'js>> {
         use fresh_core::api::DirEntry;
 
-        let entries: Vec<DirEntry> = match std::fs::read_dir(&path) {
+        let listing = self
+            .permit(Request::Read(Path::new(&path)))
+            .map_err(std::io::Error::other)
+            .and_then(|()| std::fs::read_dir(&path));
+        let entries: Vec<DirEntry> = match listing {
             Ok(entries) => entries
                 .filter_map(|e| e.ok())
                 .map(|entry| {
@@ -1816,6 +2002,9 @@ impl JsEditorApi {
     /// Returns true if the directory was created or already exists.
     pub fn create_dir(&self, path: String) -> bool {
         let p = Path::new(&path);
+        if self.permit(Request::Write(p)).is_err() {
+            return false;
+        }
         if p.is_dir() {
             return true;
         }
@@ -1826,6 +2015,9 @@ impl JsEditorApi {
     /// For safety, the path must be under the OS temp directory or the Fresh
     /// config directory. Returns true on success.
     pub fn remove_path(&self, path: String) -> bool {
+        if self.permit(Request::Write(Path::new(&path))).is_err() {
+            return false;
+        }
         let target = match Path::new(&path).canonicalize() {
             Ok(p) => p,
             Err(_) => return false, // path doesn't exist or can't be resolved
@@ -1878,6 +2070,11 @@ impl JsEditorApi {
     /// Rename/move a file or directory. Returns true on success.
     /// Falls back to copy then trash for cross-filesystem moves.
     pub fn rename_path(&self, from: String, to: String) -> bool {
+        if self.permit(Request::Write(Path::new(&from))).is_err()
+            || self.permit(Request::Write(Path::new(&to))).is_err()
+        {
+            return false;
+        }
         // Try direct rename first (works for same-filesystem moves)
         if std::fs::rename(&from, &to).is_ok() {
             return true;
@@ -1900,6 +2097,11 @@ impl JsEditorApi {
     pub fn copy_path(&self, from: String, to: String) -> bool {
         let from_path = Path::new(&from);
         let to_path = Path::new(&to);
+        if self.permit(Request::Read(from_path)).is_err()
+            || self.permit(Request::Write(to_path)).is_err()
+        {
+            return false;
+        }
         if from_path.is_dir() {
             copy_dir_recursive(from_path, to_path).is_ok()
         } else {
@@ -1989,13 +2191,21 @@ impl JsEditorApi {
     ///
     /// Returns `true` if the write was queued. The actual update is
     /// asynchronous; a subsequent `getConfig()` will reflect it after the
-    /// editor processes the command.
+    /// editor processes the command. Plugin packages may not change plugin
+    /// settings or settings holding commands, and get `false`.
     pub fn set_setting<'js>(
         &self,
         _ctx: rquickjs::Ctx<'js>,
         path: String,
         value: Value<'js>,
     ) -> rquickjs::Result<bool> {
+        if is_protected_setting(&path)
+            && self
+                .refuse_if_sandboxed(&format!("change the setting '{}'", path))
+                .is_err()
+        {
+            return Ok(false);
+        }
         let json: serde_json::Value = rquickjs_serde::from_value(value)
             .map_err(|e| rquickjs::Error::new_from_js_message("serialize", "", &e.to_string()))?;
         Ok(self
@@ -2069,6 +2279,15 @@ impl JsEditorApi {
         language: String,
         config: LanguagePackConfig,
     ) -> rquickjs::Result<bool> {
+        if let Some(formatter) = &config.formatter {
+            if self
+                .permit_spawn(&formatter.command, &formatter.args)
+                .is_err()
+            {
+                return Ok(false);
+            }
+        }
+
         // First-writer-wins
         {
             let langs = self.registered_language_configs.borrow();
@@ -2102,6 +2321,10 @@ impl JsEditorApi {
         language: String,
         config: LspServerPackConfig,
     ) -> rquickjs::Result<bool> {
+        if self.permit_spawn(&config.command, &config.args).is_err() {
+            return Ok(false);
+        }
+
         // First-writer-wins
         {
             let langs = self.registered_lsp_servers.borrow();
@@ -2310,7 +2533,10 @@ impl JsEditorApi {
         ctx: rquickjs::Ctx<'js>,
         path: String,
     ) -> rquickjs::Result<Value<'js>> {
-        let metadata = std::fs::metadata(&path).ok();
+        let metadata = self
+            .permit(Request::Read(Path::new(&path)))
+            .ok()
+            .and_then(|()| std::fs::metadata(&path).ok());
         let stat = metadata.map(|m| {
             serde_json::json!({
                 "isFile": m.is_file(),
@@ -2466,6 +2692,89 @@ impl JsEditorApi {
         Ok(id)
     }
 
+    // === Symbols ===
+
+    /// Get the symbol outline of a buffer (async)
+    ///
+    /// Symbols come from the language server when one supports
+    /// `textDocument/documentSymbol`, otherwise from the grammar's
+    /// tree-sitter tags query. Resolves to an empty array when neither is
+    /// available.
+    #[plugin_api(
+        async_promise,
+        js_name = "getDocumentSymbols",
+        ts_return = "DocumentSymbolInfo[]"
+    )]
+    #[qjs(rename = "_getDocumentSymbolsStart")]
+    pub fn get_document_symbols_start(&self, buffer_id: u32) -> u64 {
+        let id = self.alloc_request_id();
+
+        let _ = self.command_sender.send(PluginCommand::GetDocumentSymbols {
+            buffer_id: BufferId(buffer_id as usize),
+            request_id: id,
+        });
+
+        id
+    }
+
+    /// Get the call or type hierarchy items at a byte offset (async)
+    ///
+    /// `kind` is "call" or "type". Resolves to an empty array when no
+    /// language server for the buffer supports the hierarchy.
+    #[plugin_api(
+        async_promise,
+        js_name = "prepareHierarchy",
+        ts_return = "HierarchyItemInfo[]"
+    )]
+    #[qjs(rename = "_prepareHierarchyStart")]
+    pub fn prepare_hierarchy_start(&self, buffer_id: u32, position: u32, kind: String) -> u64 {
+        let id = self.alloc_request_id();
+
+        let _ = self.command_sender.send(PluginCommand::PrepareHierarchy {
+            buffer_id: BufferId(buffer_id as usize),
+            position: position as usize,
+            kind,
+            request_id: id,
+        });
+
+        id
+    }
+
+    /// Get the neighbours of a hierarchy item (async)
+    ///
+    /// `direction` is "incoming" or "outgoing" for call hierarchy items
+    /// and "supertypes" or "subtypes" for type hierarchy items.
+    #[plugin_api(
+        async_promise,
+        js_name = "getHierarchyChildren",
+        ts_return = "HierarchyItemInfo[]"
+    )]
+    #[qjs(rename = "_getHierarchyChildrenStart")]
+    pub fn get_hierarchy_children_start<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        #[plugin_api(ts_type = "HierarchyItemInfo")] node: rquickjs::Object<'js>,
+        direction: String,
+    ) -> rquickjs::Result<u64> {
+        let id = self.alloc_request_id();
+
+        let server: String = node.get("server").unwrap_or_default();
+        let item = node
+            .get::<_, rquickjs::Value>("item")
+            .map(|item| js_to_json(&ctx, item))
+            .unwrap_or(serde_json::Value::Null);
+        let _ = self
+            .command_sender
+            .send(PluginCommand::GetHierarchyChildren {
+                server,
+                direction,
+                item,
+                request_id: id,
+            });
+
+        Ok(id)
+    }
+
     // === Overlays ===
 
     /// Add an overlay with styling options
@@ -3391,6 +3700,9 @@ impl JsEditorApi {
     /// Returns `false` only when the IPC channel to the editor is
     /// closed (editor is shutting down).
     pub fn create_window(&self, root: String, label: String) -> bool {
+        if self.permit(Request::Read(Path::new(&root))).is_err() {
+            return false;
+        }
         self.command_sender
             .send(PluginCommand::CreateWindow {
                 root: std::path::PathBuf::from(root),
@@ -3452,6 +3764,9 @@ impl JsEditorApi {
         recursive: rquickjs::function::Opt<bool>,
     ) -> rquickjs::Result<u64> {
         let id = self.alloc_request_id();
+        if !self.permit_callback(Request::Read(Path::new(&path)), id) {
+            return Ok(id);
+        }
         if let Ok(mut owners) = self.async_resource_owners.lock() {
             owners.insert(id, self.plugin_name.clone());
         }
@@ -3982,8 +4297,12 @@ impl JsEditorApi {
 
     /// Execute multiple actions in sequence
     ///
-    /// Takes typed ActionSpec array - serde validates field names at runtime
+    /// Takes typed ActionSpec array - serde validates field names at runtime.
+    /// Not available to plugin packages, like `execute_action`.
     pub fn execute_actions(&self, actions: Vec<ActionSpec>) -> bool {
+        if self.refuse_if_sandboxed("run actions").is_err() {
+            return false;
+        }
         self.command_sender
             .send(PluginCommand::ExecuteActions { actions })
             .is_ok()
@@ -4486,6 +4805,9 @@ impl JsEditorApi {
         cwd: rquickjs::function::Opt<String>,
     ) -> u64 {
         let id = self.alloc_request_id();
+        if !self.permit_spawn_callback(&command, &args, id) {
+            return id;
+        }
         // Use provided cwd, or fall back to snapshot's working_dir.
         // An explicit empty string is treated the same as omitting the
         // argument — the TS declaration says `cwd?: string`, so scripts
@@ -4534,6 +4856,9 @@ impl JsEditorApi {
         cwd: rquickjs::function::Opt<String>,
     ) -> u64 {
         let id = self.alloc_request_id();
+        if !self.permit_spawn_callback(&command, &args, id) {
+            return id;
+        }
         let effective_cwd = cwd.0.or_else(|| {
             self.state_snapshot
                 .read()
@@ -4579,6 +4904,9 @@ impl JsEditorApi {
         ctx: rquickjs::Ctx<'_>,
         #[plugin_api(ts_type = "AuthorityPayload")] payload: rquickjs::Value<'_>,
     ) -> bool {
+        if self.permit(Request::Authority).is_err() {
+            return false;
+        }
         let json = js_to_json(&ctx, payload);
         let _ = self
             .command_sender
@@ -4590,6 +4918,9 @@ impl JsEditorApi {
     /// `setAuthority`.
     #[plugin_api(js_name = "clearAuthority")]
     pub fn clear_authority(&self) {
+        if self.permit(Request::Authority).is_err() {
+            return;
+        }
         let _ = self.command_sender.send(PluginCommand::ClearAuthority);
     }
 
@@ -4637,6 +4968,12 @@ impl JsEditorApi {
     #[qjs(rename = "_spawnProcessWaitStart")]
     pub fn spawn_process_wait_start(&self, _ctx: rquickjs::Ctx<'_>, process_id: u64) -> u64 {
         let id = self.alloc_request_id();
+        if !self.owns_background_process(process_id) {
+            let refused = self.refuse_if_sandboxed("wait for another plugin's process");
+            if !self.settle_refused(refused, id) {
+                return id;
+            }
+        }
         let _ = self.command_sender.send(PluginCommand::SpawnProcessWait {
             process_id,
             callback_id: JsCallbackId::new(id),
@@ -4691,6 +5028,9 @@ impl JsEditorApi {
         whole_words: Option<bool>,
     ) -> u64 {
         let id = self.alloc_request_id();
+        if !self.permit_callback(Request::Read(&self.working_dir()), id) {
+            return id;
+        }
         let _ = self.command_sender.send(PluginCommand::GrepProject {
             pattern,
             fixed_string: fixed_string.unwrap_or(true),
@@ -4725,7 +5065,14 @@ impl JsEditorApi {
         // editor's task always sees an entry on lookup.
         let entry = Arc::new(SearchHandleState::new());
         if let Ok(mut map) = self.search_handles.lock() {
-            map.insert(id, entry);
+            map.insert(id, entry.clone());
+        }
+        if let Err(error) = self.permit(Request::Read(&self.working_dir())) {
+            if let Ok(mut state) = entry.state.lock() {
+                state.error = Some(error);
+                state.done = true;
+            }
+            return id;
         }
         let _ = self.command_sender.send(PluginCommand::BeginSearch {
             pattern,
@@ -4816,6 +5163,9 @@ impl JsEditorApi {
         replacement: String,
     ) -> u64 {
         let id = self.alloc_request_id();
+        if !self.permit_callback(Request::Write(Path::new(&file_path)), id) {
+            return id;
+        }
         // Convert [[offset, length], ...] to Vec<(usize, usize)>
         let match_pairs: Vec<(usize, usize)> = matches
             .iter()
@@ -4855,6 +5205,28 @@ impl JsEditorApi {
         Ok(id)
     }
 
+    /// Send a request to the running debug adapter (async, returns request_id)
+    #[plugin_api(async_promise, js_name = "sendDebugRequest", ts_return = "unknown")]
+    #[qjs(rename = "_sendDebugRequestStart")]
+    pub fn send_debug_request_start<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        command: String,
+        arguments: Option<rquickjs::Object<'js>>,
+    ) -> rquickjs::Result<u64> {
+        let id = self.alloc_request_id();
+        let arguments_json: Option<serde_json::Value> = arguments.map(|obj| {
+            let val = obj.into_value();
+            js_to_json(&ctx, val)
+        });
+        let _ = self.command_sender.send(PluginCommand::SendDebugRequest {
+            request_id: id,
+            command,
+            arguments: arguments_json,
+        });
+        Ok(id)
+    }
+
     /// Spawn a background process (async, returns request_id which is also process_id)
     #[plugin_api(
         async_thenable,
@@ -4870,6 +5242,9 @@ impl JsEditorApi {
         cwd: rquickjs::function::Opt<String>,
     ) -> u64 {
         let id = self.alloc_request_id();
+        if !self.permit_spawn_callback(&command, &args, id) {
+            return id;
+        }
         // Use id as process_id for simplicity
         let process_id = id;
         // Track process ID for cleanup on unload
@@ -4894,6 +5269,13 @@ impl JsEditorApi {
 
     /// Kill a background process
     pub fn kill_background_process(&self, process_id: u64) -> bool {
+        if !self.owns_background_process(process_id)
+            && self
+                .refuse_if_sandboxed("kill another plugin's process")
+                .is_err()
+        {
+            return false;
+        }
         self.command_sender
             .send(PluginCommand::KillBackgroundProcess { process_id })
             .is_ok()
@@ -4914,6 +5296,9 @@ impl JsEditorApi {
         opts: rquickjs::function::Opt<fresh_core::api::CreateTerminalOptions>,
     ) -> rquickjs::Result<u64> {
         let id = self.alloc_request_id();
+        if !self.settle_refused(self.permit_terminal(), id) {
+            return Ok(id);
+        }
 
         let opts = opts.0.unwrap_or(fresh_core::api::CreateTerminalOptions {
             cwd: None,
@@ -4945,6 +5330,9 @@ impl JsEditorApi {
 
     /// Send input data to a terminal
     pub fn send_terminal_input(&self, terminal_id: u64, data: String) -> bool {
+        if self.permit_terminal().is_err() {
+            return false;
+        }
         self.command_sender
             .send(PluginCommand::SendTerminalInput {
                 terminal_id: fresh_core::TerminalId(terminal_id as usize),
@@ -4985,6 +5373,9 @@ impl JsEditorApi {
     #[qjs(rename = "_loadPluginStart")]
     pub fn load_plugin_start(&self, _ctx: rquickjs::Ctx<'_>, path: String) -> u64 {
         let id = self.alloc_request_id();
+        if !self.settle_refused(self.refuse_if_sandboxed("load plugins"), id) {
+            return id;
+        }
         let _ = self.command_sender.send(PluginCommand::LoadPlugin {
             path: std::path::PathBuf::from(path),
             callback_id: JsCallbackId::new(id),
@@ -4997,6 +5388,9 @@ impl JsEditorApi {
     #[qjs(rename = "_unloadPluginStart")]
     pub fn unload_plugin_start(&self, _ctx: rquickjs::Ctx<'_>, name: String) -> u64 {
         let id = self.alloc_request_id();
+        if !self.settle_refused(self.refuse_if_sandboxed("unload plugins"), id) {
+            return id;
+        }
         let _ = self.command_sender.send(PluginCommand::UnloadPlugin {
             name,
             callback_id: JsCallbackId::new(id),
@@ -5009,6 +5403,9 @@ impl JsEditorApi {
     #[qjs(rename = "_reloadPluginStart")]
     pub fn reload_plugin_start(&self, _ctx: rquickjs::Ctx<'_>, name: String) -> u64 {
         let id = self.alloc_request_id();
+        if !self.settle_refused(self.refuse_if_sandboxed("reload plugins"), id) {
+            return id;
+        }
         let _ = self.command_sender.send(PluginCommand::ReloadPlugin {
             name,
             callback_id: JsCallbackId::new(id),
@@ -5244,6 +5641,14 @@ pub struct QuickJsBackend {
     plugin_api_exports: PluginApiExports,
     /// Streaming-search handle registry shared with the editor thread.
     search_handles: SearchHandleRegistry,
+    /// Per-plugin execution statistics shared with the editor thread.
+    plugin_stats: PluginStatsRegistry,
+    /// Capability sandboxes and grants shared with the editor thread.
+    permissions: PermissionRegistry,
+    /// Plugins that exceeded a limit since the last `take_offenders`
+    offenders: RefCell<Vec<(String, LimitExceeded)>>,
+    /// Time budgets and memory cap enforced by the watchdog
+    limits: Limits,
 }
 
 impl Drop for QuickJsBackend {
@@ -5286,6 +5691,8 @@ impl QuickJsBackend {
         let async_resource_owners: AsyncResourceOwners =
             Arc::new(std::sync::Mutex::new(HashMap::new()));
         let search_handles: SearchHandleRegistry = Arc::new(std::sync::Mutex::new(HashMap::new()));
+        let plugin_stats: PluginStatsRegistry = Arc::new(std::sync::Mutex::new(HashMap::new()));
+        let permissions = PermissionRegistry::default();
         Self::with_state_responses_and_resources(
             state_snapshot,
             command_sender,
@@ -5293,11 +5700,14 @@ impl QuickJsBackend {
             services,
             async_resource_owners,
             search_handles,
+            plugin_stats,
+            permissions,
         )
     }
 
     /// Create a new QuickJS backend with editor state, shared pending responses,
     /// and a shared async resource owner map
+    #[allow(clippy::too_many_arguments)]
     pub fn with_state_responses_and_resources(
         state_snapshot: Arc<RwLock<EditorStateSnapshot>>,
         command_sender: mpsc::Sender<PluginCommand>,
@@ -5305,12 +5715,20 @@ impl QuickJsBackend {
         services: Arc<dyn fresh_core::services::PluginServiceBridge>,
         async_resource_owners: AsyncResourceOwners,
         search_handles: SearchHandleRegistry,
+        plugin_stats: PluginStatsRegistry,
+        permissions: PermissionRegistry,
     ) -> Result<Self> {
         tracing::debug!("QuickJsBackend::new: creating QuickJS runtime");
 
         let runtime =
             Runtime::new().map_err(|e| anyhow!("Failed to create QuickJS runtime: {}", e))?;
 
+        // Abort calls that overrun their time budget, and turn runaway
+        // allocation into an exception instead of exhausting the process.
+        let limits = Limits::default();
+        runtime.set_interrupt_handler(Some(Box::new(watchdog::should_interrupt)));
+        runtime.set_memory_limit(limits.memory);
+
         // Set up promise rejection tracker to catch unhandled rejections
         runtime.set_host_promise_rejection_tracker(Some(Box::new(
             |_ctx, _promise, reason, is_handled| {
@@ -5327,8 +5745,9 @@ impl QuickJsBackend {
                     };
 
                     tracing::error!("Unhandled Promise rejection: {}", error_msg);
+                    watchdog::note_error(&error_msg);
 
-                    if should_panic_on_js_errors() {
+                    if should_panic_on_js_errors() && !watchdog::limit_hit() {
                         // Don't panic here - we're inside an FFI callback and rquickjs catches panics.
                         // Instead, set a fatal error flag that the plugin thread loop will check.
                         let full_msg = format!("Unhandled Promise rejection: {}", error_msg);
@@ -5373,6 +5792,10 @@ impl QuickJsBackend {
             registered_lsp_servers,
             plugin_api_exports,
             search_handles,
+            plugin_stats,
+            permissions,
+            offenders: RefCell::new(Vec::new()),
+            limits,
         };
 
         // Initialize main context (for internal utilities if needed)
@@ -5382,6 +5805,84 @@ impl QuickJsBackend {
         Ok(backend)
     }
 
+    /// Run one call into a plugin under the watchdog: the call is timed for
+    /// the plugin's statistics, and the plugin is reported by
+    /// `take_offenders` if the call exceeded `budget` or ran out of memory.
+    fn guarded<R>(
+        &self,
+        plugin_name: &str,
+        hook: Option<&str>,
+        budget: Duration,
+        call: impl FnOnce() -> R,
+    ) -> R {
+        watchdog::arm(budget);
+        let started = Instant::now();
+        let result = call();
+        let elapsed = started.elapsed();
+        let (timed_out, out_of_memory) = watchdog::disarm();
+        watchdog::record_call(&self.plugin_stats, plugin_name, hook, elapsed);
+
+        let limit = if timed_out {
+            Some(LimitExceeded::Time(budget))
+        } else if out_of_memory
+            || (elapsed >= watchdog::MEMORY_CHECK_AFTER
+                && watchdog::near_memory_limit(
+                    self.runtime.memory_usage().malloc_size,
+                    self.limits.memory,
+                ))
+        {
+            Some(LimitExceeded::Memory(self.limits.memory))
+        } else {
+            None
+        };
+        if let Some(limit) = limit.filter(|_| !self.is_offender(plugin_name)) {
+            self.offenders
+                .borrow_mut()
+                .push((plugin_name.to_string(), limit));
+        }
+        result
+    }
+
+    /// Whether a plugin has exceeded a limit and is waiting to be disabled
+    fn is_offender(&self, plugin_name: &str) -> bool {
+        self.offenders
+            .borrow()
+            .iter()
+            .any(|(name, _)| name == plugin_name)
+    }
+
+    /// Replace the default time budgets and memory cap
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.runtime.set_memory_limit(limits.memory);
+        self.limits = limits;
+    }
+
+    /// Take the plugins that exceeded a limit since the last call. The
+    /// plugin thread disables them.
+    pub fn take_offenders(&self) -> Vec<(String, LimitExceeded)> {
+        std::mem::take(&mut *self.offenders.borrow_mut())
+    }
+
+    /// Sandbox a plugin that was installed as a package, from its
+    /// manifest's permissions; any other plugin is trusted.
+    pub fn set_plugin_sandbox(&self, plugin_name: &str, path: &Path) {
+        if let Ok(mut state) = self.permissions.write() {
+            state.set_sandbox(plugin_name, Sandbox::for_plugin(path));
+        }
+    }
+
+    /// Record whether a plugin is disabled, for the performance view
+    pub fn set_disabled(&self, plugin_name: &str, limit: Option<LimitExceeded>) {
+        if let Ok(mut stats) = self.plugin_stats.lock() {
+            stats.entry(plugin_name.to_string()).or_default().disabled = limit;
+        }
+    }
+
+    /// Free memory left behind by unloaded plugins
+    pub fn collect_garbage(&self) {
+        self.runtime.run_gc();
+    }
+
     /// Set up the editor API in a specific JavaScript context
     fn setup_context_api(&self, context: &Context, plugin_name: &str) -> Result<()> {
         let state_snapshot = Arc::clone(&self.state_snapshot);
@@ -5419,6 +5920,7 @@ impl QuickJsBackend {
                 registered_lsp_servers: Rc::clone(&registered_lsp_servers),
                 plugin_api_exports: Rc::clone(&plugin_api_exports),
                 search_handles: Arc::clone(&self.search_handles),
+                permissions: Arc::clone(&self.permissions),
                 plugin_name: plugin_name.to_string(),
             };
             let editor = rquickjs::Class::<JsEditorApi>::instance(ctx.clone(), js_api)?;
@@ -5620,11 +6122,15 @@ impl QuickJsBackend {
                 editor.createVirtualBufferInExistingSplit = _wrapAsync("_createVirtualBufferInExistingSplitStart", "createVirtualBufferInExistingSplit");
                 editor.createBufferGroup = _wrapAsync("_createBufferGroupStart", "createBufferGroup");
                 editor.sendLspRequest = _wrapAsync("_sendLspRequestStart", "sendLspRequest");
+                editor.sendDebugRequest = _wrapAsync("_sendDebugRequestStart", "sendDebugRequest");
                 editor.spawnBackgroundProcess = _wrapAsyncThenable("_spawnBackgroundProcessStart", "spawnBackgroundProcess");
                 editor.spawnProcessWait = _wrapAsync("_spawnProcessWaitStart", "spawnProcessWait");
                 editor.getBufferText = _wrapAsync("_getBufferTextStart", "getBufferText");
                 editor.createCompositeBuffer = _wrapAsync("_createCompositeBufferStart", "createCompositeBuffer");
                 editor.getHighlights = _wrapAsync("_getHighlightsStart", "getHighlights");
+                editor.getDocumentSymbols = _wrapAsync("_getDocumentSymbolsStart", "getDocumentSymbols");
+                editor.prepareHierarchy = _wrapAsync("_prepareHierarchyStart", "prepareHierarchy");
+                editor.getHierarchyChildren = _wrapAsync("_getHierarchyChildrenStart", "getHierarchyChildren");
                 editor.loadPlugin = _wrapAsync("_loadPluginStart", "loadPlugin");
                 editor.unloadPlugin = _wrapAsync("_unloadPluginStart", "unloadPlugin");
                 editor.reloadPlugin = _wrapAsync("_reloadPluginStart", "reloadPlugin");
@@ -5731,11 +6237,9 @@ impl QuickJsBackend {
 
     /// Execute JavaScript code in the context
     pub(crate) fn execute_js(&mut self, code: &str, source_name: &str) -> Result<()> {
-        // Extract plugin name from path (filename without extension)
-        let plugin_name = Path::new(source_name)
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("unknown");
+        // Extract plugin name from path (file stem, qualified by its package)
+        let plugin_name = plugin_name(Path::new(source_name));
+        let plugin_name = plugin_name.as_deref().unwrap_or("unknown");
 
         tracing::debug!(
             "execute_js: starting for plugin '{}' from '{}'",
@@ -5762,30 +6266,39 @@ impl QuickJsBackend {
             }
         };
 
+        // Loading again (e.g. a reload after the plugin was fixed) re-enables it
+        self.set_disabled(plugin_name, None);
+
         // Wrap plugin code in IIFE to prevent TDZ errors and scope pollution
         // This is critical for plugins like vi_mode that declare `const editor = ...`
         // which shadows the global `editor` causing TDZ if not wrapped.
         let wrapped_code = format!("(function() {{ {} }})();", code);
         let wrapped = wrapped_code.as_str();
 
-        context.with(|ctx| {
-            tracing::debug!("execute_js: executing plugin code for '{}'", plugin_name);
-
-            // Execute the plugin code with filename for better stack traces
-            let mut eval_options = rquickjs::context::EvalOptions::default();
-            eval_options.global = true;
-            eval_options.filename = Some(source_name.to_string());
-            let result = ctx
-                .eval_with_options::<(), _>(wrapped.as_bytes(), eval_options)
-                .map_err(|e| format_js_error(&ctx, e, source_name));
+        self.guarded(plugin_name, None, self.limits.load, || {
+            context.with(|ctx| {
+                tracing::debug!("execute_js: executing plugin code for '{}'", plugin_name);
+
+                // Execute the plugin code with filename for better stack traces
+                let mut eval_options = rquickjs::context::EvalOptions::default();
+                eval_options.global = true;
+                eval_options.filename = Some(source_name.to_string());
+                let result = ctx
+                    .eval_with_options::<(), _>(wrapped.as_bytes(), eval_options)
+                    .map_err(|e| {
+                        let error = format_js_error(&ctx, e, source_name);
+                        watchdog::note_error(&error.to_string());
+                        error
+                    });
 
-            tracing::debug!(
-                "execute_js: plugin code execution finished for '{}', result: {:?}",
-                plugin_name,
-                result.is_ok()
-            );
+                tracing::debug!(
+                    "execute_js: plugin code execution finished for '{}', result: {:?}",
+                    plugin_name,
+                    result.is_ok()
+                );
 
-            result
+                result
+            })
         })
     }
 
@@ -6036,9 +6549,19 @@ impl QuickJsBackend {
                 let Some(context) = plugin_contexts.get(&handler.plugin_name) else {
                     continue;
                 };
-                context.with(|ctx| {
-                    call_handler(&ctx, &handler.handler_name, event_data);
-                });
+                if self.is_offender(&handler.plugin_name) {
+                    continue;
+                }
+                self.guarded(
+                    &handler.plugin_name,
+                    Some(event_name),
+                    self.limits.call,
+                    || {
+                        context.with(|ctx| {
+                            call_handler(&ctx, &handler.handler_name, event_data);
+                        });
+                    },
+                );
             }
         }
 
@@ -6121,14 +6644,17 @@ impl QuickJsBackend {
         );
 
         tracing::info!("start_action: evaluating JS code");
-        context.with(|ctx| {
-            if let Err(e) = ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
-                log_js_error(&ctx, e, &format!("action {}", action_name));
-            }
-            tracing::info!("start_action: running pending microtasks");
-            // Run any immediate microtasks
-            let count = run_pending_jobs_checked(&ctx, &format!("start_action {}", action_name));
-            tracing::info!("start_action: executed {} pending jobs", count);
+        self.guarded(&plugin_name, None, self.limits.call, || {
+            context.with(|ctx| {
+                if let Err(e) = ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
+                    log_js_error(&ctx, e, &format!("action {}", action_name));
+                }
+                tracing::info!("start_action: running pending microtasks");
+                // Run any immediate microtasks
+                let count =
+                    run_pending_jobs_checked(&ctx, &format!("start_action {}", action_name));
+                tracing::info!("start_action: executed {} pending jobs", count);
+            });
         });
 
         tracing::info!("start_action: END '{}'", action_name);
@@ -6183,29 +6709,31 @@ impl QuickJsBackend {
             action = action_name
         );
 
-        context.with(|ctx| {
-            // Eval returns a Promise for the async IIFE, which we need to drive
-            match ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
-                Ok(value) => {
-                    // If it's a Promise, we need to drive the runtime to completion
-                    if value.is_object() {
-                        if let Some(obj) = value.as_object() {
-                            // Check if it's a Promise by looking for 'then' method
-                            if obj.get::<_, rquickjs::Function>("then").is_ok() {
-                                // Drive the runtime to process the promise
-                                // QuickJS processes promises synchronously when we call execute_pending_job
-                                run_pending_jobs_checked(
-                                    &ctx,
-                                    &format!("execute_action {} promise", action_name),
-                                );
+        self.guarded(&plugin_name, None, self.limits.call, || {
+            context.with(|ctx| {
+                // Eval returns a Promise for the async IIFE, which we need to drive
+                match ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
+                    Ok(value) => {
+                        // If it's a Promise, we need to drive the runtime to completion
+                        if value.is_object() {
+                            if let Some(obj) = value.as_object() {
+                                // Check if it's a Promise by looking for 'then' method
+                                if obj.get::<_, rquickjs::Function>("then").is_ok() {
+                                    // Drive the runtime to process the promise
+                                    // QuickJS processes promises synchronously when we call execute_pending_job
+                                    run_pending_jobs_checked(
+                                        &ctx,
+                                        &format!("execute_action {} promise", action_name),
+                                    );
+                                }
                             }
                         }
                     }
+                    Err(e) => {
+                        log_js_error(&ctx, e, &format!("action {}", action_name));
+                    }
                 }
-                Err(e) => {
-                    log_js_error(&ctx, e, &format!("action {}", action_name));
-                }
-            }
+            })
         });
 
         Ok(())
@@ -6226,12 +6754,17 @@ impl QuickJsBackend {
         // Poll all plugin contexts
         let contexts = self.plugin_contexts.borrow().clone();
         for (name, context) in contexts {
-            context.with(|ctx| {
-                let count = run_pending_jobs_checked(&ctx, &format!("poll_event_loop {}", name));
-                if count > 0 {
-                    had_work = true;
-                }
+            if self.is_offender(&name) {
+                continue;
+            }
+            let count = self.guarded(&name, None, self.limits.call, || {
+                context.with(|ctx| {
+                    run_pending_jobs_checked(&ctx, &format!("poll_event_loop {}", name))
+                })
             });
+            if count > 0 {
+                had_work = true;
+            }
         }
         had_work
     }
@@ -6281,59 +6814,61 @@ impl QuickJsBackend {
             return;
         };
 
-        context.with(|ctx| {
-            // Parse JSON string to serde_json::Value
-            let json_value: serde_json::Value = match serde_json::from_str(result_json) {
-                Ok(v) => v,
-                Err(e) => {
-                    tracing::error!(
-                        "resolve_callback: failed to parse JSON for callback_id={}: {}",
-                        id,
-                        e
-                    );
-                    return;
-                }
-            };
+        self.guarded(&name, None, self.limits.call, || {
+            context.with(|ctx| {
+                // Parse JSON string to serde_json::Value
+                let json_value: serde_json::Value = match serde_json::from_str(result_json) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        tracing::error!(
+                            "resolve_callback: failed to parse JSON for callback_id={}: {}",
+                            id,
+                            e
+                        );
+                        return;
+                    }
+                };
 
-            // Convert to JS value using rquickjs_serde
-            let js_value = match rquickjs_serde::to_value(ctx.clone(), &json_value) {
-                Ok(v) => v,
-                Err(e) => {
-                    tracing::error!(
+                // Convert to JS value using rquickjs_serde
+                let js_value = match rquickjs_serde::to_value(ctx.clone(), &json_value) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        tracing::error!(
                         "resolve_callback: failed to convert to JS value for callback_id={}: {}",
                         id,
                         e
                     );
-                    return;
-                }
-            };
+                        return;
+                    }
+                };
 
-            // Get _resolveCallback function from globalThis
-            let globals = ctx.globals();
-            let resolve_fn: rquickjs::Function = match globals.get("_resolveCallback") {
-                Ok(f) => f,
-                Err(e) => {
-                    tracing::error!(
-                        "resolve_callback: _resolveCallback not found for callback_id={}: {:?}",
-                        id,
-                        e
-                    );
-                    return;
-                }
-            };
+                // Get _resolveCallback function from globalThis
+                let globals = ctx.globals();
+                let resolve_fn: rquickjs::Function = match globals.get("_resolveCallback") {
+                    Ok(f) => f,
+                    Err(e) => {
+                        tracing::error!(
+                            "resolve_callback: _resolveCallback not found for callback_id={}: {:?}",
+                            id,
+                            e
+                        );
+                        return;
+                    }
+                };
 
-            // Call the function with callback_id (as u64) and the JS value
-            if let Err(e) = resolve_fn.call::<_, ()>((id, js_value)) {
-                log_js_error(&ctx, e, &format!("resolving callback {}", id));
-            }
+                // Call the function with callback_id (as u64) and the JS value
+                if let Err(e) = resolve_fn.call::<_, ()>((id, js_value)) {
+                    log_js_error(&ctx, e, &format!("resolving callback {}", id));
+                }
 
-            // IMPORTANT: Run pending jobs to process Promise continuations
-            let job_count = run_pending_jobs_checked(&ctx, &format!("resolve_callback {}", id));
-            tracing::info!(
-                "resolve_callback: executed {} pending jobs for callback_id={}",
-                job_count,
-                id
-            );
+                // IMPORTANT: Run pending jobs to process Promise continuations
+                let job_count = run_pending_jobs_checked(&ctx, &format!("resolve_callback {}", id));
+                tracing::info!(
+                    "resolve_callback: executed {} pending jobs for callback_id={}",
+                    job_count,
+                    id
+                );
+            })
         });
     }
 
@@ -6358,28 +6893,30 @@ impl QuickJsBackend {
             return;
         };
 
-        context.with(|ctx| {
-            // Get _rejectCallback function from globalThis
-            let globals = ctx.globals();
-            let reject_fn: rquickjs::Function = match globals.get("_rejectCallback") {
-                Ok(f) => f,
-                Err(e) => {
-                    tracing::error!(
-                        "reject_callback: _rejectCallback not found for callback_id={}: {:?}",
-                        id,
-                        e
-                    );
-                    return;
-                }
-            };
+        self.guarded(&name, None, self.limits.call, || {
+            context.with(|ctx| {
+                // Get _rejectCallback function from globalThis
+                let globals = ctx.globals();
+                let reject_fn: rquickjs::Function = match globals.get("_rejectCallback") {
+                    Ok(f) => f,
+                    Err(e) => {
+                        tracing::error!(
+                            "reject_callback: _rejectCallback not found for callback_id={}: {:?}",
+                            id,
+                            e
+                        );
+                        return;
+                    }
+                };
 
-            // Call the function with callback_id (as u64) and error string
-            if let Err(e) = reject_fn.call::<_, ()>((id, error)) {
-                log_js_error(&ctx, e, &format!("rejecting callback {}", id));
-            }
+                // Call the function with callback_id (as u64) and error string
+                if let Err(e) = reject_fn.call::<_, ()>((id, error)) {
+                    log_js_error(&ctx, e, &format!("rejecting callback {}", id));
+                }
 
-            // IMPORTANT: Run pending jobs to process Promise continuations
-            run_pending_jobs_checked(&ctx, &format!("reject_callback {}", id));
+                // IMPORTANT: Run pending jobs to process Promise continuations
+                run_pending_jobs_checked(&ctx, &format!("reject_callback {}", id));
+            })
         });
     }
 }
@@ -6388,6 +6925,7 @@ impl QuickJsBackend {
 mod tests {
     use super::*;
     use fresh_core::api::{BufferInfo, CursorInfo};
+    use fresh_core::permissions::Capability;
     use std::sync::mpsc;
 
     /// Helper to create a backend with a command receiver for testing
@@ -9446,4 +9984,392 @@ mod tests {
             "Non-%-prefixed names should still collide across plugins"
         );
     }
+
+    // ==================== Watchdog Tests ====================
+
+    /// Test backend with `budget` for every call and a 64 MiB memory cap
+    fn create_limited_backend(budget: Duration) -> (QuickJsBackend, mpsc::Receiver<PluginCommand>) {
+        let (mut backend, rx) = create_test_backend();
+        backend.set_limits(Limits {
+            call: budget,
+            load: budget,
+            memory: 64 * 1024 * 1024,
+        });
+        (backend, rx)
+    }
+
+    #[tokio::test]
+    async fn test_runaway_hook_is_interrupted_and_reported() {
+        let budget = Duration::from_millis(100);
+        let (mut backend, _rx) = create_limited_backend(budget);
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.spin = function() { while (true) {} };
+            editor.on("bufferSave", "spin");
+        "#,
+                "spinner.js",
+            )
+            .unwrap();
+
+        let started = Instant::now();
+        backend
+            .emit("bufferSave", &serde_json::json!({}))
+            .await
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(
+            backend.take_offenders(),
+            vec![("spinner".to_string(), LimitExceeded::Time(budget))]
+        );
+
+        // Top-level code that never finishes fails with the interrupt
+        let error = backend
+            .execute_js("while (true) {}", "looper.js")
+            .unwrap_err();
+        assert!(error.to_string().contains("interrupted"), "{error}");
+        assert_eq!(
+            backend.take_offenders(),
+            vec![("looper".to_string(), LimitExceeded::Time(budget))]
+        );
+    }
+
+    #[test]
+    fn test_runaway_allocation_is_stopped_and_runtime_survives() {
+        let (mut backend, rx) = create_limited_backend(Duration::from_secs(30));
+        let result = backend.execute_js(
+            r#"
+            const hoard = [];
+            while (true) { hoard.push(new Array(100000).fill(hoard.length)); }
+        "#,
+            "hoarder.js",
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            backend.take_offenders(),
+            vec![(
+                "hoarder".to_string(),
+                LimitExceeded::Memory(64 * 1024 * 1024)
+            )]
+        );
+
+        // Once the plugin is gone, its memory is reclaimed
+        backend.cleanup_plugin("hoarder");
+        backend.collect_garbage();
+        while rx.try_recv().is_ok() {}
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.setStatus("still running: " + new Array(1000).fill(1).length);
+        "#,
+                "survivor.js",
+            )
+            .unwrap();
+        assert!(backend.take_offenders().is_empty());
+        match rx.try_recv().unwrap() {
+            PluginCommand::SetStatus { message } => assert_eq!(message, "still running: 1000"),
+            cmd => panic!("Expected SetStatus command, got {:?}", cmd),
+        }
+    }
+
+    /// A package plugin under `<root>/plugins/packages/<name>/main.js`
+    /// whose manifest declares `permissions`, sandboxed the way the plugin
+    /// thread sandboxes it on load.
+    fn write_package_plugin(root: &Path, name: &str, permissions: &str, code: &str) -> PathBuf {
+        let package_dir = root.join("plugins").join("packages").join(name);
+        std::fs::create_dir_all(&package_dir).unwrap();
+        std::fs::write(
+            package_dir.join("package.json"),
+            format!(r#"{{"fresh": {{"permissions": {}}}}}"#, permissions),
+        )
+        .unwrap();
+        let path = package_dir.join("main.js");
+        std::fs::write(&path, code).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_package_plugin_is_confined_to_its_manifest() {
+        let root = std::env::temp_dir().join(format!("fresh-sandbox-test-{}", std::process::id()));
+        let workspace = root.join("workspace");
+        std::fs::create_dir_all(&workspace).unwrap();
+        let in_scope = workspace.join("notes.txt");
+        std::fs::write(&in_scope, "hello").unwrap();
+        let path = write_package_plugin(
+            &root,
+            "lint",
+            r#"{"filesystem": {"read": ["${workspace}"]}}"#,
+            &format!(
+                r#"
+            const editor = getEditor();
+            globalThis._passwdRefused = editor.readFile("/etc/passwd") === undefined;
+            editor.spawnProcess("sh", ["-c", "true"]).catch(e => {{
+                globalThis._spawnError = String(e);
+            }});
+            globalThis._firstReadRefused = editor.readFile({in_scope:?}) === undefined;
+            globalThis._secondReadRefused = editor.readFile({in_scope:?}) === undefined;
+        "#
+            ),
+        );
+
+        let name = plugin_name(&path).unwrap();
+        assert_eq!(name, "lint:main");
+        let (mut backend, rx) = create_test_backend();
+        backend.state_snapshot.write().unwrap().working_dir = workspace.clone();
+        backend.set_plugin_sandbox(&name, &path);
+        backend
+            .load_module_with_source(path.to_str().unwrap(), "")
+            .await
+            .unwrap();
+
+        let commands: Vec<PluginCommand> = rx.try_iter().collect();
+        let asked: Vec<_> = commands
+            .iter()
+            .filter_map(|cmd| match cmd {
+                PluginCommand::RequestPluginPermission { capability, .. } => Some(*capability),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(asked, vec![Capability::Filesystem]);
+        assert!(!commands
+            .iter()
+            .any(|cmd| matches!(cmd, PluginCommand::SpawnProcess { .. })));
+        let (callback_id, error) = commands
+            .iter()
+            .find_map(|cmd| match cmd {
+                PluginCommand::RejectCallback { callback_id, error } => {
+                    Some((*callback_id, error.clone()))
+                }
+                _ => None,
+            })
+            .expect("spawnProcess should be rejected");
+        assert!(error.contains("may not run `sh`"), "{}", error);
+        backend.reject_callback(callback_id, &error);
+
+        // Reading in scope works once the user allows filesystem access
+        backend
+            .permissions
+            .write()
+            .unwrap()
+            .answer(&name, Capability::Filesystem, true);
+        backend
+            .execute_js(
+                &format!("globalThis._content = getEditor().readFile({in_scope:?});"),
+                path.to_str().unwrap(),
+            )
+            .unwrap();
+
+        backend
+            .plugin_contexts
+            .borrow()
+            .get(&name)
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                let global = ctx.globals();
+                assert!(global.get::<_, bool>("_passwdRefused").unwrap());
+                assert!(global.get::<_, bool>("_firstReadRefused").unwrap());
+                assert!(global.get::<_, bool>("_secondReadRefused").unwrap());
+                let spawn_error: String = global.get("_spawnError").unwrap();
+                assert!(spawn_error.contains("may not run `sh`"), "{}", spawn_error);
+                let content: String = global.get("_content").unwrap();
+                assert_eq!(content, "hello");
+            });
+        assert!(rx
+            .try_iter()
+            .all(|cmd| !matches!(cmd, PluginCommand::RequestPluginPermission { .. })));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_package_plugin_cannot_configure_commands() {
+        let root = std::env::temp_dir().join(format!("fresh-settings-test-{}", std::process::id()));
+        let path = write_package_plugin(
+            &root,
+            "sneaky",
+            r#"{"process": ["git"]}"#,
+            r#"
+            const editor = getEditor();
+            globalThis._grants = editor.setSetting("plugins.sneaky:main.grants", { process: true });
+            globalThis._lsp = editor.setSetting("lsp.rust", { command: "sh" });
+            globalThis._tabSize = editor.setSetting("editor.tab_size", 2);
+            globalThis._server = editor.registerLspServer("rust", { command: "sh", args: [] });
+        "#,
+        );
+
+        let name = plugin_name(&path).unwrap();
+        let (mut backend, rx) = create_test_backend();
+        backend.set_plugin_sandbox(&name, &path);
+        backend
+            .load_module_with_source(path.to_str().unwrap(), "")
+            .await
+            .unwrap();
+
+        backend
+            .plugin_contexts
+            .borrow()
+            .get(&name)
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                let global = ctx.globals();
+                assert!(!global.get::<_, bool>("_grants").unwrap());
+                assert!(!global.get::<_, bool>("_lsp").unwrap());
+                assert!(global.get::<_, bool>("_tabSize").unwrap());
+                assert!(!global.get::<_, bool>("_server").unwrap());
+            });
+        let settings: Vec<String> = rx
+            .try_iter()
+            .filter_map(|cmd| match cmd {
+                PluginCommand::SetSetting { path, .. } => Some(path),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(settings, vec!["editor.tab_size".to_string()]);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_package_plugin_cannot_bypass_its_manifest() {
+        let root = std::env::temp_dir().join(format!("fresh-bypass-test-{}", std::process::id()));
+        let workspace = root.join("workspace");
+        std::fs::create_dir_all(&workspace).unwrap();
+        let in_scope = workspace.join("notes.txt");
+        std::fs::write(&in_scope, "hello").unwrap();
+        let path = write_package_plugin(
+            &root,
+            "fetch",
+            r#"{"filesystem": {"read": ["${workspace}"]}, "process": ["curl"]}"#,
+            &format!(
+                r#"
+            const editor = getEditor();
+            globalThis._open = editor.openFile("/etc/passwd", null, null);
+            globalThis._openBackground = editor.openFileInBackground("/etc/passwd");
+            globalThis._openSplit = editor.openFileInSplit(1, "/etc/passwd", 0, 0);
+            globalThis._openInScope = editor.openFile({in_scope:?}, null, null);
+            globalThis._action = editor.executeAction("quit");
+            globalThis._actions = editor.executeActions([{{ action: "quit" }}]);
+            globalThis._kill = editor.killBackgroundProcess(4242);
+            editor.spawnProcessWait(4242).catch(e => {{
+                globalThis._waitError = String(e);
+            }});
+            editor.spawnProcess("curl", ["https://example.com"]).catch(e => {{
+                globalThis._curlError = String(e);
+            }});
+            editor.loadPlugin("/tmp/other.js").catch(e => {{
+                globalThis._loadError = String(e);
+            }});
+        "#
+            ),
+        );
+
+        let name = plugin_name(&path).unwrap();
+        let (mut backend, rx) = create_test_backend();
+        backend.state_snapshot.write().unwrap().working_dir = workspace.clone();
+        backend.set_plugin_sandbox(&name, &path);
+        {
+            let mut permissions = backend.permissions.write().unwrap();
+            permissions.answer(&name, Capability::Filesystem, true);
+            permissions.answer(&name, Capability::Process, true);
+        }
+        backend
+            .load_module_with_source(path.to_str().unwrap(), "")
+            .await
+            .unwrap();
+
+        let commands: Vec<PluginCommand> = rx.try_iter().collect();
+        let opened: Vec<&PathBuf> = commands
+            .iter()
+            .filter_map(|cmd| match cmd {
+                PluginCommand::OpenFileAtLocation { path, .. }
+                | PluginCommand::OpenFileInBackground { path, .. }
+                | PluginCommand::OpenFileInSplit { path, .. } => Some(path),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(opened, vec![&in_scope]);
+        assert!(!commands.iter().any(|cmd| matches!(
+            cmd,
+            PluginCommand::ExecuteAction { .. }
+                | PluginCommand::ExecuteActions { .. }
+                | PluginCommand::KillBackgroundProcess { .. }
+                | PluginCommand::SpawnProcessWait { .. }
+                | PluginCommand::SpawnProcess { .. }
+                | PluginCommand::LoadPlugin { .. }
+                | PluginCommand::RequestPluginPermission { .. }
+        )));
+        let rejected: Vec<(JsCallbackId, String)> = commands
+            .iter()
+            .filter_map(|cmd| match cmd {
+                PluginCommand::RejectCallback { callback_id, error } => {
+                    Some((*callback_id, error.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(rejected.len(), 3, "{:?}", rejected);
+        for (callback_id, error) in &rejected {
+            backend.reject_callback(*callback_id, error);
+        }
+
+        backend
+            .plugin_contexts
+            .borrow()
+            .get(&name)
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                let global = ctx.globals();
+                assert!(!global.get::<_, bool>("_open").unwrap());
+                assert!(!global.get::<_, bool>("_openBackground").unwrap());
+                assert!(!global.get::<_, bool>("_openSplit").unwrap());
+                assert!(global.get::<_, bool>("_openInScope").unwrap());
+                assert!(!global.get::<_, bool>("_action").unwrap());
+                assert!(!global.get::<_, bool>("_actions").unwrap());
+                assert!(!global.get::<_, bool>("_kill").unwrap());
+                let wait_error: String = global.get("_waitError").unwrap();
+                assert!(
+                    wait_error.contains("another plugin's process"),
+                    "{}",
+                    wait_error
+                );
+                let curl_error: String = global.get("_curlError").unwrap();
+                assert!(curl_error.contains("use the network"), "{}", curl_error);
+                let load_error: String = global.get("_loadError").unwrap();
+                assert!(load_error.contains("load plugins"), "{}", load_error);
+            });
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_package_plugins_get_their_own_sandbox() {
+        let root = std::env::temp_dir().join(format!("fresh-names-test-{}", std::process::id()));
+        let reader =
+            write_package_plugin(&root, "reader", r#"{"filesystem": {"read": ["*"]}}"#, "");
+        let idle = write_package_plugin(&root, "idle", "{}", "");
+        let reader_name = plugin_name(&reader).unwrap();
+        let idle_name = plugin_name(&idle).unwrap();
+        assert_ne!(reader_name, idle_name);
+
+        let (backend, _rx) = create_test_backend();
+        backend.set_plugin_sandbox(&reader_name, &reader);
+        backend.set_plugin_sandbox(&idle_name, &idle);
+        let permissions = backend.permissions.read().unwrap();
+        let passwd = Path::new("/etc/passwd");
+        assert_eq!(
+            permissions.check(&reader_name, &Request::Read(passwd), Path::new("/")),
+            Access::Ask
+        );
+        assert!(matches!(
+            permissions.check(&idle_name, &Request::Read(passwd), Path::new("/")),
+            Access::Denied(_)
+        ));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
 }
@@ -0,0 +1,241 @@
+//! Execution limits and timing for QuickJS plugins
+//!
+//! All plugins share one QuickJS runtime on the plugin thread, so a plugin
+//! stuck in a loop or allocating without bound stalls every hook of every
+//! other plugin. Each call into a plugin runs under a time budget enforced
+//! by the runtime's interrupt handler, and the runtime has a memory limit
+//! that turns runaway allocation into an exception. The backend reports
+//! plugins that hit either limit so the plugin thread can disable them.
+//!
+//! Memory is only measured for the runtime as a whole, so the plugin
+//! blamed for reaching the cap is whichever one is running at the time,
+//! not necessarily the one holding most of the memory. The editor passes
+//! its configured [`Limits`] to the plugin thread when it starts.
+//!
+//! Every call is also timed, which gives the cumulative CPU time and hook
+//! latency per plugin shown in the editor's "Plugin Performance" view.
+
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Time budget for a single hook handler, action or callback continuation
+pub const CALL_TIME_BUDGET: Duration = Duration::from_secs(2);
+
+/// Time budget for running a plugin's top-level code when it loads
+pub const LOAD_TIME_BUDGET: Duration = Duration::from_secs(10);
+
+/// Memory cap for the QuickJS runtime shared by all plugins
+pub const MEMORY_LIMIT: usize = 512 * 1024 * 1024;
+
+/// Time budgets and memory cap a backend enforces
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Budget for a hook handler, action or callback continuation
+    pub call: Duration,
+    /// Budget for a plugin's top-level code
+    pub load: Duration,
+    /// Memory cap for the runtime, in bytes
+    pub memory: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            call: CALL_TIME_BUDGET,
+            load: LOAD_TIME_BUDGET,
+            memory: MEMORY_LIMIT,
+        }
+    }
+}
+
+/// A call is blamed for running out of memory once usage gets this close
+/// to the limit, even if the plugin caught the allocation error itself.
+const MEMORY_LIMIT_SLACK: usize = 8 * 1024 * 1024;
+
+/// Measuring memory usage walks the whole heap, so it is only done after
+/// calls at least this slow. Filling the heap takes far longer than this.
+pub(crate) const MEMORY_CHECK_AFTER: Duration = Duration::from_millis(50);
+
+/// A resource limit a plugin exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+    /// A single call ran longer than its budget
+    Time(Duration),
+    /// The runtime ran out of its memory cap, in bytes, during a call
+    Memory(usize),
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Time(budget) => write!(f, "a call ran longer than {}s", budget.as_secs()),
+            Self::Memory(limit) => write!(
+                f,
+                "the plugin runtime exceeded its {} MiB memory limit",
+                limit / (1024 * 1024)
+            ),
+        }
+    }
+}
+
+/// Latency of one plugin's handlers for one hook
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HookStats {
+    pub calls: u64,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl HookStats {
+    /// Average latency per call
+    pub fn average(&self) -> Duration {
+        self.total / u32::try_from(self.calls).unwrap_or(u32::MAX).max(1)
+    }
+}
+
+/// Execution statistics for one plugin
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginStats {
+    /// Time spent running the plugin's code: loading, hooks, actions and
+    /// callback continuations
+    pub cpu_time: Duration,
+    /// Number of calls into the plugin
+    pub calls: u64,
+    /// Handler latency per hook name
+    pub hooks: BTreeMap<String, HookStats>,
+    /// Set when the plugin was disabled for exceeding a limit
+    pub disabled: Option<LimitExceeded>,
+}
+
+impl PluginStats {
+    fn record(&mut self, hook: Option<&str>, elapsed: Duration) {
+        self.cpu_time += elapsed;
+        self.calls += 1;
+        if let Some(hook) = hook {
+            let stats = self.hooks.entry(hook.to_string()).or_default();
+            stats.calls += 1;
+            stats.total += elapsed;
+            stats.max = stats.max.max(elapsed);
+        }
+    }
+}
+
+/// Plugin name → statistics. Written by the plugin thread, read by the
+/// editor thread.
+pub type PluginStatsRegistry = Arc<Mutex<HashMap<String, PluginStats>>>;
+
+/// Record a finished call in the registry.
+pub(crate) fn record_call(
+    registry: &PluginStatsRegistry,
+    plugin_name: &str,
+    hook: Option<&str>,
+    elapsed: Duration,
+) {
+    if let Ok(mut stats) = registry.lock() {
+        stats
+            .entry(plugin_name.to_string())
+            .or_default()
+            .record(hook, elapsed);
+    }
+}
+
+/// Whether memory usage after a call means the call ran out of `limit`.
+pub(crate) fn near_memory_limit(malloc_size: i64, limit: usize) -> bool {
+    usize::try_from(malloc_size).is_ok_and(|used| used + MEMORY_LIMIT_SLACK >= limit)
+}
+
+/// State of the call currently running on this thread. Thread-local
+/// because the QuickJS interrupt handler and the error logging helpers
+/// have no access to the backend; each runtime lives on a single thread.
+#[derive(Default)]
+struct CallState {
+    deadline: Cell<Option<Instant>>,
+    timed_out: Cell<bool>,
+    out_of_memory: Cell<bool>,
+}
+
+thread_local! {
+    static CALL: CallState = CallState::default();
+}
+
+/// Interrupt handler for the QuickJS runtime: aborts the running call
+/// once its deadline has passed.
+pub(crate) fn should_interrupt() -> bool {
+    CALL.with(|call| match call.deadline.get() {
+        Some(deadline) if Instant::now() >= deadline => {
+            call.timed_out.set(true);
+            true
+        }
+        _ => false,
+    })
+}
+
+/// Start the time budget for a call.
+pub(crate) fn arm(budget: Duration) {
+    CALL.with(|call| {
+        call.deadline.set(Some(Instant::now() + budget));
+        call.timed_out.set(false);
+        call.out_of_memory.set(false);
+    });
+}
+
+/// End the current call. Returns `(timed_out, out_of_memory)`.
+pub(crate) fn disarm() -> (bool, bool) {
+    CALL.with(|call| {
+        call.deadline.set(None);
+        (call.timed_out.take(), call.out_of_memory.take())
+    })
+}
+
+/// Note a JS error message; QuickJS reports allocation failures as
+/// `InternalError: out of memory`.
+pub(crate) fn note_error(message: &str) {
+    if message.contains("out of memory") {
+        CALL.with(|call| call.out_of_memory.set(true));
+    }
+}
+
+/// Whether the running call hit a limit. Errors caused by the watchdog
+/// are expected and must not be treated as plugin bugs.
+pub(crate) fn limit_hit() -> bool {
+    CALL.with(|call| call.timed_out.get() || call.out_of_memory.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadline_interrupts_once_passed() {
+        arm(Duration::from_secs(60));
+        assert!(!should_interrupt());
+        assert_eq!(disarm(), (false, false));
+
+        arm(Duration::ZERO);
+        assert!(should_interrupt());
+        assert!(limit_hit());
+        assert_eq!(disarm(), (true, false));
+        // Disarmed: no deadline, nothing to report
+        assert!(!should_interrupt());
+        assert!(!limit_hit());
+    }
+
+    #[test]
+    fn test_record_call_tracks_hook_latency() {
+        let registry = PluginStatsRegistry::default();
+        record_call(&registry, "p", Some("render"), Duration::from_millis(4));
+        record_call(&registry, "p", Some("render"), Duration::from_millis(2));
+        record_call(&registry, "p", None, Duration::from_millis(10));
+
+        let stats = registry.lock().unwrap()["p"].clone();
+        assert_eq!(stats.calls, 3);
+        assert_eq!(stats.cpu_time, Duration::from_millis(16));
+        let render = stats.hooks["render"];
+        assert_eq!(render.calls, 2);
+        assert_eq!(render.max, Duration::from_millis(4));
+        assert_eq!(render.average(), Duration::from_millis(3));
+    }
+}
@@ -10,10 +10,12 @@
 //! - Async operations complete naturally without runtime destruction
 
 use crate::backend::quickjs_backend::{AsyncResourceOwners, PendingResponses, TsPluginInfo};
+use crate::backend::watchdog::{Limits, PluginStats, PluginStatsRegistry};
 use crate::backend::QuickJsBackend;
 use anyhow::{anyhow, Result};
 use fresh_core::api::{EditorStateSnapshot, JsCallbackId, PluginCommand, SearchHandleRegistry};
 use fresh_core::hooks::HookArgs;
+use fresh_core::permissions::{plugin_name, PermissionRegistry};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -217,11 +219,23 @@ pub struct PluginThreadHandle {
     /// write directly into the same shared state the JS side drains via
     /// `_searchHandleTake`.
     search_handles: SearchHandleRegistry,
+
+    /// Per-plugin CPU time and hook latency, written by the runtime
+    plugin_stats: PluginStatsRegistry,
+
+    /// Package plugin sandboxes and the user's capability grants. The
+    /// runtime registers sandboxes and checks requests; the editor
+    /// records the user's answers.
+    permissions: PermissionRegistry,
 }
 
 impl PluginThreadHandle {
-    /// Create a new plugin thread and return its handle
-    pub fn spawn(services: Arc<dyn fresh_core::services::PluginServiceBridge>) -> Result<Self> {
+    /// Create a new plugin thread whose runtime enforces `limits`, and
+    /// return its handle
+    pub fn spawn(
+        services: Arc<dyn fresh_core::services::PluginServiceBridge>,
+        limits: Limits,
+    ) -> Result<Self> {
         tracing::debug!("PluginThreadHandle::spawn: starting plugin thread creation");
 
         // Create channel for plugin commands
@@ -245,6 +259,15 @@ impl PluginThreadHandle {
             Arc::new(std::sync::Mutex::new(std::collections::HashMap::new()));
         let thread_search_handles = Arc::clone(&search_handles);
 
+        // Per-plugin execution statistics shared with the editor thread.
+        let plugin_stats: PluginStatsRegistry =
+            Arc::new(std::sync::Mutex::new(std::collections::HashMap::new()));
+        let thread_plugin_stats = Arc::clone(&plugin_stats);
+
+        // Plugin capability permissions shared with the editor thread.
+        let permissions = PermissionRegistry::default();
+        let thread_permissions = Arc::clone(&permissions);
+
         // Create channel for requests (unbounded allows sync send, async recv)
         let (request_sender, request_receiver) = tokio::sync::mpsc::unbounded_channel();
 
@@ -272,13 +295,15 @@ impl PluginThreadHandle {
 
             // Create QuickJS runtime with state
             tracing::debug!("Plugin thread: creating QuickJS runtime");
-            let runtime = match QuickJsBackend::with_state_responses_and_resources(
+            let mut runtime = match QuickJsBackend::with_state_responses_and_resources(
                 Arc::clone(&thread_state_snapshot),
                 command_sender,
                 thread_pending_responses,
                 services.clone(),
                 thread_async_resource_owners,
                 thread_search_handles,
+                thread_plugin_stats,
+                thread_permissions,
             ) {
                 Ok(rt) => {
                     tracing::debug!("Plugin thread: QuickJS runtime created successfully");
@@ -289,6 +314,7 @@ impl PluginThreadHandle {
                     return;
                 }
             };
+            runtime.set_limits(limits);
 
             // Create internal manager state
             let mut plugins: HashMap<String, TsPluginInfo> = HashMap::new();
@@ -317,6 +343,8 @@ impl PluginThreadHandle {
             command_receiver,
             async_resource_owners,
             search_handles,
+            plugin_stats,
+            permissions,
         })
     }
 
@@ -325,6 +353,27 @@ impl PluginThreadHandle {
         Arc::clone(&self.search_handles)
     }
 
+    /// Accessor for the plugin permission registry.
+    pub fn permissions_handle(&self) -> PermissionRegistry {
+        Arc::clone(&self.permissions)
+    }
+
+    /// Execution statistics per plugin, sorted by plugin name
+    pub fn plugin_stats(&self) -> Vec<(String, PluginStats)> {
+        let mut stats: Vec<_> = self
+            .plugin_stats
+            .lock()
+            .map(|stats| {
+                stats
+                    .iter()
+                    .map(|(name, stats)| (name.clone(), stats.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+
     /// Check if the plugin thread is still alive
     pub fn is_alive(&self) -> bool {
         self.thread_handle
@@ -399,6 +448,15 @@ impl PluginThreadHandle {
             PluginResponse::HighlightsComputed { request_id, spans } => {
                 self.resolve_json_callback(request_id, &spans, "[]");
             }
+            PluginResponse::DocumentSymbols {
+                request_id,
+                symbols,
+            } => {
+                self.resolve_json_callback(request_id, &symbols, "[]");
+            }
+            PluginResponse::HierarchyItems { request_id, items } => {
+                self.resolve_json_callback(request_id, &items, "[]");
+            }
             PluginResponse::BufferText { request_id, text } => match text {
                 Ok(content) => {
                     // JSON stringify the content string
@@ -1034,7 +1092,31 @@ async fn plugin_thread_loop(
                 has_pending_work = runtime.borrow_mut().poll_event_loop_once();
             }
         }
+
+        disable_offending_plugins(&runtime, plugins);
+    }
+}
+
+/// Unload plugins that exceeded a time or memory limit, so one runaway
+/// plugin cannot stall the others sharing the runtime.
+fn disable_offending_plugins(
+    runtime: &Rc<RefCell<QuickJsBackend>>,
+    plugins: &mut HashMap<String, TsPluginInfo>,
+) {
+    let offenders = runtime.borrow().take_offenders();
+    if offenders.is_empty() {
+        return;
+    }
+    for (name, limit) in offenders {
+        tracing::warn!("Plugin '{}' disabled: {}", name, limit);
+        // A plugin that hit a limit while loading never made it into
+        // `plugins`, but may still have a context and handlers.
+        if unload_plugin_internal(Rc::clone(runtime), plugins, &name).is_err() {
+            runtime.borrow().cleanup_plugin(&name);
+        }
+        runtime.borrow().set_disabled(&name, Some(limit));
     }
+    runtime.borrow().collect_garbage();
 }
 
 /// Run a hook with Rc<RefCell<QuickJsBackend>>
@@ -1271,11 +1353,7 @@ struct PreparedPlugin {
 /// This function does I/O and CPU-bound work only — no QuickJS interaction.
 /// It is safe to call from any thread (all inputs/outputs are Send).
 fn prepare_plugin(path: &Path) -> Result<PreparedPlugin> {
-    let plugin_name = path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .ok_or_else(|| anyhow!("Invalid plugin filename"))?
-        .to_string();
+    let plugin_name = plugin_name(path).ok_or_else(|| anyhow!("Invalid plugin filename"))?;
 
     let source = std::fs::read_to_string(path)
         .map_err(|e| anyhow!("Failed to read plugin {}: {}", path.display(), e))?;
@@ -1377,6 +1455,10 @@ fn execute_prepared_plugin(
         .to_str()
         .ok_or_else(|| anyhow!("Invalid path encoding"))?;
 
+    runtime
+        .borrow()
+        .set_plugin_sandbox(&prepared.name, &prepared.path);
+
     let exec_start = std::time::Instant::now();
     runtime
         .borrow_mut()
@@ -1408,11 +1490,7 @@ async fn load_plugin_internal(
     plugins: &mut HashMap<String, TsPluginInfo>,
     path: &Path,
 ) -> Result<()> {
-    let plugin_name = path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .ok_or_else(|| anyhow!("Invalid plugin filename"))?
-        .to_string();
+    let plugin_name = plugin_name(path).ok_or_else(|| anyhow!("Invalid plugin filename"))?;
 
     tracing::info!("Loading TypeScript plugin: {} from {:?}", plugin_name, path);
     tracing::debug!(
@@ -1442,6 +1520,8 @@ async fn load_plugin_internal(
         }
     }
 
+    runtime.borrow().set_plugin_sandbox(&plugin_name, path);
+
     let load_start = std::time::Instant::now();
     runtime
         .borrow_mut()
@@ -1564,12 +1644,8 @@ async fn load_plugins_from_dir_with_config_internal(
                     if path.to_string_lossy().contains(".i18n.") {
                         continue;
                     }
-                    // Get plugin name from filename (without extension)
-                    let plugin_name = path
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("unknown")
-                        .to_string();
+                    // Get plugin name from the file and the package it is in
+                    let plugin_name = plugin_name(&path).unwrap_or_else(|| "unknown".to_string());
                     plugin_files.push((plugin_name, path));
                 }
             }
@@ -1591,6 +1667,7 @@ async fn load_plugins_from_dir_with_config_internal(
             PluginConfig {
                 enabled: existing_config.enabled,
                 path: Some(path.clone()),
+                grants: existing_config.grants.clone(),
             }
         } else {
             // Create new config with default enabled = true
@@ -1620,11 +1697,7 @@ async fn load_plugins_from_dir_with_config_internal(
             .map(|path| {
                 let path = path.clone();
                 scope.spawn(move || {
-                    let name = path
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("unknown")
-                        .to_string();
+                    let name = plugin_name(&path).unwrap_or_else(|| "unknown".to_string());
                     let result = prepare_plugin(&path);
                     (name, result)
                 })
@@ -1847,4 +1920,49 @@ mod tests {
         assert_eq!(json["prompt_type"], "search");
         assert_eq!(json["input"], "test");
     }
+
+    /// A plugin whose hook never returns is unloaded and gets no more hooks
+    #[tokio::test]
+    async fn test_disable_offending_plugins_stops_hook_dispatch() {
+        let dir = std::env::temp_dir().join(format!("fresh-watchdog-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("spinner.js");
+        std::fs::write(
+            &path,
+            r#"
+            const editor = getEditor();
+            globalThis.spin = function() { while (true) {} };
+            editor.on("editor_initialized", "spin");
+        "#,
+        )
+        .unwrap();
+
+        let budget = Duration::from_millis(100);
+        let mut backend = QuickJsBackend::new().unwrap();
+        backend.set_limits(Limits {
+            call: budget,
+            ..Limits::default()
+        });
+        let runtime = Rc::new(RefCell::new(backend));
+        let mut plugins = HashMap::new();
+        let loaded = load_plugin_internal(Rc::clone(&runtime), &mut plugins, &path).await;
+        std::fs::remove_dir_all(&dir).unwrap();
+        loaded.unwrap();
+
+        let hook = HookArgs::EditorInitialized {};
+        run_hook_internal_rc(Rc::clone(&runtime), "editor_initialized", &hook)
+            .await
+            .unwrap();
+        disable_offending_plugins(&runtime, &mut plugins);
+        assert!(plugins.is_empty());
+        assert!(!runtime.borrow().has_handlers("editor_initialized"));
+
+        // Running the hook again doesn't reach the plugin
+        let started = std::time::Instant::now();
+        run_hook_internal_rc(Rc::clone(&runtime), "editor_initialized", &hook)
+            .await
+            .unwrap();
+        assert!(started.elapsed() < budget);
+        assert!(runtime.borrow().take_offenders().is_empty());
+    }
 }
@@ -19,13 +19,13 @@ use fresh_core::api::{
     BufferGroupResult, BufferInfo, BufferSavedDiff, CompositeHunk, CompositeLayoutConfig,
     CompositePaneStyle, CompositeSourceConfig, CreateCompositeBufferOptions, CreateTerminalOptions,
     CreateVirtualBufferInExistingSplitOptions, CreateVirtualBufferInSplitOptions,
-    CreateVirtualBufferOptions, CursorInfo, DirEntry, FormatterPackConfig, GrammarInfoSnapshot,
-    GrepMatch, JsDiagnostic, JsPosition, JsRange, JsTextPropertyEntry, KeyEventPayload,
-    LanguagePackConfig, LayoutHints, LspServerPackConfig, OverlayColorSpec, OverlayOptions,
-    PluginAnimationEdge, PluginAnimationKind, ProcessLimitsPackConfig, ReplaceResult,
-    SearchTakeResult, SpawnResult, SplitSnapshot, TerminalResult, TextPropertiesAtCursor,
-    TsHighlightSpan, ViewTokenStyle, ViewTokenWire, ViewTokenWireKind, ViewportInfo,
-    VirtualBufferResult, WindowInfo,
+    CreateVirtualBufferOptions, CursorInfo, DirEntry, DocumentSymbolInfo, FormatterPackConfig,
+    GrammarInfoSnapshot, GrepMatch, HierarchyCallSite, HierarchyItemInfo, JsDiagnostic, JsPosition,
+    JsRange, JsTextPropertyEntry, KeyEventPayload, LanguagePackConfig, LayoutHints,
+    LspServerPackConfig, OverlayColorSpec, OverlayOptions, PluginAnimationEdge,
+    PluginAnimationKind, ProcessLimitsPackConfig, ReplaceResult, SearchTakeResult, SpawnResult,
+    SplitSnapshot, TerminalResult, TextPropertiesAtCursor, TsHighlightSpan, ViewTokenStyle,
+    ViewTokenWire, ViewTokenWireKind, ViewportInfo, VirtualBufferResult, WindowInfo,
 };
 use fresh_core::command::Suggestion;
 use fresh_core::file_explorer::FileExplorerDecoration;
@@ -96,6 +96,9 @@ fn get_type_decl(type_name: &str) -> Option<String> {
         "TsActionPopupAction" | "ActionPopupAction" => Some(ActionPopupAction::decl(&cfg)),
         "ActionPopupOptions" => Some(ActionPopupOptions::decl(&cfg)),
         "TsHighlightSpan" => Some(TsHighlightSpan::decl(&cfg)),
+        "DocumentSymbolInfo" => Some(DocumentSymbolInfo::decl(&cfg)),
+        "HierarchyItemInfo" => Some(HierarchyItemInfo::decl(&cfg)),
+        "HierarchyCallSite" => Some(HierarchyCallSite::decl(&cfg)),
         "FileExplorerDecoration" => Some(FileExplorerDecoration::decl(&cfg)),
 
         // Virtual buffer option types
@@ -507,6 +510,7 @@ interface HookEventMap {
   diagnostics_updated: { uri: string; count: number };
   lsp_references: {
     symbol: string;
+    kind: "references" | "implementation" | "type_definition" | "declaration";
     locations: { file: string; line: number; column: number }[];
   };
   lsp_server_request: {
@@ -515,6 +519,13 @@ interface HookEventMap {
     server_command: string;
     params: string | null;
   };
+  lsp_client_command: {
+    language: string;
+    server_name: string;
+    command: string;
+    title: string;
+    arguments: string | null;
+  };
   lsp_server_error: {
     language: string;
     server_command: string;
@@ -528,6 +539,20 @@ interface HookEventMap {
     user_dismissed: boolean;
   };
 
+  // ── Debugger ─────────────────────────────────────────────────────────────
+  debug_started: { session_id: number; configuration: string; adapter: string };
+  debug_stopped: {
+    session_id: number;
+    reason: string;
+    thread_id: number | null;
+    frame_id: number | null;
+    file: string | null;
+    line: number | null;
+  };
+  debug_continued: { session_id: number; thread_id: number | null };
+  debug_output: { session_id: number; category: string; output: string };
+  debug_ended: { session_id: number; error: string | null };
+
   // ── UI events ────────────────────────────────────────────────────────────
   action_popup_result: { popup_id: string; action_id: string };
   process_output: { process_id: number; data: string };
@@ -749,6 +774,9 @@ mod tests {
             "TsActionPopupAction",
             "ActionPopupOptions",
             "TsHighlightSpan",
+            "DocumentSymbolInfo",
+            "HierarchyItemInfo",
+            "HierarchyCallSite",
             "FileExplorerDecoration",
             "TextPropertyEntry",
             "CreateVirtualBufferOptions",
@@ -1202,6 +1230,9 @@ mod tests {
             "compositeNextHunk",
             "compositePrevHunk",
             "getHighlights",
+            "getDocumentSymbols",
+            "prepareHierarchy",
+            "getHierarchyChildren",
             "addOverlay",
             "clearNamespace",
             "clearAllOverlays",
@@ -1275,6 +1306,7 @@ mod tests {
             "getBufferText",
             "delay",
             "sendLspRequest",
+            "sendDebugRequest",
             "spawnBackgroundProcess",
             "killBackgroundProcess",
             "createTerminal",
@@ -52,9 +52,11 @@ export default defineConfig({
           { text: "Search and Replace", link: "/features/search-replace" },
           { text: "Integrated Terminal", link: "/features/terminal" },
           { text: "LSP Integration", link: "/features/lsp" },
+          { text: "Debugging", link: "/features/debugging" },
           { text: "Git", link: "/features/git" },
           { text: "Themes", link: "/features/themes" },
           { text: "Encoding", link: "/features/encoding" },
+          { text: "Hex View", link: "/features/hex-view" },
           { text: "Remote Editing (SSH)", link: "/features/ssh" },
           { text: "Devcontainers", link: "/features/devcontainer" },
           { text: "Session Persistence", link: "/features/session-persistence" },